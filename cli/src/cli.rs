@@ -36,11 +36,16 @@ pub enum Command {
         /// Might be used to set up things like HTTP query timeout
         #[arg(long)]
         timeout_s: Option<u64>,
+        /// Number of query solutions or triples serialized before they are sent to the client
+        ///
+        /// Higher values reduce the number of writes to the socket at the cost of latency
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        results_buffer_size: u64,
     },
     /// Start Oxigraph HTTP server in read-only mode
     ///
-    /// It allows reading the database while other processes are also reading it.
-    /// Opening as read-only while having another process writing the database is undefined behavior.
+    /// It allows reading the database while other processes are also reading it
+    /// or while another process is writing it, each query seeing the latest writes.
     ServeReadOnly {
         /// Directory in which Oxigraph data are persisted
         #[arg(short, long, value_hint = ValueHint::DirPath)]
@@ -63,6 +68,11 @@ pub enum Command {
         /// Might be used to set up things like HTTP query timeout
         #[arg(long)]
         timeout_s: Option<u64>,
+        /// Number of query solutions or triples serialized before they are sent to the client
+        ///
+        /// Higher values reduce the number of writes to the socket at the cost of latency
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        results_buffer_size: u64,
     },
     /// Create a database backup into a target directory
     ///
@@ -236,6 +246,16 @@ pub enum Command {
         #[arg(short, long, value_hint = ValueHint::DirPath)]
         location: PathBuf,
     },
+    /// Upgrade the database storage to the format of this Oxigraph version
+    ///
+    /// Databases written by an older Oxigraph version are also upgraded when opened read-write,
+    /// the migration is only required before opening them read-only.
+    /// The database must not be opened by another process during the migration.
+    Migrate {
+        /// Directory in which Oxigraph data are persisted
+        #[arg(short, long, value_hint = ValueHint::DirPath)]
+        location: PathBuf,
+    },
     /// Convert an RDF file from one format to another
     Convert {
         /// File to convert from
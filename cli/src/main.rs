@@ -78,6 +78,7 @@ pub fn main() -> anyhow::Result<()> {
             cors,
             union_default_graph,
             timeout_s,
+            results_buffer_size,
         } => serve(
             if let Some(location) = location {
                 Store::open(location)
@@ -89,6 +90,9 @@ pub fn main() -> anyhow::Result<()> {
             cors,
             union_default_graph,
             timeout_s,
+            ResultsWriterOptions {
+                buffer_size: results_buffer_size.try_into()?,
+            },
         ),
         Command::ServeReadOnly {
             location,
@@ -96,6 +100,7 @@ pub fn main() -> anyhow::Result<()> {
             cors,
             union_default_graph,
             timeout_s,
+            results_buffer_size,
         } => serve(
             Store::open_read_only(location)?,
             &bind,
@@ -103,6 +108,9 @@ pub fn main() -> anyhow::Result<()> {
             cors,
             union_default_graph,
             timeout_s,
+            ResultsWriterOptions {
+                buffer_size: results_buffer_size.try_into()?,
+            },
         ),
         Command::Backup {
             location,
@@ -477,6 +485,10 @@ pub fn main() -> anyhow::Result<()> {
             store.optimize()?;
             Ok(())
         }
+        Command::Migrate { location } => {
+            Store::migrate(location)?;
+            Ok(())
+        }
         Command::Convert {
             from_file,
             from_format,
@@ -756,6 +768,7 @@ fn serve(
     cors: bool,
     union_default_graph: bool,
     timeout_s: Option<u64>,
+    results_options: ResultsWriterOptions,
 ) -> anyhow::Result<()> {
     let timeout = timeout_s.map(Duration::from_secs);
     let mut server = if cors {
@@ -766,6 +779,7 @@ fn serve(
                 read_only,
                 union_default_graph,
                 timeout,
+                results_options,
             )
             .unwrap_or_else(|(status, message)| error(status, message))
         }))
@@ -777,6 +791,7 @@ fn serve(
                 read_only,
                 union_default_graph,
                 timeout,
+                results_options,
             )
             .unwrap_or_else(|(status, message)| error(status, message))
         })
@@ -839,12 +854,28 @@ fn cors_middleware(
 
 type HttpError = (StatusCode, String);
 
+/// Options controlling how query results are streamed to the HTTP clients
+#[derive(Clone, Copy)]
+struct ResultsWriterOptions {
+    /// Number of solutions or triples serialized before the output is handed to the socket
+    ///
+    /// Lower values reduce the latency to the first results, higher values the number of writes.
+    buffer_size: usize,
+}
+
+impl Default for ResultsWriterOptions {
+    fn default() -> Self {
+        Self { buffer_size: 1 }
+    }
+}
+
 fn handle_request(
     request: &mut Request<Body>,
     store: Store,
     read_only: bool,
     union_default_graph: bool,
     timeout: Option<Duration>,
+    results_options: ResultsWriterOptions,
 ) -> Result<Response<Body>, HttpError> {
     match (request.uri().path(), request.method().as_ref()) {
         ("/", "HEAD") => Response::builder()
@@ -923,6 +954,7 @@ fn handle_request(
                     request,
                     union_default_graph,
                     timeout,
+                    results_options,
                 )
             }
         }
@@ -938,6 +970,7 @@ fn handle_request(
                     request,
                     union_default_graph,
                     timeout,
+                    results_options,
                 )
             } else if content_type == "application/x-www-form-urlencoded" {
                 let buffer = limited_body(request)?;
@@ -948,6 +981,7 @@ fn handle_request(
                     request,
                     union_default_graph,
                     timeout,
+                    results_options,
                 )
             } else {
                 Err(unsupported_media_type(&content_type))
@@ -1265,6 +1299,7 @@ fn configure_and_evaluate_sparql_query(
     request: &Request<Body>,
     default_use_default_graph_as_union: bool,
     timeout: Option<Duration>,
+    results_options: ResultsWriterOptions,
 ) -> Result<Response<Body>, HttpError> {
     let mut default_graph_uris = Vec::new();
     let mut named_graph_uris = Vec::new();
@@ -1297,6 +1332,7 @@ fn configure_and_evaluate_sparql_query(
         named_graph_uris,
         request,
         timeout,
+        results_options,
     )
 }
 
@@ -1308,6 +1344,7 @@ fn evaluate_sparql_query(
     named_graph_uris: Vec<String>,
     request: &Request<Body>,
     timeout: Option<Duration>,
+    results_options: ResultsWriterOptions,
 ) -> Result<Response<Body>, HttpError> {
     let mut evaluator = default_sparql_evaluator()
         .with_base_iri(base_url(request))
@@ -1355,6 +1392,7 @@ fn evaluate_sparql_query(
         .on_store(store)
         .execute()
         .map_err(internal_server_error)?;
+    let buffer_size = results_options.buffer_size;
     match results {
         QueryResults::Solutions(solutions) => {
             let format = query_results_content_negotiation(request)?;
@@ -1366,14 +1404,15 @@ fn evaluate_sparql_query(
                         solutions,
                     ))
                 },
-                |(mut serializer, mut solutions)| {
-                    Ok(if let Some(solution) = solutions.next() {
+                move |(mut serializer, mut solutions)| {
+                    for _ in 0..buffer_size {
+                        let Some(solution) = solutions.next() else {
+                            serializer.finish()?;
+                            return Ok(None);
+                        };
                         serializer.serialize(&solution.map_err(io::Error::other)?)?;
-                        Some((serializer, solutions))
-                    } else {
-                        serializer.finish()?;
-                        None
-                    })
+                    }
+                    Ok(Some((serializer, solutions)))
                 },
                 format.media_type(),
             )
@@ -1393,14 +1432,15 @@ fn evaluate_sparql_query(
             let format = rdf_content_negotiation(request)?;
             ReadForWrite::build_response(
                 move |w| Ok((RdfSerializer::from_format(format).for_writer(w), triples)),
-                |(mut serializer, mut triples)| {
-                    Ok(if let Some(t) = triples.next() {
+                move |(mut serializer, mut triples)| {
+                    for _ in 0..buffer_size {
+                        let Some(t) = triples.next() else {
+                            serializer.finish()?;
+                            return Ok(None);
+                        };
                         serializer.serialize_triple(&t.map_err(io::Error::other)?)?;
-                        Some((serializer, triples))
-                    } else {
-                        serializer.finish()?;
-                        None
-                    })
+                    }
+                    Ok(Some((serializer, triples)))
                 },
                 format.media_type(),
             )
@@ -1796,6 +1836,8 @@ fn loader_to_http_error(e: LoaderError) -> HttpError {
         LoaderError::Parsing(e) => bad_request(e),
         LoaderError::Storage(e) => internal_server_error(e),
         LoaderError::InvalidBaseIri { .. } => bad_request(e),
+        LoaderError::Timeout(_) => internal_server_error(e),
+        LoaderError::DatasetFormatExpected(_) => bad_request(e),
     }
 }
 
@@ -2175,6 +2217,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn cli_migrate() -> Result<()> {
+        let store_dir = initialized_cli_store(
+            "<http://example.com/s> <http://example.com/p> <http://example.com/o> .",
+        )?;
+
+        // Migrating an up-to-date database is a no-op
+        cli_command()
+            .arg("migrate")
+            .arg("--location")
+            .arg(store_dir.path())
+            .assert()
+            .success();
+
+        assert_cli_state(
+            &store_dir,
+            "<http://example.com/s> <http://example.com/p> <http://example.com/o> .\n",
+        );
+        Ok(())
+    }
+
     #[test]
     fn cli_ask_query_inline() -> Result<()> {
         let store_dir = initialized_cli_store(
@@ -2467,6 +2530,67 @@ mod tests {
         )
     }
 
+    #[test]
+    fn get_query_results_buffer_size() -> Result<()> {
+        struct CountingWriter {
+            data: Vec<u8>,
+            writes: usize,
+        }
+
+        impl Write for CountingWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.writes += 1;
+                self.data.write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut server = ServerTest::new()?;
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost/store")
+            .header(CONTENT_TYPE, "application/n-quads")
+            .body(
+                (1..=5)
+                    .map(|i| {
+                        format!("<http://example.com/s{i}> <http://example.com/p> \"{i}\" .\n")
+                    })
+                    .collect::<String>(),
+            )?;
+        server.test_status(request, StatusCode::NO_CONTENT)?;
+
+        let mut outputs = Vec::new();
+        for (buffer_size, expected_writes) in [(1, 6), (2, 4), (100, 2)] {
+            server.results_options = ResultsWriterOptions { buffer_size };
+            let request = Request::builder()
+                .uri("http://localhost/query?query=SELECT%20?s%20?o%20WHERE%20{%20?s%20?p%20?o%20}%20ORDER%20BY%20?s")
+                .header(ACCEPT, "text/csv")
+                .body(())?;
+            let mut response = server.exec(request);
+            assert_eq!(response.status(), StatusCode::OK);
+            let mut writer = CountingWriter {
+                data: Vec::new(),
+                writes: 0,
+            };
+            io::copy(response.body_mut(), &mut writer)?;
+            // The header, then one write per batch of solutions
+            assert_eq!(
+                writer.writes, expected_writes,
+                "Unexpected number of writes with a buffer size of {buffer_size}"
+            );
+            outputs.push(writer.data);
+        }
+        assert!(outputs.iter().all(|output| *output == outputs[0]));
+        assert!(
+            String::from_utf8(outputs[0].clone())?
+                .starts_with("s,o\r\nhttp://example.com/s1,1\r\n")
+        );
+        Ok(())
+    }
+
     #[test]
     fn get_query_accept_star() -> Result<()> {
         let request = Request::builder()
@@ -3155,12 +3279,14 @@ mod tests {
 
     struct ServerTest {
         store: Store,
+        results_options: ResultsWriterOptions,
     }
 
     impl ServerTest {
         fn new() -> Result<Self> {
             Ok(Self {
                 store: Store::new()?,
+                results_options: ResultsWriterOptions::default(),
             })
         }
 
@@ -3171,6 +3297,7 @@ mod tests {
                 false,
                 false,
                 None,
+                self.results_options,
             )
             .unwrap_or_else(|(status, message)| error(status, message))
         }
@@ -3182,6 +3309,7 @@ mod tests {
                 true,
                 false,
                 None,
+                self.results_options,
             )
             .unwrap_or_else(|(status, message)| error(status, message))
         }
@@ -343,7 +343,7 @@ impl JsShaclValidationResult {
     /// The human-readable message (if any).
     #[wasm_bindgen(getter)]
     pub fn message(&self) -> Option<String> {
-        self.inner.result_message.clone()
+        self.inner.message().map(ToOwned::to_owned)
     }
 
     /// The severity level as a string.
@@ -0,0 +1,184 @@
+use codspeed_criterion_compat::{
+    BenchmarkId, Criterion, Throughput, criterion_group, criterion_main,
+};
+use oxigraph::bench::{
+    QUERIES, TRIPLES_PER_PERSON, generate_graph, generate_ntriples, generate_quads, person_shapes,
+};
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::sparql::{QueryResults, SparqlEvaluator};
+use oxigraph::store::Store;
+use sparshacl::ShaclValidator;
+
+const EX: &str = "http://example.com/";
+
+/// Dataset sizes, in triples, used by the insert, bulk load and SHACL benchmarks.
+const DATASET_SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// Dataset size, in triples, of each of the graphs validated by `shacl_validator_reuse`.
+const SHACL_REUSE_GRAPH_SIZE: usize = 100;
+
+/// Dataset size, in triples, used by the query benchmarks.
+const QUERY_DATASET_SIZE: usize = 100_000;
+
+/// Number of rows of the `VALUES` clause joined by `values_join`.
+const VALUES_SIZE: usize = 5_000;
+
+/// Benchmarks `Store::extend` on in-memory stores of growing sizes.
+fn insert_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert throughput");
+    group.sample_size(10);
+    for size in DATASET_SIZES {
+        let quads = generate_quads(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &quads, |b, quads| {
+            b.iter(|| {
+                let store = Store::new().unwrap();
+                store.extend(quads.iter().cloned()).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks the evaluation of each of the `QUERIES` against an in-memory store.
+fn query_latency(c: &mut Criterion) {
+    let store = Store::new().unwrap();
+    store.extend(generate_quads(QUERY_DATASET_SIZE)).unwrap();
+    let mut group = c.benchmark_group("query latency");
+    group.sample_size(10);
+    for (pattern, query) in QUERIES {
+        let query = SparqlEvaluator::new().parse_query(query).unwrap();
+        group.bench_function(pattern, |b| {
+            b.iter(|| {
+                if let QueryResults::Solutions(solutions) =
+                    query.clone().on_store(&store).execute().unwrap()
+                {
+                    for solution in solutions {
+                        solution.unwrap();
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks the join of a large `VALUES` clause of persons with an in-memory store.
+fn values_join(c: &mut Criterion) {
+    let store = Store::new().unwrap();
+    store.extend(generate_quads(QUERY_DATASET_SIZE)).unwrap();
+    let people = QUERY_DATASET_SIZE / TRIPLES_PER_PERSON;
+    let values = (0..VALUES_SIZE)
+        .map(|i| format!("(<{EX}person{}> UNDEF)", i * people / VALUES_SIZE))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let query = SparqlEvaluator::new()
+        .parse_query(&format!(
+            "SELECT ?s ?name ?age WHERE {{ ?s <{EX}name> ?name ; <{EX}age> ?age VALUES (?s ?age) {{ {values} }} }}"
+        ))
+        .unwrap();
+    let mut group = c.benchmark_group("VALUES join");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(VALUES_SIZE as u64));
+    group.bench_function(BenchmarkId::from_parameter(VALUES_SIZE), |b| {
+        b.iter(|| {
+            if let QueryResults::Solutions(solutions) =
+                query.clone().on_store(&store).execute().unwrap()
+            {
+                assert_eq!(
+                    solutions.map(Result::unwrap).count(),
+                    VALUES_SIZE,
+                    "each row of the VALUES clause must match exactly one person"
+                );
+            }
+        })
+    });
+    group.finish();
+}
+
+/// Benchmarks the bulk loader on N-Triples files of growing sizes.
+fn bulk_load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk load");
+    group.sample_size(10);
+    for size in DATASET_SIZES {
+        let data = generate_ntriples(size).unwrap();
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| {
+                let store = Store::new().unwrap();
+                let mut loader = store.bulk_loader();
+                loader
+                    .load_from_slice(RdfParser::from_format(RdfFormat::NTriples).lenient(), data)
+                    .unwrap();
+                loader.commit().unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks the validation of graphs of growing sizes against `person_shapes`.
+fn shacl_validation(c: &mut Criterion) {
+    let validator = ShaclValidator::new(person_shapes().unwrap());
+    let mut group = c.benchmark_group("SHACL validation");
+    group.sample_size(10);
+    for size in DATASET_SIZES {
+        let graph = generate_graph(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &graph, |b, graph| {
+            b.iter(|| {
+                assert!(
+                    validator.validate(graph).unwrap().conforms(),
+                    "the generated data must conform to the person shapes"
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks the validation of many small graphs with a single `ShaclValidator`
+/// against parsing the shapes and building a new validator for each graph.
+fn shacl_validator_reuse(c: &mut Criterion) {
+    let graphs = (1..=10)
+        .map(|i| generate_graph(i * SHACL_REUSE_GRAPH_SIZE))
+        .collect::<Vec<_>>();
+    let mut group = c.benchmark_group("SHACL validator reuse");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(graphs.len() as u64));
+    group.bench_function("reused validator", |b| {
+        let validator = ShaclValidator::new(person_shapes().unwrap());
+        b.iter(|| {
+            for graph in &graphs {
+                assert!(
+                    validator.validate(graph).unwrap().conforms(),
+                    "the generated data must conform to the person shapes"
+                );
+            }
+        })
+    });
+    group.bench_function("validator per graph", |b| {
+        b.iter(|| {
+            for graph in &graphs {
+                let validator = ShaclValidator::new(person_shapes().unwrap());
+                assert!(
+                    validator.validate(graph).unwrap().conforms(),
+                    "the generated data must conform to the person shapes"
+                );
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    generated,
+    insert_throughput,
+    query_latency,
+    values_join,
+    bulk_load,
+    shacl_validation,
+    shacl_validator_reuse
+);
+
+criterion_main!(generated);
@@ -6,10 +6,18 @@
 //! Run with: cargo run -p oxigraph --example soak -- --duration 3600
 //! (runs for 1 hour by default, or specify duration in seconds)
 //!
-//! The test performs:
-//! - 70% SELECT queries (read operations)
-//! - 20% INSERT operations (write operations)
-//! - 10% complex queries (aggregations, filters)
+//! The test performs by default:
+//! - 70% SPARQL queries (simple lookups, aggregations, filters)
+//! - 10% SHACL validations of the default graph
+//! - 10% ingestions (inserts and removals of quads)
+//! - 10% SPARQL updates
+//!
+//! The mix can be overridden with the `SOAK_QUERY_PCT`, `SOAK_SHACL_PCT`, `SOAK_INGEST_PCT`
+//! and `SOAK_UPDATE_PCT` environment variables, which must sum to 100.
+//!
+//! The store is shared by `SOAK_READER_THREADS` reader threads (3 by default), which only run
+//! queries and SHACL validations, and `SOAK_WRITER_THREADS` writer threads (1 by default), which
+//! run the full mix including ingestions and updates.
 //!
 //! Monitors:
 //! - Memory usage over time (leak detection)
@@ -17,56 +25,87 @@
 //! - Error rates
 //! - Throughput stability
 
-use oxigraph::io::RdfFormat;
+use oxigraph::io::{RdfFormat, RdfParser};
 use oxigraph::model::*;
 use oxigraph::sparql::{QueryResults, SparqlEvaluator};
 use oxigraph::store::Store;
+use sparshacl::{ShaclValidator, ShapesGraph};
+use std::fmt::Display;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use std::thread;
 
 const DEFAULT_DURATION_SECS: u64 = 3600; // 1 hour
-const WORKER_THREADS: usize = 4;
+const DEFAULT_READER_THREADS: usize = 3;
+const DEFAULT_WRITER_THREADS: usize = 1;
 const STATS_INTERVAL_SECS: u64 = 60;
 const WARMUP_SECS: u64 = 60;
+const LATENCY_RESERVOIR_SIZE: usize = 10_000;
 
-// Workload distribution
-const READ_PERCENTAGE: u64 = 70;
-const WRITE_PERCENTAGE: u64 = 20;
-const COMPLEX_PERCENTAGE: u64 = 10;
+// Default workload distribution
+const QUERY_PERCENTAGE: u64 = 70;
+const SHACL_PERCENTAGE: u64 = 10;
+const INGEST_PERCENTAGE: u64 = 10;
+const UPDATE_PERCENTAGE: u64 = 10;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let duration_secs = parse_args();
+    let mix = WorkloadMix::from_env()?;
+    let reader_threads = env_var("SOAK_READER_THREADS", DEFAULT_READER_THREADS)?;
+    let writer_threads = env_var("SOAK_WRITER_THREADS", DEFAULT_WRITER_THREADS)?;
+    if reader_threads + writer_threads == 0 {
+        return Err("SOAK_READER_THREADS and SOAK_WRITER_THREADS can't both be 0".into());
+    }
+    if reader_threads > 0 && mix.query + mix.shacl == 0 {
+        return Err("SOAK_READER_THREADS must be 0 when SOAK_QUERY_PCT and SOAK_SHACL_PCT are both 0: readers only run queries and SHACL validations".into());
+    }
 
     println!("=== Oxigraph Soak Test ===");
-    println!("Duration: {}s ({} minutes)", duration_secs, duration_secs / 60);
-    println!("Workers: {}", WORKER_THREADS);
-    println!("Workload: {}% reads, {}% writes, {}% complex queries",
-             READ_PERCENTAGE, WRITE_PERCENTAGE, COMPLEX_PERCENTAGE);
+    println!(
+        "Duration: {}s ({} minutes)",
+        duration_secs,
+        duration_secs / 60
+    );
+    println!(
+        "Workers: {} readers, {} writers",
+        reader_threads, writer_threads
+    );
+    println!(
+        "Workload: {}% queries, {}% SHACL validations, {}% ingestions, {}% updates",
+        mix.query, mix.shacl, mix.ingest, mix.update
+    );
     println!();
 
     let start = Instant::now();
     let running = Arc::new(AtomicBool::new(true));
     let query_count = Arc::new(AtomicU64::new(0));
     let error_count = Arc::new(AtomicU64::new(0));
-    let latencies = Arc::new(Mutex::new(Vec::new()));
+    let latencies = Arc::new(Mutex::new(LatencyReservoir::new()));
     let memory_samples = Arc::new(Mutex::new(Vec::new()));
 
     // Setup store with initial data
     println!("Initializing store with test data...");
     let store = setup_store()?;
+    let validator = Arc::new(setup_validator()?);
     println!("Store initialized with {} quads", store.len()?);
     println!();
 
     // Record initial memory
     if let Some(mem) = get_memory_usage() {
         memory_samples.lock().unwrap().push((0, mem));
+    } else {
+        eprintln!("WARNING: memory usage is not available on this platform, memory checks are skipped");
     }
 
     // Spawn worker threads
     let handles = spawn_workers(
         &store,
+        &validator,
+        mix,
+        reader_threads,
+        writer_threads,
         &running,
         &query_count,
         &error_count,
@@ -132,6 +171,82 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Share of each kind of operation in the workload, in percent
+#[derive(Clone, Copy)]
+struct WorkloadMix {
+    query: u64,
+    shacl: u64,
+    ingest: u64,
+    update: u64,
+}
+
+impl WorkloadMix {
+    /// Reads the mix from the `SOAK_*_PCT` environment variables, using the default for unset ones
+    fn from_env() -> Result<Self, String> {
+        let mix = Self {
+            query: env_var("SOAK_QUERY_PCT", QUERY_PERCENTAGE)?,
+            shacl: env_var("SOAK_SHACL_PCT", SHACL_PERCENTAGE)?,
+            ingest: env_var("SOAK_INGEST_PCT", INGEST_PERCENTAGE)?,
+            update: env_var("SOAK_UPDATE_PCT", UPDATE_PERCENTAGE)?,
+        };
+        let total = [mix.query, mix.shacl, mix.ingest, mix.update]
+            .into_iter()
+            .try_fold(0_u64, u64::checked_add);
+        if total != Some(100) {
+            return Err(format!(
+                "SOAK_QUERY_PCT ({}), SOAK_SHACL_PCT ({}), SOAK_INGEST_PCT ({}) and SOAK_UPDATE_PCT ({}) must sum to 100",
+                mix.query, mix.shacl, mix.ingest, mix.update
+            ));
+        }
+        Ok(mix)
+    }
+
+    /// Picks the kind of the operation to run from a random seed
+    ///
+    /// Readers never write: they split their operations between queries and SHACL validations
+    /// following the relative shares of the mix.
+    fn operation(self, seed: u64, role: WorkerRole) -> Operation {
+        let value = match role {
+            WorkerRole::Writer => seed % 100,
+            // main rejects reader threads if both shares are 0
+            WorkerRole::Reader => seed % (self.query + self.shacl),
+        };
+        if value < self.query {
+            Operation::Query
+        } else if value < self.query + self.shacl {
+            Operation::Shacl
+        } else if value < self.query + self.shacl + self.ingest {
+            Operation::Ingest
+        } else {
+            Operation::Update
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum WorkerRole {
+    Reader,
+    Writer,
+}
+
+enum Operation {
+    Query,
+    Shacl,
+    Ingest,
+    Update,
+}
+
+fn env_var<T: FromStr<Err: Display>>(name: &str, default: T) -> Result<T, String> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|e| format!("Invalid {name} value '{value}': {e}")),
+        Err(std::env::VarError::NotPresent) => Ok(default),
+        Err(e) => Err(format!("Invalid {name} value: {e}")),
+    }
+}
+
 fn parse_args() -> u64 {
     let args: Vec<String> = std::env::args().collect();
 
@@ -203,24 +318,66 @@ ex:product3 a schema:Product ;
     Ok(store)
 }
 
+/// Builds the SHACL validator run by the SHACL operations, shared by all the workers
+fn setup_validator() -> Result<ShaclValidator, Box<dyn std::error::Error>> {
+    let shapes = r#"
+@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix schema: <http://schema.org/> .
+@prefix ex: <http://example.com/> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+ex:PersonShape a sh:NodeShape ;
+    sh:targetClass schema:Person ;
+    sh:property [ sh:path schema:name ; sh:minCount 1 ; sh:datatype xsd:string ] ;
+    sh:property [ sh:path schema:age ; sh:maxCount 1 ; sh:minInclusive 0 ; sh:maxInclusive 150 ] .
+
+ex:ProductShape a sh:NodeShape ;
+    sh:targetClass schema:Product ;
+    sh:property [ sh:path schema:price ; sh:minCount 1 ; sh:datatype xsd:decimal ] .
+"#;
+
+    let mut graph = Graph::new();
+    for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(shapes) {
+        graph.insert(&Triple::from(quad?));
+    }
+    Ok(ShaclValidator::new(ShapesGraph::from_graph(&graph)?))
+}
+
 fn spawn_workers(
     store: &Store,
+    validator: &Arc<ShaclValidator>,
+    mix: WorkloadMix,
+    reader_threads: usize,
+    writer_threads: usize,
     running: &Arc<AtomicBool>,
     query_count: &Arc<AtomicU64>,
     error_count: &Arc<AtomicU64>,
-    latencies: &Arc<Mutex<Vec<u128>>>,
+    latencies: &Arc<Mutex<LatencyReservoir>>,
 ) -> Vec<thread::JoinHandle<()>> {
     let mut handles = Vec::new();
 
-    for worker_id in 0..WORKER_THREADS {
+    let roles = std::iter::repeat_n(WorkerRole::Reader, reader_threads)
+        .chain(std::iter::repeat_n(WorkerRole::Writer, writer_threads));
+    for (worker_id, role) in roles.enumerate() {
         let store = store.clone();
+        let validator = Arc::clone(validator);
         let running = Arc::clone(running);
         let query_count = Arc::clone(query_count);
         let error_count = Arc::clone(error_count);
         let latencies = Arc::clone(latencies);
 
         let handle = thread::spawn(move || {
-            worker_loop(worker_id, store, running, query_count, error_count, latencies);
+            worker_loop(
+                worker_id,
+                store,
+                &validator,
+                mix,
+                role,
+                running,
+                query_count,
+                error_count,
+                latencies,
+            );
         });
 
         handles.push(handle);
@@ -230,29 +387,30 @@ fn spawn_workers(
 }
 
 fn worker_loop(
-    _worker_id: usize,
+    worker_id: usize,
     store: Store,
+    validator: &ShaclValidator,
+    mix: WorkloadMix,
+    role: WorkerRole,
     running: Arc<AtomicBool>,
     query_count: Arc<AtomicU64>,
     error_count: Arc<AtomicU64>,
-    latencies: Arc<Mutex<Vec<u128>>>,
+    latencies: Arc<Mutex<LatencyReservoir>>,
 ) {
-    let mut rng = query_count.load(Ordering::Relaxed);
+    // Offset the seeds so that the workers do not all run the same operation sequence
+    let mut rng = query_count.load(Ordering::Relaxed) + worker_id as u64 * 7919;
 
     while running.load(Ordering::Relaxed) {
         rng = rng.wrapping_add(1);
-        let operation_type = rng % 100;
 
         let start = Instant::now();
-        let result = if operation_type < READ_PERCENTAGE {
-            // 70% reads
-            execute_read_query(&store, rng)
-        } else if operation_type < READ_PERCENTAGE + WRITE_PERCENTAGE {
-            // 20% writes
-            execute_write_operation(&store, rng)
-        } else {
-            // 10% complex queries
-            execute_complex_query(&store, rng)
+        let result = match mix.operation(rng, role) {
+            // Half of the queries are simple lookups, the other half aggregations and filters
+            Operation::Query if rng % 2 == 0 => execute_read_query(&store, rng / 2),
+            Operation::Query => execute_complex_query(&store, rng / 2),
+            Operation::Shacl => execute_shacl_validation(&store, validator),
+            Operation::Ingest => execute_ingest_operation(&store, rng),
+            Operation::Update => execute_update(&store, rng),
         };
 
         let latency = start.elapsed().as_micros();
@@ -260,11 +418,7 @@ fn worker_loop(
         // Record latency (sample 1% to avoid lock contention)
         if rng % 100 == 0 {
             if let Ok(mut lats) = latencies.lock() {
-                lats.push(latency);
-                // Keep only last 10000 samples
-                if lats.len() > 10000 {
-                    lats.drain(0..5000);
-                }
+                lats.record(latency);
             }
         }
 
@@ -337,7 +491,7 @@ fn execute_read_query(store: &Store, seed: u64) -> Result<(), Box<dyn std::error
     Ok(())
 }
 
-fn execute_write_operation(store: &Store, seed: u64) -> Result<(), Box<dyn std::error::Error>> {
+fn execute_ingest_operation(store: &Store, seed: u64) -> Result<(), Box<dyn std::error::Error>> {
     let person_id = seed % 1000;
     let subject = NamedNode::new(format!("http://example.com/person{}", person_id))?;
     let name_pred = NamedNode::new("http://schema.org/name")?;
@@ -387,6 +541,75 @@ fn execute_write_operation(store: &Store, seed: u64) -> Result<(), Box<dyn std::
     Ok(())
 }
 
+fn execute_shacl_validation(
+    store: &Store,
+    validator: &ShaclValidator,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // The report is expected to have violations: the updates may give several ages to a person
+    #[cfg(feature = "shacl")]
+    let report = store.validate_shacl(validator, GraphNameRef::DefaultGraph)?;
+    #[cfg(not(feature = "shacl"))]
+    let report = {
+        // Without the shacl feature the store can't be validated in place, so it is copied first
+        let mut graph = Graph::new();
+        for quad in store.quads_for_pattern(None, None, None, Some(GraphNameRef::DefaultGraph)) {
+            graph.insert(&Triple::from(quad?));
+        }
+        validator.validate(&graph)?
+    };
+    drop(report);
+    Ok(())
+}
+
+fn execute_update(store: &Store, seed: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let person_id = seed % 1000;
+    let updates = [
+        // Update 1: Increment the age of a person
+        format!(
+            r#"
+            PREFIX schema: <http://schema.org/>
+            PREFIX ex: <http://example.com/>
+            DELETE {{ ex:person{person_id} schema:age ?age }}
+            INSERT {{ ex:person{person_id} schema:age ?newAge }}
+            WHERE {{
+                ex:person{person_id} schema:age ?age .
+                BIND(?age + 1 AS ?newAge)
+            }}
+        "#
+        ),
+        // Update 2: Tag the people in a range of ages
+        format!(
+            r#"
+            PREFIX schema: <http://schema.org/>
+            PREFIX ex: <http://example.com/>
+            INSERT {{ ?person ex:ageGroup "{}" }}
+            WHERE {{
+                ?person a schema:Person ;
+                        schema:age ?age .
+                FILTER(?age >= {} && ?age < {})
+            }}
+        "#,
+            seed % 8,
+            seed % 8 * 10 + 18,
+            seed % 8 * 10 + 28
+        ),
+        // Update 3: Remove the tags
+        r#"
+            PREFIX ex: <http://example.com/>
+            DELETE WHERE { ?person ex:ageGroup ?group }
+        "#
+        .to_owned(),
+    ];
+
+    let update = &updates[(seed % updates.len() as u64) as usize];
+    SparqlEvaluator::new()
+        .parse_update(update)?
+        .on_store(store)
+        .execute()?;
+
+    Ok(())
+}
+
 fn execute_complex_query(store: &Store, seed: u64) -> Result<(), Box<dyn std::error::Error>> {
     let queries = [
         // Query 1: Aggregation
@@ -457,19 +680,53 @@ fn execute_complex_query(store: &Store, seed: u64) -> Result<(), Box<dyn std::er
     Ok(())
 }
 
-fn calculate_latency_stats(latencies: &Arc<Mutex<Vec<u128>>>) -> (u128, u128) {
-    let lats = latencies.lock().unwrap();
-    if lats.is_empty() {
-        return (0, 0);
+/// Fixed-size uniform sample of all the recorded latencies (reservoir sampling, algorithm R)
+///
+/// Memory stays bounded however long the test runs, so it does not skew the memory growth check,
+/// and percentiles are computed over the whole run, not only over the most recent operations.
+struct LatencyReservoir {
+    samples: Vec<u128>,
+    recorded: u64,
+}
+
+impl LatencyReservoir {
+    fn new() -> Self {
+        Self {
+            samples: Vec::with_capacity(LATENCY_RESERVOIR_SIZE),
+            recorded: 0,
+        }
     }
 
-    let mut sorted = lats.clone();
-    sorted.sort_unstable();
+    fn record(&mut self, latency: u128) {
+        self.recorded += 1;
+        if self.samples.len() < LATENCY_RESERVOIR_SIZE {
+            self.samples.push(latency);
+        } else {
+            // Keeps the new sample with probability LATENCY_RESERVOIR_SIZE / recorded
+            let index = rand::random_range(0..self.recorded);
+            let slot = usize::try_from(index)
+                .ok()
+                .and_then(|i| self.samples.get_mut(i));
+            if let Some(slot) = slot {
+                *slot = latency;
+            }
+        }
+    }
 
-    let p50 = sorted[sorted.len() * 50 / 100];
-    let p95 = sorted[sorted.len() * 95 / 100];
+    /// Returns a percentile (between 0 and 100) of the sampled latencies, 0 if none was recorded
+    fn percentile(&self, percentile: usize) -> u128 {
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+        sorted
+            .get((sorted.len() * percentile / 100).min(sorted.len().saturating_sub(1)))
+            .copied()
+            .unwrap_or(0)
+    }
+}
 
-    (p50, p95)
+fn calculate_latency_stats(latencies: &Arc<Mutex<LatencyReservoir>>) -> (u128, u128) {
+    let lats = latencies.lock().unwrap();
+    (lats.percentile(50), lats.percentile(95))
 }
 
 fn print_stats_row(
@@ -538,7 +795,7 @@ fn print_final_report(
     start: &Instant,
     query_count: &Arc<AtomicU64>,
     error_count: &Arc<AtomicU64>,
-    latencies: &Arc<Mutex<Vec<u128>>>,
+    latencies: &Arc<Mutex<LatencyReservoir>>,
     memory_samples: &Arc<Mutex<Vec<(u64, usize)>>>,
 ) {
     let duration = start.elapsed().as_secs();
@@ -551,12 +808,9 @@ fn print_final_report(
     };
 
     let lats = latencies.lock().unwrap();
-    let mut sorted = lats.clone();
-    sorted.sort_unstable();
-
-    let p50 = if !sorted.is_empty() { sorted[sorted.len() * 50 / 100] } else { 0 };
-    let p95 = if !sorted.is_empty() { sorted[sorted.len() * 95 / 100] } else { 0 };
-    let p99 = if !sorted.is_empty() { sorted[sorted.len() * 99 / 100] } else { 0 };
+    let p50 = lats.percentile(50);
+    let p95 = lats.percentile(95);
+    let p99 = lats.percentile(99);
 
     let samples = memory_samples.lock().unwrap();
     let mem_start = samples.first().map(|(_, m)| *m).unwrap_or(0);
@@ -585,15 +839,22 @@ fn print_final_report(
     println!("  p99:          {}μs", p99);
     println!();
     println!("Memory Stats:");
-    println!("  Start:        {}", format_memory(mem_start));
-    println!("  End:          {}", format_memory(mem_end));
-    println!("  Growth:       {:.1}%", mem_growth);
+    if samples.is_empty() {
+        println!("  SKIPPED:      memory usage is not available on this platform");
+    } else {
+        println!("  Start:        {}", format_memory(mem_start));
+        println!("  End:          {}", format_memory(mem_end));
+        println!("  Growth:       {:.1}%", mem_growth);
+    }
     println!("  Trend:        {}", latency_trend);
     println!();
 
     // Verdict
     let pass = error_rate < 1.0 && mem_growth < 50.0;
     println!("VERDICT:        {}", if pass { "✓ PASS" } else { "✗ FAIL" });
+    if pass && samples.is_empty() {
+        println!("WARNING:        memory stability was not validated");
+    }
 
     if !pass {
         println!();
@@ -645,9 +906,72 @@ fn get_memory_usage() -> Option<usize> {
     None
 }
 
-#[cfg(not(target_os = "linux"))]
+#[cfg(target_os = "macos")]
+#[expect(unsafe_code, deprecated)] // libc only deprecates mach_task_self_ in favor of the mach2 crate
+fn get_memory_usage() -> Option<usize> {
+    let mut info = std::mem::MaybeUninit::<libc::mach_task_basic_info>::uninit();
+    let mut count = libc::MACH_TASK_BASIC_INFO_COUNT;
+    // SAFETY: task_info writes at most `count` integers into `info`, which is large enough
+    let result = unsafe {
+        libc::task_info(
+            libc::mach_task_self_,
+            libc::MACH_TASK_BASIC_INFO,
+            info.as_mut_ptr().cast(),
+            &mut count,
+        )
+    };
+    if result != libc::KERN_SUCCESS {
+        return None;
+    }
+    // SAFETY: task_info succeeded so the structure is initialized
+    let info = unsafe { info.assume_init() };
+    usize::try_from(info.resident_size).ok()
+}
+
+#[cfg(windows)]
+#[expect(unsafe_code)]
+fn get_memory_usage() -> Option<usize> {
+    use std::ffi::c_void;
+
+    // PROCESS_MEMORY_COUNTERS from psapi.h
+    #[repr(C)]
+    #[derive(Default)]
+    struct ProcessMemoryCounters {
+        cb: u32,
+        page_fault_count: u32,
+        peak_working_set_size: usize,
+        working_set_size: usize,
+        quota_peak_paged_pool_usage: usize,
+        quota_paged_pool_usage: usize,
+        quota_peak_non_paged_pool_usage: usize,
+        quota_non_paged_pool_usage: usize,
+        pagefile_usage: usize,
+        peak_pagefile_usage: usize,
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetCurrentProcess() -> *mut c_void;
+        fn K32GetProcessMemoryInfo(
+            process: *mut c_void,
+            counters: *mut ProcessMemoryCounters,
+            cb: u32,
+        ) -> i32;
+    }
+
+    let mut counters = ProcessMemoryCounters {
+        cb: u32::try_from(size_of::<ProcessMemoryCounters>()).ok()?,
+        ..ProcessMemoryCounters::default()
+    };
+    // SAFETY: the pseudo handle of the current process is always valid and `counters` is a
+    // properly sized PROCESS_MEMORY_COUNTERS structure
+    let result =
+        unsafe { K32GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, counters.cb) };
+    // The working set is the Windows equivalent of the resident set size
+    (result != 0).then_some(counters.working_set_size)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
 fn get_memory_usage() -> Option<usize> {
-    // Fallback: use a rough estimate based on allocator stats
-    // This is not accurate but better than nothing
     None
 }
@@ -0,0 +1,128 @@
+//! Datasets and queries for benchmarking Oxigraph.
+//!
+//! Provides deterministic dataset generators, the queries and the SHACL shapes used by the
+//! [criterion](https://docs.rs/criterion) benchmarks of the `benches/generated.rs` harness
+//! for insert throughput, query latency, `VALUES` joins, bulk loading and SHACL validation.
+//!
+//! This module is only available with the `bench` feature.
+//!
+//! ```
+//! use oxigraph::bench::generate_quads;
+//! use oxigraph::store::Store;
+//!
+//! let store = Store::new()?;
+//! store.extend(generate_quads(1_000))?;
+//! assert_eq!(store.len()?, 1_000);
+//! # Result::<_, Box<dyn std::error::Error>>::Ok(())
+//! ```
+
+use crate::io::{RdfFormat, RdfParser, RdfSerializer};
+use crate::model::vocab::{rdf, xsd};
+use crate::model::{Graph, GraphName, Literal, NamedNode, Quad, Triple};
+use sparshacl::ShapesGraph;
+use std::error::Error;
+use std::io;
+
+const EX: &str = "http://example.com/";
+
+/// Number of triples generated for each person, see [`generate_quads`].
+pub const TRIPLES_PER_PERSON: usize = 4;
+
+/// Queries of the query latency benchmark, keyed by the pattern they exercise.
+pub const QUERIES: [(&str, &str); 5] = [
+    (
+        "single pattern",
+        "SELECT ?s WHERE { ?s a <http://example.com/Person> }",
+    ),
+    (
+        "star join",
+        "SELECT ?s ?name ?age WHERE { ?s <http://example.com/name> ?name ; <http://example.com/age> ?age }",
+    ),
+    (
+        "path join",
+        "SELECT ?a ?c WHERE { ?a <http://example.com/knows> ?b . ?b <http://example.com/knows> ?c }",
+    ),
+    (
+        "filter",
+        "SELECT ?s WHERE { ?s <http://example.com/age> ?age FILTER(?age > 50) }",
+    ),
+    (
+        "aggregate",
+        "SELECT ?age (COUNT(?s) AS ?count) WHERE { ?s <http://example.com/age> ?age } GROUP BY ?age",
+    ),
+];
+
+/// SHACL shapes matching the data of [`generate_graph`].
+const PERSON_SHAPES: &str = "
+@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+@prefix ex: <http://example.com/> .
+
+ex:PersonShape a sh:NodeShape ;
+    sh:targetClass ex:Person ;
+    sh:property [ sh:path ex:name ; sh:minCount 1 ; sh:maxCount 1 ; sh:datatype xsd:string ; sh:pattern '^Person [0-9]+$' ] ;
+    sh:property [ sh:path ex:age ; sh:datatype xsd:integer ; sh:minInclusive 0 ; sh:maxInclusive 100 ] ;
+    sh:property [ sh:path ex:knows ; sh:nodeKind sh:IRI ] .
+";
+
+/// Generates a deterministic social network dataset of exactly `size` quads in the default graph.
+///
+/// Each person gets a type, a name, an age and a link to the next person.
+/// The last person might be incomplete if `size` is not a multiple of four.
+pub fn generate_quads(size: usize) -> Vec<Quad> {
+    let people = size.div_ceil(TRIPLES_PER_PERSON);
+    let person_class = NamedNode::new_unchecked(format!("{EX}Person"));
+    let name = NamedNode::new_unchecked(format!("{EX}name"));
+    let age = NamedNode::new_unchecked(format!("{EX}age"));
+    let knows = NamedNode::new_unchecked(format!("{EX}knows"));
+    (0..size)
+        .map(|i| {
+            let id = i / TRIPLES_PER_PERSON;
+            let person = NamedNode::new_unchecked(format!("{EX}person{id}"));
+            let triple = match i % TRIPLES_PER_PERSON {
+                0 => Triple::new(person, rdf::TYPE, person_class.clone()),
+                1 => Triple::new(
+                    person,
+                    name.clone(),
+                    Literal::new_simple_literal(format!("Person {id}")),
+                ),
+                2 => Triple::new(
+                    person,
+                    age.clone(),
+                    Literal::new_typed_literal((id % 100).to_string(), xsd::INTEGER),
+                ),
+                _ => Triple::new(
+                    person,
+                    knows.clone(),
+                    NamedNode::new_unchecked(format!("{EX}person{}", (id + 1) % people)),
+                ),
+            };
+            triple.in_graph(GraphName::DefaultGraph)
+        })
+        .collect()
+}
+
+/// Generates the dataset of [`generate_quads`] as a [`Graph`] of exactly `size` triples.
+pub fn generate_graph(size: usize) -> Graph {
+    generate_quads(size).into_iter().map(Triple::from).collect()
+}
+
+/// Generates the dataset of [`generate_quads`] serialized as N-Triples.
+pub fn generate_ntriples(size: usize) -> io::Result<Vec<u8>> {
+    let mut serializer = RdfSerializer::from_format(RdfFormat::NTriples).for_writer(Vec::new());
+    for quad in generate_quads(size) {
+        serializer.serialize_quad(&quad)?;
+    }
+    serializer.finish()
+}
+
+/// Returns SHACL shapes constraining the persons of [`generate_graph`].
+///
+/// The generated data conforms to these shapes.
+pub fn person_shapes() -> Result<ShapesGraph, Box<dyn Error + Send + Sync>> {
+    let mut graph = Graph::new();
+    for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(PERSON_SHAPES) {
+        graph.insert(&Triple::from(quad?));
+    }
+    Ok(ShapesGraph::from_graph(&graph)?)
+}
@@ -5,9 +5,14 @@
 #![doc(html_favicon_url = "https://raw.githubusercontent.com/oxigraph/oxigraph/main/logo.svg")]
 #![doc(html_logo_url = "https://raw.githubusercontent.com/oxigraph/oxigraph/main/logo.svg")]
 
+#[cfg(feature = "bench")]
+pub mod bench;
 pub mod io;
 pub mod metrics;
 pub mod model;
+#[cfg(feature = "shacl")]
+pub mod shacl;
 pub mod sparql;
 mod storage;
 pub mod store;
+pub mod util;
@@ -2,9 +2,71 @@
 //!
 //! Provides lightweight metrics collection for production monitoring.
 //! Uses atomic counters for lock-free metric recording.
+//!
+//! A [`MetricsCollector`] can be registered on a [`Store`](crate::store::Store) with [`Store::with_metrics`](crate::store::Store::with_metrics).
+//!
+//! ```
+//! use oxigraph::metrics::StoreMetrics;
+//! use oxigraph::model::*;
+//! use oxigraph::sparql::{QueryResults, SparqlEvaluator};
+//! use oxigraph::store::Store;
+//! use std::sync::Arc;
+//!
+//! let metrics = Arc::new(StoreMetrics::new());
+//! let store = Store::new()?.with_metrics(metrics.clone());
+//! let ex = NamedNodeRef::new("http://example.com")?;
+//! store.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph))?;
+//! if let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+//!     .parse_query("SELECT * WHERE { ?s ?p ?o }")?
+//!     .on_store(&store)
+//!     .execute()?
+//! {
+//!     for solution in solutions {
+//!         solution?;
+//!     }
+//! }
+//! let output = metrics.to_prometheus_format();
+//! assert!(output.contains("oxigraph_queries_total 1"));
+//! assert!(output.contains("oxigraph_inserts_total 1"));
+//! # Result::<_, Box<dyn std::error::Error>>::Ok(())
+//! ```
 
+use crate::sparql::{QueryEvaluationError, QueryResults, QuerySolutionIter, QueryTripleIter};
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// Callbacks called by a [`Store`](crate::store::Store) on the operations done on it.
+///
+/// All methods do nothing by default.
+/// The callbacks are called synchronously so they should be fast, for example by only updating counters.
+pub trait MetricsCollector: Send + Sync {
+    /// Called when the evaluation of a SPARQL query starts.
+    fn query_started(&self) {}
+
+    /// Called when the results of a SPARQL query have been fully consumed or dropped.
+    ///
+    /// `result_count` is the number of returned solutions or triples, `1` for `ASK` queries.
+    fn query_finished(&self, _duration: Duration, _result_count: usize) {}
+
+    /// Called when the evaluation of a SPARQL query fails.
+    fn query_failed(&self, _duration: Duration, _error: &QueryEvaluationError) {}
+
+    /// Called from time to time during bulk loads with the number of quads loaded so far.
+    fn bulk_load_progress(&self, _loaded_quads: u64) {}
+
+    /// Called when a bulk load is committed with the number of loaded quads.
+    fn bulk_load_committed(&self, _loaded_quads: u64) {}
+
+    /// Called when a transaction is committed.
+    ///
+    /// `inserted_quads` and `removed_quads` are the number of insertions and removals done in the transaction,
+    /// including the ones of quads that were already in the store or missing from it.
+    fn transaction_committed(&self, _inserted_quads: usize, _removed_quads: usize) {}
+
+    /// Called when a transaction is rolled back, dropped without being committed or when its commit fails.
+    fn transaction_rolled_back(&self) {}
+}
 
 /// Metrics collected by the Store for observability
 #[derive(Debug, Default)]
@@ -75,6 +137,111 @@ impl StoreMetrics {
     }
 }
 
+impl MetricsCollector for StoreMetrics {
+    fn query_finished(&self, duration: Duration, _result_count: usize) {
+        self.record_query(duration_ms(duration), false);
+    }
+
+    fn query_failed(&self, duration: Duration, _error: &QueryEvaluationError) {
+        self.record_query(duration_ms(duration), true);
+    }
+
+    fn bulk_load_committed(&self, loaded_quads: u64) {
+        self.record_insert(loaded_quads);
+    }
+
+    fn transaction_committed(&self, inserted_quads: usize, removed_quads: usize) {
+        self.record_insert(inserted_quads.try_into().unwrap_or(u64::MAX));
+        self.record_delete(removed_quads.try_into().unwrap_or(u64::MAX));
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+/// Reports the evaluation of a query started at `start` to `metrics`.
+///
+/// Solutions and triples are counted while they are iterated.
+pub(crate) fn measure_query_results<'a>(
+    results: Result<QueryResults<'a>, QueryEvaluationError>,
+    metrics: Arc<dyn MetricsCollector>,
+    start: Instant,
+) -> Result<QueryResults<'a>, QueryEvaluationError> {
+    match results {
+        Ok(QueryResults::Solutions(solutions)) => {
+            let variables = solutions.variables().into();
+            Ok(QueryResults::Solutions(QuerySolutionIter::new(
+                variables,
+                MeasuredIter::new(solutions, metrics, start),
+            )))
+        }
+        Ok(QueryResults::Graph(triples)) => Ok(QueryResults::Graph(QueryTripleIter::new(
+            MeasuredIter::new(triples, metrics, start),
+        ))),
+        Ok(QueryResults::Boolean(value)) => {
+            metrics.query_finished(start.elapsed(), 1);
+            Ok(QueryResults::Boolean(value))
+        }
+        Err(error) => {
+            metrics.query_failed(start.elapsed(), &error);
+            Err(error)
+        }
+    }
+}
+
+/// Counts the results of a query and reports them to the [`MetricsCollector`] when dropped.
+struct MeasuredIter<I> {
+    inner: I,
+    metrics: Arc<dyn MetricsCollector>,
+    start: Instant,
+    count: usize,
+    failed: bool,
+}
+
+impl<I> MeasuredIter<I> {
+    fn new(inner: I, metrics: Arc<dyn MetricsCollector>, start: Instant) -> Self {
+        Self {
+            inner,
+            metrics,
+            start,
+            count: 0,
+            failed: false,
+        }
+    }
+}
+
+impl<T, I: Iterator<Item = Result<T, QueryEvaluationError>>> Iterator for MeasuredIter<I> {
+    type Item = Result<T, QueryEvaluationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.inner.next()?;
+        match &result {
+            Ok(_) => self.count += 1,
+            Err(error) => {
+                if !self.failed {
+                    self.failed = true;
+                    self.metrics.query_failed(self.start.elapsed(), error);
+                }
+            }
+        }
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I> Drop for MeasuredIter<I> {
+    fn drop(&mut self) {
+        if !self.failed {
+            self.metrics
+                .query_finished(self.start.elapsed(), self.count);
+        }
+    }
+}
+
 /// Timer for measuring operation duration
 pub struct Timer {
     start: Instant,
@@ -129,7 +296,7 @@ mod tests {
     #[test]
     fn test_timer() {
         let timer = Timer::start();
-        std::thread::sleep(std::time::Duration::from_millis(10));
+        std::thread::sleep(Duration::from_millis(10));
         let elapsed = timer.elapsed_ms();
         assert!(elapsed >= 10);
     }
@@ -0,0 +1,142 @@
+//! [SHACL](https://www.w3.org/TR/shacl/) validation provided by the [`sparshacl`] crate.
+//!
+//! The content of a store can be validated without being copied with [`Store::validate_shacl`](crate::store::Store::validate_shacl).
+
+use crate::model::{
+    NamedNode, NamedNodeRef, NamedOrBlankNode, NamedOrBlankNodeRef, Term, TermRef, Triple,
+};
+use crate::storage::numeric_encoder::{Decoder, EncodedQuad, EncodedTerm};
+use crate::storage::{StorageError, StorageReader};
+pub use sparshacl::{
+    Constraint, ConstraintComponent, DataGraph, NodeShape, PropertyPath, PropertyShape, Severity,
+    ShaclError, ShaclParseError, ShaclValidationError, ShaclValidator, Shape, ShapeId, ShapesGraph,
+    SparqlConstraint, Target, ValidationReport, ValidationResult, shacl,
+};
+
+/// A graph of a store snapshot that the SHACL validator reads directly.
+pub(crate) struct StoreDataGraph<'a> {
+    reader: StorageReader<'a>,
+    graph_name: EncodedTerm,
+}
+
+impl<'a> StoreDataGraph<'a> {
+    pub(crate) fn new(reader: StorageReader<'a>, graph_name: EncodedTerm) -> Self {
+        Self { reader, graph_name }
+    }
+
+    fn quads_for_pattern(
+        &self,
+        subject: Option<&EncodedTerm>,
+        predicate: Option<&EncodedTerm>,
+        object: Option<&EncodedTerm>,
+    ) -> impl Iterator<Item = Result<EncodedQuad, ShaclError>> + use<'a> {
+        self.reader
+            .quads_for_pattern(subject, predicate, object, Some(&self.graph_name))
+            .map(|quad| quad.map_err(data_graph_error))
+    }
+}
+
+impl DataGraph for StoreDataGraph<'_> {
+    fn objects_for_subject_predicate<'b>(
+        &'b self,
+        subject: NamedOrBlankNodeRef<'_>,
+        predicate: NamedNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<Term, ShaclError>> + 'b> {
+        Box::new(
+            self.quads_for_pattern(Some(&subject.into()), Some(&predicate.into()), None)
+                .map(|quad| {
+                    self.reader
+                        .decode_term(&quad?.object)
+                        .map_err(data_graph_error)
+                }),
+        )
+    }
+
+    fn subjects_for_predicate_object<'b>(
+        &'b self,
+        predicate: NamedNodeRef<'_>,
+        object: TermRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<NamedOrBlankNode, ShaclError>> + 'b> {
+        Box::new(
+            self.quads_for_pattern(None, Some(&predicate.into()), Some(&object.into()))
+                .map(|quad| {
+                    self.reader
+                        .decode_named_or_blank_node(&quad?.subject)
+                        .map_err(data_graph_error)
+                }),
+        )
+    }
+
+    fn subjects_objects_for_predicate<'b>(
+        &'b self,
+        predicate: NamedNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<(NamedOrBlankNode, Term), ShaclError>> + 'b> {
+        Box::new(
+            self.quads_for_pattern(None, Some(&predicate.into()), None)
+                .map(|quad| {
+                    let quad = quad?;
+                    Ok((
+                        self.reader
+                            .decode_named_or_blank_node(&quad.subject)
+                            .map_err(data_graph_error)?,
+                        self.reader
+                            .decode_term(&quad.object)
+                            .map_err(data_graph_error)?,
+                    ))
+                }),
+        )
+    }
+
+    fn predicates_objects_for_subject<'b>(
+        &'b self,
+        subject: NamedOrBlankNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<(NamedNode, Term), ShaclError>> + 'b> {
+        Box::new(
+            self.quads_for_pattern(Some(&subject.into()), None, None)
+                .map(|quad| {
+                    let quad = quad?;
+                    Ok((
+                        self.reader
+                            .decode_named_node(&quad.predicate)
+                            .map_err(data_graph_error)?,
+                        self.reader
+                            .decode_term(&quad.object)
+                            .map_err(data_graph_error)?,
+                    ))
+                }),
+        )
+    }
+
+    fn triples_for_pattern<'b>(
+        &'b self,
+        subject: Option<NamedOrBlankNodeRef<'_>>,
+        predicate: Option<NamedNodeRef<'_>>,
+        object: Option<TermRef<'_>>,
+    ) -> Box<dyn Iterator<Item = Result<Triple, ShaclError>> + 'b> {
+        Box::new(
+            self.quads_for_pattern(
+                subject.map(EncodedTerm::from).as_ref(),
+                predicate.map(EncodedTerm::from).as_ref(),
+                object.map(EncodedTerm::from).as_ref(),
+            )
+            .map(|quad| {
+                let quad = quad?;
+                Ok(Triple::new(
+                    self.reader
+                        .decode_named_or_blank_node(&quad.subject)
+                        .map_err(data_graph_error)?,
+                    self.reader
+                        .decode_named_node(&quad.predicate)
+                        .map_err(data_graph_error)?,
+                    self.reader
+                        .decode_term(&quad.object)
+                        .map_err(data_graph_error)?,
+                ))
+            }),
+        )
+    }
+}
+
+fn data_graph_error(error: StorageError) -> ShaclError {
+    ShaclError::DataGraph(Box::new(error))
+}
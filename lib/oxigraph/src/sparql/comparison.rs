@@ -0,0 +1,261 @@
+use crate::model::graph::CanonicalizationAlgorithm;
+use crate::model::vocab::rdf;
+use crate::model::{BlankNode, Graph, Literal, NamedNodeRef, Triple};
+use crate::sparql::QueryResults;
+
+const RESULT_SET: NamedNodeRef<'_> =
+    NamedNodeRef::new_unchecked("http://www.w3.org/2001/sw/DataAccess/tests/result-set#ResultSet");
+const RESULT_VARIABLE: NamedNodeRef<'_> = NamedNodeRef::new_unchecked(
+    "http://www.w3.org/2001/sw/DataAccess/tests/result-set#resultVariable",
+);
+const SOLUTION: NamedNodeRef<'_> =
+    NamedNodeRef::new_unchecked("http://www.w3.org/2001/sw/DataAccess/tests/result-set#solution");
+const BINDING: NamedNodeRef<'_> =
+    NamedNodeRef::new_unchecked("http://www.w3.org/2001/sw/DataAccess/tests/result-set#binding");
+const VARIABLE: NamedNodeRef<'_> =
+    NamedNodeRef::new_unchecked("http://www.w3.org/2001/sw/DataAccess/tests/result-set#variable");
+const VALUE: NamedNodeRef<'_> =
+    NamedNodeRef::new_unchecked("http://www.w3.org/2001/sw/DataAccess/tests/result-set#value");
+const INDEX: NamedNodeRef<'_> =
+    NamedNodeRef::new_unchecked("http://www.w3.org/2001/sw/DataAccess/tests/result-set#index");
+
+/// Checks if two query results are equal, ignoring the order of the solutions.
+///
+/// Blank nodes are compared up to isomorphism:
+/// two results are equal if there is a bijection between their blank nodes that makes them identical.
+/// The blank node bijection is shared by all the solutions of a result set.
+/// Use [`ordered_results_equal`] to also compare the order of the solutions,
+/// for example when the query has an `ORDER BY` clause.
+///
+/// Results that fail to be evaluated are never equal.
+///
+/// ```
+/// use oxigraph::sparql::{SparqlEvaluator, results_equal};
+/// use oxigraph::store::Store;
+///
+/// let store = Store::new()?;
+/// let evaluate = |query| SparqlEvaluator::new().parse_query(query)?.on_store(&store).execute();
+/// assert!(results_equal(
+///     evaluate("SELECT ?v WHERE { VALUES ?v { 1 2 } }")?,
+///     evaluate("SELECT ?v WHERE { VALUES ?v { 2 1 } }")?
+/// ));
+/// assert!(results_equal(
+///     evaluate("CONSTRUCT { _:a <http://example.com/p> _:b } WHERE {}")?,
+///     evaluate("CONSTRUCT { _:c <http://example.com/p> _:d } WHERE {}")?
+/// ));
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+pub fn results_equal(left: QueryResults<'_>, right: QueryResults<'_>) -> bool {
+    compare_results(left, right, false)
+}
+
+/// Checks if two query results are equal, including the order of the solutions.
+///
+/// It behaves like [`results_equal`] except that the solutions must be in the same order.
+///
+/// ```
+/// use oxigraph::sparql::{SparqlEvaluator, ordered_results_equal};
+/// use oxigraph::store::Store;
+///
+/// let store = Store::new()?;
+/// let evaluate = |query| SparqlEvaluator::new().parse_query(query)?.on_store(&store).execute();
+/// assert!(!ordered_results_equal(
+///     evaluate("SELECT ?v WHERE { VALUES ?v { 1 2 } } ORDER BY ?v")?,
+///     evaluate("SELECT ?v WHERE { VALUES ?v { 1 2 } } ORDER BY DESC(?v)")?
+/// ));
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+pub fn ordered_results_equal(left: QueryResults<'_>, right: QueryResults<'_>) -> bool {
+    compare_results(left, right, true)
+}
+
+fn compare_results(left: QueryResults<'_>, right: QueryResults<'_>, ordered: bool) -> bool {
+    match (left, right) {
+        (QueryResults::Boolean(left), QueryResults::Boolean(right)) => left == right,
+        (left @ QueryResults::Solutions(_), right @ QueryResults::Solutions(_))
+        | (left @ QueryResults::Graph(_), right @ QueryResults::Graph(_)) => {
+            let (Some(mut left), Some(mut right)) =
+                (to_graph(left, ordered), to_graph(right, ordered))
+            else {
+                return false;
+            };
+            left.canonicalize(CanonicalizationAlgorithm::Unstable);
+            right.canonicalize(CanonicalizationAlgorithm::Unstable);
+            left == right
+        }
+        _ => false,
+    }
+}
+
+/// Encodes the results as a graph so that blank nodes can be compared with graph isomorphism.
+///
+/// Solutions are encoded with the vocabulary of the W3C SPARQL test suite.
+fn to_graph(results: QueryResults<'_>, ordered: bool) -> Option<Graph> {
+    let mut graph = Graph::new();
+    match results {
+        QueryResults::Solutions(solutions) => {
+            let result_set = BlankNode::default();
+            graph.insert(&Triple::new(result_set.clone(), rdf::TYPE, RESULT_SET));
+            for variable in solutions.variables() {
+                graph.insert(&Triple::new(
+                    result_set.clone(),
+                    RESULT_VARIABLE,
+                    Literal::new_simple_literal(variable.as_str()),
+                ));
+            }
+            for (i, solution) in solutions.enumerate() {
+                let solution_id = BlankNode::default();
+                graph.insert(&Triple::new(
+                    result_set.clone(),
+                    SOLUTION,
+                    solution_id.clone(),
+                ));
+                for (variable, value) in solution.ok()?.iter() {
+                    let binding = BlankNode::default();
+                    graph.insert(&Triple::new(solution_id.clone(), BINDING, binding.clone()));
+                    graph.insert(&Triple::new(
+                        binding.clone(),
+                        VARIABLE,
+                        Literal::new_simple_literal(variable.as_str()),
+                    ));
+                    graph.insert(&Triple::new(binding, VALUE, value.clone()));
+                }
+                if ordered {
+                    graph.insert(&Triple::new(
+                        solution_id,
+                        INDEX,
+                        Literal::from(i64::try_from(i).ok()?),
+                    ));
+                }
+            }
+        }
+        QueryResults::Graph(triples) => {
+            for triple in triples {
+                graph.insert(&triple.ok()?);
+            }
+        }
+        QueryResults::Boolean(_) => return None,
+    }
+    Some(graph)
+}
+
+#[cfg(test)]
+#[expect(clippy::panic_in_result_fn)]
+mod tests {
+    use super::*;
+    use crate::sparql::{QueryEvaluationError, QuerySolutionIter, SparqlEvaluator, Variable};
+    use crate::store::Store;
+    use std::error::Error;
+
+    fn evaluate<'a>(store: &'a Store, query: &str) -> Result<QueryResults<'a>, Box<dyn Error>> {
+        Ok(SparqlEvaluator::new()
+            .parse_query(query)?
+            .on_store(store)
+            .execute()?)
+    }
+
+    #[test]
+    fn test_results_equal() -> Result<(), Box<dyn Error>> {
+        let store = Store::new()?;
+        store.load_from_slice(
+            crate::io::RdfFormat::Turtle,
+            "<http://example.com/s> <http://example.com/p> [ <http://example.com/q> 1 ], 2 .",
+        )?;
+
+        // Same solutions in a different order
+        assert!(results_equal(
+            evaluate(&store, "SELECT ?o WHERE { ?s ?p ?o }")?,
+            evaluate(&store, "SELECT ?o WHERE { ?s ?p ?o } ORDER BY DESC(?o)")?
+        ));
+        // Same solutions with blank nodes with different labels
+        assert!(results_equal(
+            evaluate(
+                &store,
+                "SELECT ?b WHERE { VALUES ?x { 1 2 } BIND(BNODE() AS ?b) }"
+            )?,
+            evaluate(
+                &store,
+                "SELECT ?b WHERE { VALUES ?x { 3 4 } BIND(BNODE() AS ?b) }"
+            )?
+        ));
+        assert!(results_equal(
+            evaluate(&store, "CONSTRUCT { ?o ?p ?s } WHERE { ?s ?p ?o }")?,
+            evaluate(
+                &store,
+                "CONSTRUCT { _:x <http://example.com/p> <http://example.com/s> } WHERE {}"
+            )?
+        ));
+        assert!(results_equal(
+            evaluate(&store, "ASK { ?s ?p 2 }")?,
+            evaluate(&store, "ASK { ?s ?p ?o }")?
+        ));
+
+        // Different results
+        assert!(!results_equal(
+            evaluate(&store, "SELECT ?o WHERE { ?s ?p ?o }")?,
+            evaluate(&store, "SELECT ?s WHERE { ?s ?p ?o }")?
+        ));
+        assert!(!results_equal(
+            evaluate(&store, "SELECT ?o WHERE { ?s ?p ?o }")?,
+            evaluate(&store, "SELECT ?o WHERE { ?s ?p ?o FILTER(isBlank(?o)) }")?
+        ));
+        // The same blank node used twice is not two different blank nodes
+        assert!(!results_equal(
+            evaluate(
+                &store,
+                "SELECT ?b WHERE { VALUES ?x { 1 2 } BIND(BNODE() AS ?b) }"
+            )?,
+            evaluate(
+                &store,
+                "SELECT ?b WHERE { VALUES ?x { 1 2 } ?s ?p ?b FILTER(isBlank(?b)) }"
+            )?
+        ));
+        assert!(!results_equal(
+            evaluate(&store, "CONSTRUCT { ?o ?p ?s } WHERE { ?s ?p ?o }")?,
+            evaluate(&store, "CONSTRUCT { ?s ?p ?o } WHERE { ?s ?p ?o }")?
+        ));
+        assert!(!results_equal(
+            evaluate(&store, "ASK { ?s ?p 2 }")?,
+            evaluate(&store, "ASK { ?s ?p 3 }")?
+        ));
+        assert!(!results_equal(
+            evaluate(&store, "ASK { ?s ?p ?o }")?,
+            evaluate(&store, "SELECT * WHERE { ?s ?p ?o }")?
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ordered_results_equal() -> Result<(), Box<dyn Error>> {
+        let store = Store::new()?;
+        let ascending = "SELECT ?v WHERE { VALUES ?v { 2 1 3 } } ORDER BY ?v";
+        let descending = "SELECT ?v WHERE { VALUES ?v { 2 1 3 } } ORDER BY DESC(?v)";
+        assert!(ordered_results_equal(
+            evaluate(&store, ascending)?,
+            evaluate(&store, "SELECT ?v WHERE { VALUES ?v { 1 2 3 } }")?
+        ));
+        assert!(!ordered_results_equal(
+            evaluate(&store, ascending)?,
+            evaluate(&store, descending)?
+        ));
+        assert!(results_equal(
+            evaluate(&store, ascending)?,
+            evaluate(&store, descending)?
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_results_with_errors_are_not_equal() -> Result<(), Box<dyn Error>> {
+        let store = Store::new()?;
+        let failing = QuerySolutionIter::new(
+            [Variable::new("v")?].into(),
+            [Err(QueryEvaluationError::Unexpected("error".into()))],
+        );
+        assert!(!results_equal(
+            failing.into(),
+            evaluate(&store, "SELECT ?v WHERE { VALUES ?v { 1 } }")?
+        ));
+        Ok(())
+    }
+}
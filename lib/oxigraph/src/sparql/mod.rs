@@ -3,37 +3,46 @@
 //! The entry point for SPARQL execution is the [`SparqlEvaluator`] type.
 
 mod algebra;
+mod comparison;
 mod dataset;
 mod error;
 #[cfg(feature = "http-client")]
 mod http;
+mod query_cache;
 pub mod results;
+pub(crate) mod results_cache;
 mod update;
 
+use crate::metrics::{MetricsCollector, measure_query_results};
 use crate::model::{NamedNode, Term};
 #[expect(deprecated)]
 pub use crate::sparql::algebra::{Query, Update};
+pub use crate::sparql::comparison::{ordered_results_equal, results_equal};
 use crate::sparql::dataset::DatasetView;
 pub use crate::sparql::error::UpdateEvaluationError;
 #[cfg(feature = "http-client")]
 use crate::sparql::http::HttpServiceHandler;
-pub use crate::sparql::update::{BoundPreparedSparqlUpdate, PreparedSparqlUpdate};
+use crate::sparql::query_cache::QueryCache;
+use crate::sparql::results_cache::{QueryResultsCache, QueryResultsCacheKey};
+pub use crate::sparql::update::{BoundPreparedSparqlUpdate, PreparedSparqlUpdate, UpdateDiff};
 use crate::store::{Store, Transaction};
+use oxiri::Iri;
 use oxrdf::IriParseError;
 pub use oxrdf::{Variable, VariableNameParseError};
 pub use spareval::{
-    AggregateFunctionAccumulator, CancellationToken, DefaultServiceHandler,
+    AggregateFunctionAccumulator, CancellationToken, DefaultServiceHandler, DescribeStrategy,
     QueryDatasetSpecification, QueryEvaluationError, QueryExplanation, QueryResults, QuerySolution,
     QuerySolutionIter, QueryTripleIter, ServiceHandler,
 };
 use spareval::{QueryEvaluator, QueryableDataset};
 use spargebra::SparqlParser;
 pub use spargebra::SparqlSyntaxError;
+use spargebra::algebra::GraphPattern;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::mem::take;
-#[cfg(feature = "http-client")]
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[deprecated(note = "Use SparqlEvaluator instead", since = "0.5.0")]
 pub type QueryOptions = SparqlEvaluator;
@@ -76,6 +85,9 @@ pub struct SparqlEvaluator {
     #[cfg(feature = "http-client")]
     with_http_default_service_handler: bool,
     parser: SparqlParser,
+    query_cache: Option<QueryCache>,
+    /// The store results cache is only keyed on the queries, so only the default evaluation might use it
+    use_results_cache: bool,
     inner: QueryEvaluator,
 }
 
@@ -107,6 +119,7 @@ impl SparqlEvaluator {
     #[inline]
     pub fn with_base_iri(mut self, base_iri: impl Into<String>) -> Result<Self, IriParseError> {
         self.parser = self.parser.with_base_iri(base_iri)?;
+        self.reset_query_cache();
         Ok(self)
     }
 
@@ -137,11 +150,39 @@ impl SparqlEvaluator {
         prefix_iri: impl Into<String>,
     ) -> Result<Self, IriParseError> {
         self.parser = self.parser.with_prefix(prefix_name, prefix_iri)?;
+        self.reset_query_cache();
         Ok(self)
     }
 
+    /// Makes [`parse_query`](Self::parse_query) fail if a variable projected by a `SELECT` query is never bound by the query pattern.
+    ///
+    /// Such variables are always unbound in the results, which is allowed by the SPARQL specification
+    /// but is most of the time a typo. This check is disabled by default.
+    ///
+    /// ```
+    /// use oxigraph::sparql::SparqlEvaluator;
+    ///
+    /// let query = "SELECT ?nmae WHERE { ?s <http://example.com/name> ?name }";
+    /// assert!(SparqlEvaluator::new().parse_query(query).is_ok());
+    /// assert!(
+    ///     SparqlEvaluator::new()
+    ///         .with_strict_projection()
+    ///         .parse_query(query)
+    ///         .is_err()
+    /// );
+    /// ```
+    #[inline]
+    pub fn with_strict_projection(mut self) -> Self {
+        self.parser = self.parser.with_strict_projection();
+        self.reset_query_cache();
+        self
+    }
+
     /// Use a given [`ServiceHandler`] to execute [SPARQL 1.1 Federated Query](https://www.w3.org/TR/sparql11-federated-query/) SERVICE calls.
     ///
+    /// It is used instead of the default handler, i.e. no HTTP request is done for this service.
+    /// Closures and [`Store`]s are also service handlers, the latter allowing to federate local stores.
+    ///
     /// See [`ServiceHandler`] for an example.
     #[inline]
     pub fn with_service_handler(
@@ -150,6 +191,7 @@ impl SparqlEvaluator {
         handler: impl ServiceHandler + 'static,
     ) -> Self {
         self.inner = self.inner.with_service_handler(service_name, handler);
+        self.use_results_cache = false;
         self
     }
 
@@ -168,6 +210,7 @@ impl SparqlEvaluator {
             self.with_http_default_service_handler = false;
         }
         self.inner = self.inner.with_default_service_handler(handler);
+        self.use_results_cache = false;
         self
     }
 
@@ -210,6 +253,10 @@ impl SparqlEvaluator {
 
     /// Adds a custom SPARQL evaluation function.
     ///
+    /// It is called with the evaluated arguments when the query calls the function IRI.
+    /// Returning `None` is an evaluation error: the variable bound by `BIND` or `SELECT` stays unbound
+    /// and the `FILTER` evaluates to false.
+    ///
     /// Example with a function serializing terms to N-Triples:
     /// ```
     /// use oxigraph::model::*;
@@ -239,6 +286,47 @@ impl SparqlEvaluator {
         evaluator: impl Fn(&[Term]) -> Option<Term> + Send + Sync + 'static,
     ) -> Self {
         self.inner = self.inner.with_custom_function(name, evaluator);
+        self.use_results_cache = false;
+        self
+    }
+
+    /// Adds a custom SPARQL evaluation function that might fail.
+    ///
+    /// It behaves like [`with_custom_function`](Self::with_custom_function)
+    /// but allows to use `?` in the function body.
+    /// Returning `Ok(None)` or an error is an evaluation error:
+    /// the variable bound by `BIND` or `SELECT` stays unbound and the `FILTER` evaluates to false.
+    ///
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::sparql::{QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    ///
+    /// if let QueryResults::Solutions(mut solutions) = SparqlEvaluator::new()
+    ///     .with_fallible_custom_function(NamedNode::new("http://example.com/double")?, |args| {
+    ///         let [Term::Literal(value)] = args else {
+    ///             return Ok(None);
+    ///         };
+    ///         let value = value.value().parse::<i64>()?;
+    ///         Ok::<_, std::num::ParseIntError>(value.checked_mul(2).map(|v| Literal::from(v).into()))
+    ///     })
+    ///     .parse_query("SELECT (<http://example.com/double>(\"a\") AS ?d) WHERE {}")?
+    ///     .on_store(&Store::new()?)
+    ///     .execute()?
+    /// {
+    ///     // The function fails on "a" so ?d is unbound
+    ///     assert_eq!(solutions.next().unwrap()?.get("d"), None);
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_fallible_custom_function<E>(
+        mut self,
+        name: NamedNode,
+        evaluator: impl Fn(&[Term]) -> Result<Option<Term>, E> + Send + Sync + 'static,
+    ) -> Self {
+        self.inner = self.inner.with_fallible_custom_function(name, evaluator);
+        self.use_results_cache = false;
         self
     }
 
@@ -299,7 +387,9 @@ impl SparqlEvaluator {
         + 'static,
     ) -> Self {
         self.parser = self.parser.with_custom_aggregate_function(name.clone());
+        self.reset_query_cache();
         self.inner = self.inner.with_custom_aggregate_function(name, evaluator);
+        self.use_results_cache = false;
         self
     }
 
@@ -345,9 +435,216 @@ impl SparqlEvaluator {
     /// ```
     pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
         self.inner = self.inner.with_cancellation_token(cancellation_token);
+        self.use_results_cache = false;
+        self
+    }
+
+    /// Sets a timeout for the whole SPARQL evaluation.
+    ///
+    /// Contrary to [`with_http_timeout`](Self::with_http_timeout) that only bounds the HTTP requests done by `SERVICE` calls,
+    /// it bounds the full query evaluation, including the iteration over the results.
+    /// The timeout starts when the query is executed.
+    /// When it is exceeded, the evaluation fails with [`QueryEvaluationError::Timeout`].
+    ///
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::sparql::{QueryEvaluationError, QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let store = Store::new()?;
+    /// store.insert(QuadRef::new(
+    ///     NamedNodeRef::new("http://example.com/s")?,
+    ///     NamedNodeRef::new("http://example.com/p")?,
+    ///     NamedNodeRef::new("http://example.com/o")?,
+    ///     GraphNameRef::DefaultGraph,
+    /// ))?;
+    /// if let QueryResults::Solutions(mut solutions) = SparqlEvaluator::new()
+    ///     .with_timeout(Duration::from_millis(10))
+    ///     .parse_query("SELECT * WHERE { ?s ?p ?o }")?
+    ///     .on_store(&store)
+    ///     .execute()?
+    /// {
+    ///     sleep(Duration::from_millis(20));
+    ///     assert!(matches!(
+    ///         solutions.next().unwrap().unwrap_err(), // It's timed out
+    ///         QueryEvaluationError::Timeout(_)
+    ///     ));
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.with_timeout(timeout);
+        self.use_results_cache = false;
+        self
+    }
+
+    /// Sets the maximum number of nodes visited when evaluating a transitive property path (`*` or `+`).
+    ///
+    /// The limit applies to each evaluation of a transitive path independently.
+    /// When it is exceeded, the evaluation fails with [`QueryEvaluationError::PropertyPathDepthExceeded`].
+    ///
+    /// ```
+    /// use oxigraph::io::RdfFormat;
+    /// use oxigraph::sparql::{QueryEvaluationError, QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    ///
+    /// let store = Store::new()?;
+    /// store.load_from_slice(
+    ///     RdfFormat::Turtle,
+    ///     "<http://example.com/a> <http://example.com/p> <http://example.com/b> .
+    ///      <http://example.com/b> <http://example.com/p> <http://example.com/c> .",
+    /// )?;
+    /// if let QueryResults::Solutions(mut solutions) = SparqlEvaluator::new()
+    ///     .with_max_path_visits(1)
+    ///     .parse_query("SELECT ?o WHERE { <http://example.com/a> <http://example.com/p>+ ?o }")?
+    ///     .on_store(&store)
+    ///     .execute()?
+    /// {
+    ///     assert!(matches!(
+    ///         solutions.next().unwrap().unwrap_err(),
+    ///         QueryEvaluationError::PropertyPathDepthExceeded(1)
+    ///     ));
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_max_path_visits(mut self, max_path_visits: usize) -> Self {
+        self.inner = self.inner.with_max_path_visits(max_path_visits);
+        self.use_results_cache = false;
+        self
+    }
+
+    /// Bounds the number of rows kept in memory by `DISTINCT` and by the groups of `GROUP BY`.
+    ///
+    /// When the limit is exceeded, the evaluation fails with [`QueryEvaluationError::GroupLimitExceeded`].
+    ///
+    /// ```
+    /// use oxigraph::sparql::{QueryEvaluationError, QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    ///
+    /// if let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+    ///     .with_max_materialized_rows(2)
+    ///     .parse_query("SELECT ?v (COUNT(*) AS ?c) WHERE { VALUES ?v { 1 2 3 } } GROUP BY ?v")?
+    ///     .on_store(&Store::new()?)
+    ///     .execute()?
+    /// {
+    ///     assert!(matches!(
+    ///         solutions.last().unwrap().unwrap_err(),
+    ///         QueryEvaluationError::GroupLimitExceeded(2)
+    ///     ));
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_max_materialized_rows(mut self, max_materialized_rows: usize) -> Self {
+        self.inner = self.inner.with_max_materialized_rows(max_materialized_rows);
+        self.use_results_cache = false;
+        self
+    }
+
+    /// Bounds the memory used by `ORDER BY` to approximately `sort_memory_budget` bytes.
+    ///
+    /// Above the budget, the solutions are sorted in runs spilled to temporary files and merged while iterating the results.
+    /// The results are the same as with an in-memory sort
+    /// and the temporary files are removed when the results iterator is dropped.
+    ///
+    /// ```
+    /// use oxigraph::model::Literal;
+    /// use oxigraph::sparql::{QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    ///
+    /// if let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+    ///     .with_sort_memory_budget(1)
+    ///     .parse_query("SELECT ?v WHERE { VALUES ?v { 3 1 2 } } ORDER BY ?v")?
+    ///     .on_store(&Store::new()?)
+    ///     .execute()?
+    /// {
+    ///     let values = solutions
+    ///         .map(|s| Ok(s?.get("v").cloned()))
+    ///         .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+    ///     assert_eq!(values, [1, 2, 3].map(|v| Some(Literal::from(v).into())));
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_sort_memory_budget(mut self, sort_memory_budget: usize) -> Self {
+        self.inner = self.inner.with_sort_memory_budget(sort_memory_budget);
+        self.use_results_cache = false;
+        self
+    }
+
+    /// Sets how the resources returned by `DESCRIBE` queries are described.
+    ///
+    /// Defaults to the [Concise Bounded Description](DescribeStrategy::Cbd).
+    ///
+    /// ```
+    /// use oxigraph::io::RdfFormat;
+    /// use oxigraph::sparql::{DescribeStrategy, QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    ///
+    /// let store = Store::new()?;
+    /// store.load_from_slice(
+    ///     RdfFormat::Turtle,
+    ///     "<http://example.com/a> <http://example.com/p> [ <http://example.com/p> [ <http://example.com/p> 1 ] ] .",
+    /// )?;
+    /// if let QueryResults::Graph(triples) = SparqlEvaluator::new()
+    ///     .with_describe_strategy(DescribeStrategy::BlankNodeClosure { max_depth: 1 })
+    ///     .parse_query("DESCRIBE <http://example.com/a>")?
+    ///     .on_store(&store)
+    ///     .execute()?
+    /// {
+    ///     assert_eq!(triples.count(), 2);
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_describe_strategy(mut self, describe_strategy: DescribeStrategy) -> Self {
+        self.inner = self.inner.with_describe_strategy(describe_strategy);
+        self
+    }
+
+    /// Caches up to `capacity` parsed queries and query plans
+    /// so that executing again the same query skips its parsing and optimization.
+    ///
+    /// Queries only differing by their whitespaces and comments share the same cache entry.
+    /// The cache is shared between the clones of this evaluator.
+    /// When it is full, the least recently used query is evicted.
+    ///
+    /// ```
+    /// use oxigraph::sparql::{QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    ///
+    /// let store = Store::new()?;
+    /// let evaluator = SparqlEvaluator::new().with_plan_cache(100);
+    /// for _ in 0..10 {
+    ///     if let QueryResults::Solutions(solutions) = evaluator
+    ///         .clone()
+    ///         .parse_query("SELECT * WHERE { ?s ?p ?o }")?
+    ///         .on_store(&store)
+    ///         .execute()?
+    ///     {
+    ///         assert_eq!(solutions.count(), 0);
+    ///     }
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_plan_cache(mut self, capacity: usize) -> Self {
+        self.query_cache = Some(QueryCache::new(capacity));
+        self.inner = self.inner.with_plan_cache(capacity);
         self
     }
 
+    /// The parsing of the cached queries depends on the parser configuration.
+    fn reset_query_cache(&mut self) {
+        if let Some(query_cache) = &self.query_cache {
+            self.query_cache = Some(QueryCache::new(query_cache.capacity()));
+        }
+    }
+
     #[cfg_attr(not(feature = "http-client"), expect(unused_mut))]
     fn into_evaluator(mut self) -> QueryEvaluator {
         #[cfg(feature = "http-client")]
@@ -388,7 +685,12 @@ impl SparqlEvaluator {
         mut self,
         query: &(impl AsRef<str> + ?Sized),
     ) -> Result<PreparedSparqlQuery, SparqlSyntaxError> {
-        let query = take(&mut self.parser).parse_query(query.as_ref())?;
+        let parser = take(&mut self.parser);
+        let query = if let Some(query_cache) = &self.query_cache {
+            query_cache.get_or_parse_with(query.as_ref(), |query| parser.parse_query(query))?
+        } else {
+            parser.parse_query(query.as_ref())?
+        };
         Ok(self.for_query(query))
     }
 
@@ -423,6 +725,7 @@ impl SparqlEvaluator {
         PreparedSparqlQuery {
             dataset: query.dataset,
             query: query.inner,
+            use_results_cache: self.use_results_cache,
             evaluator: self.into_evaluator(),
             substitutions: HashMap::new(),
         }
@@ -495,6 +798,8 @@ impl Default for SparqlEvaluator {
             #[cfg(feature = "http-client")]
             with_http_default_service_handler: true,
             parser: SparqlParser::new(),
+            query_cache: None,
+            use_results_cache: true,
             inner: QueryEvaluator::new(),
         }
     }
@@ -531,6 +836,7 @@ pub struct PreparedSparqlQuery {
     query: spargebra::Query,
     dataset: QueryDatasetSpecification,
     substitutions: HashMap<Variable, Term>,
+    use_results_cache: bool,
 }
 
 impl PreparedSparqlQuery {
@@ -578,21 +884,71 @@ impl PreparedSparqlQuery {
         &mut self.dataset
     }
 
+    /// Replaces the [query dataset specification](https://www.w3.org/TR/sparql11-query/#specifyingDataset) of this prepared query.
+    ///
+    /// It takes precedence over the `FROM` and `FROM NAMED` clauses of the query.
+    /// It allows to run the same parsed query against different graphs of the store.
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::sparql::{QueryDataset, QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    ///
+    /// let ex = NamedNode::new("http://example.com/s")?;
+    /// let tenant = NamedNode::new("http://example.com/tenant1")?;
+    /// let store = Store::new()?;
+    /// store.insert(QuadRef::new(&ex, &ex, &ex, &tenant))?;
+    ///
+    /// let mut dataset = QueryDataset::new();
+    /// dataset.set_default_graph(vec![tenant.into()]);
+    /// if let QueryResults::Solutions(mut solutions) = SparqlEvaluator::new()
+    ///     .parse_query("SELECT ?s WHERE { ?s ?p ?o }")?
+    ///     .with_dataset(dataset)
+    ///     .on_store(&store)
+    ///     .execute()?
+    /// {
+    ///     assert_eq!(solutions.next().unwrap()?.get("s"), Some(&ex.into()));
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_dataset(mut self, dataset: QueryDataset) -> Self {
+        self.dataset = dataset;
+        self
+    }
+
     /// Bind the prepared query to the [`Store`] it should be evaluated on.
     pub fn on_store(self, store: &Store) -> BoundPreparedSparqlQuery<'static> {
+        // The generation is read first so that the snapshot contains at least all the counted writes
+        let generation = store.storage().generation();
         let reader = store.storage().snapshot();
         let queryable_dataset = DatasetView::new(reader);
-        self.on_queryable_dataset(queryable_dataset)
+        let use_results_cache = self.use_results_cache;
+        let mut bound = self.on_queryable_dataset(queryable_dataset);
+        bound.metrics = store.storage().metrics().cloned();
+        bound.results_cache = store
+            .storage()
+            .query_results_cache()
+            .filter(|_| use_results_cache)
+            .map(|cache| (cache.clone(), generation));
+        bound
     }
 
     /// Bind the prepared query to the [`Transaction`] it should be evaluated on.
+    ///
+    /// The query sees the transaction's own uncommitted writes
+    /// and is isolated from the transactions committed after the transaction started.
+    /// It allows to atomically update the store based on query results.
     pub fn on_transaction<'b>(
         self,
         transaction: &'b Transaction<'_>,
     ) -> BoundPreparedSparqlQuery<'b> {
         let reader = transaction.inner().reader();
         let dataset = DatasetView::new(reader);
-        self.on_queryable_dataset(dataset)
+        let mut bound = self.on_queryable_dataset(dataset);
+        bound.metrics = transaction.inner().metrics().cloned();
+        bound
     }
 
     /// Bind the prepared query to the [`QueryableDataset`] it should be evaluated on.
@@ -606,6 +962,8 @@ impl PreparedSparqlQuery {
             queryable_dataset,
             substitutions: self.substitutions,
             dataset: self.dataset,
+            metrics: None,
+            results_cache: None,
             marker: PhantomData,
         }
     }
@@ -640,6 +998,8 @@ pub struct BoundPreparedSparqlQuery<'a, D: QueryableDataset<'a> = DatasetView<'a
     queryable_dataset: D,
     substitutions: HashMap<Variable, Term>,
     dataset: QueryDatasetSpecification,
+    metrics: Option<Arc<dyn MetricsCollector>>,
+    results_cache: Option<(QueryResultsCache, u64)>,
     marker: PhantomData<&'a ()>,
 }
 
@@ -676,13 +1036,83 @@ impl<'a, D: QueryableDataset<'a>> BoundPreparedSparqlQuery<'a, D> {
     }
 
     /// Evaluate the query against the given store.
+    ///
+    /// If the store has a [`MetricsCollector`](crate::metrics::MetricsCollector), the evaluation is reported to it.
+    /// If the store has a query cache (see [`Store::with_query_cache`]), the results are looked up and stored in it.
+    ///
+    /// The errors returned here and while iterating the results are [`QueryEvaluationError`]s:
+    /// match on their variants to tell cancellations, timeouts and storage errors apart.
+    /// Storage errors are wrapped in [`QueryEvaluationError::Dataset`] and can be downcast to [`StorageError`](crate::store::StorageError).
+    /// Syntax errors are returned earlier by [`SparqlEvaluator::parse_query`] as [`SparqlSyntaxError`].
+    ///
+    /// ```
+    /// use oxigraph::model::{GraphNameRef, NamedNodeRef, QuadRef};
+    /// use oxigraph::sparql::{CancellationToken, QueryEvaluationError, QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::{StorageError, Store};
+    ///
+    /// let store = Store::new()?;
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// store.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph))?;
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    /// let results = SparqlEvaluator::new()
+    ///     .with_cancellation_token(token)
+    ///     .parse_query("SELECT * WHERE { ?s ?p ?o }")?
+    ///     .on_store(&store)
+    ///     .execute()
+    ///     .and_then(|results| match results {
+    ///         QueryResults::Solutions(solutions) => solutions.collect::<Result<Vec<_>, _>>(),
+    ///         _ => Ok(Vec::new()),
+    ///     });
+    /// match results {
+    ///     Ok(_) => panic!("the query has been cancelled"),
+    ///     Err(QueryEvaluationError::Cancelled) => (),
+    ///     Err(QueryEvaluationError::Timeout(timeout)) => panic!("timed out after {timeout:?}"),
+    ///     Err(QueryEvaluationError::Dataset(error)) => match error.downcast::<StorageError>() {
+    ///         Ok(error) => panic!("storage error: {error}"),
+    ///         Err(error) => panic!("dataset error: {error}"),
+    ///     },
+    ///     Err(error) => panic!("evaluation error: {error}"),
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
     pub fn execute(self) -> Result<QueryResults<'a>, QueryEvaluationError> {
-        let mut prepared = self.evaluator.prepare(&self.query);
-        for (variable, term) in self.substitutions {
-            prepared = prepared.substitute_variable(variable, term);
+        // The clock is only read if needed because it is not available on all platforms
+        let metrics = self.metrics.map(|metrics| {
+            metrics.query_started();
+            (metrics, Instant::now())
+        });
+        let cache = self.results_cache.and_then(|(cache, generation)| {
+            let key = QueryResultsCacheKey::new(
+                generation,
+                &self.query,
+                &self.dataset,
+                &self.substitutions,
+            )?;
+            Some((cache, key))
+        });
+        let cached = cache.as_ref().and_then(|(cache, key)| cache.get(key));
+        let results = if let Some(cached) = cached {
+            Ok(cached)
+        } else {
+            let mut prepared = self.evaluator.prepare(&self.query);
+            for (variable, term) in self.substitutions {
+                prepared = prepared.substitute_variable(variable, term);
+            }
+            *prepared.dataset_mut() = self.dataset;
+            let results = prepared.execute(self.queryable_dataset);
+            if let Some((cache, key)) = cache {
+                results.map(|results| cache.materialize(key, results))
+            } else {
+                results
+            }
+        };
+        if let Some((metrics, start)) = metrics {
+            measure_query_results(results, metrics, start)
+        } else {
+            results
         }
-        *prepared.dataset_mut() = self.dataset;
-        prepared.execute(self.queryable_dataset)
     }
 
     /// Compute statistics during evaluation and fills them in the explanation tree.
@@ -696,6 +1126,9 @@ impl<'a, D: QueryableDataset<'a>> BoundPreparedSparqlQuery<'a, D> {
     ///
     /// <div class="warning">If you want to compute statistics, you need to exhaust the results iterator before having a look at them.</div>
     ///
+    /// The results are evaluated lazily: only the consumed results are computed.
+    /// The explanation might also be pretty-printed as a tree using its [`Display`](std::fmt::Display) implementation.
+    ///
     /// Usage example serializing the explanation with statistics in JSON:
     /// ```
     /// use oxigraph::sparql::{QueryResults, SparqlEvaluator};
@@ -727,3 +1160,53 @@ impl<'a, D: QueryableDataset<'a>> BoundPreparedSparqlQuery<'a, D> {
         prepared.explain(self.queryable_dataset)
     }
 }
+
+/// A [`Store`] can be used as a [`ServiceHandler`] to evaluate `SERVICE` calls against it without any network request.
+///
+/// The service graph pattern is evaluated on a snapshot of the store taken when the `SERVICE` call is executed.
+///
+/// ```
+/// use oxigraph::model::*;
+/// use oxigraph::sparql::{QueryResults, SparqlEvaluator};
+/// use oxigraph::store::Store;
+///
+/// let ex = NamedNode::new("http://example.com")?;
+/// let remote = Store::new()?;
+/// remote.insert(QuadRef::new(&ex, &ex, &ex, GraphNameRef::DefaultGraph))?;
+///
+/// let local = Store::new()?;
+/// if let QueryResults::Solutions(mut solutions) = SparqlEvaluator::new()
+///     .with_service_handler(NamedNode::new("http://example.com/service")?, remote)
+///     .parse_query("SELECT ?s WHERE { SERVICE <http://example.com/service> { ?s ?p ?o } }")?
+///     .on_store(&local)
+///     .execute()?
+/// {
+///     assert_eq!(solutions.next().unwrap()?.get("s"), Some(&ex.into()));
+/// }
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+impl ServiceHandler for Store {
+    type Error = QueryEvaluationError;
+
+    fn handle(
+        &self,
+        pattern: &GraphPattern,
+        base_iri: Option<&Iri<String>>,
+    ) -> Result<QuerySolutionIter<'static>, QueryEvaluationError> {
+        let query = spargebra::Query::Select {
+            dataset: None,
+            pattern: pattern.clone(),
+            base_iri: base_iri.cloned(),
+        };
+        match SparqlEvaluator::new()
+            .for_query(query)
+            .on_store(self)
+            .execute()?
+        {
+            QueryResults::Solutions(solutions) => Ok(solutions),
+            QueryResults::Boolean(_) | QueryResults::Graph(_) => Err(
+                QueryEvaluationError::Unexpected("SELECT queries should return solutions".into()),
+            ),
+        }
+    }
+}
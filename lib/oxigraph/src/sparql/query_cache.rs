@@ -0,0 +1,175 @@
+use spareval::LruCache;
+use spargebra::{Query, SparqlSyntaxError};
+
+/// A bounded cache of parsed queries keyed on their normalized text.
+///
+/// It is shared between the clones of a [`SparqlEvaluator`](super::SparqlEvaluator).
+/// When it is full, the least recently used query is evicted.
+#[derive(Clone)]
+pub struct QueryCache {
+    queries: LruCache<String, Query>,
+}
+
+impl QueryCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queries: LruCache::new(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.queries.capacity()
+    }
+
+    /// Returns the cached parsing of `query` or parses it with `parse` and caches it.
+    pub fn get_or_parse_with(
+        &self,
+        query: &str,
+        parse: impl FnOnce(&str) -> Result<Query, SparqlSyntaxError>,
+    ) -> Result<Query, SparqlSyntaxError> {
+        if self.capacity() == 0 {
+            return parse(query);
+        }
+        let key = normalize_query(query);
+        if let Some(query) = self.queries.get(&key) {
+            return Ok(query);
+        }
+        // We do not hold the cache lock while parsing so that other queries are not blocked
+        let parsed = parse(query)?;
+        self.queries.insert(key, parsed.clone());
+        Ok(parsed)
+    }
+}
+
+/// Normalizes the query text so that queries only differing by their whitespaces and comments
+/// share the same cache entry.
+///
+/// IRIs and string literals are kept as is.
+fn normalize_query(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut pending_space = false;
+    let mut chars = query.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if c == '#' {
+            // Comment until the end of the line
+            for (_, c) in chars.by_ref() {
+                if c == '\n' || c == '\r' {
+                    break;
+                }
+            }
+            pending_space = true;
+            continue;
+        }
+        if pending_space && !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        pending_space = false;
+        let end = match c {
+            '<' => iri_end(query, start),
+            '"' | '\'' => Some(string_literal_end(query, start, c)),
+            _ => None,
+        };
+        if let Some(end) = end {
+            normalized.push_str(&query[start..end]);
+            while chars.peek().is_some_and(|(i, _)| *i < end) {
+                chars.next();
+            }
+        } else {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+/// Returns the end of the IRI starting at `start` or `None` if `<` is the less than operator.
+fn iri_end(query: &str, start: usize) -> Option<usize> {
+    for (i, c) in query[start + 1..].char_indices() {
+        match c {
+            '>' => return Some(start + 1 + i + 1),
+            '<' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' => return None,
+            _ if c.is_whitespace() => return None,
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Returns the end of the string literal starting at `start` with the `quote` character.
+///
+/// If the literal is not closed, returns the end of the query.
+fn string_literal_end(query: &str, start: usize, quote: char) -> usize {
+    let long_quote = if quote == '"' { "\"\"\"" } else { "'''" };
+    let (delimiter, content_start) = if query[start..].starts_with(long_quote) {
+        (long_quote, start + 3)
+    } else {
+        (&query[start..=start], start + 1)
+    };
+    let mut escaped = false;
+    for (i, c) in query[content_start..].char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if query[content_start + i..].starts_with(delimiter) {
+            return content_start + i + delimiter.len();
+        }
+    }
+    query.len()
+}
+
+#[cfg(test)]
+#[expect(clippy::panic_in_result_fn)]
+mod tests {
+    use super::*;
+    use spargebra::SparqlParser;
+
+    #[test]
+    fn test_normalize_query() {
+        assert_eq!(
+            normalize_query("  SELECT *\n\tWHERE { ?s ?p ?o } # all triples\n"),
+            "SELECT * WHERE { ?s ?p ?o }"
+        );
+        assert_eq!(
+            normalize_query("SELECT * WHERE { ?s <http://example.com/#a> \"a  #b\" }"),
+            "SELECT * WHERE { ?s <http://example.com/#a> \"a  #b\" }"
+        );
+        assert_eq!(
+            normalize_query(
+                "ASK { FILTER(1 <  2 && \"a  b\" = 'c\\'  d' && \"\"\"e \" f\"\"\" != '') }"
+            ),
+            "ASK { FILTER(1 < 2 && \"a  b\" = 'c\\'  d' && \"\"\"e \" f\"\"\" != '') }"
+        );
+    }
+
+    #[test]
+    fn test_query_cache_skips_parsing() -> Result<(), SparqlSyntaxError> {
+        let cache = QueryCache::new(10);
+        let parse = |query: &str| SparqlParser::new().parse_query(query);
+        let first = cache.get_or_parse_with("SELECT * WHERE { ?s ?p ?o }", parse)?;
+        let second = cache.get_or_parse_with("SELECT *\nWHERE {\n  ?s ?p ?o\n}", |_| {
+            unreachable!("the query should be cached")
+        })?;
+        assert_eq!(first, second);
+        // A different literal is a different query
+        cache.get_or_parse_with("SELECT * WHERE { ?s ?p \"a  b\" }", parse)?;
+        let mut parsed = false;
+        cache.get_or_parse_with("SELECT * WHERE { ?s ?p \"a b\" }", |query| {
+            parsed = true;
+            parse(query)
+        })?;
+        assert!(parsed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_cache_does_not_cache_errors() {
+        let cache = QueryCache::new(10);
+        let parse = |query: &str| SparqlParser::new().parse_query(query);
+        cache.get_or_parse_with("SELECT", parse).unwrap_err();
+        cache.get_or_parse_with("SELECT", parse).unwrap_err();
+    }
+}
@@ -0,0 +1,341 @@
+use crate::model::Term;
+use oxrdf::Variable;
+use spareval::{
+    LruCache, QueryDatasetSpecification, QueryResults, QuerySolution, QuerySolutionIter,
+};
+use spargebra::algebra::{
+    AggregateExpression, Expression, Function, GraphPattern, OrderExpression,
+};
+use std::collections::HashMap;
+use std::iter::once;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Maximal number of solutions of a query for its results to be cached
+pub const MAX_CACHED_SOLUTIONS: usize = 10_000;
+
+/// A bounded cache of materialized `SELECT` and `ASK` query results.
+///
+/// The entries are bound to a generation of the store, i.e. a counter incremented on each write.
+/// When a query is evaluated on a newer generation all the entries are dropped.
+/// When it is full, the least recently used results are evicted.
+#[derive(Clone)]
+pub struct QueryResultsCache {
+    results: LruCache<QueryResultsCacheKey, CachedResults>,
+    /// The most recent generation seen by the cache
+    generation: Arc<AtomicU64>,
+}
+
+#[derive(Eq, PartialEq, Hash, Clone)]
+pub struct QueryResultsCacheKey {
+    generation: u64,
+    query: spargebra::Query,
+    dataset: QueryDatasetSpecification,
+    substitutions: Vec<(Variable, Term)>,
+}
+
+impl QueryResultsCacheKey {
+    /// Returns `None` if the query results should not be cached
+    pub fn new(
+        generation: u64,
+        query: &spargebra::Query,
+        dataset: &QueryDatasetSpecification,
+        substitutions: &HashMap<Variable, Term>,
+    ) -> Option<Self> {
+        match query {
+            spargebra::Query::Select { pattern, .. } | spargebra::Query::Ask { pattern, .. } => {
+                if !is_deterministic(pattern) {
+                    return None;
+                }
+            }
+            // We do not want to cache the blank nodes generated by CONSTRUCT
+            spargebra::Query::Construct { .. } | spargebra::Query::Describe { .. } => return None,
+        }
+        let mut substitutions = substitutions
+            .iter()
+            .map(|(v, t)| (v.clone(), t.clone()))
+            .collect::<Vec<_>>();
+        substitutions.sort_unstable_by(|(v1, _), (v2, _)| v1.as_str().cmp(v2.as_str()));
+        Some(Self {
+            generation,
+            query: query.clone(),
+            dataset: dataset.clone(),
+            substitutions,
+        })
+    }
+}
+
+/// Checks that the pattern returns the same results when evaluated twice on the same data.
+///
+/// `SERVICE` calls and the `NOW`, `RAND`, `UUID`, `STRUUID` and `BNODE` functions are not deterministic.
+fn is_deterministic(pattern: &GraphPattern) -> bool {
+    match pattern {
+        GraphPattern::Bgp { .. } | GraphPattern::Path { .. } | GraphPattern::Values { .. } => true,
+        GraphPattern::Join { left, right }
+        | GraphPattern::Lateral { left, right }
+        | GraphPattern::Union { left, right }
+        | GraphPattern::Minus { left, right } => is_deterministic(left) && is_deterministic(right),
+        GraphPattern::LeftJoin {
+            left,
+            right,
+            expression,
+        } => {
+            is_deterministic(left)
+                && is_deterministic(right)
+                && expression.as_ref().is_none_or(is_deterministic_expression)
+        }
+        GraphPattern::Filter { expr, inner }
+        | GraphPattern::Extend {
+            inner,
+            expression: expr,
+            ..
+        } => is_deterministic_expression(expr) && is_deterministic(inner),
+        GraphPattern::OrderBy { inner, expression } => {
+            expression.iter().all(|e| match e {
+                OrderExpression::Asc(e) | OrderExpression::Desc(e) => {
+                    is_deterministic_expression(e)
+                }
+            }) && is_deterministic(inner)
+        }
+        GraphPattern::Graph { inner, .. }
+        | GraphPattern::Project { inner, .. }
+        | GraphPattern::Distinct { inner }
+        | GraphPattern::Reduced { inner }
+        | GraphPattern::Slice { inner, .. } => is_deterministic(inner),
+        GraphPattern::Group {
+            inner, aggregates, ..
+        } => {
+            aggregates.iter().all(|(_, a)| match a {
+                AggregateExpression::CountSolutions { .. } => true,
+                AggregateExpression::FunctionCall { expr, .. } => is_deterministic_expression(expr),
+            }) && is_deterministic(inner)
+        }
+        GraphPattern::Service { .. } => false,
+    }
+}
+
+fn is_deterministic_expression(expression: &Expression) -> bool {
+    match expression {
+        Expression::NamedNode(_)
+        | Expression::Literal(_)
+        | Expression::Variable(_)
+        | Expression::Bound(_) => true,
+        Expression::UnaryPlus(e) | Expression::UnaryMinus(e) | Expression::Not(e) => {
+            is_deterministic_expression(e)
+        }
+        Expression::Or(a, b)
+        | Expression::And(a, b)
+        | Expression::Equal(a, b)
+        | Expression::SameTerm(a, b)
+        | Expression::Greater(a, b)
+        | Expression::GreaterOrEqual(a, b)
+        | Expression::Less(a, b)
+        | Expression::LessOrEqual(a, b)
+        | Expression::Add(a, b)
+        | Expression::Subtract(a, b)
+        | Expression::Multiply(a, b)
+        | Expression::Divide(a, b) => {
+            is_deterministic_expression(a) && is_deterministic_expression(b)
+        }
+        Expression::In(a, b) => {
+            is_deterministic_expression(a) && b.iter().all(is_deterministic_expression)
+        }
+        Expression::Exists(pattern) => is_deterministic(pattern),
+        Expression::If(a, b, c) => {
+            is_deterministic_expression(a)
+                && is_deterministic_expression(b)
+                && is_deterministic_expression(c)
+        }
+        Expression::Coalesce(parameters) => parameters.iter().all(is_deterministic_expression),
+        Expression::FunctionCall(function, parameters) => {
+            !matches!(
+                function,
+                Function::Now
+                    | Function::Rand
+                    | Function::Uuid
+                    | Function::StrUuid
+                    | Function::BNode
+            ) && parameters.iter().all(is_deterministic_expression)
+        }
+    }
+}
+
+#[derive(Clone)]
+enum CachedResults {
+    Solutions {
+        variables: Arc<[Variable]>,
+        solutions: Arc<[Vec<Option<Term>>]>,
+    },
+    Boolean(bool),
+}
+
+impl CachedResults {
+    fn to_results(&self) -> QueryResults<'static> {
+        match self {
+            Self::Solutions {
+                variables,
+                solutions,
+            } => {
+                let variables = Arc::clone(variables);
+                let solutions = Arc::clone(solutions);
+                QuerySolutionIter::new(
+                    Arc::clone(&variables),
+                    (0..solutions.len()).map(move |i| {
+                        Ok(QuerySolution::from((
+                            Arc::clone(&variables),
+                            solutions[i].clone(),
+                        )))
+                    }),
+                )
+                .into()
+            }
+            Self::Boolean(value) => (*value).into(),
+        }
+    }
+}
+
+impl QueryResultsCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            results: LruCache::new(capacity),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns the cached results for the given key if any
+    pub fn get(&self, key: &QueryResultsCacheKey) -> Option<QueryResults<'static>> {
+        self.clear_if_outdated(key.generation);
+        Some(self.results.get(key)?.to_results())
+    }
+
+    /// Materializes the results and caches them if they are small enough.
+    ///
+    /// The returned results are equivalent to the given ones, even if they are not cached.
+    pub fn materialize<'a>(
+        &self,
+        key: QueryResultsCacheKey,
+        results: QueryResults<'a>,
+    ) -> QueryResults<'a> {
+        let results = match results {
+            QueryResults::Solutions(mut iter) => {
+                let variables = Arc::<[Variable]>::from(iter.variables());
+                let mut solutions = Vec::new();
+                loop {
+                    match iter.next() {
+                        Some(Ok(solution)) => {
+                            solutions.push(solution);
+                            if solutions.len() > MAX_CACHED_SOLUTIONS {
+                                // Too big, we stream the remaining solutions
+                                return QuerySolutionIter::new(
+                                    variables,
+                                    solutions.into_iter().map(Ok).chain(iter),
+                                )
+                                .into();
+                            }
+                        }
+                        Some(Err(error)) => {
+                            // We do not cache errors
+                            return QuerySolutionIter::new(
+                                variables,
+                                solutions
+                                    .into_iter()
+                                    .map(Ok)
+                                    .chain(once(Err(error)))
+                                    .chain(iter),
+                            )
+                            .into();
+                        }
+                        None => break,
+                    }
+                }
+                CachedResults::Solutions {
+                    variables,
+                    solutions: solutions
+                        .into_iter()
+                        .map(|solution| solution.values().to_vec())
+                        .collect(),
+                }
+            }
+            QueryResults::Boolean(value) => CachedResults::Boolean(value),
+            QueryResults::Graph(iter) => return QueryResults::Graph(iter),
+        };
+        let returned = results.to_results();
+        self.insert(key, results);
+        returned
+    }
+
+    fn insert(&self, key: QueryResultsCacheKey, results: CachedResults) {
+        self.clear_if_outdated(key.generation);
+        if key.generation < self.generation.load(Ordering::Acquire) {
+            return; // The results are already outdated
+        }
+        self.results.insert(key, results);
+    }
+
+    fn clear_if_outdated(&self, generation: u64) {
+        if self.generation.fetch_max(generation, Ordering::AcqRel) < generation {
+            self.results.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::panic_in_result_fn)]
+mod tests {
+    use super::*;
+    use spargebra::SparqlParser;
+    use std::error::Error;
+
+    fn key(generation: u64, query: &str) -> Result<Option<QueryResultsCacheKey>, Box<dyn Error>> {
+        Ok(QueryResultsCacheKey::new(
+            generation,
+            &SparqlParser::new().parse_query(query)?,
+            &QueryDatasetSpecification::new(),
+            &HashMap::new(),
+        ))
+    }
+
+    #[test]
+    fn test_non_deterministic_queries_are_not_cached() -> Result<(), Box<dyn Error>> {
+        for query in [
+            "SELECT (RAND() AS ?r) WHERE {}",
+            "ASK { FILTER EXISTS { BIND(NOW() AS ?n) } }",
+            "SELECT (SAMPLE(UUID()) AS ?u) WHERE {}",
+            "SELECT ?s WHERE { ?s ?p ?o } ORDER BY (STRUUID())",
+            "SELECT ?s WHERE { ?s ?p ?o OPTIONAL { ?o ?p2 ?o2 FILTER(?o2 != BNODE()) } }",
+            "SELECT * WHERE { SERVICE <http://example.com/> { ?s ?p ?o } }",
+            "CONSTRUCT WHERE { ?s ?p ?o }",
+        ] {
+            assert!(key(0, query)?.is_none(), "{query} should not be cached");
+        }
+        // Only the function calls matter, not the IRIs or literals
+        for query in [
+            "SELECT ?s WHERE { ?s <http://example.com/RAND()> \"NOW()\" }",
+            "ASK { <http://example.com/SERVICE> ?p ?o }",
+        ] {
+            assert!(key(0, query)?.is_some(), "{query} should be cached");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_results_cache_generations() -> Result<(), Box<dyn Error>> {
+        let cache = QueryResultsCache::new(2);
+        let key = |generation| -> Result<_, Box<dyn Error>> {
+            Ok(key(generation, "ASK { ?s ?p ?o }")?.ok_or("the query should be cacheable")?)
+        };
+        assert!(cache.get(&key(0)?).is_none());
+        cache.materialize(key(0)?, true.into());
+        assert!(matches!(
+            cache.get(&key(0)?),
+            Some(QueryResults::Boolean(true))
+        ));
+        // A newer generation drops the entries
+        assert!(cache.get(&key(1)?).is_none());
+        assert!(cache.get(&key(0)?).is_none());
+        // Outdated results are not cached
+        cache.materialize(key(0)?, true.into());
+        assert!(cache.get(&key(0)?).is_none());
+        Ok(())
+    }
+}
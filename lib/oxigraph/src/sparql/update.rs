@@ -1,18 +1,19 @@
 #[cfg(feature = "http-client")]
 use crate::io::{RdfFormat, RdfParser};
-use crate::model::{GraphName as OxGraphName, GraphNameRef, Quad as OxQuad};
+use crate::model::{GraphName as OxGraphName, GraphNameRef, Quad as OxQuad, QuadRef};
 #[expect(deprecated)]
 use crate::sparql::algebra::Update;
 use crate::sparql::dataset::DatasetView;
 use crate::sparql::error::UpdateEvaluationError;
 #[cfg(feature = "http-client")]
 use crate::sparql::http::Client;
+use crate::storage::numeric_encoder::{Decoder, EncodedQuad, EncodedTerm};
 use crate::storage::{Storage, StorageError, StorageReadableTransaction, StorageTransaction};
 use crate::store::{Store, Transaction};
 use oxiri::Iri;
 #[cfg(feature = "http-client")]
 use oxrdfio::LoadedDocument;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use spareval::{DeleteInsertQuad, QueryDatasetSpecification, QueryEvaluator};
 use spargebra::GraphUpdateOperation;
 use spargebra::algebra::{GraphPattern, GraphTarget};
@@ -122,6 +123,9 @@ impl PreparedSparqlUpdate {
 
     /// Bind the prepared update to the [`Transaction`] it should be evaluated on.
     ///
+    /// The update `WHERE` clauses see the transaction's own uncommitted writes.
+    /// The changes are only applied to the store when the transaction is committed.
+    ///
     /// Usage example:
     /// ```
     /// use oxigraph::sparql::SparqlEvaluator;
@@ -192,6 +196,7 @@ impl BoundPreparedSparqlUpdate<'_, '_> {
                     query_evaluator: self.evaluator,
                     #[cfg(feature = "http-client")]
                     client: Client::new(self.http_timeout, self.http_redirection_limit),
+                    changes: None,
                 }
                 .eval_all(&self.update.operations, &self.using_datasets)?;
                 transaction.commit()?;
@@ -203,6 +208,7 @@ impl BoundPreparedSparqlUpdate<'_, '_> {
                 query_evaluator: self.evaluator,
                 #[cfg(feature = "http-client")]
                 client: Client::new(self.http_timeout, self.http_redirection_limit),
+                changes: None,
             }
             .eval_all(&self.update.operations, &self.using_datasets),
             UpdateTransaction::Owned(mut transaction, storage) => {
@@ -220,6 +226,122 @@ impl BoundPreparedSparqlUpdate<'_, '_> {
             }
         }
     }
+
+    /// Evaluates the update without modifying the store and returns the quads it would insert and delete.
+    ///
+    /// The `WHERE` clauses are evaluated exactly like in [`execute`](Self::execute).
+    /// Only the net changes are returned: a quad inserted then deleted by the same update is not part of the diff.
+    /// Blank nodes created by the update get different identifiers each time it is evaluated.
+    ///
+    /// Previews are not supported on updates bound to a [`Transaction`] with [`PreparedSparqlUpdate::on_transaction`].
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::sparql::SparqlEvaluator;
+    /// use oxigraph::store::Store;
+    ///
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// let store = Store::new()?;
+    /// store.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph))?;
+    ///
+    /// let diff = SparqlEvaluator::new()
+    ///     .parse_update("DELETE WHERE { ?s ?p ?o }")?
+    ///     .on_store(&store)
+    ///     .preview()?;
+    /// assert!(diff.inserted.is_empty());
+    /// assert_eq!(diff.deleted, [Quad::new(ex, ex, ex, GraphName::DefaultGraph)]);
+    /// // The store is left untouched
+    /// assert_eq!(store.len()?, 1);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn preview(self) -> Result<UpdateDiff, UpdateEvaluationError> {
+        let mut transaction = match self.transaction? {
+            UpdateTransaction::OwnedReadable(transaction) => transaction,
+            UpdateTransaction::Owned(transaction, storage) => {
+                // We need to read the update changes
+                drop(transaction);
+                storage.start_readable_transaction()?
+            }
+            UpdateTransaction::BorrowedReadable(_) => {
+                return Err(UpdateEvaluationError::Unexpected(
+                    "Update previews are not supported inside transactions".into(),
+                ));
+            }
+        };
+        let mut evaluator = ReadableUpdateEvaluator {
+            transaction: &mut transaction,
+            base_iri: self.update.base_iri.clone(),
+            query_evaluator: self.evaluator,
+            #[cfg(feature = "http-client")]
+            client: Client::new(self.http_timeout, self.http_redirection_limit),
+            changes: Some(UpdateChanges::default()),
+        };
+        evaluator.eval_all(&self.update.operations, &self.using_datasets)?;
+        let changes = evaluator.changes.take().unwrap_or_default();
+        let reader = transaction.reader();
+        let mut diff = UpdateDiff::default();
+        for quad in changes.quads {
+            let was_present = changes.initially_present.contains(&quad);
+            if was_present != reader.contains(&quad)? {
+                let decoded = reader.decode_quad(&quad)?;
+                if was_present {
+                    diff.deleted.push(decoded);
+                } else {
+                    diff.inserted.push(decoded);
+                }
+            }
+        }
+        transaction.rollback();
+        Ok(diff)
+    }
+}
+
+/// The changes a SPARQL update would do, returned by [`BoundPreparedSparqlUpdate::preview`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateDiff {
+    /// The quads that would be inserted into the store.
+    pub inserted: Vec<OxQuad>,
+    /// The quads that would be deleted from the store.
+    pub deleted: Vec<OxQuad>,
+}
+
+/// The quads touched by an update, in the order they are first touched, and their state before it.
+#[derive(Default)]
+struct UpdateChanges {
+    quads: Vec<EncodedQuad>,
+    seen: FxHashSet<EncodedQuad>,
+    initially_present: FxHashSet<EncodedQuad>,
+}
+
+impl UpdateChanges {
+    fn record(
+        &mut self,
+        quad: EncodedQuad,
+        is_present: impl FnOnce(&EncodedQuad) -> Result<bool, StorageError>,
+    ) -> Result<(), StorageError> {
+        if self.seen.contains(&quad) {
+            return Ok(());
+        }
+        if is_present(&quad)? {
+            self.initially_present.insert(quad.clone());
+        }
+        self.seen.insert(quad.clone());
+        self.quads.push(quad);
+        Ok(())
+    }
+}
+
+/// Records the state of a quad before it is modified if the changes are recorded.
+fn record_change(
+    changes: &mut Option<UpdateChanges>,
+    transaction: &StorageReadableTransaction<'_>,
+    quad: QuadRef<'_>,
+) -> Result<(), StorageError> {
+    let Some(changes) = changes else {
+        return Ok(());
+    };
+    changes.record(quad.into(), |quad| transaction.reader().contains(quad))
 }
 
 enum UpdateTransaction<'a, 'b> {
@@ -234,9 +356,49 @@ struct ReadableUpdateEvaluator<'a, 'b> {
     query_evaluator: QueryEvaluator,
     #[cfg(feature = "http-client")]
     client: Client,
+    /// The quads possibly changed by the update, only recorded for previews
+    changes: Option<UpdateChanges>,
 }
 
 impl<'a, 'b: 'a> ReadableUpdateEvaluator<'a, 'b> {
+    fn insert(&mut self, quad: QuadRef<'_>) -> Result<(), StorageError> {
+        record_change(&mut self.changes, self.transaction, quad)?;
+        self.transaction.insert(quad);
+        Ok(())
+    }
+
+    fn remove(&mut self, quad: QuadRef<'_>) -> Result<(), StorageError> {
+        record_change(&mut self.changes, self.transaction, quad)?;
+        self.transaction.remove(quad);
+        Ok(())
+    }
+
+    /// Records the quads of the target graphs before they are removed.
+    fn record_graph_removal(&mut self, graph: &GraphTarget) -> Result<(), StorageError> {
+        let Some(changes) = &mut self.changes else {
+            return Ok(());
+        };
+        let (graph_name, named_graphs_only) = match graph {
+            GraphTarget::NamedNode(graph_name) => {
+                (Some(EncodedTerm::from(graph_name.as_ref())), false)
+            }
+            GraphTarget::DefaultGraph => (Some(EncodedTerm::DefaultGraph), false),
+            GraphTarget::NamedGraphs => (None, true),
+            GraphTarget::AllGraphs => (None, false),
+        };
+        for quad in
+            self.transaction
+                .reader()
+                .quads_for_pattern(None, None, None, graph_name.as_ref())
+        {
+            let quad = quad?;
+            if !named_graphs_only || !quad.graph_name.is_default_graph() {
+                changes.record(quad, |_| Ok(true))?;
+            }
+        }
+        Ok(())
+    }
+
     fn eval_all(
         &mut self,
         updates: &[GraphUpdateOperation],
@@ -254,14 +416,8 @@ impl<'a, 'b: 'a> ReadableUpdateEvaluator<'a, 'b> {
         using_dataset: &Option<QueryDatasetSpecification>,
     ) -> Result<(), UpdateEvaluationError> {
         match update {
-            GraphUpdateOperation::InsertData { data } => {
-                self.eval_insert_data(data);
-                Ok(())
-            }
-            GraphUpdateOperation::DeleteData { data } => {
-                self.eval_delete_data(data);
-                Ok(())
-            }
+            GraphUpdateOperation::InsertData { data } => Ok(self.eval_insert_data(data)?),
+            GraphUpdateOperation::DeleteData { data } => Ok(self.eval_delete_data(data)?),
             GraphUpdateOperation::DeleteInsert {
                 delete,
                 insert,
@@ -292,19 +448,21 @@ impl<'a, 'b: 'a> ReadableUpdateEvaluator<'a, 'b> {
         }
     }
 
-    fn eval_insert_data(&mut self, data: &[Quad]) {
+    fn eval_insert_data(&mut self, data: &[Quad]) -> Result<(), StorageError> {
         let mut bnodes = FxHashMap::default();
         for quad in data {
             let quad = convert_quad(quad, &mut bnodes);
-            self.transaction.insert(quad.as_ref());
+            self.insert(quad.as_ref())?;
         }
+        Ok(())
     }
 
-    fn eval_delete_data(&mut self, data: &[GroundQuad]) {
+    fn eval_delete_data(&mut self, data: &[GroundQuad]) -> Result<(), StorageError> {
         for quad in data {
             let quad = convert_ground_quad(quad);
-            self.transaction.remove(quad.as_ref());
+            self.remove(quad.as_ref())?;
         }
+        Ok(())
     }
 
     fn eval_delete_insert(
@@ -327,21 +485,31 @@ impl<'a, 'b: 'a> ReadableUpdateEvaluator<'a, 'b> {
             .collect::<Result<Vec<_>, _>>()?;
         for mutation in mutations {
             match mutation {
-                DeleteInsertQuad::Delete(quad) => self.transaction.remove(quad.as_ref()),
-                DeleteInsertQuad::Insert(quad) => self.transaction.insert(quad.as_ref()),
+                DeleteInsertQuad::Delete(quad) => self.remove(quad.as_ref())?,
+                DeleteInsertQuad::Insert(quad) => self.insert(quad.as_ref())?,
             }
         }
         Ok(())
     }
 
     fn eval_load(&mut self, from: &NamedNode, to: &GraphName) -> Result<(), UpdateEvaluationError> {
+        let mut record_error = None;
         eval_load(
             from,
             to,
             #[cfg(feature = "http-client")]
             &self.client,
-            |q| self.transaction.insert(q.as_ref()),
-        )
+            |q| {
+                if let Err(error) = record_change(&mut self.changes, self.transaction, q.as_ref()) {
+                    record_error.get_or_insert(error);
+                }
+                self.transaction.insert(q.as_ref());
+            },
+        )?;
+        match record_error {
+            Some(error) => Err(error.into()),
+            None => Ok(()),
+        }
     }
 
     fn eval_create(
@@ -372,6 +540,7 @@ impl<'a, 'b: 'a> ReadableUpdateEvaluator<'a, 'b> {
         graph: &GraphTarget,
         silent: bool,
     ) -> Result<(), UpdateEvaluationError> {
+        self.record_graph_removal(graph)?;
         match graph {
             GraphTarget::NamedNode(graph_name) => {
                 if self
@@ -400,6 +569,7 @@ impl<'a, 'b: 'a> ReadableUpdateEvaluator<'a, 'b> {
         graph: &GraphTarget,
         silent: bool,
     ) -> Result<(), UpdateEvaluationError> {
+        self.record_graph_removal(graph)?;
         match graph {
             GraphTarget::NamedNode(graph_name) => {
                 if self
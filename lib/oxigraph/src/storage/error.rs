@@ -2,6 +2,7 @@ use crate::io::{RdfFormat, RdfParseError};
 use oxiri::IriParseError;
 use std::error::Error;
 use std::io;
+use std::time::Duration;
 
 /// An error related to storage operations (reads, writes...).
 #[derive(Debug, thiserror::Error)]
@@ -13,17 +14,70 @@ pub enum StorageError {
     /// Error related to data corruption.
     #[error(transparent)]
     Corruption(#[from] CorruptionError),
+    /// The database has been written with an other storage format version.
+    ///
+    /// Older databases are upgraded when opened read-write, with `Store::migrate`
+    /// or with the `oxigraph migrate` command.
+    #[error("The database is using the storage version {found} but version {expected} is expected")]
+    IncompatibleVersion {
+        /// The version stored in the database.
+        found: u64,
+        /// The version supported by this Oxigraph version.
+        expected: u64,
+    },
+    /// The database has been opened with [`Store::open_read_only`](crate::store::Store::open_read_only) and can't be modified.
+    #[error("The database has been opened read-only and can't be modified")]
+    ReadOnly,
     #[doc(hidden)]
     #[error("{0}")]
     Other(#[source] Box<dyn Error + Send + Sync + 'static>),
 }
 
+impl StorageError {
+    /// Returns `true` if the operation might succeed if retried.
+    ///
+    /// It is the case of conflicts with concurrent transactions and of lock timeouts
+    /// but not of the timeout set with [`BulkLoader::with_timeout`](crate::store::BulkLoader::with_timeout).
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Io(error) if matches!(
+                error.kind(),
+                io::ErrorKind::ResourceBusy
+                    | io::ErrorKind::WouldBlock
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::Deadlock
+                    | io::ErrorKind::Interrupted
+            ) && !error.get_ref().is_some_and(|e| e.is::<BulkLoadTimeout>())
+        )
+    }
+
+    /// The error returned when the timeout set with [`BulkLoader::with_timeout`](crate::store::BulkLoader::with_timeout)
+    /// is exceeded by a method returning [`StorageError`].
+    ///
+    /// It is converted back to [`LoaderError::Timeout`] by the methods returning [`LoaderError`].
+    pub(crate) fn bulk_load_timeout(timeout: Duration) -> Self {
+        Self::Io(io::Error::new(
+            io::ErrorKind::TimedOut,
+            BulkLoadTimeout(timeout),
+        ))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The bulk loading exceeded the timeout limit of {0:?}")]
+struct BulkLoadTimeout(Duration);
+
 impl From<StorageError> for io::Error {
     #[inline]
     fn from(error: StorageError) -> Self {
         match error {
             StorageError::Io(error) => error,
             StorageError::Corruption(error) => error.into(),
+            StorageError::IncompatibleVersion { .. } => {
+                Self::new(io::ErrorKind::InvalidData, error)
+            }
+            StorageError::ReadOnly => Self::new(io::ErrorKind::PermissionDenied, error),
             StorageError::Other(error) => Self::other(error),
         }
     }
@@ -79,7 +133,7 @@ pub enum LoaderError {
     Parsing(#[from] RdfParseError),
     /// An error raised during the insertion in the store.
     #[error(transparent)]
-    Storage(#[from] StorageError),
+    Storage(StorageError),
     /// The base IRI is invalid.
     #[error("Invalid base IRI '{iri}': {error}")]
     InvalidBaseIri {
@@ -89,6 +143,26 @@ pub enum LoaderError {
         #[source]
         error: IriParseError,
     },
+    /// The loading took longer than the timeout set with [`BulkLoader::with_timeout`](crate::store::BulkLoader::with_timeout).
+    #[error("The bulk loading exceeded the timeout limit of {0:?}")]
+    Timeout(Duration),
+    /// A format compatible with [RDF dataset](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset) is required.
+    #[error("A RDF format supporting datasets was expected, {0} found")]
+    DatasetFormatExpected(RdfFormat),
+}
+
+impl From<StorageError> for LoaderError {
+    #[inline]
+    fn from(error: StorageError) -> Self {
+        if let StorageError::Io(io_error) = &error {
+            if let Some(BulkLoadTimeout(timeout)) =
+                io_error.get_ref().and_then(|e| e.downcast_ref())
+            {
+                return Self::Timeout(*timeout);
+            }
+        }
+        Self::Storage(error)
+    }
 }
 
 impl From<LoaderError> for io::Error {
@@ -97,9 +171,10 @@ impl From<LoaderError> for io::Error {
         match error {
             LoaderError::Storage(error) => error.into(),
             LoaderError::Parsing(error) => error.into(),
-            LoaderError::InvalidBaseIri { .. } => {
+            LoaderError::InvalidBaseIri { .. } | LoaderError::DatasetFormatExpected(_) => {
                 Self::new(io::ErrorKind::InvalidInput, error.to_string())
             }
+            LoaderError::Timeout(_) => Self::new(io::ErrorKind::TimedOut, error.to_string()),
         }
     }
 }
@@ -130,3 +205,14 @@ impl From<SerializerError> for io::Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_load_timeout_is_not_transient() {
+        assert!(!StorageError::bulk_load_timeout(Duration::from_secs(1)).is_transient());
+        assert!(StorageError::from(io::Error::from(io::ErrorKind::TimedOut)).is_transient());
+    }
+}
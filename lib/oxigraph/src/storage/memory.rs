@@ -432,7 +432,8 @@ impl MemoryStorageTransaction<'_> {
         }
     }
 
-    pub fn insert(&mut self, quad: QuadRef<'_>) {
+    /// Returns `false` if the quad was already in the store
+    pub fn insert(&mut self, quad: QuadRef<'_>) -> bool {
         let encoded: EncodedQuad = quad.into();
         if let Some(node) = self
             .storage
@@ -456,6 +457,7 @@ impl MemoryStorageTransaction<'_> {
                     self.log.push(LogEntry::Graph(encoded.graph_name.clone()));
                 }
             }
+            added
         } else {
             let node = Arc::new(QuadListNode {
                 quad: encoded.clone(),
@@ -535,6 +537,7 @@ impl MemoryStorageTransaction<'_> {
                 GraphNameRef::DefaultGraph => (),
             }
             self.log.push(LogEntry::QuadNode(node));
+            true
         }
     }
 
@@ -838,9 +841,13 @@ impl MemoryStorageBulkLoader<'_> {
         self
     }
 
-    pub fn load_batch(&mut self, new_quads: Vec<Quad>) {
+    /// Returns the number of quads that were already in the store
+    pub fn load_batch(&mut self, new_quads: Vec<Quad>) -> u64 {
+        let mut existing = 0;
         for quad in new_quads {
-            self.transaction.insert(quad.as_ref());
+            if !self.transaction.insert(quad.as_ref()) {
+                existing += 1;
+            }
             self.done += 1;
             if self.done.is_multiple_of(1_000_000) {
                 for hook in &self.hooks {
@@ -848,6 +855,7 @@ impl MemoryStorageBulkLoader<'_> {
                 }
             }
         }
+        existing
     }
 
     pub fn commit(self) {
@@ -1,4 +1,6 @@
+use crate::metrics::MetricsCollector;
 use crate::model::{GraphNameRef, NamedOrBlankNodeRef, QuadRef};
+use crate::sparql::results_cache::QueryResultsCache;
 pub use crate::storage::error::{CorruptionError, LoaderError, SerializerError, StorageError};
 use crate::storage::memory::{
     MemoryDecodingGraphIterator, MemoryStorage, MemoryStorageBulkLoader, MemoryStorageReader,
@@ -14,6 +16,11 @@ use crate::storage::rocksdb::{
 use oxrdf::Quad;
 #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
 use std::path::Path;
+use std::sync::Arc;
+#[cfg(test)]
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 #[cfg(not(target_family = "wasm"))]
 use std::{io, thread};
 
@@ -34,6 +41,11 @@ pub const DEFAULT_BULK_LOAD_BATCH_SIZE: usize = 1_000_000;
 #[derive(Clone)]
 pub struct Storage {
     kind: StorageKind,
+    #[cfg(test)]
+    active_iterators: ActiveIterators,
+    metrics: Option<Arc<dyn MetricsCollector>>,
+    generation: Generation,
+    query_results_cache: Option<QueryResultsCache>,
 }
 
 #[derive(Clone)]
@@ -48,6 +60,11 @@ impl Storage {
     pub fn new() -> Result<Self, StorageError> {
         Ok(Self {
             kind: StorageKind::Memory(MemoryStorage::new()),
+            #[cfg(test)]
+            active_iterators: ActiveIterators::default(),
+            metrics: None,
+            generation: Generation::default(),
+            query_results_cache: None,
         })
     }
 
@@ -55,16 +72,56 @@ impl Storage {
     pub fn open(path: &Path) -> Result<Self, StorageError> {
         Ok(Self {
             kind: StorageKind::RocksDb(RocksDbStorage::open(path)?),
+            #[cfg(test)]
+            active_iterators: ActiveIterators::default(),
+            metrics: None,
+            generation: Generation::default(),
+            query_results_cache: None,
         })
     }
 
     #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
     pub fn open_read_only(path: &Path) -> Result<Self, StorageError> {
         Ok(Self {
-            kind: StorageKind::RocksDb(RocksDbStorage::open_read_only(path)?),
+            kind: StorageKind::RocksDb(RocksDbStorage::open_secondary(path)?),
+            #[cfg(test)]
+            active_iterators: ActiveIterators::default(),
+            metrics: None,
+            generation: Generation::default(),
+            query_results_cache: None,
         })
     }
 
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    pub fn migrate(path: &Path) -> Result<(), StorageError> {
+        RocksDbStorage::migrate(path)
+    }
+
+    pub fn with_metrics(mut self, metrics: Arc<dyn MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn metrics(&self) -> Option<&Arc<dyn MetricsCollector>> {
+        self.metrics.as_ref()
+    }
+
+    pub fn with_query_results_cache(mut self, cache: QueryResultsCache) -> Self {
+        self.query_results_cache = Some(cache);
+        self
+    }
+
+    pub fn query_results_cache(&self) -> Option<&QueryResultsCache> {
+        self.query_results_cache.as_ref()
+    }
+
+    /// Number of writes done on the storage
+    ///
+    /// It must be read before opening a snapshot: the snapshot then contains at least the writes counted.
+    pub fn generation(&self) -> u64 {
+        self.generation.get()
+    }
+
     pub fn snapshot(&self) -> StorageReader<'static> {
         StorageReader {
             kind: match &self.kind {
@@ -72,6 +129,8 @@ impl Storage {
                 StorageKind::RocksDb(storage) => StorageReaderKind::RocksDb(storage.snapshot()),
                 StorageKind::Memory(storage) => StorageReaderKind::Memory(storage.snapshot()),
             },
+            #[cfg(test)]
+            active_iterators: self.active_iterators.clone(),
         }
     }
 
@@ -90,6 +149,8 @@ impl Storage {
                     StorageTransactionKind::Memory(storage.start_transaction())
                 }
             },
+            metrics: TransactionMetrics::new(self.metrics.clone()),
+            generation: self.generation.clone(),
         })
     }
 
@@ -110,9 +171,19 @@ impl Storage {
                     StorageReadableTransactionKind::Memory(storage.start_transaction())
                 }
             },
+            #[cfg(test)]
+            active_iterators: self.active_iterators.clone(),
+            metrics: TransactionMetrics::new(self.metrics.clone()),
+            generation: self.generation.clone(),
         })
     }
 
+    /// Number of quad and graph iterators currently alive
+    #[cfg(test)]
+    pub fn active_iterators(&self) -> usize {
+        self.active_iterators.count()
+    }
+
     #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
     pub fn flush(&self) -> Result<(), StorageError> {
         match &self.kind {
@@ -131,6 +202,27 @@ impl Storage {
         }
     }
 
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    pub fn on_disk_size(&self) -> Result<u64, StorageError> {
+        match &self.kind {
+            #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+            StorageKind::RocksDb(storage) => storage.on_disk_size(),
+            StorageKind::Memory(_) => Ok(0),
+        }
+    }
+
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    pub fn catch_up_with_primary(&self) -> Result<(), StorageError> {
+        match &self.kind {
+            #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+            StorageKind::RocksDb(storage) => storage.catch_up_with_primary()?,
+            StorageKind::Memory(_) => (),
+        }
+        // The writes of the primary instance might now be visible
+        self.generation.increment();
+        Ok(())
+    }
+
     #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
     pub fn backup(&self, target_directory: &Path) -> Result<(), StorageError> {
         match &self.kind {
@@ -142,15 +234,34 @@ impl Storage {
         }
     }
 
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    pub fn restore(backup_directory: &Path, target_directory: &Path) -> Result<(), StorageError> {
+        RocksDbStorage::restore(backup_directory, target_directory)
+    }
+
     pub fn bulk_loader(&self) -> StorageBulkLoader<'_> {
-        match &self.kind {
-            #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
-            StorageKind::RocksDb(storage) => StorageBulkLoader {
-                kind: StorageBulkLoaderKind::RocksDb(storage.bulk_loader()),
-            },
-            StorageKind::Memory(storage) => StorageBulkLoader {
-                kind: StorageBulkLoaderKind::Memory(storage.bulk_loader()),
+        let loader = StorageBulkLoader {
+            kind: match &self.kind {
+                #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+                StorageKind::RocksDb(storage) => {
+                    StorageBulkLoaderKind::RocksDb(storage.bulk_loader())
+                }
+                StorageKind::Memory(storage) => {
+                    StorageBulkLoaderKind::Memory(storage.bulk_loader())
+                }
             },
+            metrics: self.metrics.clone(),
+            generation: self.generation.clone(),
+            loaded_quads: 0,
+            skip_existing: false,
+            skipped_quads: 0,
+            deadline: None,
+        };
+        if let Some(metrics) = &self.metrics {
+            let metrics = Arc::clone(metrics);
+            loader.on_progress(move |loaded_quads| metrics.bulk_load_progress(loaded_quads))
+        } else {
+            loader
         }
     }
 }
@@ -158,6 +269,8 @@ impl Storage {
 #[must_use]
 pub struct StorageReader<'a> {
     kind: StorageReaderKind<'a>,
+    #[cfg(test)]
+    active_iterators: ActiveIterators,
 }
 
 enum StorageReaderKind<'a> {
@@ -212,9 +325,47 @@ impl<'a> StorageReader<'a> {
                     reader.quads_for_pattern(subject, predicate, object, graph_name),
                 ),
             },
+            #[cfg(test)]
+            _guard: self.active_iterators.guard(),
         }
     }
 
+    /// Checks if at least one quad matches the pattern, stopping at the first matching key
+    pub fn contains_pattern(
+        &self,
+        subject: Option<&EncodedTerm>,
+        predicate: Option<&EncodedTerm>,
+        object: Option<&EncodedTerm>,
+        graph_name: Option<&EncodedTerm>,
+    ) -> Result<bool, StorageError> {
+        if let (Some(subject), Some(predicate), Some(object), Some(graph_name)) =
+            (subject, predicate, object, graph_name)
+        {
+            return self.contains(&EncodedQuad::new(
+                subject.clone(),
+                predicate.clone(),
+                object.clone(),
+                graph_name.clone(),
+            ));
+        }
+        self.quads_for_pattern(subject, predicate, object, graph_name)
+            .next()
+            .transpose()
+            .map(|quad| quad.is_some())
+    }
+
+    /// Counts the quads of a graph, only the graph range of the indexes is scanned
+    ///
+    /// There is no per-graph counter: it would have to be versioned like the quads
+    /// and every transaction writing to a graph would contend on it.
+    pub fn len_for_graph(&self, graph_name: &EncodedTerm) -> Result<usize, StorageError> {
+        self.quads_for_pattern(None, None, None, Some(graph_name))
+            .try_fold(0, |count, quad| {
+                quad?;
+                Ok(count + 1)
+            })
+    }
+
     pub fn named_graphs(&self) -> DecodingGraphIterator<'a> {
         DecodingGraphIterator {
             kind: match &self.kind {
@@ -226,6 +377,8 @@ impl<'a> StorageReader<'a> {
                     DecodingGraphIteratorKind::Memory(reader.named_graphs())
                 }
             },
+            #[cfg(test)]
+            _guard: self.active_iterators.guard(),
         }
     }
 
@@ -258,6 +411,8 @@ impl<'a> StorageReader<'a> {
 #[must_use]
 pub struct DecodingQuadIterator<'a> {
     kind: DecodingQuadIteratorKind<'a>,
+    #[cfg(test)]
+    _guard: ActiveIteratorGuard,
 }
 
 enum DecodingQuadIteratorKind<'a> {
@@ -281,6 +436,8 @@ impl Iterator for DecodingQuadIterator<'_> {
 #[must_use]
 pub struct DecodingGraphIterator<'a> {
     kind: DecodingGraphIteratorKind<'a>,
+    #[cfg(test)]
+    _guard: ActiveIteratorGuard,
 }
 
 enum DecodingGraphIteratorKind<'a> {
@@ -314,6 +471,8 @@ impl StrLookup for StorageReader<'_> {
 #[must_use]
 pub struct StorageTransaction<'a> {
     kind: StorageTransactionKind<'a>,
+    metrics: TransactionMetrics,
+    generation: Generation,
 }
 
 enum StorageTransactionKind<'a> {
@@ -322,12 +481,9 @@ enum StorageTransactionKind<'a> {
     Memory(MemoryStorageTransaction<'a>),
 }
 
-#[cfg_attr(
-    not(all(not(target_family = "wasm"), feature = "rocksdb")),
-    expect(clippy::unnecessary_wraps)
-)]
 impl StorageTransaction<'_> {
     pub fn insert(&mut self, quad: QuadRef<'_>) {
+        self.metrics.inserted_quads += 1;
         match &mut self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
             StorageTransactionKind::RocksDb(transaction) => transaction.insert(quad),
@@ -350,6 +506,7 @@ impl StorageTransaction<'_> {
     }
 
     pub fn remove(&mut self, quad: QuadRef<'_>) {
+        self.metrics.removed_quads += 1;
         match &mut self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
             StorageTransactionKind::RocksDb(transaction) => transaction.remove(quad),
@@ -399,21 +556,28 @@ impl StorageTransaction<'_> {
         }
     }
 
-    pub fn commit(self) -> Result<(), StorageError> {
-        match self.kind {
+    pub fn commit(mut self) -> Result<(), StorageError> {
+        let result: Result<(), StorageError> = match self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
             StorageTransactionKind::RocksDb(transaction) => transaction.commit(),
             StorageTransactionKind::Memory(transaction) => {
                 transaction.commit();
                 Ok(())
             }
-        }
+        };
+        self.generation.increment();
+        self.metrics.report_commit(&result);
+        result
     }
 }
 
 #[must_use]
 pub struct StorageReadableTransaction<'a> {
     kind: StorageReadableTransactionKind<'a>,
+    #[cfg(test)]
+    active_iterators: ActiveIterators,
+    metrics: TransactionMetrics,
+    generation: Generation,
 }
 
 enum StorageReadableTransactionKind<'a> {
@@ -427,6 +591,10 @@ enum StorageReadableTransactionKind<'a> {
     expect(clippy::unnecessary_wraps)
 )]
 impl StorageReadableTransaction<'_> {
+    pub fn metrics(&self) -> Option<&Arc<dyn MetricsCollector>> {
+        self.metrics.collector.as_ref()
+    }
+
     pub fn reader(&self) -> StorageReader<'_> {
         StorageReader {
             kind: match &self.kind {
@@ -438,10 +606,13 @@ impl StorageReadableTransaction<'_> {
                     StorageReaderKind::Memory(transaction.reader())
                 }
             },
+            #[cfg(test)]
+            active_iterators: self.active_iterators.clone(),
         }
     }
 
     pub fn insert(&mut self, quad: QuadRef<'_>) {
+        self.metrics.inserted_quads += 1;
         match &mut self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
             StorageReadableTransactionKind::RocksDb(transaction) => transaction.insert(quad),
@@ -464,6 +635,7 @@ impl StorageReadableTransaction<'_> {
     }
 
     pub fn remove(&mut self, quad: QuadRef<'_>) {
+        self.metrics.removed_quads += 1;
         match &mut self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
             StorageReadableTransactionKind::RocksDb(transaction) => transaction.remove(quad),
@@ -548,18 +720,22 @@ impl StorageReadableTransaction<'_> {
         }
     }
 
-    pub fn commit(self) -> Result<(), StorageError> {
-        match self.kind {
+    pub fn commit(mut self) -> Result<(), StorageError> {
+        let result: Result<(), StorageError> = match self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
             StorageReadableTransactionKind::RocksDb(transaction) => transaction.commit(),
             StorageReadableTransactionKind::Memory(transaction) => {
                 transaction.commit();
                 Ok(())
             }
-        }
+        };
+        self.generation.increment();
+        self.metrics.report_commit(&result);
+        result
     }
 
     pub fn rollback(self) {
+        // The rollback is reported when the metrics are dropped
         match self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
             StorageReadableTransactionKind::RocksDb(transaction) => transaction.rollback(),
@@ -568,9 +744,99 @@ impl StorageReadableTransaction<'_> {
     }
 }
 
+/// Counts the changes done in a transaction to report them to the [`MetricsCollector`] on commit
+///
+/// If the transaction is dropped without being committed, a rollback is reported.
+struct TransactionMetrics {
+    collector: Option<Arc<dyn MetricsCollector>>,
+    inserted_quads: usize,
+    removed_quads: usize,
+    committed: bool,
+}
+
+impl TransactionMetrics {
+    fn new(collector: Option<Arc<dyn MetricsCollector>>) -> Self {
+        Self {
+            collector,
+            inserted_quads: 0,
+            removed_quads: 0,
+            committed: false,
+        }
+    }
+
+    fn report_commit(&mut self, result: &Result<(), StorageError>) {
+        if result.is_err() {
+            return; // The rollback is reported on drop
+        }
+        self.committed = true;
+        if let Some(collector) = &self.collector {
+            collector.transaction_committed(self.inserted_quads, self.removed_quads);
+        }
+    }
+}
+
+impl Drop for TransactionMetrics {
+    fn drop(&mut self) {
+        if !self.committed {
+            if let Some(collector) = &self.collector {
+                collector.transaction_rolled_back();
+            }
+        }
+    }
+}
+
+/// Counter of the writes done on a [`Storage`], incremented after the writes are visible
+#[derive(Clone, Default)]
+struct Generation(Arc<AtomicU64>);
+
+impl Generation {
+    fn get(&self) -> u64 {
+        self.0.load(Ordering::Acquire)
+    }
+
+    fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// Counter of the [`DecodingQuadIterator`] and [`DecodingGraphIterator`] alive for a [`Storage`]
+#[cfg(test)]
+#[derive(Clone, Default)]
+struct ActiveIterators(Arc<AtomicUsize>);
+
+#[cfg(test)]
+impl ActiveIterators {
+    fn guard(&self) -> ActiveIteratorGuard {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        ActiveIteratorGuard(Arc::clone(&self.0))
+    }
+
+    fn count(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Decrements the [`ActiveIterators`] counter when the iterator holding it is dropped
+#[cfg(test)]
+struct ActiveIteratorGuard(Arc<AtomicUsize>);
+
+#[cfg(test)]
+impl Drop for ActiveIteratorGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 #[must_use]
 pub struct StorageBulkLoader<'a> {
     kind: StorageBulkLoaderKind<'a>,
+    metrics: Option<Arc<dyn MetricsCollector>>,
+    generation: Generation,
+    loaded_quads: u64,
+    skip_existing: bool,
+    skipped_quads: u64,
+    /// The time after which the commit fails with the timeout that has set it
+    deadline: Option<(Instant, Duration)>,
 }
 
 enum StorageBulkLoaderKind<'a> {
@@ -581,61 +847,116 @@ enum StorageBulkLoaderKind<'a> {
 
 impl StorageBulkLoader<'_> {
     pub fn on_progress(self, callback: impl Fn(u64) + Send + Sync + 'static) -> Self {
-        match self.kind {
+        let kind = match self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
-            StorageBulkLoaderKind::RocksDb(loader) => Self {
-                kind: StorageBulkLoaderKind::RocksDb(loader.on_progress(callback)),
-            },
-            StorageBulkLoaderKind::Memory(loader) => Self {
-                kind: StorageBulkLoaderKind::Memory(loader.on_progress(callback)),
-            },
-        }
+            StorageBulkLoaderKind::RocksDb(loader) => {
+                StorageBulkLoaderKind::RocksDb(loader.on_progress(callback))
+            }
+            StorageBulkLoaderKind::Memory(loader) => {
+                StorageBulkLoaderKind::Memory(loader.on_progress(callback))
+            }
+        };
+        Self { kind, ..self }
     }
 
     pub fn without_atomicity(self) -> Self {
-        match self.kind {
+        let kind = match self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
-            StorageBulkLoaderKind::RocksDb(loader) => Self {
-                kind: StorageBulkLoaderKind::RocksDb(loader.without_atomicity()),
-            },
-            StorageBulkLoaderKind::Memory(loader) => Self {
-                kind: StorageBulkLoaderKind::Memory(loader),
-            },
+            StorageBulkLoaderKind::RocksDb(loader) => {
+                StorageBulkLoaderKind::RocksDb(loader.without_atomicity())
+            }
+            StorageBulkLoaderKind::Memory(loader) => StorageBulkLoaderKind::Memory(loader),
+        };
+        Self { kind, ..self }
+    }
+
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        let deadline = Instant::now() + timeout;
+        let kind = match self.kind {
+            #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+            StorageBulkLoaderKind::RocksDb(loader) => {
+                StorageBulkLoaderKind::RocksDb(loader.with_timeout(deadline, timeout))
+            }
+            StorageBulkLoaderKind::Memory(loader) => StorageBulkLoaderKind::Memory(loader),
+        };
+        Self {
+            kind,
+            deadline: Some((deadline, timeout)),
+            ..self
+        }
+    }
+
+    fn check_deadline(&self) -> Result<(), StorageError> {
+        if let Some((deadline, timeout)) = self.deadline {
+            if Instant::now() > deadline {
+                return Err(StorageError::bulk_load_timeout(timeout));
+            }
         }
+        Ok(())
+    }
+
+    pub fn with_skip_existing(self, skip_existing: bool) -> Self {
+        Self {
+            skip_existing,
+            ..self
+        }
+    }
+
+    pub fn skipped_quads(&self) -> u64 {
+        self.skipped_quads
     }
 
     #[cfg_attr(
         any(target_family = "wasm", not(feature = "rocksdb")),
-        expect(clippy::unnecessary_wraps, unused_variables)
+        expect(unused_variables)
     )]
     pub fn load_batch(
         &mut self,
         quads: Vec<Quad>,
         max_num_threads: usize,
     ) -> Result<(), StorageError> {
-        match &mut self.kind {
-            #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
-            StorageBulkLoaderKind::RocksDb(loader) => loader.load_batch(quads, max_num_threads),
+        self.check_deadline()?;
+        self.loaded_quads += u64::try_from(quads.len()).unwrap_or(u64::MAX);
+        let result = match &mut self.kind {
+            #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+            StorageBulkLoaderKind::RocksDb(loader) => {
+                let mut quads = quads;
+                if self.skip_existing {
+                    self.skipped_quads += loader.remove_existing(&mut quads)?;
+                }
+                loader.load_batch(quads, max_num_threads)
+            }
             StorageBulkLoaderKind::Memory(loader) => {
-                loader.load_batch(quads);
+                // Quads already in the store are no-ops for the in-memory transaction
+                let existing = loader.load_batch(quads);
+                if self.skip_existing {
+                    self.skipped_quads += existing;
+                }
                 Ok(())
             }
-        }
+        };
+        // Without atomicity the batch might already be visible
+        self.generation.increment();
+        result
     }
 
-    #[cfg_attr(
-        any(target_family = "wasm", not(feature = "rocksdb")),
-        expect(clippy::unnecessary_wraps)
-    )]
     pub fn commit(self) -> Result<(), StorageError> {
-        match self.kind {
+        // Dropping the loader without committing discards everything loaded so far
+        self.check_deadline()?;
+        let result: Result<(), StorageError> = match self.kind {
             #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
             StorageBulkLoaderKind::RocksDb(loader) => loader.commit(),
             StorageBulkLoaderKind::Memory(loader) => {
                 loader.commit();
                 Ok(())
             }
+        };
+        self.generation.increment();
+        result?;
+        if let Some(metrics) = &self.metrics {
+            metrics.bulk_load_committed(self.loaded_quads);
         }
+        Ok(())
     }
 }
 
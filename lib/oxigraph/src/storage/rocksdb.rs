@@ -28,8 +28,10 @@ use std::hash::BuildHasherDefault;
 use std::hash::Hash;
 use std::mem::take;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
 use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use std::{io, thread};
 
 const BATCH_SIZE: usize = 100_000;
@@ -67,11 +69,21 @@ pub struct RocksDbStorage {
 
 impl RocksDbStorage {
     pub fn open(path: &Path) -> Result<Self, StorageError> {
-        Self::setup(Db::open_read_write(path, Self::column_families())?)
+        let this = Self::setup(Db::open_read_write(path, Self::column_families())?)?;
+        this.migrate_to_latest()?;
+        Ok(this)
     }
 
-    pub fn open_read_only(path: &Path) -> Result<Self, StorageError> {
-        Self::setup(Db::open_read_only(path, Self::column_families())?)
+    /// Opens the database as a secondary instance, it might be written by another process at the same time.
+    pub fn open_secondary(path: &Path) -> Result<Self, StorageError> {
+        let this = Self::setup(Db::open_secondary(path, Self::column_families())?)?;
+        this.check_version()?;
+        Ok(this)
+    }
+
+    /// Migrates the database at the given path to the latest storage version.
+    pub fn migrate(path: &Path) -> Result<(), StorageError> {
+        Self::setup(Db::open_read_write(path, Self::column_families())?)?.migrate_to_latest()
     }
 
     fn column_families() -> Vec<ColumnFamilyDefinition> {
@@ -146,7 +158,7 @@ impl RocksDbStorage {
     }
 
     fn setup(db: Db) -> Result<Self, StorageError> {
-        let this = Self {
+        Ok(Self {
             default_cf: db.column_family(DEFAULT_CF)?,
             id2str_cf: db.column_family(ID2STR_CF)?,
             spog_cf: db.column_family(SPOG_CF)?,
@@ -160,20 +172,25 @@ impl RocksDbStorage {
             dosp_cf: db.column_family(DOSP_CF)?,
             graphs_cf: db.column_family(GRAPHS_CF)?,
             db,
-        };
-        this.migrate()?;
-        Ok(this)
+        })
     }
 
-    fn migrate(&self) -> Result<(), StorageError> {
+    fn check_version(&self) -> Result<(), StorageError> {
+        let version = self.ensure_version()?;
+        if version == LATEST_STORAGE_VERSION {
+            Ok(())
+        } else {
+            Err(StorageError::IncompatibleVersion {
+                found: version,
+                expected: LATEST_STORAGE_VERSION,
+            })
+        }
+    }
+
+    fn migrate_to_latest(&self) -> Result<(), StorageError> {
         let mut version = self.ensure_version()?;
         if version == 0 {
             // We migrate to v1
-            if !self.db.is_writable() {
-                return Err(StorageError::Other(
-                    "It is not possible to upgrade read-only Oxigraph instances to newer Oxigraph versions, please open in read-write regular mode to upgrade.".into(),
-                ));
-            }
             let mut graph_names = FxHashSet::default();
             for quad in self.snapshot().quads() {
                 let quad = quad?;
@@ -238,11 +255,6 @@ impl RocksDbStorage {
                 Ok(reifier.as_ref().into())
             }
 
-            if !self.db.is_writable() {
-                return Err(StorageError::Other(
-                    "It is not possible to upgrade read-only Oxigraph instances to newer Oxigraph versions, please open in read-write regular mode to upgrade.".into(),
-                ));
-            }
             let snapshot = self.snapshot();
             #[cfg_attr(not(feature = "rdf-12"), expect(clippy::never_loop))]
             for quad in snapshot
@@ -291,15 +303,13 @@ impl RocksDbStorage {
             self.update_version(version)?;
         }
 
-        match version {
-            _ if version < LATEST_STORAGE_VERSION => Err(CorruptionError::msg(format!(
-                "The RocksDB database is using the outdated encoding version {version}. Automated migration is not supported, please dump the store dataset using a compatible Oxigraph version and load it again using the current version"
-            )).into()),
-            LATEST_STORAGE_VERSION => Ok(()),
-            _ => Err(CorruptionError::msg(format!(
-                "The RocksDB database is using the too recent version {version}. Upgrade to the latest Oxigraph version to load this database"
-
-            )).into())
+        if version == LATEST_STORAGE_VERSION {
+            Ok(())
+        } else {
+            Err(StorageError::IncompatibleVersion {
+                found: version,
+                expected: LATEST_STORAGE_VERSION,
+            })
         }
     }
 
@@ -352,23 +362,53 @@ impl RocksDbStorage {
     }
 
     pub fn compact(&self) -> Result<(), StorageError> {
-        self.db.compact(&self.default_cf)?;
-        self.db.compact(&self.gspo_cf)?;
-        self.db.compact(&self.gpos_cf)?;
-        self.db.compact(&self.gosp_cf)?;
-        self.db.compact(&self.spog_cf)?;
-        self.db.compact(&self.posg_cf)?;
-        self.db.compact(&self.ospg_cf)?;
-        self.db.compact(&self.dspo_cf)?;
-        self.db.compact(&self.dpos_cf)?;
-        self.db.compact(&self.dosp_cf)?;
-        self.db.compact(&self.id2str_cf)
+        for column_family in self.column_family_handles() {
+            self.db.compact(column_family)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the size in bytes of the SST files currently used by the database.
+    pub fn on_disk_size(&self) -> Result<u64, StorageError> {
+        let mut size = 0;
+        for column_family in self.column_family_handles() {
+            size += self.db.live_sst_files_size(column_family)?;
+        }
+        Ok(size)
+    }
+
+    fn column_family_handles(&self) -> [&ColumnFamily; 12] {
+        [
+            &self.default_cf,
+            &self.gspo_cf,
+            &self.gpos_cf,
+            &self.gosp_cf,
+            &self.spog_cf,
+            &self.posg_cf,
+            &self.ospg_cf,
+            &self.dspo_cf,
+            &self.dpos_cf,
+            &self.dosp_cf,
+            &self.graphs_cf,
+            &self.id2str_cf,
+        ]
+    }
+
+    pub fn catch_up_with_primary(&self) -> Result<(), StorageError> {
+        self.db.catch_up_with_primary()
     }
 
     pub fn backup(&self, target_directory: &Path) -> Result<(), StorageError> {
         self.db.backup(target_directory)
     }
 
+    /// Rebuilds a database in `target_directory` from the backup in `backup_directory`.
+    ///
+    /// The backup is opened as a secondary instance and checkpointed again so that it is left untouched.
+    pub fn restore(backup_directory: &Path, target_directory: &Path) -> Result<(), StorageError> {
+        Self::open_secondary(backup_directory)?.backup(target_directory)
+    }
+
     pub fn bulk_loader(&self) -> RocksDbStorageBulkLoader<'_> {
         RocksDbStorageBulkLoader {
             storage: self,
@@ -379,6 +419,9 @@ impl RocksDbStorage {
             done_and_displayed_counter: 0,
             cancellation_token: CancellationToken::new(),
             atomic: true,
+            deadline: None,
+            threads_completion: Arc::default(),
+            loaded_quads: FxHashSet::default(),
         }
     }
 }
@@ -1343,12 +1386,40 @@ impl RocksDbStorageReadableTransaction<'_> {
 pub struct RocksDbStorageBulkLoader<'a> {
     storage: &'a RocksDbStorage,
     hooks: Vec<Box<dyn Fn(u64) + Send + Sync>>,
-    threads: VecDeque<JoinHandle<Result<Vec<(ColumnFamily, PathBuf)>, StorageError>>>,
+    threads: VecDeque<BulkLoadThread>,
     sst_files: Vec<(ColumnFamily, PathBuf)>,
     done_counter: Arc<Mutex<u64>>,
     done_and_displayed_counter: u64,
     cancellation_token: CancellationToken,
     atomic: bool,
+    /// The time after which the loading fails with the timeout that has set it
+    deadline: Option<(Instant, Duration)>,
+    /// Notified each time a thread of `threads` is done
+    threads_completion: Arc<(Mutex<()>, Condvar)>,
+    /// The quads kept by [`remove_existing`](Self::remove_existing) in the previous batches
+    loaded_quads: FxHashSet<EncodedQuad>,
+}
+
+/// A background thread writing a batch to SST files
+struct BulkLoadThread {
+    handle: JoinHandle<Result<Vec<(ColumnFamily, PathBuf)>, StorageError>>,
+    /// Set when the thread is done, while holding the `threads_completion` lock
+    done: Arc<AtomicBool>,
+}
+
+/// Marks its thread as done when dropped, even if the thread panics
+struct BulkLoadThreadDoneGuard {
+    done: Arc<AtomicBool>,
+    threads_completion: Arc<(Mutex<()>, Condvar)>,
+}
+
+impl Drop for BulkLoadThreadDoneGuard {
+    fn drop(&mut self) {
+        let (lock, condvar) = &*self.threads_completion;
+        let _lock = lock.lock();
+        self.done.store(true, Ordering::Release);
+        condvar.notify_all();
+    }
 }
 
 impl Drop for RocksDbStorageBulkLoader<'_> {
@@ -1356,8 +1427,8 @@ impl Drop for RocksDbStorageBulkLoader<'_> {
         self.cancellation_token.cancel();
         // We wait for threads
         while let Some(thread) = self.threads.pop_front() {
-            if thread.is_finished() {
-                if let Ok(Ok(files)) = thread.join() {
+            if thread.handle.is_finished() {
+                if let Ok(Ok(files)) = thread.handle.join() {
                     self.sst_files.extend(files);
                 }
             }
@@ -1381,6 +1452,11 @@ impl RocksDbStorageBulkLoader<'_> {
         self
     }
 
+    pub fn with_timeout(mut self, deadline: Instant, timeout: Duration) -> Self {
+        self.deadline = Some((deadline, timeout));
+        self
+    }
+
     pub fn load_batch(
         &mut self,
         batch: Vec<Quad>,
@@ -1388,11 +1464,7 @@ impl RocksDbStorageBulkLoader<'_> {
     ) -> Result<(), StorageError> {
         self.on_possible_progress()?;
         while self.threads.len() >= max_num_threads {
-            if let Some(thread) = self.threads.pop_front() {
-                self.sst_files
-                    .extend(map_thread_result(thread.join()).map_err(StorageError::Io)??);
-                self.on_possible_progress()?;
-            }
+            self.join_oldest_thread()?;
         }
         if !self.atomic {
             self.do_commit()?;
@@ -1401,7 +1473,13 @@ impl RocksDbStorageBulkLoader<'_> {
         let storage = self.storage.clone();
         let counter = Arc::clone(&self.done_counter);
         let cancellation_token = self.cancellation_token.clone();
-        self.threads.push_back(thread::spawn(move || {
+        let done = Arc::new(AtomicBool::new(false));
+        let done_guard = BulkLoadThreadDoneGuard {
+            done: Arc::clone(&done),
+            threads_completion: Arc::clone(&self.threads_completion),
+        };
+        let handle = thread::spawn(move || {
+            let _done_guard = done_guard;
             let mut sst_files = Vec::new();
             match FileBulkLoader::new(&storage, batch.len(), cancellation_token).load(
                 batch,
@@ -1418,11 +1496,74 @@ impl RocksDbStorageBulkLoader<'_> {
                     Err(e)
                 }
             }
-        }));
+        });
+        self.threads.push_back(BulkLoadThread { handle, done });
         Ok(())
     }
 
+    /// Removes from the batch the quads already in the store or already loaded by this loader.
+    ///
+    /// The quads of the previous batches are not visible in the store before the end of the loading
+    /// so they are kept in memory to be detected.
+    ///
+    /// Returns the number of removed quads.
+    pub fn remove_existing(&mut self, batch: &mut Vec<Quad>) -> Result<u64, StorageError> {
+        let reader = self.storage.snapshot();
+        let mut new_quads = Vec::with_capacity(batch.len());
+        let mut removed = 0;
+        for quad in batch.drain(..) {
+            let encoded = EncodedQuad::from(quad.as_ref());
+            if self.loaded_quads.contains(&encoded) || reader.contains(&encoded)? {
+                removed += 1;
+            } else {
+                self.loaded_quads.insert(encoded);
+                new_quads.push(quad);
+            }
+        }
+        *batch = new_quads;
+        Ok(removed)
+    }
+
+    /// Waits for the oldest background thread to finish and collects its files.
+    ///
+    /// Fails if the deadline is reached before.
+    fn join_oldest_thread(&mut self) -> Result<(), StorageError> {
+        let Some(thread) = self.threads.front() else {
+            return Ok(());
+        };
+        let (lock, condvar) = &*self.threads_completion;
+        let mut guard = lock
+            .lock()
+            .map_err(|_| io::Error::other("Mutex poisoned"))?;
+        while !thread.done.load(Ordering::Acquire) {
+            guard = if let Some((deadline, timeout)) = self.deadline {
+                let Some(remaining) = deadline.checked_duration_since(Instant::now()) else {
+                    return Err(StorageError::bulk_load_timeout(timeout));
+                };
+                condvar
+                    .wait_timeout(guard, remaining)
+                    .map_err(|_| io::Error::other("Mutex poisoned"))?
+                    .0
+            } else {
+                condvar
+                    .wait(guard)
+                    .map_err(|_| io::Error::other("Mutex poisoned"))?
+            };
+        }
+        drop(guard);
+        if let Some(thread) = self.threads.pop_front() {
+            self.sst_files
+                .extend(map_thread_result(thread.handle.join()).map_err(StorageError::Io)??);
+        }
+        self.on_possible_progress()
+    }
+
     fn on_possible_progress(&mut self) -> Result<(), StorageError> {
+        if let Some((deadline, timeout)) = self.deadline {
+            if Instant::now() > deadline {
+                return Err(StorageError::bulk_load_timeout(timeout));
+            }
+        }
         let new_counter = *self
             .done_counter
             .lock()
@@ -1450,10 +1591,8 @@ impl RocksDbStorageBulkLoader<'_> {
     }
 
     pub fn commit(mut self) -> Result<(), StorageError> {
-        while let Some(thread) = self.threads.pop_front() {
-            self.sst_files
-                .extend(map_thread_result(thread.join()).map_err(StorageError::Io)??);
-            self.on_possible_progress()?;
+        while !self.threads.is_empty() {
+            self.join_oldest_thread()?;
         }
         self.do_commit()
     }
@@ -1797,4 +1936,45 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[expect(clippy::panic_in_result_fn)]
+    fn test_incompatible_version() -> Result<(), StorageError> {
+        let path = TempDir::new()?;
+
+        // An older version can't be read by a secondary instance before being migrated
+        RocksDbStorage::open(path.as_ref())?.update_version(1)?;
+        assert!(matches!(
+            RocksDbStorage::open_secondary(path.as_ref()),
+            Err(StorageError::IncompatibleVersion {
+                found: 1,
+                expected: LATEST_STORAGE_VERSION
+            })
+        ));
+        RocksDbStorage::migrate(path.as_ref())?;
+        RocksDbStorage::open_secondary(path.as_ref())?
+            .snapshot()
+            .validate()?;
+
+        // An older version is automatically migrated when opened read-write
+        RocksDbStorage::open(path.as_ref())?.update_version(1)?;
+        RocksDbStorage::open(path.as_ref())?.snapshot().validate()?;
+        RocksDbStorage::open_secondary(path.as_ref())?
+            .snapshot()
+            .validate()?;
+
+        // A more recent version can't be migrated
+        let newer_version = LATEST_STORAGE_VERSION + 1;
+        RocksDbStorage::open(path.as_ref())?.update_version(newer_version)?;
+        assert!(matches!(
+            RocksDbStorage::open(path.as_ref()),
+            Err(StorageError::IncompatibleVersion { found, .. }) if found == newer_version
+        ));
+        assert!(matches!(
+            RocksDbStorage::migrate(path.as_ref()),
+            Err(StorageError::IncompatibleVersion { found, .. }) if found == newer_version
+        ));
+
+        Ok(())
+    }
 }
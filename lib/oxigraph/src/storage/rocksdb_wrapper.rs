@@ -15,8 +15,10 @@ use std::borrow::Borrow;
 #[cfg(unix)]
 use std::cmp::min;
 use std::collections::HashMap;
+use std::env::temp_dir;
 use std::error::Error;
 use std::ffi::CString;
+use std::fs::remove_dir_all;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
@@ -102,6 +104,8 @@ struct RoDbHandler {
     column_family_names: Vec<&'static str>,
     cf_handles: Vec<*mut rocksdb_column_family_handle_t>,
     cf_options: Vec<*mut rocksdb_options_t>,
+    /// The directory of the secondary instance if the database is opened as a secondary instance
+    secondary_path: Option<PathBuf>,
 }
 
 unsafe impl Send for RoDbHandler {}
@@ -121,6 +125,10 @@ impl Drop for RoDbHandler {
             rocksdb_readoptions_destroy(self.read_options);
             rocksdb_options_destroy(self.options);
         }
+        if let Some(path) = &self.secondary_path {
+            // The directory is in the temporary directory, it is fine to leave it behind on failure
+            let _ = remove_dir_all(path);
+        }
     }
 }
 
@@ -244,21 +252,30 @@ impl Db {
         }
     }
 
-    pub fn open_read_only(
-        path: &Path,
+    /// Opens the database as a RocksDB secondary instance.
+    ///
+    /// The database might be written at the same time by a primary instance.
+    /// The secondary instance only sees these writes after a call to [`Db::catch_up_with_primary`].
+    pub fn open_secondary(
+        primary_path: &Path,
         column_families: Vec<ColumnFamilyDefinition>,
     ) -> Result<Self, StorageError> {
+        let c_primary_path = path_to_cstring(primary_path)?;
+        let secondary_path =
+            temp_dir().join(format!("oxigraph-rocksdb-secondary-{}", random::<u128>()));
+        let c_secondary_path = path_to_cstring(&secondary_path)?;
         unsafe {
-            let c_path = path_to_cstring(path)?;
-            let options = Self::db_options(true)?;
+            // Secondary instances must keep all the files opened
+            let options = Self::db_options(false)?;
             let (column_family_names, c_column_family_names, cf_options) =
                 Self::column_families_names_and_options(column_families, options);
             let mut cf_handles: Vec<*mut rocksdb_column_family_handle_t> =
                 vec![ptr::null_mut(); column_family_names.len()];
             let c_num_column_families = c_column_family_names.len().try_into().unwrap();
-            let db = ffi_result!(rocksdb_open_for_read_only_column_families(
+            let db = ffi_result!(rocksdb_open_as_secondary_column_families(
                 options,
-                c_path.as_ptr(),
+                c_primary_path.as_ptr(),
+                c_secondary_path.as_ptr(),
                 c_num_column_families,
                 c_column_family_names
                     .iter()
@@ -267,23 +284,24 @@ impl Db {
                     .as_ptr(),
                 cf_options.as_ptr().cast(),
                 cf_handles.as_mut_ptr(),
-                0, // false
             ))
             .map_err(|e| {
                 for cf_option in &cf_options {
                     rocksdb_options_destroy(*cf_option);
                 }
                 rocksdb_options_destroy(options);
+                // The directory might not have been created
+                let _ = remove_dir_all(&secondary_path);
                 e
             })?;
             assert!(
                 !db.is_null(),
-                "rocksdb_open_for_read_only_column_families returned null"
+                "rocksdb_open_as_secondary_column_families returned null"
             );
             for handle in &cf_handles {
                 assert!(
                     !handle.is_null(),
-                    "rocksdb_open_for_read_only_column_families returned a null column family"
+                    "rocksdb_open_as_secondary_column_families returned a null column family"
                 );
             }
             let read_options = rocksdb_readoptions_create();
@@ -301,6 +319,7 @@ impl Db {
                     column_family_names,
                     cf_handles,
                     cf_options,
+                    secondary_path: Some(secondary_path),
                 })),
             })
         }
@@ -392,13 +411,6 @@ impl Db {
         (column_family_names, c_column_family_names, cf_options)
     }
 
-    pub fn is_writable(&self) -> bool {
-        match &self.inner {
-            DbKind::ReadWrite(_) => true,
-            DbKind::ReadOnly(_) => false,
-        }
-    }
-
     pub fn column_family(&self, name: &'static str) -> Result<ColumnFamily, StorageError> {
         let (column_family_names, cf_handles) = match &self.inner {
             DbKind::ReadOnly(db) => (&db.column_family_names, &db.cf_handles),
@@ -442,9 +454,7 @@ impl Db {
 
     pub fn start_transaction(&self) -> Result<Transaction, StorageError> {
         let DbKind::ReadWrite(db) = &self.inner else {
-            return Err(StorageError::Other(
-                "Transaction are only possible on read-write instances".into(),
-            ));
+            return Err(StorageError::ReadOnly);
         };
         let batch = unsafe { rocksdb_writebatch_create() };
         assert!(!batch.is_null(), "rocksdb_writebatch_create returned null");
@@ -456,9 +466,7 @@ impl Db {
 
     pub fn start_readable_transaction(&self) -> Result<ReadableTransaction<'_>, StorageError> {
         let DbKind::ReadWrite(db) = &self.inner else {
-            return Err(StorageError::Other(
-                "Transaction are only possible on read-write instances".into(),
-            ));
+            return Err(StorageError::ReadOnly);
         };
         let (batch, read_options, snapshot) = unsafe {
             let snapshot = rocksdb_create_snapshot(db.db);
@@ -525,9 +533,7 @@ impl Db {
         value: &[u8],
     ) -> Result<(), StorageError> {
         let DbKind::ReadWrite(db) = &self.inner else {
-            return Err(StorageError::Other(
-                "Inserts are only possible on read-write instances".into(),
-            ));
+            return Err(StorageError::ReadOnly);
         };
         unsafe {
             ffi_result!(rocksdb_put_cf(
@@ -545,9 +551,7 @@ impl Db {
 
     pub fn flush(&self) -> Result<(), StorageError> {
         let DbKind::ReadWrite(db) = &self.inner else {
-            return Err(StorageError::Other(
-                "Flush is only possible on read-write instances".into(),
-            ));
+            return Err(StorageError::ReadOnly);
         };
         unsafe {
             ffi_result!(rocksdb_flush_cfs(
@@ -562,9 +566,7 @@ impl Db {
 
     pub fn compact(&self, column_family: &ColumnFamily) -> Result<(), StorageError> {
         let DbKind::ReadWrite(db) = &self.inner else {
-            return Err(StorageError::Other(
-                "Compact are only possible on read-write instances".into(),
-            ));
+            return Err(StorageError::ReadOnly);
         };
         unsafe {
             rocksdb_compact_range_cf_opt(
@@ -580,11 +582,30 @@ impl Db {
         Ok(())
     }
 
-    pub fn new_sst_file(&self) -> Result<SstFileWriter, StorageError> {
-        let DbKind::ReadWrite(db) = &self.inner else {
+    pub fn live_sst_files_size(&self, column_family: &ColumnFamily) -> Result<u64, StorageError> {
+        let mut size = 0;
+        let status = unsafe {
+            rocksdb_property_int_cf(
+                match &self.inner {
+                    DbKind::ReadOnly(db) => db.db,
+                    DbKind::ReadWrite(db) => db.db,
+                },
+                column_family.0,
+                c"rocksdb.live-sst-files-size".as_ptr(),
+                &raw mut size,
+            )
+        };
+        if status != 0 {
             return Err(StorageError::Other(
-                "SST creation is only possible on read-write instances".into(),
+                "Not able to read the rocksdb.live-sst-files-size property".into(),
             ));
+        }
+        Ok(size)
+    }
+
+    pub fn new_sst_file(&self) -> Result<SstFileWriter, StorageError> {
+        let DbKind::ReadWrite(db) = &self.inner else {
+            return Err(StorageError::ReadOnly);
         };
         let path = db.path.join(format!("bulk-{}.sst", random::<u128>()));
         unsafe {
@@ -606,9 +627,7 @@ impl Db {
         ssts_for_cf: &[(ColumnFamily, PathBuf)],
     ) -> Result<(), StorageError> {
         let DbKind::ReadWrite(db) = &self.inner else {
-            return Err(StorageError::Other(
-                "SST ingestion is only possible on read-write instances".into(),
-            ));
+            return Err(StorageError::ReadOnly);
         };
         if ssts_for_cf.is_empty() {
             return Ok(()); // Rocksdb does not support empty lists
@@ -643,6 +662,18 @@ impl Db {
         Ok(())
     }
 
+    /// Reads the writes done by the primary instance since the opening or the previous call.
+    ///
+    /// It is a no-op if the database is not opened as a secondary instance.
+    pub fn catch_up_with_primary(&self) -> Result<(), StorageError> {
+        if let DbKind::ReadOnly(db) = &self.inner {
+            if db.secondary_path.is_some() {
+                unsafe { ffi_result!(rocksdb_try_catch_up_with_primary(db.db)) }?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn backup(&self, target_directory: &Path) -> Result<(), StorageError> {
         let path = path_to_cstring(target_directory)?;
         unsafe {
@@ -1220,6 +1251,13 @@ impl From<ErrorStatus> for StorageError {
                 status,
             )),
             Some("Corruption") => Self::Corruption(CorruptionError::new(status)),
+            Some("Resource busy") => Self::Io(io::Error::new(io::ErrorKind::ResourceBusy, status)),
+            Some("Operation timed out") => {
+                Self::Io(io::Error::new(io::ErrorKind::TimedOut, status))
+            }
+            Some("Operation failed. Try again.") => {
+                Self::Io(io::Error::new(io::ErrorKind::WouldBlock, status))
+            }
             _ => Self::Other(Box::new(status)),
         }
     }
@@ -29,8 +29,13 @@
 //! };
 //! # Result::<_, Box<dyn std::error::Error>>::Ok(())
 //! ```
-use crate::io::{RdfParseError, RdfParser, RdfSerializer};
+use crate::io::{RdfFormat, RdfParseError, RdfParser, RdfSerializer};
+use crate::metrics::MetricsCollector;
+use crate::model::graph::CanonicalizationAlgorithm;
 use crate::model::*;
+#[cfg(feature = "shacl")]
+use crate::shacl::{ShaclError, ShaclValidator, ShapesGraph, StoreDataGraph, ValidationReport};
+use crate::sparql::results_cache::QueryResultsCache;
 #[expect(deprecated)]
 use crate::sparql::{
     Query, QueryEvaluationError, QueryExplanation, QueryResults, SparqlEvaluator, Update,
@@ -46,6 +51,7 @@ use crate::storage::{
 };
 #[cfg(not(target_family = "wasm"))]
 use std::cmp::max;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 #[cfg(not(target_family = "wasm"))]
 use std::fs::File;
@@ -62,6 +68,7 @@ use std::sync::mpsc;
 use std::thread;
 #[cfg(not(target_family = "wasm"))]
 use std::thread::available_parallelism;
+use std::time::Duration;
 
 /// An on-disk [RDF dataset](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset).
 /// Allows querying and updating it using SPARQL.
@@ -111,11 +118,92 @@ impl Store {
         })
     }
 
+    /// Registers a [`MetricsCollector`] called on the queries, transactions and bulk loads done on this store.
+    ///
+    /// The collector is shared with the clones of the returned store.
+    /// Without collector, no metric is computed.
+    ///
+    /// See [`StoreMetrics`](crate::metrics::StoreMetrics) for a collector exposing Prometheus counters.
+    ///
+    /// ```
+    /// use oxigraph::metrics::MetricsCollector;
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// #[derive(Default)]
+    /// struct InsertCounter(AtomicUsize);
+    ///
+    /// impl MetricsCollector for InsertCounter {
+    ///     fn transaction_committed(&self, inserted_quads: usize, _removed_quads: usize) {
+    ///         self.0.fetch_add(inserted_quads, Ordering::Relaxed);
+    ///     }
+    /// }
+    ///
+    /// let counter = Arc::new(InsertCounter::default());
+    /// let store = Store::new()?.with_metrics(counter.clone());
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// store.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph))?;
+    /// assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[must_use]
+    pub fn with_metrics(self, metrics: Arc<dyn MetricsCollector>) -> Self {
+        Self {
+            storage: self.storage.with_metrics(metrics),
+        }
+    }
+
+    /// Enables a cache of the results of the `SELECT` and `ASK` queries evaluated on this store.
+    ///
+    /// At most `capacity` results are kept, the least recently used ones are evicted first.
+    /// The cache is shared with the clones of the returned store and is invalidated on every write.
+    /// Results with more than 10,000 solutions are not cached.
+    /// Queries using `SERVICE` or non-deterministic functions like `NOW()` or `RAND()` are never cached.
+    /// The cache is also bypassed by the [`SparqlEvaluator`]s customizing the evaluation,
+    /// e.g. with custom functions, service handlers, a timeout or evaluation limits.
+    ///
+    /// Note that the solutions of a cacheable query are computed before the first one is returned.
+    ///
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::sparql::{QueryResults, SparqlEvaluator};
+    /// use oxigraph::store::Store;
+    ///
+    /// let store = Store::new()?.with_query_cache(16);
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// store.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph))?;
+    /// let query = SparqlEvaluator::new().parse_query("ASK { ?s ?p ?o }")?;
+    /// // The second evaluation is served from the cache
+    /// for _ in 0..2 {
+    ///     if let QueryResults::Boolean(result) = query.clone().on_store(&store).execute()? {
+    ///         assert!(result);
+    ///     }
+    /// }
+    /// // Writes invalidate the cache
+    /// store.clear()?;
+    /// if let QueryResults::Boolean(result) = query.on_store(&store).execute()? {
+    ///     assert!(!result);
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[must_use]
+    pub fn with_query_cache(self, capacity: usize) -> Self {
+        Self {
+            storage: self
+                .storage
+                .with_query_results_cache(QueryResultsCache::new(capacity)),
+        }
+    }
+
     /// Opens a read-write [`Store`] and creates it if it does not exist yet.
     ///
     /// Only one read-write [`Store`] can exist at the same time.
     /// If you want to have extra [`Store`] instance opened on the same data
     /// use [`Store::open_read_only`].
+    ///
+    /// Databases written with an older storage version are automatically migrated like with [`Store::migrate`].
     #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
     pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
         Ok(Self {
@@ -125,7 +213,16 @@ impl Store {
 
     /// Opens a read-only [`Store`] from disk.
     ///
-    /// Opening as read-only while having an other process writing the database is undefined behavior.
+    /// Contrary to [`Store::open`], it does not lock the database:
+    /// multiple processes can open the same database read-only at the same time,
+    /// including while another process has it opened with [`Store::open`] and writes to it.
+    /// Queries and reads like [`Store::quads_for_pattern`] work normally
+    /// but all the write operations (insertions, removals, transactions, bulk loads...) fail with [`StorageError::ReadOnly`].
+    ///
+    /// The database is opened as a [RocksDB secondary instance](https://github.com/facebook/rocksdb/wiki/Read-only-and-Secondary-instances)
+    /// whose files are kept in a temporary directory removed when the store is dropped.
+    /// The store reads the database as it was when it was opened,
+    /// call [`Store::catch_up_with_primary`] to see the writes done since by the writing process.
     #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
     pub fn open_read_only(path: impl AsRef<Path>) -> Result<Self, StorageError> {
         Ok(Self {
@@ -133,6 +230,57 @@ impl Store {
         })
     }
 
+    /// Makes the writes done by the read-write [`Store`] visible to this store opened with [`Store::open_read_only`].
+    ///
+    /// It is a no-op on stores opened with [`Store::open`] or in memory.
+    /// Reads already running keep using the state of the database when they started.
+    ///
+    /// ```no_run
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    ///
+    /// let read_write = Store::open("example.db")?;
+    /// let read_only = Store::open_read_only("example.db")?;
+    /// let quad = QuadRef::new(
+    ///     NamedNodeRef::new("http://example.com")?,
+    ///     NamedNodeRef::new("http://example.com")?,
+    ///     NamedNodeRef::new("http://example.com")?,
+    ///     GraphNameRef::DefaultGraph,
+    /// );
+    /// read_write.insert(quad)?;
+    /// read_write.flush()?;
+    ///
+    /// read_only.catch_up_with_primary()?;
+    /// assert!(read_only.contains(quad)?);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    pub fn catch_up_with_primary(&self) -> Result<(), StorageError> {
+        self.storage.catch_up_with_primary()
+    }
+
+    /// Upgrades the on-disk [`Store`] at `path` to the storage version of this Oxigraph version.
+    ///
+    /// [`Store::open`] already migrates the databases written with an older storage version
+    /// but [`Store::open_read_only`] fails with [`StorageError::IncompatibleVersion`] on them:
+    /// this function allows to migrate them without opening a read-write store.
+    /// Migrating an up-to-date database is a no-op.
+    /// Databases written with a more recent storage version can't be migrated.
+    ///
+    /// The store must not be opened while it is migrated.
+    ///
+    /// ```no_run
+    /// use oxigraph::store::Store;
+    ///
+    /// Store::migrate("example.db")?;
+    /// let store = Store::open_read_only("example.db")?;
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    pub fn migrate(path: impl AsRef<Path>) -> Result<(), StorageError> {
+        Storage::migrate(path.as_ref())
+    }
+
     /// Executes a [SPARQL 1.1 query](https://www.w3.org/TR/sparql11-query/).
     ///
     /// Usage example:
@@ -341,6 +489,9 @@ impl Store {
 
     /// Retrieves quads with a filter on each quad component
     ///
+    /// The returned iterator reads a snapshot of the store taken when this method is called:
+    /// the writes and the compactions done during the iteration do not change its results.
+    ///
     /// Usage example:
     /// ```
     /// use oxigraph::model::*;
@@ -424,6 +575,42 @@ impl Store {
         self.storage.snapshot().contains(&quad)
     }
 
+    /// Checks if the store contains at least one quad matching a given pattern.
+    ///
+    /// The lookup stops at the first matching key of the most selective index, so it is much
+    /// cheaper than collecting or counting the results of
+    /// [`quads_for_pattern`](Self::quads_for_pattern).
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    ///
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// let other = NamedNodeRef::new("http://example.com/other")?;
+    ///
+    /// let store = Store::new()?;
+    /// store.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph))?;
+    ///
+    /// assert!(store.contains_pattern(None, Some(ex), None, None)?);
+    /// assert!(!store.contains_pattern(Some(other.into()), None, None, None)?);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn contains_pattern(
+        &self,
+        subject: Option<NamedOrBlankNodeRef<'_>>,
+        predicate: Option<NamedNodeRef<'_>>,
+        object: Option<TermRef<'_>>,
+        graph_name: Option<GraphNameRef<'_>>,
+    ) -> Result<bool, StorageError> {
+        self.storage.snapshot().contains_pattern(
+            subject.map(EncodedTerm::from).as_ref(),
+            predicate.map(EncodedTerm::from).as_ref(),
+            object.map(EncodedTerm::from).as_ref(),
+            graph_name.map(EncodedTerm::from).as_ref(),
+        )
+    }
+
     /// Returns the number of quads in the store.
     ///
     /// <div class="warning">This function executes a full scan.</div>
@@ -444,6 +631,74 @@ impl Store {
         self.storage.snapshot().len()
     }
 
+    /// Returns the number of quads in a graph of the store.
+    ///
+    /// <div class="warning">This function scans all the quads of the graph:
+    /// its cost is proportional to the size of the graph, not to the size of the store.</div>
+    ///
+    /// Like for [`len`](Self::len), no counter is maintained:
+    /// counters would have to follow the snapshots and the transactions of the store
+    /// and would be a write contention point shared by all the transactions touching a graph.
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    ///
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// let store = Store::new()?;
+    /// store.insert(QuadRef::new(ex, ex, ex, ex))?;
+    /// store.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph))?;
+    /// assert_eq!(1, store.len_for_graph(ex)?);
+    /// assert_eq!(1, store.len_for_graph(GraphNameRef::DefaultGraph)?);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn len_for_graph<'a>(
+        &self,
+        graph_name: impl Into<GraphNameRef<'a>>,
+    ) -> Result<usize, StorageError> {
+        let graph_name = EncodedTerm::from(graph_name.into());
+        self.storage.snapshot().len_for_graph(&graph_name)
+    }
+
+    /// Returns the number of quads in the default graph and in each named graph of the store.
+    ///
+    /// The default graph comes first, followed by the named graphs in the order of [`named_graphs`](Self::named_graphs).
+    /// Named graphs without quads, e.g. created with [`insert_named_graph`](Self::insert_named_graph), have a count of 0.
+    /// All the counts are computed on the same snapshot of the store.
+    ///
+    /// <div class="warning">This function executes a full scan.</div>
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    ///
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// let store = Store::new()?;
+    /// store.insert(QuadRef::new(ex, ex, ex, ex))?;
+    /// assert_eq!(
+    ///     store.len_per_graph()?,
+    ///     [(GraphName::DefaultGraph, 0), (ex.into_owned().into(), 1)]
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn len_per_graph(&self) -> Result<Vec<(GraphName, usize)>, StorageError> {
+        let reader = self.storage.snapshot();
+        let mut counts = vec![(
+            GraphName::DefaultGraph,
+            reader.len_for_graph(&EncodedTerm::DefaultGraph)?,
+        )];
+        for graph_name in reader.named_graphs() {
+            let graph_name = graph_name?;
+            counts.push((
+                reader.decode_named_or_blank_node(&graph_name)?.into(),
+                reader.len_for_graph(&graph_name)?,
+            ));
+        }
+        Ok(counts)
+    }
+
     /// Returns if the store is empty.
     ///
     /// Usage example:
@@ -463,6 +718,105 @@ impl Store {
         self.storage.snapshot().is_empty()
     }
 
+    /// Validates a graph of the store against [SHACL](https://www.w3.org/TR/shacl/) shapes.
+    ///
+    /// The validator reads the triples directly from a snapshot of the store,
+    /// so the graph is not copied in memory and concurrent writes are not visible.
+    /// The same [`ShaclValidator`] can be reused to validate several graphs without compiling the shapes again.
+    /// Storage errors are returned as [`ShaclError::DataGraph`].
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::io::{RdfFormat, RdfParser};
+    /// use oxigraph::model::*;
+    /// use oxigraph::shacl::{ShaclValidator, ShapesGraph};
+    /// use oxigraph::store::Store;
+    ///
+    /// let mut shapes = Graph::new();
+    /// for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(
+    ///     "@prefix sh: <http://www.w3.org/ns/shacl#> .
+    ///     <http://example.com/PersonShape> a sh:NodeShape ;
+    ///         sh:targetClass <http://example.com/Person> ;
+    ///         sh:property [ sh:path <http://example.com/name> ; sh:minCount 1 ] .",
+    /// ) {
+    ///     shapes.insert(&Triple::from(quad?));
+    /// }
+    /// let validator = ShaclValidator::new(ShapesGraph::from_graph(&shapes)?);
+    ///
+    /// let store = Store::new()?;
+    /// let graph = NamedNodeRef::new("http://example.com/g")?;
+    /// store.load_from_slice(
+    ///     RdfParser::from_format(RdfFormat::Turtle).with_default_graph(graph),
+    ///     "<http://example.com/alice> a <http://example.com/Person> .",
+    /// )?;
+    /// assert!(
+    ///     store
+    ///         .validate_shacl(&validator, GraphNameRef::DefaultGraph)?
+    ///         .conforms()
+    /// );
+    /// assert!(!store.validate_shacl(&validator, graph)?.conforms());
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(feature = "shacl")]
+    pub fn validate_shacl<'a>(
+        &self,
+        validator: &ShaclValidator,
+        graph_name: impl Into<GraphNameRef<'a>>,
+    ) -> Result<ValidationReport, ShaclError> {
+        let data_graph = StoreDataGraph::new(
+            self.storage.snapshot(),
+            EncodedTerm::from(graph_name.into()),
+        );
+        validator.validate_data_graph(&data_graph)
+    }
+
+    /// Parses the [SHACL](https://www.w3.org/TR/shacl/) shapes stored in a graph of the store.
+    ///
+    /// The shapes are read directly from a snapshot of the store, so the graph is not copied in memory.
+    /// Blank node shapes and property paths are reassembled from the store triples as with [`ShapesGraph::from_graph`].
+    /// Storage errors are returned as [`ShaclError::DataGraph`].
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::io::{RdfFormat, RdfParser};
+    /// use oxigraph::model::*;
+    /// use oxigraph::shacl::ShaclValidator;
+    /// use oxigraph::store::Store;
+    ///
+    /// let store = Store::new()?;
+    /// let shapes_graph = NamedNodeRef::new("http://example.com/shapes")?;
+    /// store.load_from_slice(
+    ///     RdfParser::from_format(RdfFormat::Turtle).with_default_graph(shapes_graph),
+    ///     "@prefix sh: <http://www.w3.org/ns/shacl#> .
+    ///     <http://example.com/PersonShape> a sh:NodeShape ;
+    ///         sh:targetClass <http://example.com/Person> ;
+    ///         sh:property [ sh:path <http://example.com/name> ; sh:minCount 1 ] .",
+    /// )?;
+    /// store.load_from_slice(
+    ///     RdfFormat::Turtle,
+    ///     "<http://example.com/alice> a <http://example.com/Person> .",
+    /// )?;
+    ///
+    /// let validator = ShaclValidator::new(store.shapes_graph(shapes_graph)?);
+    /// assert!(
+    ///     !store
+    ///         .validate_shacl(&validator, GraphNameRef::DefaultGraph)?
+    ///         .conforms()
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(feature = "shacl")]
+    pub fn shapes_graph<'a>(
+        &self,
+        graph_name: impl Into<GraphNameRef<'a>>,
+    ) -> Result<ShapesGraph, ShaclError> {
+        let data_graph = StoreDataGraph::new(
+            self.storage.snapshot(),
+            EncodedTerm::from(graph_name.into()),
+        );
+        ShapesGraph::from_data_graph(&data_graph)
+    }
+
     /// Start a transaction.
     ///
     /// Transactions ensure the "repeatable read" isolation level: the store only exposes changes that have
@@ -505,6 +859,57 @@ impl Store {
         })
     }
 
+    /// Runs `f` in a new transaction and commits it, retrying on transient errors.
+    ///
+    /// If `f` or the commit fails with a [transient error](StorageError::is_transient),
+    /// the transaction is rolled back and `f` is run again in a new transaction,
+    /// after waiting `backoff`, doubled after each attempt.
+    /// After `max_retries` retries, the last error is returned.
+    /// Other errors are returned immediately.
+    ///
+    /// `f` might be called multiple times, it should not have side effects outside of the transaction.
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    /// use std::time::Duration;
+    ///
+    /// let store = Store::new()?;
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// let inserted = store.transaction_with_retry(3, Duration::from_millis(10), |transaction| {
+    ///     transaction.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph));
+    ///     transaction.len()
+    /// })?;
+    /// assert_eq!(inserted, 1);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(not(target_family = "wasm"))]
+    pub fn transaction_with_retry<T>(
+        &self,
+        max_retries: usize,
+        backoff: Duration,
+        mut f: impl FnMut(&mut Transaction<'_>) -> Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        let mut delay = backoff;
+        let mut retries = 0;
+        loop {
+            let result = self.start_transaction().and_then(|mut transaction| {
+                let output = f(&mut transaction)?;
+                transaction.commit()?;
+                Ok(output)
+            });
+            match result {
+                Err(error) if error.is_transient() && retries < max_retries => {
+                    retries += 1;
+                    thread::sleep(delay);
+                    delay = delay.saturating_mul(2);
+                }
+                _ => return result,
+            }
+        }
+    }
+
     /// Executes a [SPARQL 1.1 update](https://www.w3.org/TR/sparql11-update/).
     ///
     /// Usage example:
@@ -695,6 +1100,116 @@ impl Store {
         Ok(())
     }
 
+    /// Atomically adds the triples of a graph to a graph of this store without duplicating blank node structures.
+    ///
+    /// Triples without blank nodes are inserted as with [`extend`](Store::extend).
+    /// Triples connected by shared blank nodes are grouped together and each group is only inserted
+    /// if the target graph does not already contain an isomorphic group.
+    /// Isomorphism is checked by comparing the [canonical forms](Graph::canonicalize) of the groups.
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    ///
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// let mut graph = Graph::new();
+    /// let bnode = BlankNode::default();
+    /// graph.insert(TripleRef::new(ex, ex, &bnode));
+    /// graph.insert(TripleRef::new(&bnode, ex, ex));
+    ///
+    /// let store = Store::new()?;
+    /// store.merge_graph_dedup(&graph, GraphNameRef::DefaultGraph)?;
+    /// store.merge_graph_dedup(&graph, GraphNameRef::DefaultGraph)?;
+    /// assert_eq!(store.len()?, 2);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn merge_graph_dedup<'a>(
+        &self,
+        graph: &Graph,
+        to_graph_name: impl Into<GraphNameRef<'a>>,
+    ) -> Result<(), StorageError> {
+        let to_graph_name = to_graph_name.into();
+        let mut transaction = self.start_transaction()?;
+        let (ground, components) =
+            split_blank_node_components(graph.iter().map(TripleRef::into_owned));
+        for triple in &ground {
+            transaction.insert(triple.as_ref().in_graph(to_graph_name));
+        }
+        // Only the existing groups that may be isomorphic to the new ones are loaded
+        let mut existing_components = HashSet::new();
+        let mut visited_blank_nodes = HashSet::new();
+        for component in components {
+            for blank_node in candidate_blank_nodes(&transaction, &component, to_graph_name)? {
+                if visited_blank_nodes.contains(&blank_node) {
+                    continue;
+                }
+                let existing = blank_node_component(&transaction, blank_node, to_graph_name)?;
+                for triple in &existing {
+                    visited_blank_nodes.extend(triple_blank_nodes(&triple.into_owned()));
+                }
+                existing_components.insert(canonical_component_key(existing));
+            }
+            if existing_components.insert(canonical_component_key(component.clone())) {
+                for triple in &component {
+                    transaction.insert(triple.in_graph(to_graph_name));
+                }
+            }
+        }
+        transaction.commit()
+    }
+
+    /// Atomically replaces the predicate `from` by `to` in all the quads of this store.
+    ///
+    /// The quads to rewrite are looked up by predicate, without scanning the full store.
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    ///
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// let old_prop = NamedNodeRef::new("http://example.com/oldProp")?;
+    /// let new_prop = NamedNodeRef::new("http://example.com/newProp")?;
+    ///
+    /// let store = Store::new()?;
+    /// store.insert(QuadRef::new(ex, old_prop, ex, GraphNameRef::DefaultGraph))?;
+    /// store.rename_predicate(old_prop, new_prop)?;
+    ///
+    /// assert!(store.contains(QuadRef::new(ex, new_prop, ex, GraphNameRef::DefaultGraph))?);
+    /// assert!(!store.contains(QuadRef::new(ex, old_prop, ex, GraphNameRef::DefaultGraph))?);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    ///
+    /// <div class="warning">
+    ///
+    /// This operation uses a memory heavy transaction internally, it might not be suited to predicates used by tens of millions of quads.</div>
+    pub fn rename_predicate<'a>(
+        &self,
+        from: impl Into<NamedNodeRef<'a>>,
+        to: impl Into<NamedNodeRef<'a>>,
+    ) -> Result<(), StorageError> {
+        let from = from.into();
+        let to = to.into();
+        if from == to {
+            return Ok(());
+        }
+        let mut transaction = self.start_transaction()?;
+        let quads = transaction
+            .quads_for_pattern(None, Some(from), None, None)
+            .collect::<Result<Vec<_>, _>>()?;
+        for quad in &quads {
+            transaction.remove(quad);
+            transaction.insert(QuadRef::new(
+                &quad.subject,
+                to,
+                &quad.object,
+                &quad.graph_name,
+            ));
+        }
+        transaction.commit()
+    }
+
     /// Removes a quad from this store.
     ///
     /// Usage example:
@@ -719,8 +1234,57 @@ impl Store {
         Ok(())
     }
 
-    /// Dumps the store into a file.
+    /// Atomically removes all the quads matching a given pattern and returns how many were removed.
+    ///
+    /// Either all the matching quads are removed or, if an error occurs, none of them.
+    ///
+    /// Usage example:
+    /// ```
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::Store;
+    ///
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// let prop = NamedNodeRef::new("http://example.com/prop")?;
+    ///
+    /// let store = Store::new()?;
+    /// store.insert(QuadRef::new(ex, prop, ex, GraphNameRef::DefaultGraph))?;
+    /// store.insert(QuadRef::new(ex, prop, ex, ex))?;
+    /// store.insert(QuadRef::new(ex, ex, ex, ex))?;
+    ///
+    /// assert_eq!(store.remove_pattern(None, Some(prop), None, None)?, 2);
+    /// assert_eq!(store.len()?, 1);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    ///
+    /// <div class="warning">
+    ///
+    /// This operation uses a memory heavy transaction internally, it might not be suited to patterns matching tens of millions of quads.</div>
+    pub fn remove_pattern(
+        &self,
+        subject: Option<NamedOrBlankNodeRef<'_>>,
+        predicate: Option<NamedNodeRef<'_>>,
+        object: Option<TermRef<'_>>,
+        graph_name: Option<GraphNameRef<'_>>,
+    ) -> Result<usize, StorageError> {
+        let mut transaction = self.start_transaction()?;
+        let quads = transaction
+            .quads_for_pattern(subject, predicate, object, graph_name)
+            .collect::<Result<Vec<_>, _>>()?;
+        for quad in &quads {
+            transaction.remove(quad);
+        }
+        transaction.commit()?;
+        Ok(quads.len())
+    }
+
+    /// Dumps the store, i.e. the default graph and all the named graphs, into a file.
+    ///
+    /// The serializer must support datasets (N-Quads, TriG...) so that each quad keeps its graph name,
+    /// [`SerializerError::DatasetFormatExpected`] is returned otherwise.
+    /// Use [`Store::dump_graph_to_writer`] to write a single graph in a graph format.
+    /// Named graphs without any quad are not written.
     ///
+    /// The file can be loaded back with [`Store::load_dataset_from_reader`]:
     /// ```
     /// use oxigraph::io::RdfFormat;
     /// use oxigraph::store::Store;
@@ -733,7 +1297,14 @@ impl Store {
     ///
     /// let buffer = store.dump_to_writer(RdfFormat::NQuads, Vec::new())?;
     /// assert_eq!(file.as_bytes(), buffer.as_slice());
-    /// # std::io::Result::Ok(())
+    ///
+    /// let copy = Store::new()?;
+    /// copy.load_dataset_from_reader(RdfFormat::NQuads, buffer.as_slice())?;
+    /// assert_eq!(
+    ///     file.as_bytes(),
+    ///     copy.dump_to_writer(RdfFormat::NQuads, Vec::new())?
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
     /// ```
     pub fn dump_to_writer<W: Write>(
         &self,
@@ -751,6 +1322,44 @@ impl Store {
         Ok(serializer.finish()?)
     }
 
+    /// Loads a file written by [`Store::dump_to_writer`] into the store.
+    ///
+    /// The quads are added to the current content of the store and keep their graph names.
+    /// Unlike [`Store::load_from_reader`], which loads the triples of a graph format into the default graph,
+    /// it fails with [`LoaderError::DatasetFormatExpected`] if the format does not support datasets (N-Quads, TriG...).
+    ///
+    /// This function is atomic, quite slow and memory hungry. To get much better performances, you might want to use the [`bulk_loader`](Store::bulk_loader).
+    ///
+    /// ```
+    /// use oxigraph::io::RdfFormat;
+    /// use oxigraph::model::*;
+    /// use oxigraph::store::{LoaderError, Store};
+    ///
+    /// let store = Store::new()?;
+    /// store.load_dataset_from_reader(
+    ///     RdfFormat::TriG,
+    ///     "<http://example.com/g> { <http://example.com> <http://example.com> <http://example.com> }".as_bytes(),
+    /// )?;
+    /// let ex = NamedNodeRef::new("http://example.com")?;
+    /// assert!(store.contains(QuadRef::new(ex, ex, ex, NamedNodeRef::new("http://example.com/g")?))?);
+    ///
+    /// assert!(matches!(
+    ///     store.load_dataset_from_reader(RdfFormat::Turtle, "".as_bytes()),
+    ///     Err(LoaderError::DatasetFormatExpected(RdfFormat::Turtle))
+    /// ));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn load_dataset_from_reader(
+        &self,
+        format: RdfFormat,
+        reader: impl Read,
+    ) -> Result<(), LoaderError> {
+        if !format.supports_datasets() {
+            return Err(LoaderError::DatasetFormatExpected(format));
+        }
+        self.load_from_reader(format, reader)
+    }
+
     /// Dumps a store graph into a file.
     ///
     /// Usage example:
@@ -855,7 +1464,10 @@ impl Store {
         Ok(())
     }
 
-    /// Clears a graph from this store.
+    /// Clears a graph from this store, like the SPARQL `CLEAR GRAPH` operation.
+    ///
+    /// All the quads of the graph are removed but a named graph is still listed by [`named_graphs`](Self::named_graphs).
+    /// Use [`remove_named_graph`](Self::remove_named_graph) to also remove the graph itself.
     ///
     /// Usage example:
     /// ```
@@ -889,7 +1501,10 @@ impl Store {
         }
     }
 
-    /// Removes a graph from this store.
+    /// Removes a graph from this store, like the SPARQL `DROP GRAPH` operation.
+    ///
+    /// All the quads of the graph are removed and the graph is not listed by [`named_graphs`](Self::named_graphs) anymore.
+    /// Use [`clear_graph`](Self::clear_graph) to remove the quads while keeping the graph.
     ///
     /// Usage example:
     /// ```
@@ -1239,7 +1854,10 @@ impl Store {
 
     /// Optimizes the database for future workload.
     ///
-    /// Useful to call after a batch upload or another similar operation.
+    /// It runs a full manual compaction of all the indexes
+    /// and drops the space used by removed quads.
+    /// Useful to call after a batch upload, a lot of removals or another similar operation.
+    /// [`Store::on_disk_size`] allows to check the space reclaimed.
     ///
     /// <div class="warning">Can take hours on huge databases.</div>
     #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
@@ -1247,6 +1865,25 @@ impl Store {
         self.storage.compact()
     }
 
+    /// Returns the size in bytes of the database files currently in use.
+    ///
+    /// Data that has not been flushed to disk yet is not counted.
+    /// It is always 0 for in-memory databases created using [`Store::new`].
+    ///
+    /// ```no_run
+    /// use oxigraph::store::Store;
+    ///
+    /// let store = Store::open("example.db")?;
+    /// let size_before = store.on_disk_size()?;
+    /// store.optimize()?;
+    /// println!("{} bytes reclaimed", size_before.saturating_sub(store.on_disk_size()?));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    pub fn on_disk_size(&self) -> Result<u64, StorageError> {
+        self.storage.on_disk_size()
+    }
+
     /// Creates database backup into the `target_directory`.
     ///
     /// After its creation, the backup is usable using [`Store::open`]
@@ -1259,17 +1896,47 @@ impl Store {
     ///
     /// <div class="warning">An error is raised if the `target_directory` already exists.</div>
     ///
-    /// If the target directory is in the same file system as the current database,
+    /// The backup is a consistent point-in-time snapshot:
+    /// it contains all the transactions committed before the call and none of the ones still in flight.
+    ///
+    /// Backups are not incremental: each backup is a full database on its own.
+    /// However, if the target directory is in the same file system as the current database,
     /// the database content will not be fully copied
     /// but hard links will be used to point to the original database immutable snapshots.
     /// This allows cheap regular backups.
     ///
+    /// Use [`Store::restore`] to rebuild a database from a backup.
+    ///
     /// If you want to move your data to another RDF storage system, you should have a look at the [`Store::dump_to_writer`] function instead.
     #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
     pub fn backup(&self, target_directory: impl AsRef<Path>) -> Result<(), StorageError> {
         self.storage.backup(target_directory.as_ref())
     }
 
+    /// Rebuilds a database into `target_directory` from a backup created with [`Store::backup`].
+    ///
+    /// The backup is left untouched and the restored database can then be opened with [`Store::open`].
+    /// Like for [`Store::backup`], hard links are used if both directories are in the same file system.
+    ///
+    /// The backup must not be opened read-write while it is restored.
+    ///
+    /// <div class="warning">An error is raised if the `target_directory` already exists.</div>
+    ///
+    /// ```no_run
+    /// use oxigraph::store::Store;
+    ///
+    /// Store::restore("backup.db", "example.db")?;
+    /// let store = Store::open("example.db")?;
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    pub fn restore(
+        backup_directory: impl AsRef<Path>,
+        target_directory: impl AsRef<Path>,
+    ) -> Result<(), StorageError> {
+        Storage::restore(backup_directory.as_ref(), target_directory.as_ref())
+    }
+
     /// Creates a bulk loader allowing to load at a lot of data quickly into the store.
     ///
     /// Usage example:
@@ -1499,6 +2166,18 @@ impl<'a> Transaction<'a> {
         self.inner.reader().len()
     }
 
+    /// Returns the number of quads in a graph of the store.
+    ///
+    /// <div class="warning">this function scans all the quads of the graph.</div>
+    pub fn len_for_graph<'b>(
+        &self,
+        graph_name: impl Into<GraphNameRef<'b>>,
+    ) -> Result<usize, StorageError> {
+        self.inner
+            .reader()
+            .len_for_graph(&EncodedTerm::from(graph_name.into()))
+    }
+
     /// Returns if the store is empty.
     pub fn is_empty(&self) -> Result<bool, StorageError> {
         self.inner.reader().is_empty()
@@ -2309,6 +2988,69 @@ impl BulkLoader<'_> {
         self
     }
 
+    /// Sets a timeout for the whole bulk loading, starting when this method is called.
+    ///
+    /// The deadline is checked before writing each batch, while waiting for the background writes and on [`commit`](Self::commit).
+    /// If it is exceeded, the loading methods fail with [`LoaderError::Timeout`]
+    /// without saving anything: the store is left as it was before the loading.
+    /// [`load_quads`](Self::load_quads), [`load_ok_quads`](Self::load_ok_quads) and [`commit`](Self::commit)
+    /// return a [`StorageError::Io`] error of kind [`TimedOut`](std::io::ErrorKind::TimedOut) instead,
+    /// it converts into [`LoaderError::Timeout`].
+    /// If [`without_atomicity`](Self::without_atomicity) is used, the data already saved during the loading is kept.
+    ///
+    /// ```
+    /// use oxigraph::io::RdfFormat;
+    /// use oxigraph::store::{LoaderError, Store};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let store = Store::new()?;
+    /// let mut loader = store.bulk_loader().with_timeout(Duration::from_millis(10));
+    /// loader.load_from_slice(
+    ///     RdfFormat::NTriples,
+    ///     "<http://example.com/s> <http://example.com/p> <http://example.com/o> .",
+    /// )?;
+    /// sleep(Duration::from_millis(20));
+    /// assert!(matches!(
+    ///     loader.commit().map_err(LoaderError::from),
+    ///     Err(LoaderError::Timeout(_))
+    /// ));
+    /// assert!(store.is_empty()?);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.storage = self.storage.with_timeout(timeout);
+        self
+    }
+
+    /// Checks before writing each quad if it is already in the store and skips it if it is.
+    ///
+    /// The bulk loader output is always deduplicated: loading again a quad is a no-op for the store content.
+    /// This option avoids writing again the existing quads and counts them in [`skipped_quads`](Self::skipped_quads).
+    /// Quads repeated in the loaded data are skipped and counted too.
+    /// With the RocksDB storage, each quad costs a lookup in the store
+    /// and the quads loaded so far are kept in memory until the end of the loading.
+    ///
+    /// ```
+    /// use oxigraph::io::RdfFormat;
+    /// use oxigraph::store::Store;
+    ///
+    /// let file = "<http://example.com/s> <http://example.com/p> <http://example.com/o> .";
+    /// let store = Store::new()?;
+    /// store.load_from_slice(RdfFormat::NTriples, file)?;
+    ///
+    /// let mut loader = store.bulk_loader().with_skip_existing(true);
+    /// loader.load_from_slice(RdfFormat::NTriples, file)?;
+    /// assert_eq!(loader.skipped_quads(), 1);
+    /// loader.commit()?;
+    /// assert_eq!(store.len()?, 1);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn with_skip_existing(mut self, skip_existing: bool) -> Self {
+        self.storage = self.storage.with_skip_existing(skip_existing);
+        self
+    }
+
     /// Adds a `callback` evaluated from time to time with the number of loaded triples.
     pub fn on_progress(mut self, callback: impl Fn(u64) + Send + Sync + 'static) -> Self {
         self.storage = self.storage.on_progress(callback);
@@ -2724,16 +3466,168 @@ impl BulkLoader<'_> {
         Ok(())
     }
 
+    /// Returns the number of quads skipped so far because they were already in the store or already loaded.
+    ///
+    /// It is always 0 if [`with_skip_existing`](Self::with_skip_existing) is not enabled.
+    pub fn skipped_quads(&self) -> u64 {
+        self.storage.skipped_quads()
+    }
+
     /// Saves all the quads loaded using the bulk loader into the store.
+    ///
+    /// If a timeout has been set with [`with_timeout`](Self::with_timeout) and is exceeded,
+    /// it fails with a [`StorageError::Io`] error of kind [`TimedOut`](std::io::ErrorKind::TimedOut)
+    /// that converts into [`LoaderError::Timeout`] and the loaded quads are discarded.
     pub fn commit(self) -> Result<(), StorageError> {
         self.storage.commit()
     }
 }
 
+/// Splits triples into the ones without blank nodes and the groups of triples connected by blank nodes.
+fn split_blank_node_components(
+    triples: impl IntoIterator<Item = Triple>,
+) -> (Vec<Triple>, Vec<Graph>) {
+    let mut ground = Vec::new();
+    let mut with_blank_nodes = Vec::new();
+    let mut triples_by_blank_node = HashMap::<BlankNode, Vec<usize>>::new();
+    for triple in triples {
+        let blank_nodes = triple_blank_nodes(&triple);
+        if blank_nodes.is_empty() {
+            ground.push(triple);
+        } else {
+            for blank_node in blank_nodes {
+                triples_by_blank_node
+                    .entry(blank_node)
+                    .or_default()
+                    .push(with_blank_nodes.len());
+            }
+            with_blank_nodes.push(triple);
+        }
+    }
+
+    let mut visited = vec![false; with_blank_nodes.len()];
+    let mut components = Vec::new();
+    for start in 0..with_blank_nodes.len() {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut component = Graph::new();
+        let mut to_visit = vec![start];
+        while let Some(i) = to_visit.pop() {
+            let triple = &with_blank_nodes[i];
+            for blank_node in triple_blank_nodes(triple) {
+                for &j in &triples_by_blank_node[&blank_node] {
+                    if !visited[j] {
+                        visited[j] = true;
+                        to_visit.push(j);
+                    }
+                }
+            }
+            component.insert(triple);
+        }
+        components.push(component);
+    }
+    (ground, components)
+}
+
+fn triple_blank_nodes(triple: &Triple) -> Vec<BlankNode> {
+    let mut blank_nodes = Vec::new();
+    if let NamedOrBlankNode::BlankNode(b) = &triple.subject {
+        blank_nodes.push(b.clone());
+    }
+    if let Term::BlankNode(b) = &triple.object {
+        blank_nodes.push(b.clone());
+    }
+    blank_nodes
+}
+
+/// Returns the blank nodes of the graph that may belong to a group isomorphic to `component`.
+///
+/// They are the blank nodes matching the blank nodes of one of the triples of the component,
+/// picked to have as many ground terms as possible to make the lookup selective.
+fn candidate_blank_nodes(
+    transaction: &Transaction<'_>,
+    component: &Graph,
+    graph_name: GraphNameRef<'_>,
+) -> Result<Vec<BlankNode>, StorageError> {
+    let Some(anchor) = component.iter().max_by_key(|t| {
+        usize::from(!t.subject.is_blank_node()) + usize::from(!t.object.is_blank_node())
+    }) else {
+        return Ok(Vec::new());
+    };
+    let subject = (!anchor.subject.is_blank_node()).then_some(anchor.subject);
+    let object = (!anchor.object.is_blank_node()).then_some(anchor.object);
+    let mut blank_nodes = Vec::new();
+    for quad in
+        transaction.quads_for_pattern(subject, Some(anchor.predicate), object, Some(graph_name))
+    {
+        let quad = quad?;
+        if subject.is_none() {
+            let NamedOrBlankNode::BlankNode(b) = quad.subject else {
+                continue;
+            };
+            if object.is_none() && !quad.object.is_blank_node() {
+                continue;
+            }
+            blank_nodes.push(b);
+        } else if let Term::BlankNode(b) = quad.object {
+            blank_nodes.push(b);
+        }
+    }
+    Ok(blank_nodes)
+}
+
+/// Returns the triples of the graph connected to `blank_node` through shared blank nodes.
+fn blank_node_component(
+    transaction: &Transaction<'_>,
+    blank_node: BlankNode,
+    graph_name: GraphNameRef<'_>,
+) -> Result<Graph, StorageError> {
+    let mut component = Graph::new();
+    let mut visited = HashSet::from([blank_node.clone()]);
+    let mut to_visit = vec![blank_node];
+    while let Some(blank_node) = to_visit.pop() {
+        let triples = transaction
+            .quads_for_pattern(
+                Some(blank_node.as_ref().into()),
+                None,
+                None,
+                Some(graph_name),
+            )
+            .chain(transaction.quads_for_pattern(
+                None,
+                None,
+                Some(blank_node.as_ref().into()),
+                Some(graph_name),
+            ));
+        for quad in triples {
+            let triple = Triple::from(quad?);
+            for other in triple_blank_nodes(&triple) {
+                if visited.insert(other.clone()) {
+                    to_visit.push(other);
+                }
+            }
+            component.insert(&triple);
+        }
+    }
+    Ok(component)
+}
+
+/// Builds a key that is equal for isomorphic graphs.
+fn canonical_component_key(mut component: Graph) -> Vec<String> {
+    component.canonicalize(CanonicalizationAlgorithm::Unstable);
+    let mut key = component.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+    key.sort_unstable();
+    key
+}
+
 #[cfg(test)]
 #[expect(clippy::panic_in_result_fn)]
 mod tests {
     use super::*;
+    use crate::sparql::CancellationToken;
+    use std::error::Error;
 
     #[test]
     fn test_send_sync() {
@@ -2998,4 +3892,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn cancelled_queries_release_iterators() -> Result<(), Box<dyn Error>> {
+        check_cancelled_queries_release_iterators(&Store::new()?)
+    }
+
+    #[test]
+    #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+    fn cancelled_queries_release_iterators_on_disk() -> Result<(), Box<dyn Error>> {
+        let dir = tempfile::TempDir::new()?;
+        check_cancelled_queries_release_iterators(&Store::open(&dir)?)
+    }
+
+    fn check_cancelled_queries_release_iterators(store: &Store) -> Result<(), Box<dyn Error>> {
+        for i in 0..10 {
+            let ex = NamedNode::new_unchecked(format!("http://example.com/{i}"));
+            store.insert(QuadRef::new(&ex, &ex, &ex, GraphNameRef::DefaultGraph))?;
+        }
+        for _ in 0..100 {
+            let cancellation_token = CancellationToken::new();
+            let QueryResults::Solutions(mut solutions) = SparqlEvaluator::new()
+                .with_cancellation_token(cancellation_token.clone())
+                .parse_query("SELECT * WHERE { ?s ?p ?o . OPTIONAL { ?o ?p2 ?o2 } }")?
+                .on_store(store)
+                .execute()?
+            else {
+                return Err("SELECT query should return solutions".into());
+            };
+            solutions.next().transpose()?;
+            assert!(store.storage.active_iterators() > 0);
+            cancellation_token.cancel();
+            assert!(matches!(
+                solutions.next(),
+                Some(Err(QueryEvaluationError::Cancelled))
+            ));
+            drop(solutions);
+            assert_eq!(store.storage.active_iterators(), 0);
+        }
+        Ok(())
+    }
 }
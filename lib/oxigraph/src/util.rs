@@ -0,0 +1,224 @@
+//! Helpers to render RDF terms compactly in logs and error messages.
+
+use crate::model::vocab::{rdf, xsd};
+use crate::model::{GraphNameRef, LiteralRef, NamedNodeRef, QuadRef, TermRef};
+
+/// Renders a term in a compact Turtle-like syntax.
+///
+/// IRIs are shortened with the longest matching prefix of `prefixes`, given as `(name, IRI)` pairs.
+/// If the rendering is longer than `max_len` characters, it is truncated and ends with `…` (it is empty if `max_len` is 0).
+///
+/// ```
+/// use oxigraph::model::{Literal, NamedNode};
+/// use oxigraph::util::abbreviate;
+///
+/// let prefixes = [("schema", "http://schema.org/")];
+/// assert_eq!(
+///     abbreviate(&NamedNode::new("http://schema.org/name")?, &prefixes, 80),
+///     "schema:name"
+/// );
+/// assert_eq!(
+///     abbreviate(&Literal::new_simple_literal("a long description"), &prefixes, 10),
+///     "\"a long d…"
+/// );
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+pub fn abbreviate<'a>(
+    term: impl Into<TermRef<'a>>,
+    prefixes: &[(&str, &str)],
+    max_len: usize,
+) -> String {
+    let mut output = String::new();
+    write_term(&mut output, term.into(), prefixes);
+    truncate(output, max_len)
+}
+
+/// Renders a quad in a compact N-Quads-like syntax.
+///
+/// Each term is rendered like with [`abbreviate`] and the whole quad is truncated to `max_len` characters.
+///
+/// ```
+/// use oxigraph::model::{Literal, NamedNode, Quad};
+/// use oxigraph::util::abbreviate_quad;
+///
+/// let ex = NamedNode::new("http://example.com/s")?;
+/// let quad = Quad::new(ex.clone(), ex.clone(), Literal::from(1), ex);
+/// assert_eq!(
+///     abbreviate_quad(&quad, &[("ex", "http://example.com/")], 80),
+///     "ex:s ex:s 1 ex:s"
+/// );
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+pub fn abbreviate_quad<'a>(
+    quad: impl Into<QuadRef<'a>>,
+    prefixes: &[(&str, &str)],
+    max_len: usize,
+) -> String {
+    let quad = quad.into();
+    let mut output = String::new();
+    write_term(&mut output, quad.subject.into(), prefixes);
+    output.push(' ');
+    if quad.predicate == rdf::TYPE {
+        output.push('a');
+    } else {
+        write_named_node(&mut output, quad.predicate, prefixes);
+    }
+    output.push(' ');
+    write_term(&mut output, quad.object, prefixes);
+    match quad.graph_name {
+        GraphNameRef::NamedNode(graph_name) => {
+            output.push(' ');
+            write_named_node(&mut output, graph_name, prefixes);
+        }
+        GraphNameRef::BlankNode(graph_name) => {
+            output.push(' ');
+            output.push_str(&graph_name.to_string());
+        }
+        GraphNameRef::DefaultGraph => (),
+    }
+    truncate(output, max_len)
+}
+
+fn write_term(output: &mut String, term: TermRef<'_>, prefixes: &[(&str, &str)]) {
+    match term {
+        TermRef::NamedNode(node) => write_named_node(output, node, prefixes),
+        TermRef::BlankNode(node) => {
+            output.push_str(&node.to_string());
+        }
+        TermRef::Literal(literal) => write_literal(output, literal, prefixes),
+        #[cfg(feature = "rdf-12")]
+        TermRef::Triple(triple) => {
+            output.push_str("<<( ");
+            write_term(output, triple.subject.as_ref().into(), prefixes);
+            output.push(' ');
+            write_named_node(output, triple.predicate.as_ref(), prefixes);
+            output.push(' ');
+            write_term(output, triple.object.as_ref(), prefixes);
+            output.push_str(" )>>");
+        }
+    }
+}
+
+fn write_named_node(output: &mut String, node: NamedNodeRef<'_>, prefixes: &[(&str, &str)]) {
+    let prefixed = prefixes
+        .iter()
+        .filter_map(|(name, iri)| Some((name, node.as_str().strip_prefix(iri)?)))
+        .filter(|(_, local)| is_simple_local_name(local))
+        .min_by_key(|(_, local)| local.len());
+    if let Some((name, local)) = prefixed {
+        output.push_str(name);
+        output.push(':');
+        output.push_str(local);
+    } else {
+        output.push_str(&node.to_string());
+    }
+}
+
+fn write_literal(output: &mut String, literal: LiteralRef<'_>, prefixes: &[(&str, &str)]) {
+    let value = literal.value();
+    let datatype = literal.datatype();
+    let is_bare = if datatype == xsd::INTEGER {
+        let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+        !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit())
+    } else {
+        datatype == xsd::BOOLEAN && matches!(value, "true" | "false")
+    };
+    if is_bare {
+        output.push_str(value);
+        return;
+    }
+    // We reuse the N-Triples escaping of the value
+    output.push_str(&LiteralRef::new_simple_literal(value).to_string());
+    if let Some(language) = literal.language() {
+        output.push('@');
+        output.push_str(language);
+    } else if datatype != xsd::STRING {
+        output.push_str("^^");
+        write_named_node(output, datatype, prefixes);
+    }
+}
+
+/// Checks if the local name could be written after a prefix without escaping.
+fn is_simple_local_name(local: &str) -> bool {
+    !local.ends_with('.')
+        && !local.starts_with(['.', '-'])
+        && local
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn truncate(mut output: String, max_len: usize) -> String {
+    if let Some((end, _)) = output.char_indices().nth(max_len) {
+        // We keep room for the ellipsis
+        let end = output[..end]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i);
+        output.truncate(end);
+        if max_len > 0 {
+            output.push('\u{2026}'); // …
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Literal, NamedNode};
+
+    #[test]
+    fn test_abbreviate_iri_with_prefix() {
+        let iri = NamedNode::new_unchecked(
+            "http://www.example.com/a/very/long/path/to/the/vocabulary/of/the/dataset#Thing",
+        );
+        let prefixes = [
+            ("ex", "http://www.example.com/"),
+            (
+                "voc",
+                "http://www.example.com/a/very/long/path/to/the/vocabulary/of/the/dataset#",
+            ),
+        ];
+        assert_eq!(abbreviate(&iri, &prefixes, 80), "voc:Thing");
+        assert_eq!(
+            abbreviate(&iri, &[], 200),
+            "<http://www.example.com/a/very/long/path/to/the/vocabulary/of/the/dataset#Thing>"
+        );
+        // Local names that would need escaping are not abbreviated
+        assert_eq!(
+            abbreviate(
+                NamedNodeRef::new_unchecked("http://www.example.com/a/b"),
+                &prefixes,
+                80
+            ),
+            "<http://www.example.com/a/b>"
+        );
+    }
+
+    #[test]
+    fn test_abbreviate_truncates_long_literal() {
+        let literal = Literal::new_simple_literal("\u{e9}".repeat(1000));
+        let abbreviated = abbreviate(&literal, &[], 20);
+        assert_eq!(abbreviated.chars().count(), 20);
+        assert_eq!(abbreviated, format!("\"{}\u{2026}", "\u{e9}".repeat(18)));
+        assert_eq!(abbreviate(&literal, &[], 1), "\u{2026}");
+        assert_eq!(abbreviate(&literal, &[], 0), "");
+        // Short literals are kept as is
+        assert_eq!(
+            abbreviate(
+                &Literal::new_typed_literal("2020-01-01", xsd::DATE),
+                &[("xsd", "http://www.w3.org/2001/XMLSchema#")],
+                80
+            ),
+            "\"2020-01-01\"^^xsd:date"
+        );
+        assert_eq!(
+            abbreviate(
+                &Literal::new_language_tagged_literal_unchecked("chat", "fr"),
+                &[],
+                80
+            ),
+            "\"chat\"@fr"
+        );
+    }
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "bench")]
+
+use oxigraph::bench::{QUERIES, generate_graph, generate_ntriples, generate_quads, person_shapes};
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::sparql::SparqlEvaluator;
+use oxigraph::store::Store;
+use sparshacl::ShaclValidator;
+
+#[test]
+fn test_generators_produce_requested_sizes() {
+    for size in [0, 1, 3, 4, 5, 1_000, 1_003] {
+        let quads = generate_quads(size);
+        assert_eq!(quads.len(), size);
+        let store = Store::new().unwrap();
+        store.extend(quads).unwrap();
+        assert_eq!(
+            store.len().unwrap(),
+            size,
+            "generated quads must be distinct"
+        );
+        assert_eq!(generate_graph(size).len(), size);
+        let ntriples = generate_ntriples(size).unwrap();
+        assert_eq!(
+            RdfParser::from_format(RdfFormat::NTriples)
+                .for_slice(&ntriples)
+                .count(),
+            size
+        );
+    }
+}
+
+#[test]
+fn test_generators_are_deterministic() {
+    assert_eq!(generate_quads(100), generate_quads(100));
+}
+
+#[test]
+fn test_generated_data_conforms_to_shapes() {
+    let report = ShaclValidator::new(person_shapes().unwrap())
+        .validate(&generate_graph(1_000))
+        .unwrap();
+    assert!(report.conforms());
+}
+
+#[test]
+fn test_benchmark_queries_are_valid() {
+    let store = Store::new().unwrap();
+    store.extend(generate_quads(1_000)).unwrap();
+    for (pattern, query) in QUERIES {
+        SparqlEvaluator::new()
+            .parse_query(query)
+            .unwrap()
+            .on_store(&store)
+            .execute()
+            .unwrap_or_else(|e| panic!("{pattern} query failed: {e}"));
+    }
+}
@@ -8,6 +8,7 @@
 use oxigraph::model::*;
 use oxigraph::sparql::{CancellationToken, QueryResults, SparqlEvaluator};
 use oxigraph::store::Store;
+use sparshacl::{ShaclValidator, ShapesGraph};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -289,30 +290,28 @@ fn test_result_limit_via_sparql() -> Result<(), Box<dyn std::error::Error>> {
 
 /// Test SHACL validation recursion depth limit.
 ///
-/// **Status**: PASS - MAX_RECURSION_DEPTH = 50 is enforced.
-/// **Gap**: Not configurable, hardcoded in validator.
+/// **Status**: PASS - The recursion depth is limited to 50 by default.
+/// It is configurable with `ShaclValidator::with_max_recursion_depth`.
 #[test]
 fn test_shacl_recursion_limit_enforced() -> Result<(), Box<dyn std::error::Error>> {
-    // DOCUMENTED: SHACL validator has MAX_RECURSION_DEPTH = 50 (hardcoded)
-    // See: lib/sparshacl/src/validator.rs:21
+    let validator = ShaclValidator::new(ShapesGraph::new()).with_max_recursion_depth(10);
+    assert!(validator.validate(&Graph::new())?.conforms());
 
-    // This is enforced but not configurable
-    eprintln!("SHACL MAX_RECURSION_DEPTH = 50 (hardcoded, not configurable)");
+    eprintln!("SHACL recursion depth limit = 50 by default, configurable");
 
     Ok(())
 }
 
-/// Test that SHACL validation has no timeout configuration.
+/// Test that SHACL validation has a timeout configuration.
 ///
-/// **Status**: FAIL - No timeout configuration exists.
-/// **Gap**: Complex shapes can run indefinitely.
-/// **Recommendation**: Wrap validation in application-level timeout.
+/// **Status**: PASS - `ShaclValidator::with_timeout` aborts validation with `ShaclError::Timeout`.
 #[test]
-fn test_shacl_validation_timeout_not_available() -> Result<(), Box<dyn std::error::Error>> {
-    // DOCUMENTED GAP: ShaclValidator::validate() has no timeout parameter
-    // Applications must implement timeout wrapper pattern similar to queries
+fn test_shacl_validation_timeout_available() -> Result<(), Box<dyn std::error::Error>> {
+    // The deadline is checked before each focus node, before each constraint and while following `*` and `+` paths
+    let validator = ShaclValidator::new(ShapesGraph::new()).with_timeout(Duration::from_secs(5));
+    assert!(validator.validate(&Graph::new())?.conforms());
 
-    eprintln!("SHACL validation has no built-in timeout configuration");
+    eprintln!("SHACL validation timeout is configurable with ShaclValidator::with_timeout");
 
     Ok(())
 }
@@ -442,14 +441,14 @@ fn test_resource_limits_summary() {
     eprintln!("  - CancellationToken for manual query cancellation");
     eprintln!("  - HTTP SERVICE timeout (federated queries only)");
     eprintln!("  - Result streaming (memory-bounded iteration)");
-    eprintln!("  - SHACL recursion depth limit (hardcoded: 50)");
+    eprintln!("  - SHACL recursion depth limit (default: 50, configurable)");
+    eprintln!("  - SHACL validation timeout");
     eprintln!("  - BulkLoader memory/thread configuration");
 
     eprintln!("\n❌ GAPS:");
     eprintln!("  - No default query timeout");
     eprintln!("  - No configurable query timeout");
     eprintln!("  - No query memory limits");
-    eprintln!("  - No SHACL validation timeout");
     eprintln!("  - No bulk loader timeout");
 
     eprintln!("\n📋 RECOMMENDATIONS:");
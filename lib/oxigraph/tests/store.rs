@@ -1,10 +1,24 @@
 #![cfg(test)]
 #![allow(clippy::panic_in_result_fn)]
 
-use oxigraph::io::RdfFormat;
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::metrics::MetricsCollector;
 use oxigraph::model::vocab::{rdf, xsd};
 use oxigraph::model::*;
-use oxigraph::store::Store;
+#[cfg(feature = "shacl")]
+use oxigraph::shacl::{ShaclValidator, ShapesGraph};
+use oxigraph::sparql::results::QueryResultsFormat;
+use oxigraph::sparql::{
+    BoundPreparedSparqlQuery, CancellationToken, PreparedSparqlQuery, QueryDataset,
+    QueryEvaluationError, QueryResults, QuerySolution, QuerySolutionIter, SparqlEvaluator,
+};
+#[cfg(not(target_family = "wasm"))]
+use oxigraph::store::StorageError;
+use oxigraph::store::{LoaderError, SerializerError, Store};
+use oxiri::Iri;
+use spargebra::algebra::GraphPattern;
+#[cfg(not(target_family = "wasm"))]
+use std::collections::HashSet;
 use std::error::Error;
 #[cfg(all(target_os = "linux", feature = "rocksdb"))]
 use std::fs::remove_dir_all;
@@ -17,18 +31,15 @@ use std::fs::{File, create_dir_all, read_dir, remove_dir};
     feature = "rocksdb"
 ))]
 use std::fs::{read, write};
-#[cfg(all(
-    target_os = "linux",
-    target_pointer_width = "64",
-    target_endian = "little",
-    feature = "rocksdb"
-))]
+#[cfg(not(target_family = "wasm"))]
 use std::io;
 #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
 use std::io::Write;
 use std::iter::empty;
 #[cfg(all(target_os = "linux", feature = "rocksdb"))]
 use std::iter::once;
+use std::mem::take;
+use std::num::ParseIntError;
 #[cfg(all(
     target_os = "linux",
     target_pointer_width = "64",
@@ -36,6 +47,11 @@ use std::iter::once;
     feature = "rocksdb"
 ))]
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+#[cfg(not(target_family = "wasm"))]
+use std::thread;
+use std::time::Duration;
 #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
 use tempfile::TempDir;
 
@@ -177,6 +193,53 @@ fn test_bulk_load_graph_on_disk() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_bulk_load_skip_existing() -> Result<(), Box<dyn Error>> {
+    check_bulk_load_skip_existing(&Store::new()?)
+}
+
+#[test]
+#[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+fn test_bulk_load_skip_existing_on_disk() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    check_bulk_load_skip_existing(&Store::open(&dir)?)
+}
+
+fn check_bulk_load_skip_existing(store: &Store) -> Result<(), Box<dyn Error>> {
+    let mut loader = store.bulk_loader().with_skip_existing(true);
+    loader.load_from_slice(RdfFormat::Turtle, DATA.as_bytes())?;
+    assert_eq!(loader.skipped_quads(), 0);
+    loader.commit()?;
+
+    // Loading again the same data is a no-op
+    let extra = Quad::new(
+        NamedNode::new_unchecked("http://example.com/s"),
+        NamedNode::new_unchecked("http://example.com/p"),
+        NamedNode::new_unchecked("http://example.com/o"),
+        GraphName::DefaultGraph,
+    );
+    let mut loader = store.bulk_loader().with_skip_existing(true);
+    loader.load_from_slice(RdfFormat::Turtle, DATA.as_bytes())?;
+    // The repeated quad is in a different batch
+    loader.load_quads([extra.clone()])?;
+    loader.load_quads([extra])?;
+    assert_eq!(
+        loader.skipped_quads(),
+        u64::try_from(NUMBER_OF_TRIPLES)? + 1
+    );
+    loader.commit()?;
+    assert_eq!(store.len()?, NUMBER_OF_TRIPLES + 1);
+
+    // Duplicates are only counted with the option
+    let mut loader = store.bulk_loader();
+    loader.load_from_slice(RdfFormat::Turtle, DATA.as_bytes())?;
+    assert_eq!(loader.skipped_quads(), 0);
+    loader.commit()?;
+    assert_eq!(store.len()?, NUMBER_OF_TRIPLES + 1);
+    store.validate()?;
+    Ok(())
+}
+
 #[test]
 fn test_bulk_load_graph_lenient() -> Result<(), Box<dyn Error>> {
     let store = Store::new()?;
@@ -230,6 +293,755 @@ fn test_bulk_load_rollback() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_transaction_query() -> Result<(), Box<dyn Error>> {
+    check_transaction_query(&Store::new()?)
+}
+
+#[test]
+#[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+fn test_transaction_query_on_disk() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    check_transaction_query(&Store::open(&dir)?)
+}
+
+#[test]
+#[cfg(not(target_family = "wasm"))]
+fn test_transaction_with_retry() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let ex = NamedNodeRef::new_unchecked("http://example.com");
+    let mut attempts = 0;
+    let len = store.transaction_with_retry(3, Duration::from_millis(1), |transaction| {
+        attempts += 1;
+        transaction.insert(QuadRef::new(
+            ex,
+            ex,
+            &Literal::from(attempts),
+            GraphNameRef::DefaultGraph,
+        ));
+        if attempts < 3 {
+            // Simulates a conflict with a concurrent writer
+            return Err(io::Error::new(io::ErrorKind::ResourceBusy, "conflict").into());
+        }
+        transaction.len()
+    })?;
+    // Only the last attempt is committed, the previous ones are rolled back
+    assert_eq!(attempts, 3);
+    assert_eq!(len, 1);
+    assert!(store.contains(QuadRef::new(
+        ex,
+        ex,
+        &Literal::from(3),
+        GraphNameRef::DefaultGraph
+    ))?);
+    assert_eq!(store.len()?, 1);
+
+    // The last transient error is returned once the retries are exhausted
+    let mut attempts = 0;
+    let error = store
+        .transaction_with_retry(2, Duration::ZERO, |_| {
+            attempts += 1;
+            Err::<(), _>(io::Error::from(io::ErrorKind::TimedOut).into())
+        })
+        .unwrap_err();
+    assert!(error.is_transient());
+    assert_eq!(attempts, 3);
+
+    // Other errors are not retried
+    let mut attempts = 0;
+    let error = store
+        .transaction_with_retry(2, Duration::ZERO, |_| {
+            attempts += 1;
+            Err::<(), _>(StorageError::ReadOnly)
+        })
+        .unwrap_err();
+    assert!(!error.is_transient());
+    assert_eq!(attempts, 1);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(target_family = "wasm"))]
+fn test_transaction_with_retry_concurrent_writers() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let ex = NamedNodeRef::new_unchecked("http://example.com");
+    let counter = |value: i64| Quad::new(ex, ex, Literal::from(value), GraphName::DefaultGraph);
+    store.insert(&counter(0))?;
+    // All the writers read and update the same quad
+    thread::scope(|s| -> Result<(), Box<dyn Error>> {
+        let writers = (0..4)
+            .map(|_| {
+                s.spawn(|| -> Result<(), StorageError> {
+                    for _ in 0..25 {
+                        store.transaction_with_retry(
+                            3,
+                            Duration::from_millis(1),
+                            |transaction| {
+                                let current = transaction
+                                    .quads_for_pattern(Some(ex.into()), Some(ex), None, None)
+                                    .next()
+                                    .transpose()?
+                                    .ok_or_else(|| io::Error::other("the counter should exist"))?;
+                                let Term::Literal(value) = &current.object else {
+                                    unreachable!("the counter is a literal")
+                                };
+                                let value =
+                                    value.value().parse::<i64>().map_err(io::Error::other)?;
+                                transaction.remove(&current);
+                                transaction.insert(&counter(value + 1));
+                                Ok(())
+                            },
+                        )?;
+                    }
+                    Ok(())
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().map_err(|_| "a writer thread panicked")??;
+        }
+        Ok(())
+    })?;
+    // No update is lost
+    assert_eq!(store.iter().collect::<Result<Vec<_>, _>>()?, [counter(100)]);
+    Ok(())
+}
+
+fn check_transaction_query(store: &Store) -> Result<(), Box<dyn Error>> {
+    let ex = NamedNodeRef::new_unchecked("http://example.com");
+    let ask = SparqlEvaluator::new()
+        .parse_query("ASK { <http://example.com> <http://example.com> <http://example.com> }")?;
+
+    let mut transaction = store.start_transaction()?;
+    transaction.insert(QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph));
+    // The transaction sees its own writes but the store does not
+    assert!(matches!(
+        ask.clone().on_transaction(&transaction).execute()?,
+        QueryResults::Boolean(true)
+    ));
+    assert!(matches!(
+        ask.clone().on_store(store).execute()?,
+        QueryResults::Boolean(false)
+    ));
+
+    // Updates conditioned on a query are applied in the same transaction
+    SparqlEvaluator::new()
+        .parse_update(
+            "INSERT { ?s <http://example.com/p> ?o } WHERE { ?s <http://example.com> ?o }",
+        )?
+        .on_transaction(&mut transaction)
+        .execute()?;
+    assert!(transaction.contains(QuadRef::new(
+        ex,
+        NamedNodeRef::new_unchecked("http://example.com/p"),
+        ex,
+        GraphNameRef::DefaultGraph
+    ))?);
+
+    transaction.rollback();
+    assert!(matches!(
+        ask.on_store(store).execute()?,
+        QueryResults::Boolean(false)
+    ));
+    assert!(store.is_empty()?);
+    Ok(())
+}
+
+#[test]
+fn test_write_query_results() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    store.load_from_reader(
+        RdfFormat::NTriples,
+        b"<http://example.com/s> <http://example.com/p> \"o\" .\n".as_slice(),
+    )?;
+    let query = |query| -> Result<QueryResults<'_>, Box<dyn Error>> {
+        Ok(SparqlEvaluator::new()
+            .parse_query(query)?
+            .on_store(&store)
+            .execute()?)
+    };
+
+    assert_eq!(
+        query("CONSTRUCT WHERE { ?s ?p ?o }")?.write_graph(Vec::new(), RdfFormat::NTriples)?,
+        b"<http://example.com/s> <http://example.com/p> \"o\" .\n"
+    );
+    assert_eq!(
+        query("CONSTRUCT WHERE { ?s ?p ?o }")?.write(Vec::new(), QueryResultsFormat::Csv)?,
+        b"subject,predicate,object\r\nhttp://example.com/s,http://example.com/p,o\r\n"
+    );
+    assert_eq!(
+        query("SELECT ?o WHERE { ?s ?p ?o }")?.write(Vec::new(), QueryResultsFormat::Tsv)?,
+        b"?o\n\"o\"\n"
+    );
+    assert_eq!(
+        query("ASK { ?s ?p ?o }")?.write(Vec::new(), QueryResultsFormat::Json)?,
+        br#"{"head":{},"boolean":true}"#
+    );
+    assert!(matches!(
+        query("ASK { ?s ?p ?o }")?.write_graph(Vec::new(), RdfFormat::Turtle),
+        Err(QueryEvaluationError::NotAGraph)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_read_query_results() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    store.load_from_reader(
+        RdfFormat::Turtle,
+        br#"<http://example.com/s> <http://example.com/p> "o"@en , _:b , 1 , <http://example.com/o> ."#
+            .as_slice(),
+    )?;
+    let query = SparqlEvaluator::new().parse_query(
+        "SELECT ?s ?o ?unbound WHERE { ?s ?p ?o OPTIONAL { ?o ?p ?unbound } } ORDER BY ?o",
+    )?;
+    let QueryResults::Solutions(solutions) = query.clone().on_store(&store).execute()? else {
+        return Err("SELECT query should return solutions".into());
+    };
+    let expected = solutions.collect::<Result<Vec<_>, _>>()?;
+    for format in [
+        QueryResultsFormat::Json,
+        QueryResultsFormat::Xml,
+        QueryResultsFormat::Tsv,
+    ] {
+        let serialized = query
+            .clone()
+            .on_store(&store)
+            .execute()?
+            .write(Vec::new(), format)?;
+        let QueryResults::Solutions(solutions) = QueryResults::read(serialized.as_slice(), format)?
+        else {
+            return Err("Solutions should be parsed back".into());
+        };
+        assert_eq!(solutions.variables(), expected[0].variables());
+        assert_eq!(solutions.collect::<Result<Vec<_>, _>>()?, expected);
+
+        let serialized = QueryResults::Boolean(true).write(Vec::new(), format)?;
+        assert!(matches!(
+            QueryResults::read(serialized.as_slice(), format)?,
+            QueryResults::Boolean(true)
+        ));
+    }
+    // CSV is lossy
+    assert!(QueryResults::read(b"s\r\n".as_slice(), QueryResultsFormat::Csv).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_store_service_handler() -> Result<(), Box<dyn Error>> {
+    let local = Store::new()?;
+    local.load_from_reader(
+        RdfFormat::Turtle,
+        br#"@prefix ex: <http://example.com/> . ex:a ex:name "a" . ex:b ex:name "b" . ex:c ex:name "c" ."#
+            .as_slice(),
+    )?;
+    let remote = Store::new()?;
+    remote.load_from_reader(
+        RdfFormat::Turtle,
+        b"@prefix ex: <http://example.com/> . ex:a ex:age 1 . ex:b ex:age 2 . ex:d ex:age 4 ."
+            .as_slice(),
+    )?;
+    let service = NamedNode::new("http://example.com/service")?;
+    let evaluator = SparqlEvaluator::new().with_service_handler(service.clone(), remote);
+    let QueryResults::Solutions(solutions) = evaluator
+        .parse_query(
+            "PREFIX ex: <http://example.com/>
+            SELECT ?name ?age WHERE {
+                ?s ex:name ?name
+                OPTIONAL { SERVICE <http://example.com/service> { ?s ex:age ?age } }
+            } ORDER BY ?name",
+        )?
+        .on_store(&local)
+        .execute()?
+    else {
+        return Err("SELECT query should return solutions".into());
+    };
+    let solutions = solutions
+        .map(|s| {
+            let s = s?;
+            Ok((s.get("name").cloned(), s.get("age").cloned()))
+        })
+        .collect::<Result<Vec<_>, QueryEvaluationError>>()?;
+    assert_eq!(
+        solutions,
+        [
+            (
+                Some(Literal::from("a").into()),
+                Some(Literal::from(1).into())
+            ),
+            (
+                Some(Literal::from("b").into()),
+                Some(Literal::from(2).into())
+            ),
+            (Some(Literal::from("c").into()), None),
+        ]
+    );
+
+    // Closures can be used as default service handlers
+    let evaluator = SparqlEvaluator::new().with_default_service_handler(
+        |name: &NamedNode, _: &GraphPattern, _: Option<&Iri<String>>| {
+            Err::<QuerySolutionIter<'static>, _>(QueryEvaluationError::UnsupportedService(
+                name.clone(),
+            ))
+        },
+    );
+    let QueryResults::Solutions(mut solutions) = evaluator
+        .parse_query("SELECT * WHERE { SERVICE <http://example.com/other> { ?s ?p ?o } }")?
+        .on_store(&local)
+        .execute()?
+    else {
+        return Err("SELECT query should return solutions".into());
+    };
+    assert!(matches!(
+        solutions.next(),
+        Some(Err(QueryEvaluationError::UnsupportedService(name))) if name.as_str() == "http://example.com/other"
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_len_for_graph() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let ex = NamedNodeRef::new("http://example.com/")?;
+    let g1 = NamedNodeRef::new("http://example.com/g1")?;
+    let g2 = BlankNode::default();
+    let empty = NamedNodeRef::new("http://example.com/empty")?;
+    for i in 0..3 {
+        let o = Literal::from(i);
+        store.insert(QuadRef::new(ex, ex, &o, GraphNameRef::DefaultGraph))?;
+        store.insert(QuadRef::new(ex, ex, &o, g1))?;
+    }
+    store.insert(QuadRef::new(ex, ex, ex, &g2))?;
+    store.insert_named_graph(empty)?;
+    assert_eq!(store.len_for_graph(GraphNameRef::DefaultGraph)?, 3);
+    assert_eq!(store.len_for_graph(g1)?, 3);
+    assert_eq!(store.len_for_graph(&g2)?, 1);
+    assert_eq!(store.len_for_graph(empty)?, 0);
+    assert_eq!(store.len_for_graph(ex)?, 0);
+
+    store.remove(QuadRef::new(ex, ex, &Literal::from(0), g1))?;
+    store.remove(QuadRef::new(ex, ex, ex, &g2))?;
+    let mut transaction = store.start_transaction()?;
+    transaction.insert(QuadRef::new(ex, ex, ex, empty));
+    assert_eq!(transaction.len_for_graph(empty)?, 1);
+    transaction.commit()?;
+
+    let mut stats = store.len_per_graph()?;
+    stats.sort_by_key(|(g, _)| g.to_string());
+    let mut expected = vec![
+        (GraphName::DefaultGraph, 3),
+        (g1.into_owned().into(), 2),
+        (g2.into(), 0),
+        (empty.into_owned().into(), 1),
+    ];
+    expected.sort_by_key(|(g, _)| g.to_string());
+    assert_eq!(stats, expected);
+    assert_eq!(stats.iter().map(|(_, c)| c).sum::<usize>(), store.len()?);
+    Ok(())
+}
+
+#[test]
+fn test_contains_pattern() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let ex = NamedNodeRef::new("http://example.com/")?;
+    let p = NamedNodeRef::new("http://example.com/p")?;
+    let rare = NamedNodeRef::new("http://example.com/rare")?;
+    let absent = NamedNodeRef::new("http://example.com/absent")?;
+    let g = NamedNodeRef::new("http://example.com/g")?;
+    for i in 0..10_000 {
+        store.insert(QuadRef::new(
+            ex,
+            p,
+            &Literal::from(i),
+            GraphNameRef::DefaultGraph,
+        ))?;
+    }
+    store.insert(QuadRef::new(rare, rare, ex, g))?;
+
+    assert!(store.contains_pattern(None, None, None, None)?);
+    assert!(store.contains_pattern(Some(ex.into()), Some(p), None, None)?);
+    assert!(store.contains_pattern(None, None, Some(ex.into()), None)?);
+    assert!(store.contains_pattern(None, Some(rare), None, Some(g.into()))?);
+    assert!(store.contains_pattern(
+        Some(ex.into()),
+        Some(p),
+        Some(Literal::from(9_999).as_ref().into()),
+        Some(GraphNameRef::DefaultGraph)
+    )?);
+    assert!(!store.contains_pattern(Some(absent.into()), None, None, None)?);
+    assert!(!store.contains_pattern(Some(ex.into()), Some(rare), None, None)?);
+    assert!(!store.contains_pattern(None, Some(p), None, Some(g.into()))?);
+    assert!(!store.contains_pattern(
+        Some(ex.into()),
+        Some(p),
+        Some(Literal::from(10_000).as_ref().into()),
+        Some(GraphNameRef::DefaultGraph)
+    )?);
+
+    // A pattern with a single match among many quads of the same predicate
+    let results = store
+        .quads_for_pattern(None, Some(p), Some(Literal::from(42).as_ref().into()), None)
+        .count();
+    assert_eq!(results, 1);
+    assert!(store.contains_pattern(
+        None,
+        Some(p),
+        Some(Literal::from(42).as_ref().into()),
+        None
+    )?);
+
+    store.remove(QuadRef::new(rare, rare, ex, g))?;
+    assert!(!store.contains_pattern(None, Some(rare), None, None)?);
+    assert!(!store.contains_pattern(None, None, None, Some(g.into()))?);
+    Ok(())
+}
+
+#[test]
+fn test_remove_pattern() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let ex = NamedNodeRef::new("http://example.com/")?;
+    let name = NamedNodeRef::new("http://example.com/name")?;
+    let age = NamedNodeRef::new("http://example.com/age")?;
+    let g = NamedNodeRef::new("http://example.com/g")?;
+    for i in 0..100 {
+        let o = Literal::from(i);
+        store.insert(QuadRef::new(ex, name, &o, GraphNameRef::DefaultGraph))?;
+        store.insert(QuadRef::new(ex, name, &o, g))?;
+        store.insert(QuadRef::new(ex, age, &o, g))?;
+    }
+
+    assert_eq!(store.remove_pattern(None, Some(name), None, None)?, 200);
+    assert!(!store.contains_pattern(None, Some(name), None, None)?);
+    assert_eq!(store.len()?, 100);
+    assert_eq!(store.len_for_graph(GraphNameRef::DefaultGraph)?, 0);
+    assert_eq!(store.len_for_graph(g)?, 100);
+    assert!(store.contains_named_graph(g)?);
+
+    assert_eq!(store.remove_pattern(None, Some(name), None, None)?, 0);
+    assert_eq!(
+        store.remove_pattern(
+            None,
+            None,
+            Some(Literal::from(7).as_ref().into()),
+            Some(g.into())
+        )?,
+        1
+    );
+    assert_eq!(store.remove_pattern(None, None, None, Some(g.into()))?, 99);
+    assert!(store.is_empty()?);
+    store.validate()?;
+    Ok(())
+}
+
+#[test]
+fn test_clear_and_remove_named_graph() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let ex = NamedNodeRef::new("http://example.com/")?;
+    let g1 = NamedNodeRef::new("http://example.com/g1")?;
+    let g2 = NamedNodeRef::new("http://example.com/g2")?;
+    for i in 0..10 {
+        let o = Literal::from(i);
+        store.insert(QuadRef::new(ex, ex, &o, GraphNameRef::DefaultGraph))?;
+        store.insert(QuadRef::new(ex, ex, &o, g1))?;
+        store.insert(QuadRef::new(ex, ex, &o, g2))?;
+    }
+
+    store.clear_graph(g1)?;
+    assert_eq!(store.len_for_graph(g1)?, 0);
+    assert!(store.contains_named_graph(g1)?);
+
+    store.remove_named_graph(g2)?;
+    assert_eq!(store.len_for_graph(g2)?, 0);
+    assert!(!store.contains_named_graph(g2)?);
+
+    assert_eq!(
+        store.named_graphs().collect::<Result<Vec<_>, _>>()?,
+        [NamedOrBlankNode::from(g1.into_owned())]
+    );
+    assert_eq!(store.len()?, 10);
+    store.validate()?;
+    Ok(())
+}
+
+#[test]
+fn test_query_evaluation_error_matching() -> Result<(), Box<dyn Error>> {
+    fn run(query: BoundPreparedSparqlQuery<'_>) -> Result<usize, QueryEvaluationError> {
+        let QueryResults::Solutions(solutions) = query.execute()? else {
+            return Ok(0);
+        };
+        let mut count = 0;
+        for solution in solutions {
+            solution?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    let store = Store::new()?;
+    let ex = NamedNodeRef::new("http://example.com/")?;
+    for i in 0..100 {
+        store.insert(QuadRef::new(
+            ex,
+            ex,
+            &Literal::from(i),
+            GraphNameRef::DefaultGraph,
+        ))?;
+    }
+    let query = "SELECT * WHERE { ?s ?p ?o . ?s2 ?p2 ?o2 }";
+
+    assert_eq!(
+        run(SparqlEvaluator::new().parse_query(query)?.on_store(&store))?,
+        10_000
+    );
+    assert!(
+        SparqlEvaluator::new()
+            .parse_query("SELECT * WHERE {")
+            .is_err()
+    );
+
+    let token = CancellationToken::new();
+    token.cancel();
+    assert!(matches!(
+        run(SparqlEvaluator::new()
+            .with_cancellation_token(token)
+            .parse_query(query)?
+            .on_store(&store)),
+        Err(QueryEvaluationError::Cancelled)
+    ));
+
+    assert!(matches!(
+        run(SparqlEvaluator::new()
+            .with_timeout(Duration::ZERO)
+            .parse_query(query)?
+            .on_store(&store)),
+        Err(QueryEvaluationError::Timeout(_))
+    ));
+
+    assert!(matches!(
+        run(SparqlEvaluator::new()
+            .parse_query("SELECT * WHERE { SERVICE ?service { ?s ?p ?o } }")?
+            .on_store(&store)),
+        Err(QueryEvaluationError::UnboundService)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_values_with_undef() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let group = NamedNodeRef::new("http://example.com/group")?;
+    let member = NamedNodeRef::new("http://example.com/member")?;
+    let name = NamedNodeRef::new("http://example.com/name")?;
+    for i in 0..200 {
+        let person = NamedNode::new(format!("http://example.com/person{i}"))?;
+        store.insert(QuadRef::new(
+            group,
+            member,
+            &person,
+            GraphNameRef::DefaultGraph,
+        ))?;
+        store.insert(QuadRef::new(
+            &person,
+            name,
+            &Literal::new_simple_literal(format!("Person {i}")),
+            GraphNameRef::DefaultGraph,
+        ))?;
+    }
+    // UNDEF matches any name: only the rows binding ?s to a person with an other name are dropped
+    let rows = (0..200)
+        .map(|i| match i % 3 {
+            0 => format!("(<http://example.com/person{i}> UNDEF)"),
+            1 => format!("(<http://example.com/person{i}> \"Person {i}\")"),
+            _ => format!("(<http://example.com/person{i}> \"Person {}\")", i + 1),
+        })
+        .collect::<Vec<_>>();
+    // Small VALUES are evaluated in a for loop and large ones are hash joined on ?s
+    for size in [10, rows.len()] {
+        let query = format!(
+            "SELECT ?s ?name WHERE {{ <http://example.com/group> <http://example.com/member> ?s . ?s <http://example.com/name> ?name VALUES (?s ?name) {{ {} }} }}",
+            rows[..size].join(" ")
+        );
+        let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+            .parse_query(&query)?
+            .on_store(&store)
+            .execute()?
+        else {
+            panic!("SELECT queries must return solutions")
+        };
+        let mut actual = solutions
+            .map(|solution| {
+                let solution = solution?;
+                Ok((
+                    solution.get("s").cloned().unwrap(),
+                    solution.get("name").cloned().unwrap(),
+                ))
+            })
+            .collect::<Result<Vec<_>, QueryEvaluationError>>()?;
+        actual.sort_by_cached_key(|(s, _)| s.to_string());
+        let mut expected = (0..size)
+            .filter(|i| i % 3 != 2)
+            .map(|i| {
+                Ok((
+                    NamedNode::new(format!("http://example.com/person{i}"))?.into(),
+                    Literal::new_simple_literal(format!("Person {i}")).into(),
+                ))
+            })
+            .collect::<Result<Vec<(Term, Term)>, Box<dyn Error>>>()?;
+        expected.sort_by_cached_key(|(s, _)| s.to_string());
+        assert_eq!(actual, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_strict_projection() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let ex = NamedNodeRef::new("http://example.com/")?;
+    store.insert(QuadRef::new(
+        ex,
+        ex,
+        LiteralRef::new_simple_literal("Alice"),
+        GraphNameRef::DefaultGraph,
+    ))?;
+    let misspelled = "SELECT ?nmae WHERE { ?person <http://example.com/> ?name }";
+
+    let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+        .parse_query(misspelled)?
+        .on_store(&store)
+        .execute()?
+    else {
+        panic!("SELECT queries must return solutions")
+    };
+    assert!(
+        solutions.collect::<Result<Vec<_>, _>>()?[0]
+            .get("nmae")
+            .is_none()
+    );
+
+    let Err(error) = SparqlEvaluator::new()
+        .with_strict_projection()
+        .parse_query(misspelled)
+    else {
+        panic!("the misspelled projection variable must be rejected")
+    };
+    assert!(error.to_string().contains("?nmae"), "{error}");
+    let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+        .with_strict_projection()
+        .parse_query("SELECT ?name WHERE { ?person <http://example.com/> ?name }")?
+        .on_store(&store)
+        .execute()?
+    else {
+        panic!("SELECT queries must return solutions")
+    };
+    assert_eq!(
+        solutions.collect::<Result<Vec<_>, _>>()?[0].get("name"),
+        Some(&LiteralRef::new_simple_literal("Alice").into_owned().into())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_cache() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?.with_query_cache(8);
+    let ex = NamedNodeRef::new("http://example.com/")?;
+    store.insert(QuadRef::new(
+        ex,
+        ex,
+        &Literal::from(1),
+        GraphNameRef::DefaultGraph,
+    ))?;
+    let evaluate = |query: &PreparedSparqlQuery,
+                    store: &Store|
+     -> Result<Vec<QuerySolution>, Box<dyn Error>> {
+        let QueryResults::Solutions(solutions) = query.clone().on_store(store).execute()? else {
+            return Err("SELECT query should return solutions".into());
+        };
+        Ok(solutions.collect::<Result<_, _>>()?)
+    };
+    let query = SparqlEvaluator::new().parse_query("SELECT ?o WHERE { ?s ?p ?o }")?;
+
+    let first = evaluate(&query, &store)?;
+    assert_eq!(first.len(), 1);
+    // The cache is shared with the clones
+    assert_eq!(evaluate(&query, &store.clone())?, first);
+
+    // Writes invalidate the cache
+    store.insert(QuadRef::new(
+        ex,
+        ex,
+        &Literal::from(2),
+        GraphNameRef::DefaultGraph,
+    ))?;
+    assert_eq!(evaluate(&query, &store)?.len(), 2);
+    assert_eq!(evaluate(&query, &store)?.len(), 2);
+
+    // Evaluators with custom functions do not use the cache
+    let evaluations = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&evaluations);
+    let query = SparqlEvaluator::new()
+        .with_custom_function(NamedNode::new("http://example.com/count")?, move |args| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            args.first().cloned()
+        })
+        .parse_query("SELECT ?o (<http://example.com/count>(?o) AS ?c) WHERE { ?s ?p ?o }")?;
+    assert_eq!(evaluate(&query, &store)?.len(), 2);
+    assert_eq!(evaluations.load(Ordering::Relaxed), 2);
+    assert_eq!(evaluate(&query, &store)?.len(), 2);
+    assert_eq!(evaluations.load(Ordering::Relaxed), 4);
+    Ok(())
+}
+
+#[test]
+fn test_update_preview() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    store.load_from_reader(
+        RdfFormat::TriG,
+        b"<http://example.com/a> <http://example.com/p> 1 , 2 .
+        <http://example.com/g> { <http://example.com/a> <http://example.com/q> 3 }"
+            .as_slice(),
+    )?;
+    let a = NamedNode::new_unchecked("http://example.com/a");
+    let p = NamedNode::new_unchecked("http://example.com/p");
+    let q = NamedNode::new_unchecked("http://example.com/q");
+    let g = NamedNode::new_unchecked("http://example.com/g");
+
+    let diff = SparqlEvaluator::new()
+        .parse_update(
+            "DELETE { ?s <http://example.com/p> ?o } INSERT { ?s <http://example.com/p> ?o2 } WHERE { ?s <http://example.com/p> ?o BIND(?o + 1 AS ?o2) } ;
+            CLEAR GRAPH <http://example.com/g>",
+        )?
+        .on_store(&store)
+        .preview()?;
+    // 2 is deleted then inserted again so it is not part of the diff
+    assert_eq!(
+        diff.inserted,
+        [Quad::new(
+            a.clone(),
+            p.clone(),
+            Literal::from(3),
+            GraphName::DefaultGraph
+        )]
+    );
+    assert_eq!(
+        diff.deleted,
+        [
+            Quad::new(a.clone(), p, Literal::from(1), GraphName::DefaultGraph),
+            Quad::new(a, q, Literal::from(3), g)
+        ]
+    );
+    // Nothing has been written
+    assert_eq!(store.len()?, 3);
+
+    // Previews are not possible in transactions
+    let mut transaction = store.start_transaction()?;
+    SparqlEvaluator::new()
+        .parse_update("CLEAR ALL")?
+        .on_transaction(&mut transaction)
+        .preview()
+        .unwrap_err();
+    Ok(())
+}
+
 #[test]
 fn test_load_dataset() -> Result<(), Box<dyn Error>> {
     let store = Store::new()?;
@@ -243,6 +1055,103 @@ fn test_load_dataset() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_merge_graph_dedup() -> Result<(), Box<dyn Error>> {
+    let data = r#"@prefix ex: <http://example.com/> .
+        ex:s ex:p [ ex:name "a" ; ex:knows [ ex:name "b" ] ] .
+        ex:s ex:q ex:o ."#;
+    let parse = || {
+        RdfParser::from_format(RdfFormat::Turtle)
+            .for_slice(data)
+            .map(|q| q.map(Triple::from))
+            .collect::<Result<Graph, _>>()
+    };
+    let graph_name = NamedNodeRef::new_unchecked("http://example.com/g");
+    let mut graph = parse()?;
+
+    let store = Store::new()?;
+    store.merge_graph_dedup(&graph, graph_name)?;
+    assert_eq!(store.len()?, 5);
+
+    // The same structure with fresh blank nodes is not inserted again
+    store.merge_graph_dedup(&graph, graph_name)?;
+    store.merge_graph_dedup(&parse()?, graph_name)?;
+    assert_eq!(store.len()?, 5);
+
+    // A different structure or another target graph is inserted
+    graph.insert(TripleRef::new(
+        &BlankNode::default(),
+        NamedNodeRef::new_unchecked("http://example.com/name"),
+        LiteralRef::new_simple_literal("c"),
+    ));
+    store.merge_graph_dedup(&graph, graph_name)?;
+    assert_eq!(store.len()?, 6);
+    store.merge_graph_dedup(&graph, GraphNameRef::DefaultGraph)?;
+    assert_eq!(store.len()?, 12);
+
+    // Structures made only of blank nodes are deduplicated too
+    let knows = NamedNodeRef::new_unchecked("http://example.com/knows");
+    let blank_only = || {
+        let (a, b) = (BlankNode::default(), BlankNode::default());
+        Graph::from_iter([TripleRef::new(&a, knows, &b), TripleRef::new(&b, knows, &a)])
+    };
+    store.merge_graph_dedup(&blank_only(), graph_name)?;
+    store.merge_graph_dedup(&blank_only(), graph_name)?;
+    assert_eq!(store.len()?, 14);
+    store.validate()?;
+    Ok(())
+}
+
+#[test]
+fn test_rename_predicate() -> Result<(), Box<dyn Error>> {
+    let data = r#"@prefix ex: <http://example.com/> .
+        ex:a ex:oldProp ex:b ; ex:other "a" .
+        ex:b ex:oldProp "b" .
+        ex:g { ex:c ex:oldProp ex:a . }"#;
+    let old_prop = NamedNodeRef::new_unchecked("http://example.com/oldProp");
+    let new_prop = NamedNodeRef::new_unchecked("http://example.com/newProp");
+    let store = Store::new()?;
+    store.load_from_slice(RdfFormat::TriG, data)?;
+    let renamed = store
+        .quads_for_pattern(None, Some(old_prop), None, None)
+        .map(|q| {
+            let q = q?;
+            Ok(Quad::new(q.subject, new_prop, q.object, q.graph_name))
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    assert_eq!(renamed.len(), 3);
+
+    store.rename_predicate(old_prop, new_prop)?;
+    assert_eq!(store.len()?, 4);
+    for quad in &renamed {
+        assert!(store.contains(quad)?);
+    }
+    assert_eq!(
+        store
+            .quads_for_pattern(None, Some(old_prop), None, None)
+            .count(),
+        0
+    );
+
+    // Queries on the new predicate see the renamed triples
+    let query = |predicate: NamedNodeRef<'_>| -> Result<usize, Box<dyn Error>> {
+        let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+            .parse_query(&format!(
+                "SELECT * WHERE {{ {{ ?s {predicate} ?o }} UNION {{ GRAPH ?g {{ ?s {predicate} ?o }} }} }}"
+            ))?
+            .on_store(&store)
+            .execute()?
+        else {
+            return Err("SELECT query should return solutions".into());
+        };
+        Ok(solutions.collect::<Result<Vec<_>, _>>()?.len())
+    };
+    assert_eq!(query(new_prop)?, 3);
+    assert_eq!(query(old_prop)?, 0);
+    store.validate()?;
+    Ok(())
+}
+
 #[test]
 fn test_bulk_load_dataset() -> Result<(), Box<dyn Error>> {
     let store = Store::new()?;
@@ -303,6 +1212,48 @@ fn test_dump_dataset() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_dump_and_load_dataset() -> Result<(), Box<dyn Error>> {
+    let g1 = NamedNodeRef::new_unchecked("http://example.com/g1");
+    let g2 = NamedNodeRef::new_unchecked("http://example.com/g2");
+    let store = Store::new()?;
+    for graph_name in [GraphNameRef::DefaultGraph, g1.into(), g2.into()] {
+        for q in quads(graph_name) {
+            store.insert(q)?;
+        }
+    }
+    // The graph membership is kept
+    store.insert(QuadRef::new(g1, rdf::TYPE, g2, g2))?;
+
+    for format in [RdfFormat::NQuads, RdfFormat::TriG] {
+        let copy = Store::new()?;
+        copy.load_dataset_from_reader(
+            format,
+            store.dump_to_writer(format, Vec::new())?.as_slice(),
+        )?;
+        assert_eq!(
+            copy.iter().collect::<Result<HashSet<_>, _>>()?,
+            store.iter().collect::<Result<HashSet<_>, _>>()?
+        );
+        assert_eq!(copy.len()?, 3 * NUMBER_OF_TRIPLES + 1);
+        assert_eq!(
+            copy.named_graphs().collect::<Result<HashSet<_>, _>>()?,
+            HashSet::from([g1.into_owned().into(), g2.into_owned().into()])
+        );
+    }
+
+    // Graph formats would lose the graph names
+    assert!(matches!(
+        store.dump_to_writer(RdfFormat::Turtle, Vec::new()),
+        Err(SerializerError::DatasetFormatExpected(RdfFormat::Turtle))
+    ));
+    assert!(matches!(
+        store.load_dataset_from_reader(RdfFormat::NTriples, b"".as_slice()),
+        Err(LoaderError::DatasetFormatExpected(RdfFormat::NTriples))
+    ));
+    Ok(())
+}
+
 #[test]
 fn test_snapshot_isolation_iterator() -> Result<(), Box<dyn Error>> {
     let quad = QuadRef::new(
@@ -345,6 +1296,518 @@ fn test_snapshot_isolation_iterator_on_disk() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Iterates the store while another thread writes to it and runs `maintenance` on it.
+///
+/// The iterator must only see the quads of its snapshot.
+#[cfg(not(target_family = "wasm"))]
+fn check_snapshot_isolation_under_concurrent_writes(
+    store: &Store,
+    maintenance: impl Fn(&Store) -> Result<(), StorageError> + Sync,
+) -> Result<(), Box<dyn Error>> {
+    let quad = |i: usize| {
+        Quad::new(
+            NamedNode::new_unchecked(format!("http://example.com/s{i}")),
+            NamedNode::new_unchecked("http://example.com/p"),
+            Literal::from(i64::try_from(i).unwrap()),
+            GraphName::DefaultGraph,
+        )
+    };
+    let expected = (0..1000).map(quad).collect::<HashSet<_>>();
+    store.extend(expected.iter().cloned())?;
+    let mut iter = store.iter();
+    let mut seen = vec![iter.next().ok_or("the store should not be empty")??];
+    thread::scope(|s| -> Result<(), Box<dyn Error>> {
+        let writer = s.spawn(|| -> Result<(), StorageError> {
+            for i in 0..1000 {
+                store.remove(&quad(i))?;
+                store.insert(&quad(1000 + i))?;
+                if i % 200 == 0 {
+                    maintenance(store)?;
+                }
+            }
+            maintenance(store)
+        });
+        for q in iter {
+            seen.push(q?);
+        }
+        writer.join().map_err(|_| "the writer thread panicked")??;
+        Ok(())
+    })?;
+    assert_eq!(
+        seen.len(),
+        expected.len(),
+        "no quad should be returned twice"
+    );
+    assert_eq!(seen.into_iter().collect::<HashSet<_>>(), expected);
+    assert_eq!(store.len()?, 1000);
+    store.validate()?;
+    Ok(())
+}
+
+#[test]
+#[cfg(not(target_family = "wasm"))]
+fn test_snapshot_isolation_under_concurrent_writes() -> Result<(), Box<dyn Error>> {
+    check_snapshot_isolation_under_concurrent_writes(&Store::new()?, |_| Ok(()))
+}
+
+#[test]
+#[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+fn test_snapshot_isolation_under_concurrent_compaction_on_disk() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    check_snapshot_isolation_under_concurrent_writes(&Store::open(&dir)?, |store| {
+        store.flush()?;
+        store.optimize()
+    })
+}
+
+#[test]
+fn test_plan_cache_skips_planning() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    for q in quads(GraphNameRef::DefaultGraph) {
+        store.insert(q)?;
+    }
+    let patterns = (0..30)
+        .map(|i| format!("?v{i} ?p{i} ?v{} . OPTIONAL {{ ?v{i} ?q{i} ?o{i} }}", i + 1))
+        .collect::<Vec<_>>();
+    let evaluator = SparqlEvaluator::new().with_plan_cache(10);
+    let mut planning_durations = Vec::new();
+    for i in 0..20 {
+        // The formatting changes but the query stays the same
+        let query = format!(
+            "SELECT * WHERE {{{}{}}} # run {i}",
+            patterns.join(&" ".repeat(i + 1)),
+            "\n".repeat(i)
+        );
+        let (results, explanation) = evaluator
+            .clone()
+            .parse_query(&query)?
+            .on_store(&store)
+            .explain();
+        let QueryResults::Solutions(solutions) = results? else {
+            return Err("SELECT query should return solutions".into());
+        };
+        for solution in solutions {
+            solution?;
+        }
+        planning_durations.push(
+            explanation
+                .planning_duration()
+                .ok_or("the planning duration should be known")?,
+        );
+    }
+    // Planning is skipped after the first execution
+    // We look at the median to not fail if the thread is preempted during a single execution
+    let (first, cached) = planning_durations.split_first().ok_or("no execution")?;
+    let mut cached = cached.to_vec();
+    cached.sort_unstable();
+    assert!(
+        cached[cached.len() / 2] < *first / 10,
+        "{planning_durations:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_explain_shows_estimated_sizes_and_indexes() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    for q in quads(GraphNameRef::DefaultGraph) {
+        store.insert(q)?;
+    }
+    for (query, pattern) in [
+        (
+            "SELECT ?s WHERE { ?s <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> ?o }",
+            "QuadPattern(?s <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> ?o) [estimated results: 1000000, results: 1",
+        ),
+        (
+            "ASK { ?s ?p <http://www.wikidata.org/entity/Q142> }",
+            "QuadPattern(?s ?p <http://www.wikidata.org/entity/Q142>) [estimated results: 100000, results: 1",
+        ),
+        (
+            "CONSTRUCT WHERE { <http://www.wikidata.org/entity/Q90> ?p ?o }",
+            "QuadPattern(<http://www.wikidata.org/entity/Q90> ?p ?o) [estimated results: 100, results: 8",
+        ),
+    ] {
+        let (results, explanation) = SparqlEvaluator::new()
+            .parse_query(query)?
+            .on_store(&store)
+            .compute_statistics()
+            .explain();
+        // Statistics are filled while consuming the results
+        match results? {
+            QueryResults::Solutions(solutions) => {
+                for solution in solutions {
+                    solution?;
+                }
+            }
+            QueryResults::Graph(triples) => {
+                for triple in triples {
+                    triple?;
+                }
+            }
+            QueryResults::Boolean(_) => (),
+        }
+        let explanation = explanation.to_string();
+        assert!(explanation.contains(pattern), "{explanation}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_explain_shows_used_indexes() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    for q in quads(GraphNameRef::DefaultGraph) {
+        store.insert(q)?;
+    }
+    for (query, index) in [
+        ("SELECT * WHERE { ?s ?p ?o }", "SPO"),
+        ("SELECT * WHERE { <http://example.com> ?p ?o }", "SPO"),
+        ("SELECT * WHERE { ?s <http://example.com> ?o }", "POS"),
+        ("SELECT * WHERE { ?s ?p <http://example.com> }", "OSP"),
+        (
+            "SELECT * WHERE { <http://example.com> ?p <http://example.com> }",
+            "OSP",
+        ),
+    ] {
+        let (results, explanation) = SparqlEvaluator::new()
+            .parse_query(query)?
+            .on_store(&store)
+            .compute_statistics()
+            .explain();
+        let QueryResults::Solutions(solutions) = results? else {
+            return Err("SELECT query should return solutions".into());
+        };
+        for solution in solutions {
+            solution?;
+        }
+        let explanation = explanation.to_string();
+        assert!(
+            explanation.contains(&format!("indexes: {index}]")),
+            "{explanation}"
+        );
+    }
+    Ok(())
+}
+
+/// Records the calls to the [`MetricsCollector`] callbacks
+#[derive(Default)]
+struct RecordingMetrics(Mutex<Vec<String>>);
+
+impl RecordingMetrics {
+    fn take(&self) -> Vec<String> {
+        take(&mut *self.0.lock().unwrap())
+    }
+
+    fn record(&self, event: String) {
+        self.0.lock().unwrap().push(event);
+    }
+}
+
+impl MetricsCollector for RecordingMetrics {
+    fn query_started(&self) {
+        self.record("query started".into());
+    }
+
+    fn query_finished(&self, _duration: Duration, result_count: usize) {
+        self.record(format!("query finished with {result_count} results"));
+    }
+
+    fn query_failed(&self, _duration: Duration, _error: &QueryEvaluationError) {
+        self.record("query failed".into());
+    }
+
+    fn bulk_load_committed(&self, loaded_quads: u64) {
+        self.record(format!("bulk load of {loaded_quads} quads"));
+    }
+
+    fn transaction_committed(&self, inserted_quads: usize, removed_quads: usize) {
+        self.record(format!(
+            "commit of {inserted_quads} insertions and {removed_quads} removals"
+        ));
+    }
+
+    fn transaction_rolled_back(&self) {
+        self.record("rollback".into());
+    }
+}
+
+#[test]
+fn test_metrics_collector() -> Result<(), Box<dyn Error>> {
+    let metrics = Arc::new(RecordingMetrics::default());
+    let store = Store::new()?.with_metrics(Arc::<RecordingMetrics>::clone(&metrics));
+    let ex = NamedNodeRef::new("http://example.com")?;
+    let quad = QuadRef::new(ex, ex, ex, GraphNameRef::DefaultGraph);
+
+    store.insert(quad)?;
+    let mut transaction = store.start_transaction()?;
+    transaction.remove(quad);
+    transaction.rollback();
+    // Dropping a transaction without committing it is a rollback
+    let mut transaction = store.start_transaction()?;
+    transaction.remove(quad);
+    drop(transaction);
+    let mut loader = store.bulk_loader();
+    loader.load_quads(quads(GraphNameRef::DefaultGraph))?;
+    loader.commit()?;
+    SparqlEvaluator::new()
+        .parse_update("DELETE WHERE { ?s ?p ?o }")?
+        .on_store(&store)
+        .execute()?;
+    let loaded = quads(GraphNameRef::DefaultGraph).len();
+    assert_eq!(
+        metrics.take(),
+        [
+            "commit of 1 insertions and 0 removals".to_owned(),
+            "rollback".into(),
+            "rollback".into(),
+            format!("bulk load of {loaded} quads"),
+            format!("commit of 0 insertions and {} removals", loaded + 1)
+        ]
+    );
+
+    store.insert(quad)?;
+    metrics.take();
+    let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+        .parse_query("SELECT * WHERE { VALUES ?v { 1 2 3 } }")?
+        .on_store(&store)
+        .execute()?
+    else {
+        return Err("SELECT query should return solutions".into());
+    };
+    assert_eq!(metrics.take(), ["query started"]);
+    // Results are counted when iterated
+    assert_eq!(solutions.count(), 3);
+    SparqlEvaluator::new()
+        .parse_query("ASK { ?s ?p ?o }")?
+        .on_store(&store)
+        .execute()?;
+    let QueryResults::Graph(mut triples) = SparqlEvaluator::new()
+        .parse_query("CONSTRUCT WHERE { ?s ?p ?o }")?
+        .on_store(&store)
+        .execute()?
+    else {
+        return Err("CONSTRUCT query should return triples".into());
+    };
+    // Abandoned iterations are also reported
+    triples.next().transpose()?;
+    drop(triples);
+    let QueryResults::Solutions(mut solutions) = SparqlEvaluator::new()
+        .with_max_materialized_rows(1)
+        .parse_query("SELECT DISTINCT ?v WHERE { VALUES ?v { 1 2 3 } }")?
+        .on_store(&store)
+        .execute()?
+    else {
+        return Err("SELECT query should return solutions".into());
+    };
+    assert!(solutions.any(|solution| solution.is_err()));
+    drop(solutions);
+    assert_eq!(
+        metrics.take(),
+        [
+            "query finished with 3 results",
+            "query started",
+            "query finished with 1 results",
+            "query started",
+            "query finished with 1 results",
+            "query started",
+            "query failed"
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_with_dataset_override() -> Result<(), Box<dyn Error>> {
+    let store = Store::new()?;
+    let p = NamedNode::new("http://example.com/p")?;
+    let tenants =
+        ["http://example.com/tenant1", "http://example.com/tenant2"].map(NamedNode::new_unchecked);
+    for tenant in &tenants {
+        store.insert(QuadRef::new(tenant, &p, tenant, tenant))?;
+    }
+    let subjects = |query: PreparedSparqlQuery| -> Result<Vec<Term>, Box<dyn Error>> {
+        let QueryResults::Solutions(solutions) = query.on_store(&store).execute()? else {
+            return Err("SELECT query should return solutions".into());
+        };
+        let mut subjects = Vec::new();
+        for solution in solutions {
+            subjects.extend(solution?.get("s").cloned());
+        }
+        Ok(subjects)
+    };
+    let query = SparqlEvaluator::new().parse_query(
+        "SELECT ?s FROM <http://example.com/tenant1> WHERE { ?s <http://example.com/p> ?o }",
+    )?;
+    // Without override, the FROM clause is used
+    assert_eq!(subjects(query.clone())?, [tenants[0].clone().into()]);
+    // The same query against each tenant graph
+    for tenant in &tenants {
+        let mut dataset = QueryDataset::new();
+        dataset.set_default_graph(vec![tenant.clone().into()]);
+        assert_eq!(
+            subjects(query.clone().with_dataset(dataset))?,
+            [tenant.clone().into()]
+        );
+    }
+    // The union of the tenant graphs
+    let mut dataset = QueryDataset::new();
+    dataset.set_default_graph(tenants.iter().cloned().map(Into::into).collect());
+    assert_eq!(subjects(query.with_dataset(dataset))?.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_custom_function_in_bind() -> Result<(), Box<dyn Error>> {
+    let double = NamedNode::new("http://example.com/double")?;
+    let evaluator = SparqlEvaluator::new().with_fallible_custom_function(double, |args| {
+        let [Term::Literal(value)] = args else {
+            return Ok(None);
+        };
+        let value = value.value().parse::<i64>()?;
+        Ok::<_, ParseIntError>(
+            value
+                .checked_mul(2)
+                .map(|value| Literal::from(value).into()),
+        )
+    });
+    let QueryResults::Solutions(solutions) = evaluator
+        .parse_query(
+            "PREFIX ex: <http://example.com/>
+            SELECT ?v ?d WHERE {
+                VALUES ?v { 1 21 \"a\" }
+                BIND(ex:double(?v) AS ?d)
+                FILTER(!BOUND(?d) || ex:double(?d) > 4)
+            }",
+        )?
+        .on_store(&Store::new()?)
+        .execute()?
+    else {
+        return Err("SELECT query should return solutions".into());
+    };
+    let results = solutions
+        .map(|solution| {
+            let solution = solution?;
+            Ok((solution.get("v").cloned(), solution.get("d").cloned()))
+        })
+        .collect::<Result<Vec<_>, QueryEvaluationError>>()?;
+    // The function fails on "a" so ?d is unbound
+    assert_eq!(
+        results,
+        [
+            (
+                Some(Literal::from(21).into()),
+                Some(Literal::from(42).into())
+            ),
+            (Some(Literal::new_simple_literal("a").into()), None)
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "shacl")]
+fn test_validate_shacl() -> Result<(), Box<dyn Error>> {
+    let mut shapes = Graph::new();
+    for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(
+        r"
+        @prefix schema: <http://schema.org/> .
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        <http://example.com/CityShape> a sh:NodeShape ;
+            sh:targetClass schema:City ;
+            sh:property [ sh:path schema:name ; sh:maxCount 1 ] ;
+            sh:property [ sh:path schema:population ; sh:datatype xsd:integer ] ;
+            sh:property [ sh:path schema:country ; sh:class schema:Country ] .",
+    ) {
+        shapes.insert(&Triple::from(quad?));
+    }
+    let validator = ShaclValidator::new(ShapesGraph::from_graph(&shapes)?);
+
+    let store = Store::new()?;
+    store.load_from_slice(RdfFormat::TriG, GRAPH_DATA)?;
+    let graph_name = NamedNodeRef::new("http://www.wikidata.org/wiki/Special:EntityData/Q90")?;
+
+    // The default graph has no city
+    assert!(
+        store
+            .validate_shacl(&validator, GraphNameRef::DefaultGraph)?
+            .conforms()
+    );
+
+    // Paris has two names and an untyped country
+    let report = store.validate_shacl(&validator, graph_name)?;
+    assert_eq!(report.violation_count(), 2);
+
+    // The report is the same as when validating a copy of the graph
+    let mut graph = Graph::new();
+    for quad in store.quads_for_pattern(None, None, None, Some(graph_name.into())) {
+        graph.insert(&Triple::from(quad?));
+    }
+    let expected = validator.validate(&graph)?;
+    assert_eq!(
+        format!("{:?}", report.results()),
+        format!("{:?}", expected.results())
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "shacl")]
+fn test_shapes_graph() -> Result<(), Box<dyn Error>> {
+    let shapes_data = "
+        @prefix schema: <http://schema.org/> .
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        <http://example.com/CityShape> a sh:NodeShape ;
+            sh:targetClass schema:City ;
+            sh:property [ sh:path schema:name ; sh:maxCount 1 ] ;
+            sh:property [ sh:path [ sh:inversePath schema:containedInPlace ] ; sh:node [ sh:class schema:Place ] ] ;
+            sh:property [ sh:path schema:country ; sh:or ( [ sh:class schema:Country ] [ sh:nodeKind sh:Literal ] ) ] .";
+    let shapes_graph_name = NamedNodeRef::new("http://example.com/shapes")?;
+    let store = Store::new()?;
+    store.load_from_slice(
+        RdfParser::from_format(RdfFormat::Turtle).with_default_graph(shapes_graph_name),
+        shapes_data,
+    )?;
+    store.load_from_slice(RdfFormat::TriG, GRAPH_DATA)?;
+    let graph_name = NamedNodeRef::new("http://www.wikidata.org/wiki/Special:EntityData/Q90")?;
+
+    // The blank node shapes are reassembled from the store triples
+    let mut graph = Graph::new();
+    for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(shapes_data) {
+        graph.insert(&Triple::from(quad?));
+    }
+    let expected = ShapesGraph::from_graph(&graph)?;
+    let shapes = store.shapes_graph(shapes_graph_name)?;
+    assert_eq!(shapes.len(), expected.len());
+    assert_eq!(shapes.node_shapes().count(), expected.node_shapes().count());
+    let mut paths = shapes
+        .node_shapes()
+        .flat_map(|shape| &shape.base.property_shapes)
+        .map(|shape| shape.path().to_string())
+        .collect::<Vec<_>>();
+    paths.sort();
+    let mut expected_paths = expected
+        .node_shapes()
+        .flat_map(|shape| &shape.base.property_shapes)
+        .map(|shape| shape.path().to_string())
+        .collect::<Vec<_>>();
+    expected_paths.sort();
+    assert_eq!(paths.len(), 3);
+    assert_eq!(paths, expected_paths);
+
+    // Validation gives the same results
+    let report = store.validate_shacl(&ShaclValidator::new(shapes), graph_name)?;
+    let expected_report = store.validate_shacl(&ShaclValidator::new(expected), graph_name)?;
+    assert_eq!(report.violation_count(), expected_report.violation_count());
+    assert!(!report.conforms());
+
+    // An empty graph has no shapes
+    assert!(store.shapes_graph(GraphNameRef::DefaultGraph)?.is_empty());
+    Ok(())
+}
+
 #[test]
 fn test_bulk_load_on_existing_delete_overrides_the_delete() -> Result<(), Box<dyn Error>> {
     let quad = QuadRef::new(
@@ -381,6 +1844,62 @@ fn test_bulk_load_on_existing_delete_overrides_the_delete_on_disk() -> Result<()
     Ok(())
 }
 
+#[cfg(not(target_family = "wasm"))]
+fn check_bulk_load_timeout(store: &Store) -> Result<(), Box<dyn Error>> {
+    let existing = QuadRef::new(
+        NamedNodeRef::new_unchecked("http://example.com/s"),
+        NamedNodeRef::new_unchecked("http://example.com/p"),
+        NamedNodeRef::new_unchecked("http://example.com/o"),
+        GraphNameRef::DefaultGraph,
+    );
+    store.insert(existing)?;
+
+    // The timeout is exceeded: nothing is saved
+    let mut loader = store.bulk_loader().with_timeout(Duration::from_millis(10));
+    loader.load_from_slice(RdfFormat::Turtle, DATA)?;
+    thread::sleep(Duration::from_millis(20));
+    assert!(matches!(
+        loader.commit().map_err(LoaderError::from),
+        Err(LoaderError::Timeout(_))
+    ));
+    assert_eq!(store.len()?, 1);
+    assert!(store.contains(existing)?);
+
+    // The loading itself fails once the timeout is exceeded
+    let mut loader = store.bulk_loader().with_timeout(Duration::ZERO);
+    thread::sleep(Duration::from_millis(1));
+    assert!(matches!(
+        loader.load_from_slice(RdfFormat::Turtle, DATA),
+        Err(LoaderError::Timeout(_))
+    ));
+    let Err(StorageError::Io(error)) = loader.load_quads([existing.into_owned()]) else {
+        return Err("load_quads should fail with an I/O error".into());
+    };
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    drop(loader);
+    assert_eq!(store.len()?, 1);
+
+    // The timeout is not exceeded
+    let mut loader = store.bulk_loader().with_timeout(Duration::from_secs(60));
+    loader.load_from_slice(RdfFormat::Turtle, DATA)?;
+    loader.commit()?;
+    assert_eq!(store.len()?, NUMBER_OF_TRIPLES + 1);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(target_family = "wasm"))]
+fn test_bulk_load_timeout() -> Result<(), Box<dyn Error>> {
+    check_bulk_load_timeout(&Store::new()?)
+}
+
+#[test]
+#[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+fn test_bulk_load_timeout_on_disk() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    check_bulk_load_timeout(&Store::open(&dir)?)
+}
+
 #[test]
 #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
 fn test_open_bad_dir() -> Result<(), Box<dyn Error>> {
@@ -443,6 +1962,74 @@ fn test_backup() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+#[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+fn test_optimize_reclaims_space() -> Result<(), Box<dyn Error>> {
+    let quads = (0..1000)
+        .map(|i| {
+            Quad::new(
+                NamedNode::new_unchecked(format!("http://example.com/s{i}")),
+                NamedNode::new_unchecked("http://example.com/p"),
+                Literal::from(i),
+                GraphName::DefaultGraph,
+            )
+        })
+        .collect::<Vec<_>>();
+    let store_dir = TempDir::new()?;
+    let store = Store::open(&store_dir)?;
+    let mut loader = store.bulk_loader();
+    loader.load_quads(quads.clone())?;
+    loader.commit()?;
+    assert_eq!(store.len()?, quads.len());
+    store.optimize()?;
+    let size_after_load = store.on_disk_size()?;
+    assert!(size_after_load > 0);
+
+    for quad in &quads {
+        store.remove(quad)?;
+    }
+    store.optimize()?;
+    assert!(store.is_empty()?);
+    assert!(store.on_disk_size()? < size_after_load);
+
+    assert_eq!(Store::new()?.on_disk_size()?, 0);
+    Ok(())
+}
+
+#[test]
+#[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+fn test_restore() -> Result<(), Box<dyn Error>> {
+    let quad = QuadRef::new(
+        NamedNodeRef::new_unchecked("http://example.com/s"),
+        NamedNodeRef::new_unchecked("http://example.com/p"),
+        NamedNodeRef::new_unchecked("http://example.com/o"),
+        GraphNameRef::DefaultGraph,
+    );
+    let store_dir = TempDir::new()?;
+    let backup_dir = TempDir::new()?;
+    remove_dir(&backup_dir)?;
+    let restored_dir = TempDir::new()?;
+    remove_dir(&restored_dir)?;
+
+    let store = Store::open(&store_dir)?;
+    store.insert(quad)?;
+    store.backup(&backup_dir)?;
+    drop(store);
+
+    Store::restore(&backup_dir, &restored_dir)?;
+    let restored = Store::open(&restored_dir)?;
+    restored.validate()?;
+    assert!(restored.contains(quad)?);
+
+    // The restored database is independent from the backup
+    restored.remove(quad)?;
+    assert!(Store::open_read_only(&backup_dir)?.contains(quad)?);
+
+    // The target directory must not exist
+    Store::restore(&backup_dir, &restored_dir).unwrap_err();
+    Ok(())
+}
+
 #[test]
 #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
 fn test_bad_backup() -> Result<(), Box<dyn Error>> {
@@ -472,6 +2059,14 @@ fn test_backup_on_in_memory() -> Result<(), Box<dyn Error>> {
 fn test_backward_compatibility() -> Result<(), Box<dyn Error>> {
     // We run twice to check if data is properly saved and closed
     let _reset = DirSaver::new("tests/rocksdb_bc_data")?;
+    assert!(matches!(
+        Store::open("tests/rocksdb_bc_data"),
+        Err(StorageError::IncompatibleVersion {
+            found: 0,
+            expected: 2
+        })
+    ));
+    Store::migrate("tests/rocksdb_bc_data")?;
     for _ in 0..2 {
         let store = Store::open("tests/rocksdb_bc_data")?;
         for q in quads(GraphNameRef::DefaultGraph) {
@@ -507,6 +2102,7 @@ fn test_rdf_star_backward_compatibility() -> Result<(), Box<dyn Error>> {
     let o = NamedNodeRef::new_unchecked("http://example.com/o");
     let g = NamedNodeRef::new_unchecked("http://example.com/g");
     let bnode = BlankNodeRef::new_unchecked("f2fef82410957224105241225fd0a648");
+    Store::migrate("tests/rocksdb_bc_rdf_star_data")?;
     for _ in 0..2 {
         let store = Store::open("tests/rocksdb_bc_rdf_star_data")?;
         assert!(store.contains(QuadRef::new(s, p, o, g))?);
@@ -572,14 +2168,69 @@ fn test_read_only() -> Result<(), Box<dyn Error>> {
     read_write.flush()?;
     read_write.optimize()?; // Makes sure it's well flushed
 
-    // The new quad is in the read-write instance but not the read-only instance
+    // The read-only instance only sees the new writes after catching up with the read-write instance
     assert!(read_write.contains(second_quad)?);
     assert!(!read_only.contains(second_quad)?);
+    read_only.catch_up_with_primary()?;
+    assert!(read_only.contains(second_quad)?);
     read_only.validate()?;
 
     Ok(())
 }
 
+#[test]
+#[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
+fn test_read_only_writes() -> Result<(), Box<dyn Error>> {
+    let quad = QuadRef::new(
+        NamedNodeRef::new_unchecked("http://example.com/s"),
+        NamedNodeRef::new_unchecked("http://example.com/p"),
+        NamedNodeRef::new_unchecked("http://example.com/o"),
+        GraphNameRef::DefaultGraph,
+    );
+    let store_dir = TempDir::new()?;
+    Store::open(&store_dir)?.insert(quad)?;
+
+    // Several read-only instances can be opened at the same time
+    let read_only = Store::open_read_only(&store_dir)?;
+    let other_read_only = Store::open_read_only(&store_dir)?;
+    assert!(matches!(
+        read_only.insert(quad),
+        Err(StorageError::ReadOnly)
+    ));
+    assert!(matches!(
+        read_only.remove(quad),
+        Err(StorageError::ReadOnly)
+    ));
+    assert!(matches!(
+        read_only.start_transaction(),
+        Err(StorageError::ReadOnly)
+    ));
+    // The bulk loader might only fail when the loaded data is written
+    let mut loader = read_only.bulk_loader();
+    match loader.load_quads([quad.into_owned()]) {
+        Ok(()) => assert!(matches!(loader.commit(), Err(StorageError::ReadOnly))),
+        Err(error) => assert!(matches!(error, StorageError::ReadOnly)),
+    }
+
+    // Reads work normally
+    assert_eq!(
+        other_read_only
+            .quads_for_pattern(None, None, None, None)
+            .collect::<Result<Vec<_>, _>>()?,
+        [quad.into_owned()]
+    );
+    if let QueryResults::Boolean(result) = SparqlEvaluator::new()
+        .parse_query("ASK { <http://example.com/s> ?p ?o }")?
+        .on_store(&read_only)
+        .execute()?
+    {
+        assert!(result);
+    } else {
+        panic!("ASK queries should return a boolean");
+    }
+    Ok(())
+}
+
 #[test]
 #[cfg(all(not(target_family = "wasm"), feature = "rocksdb"))]
 fn test_open_read_only_bad_dir() -> Result<(), Box<dyn Error>> {
@@ -1,6 +1,9 @@
+use crate::context::{JsonLdContext, JsonLdContextProcessor, JsonNode, json_node_from_events};
+use crate::profile::JsonLdProcessingMode;
+use crate::{JsonLdSyntaxError, MAX_CONTEXT_RECURSION};
 #[cfg(feature = "async-tokio")]
 use json_event_parser::TokioAsyncWriterJsonSerializer;
-use json_event_parser::{JsonEvent, WriterJsonSerializer};
+use json_event_parser::{JsonEvent, SliceJsonParser, WriterJsonSerializer};
 use oxiri::{Iri, IriParseError};
 #[cfg(feature = "rdf-12")]
 use oxrdf::BaseDirection;
@@ -9,9 +12,10 @@ use oxrdf::{
     GraphName, GraphNameRef, NamedNode, NamedOrBlankNode, NamedOrBlankNodeRef, QuadRef, TermRef,
 };
 use std::borrow::Cow;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::io;
 use std::io::Write;
+use std::sync::{Arc, Mutex};
 #[cfg(feature = "async-tokio")]
 use tokio::io::AsyncWrite;
 
@@ -52,6 +56,7 @@ use tokio::io::AsyncWrite;
 pub struct JsonLdSerializer {
     prefixes: BTreeMap<String, String>,
     base_iri: Option<Iri<String>>,
+    context: Option<Vec<JsonEvent<'static>>>,
 }
 
 impl JsonLdSerializer {
@@ -61,6 +66,7 @@ impl JsonLdSerializer {
         Self {
             prefixes: BTreeMap::new(),
             base_iri: None,
+            context: None,
         }
     }
 
@@ -112,6 +118,57 @@ impl JsonLdSerializer {
         Ok(self)
     }
 
+    /// Allows to set a [JSON-LD context](https://www.w3.org/TR/json-ld/#the-context) used to compact the output.
+    ///
+    /// The context is written as is in the `@context` entry of the output.
+    /// Its terms, compact IRI prefixes, `@vocab` and `@base` are used to shorten the property names, the `@id` values and the literal datatypes.
+    /// Objects are still written as value and node objects so that expanding the output gives back exactly the serialized quads.
+    ///
+    /// Remote contexts and [framing](https://www.w3.org/TR/json-ld11-framing/) are not supported.
+    /// ```
+    /// use oxrdf::{GraphNameRef, LiteralRef, NamedNodeRef, QuadRef};
+    /// use oxrdf::vocab::rdf;
+    /// use oxjsonld::JsonLdSerializer;
+    ///
+    /// let mut serializer = JsonLdSerializer::new()
+    ///     .with_context(r#"{"schema":"http://schema.org/","name":"schema:name"}"#)?
+    ///     .for_writer(Vec::new());
+    /// serializer.serialize_quad(QuadRef::new(
+    ///     NamedNodeRef::new("http://example.com#me")?,
+    ///     rdf::TYPE,
+    ///     NamedNodeRef::new("http://schema.org/Person")?,
+    ///     GraphNameRef::DefaultGraph
+    /// ))?;
+    /// serializer.serialize_quad(QuadRef::new(
+    ///     NamedNodeRef::new("http://example.com#me")?,
+    ///     NamedNodeRef::new("http://schema.org/name")?,
+    ///     LiteralRef::new_language_tagged_literal_unchecked("Foo Bar", "en"),
+    ///     GraphNameRef::DefaultGraph
+    /// ))?;
+    /// assert_eq!(
+    ///     b"{\"@context\":{\"schema\":\"http://schema.org/\",\"name\":\"schema:name\"},\"@graph\":[{\"@id\":\"http://example.com#me\",\"http://www.w3.org/1999/02/22-rdf-syntax-ns#type\":[{\"@id\":\"schema:Person\"}],\"name\":[{\"@language\":\"en\",\"@value\":\"Foo Bar\"}]}]}",
+    ///     serializer.finish()?.as_slice()
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn with_context(mut self, context: &str) -> Result<Self, JsonLdSyntaxError> {
+        let mut parser = SliceJsonParser::new(context.as_bytes());
+        let mut events = Vec::new();
+        loop {
+            match parser.parse_next()? {
+                JsonEvent::Eof => break,
+                event => events.push(to_owned_event(event)),
+            }
+        }
+        let mut errors = Vec::new();
+        self.compaction_context(&events, &mut errors);
+        if let Some(error) = errors.into_iter().next() {
+            return Err(error);
+        }
+        self.context = Some(events);
+        Ok(self)
+    }
+
     /// Serializes a JSON-LD file to a [`Write`] implementation.
     ///
     /// This writer does unbuffered writes.
@@ -190,6 +247,11 @@ impl JsonLdSerializer {
     }
 
     fn inner_writer(self) -> InnerJsonLdWriter {
+        let compaction = self.context.as_ref().map(|context| {
+            // The context has already been validated by with_context
+            let (processor, context) = self.compaction_context(context, &mut Vec::new());
+            Box::new(JsonLdCompaction::new(processor, context))
+        });
         InnerJsonLdWriter {
             started: false,
             current_graph_name: None,
@@ -198,8 +260,73 @@ impl JsonLdSerializer {
             emitted_predicates: BTreeSet::new(),
             prefixes: self.prefixes,
             base_iri: self.base_iri,
+            context: self.context,
+            compaction,
         }
     }
+
+    /// Processes the context written in the output i.e. the base IRI and the prefixes followed by the user context
+    fn compaction_context(
+        &self,
+        context: &[JsonEvent<'static>],
+        errors: &mut Vec<JsonLdSyntaxError>,
+    ) -> (JsonLdContextProcessor, JsonLdContext) {
+        let processor = JsonLdContextProcessor {
+            processing_mode: JsonLdProcessingMode::JsonLd1_1,
+            lenient: false,
+            max_context_recursion: MAX_CONTEXT_RECURSION,
+            remote_context_cache: Arc::new(Mutex::new(HashMap::new())),
+            load_document_callback: None,
+        };
+        let Ok(user_context) = json_node_from_events(context.iter().cloned().map(Ok)) else {
+            unreachable!("The context events are always valid JSON")
+        };
+        let local_context = if self.base_iri.is_some() || !self.prefixes.is_empty() {
+            let mut serializer_context = HashMap::new();
+            if let Some(base_iri) = &self.base_iri {
+                serializer_context.insert("@base".into(), JsonNode::String(base_iri.to_string()));
+            }
+            for (prefix_name, prefix_iri) in &self.prefixes {
+                serializer_context.insert(
+                    if prefix_name.is_empty() {
+                        "@vocab".into()
+                    } else {
+                        prefix_name.clone()
+                    },
+                    JsonNode::String(prefix_iri.clone()),
+                );
+            }
+            JsonNode::Array(vec![JsonNode::Object(serializer_context), user_context])
+        } else {
+            user_context
+        };
+        let active_context = processor.process_context(
+            &JsonLdContext::new_empty(None),
+            local_context,
+            None,
+            &mut Vec::new(),
+            false,
+            true,
+            true,
+            errors,
+        );
+        (processor, active_context)
+    }
+}
+
+fn to_owned_event(event: JsonEvent<'_>) -> JsonEvent<'static> {
+    match event {
+        JsonEvent::String(value) => JsonEvent::String(value.into_owned().into()),
+        JsonEvent::Number(value) => JsonEvent::Number(value.into_owned().into()),
+        JsonEvent::Boolean(value) => JsonEvent::Boolean(value),
+        JsonEvent::Null => JsonEvent::Null,
+        JsonEvent::StartArray => JsonEvent::StartArray,
+        JsonEvent::EndArray => JsonEvent::EndArray,
+        JsonEvent::StartObject => JsonEvent::StartObject,
+        JsonEvent::EndObject => JsonEvent::EndObject,
+        JsonEvent::ObjectKey(key) => JsonEvent::ObjectKey(key.into_owned().into()),
+        JsonEvent::Eof => JsonEvent::Eof,
+    }
 }
 
 /// Serializes a JSON-LD file to a [`Write`] implementation.
@@ -331,6 +458,48 @@ pub struct InnerJsonLdWriter {
     emitted_predicates: BTreeSet<String>,
     prefixes: BTreeMap<String, String>,
     base_iri: Option<Iri<String>>,
+    context: Option<Vec<JsonEvent<'static>>>,
+    compaction: Option<Box<JsonLdCompaction>>,
+}
+
+/// The context used to compact the IRIs, with its terms indexed by the IRIs they expand to
+struct JsonLdCompaction {
+    processor: JsonLdContextProcessor,
+    context: JsonLdContext,
+    /// The terms usable as property names or types, by IRI
+    terms: HashMap<String, Vec<String>>,
+    /// The terms usable as compact IRI prefixes, with the IRI they expand to
+    prefixes: Vec<(String, String)>,
+}
+
+impl JsonLdCompaction {
+    fn new(processor: JsonLdContextProcessor, context: JsonLdContext) -> Self {
+        let mut terms = HashMap::<_, Vec<_>>::new();
+        let mut prefixes = Vec::new();
+        for (term, definition) in &context.term_definitions {
+            let Some(Some(iri_mapping)) = &definition.iri_mapping else {
+                continue;
+            };
+            if !definition.reverse_property
+                && definition.context.is_none()
+                && matches!(definition.container_mapping, [] | ["@set"])
+            {
+                terms
+                    .entry(iri_mapping.clone())
+                    .or_default()
+                    .push(term.clone());
+            }
+            if definition.prefix_flag {
+                prefixes.push((term.clone(), iri_mapping.clone()));
+            }
+        }
+        Self {
+            processor,
+            context,
+            terms,
+            prefixes,
+        }
+    }
 }
 
 impl InnerJsonLdWriter {
@@ -433,7 +602,7 @@ impl InnerJsonLdWriter {
         if self.current_predicate.is_none() {
             output.push(JsonEvent::ObjectKey(
                 // TODO: use @type
-                quad.predicate.as_str().into(), // TODO: prefixes including @vocab
+                self.vocab_value(quad.predicate.as_str()),
             ));
             output.push(JsonEvent::StartArray);
             self.current_predicate = Some(quad.predicate.into_owned());
@@ -443,9 +612,14 @@ impl InnerJsonLdWriter {
     }
 
     fn serialize_start(&self, output: &mut Vec<JsonEvent<'_>>) {
-        if self.base_iri.is_some() || !self.prefixes.is_empty() {
+        if self.has_context() {
             output.push(JsonEvent::StartObject);
             output.push(JsonEvent::ObjectKey("@context".into()));
+        }
+        if self.base_iri.is_some() || !self.prefixes.is_empty() {
+            if self.context.is_some() {
+                output.push(JsonEvent::StartArray);
+            }
             output.push(JsonEvent::StartObject);
             if let Some(base_iri) = &self.base_iri {
                 output.push(JsonEvent::ObjectKey("@base".into()));
@@ -460,13 +634,25 @@ impl InnerJsonLdWriter {
                 output.push(JsonEvent::String(prefix_iri.clone().into()));
             }
             output.push(JsonEvent::EndObject);
+        }
+        if let Some(context) = &self.context {
+            output.extend(context.iter().cloned());
+            if self.base_iri.is_some() || !self.prefixes.is_empty() {
+                output.push(JsonEvent::EndArray);
+            }
+        }
+        if self.has_context() {
             output.push(JsonEvent::ObjectKey("@graph".into()));
         }
         output.push(JsonEvent::StartArray);
     }
 
+    fn has_context(&self) -> bool {
+        self.base_iri.is_some() || !self.prefixes.is_empty() || self.context.is_some()
+    }
+
     fn serialize_term<'a>(
-        &self,
+        &mut self,
         term: TermRef<'a>,
         output: &mut Vec<JsonEvent<'a>>,
     ) -> io::Result<()> {
@@ -502,9 +688,9 @@ impl InnerJsonLdWriter {
                     }
                 } else if literal.datatype() != xsd::STRING {
                     output.push(JsonEvent::ObjectKey("@type".into()));
-                    output.push(JsonEvent::String(Self::type_value(
-                        literal.datatype().into(),
-                    )));
+                    output.push(JsonEvent::String(
+                        self.vocab_value(literal.datatype().as_str()),
+                    ));
                 }
                 output.push(JsonEvent::ObjectKey("@value".into()));
                 output.push(JsonEvent::String(literal.value().into()));
@@ -520,9 +706,12 @@ impl InnerJsonLdWriter {
         Ok(())
     }
 
-    fn id_value<'a>(&self, id: NamedOrBlankNodeRef<'a>) -> Cow<'a, str> {
+    fn id_value<'a>(&mut self, id: NamedOrBlankNodeRef<'a>) -> Cow<'a, str> {
         match id {
             NamedOrBlankNodeRef::NamedNode(iri) => {
+                if self.compaction.is_some() {
+                    return self.compact_iri(iri.as_str(), false);
+                }
                 if let Some(base_iri) = &self.base_iri {
                     if let Ok(relative) = base_iri.relativize(&Iri::parse_unchecked(iri.as_str())) {
                         let relative = relative.into_inner();
@@ -540,13 +729,74 @@ impl InnerJsonLdWriter {
         }
     }
 
-    fn type_value(id: NamedOrBlankNodeRef<'_>) -> Cow<'_, str> {
-        match id {
-            NamedOrBlankNodeRef::NamedNode(iri) => iri.as_str().into(),
-            NamedOrBlankNodeRef::BlankNode(bnode) => bnode.to_string().into(),
+    fn vocab_value<'a>(&mut self, iri: &'a str) -> Cow<'a, str> {
+        if self.compaction.is_some() {
+            self.compact_iri(iri, true)
+        } else {
+            iri.into()
         }
     }
 
+    /// Returns the shortest string that expands back to the given IRI using the context.
+    ///
+    /// The candidates are the context terms, the compact IRIs and, depending on `vocab`,
+    /// the IRIs relative to `@vocab` or to the base IRI.
+    /// Terms changing the shape of the values (reverse properties, containers, scoped contexts) are not used.
+    fn compact_iri<'a>(&mut self, iri: &'a str, vocab: bool) -> Cow<'a, str> {
+        let Some(compaction) = &mut self.compaction else {
+            return iri.into();
+        };
+        let mut candidates = Vec::new();
+        if vocab {
+            if let Some(terms) = compaction.terms.get(iri) {
+                candidates.extend(terms.iter().cloned());
+            }
+        }
+        for (term, prefix_iri) in &compaction.prefixes {
+            if let Some(suffix) = iri.strip_prefix(prefix_iri.as_str()) {
+                if !suffix.is_empty() {
+                    candidates.push(format!("{term}:{suffix}"));
+                }
+            }
+        }
+        if vocab {
+            if let Some(suffix) = compaction
+                .context
+                .vocabulary_mapping
+                .as_deref()
+                .and_then(|vocabulary_mapping| iri.strip_prefix(vocabulary_mapping))
+            {
+                if !suffix.is_empty() {
+                    candidates.push(suffix.into());
+                }
+            }
+        } else if let Some(base_iri) = &compaction.context.base_iri {
+            if let Ok(relative) = base_iri.relativize(&Iri::parse_unchecked(iri)) {
+                candidates.push(relative.into_inner());
+            }
+        }
+        candidates.sort_unstable_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        for candidate in candidates {
+            // We check the candidate is not understood differently by IRI expansion
+            if compaction
+                .processor
+                .expand_iri(
+                    &mut compaction.context,
+                    candidate.as_str().into(),
+                    !vocab,
+                    vocab,
+                    None,
+                    &mut HashMap::new(),
+                    &mut Vec::new(),
+                )
+                .is_some_and(|expanded| expanded == iri)
+            {
+                return candidate.into();
+            }
+        }
+        iri.into()
+    }
+
     fn finish(&mut self, output: &mut Vec<JsonEvent<'static>>) {
         if !self.started {
             self.serialize_start(output);
@@ -566,8 +816,188 @@ impl InnerJsonLdWriter {
             output.push(JsonEvent::EndObject)
         }
         output.push(JsonEvent::EndArray);
-        if self.base_iri.is_some() || !self.prefixes.is_empty() {
+        if self.has_context() {
             output.push(JsonEvent::EndObject);
         }
     }
 }
+
+#[cfg(test)]
+#[expect(clippy::panic_in_result_fn)]
+mod tests {
+    use super::*;
+    use crate::JsonLdParser;
+    use oxrdf::{GraphNameRef, LiteralRef, NamedNodeRef, Quad};
+    use std::error::Error;
+
+    fn serialize(
+        serializer: JsonLdSerializer,
+        quads: &[QuadRef<'_>],
+    ) -> Result<String, Box<dyn Error>> {
+        let mut serializer = serializer.for_writer(Vec::new());
+        for quad in quads {
+            serializer.serialize_quad(*quad)?;
+        }
+        Ok(String::from_utf8(serializer.finish()?)?)
+    }
+
+    fn parse(json: &str) -> Result<Vec<Quad>, Box<dyn Error>> {
+        Ok(JsonLdParser::new()
+            .for_slice(json)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn quads() -> Result<Vec<QuadRef<'static>>, Box<dyn Error>> {
+        let me = NamedNodeRef::new("http://example.com/people/me")?;
+        let graph = GraphNameRef::NamedNode(NamedNodeRef::new("http://example.com/graphs/1")?);
+        Ok(vec![
+            QuadRef::new(
+                me,
+                NamedNodeRef::new("http://schema.org/name")?,
+                LiteralRef::new_language_tagged_literal_unchecked("Foo Bar", "en"),
+                GraphNameRef::DefaultGraph,
+            ),
+            QuadRef::new(
+                me,
+                NamedNodeRef::new("http://schema.org/birthDate")?,
+                LiteralRef::new_typed_literal("2000-01-01", xsd::DATE),
+                GraphNameRef::DefaultGraph,
+            ),
+            QuadRef::new(
+                me,
+                NamedNodeRef::new("http://schema.org/description")?,
+                LiteralRef::new_simple_literal("A person"),
+                GraphNameRef::DefaultGraph,
+            ),
+            QuadRef::new(
+                me,
+                NamedNodeRef::new("http://xmlns.com/foaf/0.1/knows")?,
+                NamedNodeRef::new("http://example.com/people/other")?,
+                GraphNameRef::DefaultGraph,
+            ),
+            QuadRef::new(
+                me,
+                NamedNodeRef::new("http://xmlns.com/foaf/0.1/knows")?,
+                NamedNodeRef::new("http://other.example.com/people/1")?,
+                GraphNameRef::DefaultGraph,
+            ),
+            QuadRef::new(
+                me,
+                NamedNodeRef::new("http://schema.org/name")?,
+                LiteralRef::new_simple_literal("Foo"),
+                graph,
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_context_round_trip() -> Result<(), Box<dyn Error>> {
+        let quads = quads()?;
+        let json = serialize(
+            JsonLdSerializer::new().with_context(
+                r#"{
+                "@base": "http://example.com/people/",
+                "@vocab": "http://schema.org/",
+                "@language": "fr",
+                "xsd": "http://www.w3.org/2001/XMLSchema#",
+                "foaf": "http://xmlns.com/foaf/0.1/",
+                "knows": {"@id": "foaf:knows", "@type": "@id"},
+                "desc": "http://schema.org/description"
+            }"#,
+            )?,
+            &quads,
+        )?;
+        assert!(json.contains(r#""@id":"me""#), "{json}");
+        assert!(
+            json.contains(r#""name":[{"@language":"en","@value":"Foo Bar"}]"#),
+            "{json}"
+        );
+        assert!(json.contains(r#""@type":"xsd:date""#), "{json}");
+        assert!(json.contains(r#""desc":[{"@value":"A person"}]"#), "{json}");
+        assert!(
+            json.contains(r#""knows":[{"@id":"other"},{"@id":"//other.example.com/people/1"}]"#),
+            "{json}"
+        );
+        assert!(json.contains(r#""@id":"/graphs/1""#), "{json}");
+        assert_eq!(
+            parse(&json)?,
+            quads
+                .into_iter()
+                .map(QuadRef::into_owned)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_context_with_serializer_prefixes_round_trip() -> Result<(), Box<dyn Error>> {
+        let quads = quads()?;
+        let json = serialize(
+            JsonLdSerializer::new()
+                .with_base_iri("http://example.com/")?
+                .with_prefix("schema", "http://schema.org/")?
+                .with_context(r#"{"foaf": "http://xmlns.com/foaf/0.1/"}"#)?,
+            &quads,
+        )?;
+        assert!(
+            json.starts_with(r#"{"@context":[{"@base":"http://example.com/","schema":"http://schema.org/"},{"foaf":"http://xmlns.com/foaf/0.1/"}],"@graph":["#),
+            "{json}"
+        );
+        assert!(json.contains(r#""@id":"people/me""#), "{json}");
+        assert!(json.contains(r#""schema:name""#), "{json}");
+        assert!(json.contains(r#""foaf:knows""#), "{json}");
+        assert_eq!(
+            parse(&json)?,
+            quads
+                .into_iter()
+                .map(QuadRef::into_owned)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_context_ignores_shape_changing_terms() -> Result<(), Box<dyn Error>> {
+        let quads = quads()?;
+        let json = serialize(
+            JsonLdSerializer::new().with_context(
+                r#"{
+                "knows": {"@id": "http://xmlns.com/foaf/0.1/knows", "@container": "@list"},
+                "name": {"@reverse": "http://schema.org/name"},
+                "schema": "http://schema.org/"
+            }"#,
+            )?,
+            &quads,
+        )?;
+        assert!(!json.contains(r#""knows":["#), "{json}");
+        assert!(!json.contains(r#""name":["#), "{json}");
+        assert!(
+            json.contains(r#""http://xmlns.com/foaf/0.1/knows":"#),
+            "{json}"
+        );
+        assert!(json.contains(r#""schema:name":"#), "{json}");
+        assert_eq!(
+            parse(&json)?,
+            quads
+                .into_iter()
+                .map(QuadRef::into_owned)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_context() {
+        assert!(JsonLdSerializer::new().with_context("{").is_err());
+        assert!(
+            JsonLdSerializer::new()
+                .with_context(r#"{"foo": 1}"#)
+                .is_err()
+        );
+        assert!(
+            JsonLdSerializer::new()
+                .with_context(r#""http://example.com/context.jsonld""#)
+                .is_err()
+        );
+    }
+}
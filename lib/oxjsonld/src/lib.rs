@@ -24,3 +24,8 @@ pub use to_rdf::TokioAsyncReaderJsonLdParser;
 pub use to_rdf::{JsonLdParser, JsonLdPrefixesIter, ReaderJsonLdParser, SliceJsonLdParser};
 
 const MAX_CONTEXT_RECURSION: usize = 8;
+/// Default maximal nesting depth of JSON objects and arrays.
+///
+/// It is higher than the Turtle and TriG default of 100 because every JSON object and array counts as a level:
+/// a nested node object usually takes two levels (its value array and itself) and `@context`, `@graph`, `@list` or value objects add more.
+const DEFAULT_MAX_NESTING_DEPTH: usize = 1024;
@@ -1,3 +1,4 @@
+use crate::DEFAULT_MAX_NESTING_DEPTH;
 use crate::context::{JsonLdLoadDocumentOptions, JsonLdRemoteDocument, JsonLdTermDefinition};
 use crate::error::{JsonLdParseError, JsonLdSyntaxError};
 use crate::expansion::{JsonLdEvent, JsonLdExpansionConverter, JsonLdValue};
@@ -65,6 +66,7 @@ pub struct JsonLdParser {
     lenient: bool,
     profile: JsonLdProfileSet,
     base: Option<Iri<String>>,
+    max_nesting_depth: Option<usize>,
 }
 
 impl JsonLdParser {
@@ -143,6 +145,31 @@ impl JsonLdParser {
         Ok(self)
     }
 
+    /// Sets the maximum nesting depth of JSON objects and arrays.
+    ///
+    /// Documents nested more deeply are rejected with a syntax error.
+    ///
+    /// Default: 1024 levels
+    ///
+    /// ```
+    /// use oxjsonld::JsonLdParser;
+    ///
+    /// let file = format!(
+    ///     r#"{{"@context": {{"@vocab": "http://example.com/"}}, "p": {}{{}}{}}}"#,
+    ///     r#"{"p": "#.repeat(20),
+    ///     "}".repeat(20)
+    /// );
+    /// let mut parser = JsonLdParser::new()
+    ///     .with_max_nesting_depth(10)
+    ///     .for_slice(&file);
+    /// assert!(parser.any(|quad| quad.is_err()));
+    /// ```
+    #[inline]
+    pub fn with_max_nesting_depth(mut self, max_depth: usize) -> Self {
+        self.max_nesting_depth = Some(max_depth);
+        self
+    }
+
     /// Parses a JSON-LD file from a [`Read`] implementation.
     ///
     /// Count the number of people:
@@ -294,6 +321,8 @@ impl JsonLdParser {
                 lenient: self.lenient,
             },
             json_error: false,
+            depth: 0,
+            max_nesting_depth: self.max_nesting_depth.unwrap_or(DEFAULT_MAX_NESTING_DEPTH),
         }
     }
 }
@@ -850,6 +879,8 @@ struct InternalJsonLdParser {
     expended_events: Vec<JsonLdEvent>,
     to_rdf: JsonLdToRdfConverter,
     json_error: bool,
+    depth: usize,
+    max_nesting_depth: usize,
 }
 
 impl InternalJsonLdParser {
@@ -859,6 +890,23 @@ impl InternalJsonLdParser {
         results: &mut Vec<Quad>,
         errors: &mut Vec<JsonLdSyntaxError>,
     ) {
+        match event {
+            JsonEvent::StartObject | JsonEvent::StartArray => {
+                self.depth += 1;
+                if self.depth > self.max_nesting_depth {
+                    errors.push(JsonLdSyntaxError::msg(format!(
+                        "Maximum nesting depth of {} exceeded",
+                        self.max_nesting_depth
+                    )));
+                    self.json_error = true;
+                    return;
+                }
+            }
+            JsonEvent::EndObject | JsonEvent::EndArray => {
+                self.depth = self.depth.saturating_sub(1);
+            }
+            _ => (),
+        }
         self.expansion
             .convert_event(event, &mut self.expended_events, errors);
         for event in self.expended_events.drain(..) {
@@ -1512,4 +1560,28 @@ mod tests {
             Some(RdfJsonNumber::Double("0.0E0".into()))
         );
     }
+
+    #[test]
+    fn test_max_nesting_depth() {
+        let file = format!(
+            r#"{{"@context": {{"@vocab": "http://example.com/"}}, "p": {}{{}}{}}}"#,
+            r#"{"p": "#.repeat(10_000),
+            "}".repeat(10_000)
+        );
+        let error = JsonLdParser::new()
+            .for_slice(&file)
+            .find_map(Result::err)
+            .unwrap();
+        assert_eq!(error.to_string(), "Maximum nesting depth of 1024 exceeded");
+        let error = JsonLdParser::new()
+            .with_max_nesting_depth(5)
+            .for_reader(file.as_bytes())
+            .find_map(Result::err)
+            .unwrap();
+        assert_eq!(error.to_string(), "Maximum nesting depth of 5 exceeded");
+        assert!(JsonLdParser::new()
+            .with_max_nesting_depth(5)
+            .for_slice(r#"{"@id": "http://example.com/s", "http://example.com/p": {"@id": "http://example.com/o"}}"#)
+            .all(|quad| quad.is_ok()));
+    }
 }
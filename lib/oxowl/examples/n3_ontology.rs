@@ -172,7 +172,7 @@ fn n3_rules_example() -> Result<(), Box<dyn std::error::Error>> {
         println!("\n✓ Rule is OWL-compatible!");
 
         // Convert to OWL axioms
-        let axioms = rule.to_owl_axioms();
+        let axioms = rule.to_owl_axioms()?;
         println!("\nConverted to {} OWL axiom(s):", axioms.len());
 
         for axiom in &axioms {
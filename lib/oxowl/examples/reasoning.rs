@@ -202,6 +202,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         max_iterations: 100_000,
         check_consistency: true,
         materialize: true,
+        ..Default::default()
     };
 
     let mut reasoner = RlReasoner::with_config(&ontology, config);
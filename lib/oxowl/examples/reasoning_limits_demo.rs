@@ -153,6 +153,7 @@ fn demo_timeout_enforcement() -> Result<(), Box<dyn std::error::Error>> {
         max_inferred_triples: None,
         check_consistency: false,
         materialize: true,
+        ..Default::default()
     };
 
     let start = Instant::now();
@@ -223,6 +224,7 @@ fn demo_materialization_limit() -> Result<(), Box<dyn std::error::Error>> {
         max_inferred_triples: Some(5_000),
         check_consistency: false,
         materialize: true,
+        ..Default::default()
     };
 
     let start = Instant::now();
@@ -352,6 +354,7 @@ fn demo_symmetric_transitive_worst_case() -> Result<(), Box<dyn std::error::Erro
         max_inferred_triples: Some(2_000),
         check_consistency: false,
         materialize: true,
+        ..Default::default()
     };
 
     let start = Instant::now();
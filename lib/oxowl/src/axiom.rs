@@ -7,7 +7,7 @@ use crate::expression::{ClassExpression, ObjectPropertyExpression, DataRange};
 use oxrdf::Literal;
 
 /// An OWL 2 axiom.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Axiom {
     // === Class Axioms ===
 
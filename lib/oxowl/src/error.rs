@@ -165,8 +165,12 @@ pub enum ReasoningErrorKind {
     MaxIterationsExceeded,
     /// Reasoning exceeded memory limit.
     MemoryLimitExceeded,
+    /// Reasoning derived more axioms than allowed.
+    AxiomLimitExceeded,
     /// Unsupported OWL construct for the profile.
     UnsupportedConstruct,
+    /// The reasoner configuration is invalid.
+    InvalidConfiguration,
     /// Timeout during reasoning.
     Timeout,
     /// Internal error.
@@ -178,7 +182,9 @@ impl fmt::Display for ReasoningErrorKind {
         match self {
             Self::MaxIterationsExceeded => write!(f, "Max iterations exceeded"),
             Self::MemoryLimitExceeded => write!(f, "Memory limit exceeded"),
+            Self::AxiomLimitExceeded => write!(f, "Axiom limit exceeded"),
             Self::UnsupportedConstruct => write!(f, "Unsupported construct"),
+            Self::InvalidConfiguration => write!(f, "Invalid configuration"),
             Self::Timeout => write!(f, "Timeout"),
             Self::Internal => write!(f, "Internal error"),
         }
@@ -202,6 +208,14 @@ impl ReasoningError {
         )
     }
 
+    /// Creates an inferred axiom limit exceeded error.
+    pub fn axiom_limit_exceeded(limit: usize) -> Self {
+        Self::new(
+            ReasoningErrorKind::AxiomLimitExceeded,
+            format!("Inferred more than {limit} axioms"),
+        )
+    }
+
     /// Creates an unsupported construct error.
     pub fn unsupported_construct(construct: &str, profile: &str) -> Self {
         Self::new(
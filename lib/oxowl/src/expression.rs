@@ -6,7 +6,7 @@ use oxrdf::{NamedNode, Literal};
 /// An OWL 2 class expression.
 ///
 /// Class expressions describe sets of individuals through various constructors.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ClassExpression {
     /// A named class (atomic class)
     Class(OwlClass),
@@ -207,7 +207,7 @@ impl From<ObjectProperty> for ObjectPropertyExpression {
 }
 
 /// An OWL 2 data range.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DataRange {
     /// A named datatype (e.g., xsd:string)
     Datatype(NamedNode),
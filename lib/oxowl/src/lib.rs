@@ -73,17 +73,20 @@ mod entity;
 mod axiom;
 mod expression;
 mod ontology;
+mod profile;
 mod error;
 mod parser;
 mod serializer;
 pub mod n3_integration;
 pub mod n3_rules;
+pub mod swrl_rules;
 
 pub use entity::{OwlClass, ObjectProperty, DataProperty, AnnotationProperty, Individual};
 pub use axiom::Axiom;
 pub use expression::{ClassExpression, ObjectPropertyExpression, DataRange};
 pub use ontology::Ontology;
-pub use error::{OwlError, OwlParseError};
+pub use profile::ProfileViolation;
+pub use error::{InconsistencyError, OwlError, OwlParseError, ParseErrorKind, ReasoningError, ReasoningErrorKind};
 pub use parser::{parse_ontology, parse_ontology_with_config, parse_ontology_from_n3, parse_ontology_from_n3_with_config, OntologyParser, ParserConfig};
 pub use serializer::{serialize_ontology, serialize_ontology_with_config, OntologySerializer, SerializerConfig};
 
@@ -91,4 +94,4 @@ pub use serializer::{serialize_ontology, serialize_ontology_with_config, Ontolog
 mod reasoner;
 
 #[cfg(feature = "reasoner-rl")]
-pub use reasoner::{Reasoner, RlReasoner, ReasonerConfig};
+pub use reasoner::{Justification, Reasoner, ReasonerConfig, ReasonerStats, RlReasoner, RlRule};
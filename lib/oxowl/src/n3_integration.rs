@@ -6,10 +6,12 @@
 //! - Support N3-based reasoning rules
 
 use crate::error::{OwlParseError, ParseErrorKind};
+use crate::n3_rules::{convert_rules, N3RuleExtractor, UnconvertedRule};
 use crate::ontology::Ontology;
 use crate::parser::{OntologyParser, ParserConfig};
-use oxrdf::{Graph, Quad};
+use oxrdf::{BlankNode, Graph, Quad, Variable};
 use oxttl::n3::{N3Parser, N3Quad};
+use rustc_hash::FxHashMap;
 use std::io::Read;
 
 /// Parses an OWL ontology from N3 format.
@@ -58,45 +60,82 @@ pub fn parse_n3_ontology_with_config<R: Read>(
     reader: R,
     config: ParserConfig,
 ) -> Result<Ontology, OwlParseError> {
+    parse_n3_ontology_with_rules(reader, config).map(|(ontology, _)| ontology)
+}
+
+/// Parses an OWL ontology from N3 format and reports the rules that could not be converted.
+///
+/// The `log:implies` rules matching a pattern supported by [`N3Rule::to_owl_axioms`](crate::n3_rules::N3Rule::to_owl_axioms)
+/// are added to the ontology as OWL axioms.
+/// The other rules are returned with the reason they were not converted,
+/// they need an external N3 engine to be applied.
+///
+/// # Example
+///
+/// ```
+/// use oxowl::n3_integration::parse_n3_ontology_with_rules;
+/// use oxowl::ParserConfig;
+///
+/// let n3_data = r#"
+/// @prefix ex: <http://example.org/> .
+///
+/// { ?x a ex:Dog } => { ?x a ex:Animal } .
+/// { ?x ex:owns ?y } => { ?y a ex:Pet } .
+/// "#;
+///
+/// let (ontology, unconverted) =
+///     parse_n3_ontology_with_rules(n3_data.as_bytes(), ParserConfig::new()).unwrap();
+/// assert_eq!(ontology.axiom_count(), 1); // Dog subClassOf Animal
+/// assert_eq!(unconverted.len(), 1);
+/// assert_eq!(unconverted[0].body().len(), 1);
+/// ```
+pub fn parse_n3_ontology_with_rules<R: Read>(
+    reader: R,
+    config: ParserConfig,
+) -> Result<(Ontology, Vec<UnconvertedRule>), OwlParseError> {
     // Parse N3 into quads
-    let quads = parse_n3_to_quads(reader)?;
+    let n3_quads = parse_n3_quads(reader)?;
+    let quads = n3_quads
+        .iter()
+        .cloned()
+        .filter_map(n3_quad_to_quad)
+        .collect::<Vec<_>>();
 
     // Convert quads to graph
     let graph = quads_to_graph(&quads);
 
     // Parse OWL ontology from the graph
     let mut parser = OntologyParser::with_config(&graph, config);
-    parser.parse()
+    let mut ontology = parser.parse()?;
+
+    // Rules are usually written with variables, keep them as blank nodes
+    let mut variables = FxHashMap::default();
+    let rule_quads = n3_quads
+        .into_iter()
+        .filter_map(|n3_quad| n3_quad_to_rule_quad(n3_quad, &mut variables))
+        .collect();
+    let extractor = N3RuleExtractor::new(rule_quads);
+    let (axioms, unconverted) = convert_rules(&extractor.extract_rules());
+    for axiom in axioms {
+        ontology.add_axiom(axiom);
+    }
+
+    Ok((ontology, unconverted))
 }
 
-/// Parses N3 input into a vector of quads.
+/// Parses N3 input into a vector of N3 quads.
 ///
-/// This handles N3-specific features like formulas and variables,
-/// converting them into RDF quads where possible.
-fn parse_n3_to_quads<R: Read>(reader: R) -> Result<Vec<Quad>, OwlParseError> {
-    let parser = N3Parser::new().for_reader(reader);
-    let mut quads = Vec::new();
-
-    for result in parser {
-        match result {
-            Ok(n3_quad) => {
-                // Try to convert N3Quad to standard Quad
-                if let Some(quad) = n3_quad_to_quad(n3_quad) {
-                    quads.push(quad);
-                }
-                // Skip quads with variables or other N3-specific features that
-                // don't translate directly to OWL
-            }
-            Err(e) => {
-                return Err(OwlParseError::new(
-                    ParseErrorKind::Syntax,
-                    format!("N3 parsing error: {}", e),
-                ));
-            }
-        }
-    }
-
-    Ok(quads)
+/// Variables are kept, they are converted by [`n3_quad_to_quad`] or [`n3_quad_to_rule_quad`]
+/// depending on whether the quads are used for the ontology or for the rules.
+fn parse_n3_quads<R: Read>(reader: R) -> Result<Vec<N3Quad>, OwlParseError> {
+    N3Parser::new()
+        .for_reader(reader)
+        .map(|result| {
+            result.map_err(|e| {
+                OwlParseError::new(ParseErrorKind::Syntax, format!("N3 parsing error: {e}"))
+            })
+        })
+        .collect()
 }
 
 /// Converts an N3Quad to a standard RDF Quad if possible.
@@ -104,15 +143,36 @@ fn parse_n3_to_quads<R: Read>(reader: R) -> Result<Vec<Quad>, OwlParseError> {
 /// Returns None if the N3Quad contains variables or other N3-specific
 /// features that cannot be represented in standard RDF.
 fn n3_quad_to_quad(n3_quad: N3Quad) -> Option<Quad> {
-    use oxrdf::{NamedOrBlankNode, Subject, Term};
+    convert_n3_quad(n3_quad, |_| None)
+}
+
+/// Converts an N3Quad to a standard RDF Quad, replacing variables by blank nodes.
+///
+/// A variable is always replaced by the same blank node so that a rule keeps
+/// the bindings shared by its antecedent and its consequent.
+fn n3_quad_to_rule_quad(
+    n3_quad: N3Quad,
+    variables: &mut FxHashMap<Variable, BlankNode>,
+) -> Option<Quad> {
+    convert_n3_quad(n3_quad, |variable| {
+        Some(variables.entry(variable).or_default().clone())
+    })
+}
+
+/// Converts an N3Quad to a standard RDF Quad, using `variable` to convert variables.
+fn convert_n3_quad(
+    n3_quad: N3Quad,
+    mut variable: impl FnMut(Variable) -> Option<BlankNode>,
+) -> Option<Quad> {
+    use oxrdf::{NamedOrBlankNode, Term};
     use oxttl::n3::N3Term;
 
     // Convert subject
     #[allow(unreachable_patterns)]
     let subject = match n3_quad.subject {
-        N3Term::NamedNode(n) => Subject::NamedNode(n),
-        N3Term::BlankNode(b) => Subject::BlankNode(b),
-        N3Term::Variable(_) => return None, // Skip variables
+        N3Term::NamedNode(n) => NamedOrBlankNode::NamedNode(n),
+        N3Term::BlankNode(b) => NamedOrBlankNode::BlankNode(b),
+        N3Term::Variable(v) => NamedOrBlankNode::BlankNode(variable(v)?),
         N3Term::Literal(_) => return None,  // Invalid as subject in standard RDF
         #[cfg(feature = "rdf-12")]
         N3Term::Triple(_) => return None,   // RDF-star triples require special handling
@@ -132,7 +192,7 @@ fn n3_quad_to_quad(n3_quad: N3Quad) -> Option<Quad> {
         N3Term::NamedNode(n) => Term::NamedNode(n),
         N3Term::BlankNode(b) => Term::BlankNode(b),
         N3Term::Literal(l) => Term::Literal(l),
-        N3Term::Variable(_) => return None, // Skip variables
+        N3Term::Variable(v) => Term::BlankNode(variable(v)?),
         #[cfg(feature = "rdf-12")]
         N3Term::Triple(_) => return None,   // RDF-star triples require special handling
         #[cfg(not(feature = "rdf-12"))]
@@ -260,7 +320,11 @@ ex:hasOwner rdfs:range ex:Person .
 ex:Fido a ex:Dog .
 "#;
 
-        let quads = parse_n3_to_quads(n3_data.as_bytes()).unwrap();
+        let quads = parse_n3_quads(n3_data.as_bytes())
+            .unwrap()
+            .into_iter()
+            .filter_map(n3_quad_to_quad)
+            .collect::<Vec<_>>();
         // Should have at least the Fido triple
         // Variables in formulas are skipped
         assert!(!quads.is_empty());
@@ -306,13 +370,13 @@ ex:Fido a ex:Dog .
 
     #[test]
     fn test_extract_formulas() {
-        use oxrdf::{BlankNode, GraphName, NamedNode, Subject, Term};
+        use oxrdf::{BlankNode, GraphName, NamedNode, NamedOrBlankNode, Term};
 
         let bn = BlankNode::new("f1").unwrap();
         let ex = NamedNode::new("http://example.org/test").unwrap();
 
         let quad = Quad {
-            subject: Subject::NamedNode(ex.clone()),
+            subject: NamedOrBlankNode::NamedNode(ex.clone()),
             predicate: ex.clone(),
             object: Term::NamedNode(ex),
             graph_name: GraphName::BlankNode(bn.clone()),
@@ -5,10 +5,11 @@
 //! allowing for rule-based inference.
 
 use crate::axiom::Axiom;
-use crate::entity::{Individual, ObjectProperty, OwlClass};
-use crate::expression::ClassExpression;
+use crate::entity::{ObjectProperty, OwlClass};
+use crate::error::{OwlParseError, ParseErrorKind};
+use crate::expression::{ClassExpression, ObjectPropertyExpression};
 use crate::ontology::Ontology;
-use oxrdf::{BlankNode, Formula, NamedNode, Quad, Term, Triple};
+use oxrdf::{BlankNode, Formula, NamedNode, NamedOrBlankNode, Quad, Term, Triple};
 use oxrdf::vocab::rdf;
 use rustc_hash::FxHashSet;
 use std::collections::HashMap;
@@ -38,11 +39,11 @@ impl N3Rule {
     ///
     /// Returns true if the rule expresses patterns that can be
     /// converted to OWL axioms (e.g., subclass relationships,
-    /// property assertions).
+    /// sub-property relationships, property chains).
     pub fn is_owl_compatible(&self) -> bool {
-        // Check if the rule expresses simple class hierarchy patterns
-        // For example: { ?x a :Dog } => { ?x a :Animal }
-        self.is_subclass_pattern() || self.is_property_implication()
+        self.is_subclass_pattern()
+            || self.is_property_implication()
+            || self.is_property_chain_pattern()
     }
 
     /// Checks if the rule represents a subclass relationship.
@@ -63,33 +64,60 @@ impl N3Rule {
             && subjects_match(&ant_triple.subject, &cons_triple.subject)
     }
 
+    /// Checks if the rule represents a property chain.
+    ///
+    /// Pattern: { ?x P1 ?y1 . ?y1 P2 ?y2 . ... ?yn-1 Pn ?z } => { ?x P ?z }
+    /// This implies P1 o ... o Pn rdfs:subPropertyOf P
+    pub fn is_property_chain_pattern(&self) -> bool {
+        self.property_path()
+            .is_some_and(|(chain, _)| chain.len() > 1)
+    }
+
     /// Checks if the rule represents a property implication.
+    ///
+    /// Pattern: { ?x P1 ?y } => { ?x P2 ?y }
+    /// This implies P1 rdfs:subPropertyOf P2
     fn is_property_implication(&self) -> bool {
-        // Pattern: { ?x property1 ?y } => { ?x property2 ?y }
-        // This could represent property hierarchy or characteristics
-        if self.antecedent.triples().len() != 1 || self.consequent.triples().len() != 1 {
-            return false;
-        }
-
-        let ant_triple = &self.antecedent.triples()[0];
-        let cons_triple = &self.consequent.triples()[0];
-
-        // Subject and object should match (same variables)
-        subjects_match(&ant_triple.subject, &cons_triple.subject)
-            && objects_match(&ant_triple.object, &cons_triple.object)
+        self.property_path()
+            .is_some_and(|(chain, _)| chain.len() == 1)
     }
 
-    /// Converts this N3 rule to OWL axioms if possible.
-    pub fn to_owl_axioms(&self) -> Vec<Axiom> {
-        let mut axioms = Vec::new();
-
+    /// Converts this N3 rule to OWL axioms.
+    ///
+    /// Returns an error if the rule does not match any pattern with an OWL 2 RL equivalent,
+    /// so that such rules are not silently ignored.
+    pub fn to_owl_axioms(&self) -> Result<Vec<Axiom>, OwlParseError> {
         if self.is_subclass_pattern() {
             if let Some(axiom) = self.extract_subclass_axiom() {
-                axioms.push(axiom);
+                return Ok(vec![axiom]);
             }
         }
 
-        axioms
+        if let Some((mut chain, super_property)) = self.property_path() {
+            let axiom = if chain.len() == 1 {
+                Axiom::SubObjectPropertyOf {
+                    sub_property: ObjectPropertyExpression::ObjectProperty(chain.remove(0)),
+                    super_property: ObjectPropertyExpression::ObjectProperty(super_property),
+                }
+            } else {
+                Axiom::SubPropertyChainOf {
+                    property_chain: chain
+                        .into_iter()
+                        .map(ObjectPropertyExpression::ObjectProperty)
+                        .collect(),
+                    super_property,
+                }
+            };
+            return Ok(vec![axiom]);
+        }
+
+        Err(OwlParseError::new(
+            ParseErrorKind::UnknownConstruct,
+            format!(
+                "N3 rule {} => {} does not match any OWL 2 RL pattern",
+                self.antecedent, self.consequent
+            ),
+        ))
     }
 
     /// Extracts a SubClassOf axiom from a subclass pattern rule.
@@ -113,6 +141,87 @@ impl N3Rule {
             super_class: ClassExpression::Class(super_class),
         })
     }
+
+    /// Orders the antecedent triples into a path of properties linking the subject of the
+    /// consequent to its object.
+    ///
+    /// Returns the properties of the path and the property of the consequent, or `None` if
+    /// the antecedent is not a single chain of distinct variables or contains anything else.
+    fn property_path(&self) -> Option<(Vec<ObjectProperty>, ObjectProperty)> {
+        let [cons_triple] = self.consequent.triples() else {
+            return None;
+        };
+        let start = variable_subject(&cons_triple.subject)?;
+        let end = variable_object(&cons_triple.object)?;
+        if start == end || cons_triple.predicate.as_ref() == rdf::TYPE {
+            return None;
+        }
+
+        let mut links = self
+            .antecedent
+            .triples()
+            .iter()
+            .map(|triple| {
+                if triple.predicate.as_ref() == rdf::TYPE {
+                    return None;
+                }
+                Some((
+                    variable_subject(&triple.subject)?,
+                    &triple.predicate,
+                    variable_object(&triple.object)?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut chain = Vec::with_capacity(links.len());
+        let mut visited = FxHashSet::default();
+        let mut current = start;
+        while current != end {
+            if !visited.insert(current) {
+                return None;
+            }
+            let mut next = links
+                .iter()
+                .enumerate()
+                .filter(|(_, (s, _, _))| *s == current);
+            let (index, _) = next.next()?;
+            if next.next().is_some() {
+                // Branching bodies are not chains
+                return None;
+            }
+            let (_, predicate, object) = links.swap_remove(index);
+            chain.push(ObjectProperty::new(predicate.clone()));
+            current = object;
+        }
+        if !links.is_empty() {
+            return None;
+        }
+
+        Some((chain, ObjectProperty::new(cons_triple.predicate.clone())))
+    }
+}
+
+/// An N3 rule that does not match any pattern with an OWL 2 RL equivalent.
+///
+/// Such rules need an external N3 engine to be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnconvertedRule {
+    /// The rule that could not be converted
+    pub rule: N3Rule,
+    /// Why the rule could not be converted
+    pub reason: String,
+}
+
+impl UnconvertedRule {
+    /// Returns the triples of the rule body (antecedent).
+    pub fn body(&self) -> &[Triple] {
+        self.rule.antecedent.triples()
+    }
+
+    /// Returns the triples of the rule head (consequent).
+    pub fn head(&self) -> &[Triple] {
+        self.rule.consequent.triples()
+    }
 }
 
 /// N3 rule extractor that finds logical rules in RDF graphs.
@@ -152,7 +261,7 @@ impl N3RuleExtractor {
         for quad in &self.quads {
             if quad.predicate == implies {
                 // Subject and object should be blank nodes representing formulas
-                if let oxrdf::Subject::BlankNode(ant_id) = &quad.subject {
+                if let NamedOrBlankNode::BlankNode(ant_id) = &quad.subject {
                     if let Term::BlankNode(cons_id) = &quad.object {
                         if let (Some(&ant_formula), Some(&cons_formula)) =
                             (formula_map.get(ant_id), formula_map.get(cons_id))
@@ -176,59 +285,88 @@ impl N3RuleExtractor {
     }
 
     /// Converts extracted rules to OWL axioms.
-    pub fn rules_to_axioms(&self, rules: &[N3Rule]) -> Vec<Axiom> {
-        rules
-            .iter()
-            .flat_map(|rule| rule.to_owl_axioms())
-            .collect()
+    ///
+    /// Fails on the first rule without an OWL 2 RL equivalent.
+    pub fn rules_to_axioms(rules: &[N3Rule]) -> Result<Vec<Axiom>, OwlParseError> {
+        let mut axioms = Vec::new();
+        for rule in rules {
+            axioms.extend(rule.to_owl_axioms()?);
+        }
+        Ok(axioms)
     }
 }
 
+/// Converts rules to OWL axioms, collecting the rules that can't be converted instead of failing.
+pub fn convert_rules(rules: &[N3Rule]) -> (Vec<Axiom>, Vec<UnconvertedRule>) {
+    let mut axioms = Vec::new();
+    let mut unconverted = Vec::new();
+    for rule in rules {
+        match rule.to_owl_axioms() {
+            Ok(rule_axioms) => axioms.extend(rule_axioms),
+            Err(e) => unconverted.push(UnconvertedRule {
+                rule: rule.clone(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+    (axioms, unconverted)
+}
+
 /// Extends an ontology with axioms derived from N3 rules.
 ///
 /// This analyzes N3 logical rules in the source data and adds
-/// corresponding OWL axioms to the ontology where possible.
+/// corresponding OWL axioms to the ontology.
+/// Returns the number of axioms added, or an error without modifying the ontology
+/// if a rule has no OWL 2 RL equivalent.
 pub fn extend_ontology_with_n3_rules(
     ontology: &mut Ontology,
     quads: &[Quad],
-) -> usize {
+) -> Result<usize, OwlParseError> {
     let extractor = N3RuleExtractor::new(quads.to_vec());
     let rules = extractor.extract_rules();
-    let axioms = extractor.rules_to_axioms(&rules);
+    let axioms = N3RuleExtractor::rules_to_axioms(&rules)?;
 
     let count = axioms.len();
     for axiom in axioms {
         ontology.add_axiom(axiom);
     }
 
-    count
+    Ok(count)
 }
 
 /// Helper function to check if two subjects match (typically both variables or both the same IRI).
-fn subjects_match(s1: &oxrdf::Subject, s2: &oxrdf::Subject) -> bool {
+fn subjects_match(s1: &NamedOrBlankNode, s2: &NamedOrBlankNode) -> bool {
     // For now, we check if they're both blank nodes (representing variables in formulas)
     // or if they're the same named node
     match (s1, s2) {
-        (oxrdf::Subject::BlankNode(_), oxrdf::Subject::BlankNode(_)) => true,
-        (oxrdf::Subject::NamedNode(n1), oxrdf::Subject::NamedNode(n2)) => n1 == n2,
+        (NamedOrBlankNode::BlankNode(_), NamedOrBlankNode::BlankNode(_)) => true,
+        (NamedOrBlankNode::NamedNode(n1), NamedOrBlankNode::NamedNode(n2)) => n1 == n2,
         _ => false,
     }
 }
 
-/// Helper function to check if two objects match.
-fn objects_match(o1: &Term, o2: &Term) -> bool {
-    match (o1, o2) {
-        (Term::BlankNode(_), Term::BlankNode(_)) => true,
-        (Term::NamedNode(n1), Term::NamedNode(n2)) => n1 == n2,
-        (Term::Literal(l1), Term::Literal(l2)) => l1 == l2,
-        _ => false,
+/// Returns the variable (blank node) in subject position, if any.
+fn variable_subject(subject: &NamedOrBlankNode) -> Option<&BlankNode> {
+    if let NamedOrBlankNode::BlankNode(b) = subject {
+        Some(b)
+    } else {
+        None
+    }
+}
+
+/// Returns the variable (blank node) in object position, if any.
+fn variable_object(object: &Term) -> Option<&BlankNode> {
+    if let Term::BlankNode(b) = object {
+        Some(b)
+    } else {
+        None
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use oxrdf::{BlankNode, GraphName, NamedNode, Subject, Triple};
+    use oxrdf::{BlankNode, GraphName, NamedNode, NamedOrBlankNode, Triple};
 
     #[test]
     fn test_n3_rule_creation() {
@@ -270,7 +408,7 @@ mod tests {
         let cons_formula = Formula::new(BlankNode::default(), vec![cons_triple]);
 
         let rule = N3Rule::new(ant_formula, cons_formula);
-        let axioms = rule.to_owl_axioms();
+        let axioms = rule.to_owl_axioms().unwrap();
 
         assert_eq!(axioms.len(), 1);
         match &axioms[0] {
@@ -282,6 +420,119 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_property_chain_pattern() {
+        let has_parent = NamedNode::new("http://example.org/hasParent").unwrap();
+        let has_brother = NamedNode::new("http://example.org/hasBrother").unwrap();
+        let has_uncle = NamedNode::new("http://example.org/hasUncle").unwrap();
+        let x = BlankNode::new("x").unwrap();
+        let y = BlankNode::new("y").unwrap();
+        let z = BlankNode::new("z").unwrap();
+
+        // Body triples are not in chain order
+        let ant_formula = Formula::new(
+            BlankNode::default(),
+            vec![
+                Triple::new(y.clone(), has_brother.clone(), z.clone()),
+                Triple::new(x.clone(), has_parent.clone(), y),
+            ],
+        );
+        let cons_formula = Formula::new(
+            BlankNode::default(),
+            vec![Triple::new(x, has_uncle.clone(), z)],
+        );
+
+        let rule = N3Rule::new(ant_formula, cons_formula);
+        assert!(rule.is_property_chain_pattern());
+        assert!(!rule.is_subclass_pattern());
+        assert!(rule.is_owl_compatible());
+        assert_eq!(
+            rule.to_owl_axioms().unwrap(),
+            vec![Axiom::SubPropertyChainOf {
+                property_chain: vec![
+                    ObjectPropertyExpression::ObjectProperty(ObjectProperty::new(has_parent)),
+                    ObjectPropertyExpression::ObjectProperty(ObjectProperty::new(has_brother)),
+                ],
+                super_property: ObjectProperty::new(has_uncle),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_property_implication_pattern() {
+        let has_mother = NamedNode::new("http://example.org/hasMother").unwrap();
+        let has_parent = NamedNode::new("http://example.org/hasParent").unwrap();
+        let x = BlankNode::new("x").unwrap();
+        let y = BlankNode::new("y").unwrap();
+
+        let rule = N3Rule::new(
+            Formula::new(
+                BlankNode::default(),
+                vec![Triple::new(x.clone(), has_mother.clone(), y.clone())],
+            ),
+            Formula::new(
+                BlankNode::default(),
+                vec![Triple::new(x, has_parent.clone(), y)],
+            ),
+        );
+        assert!(!rule.is_property_chain_pattern());
+        assert_eq!(
+            rule.to_owl_axioms().unwrap(),
+            vec![Axiom::SubObjectPropertyOf {
+                sub_property: ObjectPropertyExpression::ObjectProperty(ObjectProperty::new(
+                    has_mother
+                )),
+                super_property: ObjectPropertyExpression::ObjectProperty(ObjectProperty::new(
+                    has_parent
+                )),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_non_chain_bodies_are_rejected() {
+        let prop_p = NamedNode::new("http://example.org/p").unwrap();
+        let prop_q = NamedNode::new("http://example.org/q").unwrap();
+        let prop_r = NamedNode::new("http://example.org/r").unwrap();
+        let var_x = BlankNode::new("x").unwrap();
+        let var_y = BlankNode::new("y").unwrap();
+        let var_z = BlankNode::new("z").unwrap();
+        let consequent = Formula::new(
+            BlankNode::default(),
+            vec![Triple::new(var_x.clone(), prop_r, var_z.clone())],
+        );
+
+        // Branching: { ?x :p ?y . ?x :q ?z } => { ?x :r ?z }
+        let branching = N3Rule::new(
+            Formula::new(
+                BlankNode::default(),
+                vec![
+                    Triple::new(var_x.clone(), prop_p.clone(), var_y.clone()),
+                    Triple::new(var_x.clone(), prop_q.clone(), var_z.clone()),
+                ],
+            ),
+            consequent.clone(),
+        );
+        // Disconnected: { ?x :p ?z . ?y :q ?y } => { ?x :r ?z }
+        let disconnected = N3Rule::new(
+            Formula::new(
+                BlankNode::default(),
+                vec![
+                    Triple::new(var_x, prop_p, var_z),
+                    Triple::new(var_y.clone(), prop_q, var_y),
+                ],
+            ),
+            consequent,
+        );
+
+        for rule in [branching, disconnected] {
+            assert!(!rule.is_property_chain_pattern());
+            assert!(!rule.is_owl_compatible());
+            let error = rule.to_owl_axioms().unwrap_err();
+            assert_eq!(error.kind(), ParseErrorKind::UnknownConstruct);
+        }
+    }
+
     #[test]
     fn test_rule_extractor_empty() {
         let extractor = N3RuleExtractor::new(vec![]);
@@ -294,7 +545,7 @@ mod tests {
         let mut ontology = Ontology::new(None);
         let quads = vec![];
 
-        let count = extend_ontology_with_n3_rules(&mut ontology, &quads);
+        let count = extend_ontology_with_n3_rules(&mut ontology, &quads).unwrap();
         assert_eq!(count, 0); // No rules to extract from empty quads
     }
 
@@ -307,27 +558,16 @@ mod tests {
         let nn3 = NamedNode::new("http://example.org/other").unwrap();
 
         assert!(subjects_match(
-            &Subject::BlankNode(bn1.clone()),
-            &Subject::BlankNode(bn2)
+            &NamedOrBlankNode::BlankNode(bn1.clone()),
+            &NamedOrBlankNode::BlankNode(bn2)
         ));
         assert!(subjects_match(
-            &Subject::NamedNode(nn1.clone()),
-            &Subject::NamedNode(nn2)
+            &NamedOrBlankNode::NamedNode(nn1.clone()),
+            &NamedOrBlankNode::NamedNode(nn2)
         ));
         assert!(!subjects_match(
-            &Subject::NamedNode(nn1),
-            &Subject::NamedNode(nn3)
+            &NamedOrBlankNode::NamedNode(nn1),
+            &NamedOrBlankNode::NamedNode(nn3)
         ));
     }
-
-    #[test]
-    fn test_objects_match() {
-        let bn1 = BlankNode::new("x").unwrap();
-        let bn2 = BlankNode::new("y").unwrap();
-        let nn1 = NamedNode::new("http://example.org/test").unwrap();
-        let nn2 = NamedNode::new("http://example.org/test").unwrap();
-
-        assert!(objects_match(&Term::BlankNode(bn1), &Term::BlankNode(bn2)));
-        assert!(objects_match(&Term::NamedNode(nn1.clone()), &Term::NamedNode(nn2)));
-    }
 }
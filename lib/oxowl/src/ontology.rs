@@ -3,6 +3,7 @@
 use crate::axiom::Axiom;
 use crate::entity::{OwlClass, ObjectProperty, DataProperty, AnnotationProperty, Individual};
 use crate::expression::ClassExpression;
+use crate::profile::{self, ProfileViolation};
 use oxrdf::NamedNode;
 use rustc_hash::FxHashSet;
 
@@ -21,6 +22,9 @@ pub struct Ontology {
     /// Imported ontology IRIs
     imports: Vec<NamedNode>,
 
+    /// Ontologies whose axioms have been merged by [`Ontology::add_imports`]
+    loaded_imports: FxHashSet<NamedNode>,
+
     /// All axioms in the ontology
     axioms: Vec<Axiom>,
 
@@ -47,6 +51,7 @@ impl Ontology {
             iri,
             version_iri: None,
             imports: Vec::new(),
+            loaded_imports: FxHashSet::default(),
             axioms: Vec::new(),
             classes: FxHashSet::default(),
             object_properties: FxHashSet::default(),
@@ -93,6 +98,72 @@ impl Ontology {
         }
     }
 
+    /// Imports an ontology and, recursively, the ontologies it imports.
+    ///
+    /// The `resolver` maps an import IRI to the ontology it identifies, e.g. by reading a file,
+    /// fetching the IRI or querying a store. The axioms of every resolved ontology are merged
+    /// into this one so that reasoning sees the full import closure.
+    /// Each ontology is loaded at most once, even across calls, so import cycles terminate.
+    ///
+    /// Returns the IRIs the resolver could not resolve.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, Ontology, OwlClass};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let animals = NamedNode::new("http://example.org/animals")?;
+    /// let pets = NamedNode::new("http://example.org/pets")?;
+    /// let resolver = |iri: &NamedNode| {
+    ///     let mut ontology = Ontology::new(Some(iri.clone()));
+    ///     if *iri == pets {
+    ///         // pets imports animals back
+    ///         ontology.add_import(animals.clone());
+    ///         ontology.add_axiom(Axiom::subclass_of(
+    ///             ClassExpression::class(OwlClass::new(NamedNode::new("http://example.org/Dog").ok()?)),
+    ///             ClassExpression::class(OwlClass::new(NamedNode::new("http://example.org/Animal").ok()?)),
+    ///         ));
+    ///     } else if *iri != animals {
+    ///         return None;
+    ///     }
+    ///     Some(ontology)
+    /// };
+    ///
+    /// let mut ontology = Ontology::new(Some(animals.clone()));
+    /// let missing = NamedNode::new("http://example.org/missing")?;
+    /// assert!(ontology.add_imports(&pets, resolver).is_empty());
+    /// assert_eq!(ontology.axiom_count(), 1);
+    /// assert_eq!(ontology.add_imports(&missing, resolver), [missing]);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn add_imports(
+        &mut self,
+        iri: &NamedNode,
+        resolver: impl Fn(&NamedNode) -> Option<Ontology>,
+    ) -> Vec<NamedNode> {
+        self.add_import(iri.clone());
+        if let Some(own_iri) = &self.iri {
+            // The ontology does not import itself
+            self.loaded_imports.insert(own_iri.clone());
+        }
+        let mut unresolved = Vec::new();
+        let mut to_load = vec![iri.clone()];
+        while let Some(iri) = to_load.pop() {
+            if !self.loaded_imports.insert(iri.clone()) {
+                continue;
+            }
+            let Some(imported) = resolver(&iri) else {
+                self.loaded_imports.remove(&iri);
+                unresolved.push(iri);
+                continue;
+            };
+            to_load.extend(imported.imports.iter().cloned());
+            for axiom in imported.axioms {
+                self.add_axiom(axiom);
+            }
+        }
+        unresolved
+    }
+
     /// Adds an axiom to the ontology.
     pub fn add_axiom(&mut self, axiom: Axiom) {
         // Update declaration indexes
@@ -154,6 +225,38 @@ impl Ontology {
         self.axioms.len()
     }
 
+    /// Checks the ontology against the OWL 2 RL profile.
+    ///
+    /// Returns one [`ProfileViolation`] per axiom using constructs outside of OWL 2 RL,
+    /// e.g. a universal restriction as subclass or an existential restriction as superclass.
+    /// The RL reasoner ignores such constructs, so its results are incomplete when this is not empty.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, ObjectProperty, Ontology, OwlClass};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let animal = OwlClass::new(NamedNode::new("http://example.org/Animal")?);
+    /// let plant = OwlClass::new(NamedNode::new("http://example.org/Plant")?);
+    /// let has_part = ObjectProperty::new(NamedNode::new("http://example.org/hasPart")?);
+    ///
+    /// let mut ontology = Ontology::new(None);
+    /// ontology.add_axiom(Axiom::subclass_of(
+    ///     ClassExpression::class(plant),
+    ///     ClassExpression::some_values_from(has_part, ClassExpression::class(animal)),
+    /// ));
+    ///
+    /// let violations = ontology.validate_profile();
+    /// assert_eq!(violations.len(), 1);
+    /// assert_eq!(
+    ///     violations[0].reason(),
+    ///     "ObjectSomeValuesFrom is not allowed in superclass position"
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn validate_profile(&self) -> Vec<ProfileViolation> {
+        profile::rl_violations(self)
+    }
+
     /// Returns all declared classes.
     pub fn classes(&self) -> impl Iterator<Item = &OwlClass> {
         self.classes.iter()
@@ -246,6 +349,7 @@ impl Ontology {
         self.data_properties.clear();
         self.annotation_properties.clear();
         self.individuals.clear();
+        self.loaded_imports.clear();
     }
 
     /// Merges another ontology into this one.
@@ -0,0 +1,196 @@
+//! OWL 2 RL profile validation.
+//!
+//! Checks axioms against the [OWL 2 RL syntactic restrictions](https://www.w3.org/TR/owl2-profiles/#OWL_2_RL)
+//! so that callers can detect constructs the rule-based reasoner silently ignores.
+
+use crate::axiom::Axiom;
+use crate::expression::{ClassExpression, DataRange};
+use crate::ontology::Ontology;
+use std::fmt;
+
+/// An axiom that falls outside of the OWL 2 RL profile.
+///
+/// Returned by [`Ontology::validate_profile`].
+/// Its [`Display`](fmt::Display) implementation only prints the reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileViolation {
+    axiom: Axiom,
+    reason: String,
+}
+
+impl ProfileViolation {
+    /// Returns the offending axiom.
+    pub fn axiom(&self) -> &Axiom {
+        &self.axiom
+    }
+
+    /// Returns a human-readable explanation of why the axiom is not in OWL 2 RL.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for ProfileViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.reason.fmt(f)
+    }
+}
+
+/// The syntactic position of a class expression inside of an axiom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// `subClassExpression` in the OWL 2 RL grammar
+    Sub,
+    /// `superClassExpression` in the OWL 2 RL grammar
+    Super,
+    /// `equivClassExpression` in the OWL 2 RL grammar
+    Equiv,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sub => write!(f, "subclass"),
+            Self::Super => write!(f, "superclass"),
+            Self::Equiv => write!(f, "equivalent class"),
+        }
+    }
+}
+
+/// Returns the axioms of the ontology that are not allowed in OWL 2 RL.
+pub(crate) fn rl_violations(ontology: &Ontology) -> Vec<ProfileViolation> {
+    ontology.axioms().iter().filter_map(rl_violation).collect()
+}
+
+/// Returns why the axiom is not allowed in OWL 2 RL, if it is not.
+pub(crate) fn rl_violation(axiom: &Axiom) -> Option<ProfileViolation> {
+    check_axiom(axiom).err().map(|reason| ProfileViolation {
+        axiom: axiom.clone(),
+        reason,
+    })
+}
+
+fn check_axiom(axiom: &Axiom) -> Result<(), String> {
+    match axiom {
+        Axiom::SubClassOf {
+            sub_class,
+            super_class,
+        } => {
+            check_class_expression(sub_class, Position::Sub)?;
+            check_class_expression(super_class, Position::Super)
+        }
+        Axiom::EquivalentClasses(classes) => classes
+            .iter()
+            .try_for_each(|c| check_class_expression(c, Position::Equiv)),
+        Axiom::DisjointClasses(classes) => classes
+            .iter()
+            .try_for_each(|c| check_class_expression(c, Position::Sub)),
+        Axiom::DisjointUnion { .. } => Err("DisjointUnion is not allowed in OWL 2 RL".into()),
+        Axiom::ReflexiveObjectProperty(_) => {
+            Err("ReflexiveObjectProperty is not allowed in OWL 2 RL".into())
+        }
+        Axiom::ObjectPropertyDomain { domain, .. } | Axiom::DataPropertyDomain { domain, .. } => {
+            check_class_expression(domain, Position::Super)
+        }
+        Axiom::ObjectPropertyRange { range, .. } => check_class_expression(range, Position::Super),
+        Axiom::DataPropertyRange { range, .. } => check_data_range(range),
+        Axiom::ClassAssertion { class, .. } => check_class_expression(class, Position::Super),
+        Axiom::HasKey { class, .. } => check_class_expression(class, Position::Sub),
+        _ => Ok(()),
+    }
+}
+
+fn check_class_expression(expression: &ClassExpression, position: Position) -> Result<(), String> {
+    match (expression, position) {
+        (
+            ClassExpression::Class(_)
+            | ClassExpression::ObjectHasValue { .. }
+            | ClassExpression::DataHasValue { .. },
+            _,
+        )
+        | (ClassExpression::ObjectOneOf(_), Position::Sub) => Ok(()),
+        (ClassExpression::ObjectIntersectionOf(operands), _)
+        | (ClassExpression::ObjectUnionOf(operands), Position::Sub) => operands
+            .iter()
+            .try_for_each(|c| check_class_expression(c, position)),
+        (ClassExpression::ObjectSomeValuesFrom { filler, .. }, Position::Sub) => {
+            check_class_expression(filler, Position::Sub)
+        }
+        (ClassExpression::DataSomeValuesFrom { filler, .. }, Position::Sub)
+        | (ClassExpression::DataAllValuesFrom { filler, .. }, Position::Super) => {
+            check_data_range(filler)
+        }
+        (ClassExpression::ObjectComplementOf(operand), Position::Super) => {
+            check_class_expression(operand, Position::Sub)
+        }
+        (ClassExpression::ObjectAllValuesFrom { filler, .. }, Position::Super) => {
+            check_class_expression(filler, Position::Super)
+        }
+        (
+            ClassExpression::ObjectMaxCardinality {
+                cardinality: 0 | 1,
+                filler,
+                ..
+            },
+            Position::Super,
+        ) => filler
+            .as_deref()
+            .map_or(Ok(()), |c| check_class_expression(c, Position::Sub)),
+        (
+            ClassExpression::DataMaxCardinality {
+                cardinality: 0 | 1,
+                filler,
+                ..
+            },
+            Position::Super,
+        ) => filler.as_ref().map_or(Ok(()), check_data_range),
+        (
+            ClassExpression::ObjectMaxCardinality { .. }
+            | ClassExpression::DataMaxCardinality { .. },
+            Position::Super,
+        ) => Err(format!(
+            "{} is only allowed with a cardinality of 0 or 1",
+            class_expression_name(expression)
+        )),
+        _ => Err(format!(
+            "{} is not allowed in {position} position",
+            class_expression_name(expression)
+        )),
+    }
+}
+
+fn check_data_range(range: &DataRange) -> Result<(), String> {
+    match range {
+        DataRange::Datatype(_) => Ok(()),
+        DataRange::DataIntersectionOf(operands) => operands.iter().try_for_each(check_data_range),
+        DataRange::DataUnionOf(_) => Err("DataUnionOf is not allowed in OWL 2 RL".into()),
+        DataRange::DataComplementOf(_) => Err("DataComplementOf is not allowed in OWL 2 RL".into()),
+        DataRange::DataOneOf(_) => Err("DataOneOf is not allowed in OWL 2 RL".into()),
+        DataRange::DatatypeRestriction { .. } => {
+            Err("DatatypeRestriction is not allowed in OWL 2 RL".into())
+        }
+    }
+}
+
+fn class_expression_name(expression: &ClassExpression) -> &'static str {
+    match expression {
+        ClassExpression::Class(_) => "Class",
+        ClassExpression::ObjectIntersectionOf(_) => "ObjectIntersectionOf",
+        ClassExpression::ObjectUnionOf(_) => "ObjectUnionOf",
+        ClassExpression::ObjectComplementOf(_) => "ObjectComplementOf",
+        ClassExpression::ObjectOneOf(_) => "ObjectOneOf",
+        ClassExpression::ObjectSomeValuesFrom { .. } => "ObjectSomeValuesFrom",
+        ClassExpression::ObjectAllValuesFrom { .. } => "ObjectAllValuesFrom",
+        ClassExpression::ObjectHasValue { .. } => "ObjectHasValue",
+        ClassExpression::ObjectHasSelf(_) => "ObjectHasSelf",
+        ClassExpression::ObjectMinCardinality { .. } => "ObjectMinCardinality",
+        ClassExpression::ObjectMaxCardinality { .. } => "ObjectMaxCardinality",
+        ClassExpression::ObjectExactCardinality { .. } => "ObjectExactCardinality",
+        ClassExpression::DataSomeValuesFrom { .. } => "DataSomeValuesFrom",
+        ClassExpression::DataAllValuesFrom { .. } => "DataAllValuesFrom",
+        ClassExpression::DataHasValue { .. } => "DataHasValue",
+        ClassExpression::DataMinCardinality { .. } => "DataMinCardinality",
+        ClassExpression::DataMaxCardinality { .. } => "DataMaxCardinality",
+        ClassExpression::DataExactCardinality { .. } => "DataExactCardinality",
+    }
+}
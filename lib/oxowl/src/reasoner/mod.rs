@@ -3,29 +3,108 @@
 //! This module implements forward-chaining reasoning based on the OWL 2 RL profile.
 //! OWL 2 RL is a polynomial-time decidable profile suitable for rule-based reasoning.
 
+mod provenance;
 mod rules;
+mod stats;
+
+pub use provenance::Justification;
+pub use rules::RlRule;
+pub use stats::ReasonerStats;
 
 use crate::axiom::Axiom;
 use crate::entity::{Individual, ObjectProperty, OwlClass};
-use crate::error::{InconsistencyError, OwlError};
-use crate::expression::ClassExpression;
+use crate::error::{InconsistencyError, OwlError, ReasoningError, ReasoningErrorKind};
+use crate::expression::{ClassExpression, ObjectPropertyExpression};
 use crate::ontology::Ontology;
+use crate::profile::rl_violation;
+use oxrdf::vocab::owl;
+use provenance::Provenance;
 use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+/// Largest accepted value of [`ReasonerConfig::max_iterations`].
+const MAX_ITERATIONS_LIMIT: usize = 100_000_000;
+
+/// A maximum cardinality restriction supported by the OWL 2 RL rules:
+/// `ObjectMaxCardinality(0|1 P)` or `ObjectMaxCardinality(0|1 P C)` with a named class `C`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MaxCardinality {
+    cardinality: u32,
+    property: ObjectProperty,
+    filler: Option<OwlClass>,
+}
+
+impl MaxCardinality {
+    fn from_expression(expression: &ClassExpression) -> Option<Self> {
+        let ClassExpression::ObjectMaxCardinality {
+            cardinality: cardinality @ (0 | 1),
+            property: ObjectPropertyExpression::ObjectProperty(property),
+            filler,
+        } = expression
+        else {
+            return None;
+        };
+        let filler = match filler.as_deref() {
+            None => None,
+            Some(ClassExpression::Class(filler)) => Some(filler.clone()),
+            Some(_) => return None,
+        };
+        Some(Self {
+            cardinality: *cardinality,
+            property: property.clone(),
+            filler,
+        })
+    }
+
+    fn to_expression(&self) -> ClassExpression {
+        ClassExpression::ObjectMaxCardinality {
+            cardinality: self.cardinality,
+            property: ObjectPropertyExpression::ObjectProperty(self.property.clone()),
+            filler: self
+                .filler
+                .as_ref()
+                .map(|filler| Box::new(ClassExpression::Class(filler.clone()))),
+        }
+    }
+}
+
 /// Configuration for the reasoner.
 #[derive(Debug, Clone)]
 pub struct ReasonerConfig {
     /// Maximum number of iterations for fixpoint computation.
+    ///
+    /// The budget is shared by all rule phases of a single classification run, in order:
+    /// class hierarchy, property hierarchy, type propagation and property rules.
+    /// A phase that starts once the budget is spent is skipped
+    /// and [`Reasoner::classify`] fails with [`ReasoningErrorKind::MaxIterationsExceeded`].
+    /// Must be between 1 and 100,000,000, see [`ReasonerConfig::validate`].
     pub max_iterations: usize,
     /// Maximum time allowed for reasoning (None = unlimited).
     pub timeout: Option<Duration>,
     /// Maximum number of inferred triples to materialize (None = unlimited).
     pub max_inferred_triples: Option<usize>,
+    /// Maximum number of axioms the rules may derive (None = unlimited).
+    ///
+    /// Unlike `max_inferred_triples`, which is checked once reasoning is done,
+    /// this limit is enforced while rules are applied so that dense ontologies
+    /// are stopped before their closure is built in memory.
+    pub max_inferred_axioms: Option<usize>,
     /// Whether to check for inconsistencies.
     pub check_consistency: bool,
     /// Whether to materialize inferred axioms.
     pub materialize: bool,
+    /// Whether to record which premises produced each inferred axiom.
+    ///
+    /// Required by [`RlReasoner::explain_entailment`]. Disabled by default because
+    /// it keeps a justification for every inferred axiom in memory.
+    pub track_provenance: bool,
+    /// Whether [`RlReasoner::classify`] should fail on axioms outside of OWL 2 RL.
+    ///
+    /// When disabled, such axioms are silently ignored and the results may be incomplete.
+    /// See [`Ontology::validate_profile`].
+    pub reject_non_rl: bool,
 }
 
 impl Default for ReasonerConfig {
@@ -34,12 +113,57 @@ impl Default for ReasonerConfig {
             max_iterations: 100_000,
             timeout: None,
             max_inferred_triples: None,
+            max_inferred_axioms: None,
             check_consistency: true,
             materialize: true,
+            track_provenance: false,
+            reject_non_rl: false,
         }
     }
 }
 
+impl ReasonerConfig {
+    /// Checks that the configuration is usable.
+    ///
+    /// Rejects a `max_iterations` of 0, which would make [`Reasoner::classify`] succeed
+    /// without deriving anything, and values above 100,000,000, which effectively disable the limit.
+    ///
+    /// Called by [`RlReasoner::classify`] before reasoning starts.
+    ///
+    /// ```
+    /// use oxowl::{ReasonerConfig, ReasoningErrorKind};
+    ///
+    /// assert!(ReasonerConfig::default().validate().is_ok());
+    ///
+    /// let config = ReasonerConfig {
+    ///     max_iterations: 0,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     config.validate().unwrap_err().kind(),
+    ///     ReasoningErrorKind::InvalidConfiguration
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ReasoningError> {
+        if self.max_iterations == 0 {
+            return Err(ReasoningError::new(
+                ReasoningErrorKind::InvalidConfiguration,
+                "max_iterations must be at least 1, reasoning requires at least one fixpoint iteration",
+            ));
+        }
+        if self.max_iterations > MAX_ITERATIONS_LIMIT {
+            return Err(ReasoningError::new(
+                ReasoningErrorKind::InvalidConfiguration,
+                format!(
+                    "max_iterations must be at most {MAX_ITERATIONS_LIMIT}, got {}",
+                    self.max_iterations
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Trait for OWL reasoners.
 pub trait Reasoner {
     /// Classifies the ontology (computes all subsumption relationships).
@@ -100,24 +224,60 @@ pub struct RlReasoner<'a> {
     /// Different-from pairs
     different_from: FxHashSet<(Individual, Individual)>,
 
+    /// Disjoint class pairs
+    disjoint_classes: FxHashSet<(OwlClass, OwlClass)>,
+
     /// Symmetric properties
     symmetric_properties: FxHashSet<ObjectProperty>,
 
     /// Transitive properties
     transitive_properties: FxHashSet<ObjectProperty>,
 
-    /// Inverse property mappings: property -> inverse property
-    inverse_properties: FxHashMap<ObjectProperty, ObjectProperty>,
+    /// Irreflexive properties
+    irreflexive_properties: FxHashSet<ObjectProperty>,
+
+    /// Inverse property mappings: property -> (inverse property, rule deriving its assertions)
+    inverse_properties: FxHashMap<ObjectProperty, (ObjectProperty, RlRule)>,
+
+    /// Property chains: (chain of properties, super property)
+    property_chains: FxHashSet<(Vec<ObjectProperty>, ObjectProperty)>,
+
+    /// Number of values of the properties of each property chain when its paths were last followed
+    property_chain_value_counts: FxHashMap<(Vec<ObjectProperty>, ObjectProperty), usize>,
+
+    /// Maximum cardinality restrictions that are superclasses of a class
+    max_cardinalities: FxHashMap<OwlClass, FxHashSet<MaxCardinality>>,
+
+    /// Maximum cardinality restrictions asserted on an individual
+    individual_max_cardinalities: FxHashMap<Individual, FxHashSet<MaxCardinality>>,
 
     /// Inferred axioms
     inferred_axioms: Vec<Axiom>,
 
+    /// Axioms asserted with `add_axioms` on top of the ontology
+    added_axioms: Vec<Axiom>,
+
+    /// Justifications of inferred axioms (only if provenance tracking is enabled)
+    provenance: Option<Provenance>,
+
     /// Whether classification has been performed
     classified: bool,
 
     /// Whether inconsistency was detected
     inconsistent: Option<InconsistencyError>,
 
+    /// Entailed axioms that clash, set when an inconsistency is detected
+    clash: Vec<Axiom>,
+
+    /// Number of fixpoint iterations performed by the current run, shared by all rule phases
+    iterations: usize,
+
+    /// Number of axioms derived by the rules so far, per rule
+    stats: ReasonerStats,
+
+    /// Whether every fixpoint loop converged before hitting `max_iterations`
+    complete: bool,
+
     /// Start time for reasoning (used for timeout enforcement)
     start_time: Option<Instant>,
 }
@@ -130,6 +290,7 @@ impl<'a> RlReasoner<'a> {
 
     /// Creates a new RL reasoner with custom configuration.
     pub fn with_config(ontology: &'a Ontology, config: ReasonerConfig) -> Self {
+        let provenance = config.track_provenance.then(Provenance::default);
         Self {
             ontology,
             config,
@@ -141,12 +302,24 @@ impl<'a> RlReasoner<'a> {
             property_values: FxHashMap::default(),
             same_as: FxHashMap::default(),
             different_from: FxHashSet::default(),
+            disjoint_classes: FxHashSet::default(),
             symmetric_properties: FxHashSet::default(),
             transitive_properties: FxHashSet::default(),
+            irreflexive_properties: FxHashSet::default(),
             inverse_properties: FxHashMap::default(),
+            property_chains: FxHashSet::default(),
+            property_chain_value_counts: FxHashMap::default(),
+            max_cardinalities: FxHashMap::default(),
+            individual_max_cardinalities: FxHashMap::default(),
             inferred_axioms: Vec::new(),
+            added_axioms: Vec::new(),
+            provenance,
             classified: false,
             inconsistent: None,
+            clash: Vec::new(),
+            iterations: 0,
+            stats: ReasonerStats::default(),
+            complete: true,
             start_time: None,
         }
     }
@@ -177,10 +350,519 @@ impl<'a> RlReasoner<'a> {
         Ok(())
     }
 
-    /// Initializes the reasoner state from ontology axioms.
-    fn initialize(&mut self) {
+    /// Returns `true` once the rules derived more axioms than `max_inferred_axioms`.
+    fn inferred_axiom_limit_reached(&self) -> bool {
+        self.config
+            .max_inferred_axioms
+            .is_some_and(|limit| self.stats.inferred_axioms() > limit)
+    }
+
+    /// Checks if the inferred axiom limit has been exceeded.
+    fn check_inferred_axiom_limit(&self) -> Result<(), OwlError> {
+        match self.config.max_inferred_axioms {
+            Some(limit) if self.stats.inferred_axioms() > limit => {
+                Err(ReasoningError::axiom_limit_exceeded(limit).into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records whether a fixpoint loop stopped because nothing changed anymore
+    /// or because it ran out of iterations.
+    fn record_convergence(&mut self, changed: bool) {
+        if changed {
+            self.complete = false;
+        }
+    }
+
+    /// Returns an error if a fixpoint loop was cut short by `max_iterations`
+    /// or skipped because an earlier phase spent the whole budget.
+    fn check_completeness(&self) -> Result<(), OwlError> {
+        if self.complete {
+            Ok(())
+        } else {
+            Err(ReasoningError::max_iterations_exceeded(self.config.max_iterations).into())
+        }
+    }
+
+    /// Returns `true` if reasoning reached a fixpoint.
+    ///
+    /// Returns `false` if [`classify`](Reasoner::classify) stopped early because
+    /// `max_iterations` was reached or an inconsistency was detected while new axioms
+    /// were still being derived.
+    /// In that case the inferred axioms are only a subset of the full closure.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Returns statistics about the work done by [`classify`](Reasoner::classify).
+    ///
+    /// Useful to find ontologies that are expensive to reason over:
+    /// the per-rule counts show which rules dominate the derivations.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, Individual, Ontology, OwlClass, Reasoner, RlReasoner, RlRule};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let animal = OwlClass::new(NamedNode::new("http://example.org/Animal")?);
+    /// let dog = OwlClass::new(NamedNode::new("http://example.org/Dog")?);
+    /// let fido = Individual::Named(NamedNode::new("http://example.org/fido")?);
+    ///
+    /// let mut ontology = Ontology::new(None);
+    /// ontology.add_axiom(Axiom::subclass_of(ClassExpression::class(dog.clone()), ClassExpression::class(animal)));
+    /// ontology.add_axiom(Axiom::class_assertion(ClassExpression::class(dog), fido));
+    ///
+    /// let mut reasoner = RlReasoner::new(&ontology);
+    /// reasoner.classify()?;
+    ///
+    /// let stats = reasoner.stats();
+    /// assert!(stats.fixpoint_reached());
+    /// assert_eq!(stats.rule_applications(RlRule::CaxSco), 1);
+    /// // Dog and Animal are subclasses of owl:Thing and fido is an instance of it
+    /// assert_eq!(stats.rule_applications(RlRule::ScmCls), 2);
+    /// assert_eq!(stats.rule_applications(RlRule::ClsThing), 1);
+    /// assert_eq!(stats.inferred_axioms(), 4);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn stats(&self) -> ReasonerStats {
+        self.stats.clone()
+    }
+
+    /// Returns the individuals asserted or inferred to be the same as the given one.
+    ///
+    /// The individual itself is not included.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, Individual, ObjectProperty, ObjectPropertyExpression, Ontology, OwlClass, Reasoner, RlReasoner};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let person = OwlClass::new(NamedNode::new("http://example.org/Person")?);
+    /// let has_mother = ObjectProperty::new(NamedNode::new("http://example.org/hasMother")?);
+    /// let alice = Individual::Named(NamedNode::new("http://example.org/alice")?);
+    /// let carol = Individual::Named(NamedNode::new("http://example.org/carol")?);
+    /// let mom = Individual::Named(NamedNode::new("http://example.org/mom")?);
+    ///
+    /// // A person has at most one mother
+    /// let mut ontology = Ontology::new(None);
+    /// ontology.add_axiom(Axiom::subclass_of(
+    ///     ClassExpression::class(person.clone()),
+    ///     ClassExpression::ObjectMaxCardinality {
+    ///         cardinality: 1,
+    ///         property: ObjectPropertyExpression::ObjectProperty(has_mother.clone()),
+    ///         filler: None,
+    ///     },
+    /// ));
+    /// ontology.add_axiom(Axiom::class_assertion(ClassExpression::class(person), alice.clone()));
+    /// for mother in [&carol, &mom] {
+    ///     ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+    ///         property: has_mother.clone(),
+    ///         source: alice.clone(),
+    ///         target: mother.clone(),
+    ///     });
+    /// }
+    ///
+    /// let mut reasoner = RlReasoner::new(&ontology);
+    /// reasoner.classify()?;
+    /// assert_eq!(reasoner.get_same_individuals(&carol), [&mom]);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn get_same_individuals(&self, individual: &Individual) -> Vec<&Individual> {
+        self.same_as
+            .get(individual)
+            .map(|same| same.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the equivalence classes of the individuals asserted or inferred to be the same.
+    ///
+    /// Only individuals that are the same as at least another one are returned.
+    /// The output is deterministic: each class is sorted with its
+    /// [canonical individual](Self::canonical_individual) first and the classes are sorted
+    /// by their canonical individuals.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, Individual, Ontology, Reasoner, RlReasoner};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let bob = Individual::Named(NamedNode::new("http://example.org/bob")?);
+    /// let robert = Individual::Named(NamedNode::new("http://example.org/robert")?);
+    /// let bobby = Individual::Named(NamedNode::new("http://example.org/bobby")?);
+    ///
+    /// let mut ontology = Ontology::new(None);
+    /// ontology.add_axiom(Axiom::SameIndividual(vec![robert.clone(), bob.clone()]));
+    /// ontology.add_axiom(Axiom::SameIndividual(vec![bobby.clone(), robert.clone()]));
+    ///
+    /// let mut reasoner = RlReasoner::new(&ontology);
+    /// reasoner.classify()?;
+    /// assert_eq!(reasoner.same_as_classes(), [[bob.clone(), bobby, robert.clone()]]);
+    /// assert_eq!(reasoner.canonical_individual(&robert), &bob);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn same_as_classes(&self) -> Vec<Vec<Individual>> {
+        let mut classes = self
+            .same_as
+            .iter()
+            .filter(|(individual, same)| {
+                same.iter()
+                    .all(|other| compare_individuals(individual, other).is_lt())
+            })
+            .map(|(individual, same)| {
+                let mut class = Vec::with_capacity(same.len() + 1);
+                class.push(individual.clone());
+                class.extend(same.iter().cloned());
+                class.sort_by(compare_individuals);
+                class
+            })
+            .collect::<Vec<_>>();
+        classes.sort_by(|a, b| compare_individuals(&a[0], &b[0]));
+        classes
+    }
+
+    /// Returns the representative of the individuals that are the same as the given one.
+    ///
+    /// The representative is the same for all the individuals of a
+    /// [sameAs equivalence class](Self::same_as_classes) and is stable across runs:
+    /// it is the named individual with the lexicographically smallest IRI,
+    /// or the anonymous individual with the smallest identifier if none is named.
+    /// An individual that is not the same as any other is its own representative.
+    ///
+    /// This allows rewriting the data to use a single individual per equivalence class.
+    pub fn canonical_individual<'b>(&'b self, individual: &'b Individual) -> &'b Individual {
+        self.same_as
+            .get(individual)
+            .into_iter()
+            .flatten()
+            .fold(individual, |canonical, other| {
+                if compare_individuals(other, canonical).is_lt() {
+                    other
+                } else {
+                    canonical
+                }
+            })
+    }
+
+    /// Returns the justifications of an entailed axiom.
+    ///
+    /// For an asserted axiom the justification is the axiom itself,
+    /// for an inferred axiom it is the premises and the rule that derived it.
+    /// Premises may themselves be inferred and can be explained in turn.
+    ///
+    /// Returns `None` if the axiom is not entailed, if the reasoner has not been
+    /// classified yet, or if [`ReasonerConfig::track_provenance`] is disabled.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, Individual, Ontology, OwlClass, Reasoner, ReasonerConfig, RlReasoner, RlRule};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let animal = OwlClass::new(NamedNode::new("http://example.org/Animal")?);
+    /// let dog = OwlClass::new(NamedNode::new("http://example.org/Dog")?);
+    /// let fido = Individual::Named(NamedNode::new("http://example.org/fido")?);
+    ///
+    /// let mut ontology = Ontology::new(None);
+    /// ontology.add_axiom(Axiom::subclass_of(ClassExpression::class(dog.clone()), ClassExpression::class(animal.clone())));
+    /// ontology.add_axiom(Axiom::class_assertion(ClassExpression::class(dog), fido.clone()));
+    ///
+    /// let config = ReasonerConfig {
+    ///     track_provenance: true,
+    ///     ..Default::default()
+    /// };
+    /// let mut reasoner = RlReasoner::with_config(&ontology, config);
+    /// reasoner.classify()?;
+    ///
+    /// let justifications = reasoner
+    ///     .justifications(&Axiom::class_assertion(ClassExpression::class(animal), fido))
+    ///     .unwrap();
+    /// assert_eq!(justifications[0].rule(), Some(RlRule::CaxSco));
+    /// assert_eq!(justifications[0].axioms().len(), 2);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn justifications(&self, axiom: &Axiom) -> Option<Vec<Justification>> {
+        let provenance = self.provenance.as_ref()?;
+        if !self.classified {
+            return None;
+        }
+        if self.ontology.axioms().contains(axiom) || self.added_axioms.contains(axiom) {
+            return Some(vec![Justification::asserted(axiom.clone())]);
+        }
+        provenance.get(axiom).map(<[Justification]>::to_vec)
+    }
+
+    /// Explains why an axiom is entailed by the ontology.
+    ///
+    /// Returns the premises of each of the [`justifications`](Self::justifications) of the axiom.
+    /// Use [`justifications`](Self::justifications) to also get the rule that fired.
+    ///
+    /// Returns `None` if the axiom is not entailed, if the reasoner has not been
+    /// classified yet, or if [`ReasonerConfig::track_provenance`] is disabled.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, Individual, Ontology, OwlClass, Reasoner, ReasonerConfig, RlReasoner};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let animal = OwlClass::new(NamedNode::new("http://example.org/Animal")?);
+    /// let dog = OwlClass::new(NamedNode::new("http://example.org/Dog")?);
+    /// let fido = Individual::Named(NamedNode::new("http://example.org/fido")?);
+    ///
+    /// let dog_animal = Axiom::subclass_of(ClassExpression::class(dog.clone()), ClassExpression::class(animal.clone()));
+    /// let fido_dog = Axiom::class_assertion(ClassExpression::class(dog), fido.clone());
+    /// let mut ontology = Ontology::new(None);
+    /// ontology.add_axiom(dog_animal.clone());
+    /// ontology.add_axiom(fido_dog.clone());
+    ///
+    /// let config = ReasonerConfig {
+    ///     track_provenance: true,
+    ///     ..Default::default()
+    /// };
+    /// let mut reasoner = RlReasoner::with_config(&ontology, config);
+    /// reasoner.classify()?;
+    ///
+    /// let explanations = reasoner
+    ///     .explain_entailment(&Axiom::class_assertion(ClassExpression::class(animal), fido))
+    ///     .unwrap();
+    /// assert_eq!(explanations, [vec![fido_dog, dog_animal]]);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn explain_entailment(&self, axiom: &Axiom) -> Option<Vec<Vec<Axiom>>> {
+        Some(
+            self.justifications(axiom)?
+                .into_iter()
+                .map(Justification::into_axioms)
+                .collect(),
+        )
+    }
+
+    /// Explains why the ontology is inconsistent.
+    ///
+    /// Returns the asserted axioms the detected clash follows from: the premises of the clash
+    /// are traced back through their justifications, as returned by
+    /// [`justifications`](Self::justifications), down to the asserted axioms.
+    /// Premises whose derivation is not tracked, e.g. sameAs assertions derived from
+    /// cardinality restrictions, are returned as is.
+    ///
+    /// Returns `None` if no inconsistency has been detected
+    /// or if [`ReasonerConfig::track_provenance`] is disabled.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, Individual, Ontology, OwlClass, Reasoner, ReasonerConfig, RlReasoner};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let dog = OwlClass::new(NamedNode::new("http://example.org/Dog")?);
+    /// let cat = OwlClass::new(NamedNode::new("http://example.org/Cat")?);
+    /// let poodle = OwlClass::new(NamedNode::new("http://example.org/Poodle")?);
+    /// let fido = Individual::Named(NamedNode::new("http://example.org/fido")?);
+    ///
+    /// let mut ontology = Ontology::new(None);
+    /// let disjoint = Axiom::DisjointClasses(vec![ClassExpression::class(dog.clone()), ClassExpression::class(cat.clone())]);
+    /// let poodle_dog = Axiom::subclass_of(ClassExpression::class(poodle.clone()), ClassExpression::class(dog));
+    /// let fido_poodle = Axiom::class_assertion(ClassExpression::class(poodle), fido.clone());
+    /// let fido_cat = Axiom::class_assertion(ClassExpression::class(cat), fido);
+    /// for axiom in [&disjoint, &poodle_dog, &fido_poodle, &fido_cat] {
+    ///     ontology.add_axiom(axiom.clone());
+    /// }
+    ///
+    /// let config = ReasonerConfig {
+    ///     track_provenance: true,
+    ///     ..Default::default()
+    /// };
+    /// let mut reasoner = RlReasoner::with_config(&ontology, config);
+    /// assert!(reasoner.classify().is_err());
+    ///
+    /// let explanation = reasoner.inconsistency_explanation().unwrap();
+    /// assert_eq!(explanation.len(), 4);
+    /// assert!([disjoint, poodle_dog, fido_poodle, fido_cat].iter().all(|axiom| explanation.contains(axiom)));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn inconsistency_explanation(&self) -> Option<Vec<Axiom>> {
+        let provenance = self.provenance.as_ref()?;
+        self.inconsistent.as_ref()?;
+        let mut explanation = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut to_explain = self.clash.iter().cloned().collect::<VecDeque<_>>();
+        while let Some(axiom) = to_explain.pop_front() {
+            if !visited.insert(axiom.clone()) {
+                continue;
+            }
+            if let Some(asserted) = self.asserted_axiom(&axiom) {
+                if !explanation.contains(asserted) {
+                    explanation.push(asserted.clone());
+                }
+                continue;
+            }
+            // The smallest justification keeps the explanation small
+            let justification = provenance
+                .get(&axiom)
+                .and_then(|justifications| justifications.iter().min_by_key(|j| j.axioms().len()));
+            if let Some(justification) = justification {
+                to_explain.extend(justification.axioms().iter().cloned());
+            } else {
+                explanation.push(axiom);
+            }
+        }
+        Some(explanation)
+    }
+
+    /// Returns the asserted axiom an entailed axiom comes from, if any.
+    ///
+    /// N-ary axioms are read pairwise, so `DisjointClasses(A, B)` comes from an asserted
+    /// `DisjointClasses(A, B, C)`.
+    fn asserted_axiom(&self, axiom: &Axiom) -> Option<&Axiom> {
+        let mut asserted = self.ontology.axioms().iter().chain(&self.added_axioms);
+        if let Some(asserted) = asserted.clone().find(|asserted| *asserted == axiom) {
+            return Some(asserted);
+        }
+        asserted.find(|asserted| match (axiom, asserted) {
+            (Axiom::DisjointClasses(some), Axiom::DisjointClasses(all)) => {
+                some.iter().all(|class| all.contains(class))
+            }
+            (Axiom::SameIndividual(some), Axiom::SameIndividual(all))
+            | (Axiom::DifferentIndividuals(some), Axiom::DifferentIndividuals(all)) => {
+                some.iter().all(|individual| all.contains(individual))
+            }
+            _ => false,
+        })
+    }
+
+    /// Checks that all the expected axioms are entailed by the ontology.
+    ///
+    /// An axiom is entailed if it is asserted or has been inferred by [`classify`](Reasoner::classify).
+    /// Returns the expected axioms that are not entailed, in the given order.
+    /// This is a concise way to check the completeness of the reasoning in test suites.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, Ontology, OwlClass, Reasoner, RlReasoner};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let animal = OwlClass::new(NamedNode::new("http://example.org/Animal")?);
+    /// let dog = OwlClass::new(NamedNode::new("http://example.org/Dog")?);
+    /// let poodle = OwlClass::new(NamedNode::new("http://example.org/Poodle")?);
+    ///
+    /// let mut ontology = Ontology::new(None);
+    /// ontology.add_axiom(Axiom::subclass_of(ClassExpression::class(poodle.clone()), ClassExpression::class(dog.clone())));
+    /// ontology.add_axiom(Axiom::subclass_of(ClassExpression::class(dog.clone()), ClassExpression::class(animal.clone())));
+    ///
+    /// let mut reasoner = RlReasoner::new(&ontology);
+    /// reasoner.classify()?;
+    ///
+    /// let poodle_is_an_animal = Axiom::subclass_of(ClassExpression::class(poodle), ClassExpression::class(animal.clone()));
+    /// assert_eq!(reasoner.assert_entails(&[poodle_is_an_animal]), Ok(()));
+    /// let animal_is_a_dog = Axiom::subclass_of(ClassExpression::class(animal), ClassExpression::class(dog));
+    /// assert_eq!(reasoner.assert_entails(&[animal_is_a_dog.clone()]), Err(vec![animal_is_a_dog]));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn assert_entails(&self, expected: &[Axiom]) -> Result<(), Vec<Axiom>> {
+        let missing = expected
+            .iter()
+            .filter(|axiom| !self.entails(axiom))
+            .cloned()
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// Checks if an axiom is asserted or inferred.
+    ///
+    /// The closure is looked up directly so that it works even if `materialize` is disabled.
+    fn entails(&self, axiom: &Axiom) -> bool {
+        if self.ontology.axioms().contains(axiom) || self.added_axioms.contains(axiom) {
+            return true;
+        }
+        match axiom {
+            Axiom::SubClassOf {
+                sub_class: ClassExpression::Class(sub),
+                super_class: ClassExpression::Class(sup),
+            } => self
+                .class_hierarchy
+                .get(sub)
+                .is_some_and(|supers| supers.contains(sup)),
+            Axiom::ClassAssertion {
+                class: ClassExpression::Class(class),
+                individual,
+            } => self
+                .individual_types
+                .get(individual)
+                .is_some_and(|types| types.contains(class)),
+            Axiom::ObjectPropertyAssertion {
+                property,
+                source,
+                target,
+            } => self
+                .property_values
+                .get(&(source.clone(), property.clone()))
+                .is_some_and(|targets| targets.contains(target)),
+            _ => self.inferred_axioms.contains(axiom),
+        }
+    }
+
+    /// Adds axioms to the reasoner and derives their consequences.
+    ///
+    /// Before [`classify`](Reasoner::classify) the axioms are only loaded and are
+    /// taken into account by the classification.
+    /// After it, the closure is not updated incrementally: the reasoner state is reset and
+    /// the ontology is classified again together with all the added axioms.
+    /// The result is the same as classifying an ontology containing all these axioms,
+    /// including [`is_complete`](Self::is_complete) and the derivation counts of
+    /// [`stats`](Self::stats).
+    ///
+    /// Each call gets its own `max_iterations` budget and timeout.
+    ///
+    /// ```
+    /// use oxowl::{Axiom, ClassExpression, Individual, Ontology, OwlClass, Reasoner, RlReasoner};
+    /// use oxrdf::NamedNode;
+    ///
+    /// let animal = OwlClass::new(NamedNode::new("http://example.org/Animal")?);
+    /// let dog = OwlClass::new(NamedNode::new("http://example.org/Dog")?);
+    /// let fido = Individual::Named(NamedNode::new("http://example.org/fido")?);
+    ///
+    /// let mut ontology = Ontology::new(None);
+    /// ontology.add_axiom(Axiom::class_assertion(ClassExpression::class(dog.clone()), fido.clone()));
+    ///
+    /// let mut reasoner = RlReasoner::new(&ontology);
+    /// reasoner.classify()?;
+    /// assert!(!reasoner.get_types(&fido).contains(&&animal));
+    ///
+    /// reasoner.add_axioms(&[Axiom::subclass_of(ClassExpression::class(dog), ClassExpression::class(animal.clone()))])?;
+    /// assert!(reasoner.get_types(&fido).contains(&&animal));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn add_axioms(&mut self, axioms: &[Axiom]) -> Result<(), OwlError> {
+        if self.config.reject_non_rl {
+            if let Some(violation) = axioms.iter().find_map(rl_violation) {
+                return Err(ReasoningError::new(
+                    ReasoningErrorKind::UnsupportedConstruct,
+                    format!("axiom outside of the OWL 2 RL profile: {violation}"),
+                )
+                .into());
+            }
+        }
+        self.added_axioms.extend_from_slice(axioms);
+
+        if !self.classified {
+            self.initialize(axioms);
+            return Ok(());
+        }
+
+        let mut stats = std::mem::take(&mut self.stats);
+        stats.clear_derivations();
+        *self = Self {
+            added_axioms: std::mem::take(&mut self.added_axioms),
+            stats,
+            ..Self::with_config(self.ontology, self.config.clone())
+        };
+        let added_axioms = self.added_axioms.clone();
+        self.initialize(&added_axioms);
+        self.classify()
+    }
+
+    /// Initializes the reasoner state from asserted axioms.
+    fn initialize(&mut self, axioms: &[Axiom]) {
         // Initialize class hierarchy from SubClassOf axioms
-        for axiom in self.ontology.axioms() {
+        for axiom in axioms {
+            if self.inferred_axiom_limit_reached() {
+                return;
+            }
             match axiom {
                 Axiom::SubClassOf {
                     sub_class,
@@ -193,6 +875,13 @@ impl<'a> RlReasoner<'a> {
                             .entry(sub.clone())
                             .or_default()
                             .insert(sup.clone());
+                    } else if let (ClassExpression::Class(sub), Some(restriction)) =
+                        (sub_class, MaxCardinality::from_expression(super_class))
+                    {
+                        self.max_cardinalities
+                            .entry(sub.clone())
+                            .or_default()
+                            .insert(restriction);
                     }
                 }
                 Axiom::EquivalentClasses(classes) => {
@@ -205,10 +894,21 @@ impl<'a> RlReasoner<'a> {
                     for i in 0..named_classes.len() {
                         for j in 0..named_classes.len() {
                             if i != j {
-                                self.class_hierarchy
+                                let inserted = self
+                                    .class_hierarchy
                                     .entry(named_classes[i].clone())
                                     .or_default()
                                     .insert(named_classes[j].clone());
+                                if inserted {
+                                    self.stats.record_inference(RlRule::ScmEqc1);
+                                }
+                                if let (true, Some(provenance)) = (inserted, &mut self.provenance) {
+                                    provenance.record(
+                                        sub_class_axiom(&named_classes[i], &named_classes[j]),
+                                        RlRule::ScmEqc1,
+                                        vec![axiom.clone()],
+                                    );
+                                }
                             }
                         }
                     }
@@ -222,6 +922,14 @@ impl<'a> RlReasoner<'a> {
                         .or_default()
                         .insert(c.clone());
                 }
+                Axiom::ClassAssertion { class, individual } => {
+                    if let Some(restriction) = MaxCardinality::from_expression(class) {
+                        self.individual_max_cardinalities
+                            .entry(individual.clone())
+                            .or_default()
+                            .insert(restriction);
+                    }
+                }
                 Axiom::ObjectPropertyAssertion {
                     property,
                     source,
@@ -233,17 +941,31 @@ impl<'a> RlReasoner<'a> {
                         .insert(target.clone());
                 }
                 Axiom::SubObjectPropertyOf {
-                    sub_property,
-                    super_property,
+                    sub_property: ObjectPropertyExpression::ObjectProperty(sub),
+                    super_property: ObjectPropertyExpression::ObjectProperty(sup),
                 } => {
-                    // Handle SubObjectPropertyOf for simple properties
-                    let sub = sub_property.as_property();
-                    let sup = super_property.as_property();
+                    // Only simple properties are handled, inverses would flip the assertions
                     self.property_hierarchy
                         .entry(sub.clone())
                         .or_default()
                         .insert(sup.clone());
                 }
+                Axiom::SubPropertyChainOf {
+                    property_chain,
+                    super_property,
+                } => {
+                    // Chains through inverse properties are not supported
+                    let chain = property_chain
+                        .iter()
+                        .map(|property| match property {
+                            ObjectPropertyExpression::ObjectProperty(p) => Some(p.clone()),
+                            ObjectPropertyExpression::ObjectInverseOf(_) => None,
+                        })
+                        .collect::<Option<Vec<_>>>();
+                    if let Some(chain) = chain {
+                        self.property_chains.insert((chain, super_property.clone()));
+                    }
+                }
                 Axiom::ObjectPropertyDomain {
                     property,
                     domain: ClassExpression::Class(c),
@@ -263,15 +985,9 @@ impl<'a> RlReasoner<'a> {
                         .insert(c.clone());
                 }
                 Axiom::SameIndividual(individuals) => {
-                    for i in 0..individuals.len() {
-                        for j in 0..individuals.len() {
-                            if i != j {
-                                self.same_as
-                                    .entry(individuals[i].clone())
-                                    .or_default()
-                                    .insert(individuals[j].clone());
-                            }
-                        }
+                    // Merged into the existing equivalence classes (eq-sym and eq-trans)
+                    for (a, b) in individuals.iter().zip(individuals.iter().skip(1)) {
+                        self.add_same_as(a.clone(), b.clone());
                     }
                 }
                 Axiom::DifferentIndividuals(individuals) => {
@@ -284,40 +1000,147 @@ impl<'a> RlReasoner<'a> {
                         }
                     }
                 }
+                Axiom::DisjointClasses(classes) => {
+                    let named_classes: Vec<_> =
+                        classes.iter().filter_map(|c| c.as_class()).collect();
+                    for i in 0..named_classes.len() {
+                        for j in (i + 1)..named_classes.len() {
+                            self.disjoint_classes
+                                .insert((named_classes[i].clone(), named_classes[j].clone()));
+                        }
+                    }
+                }
                 Axiom::SymmetricObjectProperty(property) => {
                     self.symmetric_properties.insert(property.clone());
                 }
                 Axiom::TransitiveObjectProperty(property) => {
                     self.transitive_properties.insert(property.clone());
                 }
+                Axiom::IrreflexiveObjectProperty(property) => {
+                    self.irreflexive_properties.insert(property.clone());
+                }
                 Axiom::InverseObjectProperties(p1, p2) => {
                     // Store bidirectional mapping
-                    self.inverse_properties.insert(p1.clone(), p2.clone());
-                    self.inverse_properties.insert(p2.clone(), p1.clone());
+                    self.inverse_properties
+                        .insert(p1.clone(), (p2.clone(), RlRule::PrpInv1));
+                    self.inverse_properties
+                        .insert(p2.clone(), (p1.clone(), RlRule::PrpInv2));
                 }
                 _ => {}
             }
         }
     }
 
+    /// Makes owl:Thing a superclass of every class (scm-cls) and a type of every
+    /// individual (cls-thing).
+    ///
+    /// Runs before the class hierarchy closure so that the types of owl:Thing,
+    /// e.g. from `SubClassOf(owl:Thing C)`, are propagated to all individuals.
+    fn apply_thing_rules(&mut self) {
+        let thing = OwlClass::new(owl::THING.into_owned());
+
+        let mut classes = FxHashSet::default();
+        for (sub, supers) in &self.class_hierarchy {
+            classes.insert(sub.clone());
+            classes.extend(supers.iter().cloned());
+        }
+        for types in self.individual_types.values() {
+            classes.extend(types.iter().cloned());
+        }
+        for domains in self
+            .property_domains
+            .values()
+            .chain(self.property_ranges.values())
+        {
+            classes.extend(domains.iter().cloned());
+        }
+        classes.remove(&thing);
+        for class in classes {
+            if self.inferred_axiom_limit_reached() {
+                return;
+            }
+            if self
+                .class_hierarchy
+                .entry(class.clone())
+                .or_default()
+                .insert(thing.clone())
+            {
+                self.stats.record_inference(RlRule::ScmCls);
+                if let Some(provenance) = &mut self.provenance {
+                    provenance.record(sub_class_axiom(&class, &thing), RlRule::ScmCls, Vec::new());
+                }
+            }
+        }
+
+        let mut individuals = FxHashSet::default();
+        individuals.extend(self.individual_types.keys().cloned());
+        for ((source, _), targets) in &self.property_values {
+            individuals.insert(source.clone());
+            individuals.extend(targets.iter().cloned());
+        }
+        for (individual, same) in &self.same_as {
+            individuals.insert(individual.clone());
+            individuals.extend(same.iter().cloned());
+        }
+        for (a, b) in &self.different_from {
+            individuals.insert(a.clone());
+            individuals.insert(b.clone());
+        }
+        for individual in individuals {
+            if self.inferred_axiom_limit_reached() {
+                return;
+            }
+            if self
+                .individual_types
+                .entry(individual.clone())
+                .or_default()
+                .insert(thing.clone())
+            {
+                self.stats.record_inference(RlRule::ClsThing);
+                if let Some(provenance) = &mut self.provenance {
+                    provenance.record(
+                        class_assertion_axiom(&thing, &individual),
+                        RlRule::ClsThing,
+                        Vec::new(),
+                    );
+                }
+            }
+        }
+    }
+
     /// Computes the transitive closure of the class hierarchy.
     fn compute_transitive_closure(&mut self) {
         let mut changed = true;
-        let mut iterations = 0;
 
-        while changed && iterations < self.config.max_iterations {
+        while changed && self.iterations < self.config.max_iterations {
             changed = false;
-            iterations += 1;
+            self.iterations += 1;
 
             let classes: Vec<_> = self.class_hierarchy.keys().cloned().collect();
 
             for class in classes {
+                if self.inferred_axiom_limit_reached() {
+                    return;
+                }
                 if let Some(supers) = self.class_hierarchy.get(&class).cloned() {
                     for sup in supers {
                         if let Some(transitive_supers) = self.class_hierarchy.get(&sup).cloned() {
                             let entry = self.class_hierarchy.entry(class.clone()).or_default();
                             for trans_sup in transitive_supers {
+                                if let Some(provenance) = &mut self.provenance {
+                                    if !entry.contains(&trans_sup) {
+                                        provenance.record(
+                                            sub_class_axiom(&class, &trans_sup),
+                                            RlRule::ScmSco,
+                                            vec![
+                                                sub_class_axiom(&class, &sup),
+                                                sub_class_axiom(&sup, &trans_sup),
+                                            ],
+                                        );
+                                    }
+                                }
                                 if entry.insert(trans_sup) {
+                                    self.stats.record_inference(RlRule::ScmSco);
                                     changed = true;
                                 }
                             }
@@ -326,6 +1149,7 @@ impl<'a> RlReasoner<'a> {
                 }
             }
         }
+        self.record_convergence(changed);
     }
 
     /// Applies RDFS entailment rules.
@@ -337,21 +1161,20 @@ impl<'a> RlReasoner<'a> {
     fn apply_rdfs_rules(&mut self) {
         // Step 1: Compute transitive closure of property hierarchy (rdfs:subPropertyOf transitivity)
         let mut changed = true;
-        let mut iterations = 0;
 
-        while changed && iterations < self.config.max_iterations {
+        while changed && self.iterations < self.config.max_iterations {
             changed = false;
-            iterations += 1;
+            self.iterations += 1;
 
             let properties: Vec<_> = self.property_hierarchy.keys().cloned().collect();
 
             for property in properties {
                 if let Some(supers) = self.property_hierarchy.get(&property).cloned() {
                     for sup in supers {
-                        if let Some(transitive_supers) =
-                            self.property_hierarchy.get(&sup).cloned()
+                        if let Some(transitive_supers) = self.property_hierarchy.get(&sup).cloned()
                         {
-                            let entry = self.property_hierarchy.entry(property.clone()).or_default();
+                            let entry =
+                                self.property_hierarchy.entry(property.clone()).or_default();
                             for trans_sup in transitive_supers {
                                 if entry.insert(trans_sup) {
                                     changed = true;
@@ -362,15 +1185,15 @@ impl<'a> RlReasoner<'a> {
                 }
             }
         }
+        self.record_convergence(changed);
 
         // Step 2: Propagate property domains to superproperties
         // If P rdfs:subPropertyOf Q and P rdfs:domain C, then Q rdfs:domain C
         changed = true;
-        iterations = 0;
 
-        while changed && iterations < self.config.max_iterations {
+        while changed && self.iterations < self.config.max_iterations {
             changed = false;
-            iterations += 1;
+            self.iterations += 1;
 
             let properties: Vec<_> = self.property_hierarchy.keys().cloned().collect();
 
@@ -389,15 +1212,15 @@ impl<'a> RlReasoner<'a> {
                 }
             }
         }
+        self.record_convergence(changed);
 
         // Step 3: Propagate property ranges to superproperties
         // If P rdfs:subPropertyOf Q and P rdfs:range C, then Q rdfs:range C
         changed = true;
-        iterations = 0;
 
-        while changed && iterations < self.config.max_iterations {
+        while changed && self.iterations < self.config.max_iterations {
             changed = false;
-            iterations += 1;
+            self.iterations += 1;
 
             let properties: Vec<_> = self.property_hierarchy.keys().cloned().collect();
 
@@ -416,14 +1239,34 @@ impl<'a> RlReasoner<'a> {
                 }
             }
         }
+        self.record_convergence(changed);
 
         // Step 4: Apply domain inference
         // If P rdfs:domain C and (x, P, y) exists, then x rdf:type C
-        for (subject, property) in self.property_values.keys() {
+        for ((subject, property), targets) in &self.property_values {
+            if self.inferred_axiom_limit_reached() {
+                return;
+            }
             if let Some(domains) = self.property_domains.get(property) {
                 let entry = self.individual_types.entry(subject.clone()).or_default();
                 for domain in domains {
-                    entry.insert(domain.clone());
+                    if let (Some(provenance), Some(target)) =
+                        (&mut self.provenance, targets.iter().next())
+                    {
+                        if !entry.contains(domain) {
+                            provenance.record(
+                                class_assertion_axiom(domain, subject),
+                                RlRule::PrpDom,
+                                vec![
+                                    domain_axiom(property, domain),
+                                    property_assertion_axiom(property, subject, target),
+                                ],
+                            );
+                        }
+                    }
+                    if entry.insert(domain.clone()) {
+                        self.stats.record_inference(RlRule::PrpDom);
+                    }
                 }
             }
 
@@ -433,7 +1276,24 @@ impl<'a> RlReasoner<'a> {
                     if let Some(domains) = self.property_domains.get(sup) {
                         let entry = self.individual_types.entry(subject.clone()).or_default();
                         for domain in domains {
-                            entry.insert(domain.clone());
+                            if let (Some(provenance), Some(target)) =
+                                (&mut self.provenance, targets.iter().next())
+                            {
+                                if !entry.contains(domain) {
+                                    provenance.record(
+                                        class_assertion_axiom(domain, subject),
+                                        RlRule::PrpDom,
+                                        vec![
+                                            sub_property_axiom(property, sup),
+                                            domain_axiom(sup, domain),
+                                            property_assertion_axiom(property, subject, target),
+                                        ],
+                                    );
+                                }
+                            }
+                            if entry.insert(domain.clone()) {
+                                self.stats.record_inference(RlRule::PrpDom);
+                            }
                         }
                     }
                 }
@@ -442,12 +1302,29 @@ impl<'a> RlReasoner<'a> {
 
         // Step 5: Apply range inference
         // If P rdfs:range C and (x, P, y) exists, then y rdf:type C
-        for ((_, property), targets) in &self.property_values {
+        for ((subject, property), targets) in &self.property_values {
+            if self.inferred_axiom_limit_reached() {
+                return;
+            }
             if let Some(ranges) = self.property_ranges.get(property) {
                 for target in targets {
                     let entry = self.individual_types.entry(target.clone()).or_default();
                     for range in ranges {
-                        entry.insert(range.clone());
+                        if let Some(provenance) = &mut self.provenance {
+                            if !entry.contains(range) {
+                                provenance.record(
+                                    class_assertion_axiom(range, target),
+                                    RlRule::PrpRng,
+                                    vec![
+                                        range_axiom(property, range),
+                                        property_assertion_axiom(property, subject, target),
+                                    ],
+                                );
+                            }
+                        }
+                        if entry.insert(range.clone()) {
+                            self.stats.record_inference(RlRule::PrpRng);
+                        }
                     }
                 }
             }
@@ -459,7 +1336,22 @@ impl<'a> RlReasoner<'a> {
                         for target in targets {
                             let entry = self.individual_types.entry(target.clone()).or_default();
                             for range in ranges {
-                                entry.insert(range.clone());
+                                if let Some(provenance) = &mut self.provenance {
+                                    if !entry.contains(range) {
+                                        provenance.record(
+                                            class_assertion_axiom(range, target),
+                                            RlRule::PrpRng,
+                                            vec![
+                                                sub_property_axiom(property, sup),
+                                                range_axiom(sup, range),
+                                                property_assertion_axiom(property, subject, target),
+                                            ],
+                                        );
+                                    }
+                                }
+                                if entry.insert(range.clone()) {
+                                    self.stats.record_inference(RlRule::PrpRng);
+                                }
                             }
                         }
                     }
@@ -471,22 +1363,38 @@ impl<'a> RlReasoner<'a> {
     /// Propagates types to individuals based on class hierarchy.
     fn propagate_types(&mut self) {
         let mut changed = true;
-        let mut iterations = 0;
 
-        while changed && iterations < self.config.max_iterations {
+        while changed && self.iterations < self.config.max_iterations {
             changed = false;
-            iterations += 1;
+            self.iterations += 1;
 
             let individuals: Vec<_> = self.individual_types.keys().cloned().collect();
 
             for individual in individuals {
+                if self.inferred_axiom_limit_reached() {
+                    return;
+                }
                 if let Some(types) = self.individual_types.get(&individual).cloned() {
                     for typ in types {
                         // Add all superclasses as types
                         if let Some(supers) = self.class_hierarchy.get(&typ).cloned() {
-                            let entry = self.individual_types.entry(individual.clone()).or_default();
+                            let entry =
+                                self.individual_types.entry(individual.clone()).or_default();
                             for sup in supers {
+                                if let Some(provenance) = &mut self.provenance {
+                                    if !entry.contains(&sup) {
+                                        provenance.record(
+                                            class_assertion_axiom(&sup, &individual),
+                                            RlRule::CaxSco,
+                                            vec![
+                                                class_assertion_axiom(&typ, &individual),
+                                                sub_class_axiom(&typ, &sup),
+                                            ],
+                                        );
+                                    }
+                                }
                                 if entry.insert(sup) {
+                                    self.stats.record_inference(RlRule::CaxSco);
                                     changed = true;
                                 }
                             }
@@ -495,6 +1403,7 @@ impl<'a> RlReasoner<'a> {
                 }
             }
         }
+        self.record_convergence(changed);
     }
 
     /// Applies symmetric property rules.
@@ -504,6 +1413,9 @@ impl<'a> RlReasoner<'a> {
         let keys: Vec<_> = self.property_values.keys().cloned().collect();
 
         for (subject, property) in keys {
+            if self.inferred_axiom_limit_reached() {
+                break;
+            }
             if self.symmetric_properties.contains(&property) {
                 if let Some(objects) = self
                     .property_values
@@ -512,11 +1424,29 @@ impl<'a> RlReasoner<'a> {
                 {
                     for object in objects {
                         // Infer (object, property, subject)
+                        if let Some(provenance) = &mut self.provenance {
+                            let inferred = property_assertion_axiom(&property, &object, &subject);
+                            if !self
+                                .property_values
+                                .get(&(object.clone(), property.clone()))
+                                .is_some_and(|s| s.contains(&subject))
+                            {
+                                provenance.record(
+                                    inferred,
+                                    RlRule::PrpSymp,
+                                    vec![
+                                        Axiom::SymmetricObjectProperty(property.clone()),
+                                        property_assertion_axiom(&property, &subject, &object),
+                                    ],
+                                );
+                            }
+                        }
                         let entry = self
                             .property_values
                             .entry((object, property.clone()))
                             .or_default();
                         if entry.insert(subject.clone()) {
+                            self.stats.record_inference(RlRule::PrpSymp);
                             changed = true;
                         }
                     }
@@ -534,6 +1464,9 @@ impl<'a> RlReasoner<'a> {
         let keys: Vec<_> = self.property_values.keys().cloned().collect();
 
         for (subject, property) in keys {
+            if self.inferred_axiom_limit_reached() {
+                break;
+            }
             if self.transitive_properties.contains(&property) {
                 if let Some(middle_objects) = self
                     .property_values
@@ -542,15 +1475,37 @@ impl<'a> RlReasoner<'a> {
                 {
                     for middle in middle_objects {
                         // Look for (middle, property, object)
-                        if let Some(final_objects) =
-                            self.property_values.get(&(middle, property.clone())).cloned()
+                        if let Some(final_objects) = self
+                            .property_values
+                            .get(&(middle.clone(), property.clone()))
+                            .cloned()
                         {
                             let entry = self
                                 .property_values
                                 .entry((subject.clone(), property.clone()))
                                 .or_default();
                             for final_obj in final_objects {
+                                if let Some(provenance) = &mut self.provenance {
+                                    if !entry.contains(&final_obj) {
+                                        provenance.record(
+                                            property_assertion_axiom(
+                                                &property, &subject, &final_obj,
+                                            ),
+                                            RlRule::PrpTrp,
+                                            vec![
+                                                Axiom::TransitiveObjectProperty(property.clone()),
+                                                property_assertion_axiom(
+                                                    &property, &subject, &middle,
+                                                ),
+                                                property_assertion_axiom(
+                                                    &property, &middle, &final_obj,
+                                                ),
+                                            ],
+                                        );
+                                    }
+                                }
                                 if entry.insert(final_obj) {
+                                    self.stats.record_inference(RlRule::PrpTrp);
                                     changed = true;
                                 }
                             }
@@ -570,7 +1525,11 @@ impl<'a> RlReasoner<'a> {
         let keys: Vec<_> = self.property_values.keys().cloned().collect();
 
         for (subject, property) in keys {
-            if let Some(inverse_property) = self.inverse_properties.get(&property).cloned() {
+            if self.inferred_axiom_limit_reached() {
+                break;
+            }
+            if let Some((inverse_property, rule)) = self.inverse_properties.get(&property).cloned()
+            {
                 if let Some(objects) = self
                     .property_values
                     .get(&(subject.clone(), property.clone()))
@@ -578,11 +1537,40 @@ impl<'a> RlReasoner<'a> {
                 {
                     for object in objects {
                         // Infer (object, inverse_property, subject)
+                        if let Some(provenance) = &mut self.provenance {
+                            if !self
+                                .property_values
+                                .get(&(object.clone(), inverse_property.clone()))
+                                .is_some_and(|s| s.contains(&subject))
+                            {
+                                // The inverse mapping is stored in both directions, keep the axiom as written
+                                let inverse_axiom = if rule == RlRule::PrpInv1 {
+                                    Axiom::InverseObjectProperties(
+                                        property.clone(),
+                                        inverse_property.clone(),
+                                    )
+                                } else {
+                                    Axiom::InverseObjectProperties(
+                                        inverse_property.clone(),
+                                        property.clone(),
+                                    )
+                                };
+                                provenance.record(
+                                    property_assertion_axiom(&inverse_property, &object, &subject),
+                                    rule,
+                                    vec![
+                                        inverse_axiom,
+                                        property_assertion_axiom(&property, &subject, &object),
+                                    ],
+                                );
+                            }
+                        }
                         let entry = self
                             .property_values
                             .entry((object, inverse_property.clone()))
                             .or_default();
                         if entry.insert(subject.clone()) {
+                            self.stats.record_inference(rule);
                             changed = true;
                         }
                     }
@@ -593,103 +1581,429 @@ impl<'a> RlReasoner<'a> {
         changed
     }
 
-    /// Checks for inconsistencies.
-    fn check_consistency(&mut self) -> Result<(), InconsistencyError> {
-        // Check if any individual is both same-as and different-from another
-        for (a, b) in &self.different_from {
-            if let Some(same) = self.same_as.get(a) {
-                if same.contains(b) {
-                    return Err(InconsistencyError::new(format!(
-                        "{a} is both sameAs and differentFrom {b}"
-                    )));
+    /// Applies sub-property rules.
+    /// For each (a, P, b) where P is a sub-property of Q, infer (a, Q, b).
+    fn apply_sub_property_rules(&mut self) -> bool {
+        let mut changed = false;
+        let keys: Vec<_> = self.property_values.keys().cloned().collect();
+
+        for (subject, property) in keys {
+            if self.inferred_axiom_limit_reached() {
+                break;
+            }
+            if let Some(supers) = self.property_hierarchy.get(&property).cloned() {
+                if let Some(objects) = self
+                    .property_values
+                    .get(&(subject.clone(), property.clone()))
+                    .cloned()
+                {
+                    for sup in supers {
+                        let entry = self
+                            .property_values
+                            .entry((subject.clone(), sup.clone()))
+                            .or_default();
+                        for object in &objects {
+                            if let Some(provenance) = &mut self.provenance {
+                                if !entry.contains(object) {
+                                    provenance.record(
+                                        property_assertion_axiom(&sup, &subject, object),
+                                        RlRule::PrpSpo1,
+                                        vec![
+                                            sub_property_axiom(&property, &sup),
+                                            property_assertion_axiom(&property, &subject, object),
+                                        ],
+                                    );
+                                }
+                            }
+                            if entry.insert(object.clone()) {
+                                self.stats.record_inference(RlRule::PrpSpo1);
+                                changed = true;
+                            }
+                        }
+                    }
                 }
             }
         }
 
-        // Check for instances of owl:Nothing (unsatisfiable)
-        // This would be detected if we had disjoint classes and an individual
-        // in both classes
-
-        Ok(())
+        changed
     }
 
-    /// Generates inferred axioms from the reasoning results.
-    fn generate_inferred_axioms(&mut self) {
-        if !self.config.materialize {
-            return;
-        }
+    /// Applies property chain rules.
+    /// For each chain P1 o ... o Pn sub-property of P and (a0, P1, a1), ..., (an-1, Pn, an),
+    /// infer (a0, P, an).
+    fn apply_property_chain_rules(&mut self) -> bool {
+        let mut changed = false;
+        let chains: Vec<_> = self.property_chains.iter().cloned().collect();
+
+        for (chain, super_property) in chains {
+            let Some((first, rest)) = chain.split_first() else {
+                continue;
+            };
+            // Property values are only added: if their number did not change since the paths
+            // were last followed, there are no new paths
+            let value_count = self
+                .property_values
+                .iter()
+                .filter(|((_, property), _)| chain.contains(property))
+                .map(|(_, objects)| objects.len())
+                .sum::<usize>();
+            let key = (chain.clone(), super_property.clone());
+            if self.property_chain_value_counts.get(&key) == Some(&value_count) {
+                continue;
+            }
+            // Follow the chain link by link, keeping the individuals visited for provenance
+            let mut paths: Vec<Vec<Individual>> = self
+                .property_values
+                .iter()
+                .filter(|((_, property), _)| property == first)
+                .flat_map(|((subject, _), objects)| {
+                    objects
+                        .iter()
+                        .map(move |object| vec![subject.clone(), object.clone()])
+                })
+                .collect();
+            for property in rest {
+                paths = paths
+                    .into_iter()
+                    .flat_map(|path| {
+                        path.last()
+                            .and_then(|last| {
+                                self.property_values.get(&(last.clone(), property.clone()))
+                            })
+                            .into_iter()
+                            .flatten()
+                            .map(move |next| {
+                                let mut path = path.clone();
+                                path.push(next.clone());
+                                path
+                            })
+                    })
+                    .collect();
+            }
 
-        // Generate SubClassOf axioms from transitive closure
-        for (sub, supers) in &self.class_hierarchy {
-            for sup in supers {
-                // Only add if not already in ontology
-                let axiom = Axiom::SubClassOf {
-                    sub_class: ClassExpression::Class(sub.clone()),
-                    super_class: ClassExpression::Class(sup.clone()),
+            for path in paths {
+                if self.inferred_axiom_limit_reached() {
+                    return changed;
+                }
+                let (Some(source), Some(target)) = (path.first(), path.last()) else {
+                    continue;
                 };
-                self.inferred_axioms.push(axiom);
+                let entry = self
+                    .property_values
+                    .entry((source.clone(), super_property.clone()))
+                    .or_default();
+                if let Some(provenance) = &mut self.provenance {
+                    if !entry.contains(target) {
+                        let mut premises = vec![Axiom::SubPropertyChainOf {
+                            property_chain: chain
+                                .iter()
+                                .map(|p| ObjectPropertyExpression::ObjectProperty(p.clone()))
+                                .collect(),
+                            super_property: super_property.clone(),
+                        }];
+                        premises.extend(
+                            chain
+                                .iter()
+                                .zip(path.iter().zip(&path[1..]))
+                                .map(|(p, (from, to))| property_assertion_axiom(p, from, to)),
+                        );
+                        provenance.record(
+                            property_assertion_axiom(&super_property, source, target),
+                            RlRule::PrpSpo2,
+                            premises,
+                        );
+                    }
+                }
+                if entry.insert(target.clone()) {
+                    self.stats.record_inference(RlRule::PrpSpo2);
+                    changed = true;
+                }
             }
+            self.property_chain_value_counts.insert(key, value_count);
         }
 
-        // Generate ClassAssertion axioms from type propagation
+        changed
+    }
+
+    /// Returns the values of each individual constrained by one of its maximum cardinality
+    /// restrictions, with the restriction.
+    fn max_cardinality_values(&self) -> Vec<(&Individual, &MaxCardinality, Vec<&Individual>)> {
+        let mut result = Vec::new();
+        if self.max_cardinalities.is_empty() && self.individual_max_cardinalities.is_empty() {
+            return result;
+        }
         for (individual, types) in &self.individual_types {
-            for typ in types {
-                let axiom = Axiom::ClassAssertion {
-                    class: ClassExpression::Class(typ.clone()),
-                    individual: individual.clone(),
+            let restrictions = types
+                .iter()
+                .filter_map(|class| self.max_cardinalities.get(class))
+                .chain(self.individual_max_cardinalities.get(individual))
+                .flatten();
+            for restriction in restrictions {
+                let Some(values) = self
+                    .property_values
+                    .get(&(individual.clone(), restriction.property.clone()))
+                else {
+                    continue;
                 };
-                self.inferred_axioms.push(axiom);
+                let values = values
+                    .iter()
+                    .filter(|value| {
+                        restriction.filler.as_ref().is_none_or(|filler| {
+                            self.individual_types
+                                .get(*value)
+                                .is_some_and(|types| types.contains(filler))
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                if !values.is_empty() {
+                    result.push((individual, restriction, values));
+                }
             }
         }
+        result
+    }
 
-        // Generate ObjectPropertyAssertion axioms from property reasoning
-        for ((source, property), targets) in &self.property_values {
-            for target in targets {
-                let axiom = Axiom::ObjectPropertyAssertion {
-                    property: property.clone(),
-                    source: source.clone(),
-                    target: target.clone(),
+    /// Applies maximum cardinality 1 rules (cls-maxc2, cls-maxqc3 and cls-maxqc4).
+    /// For each individual with at most one (C) value for P, all its (C) values for P are the same.
+    fn apply_max_cardinality_rules(&mut self) -> bool {
+        let mut same = Vec::new();
+        for (_, restriction, values) in self.max_cardinality_values() {
+            if restriction.cardinality != 1 {
+                continue;
+            }
+            let rule = match &restriction.filler {
+                None => RlRule::ClsMaxc2,
+                Some(filler) if filler.iri().as_ref() == owl::THING => RlRule::ClsMaxqc4,
+                Some(_) => RlRule::ClsMaxqc3,
+            };
+            for value in &values[1..] {
+                same.push((values[0].clone(), (*value).clone(), rule));
+            }
+        }
+
+        let mut changed = false;
+        for (a, b, rule) in same {
+            if self.inferred_axiom_limit_reached() {
+                break;
+            }
+            if self.add_same_as(a, b) {
+                self.stats.record_inference(rule);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Merges the sameAs equivalence classes of `a` and `b` (eq-sym and eq-trans).
+    ///
+    /// Returns `true` if they were not already known to be the same.
+    fn add_same_as(&mut self, a: Individual, b: Individual) -> bool {
+        if a == b || self.same_as.get(&a).is_some_and(|same| same.contains(&b)) {
+            return false;
+        }
+        let mut class = FxHashSet::default();
+        for individual in [a, b] {
+            if let Some(same) = self.same_as.get(&individual) {
+                class.extend(same.iter().cloned());
+            }
+            class.insert(individual);
+        }
+        for individual in &class {
+            let mut same = class.clone();
+            same.remove(individual);
+            self.same_as.insert(individual.clone(), same);
+        }
+        true
+    }
+
+    /// Looks for a clash in the current closure.
+    ///
+    /// Returns the rule detecting the first clash found, its explanation and the entailed axioms
+    /// it follows from.
+    fn find_clash(&self) -> Option<(RlRule, String, Vec<Axiom>)> {
+        // Check if any individual is both same-as and different-from another (eq-diff1)
+        for (a, b) in &self.different_from {
+            if self.same_as.get(a).is_some_and(|same| same.contains(b)) {
+                return Some((
+                    RlRule::EqDiff1,
+                    format!("{a} is both sameAs and differentFrom {b}"),
+                    vec![
+                        Axiom::SameIndividual(vec![a.clone(), b.clone()]),
+                        Axiom::DifferentIndividuals(vec![a.clone(), b.clone()]),
+                    ],
+                ));
+            }
+        }
+
+        // Check if any individual is related to itself by an irreflexive property (prp-irp)
+        for ((subject, property), objects) in &self.property_values {
+            if self.irreflexive_properties.contains(property) && objects.contains(subject) {
+                return Some((
+                    RlRule::PrpIrp,
+                    format!(
+                        "{subject} is related to itself by the irreflexive property {property}"
+                    ),
+                    vec![
+                        Axiom::IrreflexiveObjectProperty(property.clone()),
+                        property_assertion_axiom(property, subject, subject),
+                    ],
+                ));
+            }
+        }
+
+        // Check if any individual has a value for a property with a maximum cardinality of 0
+        // (cls-maxc1, cls-maxqc1 and cls-maxqc2)
+        for (individual, restriction, values) in self.max_cardinality_values() {
+            if restriction.cardinality == 0 {
+                let mut premises = self.max_cardinality_premises(individual, restriction);
+                premises.push(property_assertion_axiom(
+                    &restriction.property,
+                    individual,
+                    values[0],
+                ));
+                let rule = if let Some(filler) = &restriction.filler {
+                    premises.push(class_assertion_axiom(filler, values[0]));
+                    RlRule::ClsMaxqc1
+                } else {
+                    RlRule::ClsMaxc1
                 };
-                self.inferred_axioms.push(axiom);
+                return Some((
+                    rule,
+                    format!(
+                        "{individual} has a value for {} but its maximum cardinality is 0",
+                        restriction.property
+                    ),
+                    premises,
+                ));
             }
         }
+
+        // Check for instances of two disjoint classes (cax-dw)
+        for (individual, types) in &self.individual_types {
+            for (a, b) in &self.disjoint_classes {
+                if types.contains(a) && types.contains(b) {
+                    return Some((
+                        RlRule::CaxDw,
+                        format!("{individual} is an instance of the disjoint classes {a} and {b}"),
+                        vec![
+                            class_assertion_axiom(a, individual),
+                            class_assertion_axiom(b, individual),
+                            Axiom::DisjointClasses(vec![
+                                ClassExpression::Class(a.clone()),
+                                ClassExpression::Class(b.clone()),
+                            ]),
+                        ],
+                    ));
+                }
+            }
+        }
+
+        // Check for instances of owl:Nothing, which is always empty (cls-nothing2)
+        let nothing = OwlClass::new(owl::NOTHING.into_owned());
+        for (individual, types) in &self.individual_types {
+            if types.contains(&nothing) {
+                return Some((
+                    RlRule::ClsNothing2,
+                    format!("{individual} is an instance of owl:Nothing"),
+                    vec![class_assertion_axiom(&nothing, individual)],
+                ));
+            }
+        }
+
+        None
     }
-}
 
-impl<'a> Reasoner for RlReasoner<'a> {
-    fn classify(&mut self) -> Result<(), OwlError> {
-        if self.classified {
-            return Ok(());
+    /// Returns the entailed axioms giving a maximum cardinality restriction to an individual.
+    fn max_cardinality_premises(
+        &self,
+        individual: &Individual,
+        restriction: &MaxCardinality,
+    ) -> Vec<Axiom> {
+        if self
+            .individual_max_cardinalities
+            .get(individual)
+            .is_some_and(|restrictions| restrictions.contains(restriction))
+        {
+            return vec![Axiom::ClassAssertion {
+                class: restriction.to_expression(),
+                individual: individual.clone(),
+            }];
         }
+        self.individual_types
+            .get(individual)
+            .into_iter()
+            .flatten()
+            .find(|class| {
+                self.max_cardinalities
+                    .get(*class)
+                    .is_some_and(|restrictions| restrictions.contains(restriction))
+            })
+            .map(|class| {
+                vec![
+                    class_assertion_axiom(class, individual),
+                    Axiom::SubClassOf {
+                        sub_class: ClassExpression::Class(class.clone()),
+                        super_class: restriction.to_expression(),
+                    },
+                ]
+            })
+            .unwrap_or_default()
+    }
 
-        // Start timing for timeout enforcement
-        self.start_time = Some(Instant::now());
+    /// Runs the consistency check if configured and remembers the first inconsistency found.
+    ///
+    /// Called after every rule phase so that a clash is reported as soon as it is derived
+    /// instead of after the full closure has been computed.
+    fn detect_inconsistency(&mut self) -> Result<(), OwlError> {
+        if !self.config.check_consistency {
+            return Ok(());
+        }
+        if let Some((rule, explanation, premises)) = self.find_clash() {
+            self.stats.record_clash(rule);
+            let e = InconsistencyError::new(explanation);
+            self.inconsistent = Some(e.clone());
+            self.clash = premises;
+            return Err(OwlError::Inconsistent(e));
+        }
+        Ok(())
+    }
 
-        // Step 1: Initialize from ontology axioms
-        self.initialize();
+    /// Loads the axioms, applies the rules until a fixpoint or a limit is reached
+    /// and materializes the results.
+    fn apply_rules(&mut self, axioms: &[Axiom]) -> Result<(), OwlError> {
+        // Step 1: Initialize from the asserted axioms
+        self.initialize(axioms);
+        self.apply_thing_rules();
         self.check_timeout()?;
+        self.check_inferred_axiom_limit()?;
+        self.detect_inconsistency()?;
 
         // Step 2: Compute transitive closure of class hierarchy
         self.compute_transitive_closure();
         self.check_timeout()?;
+        self.check_inferred_axiom_limit()?;
+        self.detect_inconsistency()?;
 
         // Step 3: Apply RDFS rules (property hierarchy, domain, range)
         self.apply_rdfs_rules();
         self.check_timeout()?;
+        self.check_inferred_axiom_limit()?;
+        self.detect_inconsistency()?;
 
         // Step 4: Propagate types to individuals
         self.propagate_types();
         self.check_timeout()?;
+        self.check_inferred_axiom_limit()?;
+        self.detect_inconsistency()?;
 
         // Step 5: Apply property reasoning rules with fixpoint iteration
         let mut changed = true;
-        let mut iterations = 0;
-        while changed && iterations < self.config.max_iterations {
+        while changed && self.iterations < self.config.max_iterations {
             changed = false;
-            iterations += 1;
+            self.iterations += 1;
 
             // Check timeout periodically (every 10 iterations for responsiveness)
-            if iterations % 10 == 0 {
+            if self.iterations.is_multiple_of(10) {
                 self.check_timeout()?;
             }
 
@@ -707,26 +2021,181 @@ impl<'a> Reasoner for RlReasoner<'a> {
             if self.apply_inverse_property_rules() {
                 changed = true;
             }
-        }
 
-        // Step 6: Check consistency if configured
-        if self.config.check_consistency {
-            if let Err(e) = self.check_consistency() {
-                self.inconsistent = Some(e.clone());
-                return Err(OwlError::Inconsistent(e));
+            // Apply sub-property rules
+            if self.apply_sub_property_rules() {
+                changed = true;
+            }
+
+            // Apply property chain rules
+            if self.apply_property_chain_rules() {
+                changed = true;
+            }
+
+            // Apply maximum cardinality rules
+            if self.apply_max_cardinality_rules() {
+                changed = true;
+            }
+
+            self.check_inferred_axiom_limit()?;
+
+            // Stop as soon as a clash is derived, the rest of the closure is not needed
+            if let Err(e) = self.detect_inconsistency() {
+                self.record_convergence(changed);
+                return Err(e);
             }
         }
+        self.record_convergence(changed);
+        self.stats.record_fixpoint(self.complete);
 
         self.check_timeout()?;
 
-        // Step 7: Generate inferred axioms
+        // Step 6: Generate inferred axioms
         self.generate_inferred_axioms();
 
-        // Step 8: Check materialization limit
+        // Step 7: Check materialization limit
         self.check_materialization_limit()?;
 
         self.classified = true;
-        Ok(())
+
+        // Step 8: Report early termination so callers don't mistake a partial
+        // closure for a complete one
+        self.check_completeness()
+    }
+
+    /// Generates inferred axioms from the reasoning results.
+    fn generate_inferred_axioms(&mut self) {
+        if !self.config.materialize {
+            return;
+        }
+
+        // The closure only grows, regenerate the whole list when axioms are added
+        self.inferred_axioms.clear();
+
+        // Generate SubClassOf axioms from transitive closure
+        for (sub, supers) in &self.class_hierarchy {
+            for sup in supers {
+                // Only add if not already in ontology
+                let axiom = Axiom::SubClassOf {
+                    sub_class: ClassExpression::Class(sub.clone()),
+                    super_class: ClassExpression::Class(sup.clone()),
+                };
+                self.inferred_axioms.push(axiom);
+            }
+        }
+
+        // Generate ClassAssertion axioms from type propagation
+        for (individual, types) in &self.individual_types {
+            for typ in types {
+                let axiom = Axiom::ClassAssertion {
+                    class: ClassExpression::Class(typ.clone()),
+                    individual: individual.clone(),
+                };
+                self.inferred_axioms.push(axiom);
+            }
+        }
+
+        // Generate ObjectPropertyAssertion axioms from property reasoning
+        for ((source, property), targets) in &self.property_values {
+            for target in targets {
+                let axiom = Axiom::ObjectPropertyAssertion {
+                    property: property.clone(),
+                    source: source.clone(),
+                    target: target.clone(),
+                };
+                self.inferred_axioms.push(axiom);
+            }
+        }
+    }
+}
+
+/// Orders individuals by IRI, named individuals before anonymous ones.
+fn compare_individuals(a: &Individual, b: &Individual) -> Ordering {
+    match (a, b) {
+        (Individual::Named(a), Individual::Named(b)) => a.as_str().cmp(b.as_str()),
+        (Individual::Anonymous(a), Individual::Anonymous(b)) => a.as_str().cmp(b.as_str()),
+        (Individual::Named(_), Individual::Anonymous(_)) => Ordering::Less,
+        (Individual::Anonymous(_), Individual::Named(_)) => Ordering::Greater,
+    }
+}
+
+fn sub_class_axiom(sub: &OwlClass, sup: &OwlClass) -> Axiom {
+    Axiom::SubClassOf {
+        sub_class: ClassExpression::Class(sub.clone()),
+        super_class: ClassExpression::Class(sup.clone()),
+    }
+}
+
+fn class_assertion_axiom(class: &OwlClass, individual: &Individual) -> Axiom {
+    Axiom::ClassAssertion {
+        class: ClassExpression::Class(class.clone()),
+        individual: individual.clone(),
+    }
+}
+
+fn property_assertion_axiom(
+    property: &ObjectProperty,
+    source: &Individual,
+    target: &Individual,
+) -> Axiom {
+    Axiom::ObjectPropertyAssertion {
+        property: property.clone(),
+        source: source.clone(),
+        target: target.clone(),
+    }
+}
+
+fn sub_property_axiom(sub: &ObjectProperty, sup: &ObjectProperty) -> Axiom {
+    Axiom::SubObjectPropertyOf {
+        sub_property: ObjectPropertyExpression::ObjectProperty(sub.clone()),
+        super_property: ObjectPropertyExpression::ObjectProperty(sup.clone()),
+    }
+}
+
+fn domain_axiom(property: &ObjectProperty, domain: &OwlClass) -> Axiom {
+    Axiom::ObjectPropertyDomain {
+        property: property.clone(),
+        domain: ClassExpression::Class(domain.clone()),
+    }
+}
+
+fn range_axiom(property: &ObjectProperty, range: &OwlClass) -> Axiom {
+    Axiom::ObjectPropertyRange {
+        property: property.clone(),
+        range: ClassExpression::Class(range.clone()),
+    }
+}
+
+impl<'a> Reasoner for RlReasoner<'a> {
+    fn classify(&mut self) -> Result<(), OwlError> {
+        if self.classified {
+            return self.check_completeness();
+        }
+
+        self.config.validate()?;
+
+        if self.config.reject_non_rl {
+            let violations = self.ontology.validate_profile();
+            if let Some(violation) = violations.first() {
+                return Err(ReasoningError::new(
+                    ReasoningErrorKind::UnsupportedConstruct,
+                    format!(
+                        "{} axiom(s) outside of the OWL 2 RL profile, first: {violation}",
+                        violations.len()
+                    ),
+                )
+                .into());
+            }
+        }
+
+        // Start timing for timeout enforcement
+        let start_time = Instant::now();
+        self.start_time = Some(start_time);
+
+        let ontology = self.ontology;
+        let result = self.apply_rules(ontology.axioms());
+        self.stats.record_run(self.iterations, start_time.elapsed());
+        result
     }
 
     fn is_consistent(&self) -> Result<bool, OwlError> {
@@ -0,0 +1,66 @@
+//! Provenance tracking for inferred axioms.
+
+use super::rules::RlRule;
+use crate::axiom::Axiom;
+use rustc_hash::FxHashMap;
+
+/// A justification for an entailed axiom.
+///
+/// A justification is the set of premises (asserted or themselves inferred) together
+/// with the OWL 2 RL rule that was applied to them to derive the axiom.
+/// Asserted axioms are justified by themselves and have no rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Justification {
+    rule: Option<RlRule>,
+    axioms: Vec<Axiom>,
+}
+
+impl Justification {
+    /// Creates the justification of an asserted axiom.
+    pub(crate) fn asserted(axiom: Axiom) -> Self {
+        Self {
+            rule: None,
+            axioms: vec![axiom],
+        }
+    }
+
+    /// Returns the rule that fired to produce the axiom, or `None` if the axiom is asserted.
+    pub fn rule(&self) -> Option<RlRule> {
+        self.rule
+    }
+
+    /// Returns the premises the rule was applied to.
+    pub fn axioms(&self) -> &[Axiom] {
+        &self.axioms
+    }
+
+    /// Returns the premises the rule was applied to.
+    pub fn into_axioms(self) -> Vec<Axiom> {
+        self.axioms
+    }
+}
+
+/// Records which premises produced each inferred axiom.
+#[derive(Debug, Default)]
+pub(crate) struct Provenance {
+    justifications: FxHashMap<Axiom, Vec<Justification>>,
+}
+
+impl Provenance {
+    /// Records that `conclusion` was derived from `premises` using `rule`.
+    pub fn record(&mut self, conclusion: Axiom, rule: RlRule, premises: Vec<Axiom>) {
+        let justification = Justification {
+            rule: Some(rule),
+            axioms: premises,
+        };
+        let justifications = self.justifications.entry(conclusion).or_default();
+        if !justifications.contains(&justification) {
+            justifications.push(justification);
+        }
+    }
+
+    /// Returns the recorded justifications of an axiom.
+    pub fn get(&self, axiom: &Axiom) -> Option<&[Justification]> {
+        self.justifications.get(axiom).map(Vec::as_slice)
+    }
+}
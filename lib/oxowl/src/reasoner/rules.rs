@@ -1,10 +1,11 @@
 //! OWL 2 RL entailment rules.
 
+use std::fmt;
+
 /// OWL 2 RL rule identifier.
 ///
 /// These identifiers correspond to the OWL 2 RL/RDF rules from the W3C specification.
 /// They are available for debugging, logging, and rule selection purposes.
-#[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RlRule {
     // Class axiom rules
@@ -31,22 +32,24 @@ pub enum RlRule {
     PrpInv2, // InverseOf (2)
 
     // Class expression rules
-    ClsInt1, // IntersectionOf (1)
-    ClsInt2, // IntersectionOf (2)
-    ClsUni,  // UnionOf
-    ClsCom,  // ComplementOf
-    ClsSvf1, // SomeValuesFrom (1)
-    ClsSvf2, // SomeValuesFrom (2)
-    ClsAvf,  // AllValuesFrom
-    ClsHv1,  // HasValue (1)
-    ClsHv2,  // HasValue (2)
-    ClsOo,   // OneOf
-    ClsMaxc1,// MaxCardinality (1)
-    ClsMaxc2,// MaxCardinality (2)
-    ClsMaxqc1,// MaxQualifiedCardinality (1)
-    ClsMaxqc2,// MaxQualifiedCardinality (2)
-    ClsMaxqc3,// MaxQualifiedCardinality (3)
-    ClsMaxqc4,// MaxQualifiedCardinality (4)
+    ClsThing,    // owl:Thing
+    ClsNothing2, // owl:Nothing
+    ClsInt1,     // IntersectionOf (1)
+    ClsInt2,     // IntersectionOf (2)
+    ClsUni,      // UnionOf
+    ClsCom,      // ComplementOf
+    ClsSvf1,     // SomeValuesFrom (1)
+    ClsSvf2,     // SomeValuesFrom (2)
+    ClsAvf,      // AllValuesFrom
+    ClsHv1,      // HasValue (1)
+    ClsHv2,      // HasValue (2)
+    ClsOo,       // OneOf
+    ClsMaxc1,    // MaxCardinality (1)
+    ClsMaxc2,    // MaxCardinality (2)
+    ClsMaxqc1,   // MaxQualifiedCardinality (1)
+    ClsMaxqc2,   // MaxQualifiedCardinality (2)
+    ClsMaxqc3,   // MaxQualifiedCardinality (3)
+    ClsMaxqc4,   // MaxQualifiedCardinality (4)
 
     // Equality rules
     EqRef,   // Reflexivity of =
@@ -56,27 +59,68 @@ pub enum RlRule {
     EqDiff1, // Different => not same
     EqDiff2, // Different => not same (2)
     EqDiff3, // Different => not same (3)
+
+    // Schema vocabulary rules
+    ScmSco,  // SubClassOf transitivity
+    ScmEqc1, // EquivalentClasses => SubClassOf
+    ScmCls,  // Class => SubClassOf owl:Thing
 }
 
 impl RlRule {
     /// Returns all OWL 2 RL rules.
     pub fn all() -> &'static [RlRule] {
         &[
-            RlRule::CaxSco, RlRule::CaxEqc1, RlRule::CaxEqc2, RlRule::CaxDw,
-            RlRule::PrpDom, RlRule::PrpRng, RlRule::PrpFp, RlRule::PrpIfp,
-            RlRule::PrpIrp, RlRule::PrpSymp, RlRule::PrpAsp, RlRule::PrpTrp,
-            RlRule::PrpSpo1, RlRule::PrpSpo2, RlRule::PrpEqp1, RlRule::PrpEqp2,
-            RlRule::PrpPdw, RlRule::PrpInv1, RlRule::PrpInv2,
-            RlRule::ClsInt1, RlRule::ClsInt2, RlRule::ClsUni, RlRule::ClsCom,
-            RlRule::ClsSvf1, RlRule::ClsSvf2, RlRule::ClsAvf, RlRule::ClsHv1,
-            RlRule::ClsHv2, RlRule::ClsOo, RlRule::ClsMaxc1, RlRule::ClsMaxc2,
-            RlRule::ClsMaxqc1, RlRule::ClsMaxqc2, RlRule::ClsMaxqc3, RlRule::ClsMaxqc4,
-            RlRule::EqRef, RlRule::EqSym, RlRule::EqTrans, RlRule::EqRep,
-            RlRule::EqDiff1, RlRule::EqDiff2, RlRule::EqDiff3,
+            RlRule::CaxSco,
+            RlRule::CaxEqc1,
+            RlRule::CaxEqc2,
+            RlRule::CaxDw,
+            RlRule::PrpDom,
+            RlRule::PrpRng,
+            RlRule::PrpFp,
+            RlRule::PrpIfp,
+            RlRule::PrpIrp,
+            RlRule::PrpSymp,
+            RlRule::PrpAsp,
+            RlRule::PrpTrp,
+            RlRule::PrpSpo1,
+            RlRule::PrpSpo2,
+            RlRule::PrpEqp1,
+            RlRule::PrpEqp2,
+            RlRule::PrpPdw,
+            RlRule::PrpInv1,
+            RlRule::PrpInv2,
+            RlRule::ClsThing,
+            RlRule::ClsNothing2,
+            RlRule::ClsInt1,
+            RlRule::ClsInt2,
+            RlRule::ClsUni,
+            RlRule::ClsCom,
+            RlRule::ClsSvf1,
+            RlRule::ClsSvf2,
+            RlRule::ClsAvf,
+            RlRule::ClsHv1,
+            RlRule::ClsHv2,
+            RlRule::ClsOo,
+            RlRule::ClsMaxc1,
+            RlRule::ClsMaxc2,
+            RlRule::ClsMaxqc1,
+            RlRule::ClsMaxqc2,
+            RlRule::ClsMaxqc3,
+            RlRule::ClsMaxqc4,
+            RlRule::EqRef,
+            RlRule::EqSym,
+            RlRule::EqTrans,
+            RlRule::EqRep,
+            RlRule::EqDiff1,
+            RlRule::EqDiff2,
+            RlRule::EqDiff3,
+            RlRule::ScmSco,
+            RlRule::ScmEqc1,
+            RlRule::ScmCls,
         ]
     }
 
-    #[expect(dead_code)]
+    /// Returns the rule name used in the W3C specification (e.g. `cax-sco`).
     pub fn name(self) -> &'static str {
         match self {
             RlRule::CaxSco => "cax-sco",
@@ -98,6 +142,8 @@ impl RlRule {
             RlRule::PrpPdw => "prp-pdw",
             RlRule::PrpInv1 => "prp-inv1",
             RlRule::PrpInv2 => "prp-inv2",
+            RlRule::ClsThing => "cls-thing",
+            RlRule::ClsNothing2 => "cls-nothing2",
             RlRule::ClsInt1 => "cls-int1",
             RlRule::ClsInt2 => "cls-int2",
             RlRule::ClsUni => "cls-uni",
@@ -121,6 +167,15 @@ impl RlRule {
             RlRule::EqDiff1 => "eq-diff1",
             RlRule::EqDiff2 => "eq-diff2",
             RlRule::EqDiff3 => "eq-diff3",
+            RlRule::ScmSco => "scm-sco",
+            RlRule::ScmEqc1 => "scm-eqc1",
+            RlRule::ScmCls => "scm-cls",
         }
     }
 }
+
+impl fmt::Display for RlRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
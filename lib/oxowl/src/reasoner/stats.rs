@@ -0,0 +1,88 @@
+//! Statistics about a reasoning run.
+
+use super::rules::RlRule;
+use rustc_hash::FxHashMap;
+use std::time::Duration;
+
+/// Statistics about the work done by [`RlReasoner::classify`](super::RlReasoner).
+///
+/// Returned by [`RlReasoner::stats`](super::RlReasoner::stats).
+/// All values are zero before classification.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReasonerStats {
+    iterations: usize,
+    inferred_axioms: usize,
+    rule_applications: FxHashMap<RlRule, usize>,
+    duration: Duration,
+    fixpoint_reached: bool,
+}
+
+impl ReasonerStats {
+    /// Returns the number of fixpoint iterations performed, summed over all rule phases
+    /// and over [`RlReasoner::add_axioms`](super::RlReasoner::add_axioms) calls.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the number of axioms derived by the rules.
+    pub fn inferred_axioms(&self) -> usize {
+        self.inferred_axioms
+    }
+
+    /// Returns how many new axioms the given rule derived.
+    ///
+    /// The rule that detected an inconsistency (e.g. [`RlRule::ClsNothing2`]) is counted once.
+    pub fn rule_applications(&self, rule: RlRule) -> usize {
+        self.rule_applications.get(&rule).copied().unwrap_or(0)
+    }
+
+    /// Returns the rules that derived at least one axiom with their number of derivations,
+    /// in the order of [`RlRule::all`].
+    pub fn rules(&self) -> impl Iterator<Item = (RlRule, usize)> + '_ {
+        RlRule::all()
+            .iter()
+            .filter_map(|rule| Some((*rule, *self.rule_applications.get(rule)?)))
+    }
+
+    /// Returns the wall time spent in classification, including
+    /// [`RlReasoner::add_axioms`](super::RlReasoner::add_axioms) calls.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns `true` if the rules were applied until nothing new could be derived.
+    ///
+    /// Returns `false` if classification was stopped by a limit, a timeout or an inconsistency.
+    pub fn fixpoint_reached(&self) -> bool {
+        self.fixpoint_reached
+    }
+
+    /// Records that `rule` derived a new axiom.
+    pub(super) fn record_inference(&mut self, rule: RlRule) {
+        self.inferred_axioms += 1;
+        *self.rule_applications.entry(rule).or_default() += 1;
+    }
+
+    /// Records that `rule` detected an inconsistency.
+    pub(super) fn record_clash(&mut self, rule: RlRule) {
+        *self.rule_applications.entry(rule).or_default() += 1;
+    }
+
+    /// Records whether the last fixpoint loop converged.
+    pub(super) fn record_fixpoint(&mut self, fixpoint_reached: bool) {
+        self.fixpoint_reached = fixpoint_reached;
+    }
+
+    /// Forgets the derivations of a previous classification, keeping the iterations and the wall time.
+    pub(super) fn clear_derivations(&mut self) {
+        self.inferred_axioms = 0;
+        self.rule_applications.clear();
+        self.fixpoint_reached = false;
+    }
+
+    /// Records the iterations and the wall time of a classification run.
+    pub(super) fn record_run(&mut self, iterations: usize, duration: Duration) {
+        self.iterations += iterations;
+        self.duration += duration;
+    }
+}
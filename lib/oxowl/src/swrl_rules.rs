@@ -0,0 +1,313 @@
+//! SWRL rule import for OWL ontologies.
+//!
+//! This module reads SWRL rules serialized in RDF (`swrl:Imp` resources with
+//! `swrl:body` and `swrl:head` atom lists) and converts them to [`N3Rule`]s,
+//! so that they are turned into OWL axioms the same way as N3 `log:implies` rules.
+
+use crate::error::{OwlParseError, ParseErrorKind};
+use crate::n3_rules::N3Rule;
+use crate::ontology::Ontology;
+use oxrdf::vocab::rdf;
+use oxrdf::{
+    BlankNode, Formula, Graph, NamedNode, NamedNodeRef, NamedOrBlankNode, NamedOrBlankNodeRef,
+    TermRef, Triple, TripleRef,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// SWRL vocabulary.
+mod swrl {
+    use oxrdf::NamedNodeRef;
+
+    pub const IMP: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#Imp");
+    pub const BODY: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#body");
+    pub const HEAD: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#head");
+    pub const VARIABLE: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#Variable");
+    pub const CLASS_ATOM: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#ClassAtom");
+    pub const INDIVIDUAL_PROPERTY_ATOM: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#IndividualPropertyAtom");
+    pub const CLASS_PREDICATE: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#classPredicate");
+    pub const PROPERTY_PREDICATE: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#propertyPredicate");
+    pub const ARGUMENT1: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#argument1");
+    pub const ARGUMENT2: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2003/11/swrl#argument2");
+}
+
+/// SWRL rule extractor that finds `swrl:Imp` rules in RDF graphs.
+pub struct SwrlRuleExtractor<'a> {
+    /// The RDF graph to extract rules from
+    graph: &'a Graph,
+}
+
+impl<'a> SwrlRuleExtractor<'a> {
+    /// Creates a new rule extractor for the given graph.
+    pub fn new(graph: &'a Graph) -> Self {
+        Self { graph }
+    }
+
+    /// Extracts all SWRL rules as N3 rules.
+    ///
+    /// The body becomes the antecedent and the head the consequent.
+    /// SWRL variables are mapped to blank nodes shared by both formulas,
+    /// like variables in N3 rules.
+    ///
+    /// Only class atoms and individual property atoms are supported,
+    /// other atoms (data properties, built-ins...) are reported as errors.
+    pub fn extract_rules(&self) -> Result<Vec<N3Rule>, OwlParseError> {
+        let mut rules = Vec::new();
+
+        for rule in self
+            .graph
+            .subjects_for_predicate_object(rdf::TYPE, swrl::IMP)
+        {
+            // Each rule has its own variable scope
+            let mut variables = FxHashMap::default();
+            let body = self.parse_atoms(rule, swrl::BODY, &mut variables)?;
+            let head = self.parse_atoms(rule, swrl::HEAD, &mut variables)?;
+            rules.push(N3Rule::new(
+                Formula::new(BlankNode::default(), body),
+                Formula::new(BlankNode::default(), head),
+            ));
+        }
+
+        Ok(rules)
+    }
+
+    /// Parses the atom list of the `swrl:body` or `swrl:head` of a rule into triples.
+    fn parse_atoms(
+        &self,
+        rule: NamedOrBlankNodeRef<'_>,
+        predicate: NamedNodeRef<'_>,
+        variables: &mut FxHashMap<NamedOrBlankNode, BlankNode>,
+    ) -> Result<Vec<Triple>, OwlParseError> {
+        let list = self
+            .graph
+            .object_for_subject_predicate(rule, predicate)
+            .ok_or_else(|| OwlParseError::missing_property(predicate.as_str()))?;
+        self.parse_list(list)?
+            .into_iter()
+            .map(|atom| self.parse_atom(atom, variables))
+            .collect()
+    }
+
+    /// Parses an RDF list, `swrl:AtomList` being a subclass of `rdf:List`.
+    fn parse_list(&self, head: TermRef<'a>) -> Result<Vec<TermRef<'a>>, OwlParseError> {
+        let mut result = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut current = head;
+
+        while current != rdf::NIL.into() {
+            let current_ref = to_named_or_blank(current).ok_or_else(|| {
+                OwlParseError::malformed_list("List node must be named or blank node")
+            })?;
+            if !visited.insert(current_ref) {
+                return Err(OwlParseError::circular_reference("Cyclic SWRL atom list"));
+            }
+
+            result.push(
+                self.graph
+                    .object_for_subject_predicate(current_ref, rdf::FIRST)
+                    .ok_or_else(|| OwlParseError::malformed_list("Missing rdf:first"))?,
+            );
+            current = self
+                .graph
+                .object_for_subject_predicate(current_ref, rdf::REST)
+                .ok_or_else(|| OwlParseError::malformed_list("Missing rdf:rest"))?;
+        }
+
+        Ok(result)
+    }
+
+    /// Converts a class atom or an individual property atom to a triple.
+    fn parse_atom(
+        &self,
+        atom: TermRef<'_>,
+        variables: &mut FxHashMap<NamedOrBlankNode, BlankNode>,
+    ) -> Result<Triple, OwlParseError> {
+        let atom = to_named_or_blank(atom).ok_or_else(|| {
+            OwlParseError::invalid_value("SWRL atom must be a named or blank node")
+        })?;
+
+        if self
+            .graph
+            .contains(TripleRef::new(atom, rdf::TYPE, swrl::CLASS_ATOM))
+        {
+            let class = self.named_node(atom, swrl::CLASS_PREDICATE)?;
+            let argument = self.argument(atom, swrl::ARGUMENT1, variables)?;
+            Ok(Triple::new(argument, rdf::TYPE, class))
+        } else if self.graph.contains(TripleRef::new(
+            atom,
+            rdf::TYPE,
+            swrl::INDIVIDUAL_PROPERTY_ATOM,
+        )) {
+            let property = self.named_node(atom, swrl::PROPERTY_PREDICATE)?;
+            let subject = self.argument(atom, swrl::ARGUMENT1, variables)?;
+            let object = self.argument(atom, swrl::ARGUMENT2, variables)?;
+            Ok(Triple::new(subject, property, object))
+        } else {
+            Err(OwlParseError::new(
+                ParseErrorKind::UnknownConstruct,
+                format!(
+                    "Unsupported SWRL atom {atom}, only class and individual property atoms are supported"
+                ),
+            ))
+        }
+    }
+
+    /// Returns the IRI value of a predicate of an atom.
+    fn named_node(
+        &self,
+        atom: NamedOrBlankNodeRef<'_>,
+        predicate: NamedNodeRef<'_>,
+    ) -> Result<NamedNode, OwlParseError> {
+        match self.graph.object_for_subject_predicate(atom, predicate) {
+            Some(TermRef::NamedNode(n)) => Ok(n.into_owned()),
+            Some(_) => Err(OwlParseError::invalid_value(format!(
+                "{predicate} of SWRL atom {atom} must be an IRI"
+            ))),
+            None => Err(OwlParseError::missing_property(predicate.as_str())),
+        }
+    }
+
+    /// Returns an argument of an atom, SWRL variables being replaced by their blank node.
+    fn argument(
+        &self,
+        atom: NamedOrBlankNodeRef<'_>,
+        predicate: NamedNodeRef<'_>,
+        variables: &mut FxHashMap<NamedOrBlankNode, BlankNode>,
+    ) -> Result<NamedOrBlankNode, OwlParseError> {
+        let argument = self
+            .graph
+            .object_for_subject_predicate(atom, predicate)
+            .ok_or_else(|| OwlParseError::missing_property(predicate.as_str()))?;
+        let argument = to_named_or_blank(argument).ok_or_else(|| {
+            OwlParseError::invalid_value(format!(
+                "{predicate} of SWRL atom {atom} must be an individual or a variable"
+            ))
+        })?;
+
+        if self
+            .graph
+            .contains(TripleRef::new(argument, rdf::TYPE, swrl::VARIABLE))
+        {
+            Ok(variables
+                .entry(argument.into_owned())
+                .or_default()
+                .clone()
+                .into())
+        } else {
+            Ok(argument.into_owned())
+        }
+    }
+}
+
+/// Extends an ontology with axioms derived from the SWRL rules of a graph.
+///
+/// The rules are converted like N3 rules, see [`N3Rule::to_owl_axioms`].
+/// Returns the number of axioms added, or an error without modifying the ontology
+/// if a rule can't be read or has no OWL 2 RL equivalent.
+pub fn extend_ontology_with_swrl_rules(
+    ontology: &mut Ontology,
+    graph: &Graph,
+) -> Result<usize, OwlParseError> {
+    let rules = SwrlRuleExtractor::new(graph).extract_rules()?;
+    let mut axioms = Vec::new();
+    for rule in &rules {
+        axioms.extend(rule.to_owl_axioms()?);
+    }
+
+    let count = axioms.len();
+    for axiom in axioms {
+        ontology.add_axiom(axiom);
+    }
+
+    Ok(count)
+}
+
+fn to_named_or_blank(term: TermRef<'_>) -> Option<NamedOrBlankNodeRef<'_>> {
+    if let TermRef::NamedNode(n) = term {
+        Some(n.into())
+    } else if let TermRef::BlankNode(b) = term {
+        Some(b.into())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxttl::TurtleParser;
+
+    const PREFIXES: &str = "
+@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+@prefix swrl: <http://www.w3.org/2003/11/swrl#> .
+@prefix ex: <http://example.org/> .
+";
+
+    fn parse(data: &str) -> Graph {
+        TurtleParser::new()
+            .for_slice(format!("{PREFIXES}{data}").as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_class_atoms_share_variables() {
+        let graph = parse(
+            "
+ex:x a swrl:Variable .
+[] a swrl:Imp ;
+    swrl:body ( [ a swrl:ClassAtom ; swrl:classPredicate ex:Dog ; swrl:argument1 ex:x ] ) ;
+    swrl:head ( [ a swrl:ClassAtom ; swrl:classPredicate ex:Animal ; swrl:argument1 ex:x ] ) .
+",
+        );
+
+        let rules = SwrlRuleExtractor::new(&graph).extract_rules().unwrap();
+        assert_eq!(rules.len(), 1);
+        let rule = &rules[0];
+        assert!(rule.is_subclass_pattern());
+        assert_eq!(
+            rule.antecedent.triples()[0].subject,
+            rule.consequent.triples()[0].subject
+        );
+        assert!(rule.antecedent.triples()[0].subject.is_blank_node());
+    }
+
+    #[test]
+    fn test_unsupported_atom() {
+        let graph = parse(
+            "
+ex:x a swrl:Variable .
+ex:y a swrl:Variable .
+[] a swrl:Imp ;
+    swrl:body ( [ a swrl:BuiltinAtom ; swrl:builtin ex:greaterThan ; swrl:arguments ( ex:x ex:y ) ] ) ;
+    swrl:head ( [ a swrl:ClassAtom ; swrl:classPredicate ex:Adult ; swrl:argument1 ex:x ] ) .
+",
+        );
+
+        let error = SwrlRuleExtractor::new(&graph).extract_rules().unwrap_err();
+        assert_eq!(error.kind(), ParseErrorKind::UnknownConstruct);
+    }
+
+    #[test]
+    fn test_missing_head() {
+        let graph = parse(
+            "
+ex:x a swrl:Variable .
+[] a swrl:Imp ;
+    swrl:body ( [ a swrl:ClassAtom ; swrl:classPredicate ex:Dog ; swrl:argument1 ex:x ] ) .
+",
+        );
+
+        let error = SwrlRuleExtractor::new(&graph).extract_rules().unwrap_err();
+        assert_eq!(error.kind(), ParseErrorKind::MissingProperty);
+    }
+}
@@ -202,6 +202,45 @@ fn test_ontology_clear() {
     assert_eq!(ontology.axiom_count(), 0);
 }
 
+#[test]
+fn test_add_imports_closure() {
+    // a imports b and c, b imports c and a back, c imports nothing
+    let a = NamedNode::new("http://example.org/a").unwrap();
+    let b = NamedNode::new("http://example.org/b").unwrap();
+    let c = NamedNode::new("http://example.org/c").unwrap();
+    let unknown = NamedNode::new("http://example.org/unknown").unwrap();
+    let declaring = |iri: &NamedNode, class: &str, imports: &[&NamedNode]| {
+        let mut ontology = Ontology::new(Some(iri.clone()));
+        for import in imports {
+            ontology.add_import((*import).clone());
+        }
+        ontology.add_axiom(Axiom::DeclareClass(OwlClass::new(
+            NamedNode::new(format!("http://example.org/{class}")).unwrap(),
+        )));
+        ontology
+    };
+    let resolver = |iri: &NamedNode| {
+        if *iri == b {
+            Some(declaring(&b, "B", &[&c, &a, &unknown]))
+        } else if *iri == c {
+            Some(declaring(&c, "C", &[]))
+        } else {
+            None
+        }
+    };
+
+    let mut ontology = declaring(&a, "A", &[]);
+    let unresolved = ontology.add_imports(&b, resolver);
+    assert_eq!(unresolved, [unknown.clone()]);
+    assert_eq!(ontology.axiom_count(), 3);
+    assert_eq!(ontology.imports(), [b.clone()]);
+    assert_eq!(ontology.classes().count(), 3);
+
+    // Already loaded ontologies are not merged twice
+    assert!(ontology.add_imports(&c, resolver).is_empty());
+    assert_eq!(ontology.axiom_count(), 3);
+}
+
 #[test]
 fn test_individual_types() {
     let named = Individual::Named(NamedNode::new("http://example.org/ind1").unwrap());
@@ -229,6 +268,41 @@ fn test_ontology_display() {
     assert!(display.contains("0 axioms"));
 }
 
+#[test]
+fn test_validate_profile_accepts_rl_constructs() {
+    let mut ontology = Ontology::new(None);
+
+    let animal = OwlClass::new(NamedNode::new("http://example.org/Animal").unwrap());
+    let plant = OwlClass::new(NamedNode::new("http://example.org/Plant").unwrap());
+    let has_part = ObjectProperty::new(NamedNode::new("http://example.org/hasPart").unwrap());
+
+    // ∃hasPart.Animal ⊑ ∀hasPart.Animal ⊓ ¬Plant is allowed in OWL 2 RL
+    ontology.add_axiom(Axiom::subclass_of(
+        ClassExpression::some_values_from(has_part.clone(), ClassExpression::class(animal.clone())),
+        ClassExpression::intersection(vec![
+            ClassExpression::all_values_from(has_part.clone(), ClassExpression::class(animal.clone())),
+            ClassExpression::complement(ClassExpression::class(plant.clone())),
+        ]),
+    ));
+    assert!(ontology.validate_profile().is_empty());
+
+    // Animal ≡ Plant ⊔ ∃hasPart.Animal is not
+    ontology.add_axiom(Axiom::EquivalentClasses(vec![
+        ClassExpression::class(animal.clone()),
+        ClassExpression::union(vec![
+            ClassExpression::class(plant),
+            ClassExpression::some_values_from(has_part, ClassExpression::class(animal)),
+        ]),
+    ]));
+    let violations = ontology.validate_profile();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].axiom(), &ontology.axioms()[1]);
+    assert_eq!(
+        violations[0].reason(),
+        "ObjectUnionOf is not allowed in equivalent class position"
+    );
+}
+
 // Reasoner tests (when feature is enabled)
 #[cfg(feature = "reasoner-rl")]
 mod reasoner_tests {
@@ -261,6 +335,60 @@ mod reasoner_tests {
         assert!(super_classes.contains(&&animal));
     }
 
+    #[test]
+    fn test_reasoner_assert_entails() {
+        let mut ontology = Ontology::new(None);
+
+        let animal = OwlClass::new(NamedNode::new("http://example.org/Animal").unwrap());
+        let mammal = OwlClass::new(NamedNode::new("http://example.org/Mammal").unwrap());
+        let dog = OwlClass::new(NamedNode::new("http://example.org/Dog").unwrap());
+        let poodle = OwlClass::new(NamedNode::new("http://example.org/Poodle").unwrap());
+        let cat = OwlClass::new(NamedNode::new("http://example.org/Cat").unwrap());
+
+        // Poodle subClassOf Dog subClassOf Mammal subClassOf Animal and Cat subClassOf Mammal
+        for (sub, sup) in [
+            (&poodle, &dog),
+            (&dog, &mammal),
+            (&mammal, &animal),
+            (&cat, &mammal),
+        ] {
+            ontology.add_axiom(Axiom::subclass_of(
+                ClassExpression::class(sub.clone()),
+                ClassExpression::class(sup.clone()),
+            ));
+        }
+
+        let mut reasoner = RlReasoner::new(&ontology);
+        reasoner.classify().unwrap();
+
+        let sub_class_of = |sub: &OwlClass, sup: &OwlClass| {
+            Axiom::subclass_of(
+                ClassExpression::class(sub.clone()),
+                ClassExpression::class(sup.clone()),
+            )
+        };
+        let expected = [
+            sub_class_of(&poodle, &dog),
+            sub_class_of(&poodle, &mammal),
+            sub_class_of(&poodle, &animal),
+            sub_class_of(&dog, &animal),
+            sub_class_of(&cat, &animal),
+        ];
+        assert_eq!(reasoner.assert_entails(&expected), Ok(()));
+
+        // Siblings and reversed edges are not entailed
+        let dog_is_a_cat = sub_class_of(&dog, &cat);
+        let animal_is_a_poodle = sub_class_of(&animal, &poodle);
+        assert_eq!(
+            reasoner.assert_entails(&[
+                sub_class_of(&poodle, &animal),
+                dog_is_a_cat.clone(),
+                animal_is_a_poodle.clone(),
+            ]),
+            Err(vec![dog_is_a_cat, animal_is_a_poodle])
+        );
+    }
+
     #[test]
     fn test_reasoner_type_inference() {
         let mut ontology = Ontology::new(None);
@@ -329,7 +457,11 @@ mod reasoner_tests {
 #[cfg(feature = "reasoner-rl")]
 mod advanced_reasoner_tests {
     use super::*;
-    use oxowl::{Reasoner, RlReasoner};
+    use oxowl::{
+        ObjectPropertyExpression, OwlError, Reasoner, ReasonerConfig, ReasonerStats,
+        ReasoningErrorKind, RlReasoner, RlRule,
+    };
+    use oxrdf::vocab::owl;
 
     #[test]
     fn test_domain_range_inference() {
@@ -453,4 +585,621 @@ mod advanced_reasoner_tests {
 
         // bob knows alice should be inferred
     }
+
+    #[test]
+    fn test_explain_transitive_entailment() {
+        let mut ontology = Ontology::new(None);
+
+        let ancestor_of =
+            ObjectProperty::new(NamedNode::new("http://example.org/ancestorOf").unwrap());
+
+        let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+        let charlie = Individual::Named(NamedNode::new("http://example.org/charlie").unwrap());
+
+        ontology.add_axiom(Axiom::TransitiveObjectProperty(ancestor_of.clone()));
+        let alice_bob = Axiom::ObjectPropertyAssertion {
+            property: ancestor_of.clone(),
+            source: alice.clone(),
+            target: bob.clone(),
+        };
+        ontology.add_axiom(alice_bob.clone());
+        let bob_charlie = Axiom::ObjectPropertyAssertion {
+            property: ancestor_of.clone(),
+            source: bob,
+            target: charlie.clone(),
+        };
+        ontology.add_axiom(bob_charlie.clone());
+
+        let config = ReasonerConfig {
+            track_provenance: true,
+            ..Default::default()
+        };
+        let mut reasoner = RlReasoner::with_config(&ontology, config);
+        reasoner.classify().unwrap();
+
+        let justifications = reasoner
+            .justifications(&Axiom::ObjectPropertyAssertion {
+                property: ancestor_of.clone(),
+                source: alice,
+                target: charlie,
+            })
+            .unwrap();
+        assert_eq!(justifications[0].rule(), Some(RlRule::PrpTrp));
+        assert_eq!(
+            justifications[0].axioms(),
+            [
+                Axiom::TransitiveObjectProperty(ancestor_of),
+                alice_bob,
+                bob_charlie
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inconsistency_detected_before_fixpoint() {
+        let mut ontology = Ontology::new(None);
+
+        let ancestor_of =
+            ObjectProperty::new(NamedNode::new("http://example.org/ancestorOf").unwrap());
+        let descendant_of =
+            ObjectProperty::new(NamedNode::new("http://example.org/descendantOf").unwrap());
+
+        let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+
+        ontology.add_axiom(Axiom::TransitiveObjectProperty(ancestor_of.clone()));
+        ontology.add_axiom(Axiom::IrreflexiveObjectProperty(ancestor_of.clone()));
+        ontology.add_axiom(Axiom::InverseObjectProperties(
+            ancestor_of.clone(),
+            descendant_of.clone(),
+        ));
+
+        // alice ancestorOf bob, alice descendantOf bob
+        // The first iteration derives bob ancestorOf alice from the inverse,
+        // the second one derives alice ancestorOf alice by transitivity
+        ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+            property: ancestor_of,
+            source: alice.clone(),
+            target: bob.clone(),
+        });
+        ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+            property: descendant_of,
+            source: alice,
+            target: bob,
+        });
+
+        // A single iteration is not enough to derive the clash
+        let config = ReasonerConfig {
+            max_iterations: 1,
+            ..Default::default()
+        };
+        let mut reasoner = RlReasoner::with_config(&ontology, config);
+        assert!(matches!(
+            reasoner.classify(),
+            Err(OwlError::Reasoning(e)) if e.kind() == ReasoningErrorKind::MaxIterationsExceeded
+        ));
+        assert!(reasoner.is_consistent().unwrap());
+
+        // The clash is reported as soon as it is derived, before the closure is complete
+        let mut reasoner = RlReasoner::new(&ontology);
+        assert!(matches!(reasoner.classify(), Err(OwlError::Inconsistent(_))));
+        assert!(!reasoner.is_consistent().unwrap());
+        assert!(!reasoner.is_complete());
+        assert!(reasoner.get_inferred_axioms().is_empty());
+
+        // Without consistency checking the full closure is computed
+        let config = ReasonerConfig {
+            check_consistency: false,
+            ..Default::default()
+        };
+        let mut reasoner = RlReasoner::with_config(&ontology, config);
+        reasoner.classify().unwrap();
+        assert!(reasoner.is_complete());
+    }
+
+    #[test]
+    fn test_max_iterations_shared_by_phases() {
+        let class = |name: &str| {
+            OwlClass::new(NamedNode::new(format!("http://example.org/{name}")).unwrap())
+        };
+        let knows = ObjectProperty::new(NamedNode::new("http://example.org/knows").unwrap());
+        let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+
+        let mut ontology = Ontology::new(None);
+        // A ⊑ B ⊑ C is closed by the class hierarchy phase in two iterations
+        ontology.add_axiom(Axiom::subclass_of(
+            ClassExpression::class(class("A")),
+            ClassExpression::class(class("B")),
+        ));
+        ontology.add_axiom(Axiom::subclass_of(
+            ClassExpression::class(class("B")),
+            ClassExpression::class(class("C")),
+        ));
+        // bob knows alice is only derived by the later property phase
+        ontology.add_axiom(Axiom::SymmetricObjectProperty(knows.clone()));
+        ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+            property: knows,
+            source: alice,
+            target: bob,
+        });
+
+        let mut reasoner = RlReasoner::new(&ontology);
+        reasoner.classify().unwrap();
+        assert!(reasoner.is_complete());
+        assert_eq!(reasoner.stats().rule_applications(RlRule::ScmSco), 1);
+        assert_eq!(reasoner.stats().rule_applications(RlRule::PrpSymp), 1);
+
+        // The class hierarchy phase spends the whole budget, the later phases are skipped
+        let config = ReasonerConfig {
+            max_iterations: 2,
+            ..Default::default()
+        };
+        let mut reasoner = RlReasoner::with_config(&ontology, config);
+        let is_max_iterations_exceeded = |result: Result<(), OwlError>| {
+            matches!(
+                result,
+                Err(OwlError::Reasoning(e)) if e.kind() == ReasoningErrorKind::MaxIterationsExceeded
+            )
+        };
+        assert!(is_max_iterations_exceeded(reasoner.classify()));
+        assert!(!reasoner.is_complete());
+        let stats = reasoner.stats();
+        assert_eq!(stats.iterations(), 2);
+        assert!(!stats.fixpoint_reached());
+        assert_eq!(stats.rule_applications(RlRule::ScmSco), 1);
+        assert_eq!(stats.rule_applications(RlRule::PrpSymp), 0);
+        // The partial closure is still materialized
+        assert!(reasoner.get_inferred_axioms().contains(&Axiom::subclass_of(
+            ClassExpression::class(class("A")),
+            ClassExpression::class(class("C")),
+        )));
+        // Classifying again does not run the skipped phases and reports the same error
+        assert!(is_max_iterations_exceeded(reasoner.classify()));
+        assert_eq!(reasoner.stats().rule_applications(RlRule::PrpSymp), 0);
+    }
+
+    #[test]
+    fn test_owl_thing_and_owl_nothing() {
+        let thing = OwlClass::new(owl::THING.into_owned());
+        let nothing = OwlClass::new(owl::NOTHING.into_owned());
+        let dog = OwlClass::new(NamedNode::new("http://example.org/Dog").unwrap());
+        let knows = ObjectProperty::new(NamedNode::new("http://example.org/knows").unwrap());
+        let fido = Individual::Named(NamedNode::new("http://example.org/fido").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::class(dog.clone()),
+            fido.clone(),
+        ));
+        // bob has no asserted type
+        ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+            property: knows,
+            source: fido.clone(),
+            target: bob.clone(),
+        });
+
+        let mut reasoner = RlReasoner::new(&ontology);
+        reasoner.classify().unwrap();
+        assert!(reasoner.is_consistent().unwrap());
+        assert!(reasoner.get_types(&bob).contains(&&thing));
+        assert!(reasoner.get_types(&fido).contains(&&thing));
+        assert!(reasoner.get_super_classes(&dog, false).contains(&&thing));
+        assert!(reasoner.get_instances(&thing, false).contains(&&bob));
+
+        // Asserting an individual into owl:Nothing is inconsistent
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::class(nothing.clone()),
+            bob.clone(),
+        ));
+        let mut reasoner = RlReasoner::new(&ontology);
+        match reasoner.classify() {
+            Err(OwlError::Inconsistent(e)) => {
+                assert!(e.to_string().contains("owl:Nothing"), "unexpected error: {e}")
+            }
+            other => panic!("Expected an inconsistency, got {other:?}"),
+        }
+        assert!(!reasoner.is_consistent().unwrap());
+        assert_eq!(reasoner.stats().rule_applications(RlRule::ClsNothing2), 1);
+
+        // So is an instance of a class that is a subclass of owl:Nothing
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(Axiom::subclass_of(
+            ClassExpression::class(dog.clone()),
+            ClassExpression::class(nothing),
+        ));
+        ontology.add_axiom(Axiom::class_assertion(ClassExpression::class(dog), fido));
+        let mut reasoner = RlReasoner::new(&ontology);
+        assert!(matches!(reasoner.classify(), Err(OwlError::Inconsistent(_))));
+    }
+
+    #[test]
+    fn test_inconsistency_explanation() {
+        let a = OwlClass::new(NamedNode::new("http://example.org/A").unwrap());
+        let b = OwlClass::new(NamedNode::new("http://example.org/B").unwrap());
+        let c = OwlClass::new(NamedNode::new("http://example.org/C").unwrap());
+        let x = Individual::Named(NamedNode::new("http://example.org/x").unwrap());
+        let y = Individual::Named(NamedNode::new("http://example.org/y").unwrap());
+
+        let x_a = Axiom::class_assertion(ClassExpression::class(a.clone()), x.clone());
+        let x_b = Axiom::class_assertion(ClassExpression::class(b.clone()), x.clone());
+        let a_disjoint_b = Axiom::DisjointClasses(vec![
+            ClassExpression::class(a.clone()),
+            ClassExpression::class(b.clone()),
+        ]);
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(x_a.clone());
+        ontology.add_axiom(x_b.clone());
+        ontology.add_axiom(a_disjoint_b.clone());
+        // Not involved in the clash
+        ontology.add_axiom(Axiom::class_assertion(ClassExpression::class(c), y));
+
+        let config = ReasonerConfig {
+            track_provenance: true,
+            ..Default::default()
+        };
+        let mut reasoner = RlReasoner::with_config(&ontology, config.clone());
+        match reasoner.classify() {
+            Err(OwlError::Inconsistent(e)) => {
+                assert!(e.to_string().contains("disjoint"), "unexpected error: {e}")
+            }
+            other => panic!("Expected an inconsistency, got {other:?}"),
+        }
+        let explanation = reasoner.inconsistency_explanation().unwrap();
+        assert_eq!(explanation.len(), 3, "unexpected explanation: {explanation:?}");
+        assert!(explanation.contains(&x_a));
+        assert!(explanation.contains(&x_b));
+        assert!(explanation.contains(&a_disjoint_b));
+
+        // Without provenance tracking the inconsistency is still detected but not explained
+        let mut reasoner = RlReasoner::new(&ontology);
+        assert!(matches!(reasoner.classify(), Err(OwlError::Inconsistent(_))));
+        assert!(reasoner.inconsistency_explanation().is_none());
+
+        // Consistent ontologies have no explanation
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(x_a);
+        ontology.add_axiom(a_disjoint_b);
+        let mut reasoner = RlReasoner::with_config(&ontology, config);
+        reasoner.classify().unwrap();
+        assert!(reasoner.inconsistency_explanation().is_none());
+    }
+
+    #[test]
+    fn test_reasoner_stats() {
+        let mut ontology = Ontology::new(None);
+
+        let ancestor_of =
+            ObjectProperty::new(NamedNode::new("http://example.org/ancestorOf").unwrap());
+        let descendant_of =
+            ObjectProperty::new(NamedNode::new("http://example.org/descendantOf").unwrap());
+        let person = OwlClass::new(NamedNode::new("http://example.org/Person").unwrap());
+        let agent = OwlClass::new(NamedNode::new("http://example.org/Agent").unwrap());
+
+        let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+        let charlie = Individual::Named(NamedNode::new("http://example.org/charlie").unwrap());
+
+        ontology.add_axiom(Axiom::TransitiveObjectProperty(ancestor_of.clone()));
+        ontology.add_axiom(Axiom::InverseObjectProperties(
+            ancestor_of.clone(),
+            descendant_of,
+        ));
+        ontology.add_axiom(Axiom::subclass_of(
+            ClassExpression::class(person.clone()),
+            ClassExpression::class(agent),
+        ));
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::class(person),
+            alice.clone(),
+        ));
+        ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+            property: ancestor_of.clone(),
+            source: alice,
+            target: bob.clone(),
+        });
+        ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+            property: ancestor_of,
+            source: bob,
+            target: charlie,
+        });
+
+        let mut reasoner = RlReasoner::new(&ontology);
+        assert_eq!(reasoner.stats(), ReasonerStats::default());
+        reasoner.classify().unwrap();
+
+        let stats = reasoner.stats();
+        assert!(stats.fixpoint_reached());
+        assert!(stats.iterations() > 0);
+        // alice ancestorOf charlie
+        assert_eq!(stats.rule_applications(RlRule::PrpTrp), 1);
+        // The three ancestorOf assertions are mirrored by descendantOf
+        assert_eq!(stats.rule_applications(RlRule::PrpInv1), 3);
+        assert_eq!(stats.rule_applications(RlRule::PrpInv2), 0);
+        // alice is an Agent
+        assert_eq!(stats.rule_applications(RlRule::CaxSco), 1);
+        // Person and Agent are subclasses of owl:Thing, the individuals are instances of it
+        assert_eq!(stats.rule_applications(RlRule::ScmCls), 2);
+        assert_eq!(stats.rule_applications(RlRule::ClsThing), 3);
+        assert_eq!(stats.inferred_axioms(), 10);
+        assert_eq!(
+            stats.rules().collect::<Vec<_>>(),
+            [
+                (RlRule::CaxSco, 1),
+                (RlRule::PrpTrp, 1),
+                (RlRule::PrpInv1, 3),
+                (RlRule::ClsThing, 3),
+                (RlRule::ScmCls, 2)
+            ]
+        );
+
+        // Stopping on a limit does not reach the fixpoint
+        let config = ReasonerConfig {
+            max_inferred_axioms: Some(1),
+            ..Default::default()
+        };
+        let mut reasoner = RlReasoner::with_config(&ontology, config);
+        reasoner.classify().unwrap_err();
+        assert!(!reasoner.stats().fixpoint_reached());
+    }
+
+    #[test]
+    fn test_add_axioms() {
+        let class = |name: &str| {
+            OwlClass::new(NamedNode::new(format!("http://example.org/{name}")).unwrap())
+        };
+        let subclass_of = |sub: &OwlClass, sup: &OwlClass| {
+            Axiom::subclass_of(
+                ClassExpression::class(sub.clone()),
+                ClassExpression::class(sup.clone()),
+            )
+        };
+        let class_a = class("A");
+        let class_b = class("B");
+        let class_c = class("C");
+        let class_d = class("D");
+        let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+
+        // A subClassOf B subClassOf C, alice : A, bob : D
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(subclass_of(&class_a, &class_b));
+        ontology.add_axiom(subclass_of(&class_b, &class_c));
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::class(class_a.clone()),
+            alice,
+        ));
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::class(class_d.clone()),
+            bob.clone(),
+        ));
+
+        let mut reasoner = RlReasoner::new(&ontology);
+        reasoner.classify().unwrap();
+        let before = reasoner.stats();
+        assert!(!reasoner.get_types(&bob).contains(&&class_c));
+
+        // D subClassOf A makes D subClassOf B and C, and bob an A, B and C
+        let new_edge = subclass_of(&class_d, &class_a);
+        reasoner.add_axioms(std::slice::from_ref(&new_edge)).unwrap();
+        let after = reasoner.stats();
+        assert_eq!(after.inferred_axioms() - before.inferred_axioms(), 5);
+        assert_eq!(
+            after.rule_applications(RlRule::ScmSco) - before.rule_applications(RlRule::ScmSco),
+            2
+        );
+        assert_eq!(
+            after.rule_applications(RlRule::CaxSco) - before.rule_applications(RlRule::CaxSco),
+            3
+        );
+        assert!(after.fixpoint_reached());
+        assert!(after.iterations() > before.iterations());
+        assert!(reasoner.is_complete());
+        assert!(reasoner.get_super_classes(&class_d, false).contains(&&class_c));
+        assert!(reasoner.get_types(&bob).contains(&&class_c));
+
+        // The result is the same as classifying everything from scratch
+        let mut full_ontology = ontology.clone();
+        full_ontology.add_axiom(new_edge);
+        let mut full_reasoner = RlReasoner::new(&full_ontology);
+        full_reasoner.classify().unwrap();
+        let full = full_reasoner.stats();
+        assert_eq!(full.inferred_axioms(), after.inferred_axioms());
+        assert!(full.rules().eq(after.rules()));
+        assert_eq!(
+            full_reasoner.get_inferred_axioms().len(),
+            reasoner.get_inferred_axioms().len()
+        );
+    }
+
+    #[test]
+    fn test_sub_property_and_property_chain_inference() {
+        let property = |name: &str| {
+            ObjectProperty::new(NamedNode::new(format!("http://example.org/{name}")).unwrap())
+        };
+        let has_mother = property("hasMother");
+        let has_parent = property("hasParent");
+        let has_brother = property("hasBrother");
+        let has_uncle = property("hasUncle");
+        let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+        let carol = Individual::Named(NamedNode::new("http://example.org/carol").unwrap());
+        let assertion = |property: &ObjectProperty, source: &Individual, target: &Individual| {
+            Axiom::ObjectPropertyAssertion {
+                property: property.clone(),
+                source: source.clone(),
+                target: target.clone(),
+            }
+        };
+        let chain = Axiom::SubPropertyChainOf {
+            property_chain: vec![
+                ObjectPropertyExpression::ObjectProperty(has_parent.clone()),
+                ObjectPropertyExpression::ObjectProperty(has_brother.clone()),
+            ],
+            super_property: has_uncle.clone(),
+        };
+
+        // hasMother subPropertyOf hasParent, hasParent o hasBrother subPropertyOf hasUncle
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(Axiom::SubObjectPropertyOf {
+            sub_property: ObjectPropertyExpression::ObjectProperty(has_mother.clone()),
+            super_property: ObjectPropertyExpression::ObjectProperty(has_parent.clone()),
+        });
+        ontology.add_axiom(chain.clone());
+        ontology.add_axiom(assertion(&has_mother, &alice, &bob));
+        ontology.add_axiom(assertion(&has_brother, &bob, &carol));
+
+        let config = ReasonerConfig {
+            track_provenance: true,
+            ..Default::default()
+        };
+        let mut reasoner = RlReasoner::with_config(&ontology, config);
+        reasoner.classify().unwrap();
+
+        let stats = reasoner.stats();
+        assert_eq!(stats.rule_applications(RlRule::PrpSpo1), 1);
+        assert_eq!(stats.rule_applications(RlRule::PrpSpo2), 1);
+
+        // alice hasUncle carol through the inferred alice hasParent bob
+        let justifications = reasoner
+            .justifications(&assertion(&has_uncle, &alice, &carol))
+            .unwrap();
+        assert_eq!(justifications[0].rule(), Some(RlRule::PrpSpo2));
+        assert_eq!(
+            justifications[0].axioms(),
+            [
+                chain,
+                assertion(&has_parent, &alice, &bob),
+                assertion(&has_brother, &bob, &carol),
+            ]
+        );
+        let justifications = reasoner
+            .justifications(&assertion(&has_parent, &alice, &bob))
+            .unwrap();
+        assert_eq!(justifications[0].rule(), Some(RlRule::PrpSpo1));
+    }
+
+    #[test]
+    fn test_max_cardinality() {
+        let person = OwlClass::new(NamedNode::new("http://example.org/Person").unwrap());
+        let has_spouse =
+            ObjectProperty::new(NamedNode::new("http://example.org/hasSpouse").unwrap());
+        let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+        let robert = Individual::Named(NamedNode::new("http://example.org/robert").unwrap());
+
+        // A person has at most one spouse and alice has two
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(Axiom::subclass_of(
+            ClassExpression::class(person.clone()),
+            ClassExpression::ObjectMaxCardinality {
+                cardinality: 1,
+                property: ObjectPropertyExpression::ObjectProperty(has_spouse.clone()),
+                filler: None,
+            },
+        ));
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::class(person),
+            alice.clone(),
+        ));
+        for spouse in [&bob, &robert] {
+            ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+                property: has_spouse.clone(),
+                source: alice.clone(),
+                target: spouse.clone(),
+            });
+        }
+
+        // The two spouses are the same person (cls-maxc2)
+        let mut reasoner = RlReasoner::new(&ontology);
+        reasoner.classify().unwrap();
+        assert!(reasoner.is_consistent().unwrap());
+        assert_eq!(reasoner.get_same_individuals(&bob), [&robert]);
+        assert_eq!(reasoner.get_same_individuals(&robert), [&bob]);
+        assert!(reasoner.get_same_individuals(&alice).is_empty());
+        assert_eq!(reasoner.stats().rule_applications(RlRule::ClsMaxc2), 1);
+
+        // They can't be the same if they are different
+        ontology.add_axiom(Axiom::DifferentIndividuals(vec![bob.clone(), robert]));
+        let mut reasoner = RlReasoner::new(&ontology);
+        assert!(matches!(reasoner.classify(), Err(OwlError::Inconsistent(_))));
+        assert!(!reasoner.is_consistent().unwrap());
+
+        // With a maximum cardinality of 0 a single spouse is already a clash (cls-maxc1)
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::ObjectMaxCardinality {
+                cardinality: 0,
+                property: ObjectPropertyExpression::ObjectProperty(has_spouse.clone()),
+                filler: None,
+            },
+            alice.clone(),
+        ));
+        ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+            property: has_spouse,
+            source: alice,
+            target: bob,
+        });
+        let mut reasoner = RlReasoner::new(&ontology);
+        assert!(matches!(reasoner.classify(), Err(OwlError::Inconsistent(_))));
+    }
+
+    #[test]
+    fn test_same_as_classes() {
+        let has_spouse =
+            ObjectProperty::new(NamedNode::new("http://example.org/hasSpouse").unwrap());
+        let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+        let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+        let robert = Individual::Named(NamedNode::new("http://example.org/robert").unwrap());
+        let bobby = Individual::Anonymous(oxrdf::BlankNode::new("bobby").unwrap());
+        let carol = Individual::Named(NamedNode::new("http://example.org/carol").unwrap());
+        let caroline = Individual::Named(NamedNode::new("http://example.org/caroline").unwrap());
+        let dave = Individual::Named(NamedNode::new("http://example.org/dave").unwrap());
+
+        // alice has at most one spouse, robert and bobby, who is also asserted to be bob
+        let mut ontology = Ontology::new(None);
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::ObjectMaxCardinality {
+                cardinality: 1,
+                property: ObjectPropertyExpression::ObjectProperty(has_spouse.clone()),
+                filler: None,
+            },
+            alice.clone(),
+        ));
+        for spouse in [&robert, &bobby] {
+            ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+                property: has_spouse.clone(),
+                source: alice.clone(),
+                target: spouse.clone(),
+            });
+        }
+        ontology.add_axiom(Axiom::SameIndividual(vec![bobby.clone(), bob.clone()]));
+        ontology.add_axiom(Axiom::SameIndividual(vec![caroline.clone(), carol.clone()]));
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::class(OwlClass::new(
+                NamedNode::new("http://example.org/Person").unwrap(),
+            )),
+            dave.clone(),
+        ));
+
+        let expected = vec![
+            vec![bob.clone(), robert.clone(), bobby.clone()],
+            vec![carol.clone(), caroline.clone()],
+        ];
+        // The partition does not depend on the run
+        for _ in 0..3 {
+            let mut reasoner = RlReasoner::new(&ontology);
+            reasoner.classify().unwrap();
+            assert_eq!(reasoner.same_as_classes(), expected);
+            for class in &expected {
+                for individual in class {
+                    assert_eq!(reasoner.canonical_individual(individual), &class[0]);
+                }
+            }
+            assert_eq!(reasoner.canonical_individual(&dave), &dave);
+            assert_eq!(reasoner.canonical_individual(&alice), &alice);
+        }
+    }
 }
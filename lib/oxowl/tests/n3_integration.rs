@@ -1,9 +1,14 @@
 //! Integration tests for N3 and OWL interoperability.
 
-use oxowl::n3_integration::{parse_n3_ontology, parse_n3_ontology_with_config};
+use oxowl::n3_integration::{
+    parse_n3_ontology, parse_n3_ontology_with_config, parse_n3_ontology_with_rules,
+};
 use oxowl::n3_rules::{extend_ontology_with_n3_rules, N3Rule, N3RuleExtractor};
-use oxowl::{parse_ontology_from_n3, Axiom, ClassExpression, Individual, Ontology, ParserConfig};
-use oxrdf::{BlankNode, Formula, NamedNode, Quad, Triple};
+use oxowl::{
+    parse_ontology_from_n3, Axiom, ClassExpression, Individual, ObjectProperty,
+    ObjectPropertyExpression, Ontology, ParseErrorKind, ParserConfig, Reasoner, RlReasoner,
+};
+use oxrdf::{BlankNode, Formula, GraphName, NamedNode, Quad, Triple};
 use oxrdf::vocab::rdf;
 
 #[test]
@@ -212,7 +217,7 @@ fn test_n3_rule_subclass_pattern() {
     assert!(rule.is_owl_compatible());
 
     // Should generate a SubClassOf axiom
-    let axioms = rule.to_owl_axioms();
+    let axioms = rule.to_owl_axioms().unwrap();
     assert_eq!(axioms.len(), 1);
 
     match &axioms[0] {
@@ -237,11 +242,135 @@ fn test_extend_ontology_with_rules() {
     let initial_count = ontology.axiom_count();
 
     // Empty quads should not add any axioms
-    let added = extend_ontology_with_n3_rules(&mut ontology, &[]);
+    let added = extend_ontology_with_n3_rules(&mut ontology, &[]).unwrap();
     assert_eq!(added, 0);
     assert_eq!(ontology.axiom_count(), initial_count);
 }
 
+/// Builds the quads of the rule `{ antecedent } log:implies { consequent }`.
+fn n3_rule_quads(antecedent: Vec<Triple>, consequent: Vec<Triple>) -> Vec<Quad> {
+    let antecedent = Formula::new(BlankNode::default(), antecedent);
+    let consequent = Formula::new(BlankNode::default(), consequent);
+    let mut quads = antecedent.to_quads();
+    quads.extend(consequent.to_quads());
+    quads.push(Quad::new(
+        antecedent.id().clone(),
+        NamedNode::new("http://www.w3.org/2000/10/swap/log#implies").unwrap(),
+        consequent.id().clone(),
+        GraphName::DefaultGraph,
+    ));
+    quads
+}
+
+#[test]
+fn test_n3_property_chain_rule_drives_reasoning() {
+    let has_parent = NamedNode::new("http://example.org/hasParent").unwrap();
+    let has_brother = NamedNode::new("http://example.org/hasBrother").unwrap();
+    let has_uncle = NamedNode::new("http://example.org/hasUncle").unwrap();
+    let x = BlankNode::new("x").unwrap();
+    let y = BlankNode::new("y").unwrap();
+    let z = BlankNode::new("z").unwrap();
+
+    // { ?x :hasParent ?y . ?y :hasBrother ?z } => { ?x :hasUncle ?z }
+    let quads = n3_rule_quads(
+        vec![
+            Triple::new(x.clone(), has_parent.clone(), y.clone()),
+            Triple::new(y, has_brother.clone(), z.clone()),
+        ],
+        vec![Triple::new(x, has_uncle.clone(), z)],
+    );
+
+    let mut ontology = Ontology::new(None);
+    assert_eq!(
+        extend_ontology_with_n3_rules(&mut ontology, &quads).unwrap(),
+        1
+    );
+    assert!(ontology.axioms().contains(&Axiom::SubPropertyChainOf {
+        property_chain: vec![
+            ObjectPropertyExpression::ObjectProperty(ObjectProperty::new(has_parent.clone())),
+            ObjectPropertyExpression::ObjectProperty(ObjectProperty::new(has_brother.clone())),
+        ],
+        super_property: ObjectProperty::new(has_uncle.clone()),
+    }));
+
+    let alice = Individual::Named(NamedNode::new("http://example.org/alice").unwrap());
+    let bob = Individual::Named(NamedNode::new("http://example.org/bob").unwrap());
+    let carol = Individual::Named(NamedNode::new("http://example.org/carol").unwrap());
+    ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+        property: ObjectProperty::new(has_parent),
+        source: alice.clone(),
+        target: bob.clone(),
+    });
+    ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+        property: ObjectProperty::new(has_brother),
+        source: bob,
+        target: carol.clone(),
+    });
+
+    let mut reasoner = RlReasoner::new(&ontology);
+    reasoner.classify().unwrap();
+    assert!(reasoner
+        .get_inferred_axioms()
+        .contains(&Axiom::ObjectPropertyAssertion {
+            property: ObjectProperty::new(has_uncle),
+            source: alice,
+            target: carol,
+        }));
+}
+
+#[test]
+fn test_n3_unsupported_rule_is_reported() {
+    let knows = NamedNode::new("http://example.org/knows").unwrap();
+    let person = NamedNode::new("http://example.org/Person").unwrap();
+    let x = BlankNode::new("x").unwrap();
+    let y = BlankNode::new("y").unwrap();
+
+    // { ?x :knows ?y } => { ?y a :Person } has no OWL 2 RL rule equivalent here
+    let quads = n3_rule_quads(
+        vec![Triple::new(x, knows, y.clone())],
+        vec![Triple::new(y, rdf::TYPE, person)],
+    );
+
+    let mut ontology = Ontology::new(None);
+    let error = extend_ontology_with_n3_rules(&mut ontology, &quads).unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::UnknownConstruct);
+    assert_eq!(ontology.axiom_count(), 0);
+}
+
+#[test]
+fn test_parse_n3_ontology_reports_unconverted_rules() {
+    let n3_data = "
+@prefix ex: <http://example.org/> .
+
+{ ?x ex:hasParent ?y . ?y ex:hasBrother ?z } => { ?x ex:hasUncle ?z } .
+{ ?x ex:hasParent ?y } => { ?y a ex:Parent } .
+{ ?x ex:age ?a } => { ?x a ex:Adult } .
+";
+
+    let (ontology, unconverted) =
+        parse_n3_ontology_with_rules(n3_data.as_bytes(), ParserConfig::new()).unwrap();
+
+    // The property chain rule is converted
+    let has_uncle = ObjectProperty::new(NamedNode::new("http://example.org/hasUncle").unwrap());
+    assert!(ontology.axioms().iter().any(|axiom| matches!(
+        axiom,
+        Axiom::SubPropertyChainOf { super_property, .. } if super_property == &has_uncle
+    )));
+
+    // The other rules are reported with their triples and the reason
+    assert_eq!(unconverted.len(), 2);
+    for rule in &unconverted {
+        assert_eq!(rule.body().len(), 1);
+        assert_eq!(rule.head().len(), 1);
+        assert_eq!(rule.head()[0].predicate, rdf::TYPE);
+        assert!(rule.reason.contains("does not match any OWL 2 RL pattern"));
+    }
+
+    // The plain parsing functions keep working and include the converted rules
+    let ontology = parse_n3_ontology(n3_data.as_bytes()).unwrap();
+    assert_eq!(ontology.axiom_count(), 1);
+}
+
 #[test]
 fn test_complex_ontology_with_multiple_axiom_types() {
     let n3_data = r#"
@@ -126,7 +126,7 @@ fn test_n3_to_owl_conversion_limited() {
     // This simple pattern CAN be converted to OWL
     assert!(rule.is_subclass_pattern());
 
-    let axioms = rule.to_owl_axioms();
+    let axioms = rule.to_owl_axioms().unwrap();
     assert_eq!(axioms.len(), 1);
 
     match &axioms[0] {
@@ -363,15 +363,20 @@ fn test_owl_rl_iteration_limit_enforcement() {
         max_iterations: 10,  // Very low limit
         check_consistency: true,
         materialize: true,
+        ..Default::default()
     };
 
     let mut reasoner = RlReasoner::with_config(&ontology, config);
 
-    // Reasoning should still succeed but may not compute full closure
+    // Reasoning terminates but may not compute the full closure
     let result = reasoner.classify();
 
-    // The reasoner respects max_iterations
-    assert!(result.is_ok(), "Reasoner should respect iteration limit gracefully");
+    // The reasoner respects max_iterations and reports when it stopped early
+    assert_eq!(
+        result.is_ok(),
+        reasoner.is_complete(),
+        "Reasoner should only succeed if it reached a fixpoint"
+    );
 
     println!("✓ OWL RL respects max_iterations limit");
     println!("  Hierarchy depth: {}", classes.len());
@@ -383,12 +388,14 @@ fn test_owl_rl_iteration_limit_enforcement() {
         max_iterations: 100_000,  // Default limit
         check_consistency: true,
         materialize: true,
+        ..Default::default()
     };
 
     let mut reasoner2 = RlReasoner::with_config(&ontology, config2);
     let result2 = reasoner2.classify();
 
     assert!(result2.is_ok());
+    assert!(reasoner2.is_complete());
 
     println!("✓ OWL RL completes successfully with sufficient iterations");
 }
@@ -422,6 +429,7 @@ fn test_owl_rl_max_iterations_prevents_runaway() {
         max_iterations: 1000,
         check_consistency: true,
         materialize: true,
+        ..Default::default()
     };
 
     let mut reasoner = RlReasoner::with_config(&ontology, config);
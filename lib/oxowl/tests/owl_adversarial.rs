@@ -188,6 +188,7 @@ fn owl_iteration_limit_enforced() {
         max_iterations: 100_000,
         check_consistency: true,
         materialize: true,
+        ..Default::default()
     };
 
     let mut reasoner = RlReasoner::with_config(&ontology, config);
@@ -260,6 +261,7 @@ fn owl_class_explosion_bounded() {
         max_iterations: 100_000,
         check_consistency: true,
         materialize: false, // Don't materialize to avoid memory explosion
+        ..Default::default()
     };
 
     let mut reasoner = RlReasoner::with_config(&ontology, config);
@@ -429,6 +431,7 @@ fn owl_memory_growth_bounded() {
             max_iterations: 100_000,
             check_consistency: true,
             materialize: false, // Don't materialize to keep memory bounded
+            ..Default::default()
         };
 
         let mut reasoner = RlReasoner::with_config(&ontology, config);
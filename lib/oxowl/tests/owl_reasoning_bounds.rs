@@ -8,29 +8,25 @@
 //! 2. No timeout enforcement
 //! 3. No memory limits
 //! 4. No profile validation (accepts non-RL ontologies)
-//! 5. No entailment explanation
 //!
 //! Run with: cargo test -p oxowl owl_reasoning_bounds
 
 #![cfg(feature = "reasoner-rl")]
 
 use oxowl::{
-    Axiom, ClassExpression, Individual, ObjectProperty, Ontology, OwlClass, Reasoner,
-    ReasonerConfig, RlReasoner,
+    Axiom, ClassExpression, Individual, ObjectProperty, Ontology, OwlClass, OwlError, ProfileViolation,
+    Reasoner, ReasonerConfig, ReasoningErrorKind, RlReasoner, RlRule,
 };
 use oxrdf::NamedNode;
 use std::time::{Duration, Instant};
 
-/// Test 1: BLOCKER - Iteration limit hit silently
+/// Test 1: Iteration limit detection
 ///
 /// EXPECTED BEHAVIOR: When iteration limit is reached, reasoner should:
 /// - Return an error indicating incomplete reasoning
-/// - OR set a flag indicating results may be incomplete
-/// - OR log a warning
+/// - AND report `is_complete() == false`
 ///
-/// ACTUAL BEHAVIOR: Returns Ok(()) with incomplete results (SILENT FAILURE)
-///
-/// IMPACT: Operators cannot trust completeness of reasoning results
+/// IMPACT: Operators cannot trust completeness of reasoning results otherwise
 #[test]
 fn test_owl_reasoning_iteration_limit_detected() {
     println!("\n=== Test 1: Iteration Limit Detection ===");
@@ -88,6 +84,7 @@ fn test_owl_reasoning_iteration_limit_detected() {
         max_iterations: 10, // Very low to force limit hit
         check_consistency: true,
         materialize: true,
+        ..Default::default()
     };
 
     let mut reasoner = RlReasoner::with_config(&ontology, config);
@@ -135,6 +132,14 @@ fn test_owl_reasoning_iteration_limit_detected() {
             // Good! Reasoner properly reported the issue
             println!("✓ PASS: Reasoner correctly reported iteration limit");
             println!("  - Error: {:?}", e);
+            assert!(
+                matches!(
+                    &e,
+                    OwlError::Reasoning(e) if e.kind() == ReasoningErrorKind::MaxIterationsExceeded
+                ),
+                "Expected a max iterations error, got {e:?}"
+            );
+            assert!(!reasoner.is_complete(), "Reasoner should be flagged as incomplete");
         }
     }
 }
@@ -156,6 +161,7 @@ fn test_owl_reasoning_timeout_enforcement() {
         materialize: true,
         // Attempt to set timeout - this will fail to compile if field doesn't exist
         // timeout: Duration::from_secs(5),
+        ..Default::default()
     };
 
     println!("ReasonerConfig fields:");
@@ -214,12 +220,10 @@ fn test_owl_reasoning_timeout_enforcement() {
     );
 }
 
-/// Test 3: BLOCKER - No memory limits
-///
-/// EXPECTED BEHAVIOR: Reasoner should monitor memory and stop if limit exceeded
-/// ACTUAL BEHAVIOR: No memory monitoring or limits (RESOURCE EXHAUSTION RISK)
+/// Test 3: Memory bounds via `max_inferred_axioms`
 ///
-/// IMPACT: Malicious ontologies can cause OOM, crash the system
+/// EXPECTED BEHAVIOR: Reasoner stops with an error once the rules derived more
+/// axioms than allowed, before the full closure is built in memory
 #[test]
 fn test_owl_memory_bounded() {
     println!("\n=== Test 3: Memory Bounds ===");
@@ -255,59 +259,65 @@ fn test_owl_memory_bounded() {
         })
         .collect();
 
-    // Assert each individual is of each class (500 * 100 = 50,000 assertions)
-    for individual in &individuals {
-        for class in &classes {
-            ontology.add_axiom(Axiom::class_assertion(
-                ClassExpression::class(class.clone()),
-                individual.clone(),
-            ));
-        }
+    // Assert each individual is of one class, the reasoner infers all the others
+    // (500 * 100 = 50,000 class assertions)
+    for (i, individual) in individuals.iter().enumerate() {
+        ontology.add_axiom(Axiom::class_assertion(
+            ClassExpression::class(classes[i % classes.len()].clone()),
+            individual.clone(),
+        ));
     }
 
     println!("Created memory-intensive ontology:");
     println!("  - {} equivalent classes", classes.len());
     println!("  - {} individuals", individuals.len());
-    println!("  - {} initial assertions", individuals.len() * classes.len());
     println!(
         "  - Expected inferred axioms: >{}",
         individuals.len() * classes.len()
     );
 
+    let limit = 20_000;
     let config = ReasonerConfig {
         max_iterations: 100_000,
         check_consistency: true,
         materialize: true,
-        // Attempt to set memory limit - field doesn't exist
-        // max_memory_mb: 100,
+        max_inferred_axioms: Some(limit),
+        ..Default::default()
     };
 
-    println!("\n❌ BLOCKER CONFIRMED: No memory limits!");
-    println!("  - ReasonerConfig has no max_memory field");
-    println!("  - No memory monitoring during reasoning");
-    println!("  - Risk of OOM on large ontologies");
-
     let mut reasoner = RlReasoner::with_config(&ontology, config);
-    let _result = reasoner.classify();
+    match reasoner.classify() {
+        Err(OwlError::Reasoning(e)) => {
+            println!("✓ PASS: Reasoning stopped: {e}");
+            assert_eq!(e.kind(), ReasoningErrorKind::AxiomLimitExceeded);
+        }
+        other => panic!("Expected an axiom limit error, got {other:?}"),
+    }
 
-    let inferred = reasoner.get_inferred_axioms();
-    println!("\nReasoning completed:");
-    println!("  - Inferred axioms: {}", inferred.len());
-    println!("  - No memory limit was enforced");
+    // The limit is checked while types are propagated, not after the fact
+    let materialized_types: usize = individuals
+        .iter()
+        .map(|individual| reasoner.get_types(individual).len())
+        .sum();
+    println!("  - Materialized class assertions: {materialized_types}");
+    assert!(materialized_types < individuals.len() * classes.len());
+    assert!(reasoner.get_inferred_axioms().is_empty());
 
-    // This test documents the blocker
-    panic!(
-        "BLOCKER: No memory limits! \
-        Reasoner should have configurable memory bounds to prevent OOM"
-    );
+    // Without the limit the full closure is computed
+    let mut reasoner = RlReasoner::new(&ontology);
+    reasoner.classify().unwrap();
+    let materialized_types: usize = individuals
+        .iter()
+        .map(|individual| reasoner.get_types(individual).len())
+        .sum();
+    // Every individual is also an instance of owl:Thing
+    assert_eq!(materialized_types, individuals.len() * (classes.len() + 1));
 }
 
-/// Test 4: BLOCKER - No OWL 2 RL profile validation
+/// Test 4: OWL 2 RL profile validation
 ///
-/// EXPECTED BEHAVIOR: Reasoner should validate ontology is OWL 2 RL compliant
-/// ACTUAL BEHAVIOR: Accepts any ontology, silently ignores non-RL features
-///
-/// IMPACT: Operators get incomplete reasoning without knowing why
+/// EXPECTED BEHAVIOR: Non-RL axioms are reported by `Ontology::validate_profile`
+/// and rejected by the reasoner when `reject_non_rl` is set
 #[test]
 fn test_owl_rl_profile_enforcement() {
     println!("\n=== Test 4: OWL 2 RL Profile Validation ===");
@@ -359,30 +369,39 @@ fn test_owl_rl_profile_enforcement() {
     println!("  - Complement (¬) in subclass position");
     println!("\nThese features are NOT in OWL 2 RL profile!");
 
-    let mut reasoner = RlReasoner::new(&ontology);
-    let result = reasoner.classify();
+    let violations = ontology.validate_profile();
+    for violation in &violations {
+        println!("  - {violation}");
+    }
+    let reasons = violations
+        .iter()
+        .map(ProfileViolation::reason)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        reasons,
+        [
+            "ObjectAllValuesFrom is not allowed in subclass position",
+            "ObjectSomeValuesFrom is not allowed in superclass position",
+            "ObjectComplementOf is not allowed in subclass position",
+        ]
+    );
+    assert_eq!(violations[0].axiom(), &ontology.axioms()[0]);
 
-    match result {
-        Ok(()) => {
-            println!("\n❌ BLOCKER CONFIRMED: No profile validation!");
-            println!("  - Reasoner accepted non-RL ontology");
-            println!("  - Returned: Ok(())");
-            println!("  - No warning about unsupported features");
-            println!("  - Silently ignores non-RL axioms");
-            println!("\nIMPACT:");
-            println!("  - Operators get incomplete reasoning");
-            println!("  - No indication why inferences are missing");
-            println!("  - Cannot trust completeness");
+    // By default the non-RL axioms are ignored
+    let mut reasoner = RlReasoner::new(&ontology);
+    reasoner.classify().unwrap();
 
-            panic!(
-                "BLOCKER: No OWL 2 RL profile validation! \
-                Reasoner should reject or warn about non-RL features"
-            );
-        }
-        Err(e) => {
-            println!("✓ PASS: Reasoner correctly rejected non-RL ontology");
-            println!("  - Error: {:?}", e);
+    let config = ReasonerConfig {
+        reject_non_rl: true,
+        ..Default::default()
+    };
+    let mut reasoner = RlReasoner::with_config(&ontology, config);
+    match reasoner.classify() {
+        Err(OwlError::Reasoning(e)) => {
+            println!("✓ PASS: Reasoner correctly rejected non-RL ontology: {e}");
+            assert_eq!(e.kind(), ReasoningErrorKind::UnsupportedConstruct);
         }
+        other => panic!("Expected an unsupported construct error, got {other:?}"),
     }
 }
 
@@ -494,7 +513,7 @@ fn test_owl_class_hierarchy_explosion_detected() {
     }
 }
 
-/// Test 6: FEATURE GAP - No entailment explanation
+/// Test 6: Entailment explanation
 ///
 /// Tests whether reasoner can explain why a conclusion was derived
 #[test]
@@ -508,18 +527,21 @@ fn test_owl_entailment_explanation() {
     let fido = Individual::Named(NamedNode::new("http://example.org/fido").unwrap());
 
     // Dog ⊑ Animal
-    ontology.add_axiom(Axiom::subclass_of(
+    let dog_sub_animal = Axiom::subclass_of(
         ClassExpression::class(dog.clone()),
         ClassExpression::class(animal.clone()),
-    ));
+    );
+    ontology.add_axiom(dog_sub_animal.clone());
 
     // fido : Dog
-    ontology.add_axiom(Axiom::class_assertion(
-        ClassExpression::class(dog.clone()),
-        fido.clone(),
-    ));
+    let fido_dog = Axiom::class_assertion(ClassExpression::class(dog.clone()), fido.clone());
+    ontology.add_axiom(fido_dog.clone());
 
-    let mut reasoner = RlReasoner::new(&ontology);
+    let config = ReasonerConfig {
+        track_provenance: true,
+        ..Default::default()
+    };
+    let mut reasoner = RlReasoner::with_config(&ontology, config);
     reasoner.classify().unwrap();
 
     // fido should be inferred as Animal
@@ -529,23 +551,43 @@ fn test_owl_entailment_explanation() {
     println!("Inference verified: fido is Animal");
     println!("\nAttempting to get explanation:");
 
-    // Try to get explanation - this method doesn't exist!
-    // let explanation = reasoner.explain_entailment(&fido, &animal);
-
-    println!("\n❌ FEATURE GAP CONFIRMED: No entailment explanation!");
-    println!("  - Reasoner has no explain_entailment() method");
-    println!("  - Cannot trace derivation paths");
-    println!("  - Operators cannot debug reasoning");
-    println!("\nRECOMMENDATION:");
-    println!("  - Add explain_entailment() API");
-    println!("  - Track provenance during inference");
-    println!("  - Return justification sets");
-
-    // This documents the feature gap
-    panic!(
-        "FEATURE GAP: No entailment explanation! \
-        Add explain_entailment() for debugging support"
+    let fido_animal = Axiom::class_assertion(ClassExpression::class(animal.clone()), fido.clone());
+    let justifications = reasoner
+        .justifications(&fido_animal)
+        .expect("fido : Animal should have a justification");
+    assert_eq!(justifications.len(), 1);
+    let justification = &justifications[0];
+    println!(
+        "  - {} applied to {:?}",
+        justification.rule().unwrap(),
+        justification.axioms()
     );
+    assert_eq!(justification.rule(), Some(RlRule::CaxSco));
+    assert_eq!(justification.rule().unwrap().name(), "cax-sco");
+    assert!(justification.axioms().contains(&dog_sub_animal));
+    assert!(justification.axioms().contains(&fido_dog));
+    assert_eq!(justification.axioms().len(), 2);
+
+    assert_eq!(
+        reasoner.explain_entailment(&fido_animal),
+        Some(vec![vec![fido_dog.clone(), dog_sub_animal]])
+    );
+
+    // Asserted axioms justify themselves
+    let asserted = reasoner.justifications(&fido_dog).unwrap();
+    assert_eq!(asserted[0].rule(), None);
+    assert_eq!(asserted[0].axioms(), [fido_dog]);
+
+    // Non-entailed axioms have no justification
+    let cat = OwlClass::new(NamedNode::new("http://example.org/Cat").unwrap());
+    assert!(reasoner
+        .explain_entailment(&Axiom::class_assertion(ClassExpression::class(cat), fido.clone()))
+        .is_none());
+
+    // Without provenance tracking no explanation is available
+    let mut untracked = RlReasoner::new(&ontology);
+    untracked.classify().unwrap();
+    assert!(untracked.explain_entailment(&fido_animal).is_none());
 }
 
 /// Test 7: Configuration bounds validation
@@ -555,47 +597,62 @@ fn test_owl_entailment_explanation() {
 fn test_configuration_validation() {
     println!("\n=== Test 7: Configuration Validation ===");
 
+    let mut ontology = Ontology::new(None);
+    let dog = OwlClass::new(NamedNode::new("http://example.org/Dog").unwrap());
+    let animal = OwlClass::new(NamedNode::new("http://example.org/Animal").unwrap());
+    ontology.add_axiom(Axiom::subclass_of(
+        ClassExpression::class(dog),
+        ClassExpression::class(animal),
+    ));
+
     // Test with zero max_iterations (invalid)
     let config = ReasonerConfig {
         max_iterations: 0,
         check_consistency: true,
         materialize: true,
+        ..Default::default()
     };
 
-    let ontology = Ontology::new(None);
-    let mut reasoner = RlReasoner::with_config(&ontology, config);
-
     println!("Testing with max_iterations = 0");
 
+    let mut reasoner = RlReasoner::with_config(&ontology, config);
     match reasoner.classify() {
-        Ok(()) => {
-            println!("\n⚠ WARNING: Accepted invalid configuration!");
-            println!("  - max_iterations = 0 should be rejected");
-            println!("  - No validation performed");
-        }
-        Err(e) => {
+        Err(OwlError::Reasoning(e)) => {
             println!("✓ PASS: Rejected invalid configuration");
-            println!("  - Error: {:?}", e);
+            println!("  - Error: {e}");
+            assert_eq!(e.kind(), ReasoningErrorKind::InvalidConfiguration);
+            assert!(e.to_string().contains("at least one fixpoint iteration"));
         }
+        other => panic!("max_iterations = 0 should be rejected, got {other:?}"),
     }
+    assert!(reasoner.get_inferred_axioms().is_empty());
 
     // Test with extreme max_iterations
     let config = ReasonerConfig {
         max_iterations: usize::MAX,
         check_consistency: true,
         materialize: true,
+        ..Default::default()
     };
 
     println!("\nTesting with max_iterations = usize::MAX");
-    println!("  - This could lead to infinite-like loops");
-    println!("  - Should have reasonable upper bound");
 
     let mut reasoner = RlReasoner::with_config(&ontology, config);
-    let _result = reasoner.classify();
+    match reasoner.classify() {
+        Err(OwlError::Reasoning(e)) => {
+            println!("✓ PASS: Rejected unbounded configuration");
+            println!("  - Error: {e}");
+            assert_eq!(e.kind(), ReasoningErrorKind::InvalidConfiguration);
+        }
+        other => panic!("max_iterations = usize::MAX should be rejected, got {other:?}"),
+    }
 
-    println!("\n⚠ Configuration validation is minimal");
-    println!("  - No bounds checking on max_iterations");
-    println!("  - Could set to 0 or usize::MAX");
+    // The smallest valid budget is accepted
+    let config = ReasonerConfig {
+        max_iterations: 1,
+        ..Default::default()
+    };
+    config.validate().unwrap();
 }
 
 /// Summary test that documents all blockers
@@ -0,0 +1,99 @@
+//! Integration tests for SWRL rule import.
+
+use oxowl::swrl_rules::{extend_ontology_with_swrl_rules, SwrlRuleExtractor};
+use oxowl::{Axiom, Individual, ObjectProperty, Ontology, Reasoner, RlReasoner};
+use oxrdf::{Graph, NamedNode};
+use oxttl::TurtleParser;
+
+/// hasUncle(?x, ?z) :- hasParent(?x, ?y), hasBrother(?y, ?z)
+const UNCLE_RULE: &str = "
+@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+@prefix swrl: <http://www.w3.org/2003/11/swrl#> .
+@prefix ex: <http://example.org/> .
+
+ex:x a swrl:Variable .
+ex:y a swrl:Variable .
+ex:z a swrl:Variable .
+
+ex:uncleRule a swrl:Imp ;
+    swrl:body (
+        [ a swrl:IndividualPropertyAtom ;
+          swrl:propertyPredicate ex:hasParent ;
+          swrl:argument1 ex:x ;
+          swrl:argument2 ex:y ]
+        [ a swrl:IndividualPropertyAtom ;
+          swrl:propertyPredicate ex:hasBrother ;
+          swrl:argument1 ex:y ;
+          swrl:argument2 ex:z ]
+    ) ;
+    swrl:head (
+        [ a swrl:IndividualPropertyAtom ;
+          swrl:propertyPredicate ex:hasUncle ;
+          swrl:argument1 ex:x ;
+          swrl:argument2 ex:z ]
+    ) .
+";
+
+fn parse_turtle(data: &str) -> Graph {
+    TurtleParser::new()
+        .for_slice(data.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+fn property(name: &str) -> ObjectProperty {
+    ObjectProperty::new(NamedNode::new(format!("http://example.org/{name}")).unwrap())
+}
+
+fn individual(name: &str) -> Individual {
+    Individual::Named(NamedNode::new(format!("http://example.org/{name}")).unwrap())
+}
+
+#[test]
+fn test_swrl_rule_is_a_property_chain() {
+    let graph = parse_turtle(UNCLE_RULE);
+
+    let rules = SwrlRuleExtractor::new(&graph).extract_rules().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert!(rules[0].is_property_chain_pattern());
+}
+
+#[test]
+fn test_swrl_uncle_rule_drives_reasoning() {
+    let graph = parse_turtle(UNCLE_RULE);
+
+    let mut ontology = Ontology::new(None);
+    assert_eq!(
+        extend_ontology_with_swrl_rules(&mut ontology, &graph).unwrap(),
+        1
+    );
+    ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+        property: property("hasParent"),
+        source: individual("alice"),
+        target: individual("bob"),
+    });
+    ontology.add_axiom(Axiom::ObjectPropertyAssertion {
+        property: property("hasBrother"),
+        source: individual("bob"),
+        target: individual("carol"),
+    });
+
+    let mut reasoner = RlReasoner::new(&ontology);
+    reasoner.classify().unwrap();
+
+    // alice hasParent bob, bob hasBrother carol => alice hasUncle carol
+    assert!(reasoner
+        .get_inferred_axioms()
+        .contains(&Axiom::ObjectPropertyAssertion {
+            property: property("hasUncle"),
+            source: individual("alice"),
+            target: individual("carol"),
+        }));
+    assert!(!reasoner
+        .get_inferred_axioms()
+        .contains(&Axiom::ObjectPropertyAssertion {
+            property: property("hasUncle"),
+            source: individual("bob"),
+            target: individual("carol"),
+        }));
+}
@@ -38,6 +38,8 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::mem::take;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// An in-memory [RDF dataset](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset).
 ///
@@ -764,12 +766,45 @@ impl Dataset {
     ///     See [the RDFC specification section about it](https://www.w3.org/TR/rdf-canon/#dataset-poisoning).
     /// </div>
     pub fn canonicalize(&mut self, algorithm: CanonicalizationAlgorithm) {
-        let bnode_mapping = self.canonicalize_interned_blank_nodes(algorithm);
+        self.try_canonicalize(&Canonicalizer::new(algorithm))
+            .unwrap_or_else(|_| unreachable!("canonicalization without budget never fails"))
+    }
+
+    /// Canonicalizes the dataset by renaming blank nodes with the given [`Canonicalizer`].
+    ///
+    /// Contrary to [`canonicalize`](Self::canonicalize), it fails instead of running for a very long time
+    /// if the canonicalizer has a budget that the dataset exceeds.
+    /// The dataset is left unchanged on failure.
+    ///
+    /// ```
+    /// use oxrdf::dataset::{CanonicalizationAlgorithm, Canonicalizer};
+    /// use oxrdf::*;
+    ///
+    /// let p = NamedNodeRef::new("http://example.com/p")?;
+    /// let bnodes = (0..10).map(|_| BlankNode::default()).collect::<Vec<_>>();
+    /// let mut dataset = Dataset::new();
+    /// for s in &bnodes {
+    ///     for o in &bnodes {
+    ///         dataset.insert(QuadRef::new(s, p, o, GraphNameRef::DefaultGraph));
+    ///     }
+    /// }
+    ///
+    /// let canonicalizer =
+    ///     Canonicalizer::new(CanonicalizationAlgorithm::Unstable).with_max_permutations(1000);
+    /// assert!(dataset.try_canonicalize(&canonicalizer).is_err());
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn try_canonicalize(
+        &mut self,
+        canonicalizer: &Canonicalizer,
+    ) -> Result<(), CanonicalizationError> {
+        let bnode_mapping = self.canonicalize_interned_blank_nodes(canonicalizer)?;
         let new_quads = self.map_blank_nodes(&bnode_mapping);
         self.clear();
         for quad in new_quads {
             self.insert_encoded(quad);
         }
+        Ok(())
     }
 
     /// Returns a map between the current dataset blank node and the canonicalized blank node
@@ -780,7 +815,8 @@ impl Dataset {
         &self,
         algorithm: CanonicalizationAlgorithm,
     ) -> HashMap<BlankNodeRef<'_>, BlankNode> {
-        self.canonicalize_interned_blank_nodes(algorithm)
+        self.canonicalize_interned_blank_nodes(&Canonicalizer::new(algorithm))
+            .unwrap_or_else(|_| unreachable!("canonicalization without budget never fails"))
             .into_iter()
             .map(|(from, to)| (from.decode_from(&self.interner), to))
             .collect()
@@ -788,9 +824,9 @@ impl Dataset {
 
     fn canonicalize_interned_blank_nodes(
         &self,
-        algorithm: CanonicalizationAlgorithm,
-    ) -> HashMap<InternedBlankNode, BlankNode> {
-        let hash_algorithm = match algorithm {
+        canonicalizer: &Canonicalizer,
+    ) -> Result<HashMap<InternedBlankNode, BlankNode>, CanonicalizationError> {
+        let hash_algorithm = match canonicalizer.algorithm {
             CanonicalizationAlgorithm::Unstable => None,
             #[cfg(feature = "rdfc-10")]
             CanonicalizationAlgorithm::Rdfc10 { hash_algorithm } => Some(hash_algorithm),
@@ -802,6 +838,10 @@ impl Dataset {
             hash_to_blank_nodes_map: BTreeMap::new(),
             canonical_issuer: IdentifierIssuer::new("c14n"),
         };
+        let mut budget = CanonicalizationBudget {
+            remaining_permutations: canonicalizer.max_permutations,
+            cancellation_token: canonicalizer.cancellation_token.as_ref(),
+        };
         // 2)
         for quad in &self.spog {
             if let InternedNamedOrBlankNode::BlankNode(bnode) = quad.0 {
@@ -868,6 +908,7 @@ impl Dataset {
             .collect::<BTreeMap<_, _>>();
         // 5)
         for (_, identifier_list) in take(&mut canonicalization_state.hash_to_blank_nodes_map) {
+            budget.check_cancellation()?;
             // 5.1)
             let mut hash_path_list = Vec::new();
             // 5.2)
@@ -890,7 +931,8 @@ impl Dataset {
                     n,
                     &temporary_issuer,
                     hash_algorithm,
-                ))
+                    &mut budget,
+                )?)
             }
             // 5.3)
             hash_path_list.sort_unstable_by(|(_, hl), (_, hr)| hl.cmp(hr));
@@ -905,9 +947,9 @@ impl Dataset {
             }
         }
         // 6)
-        canonicalization_state
+        Ok(canonicalization_state
             .canonical_issuer
-            .issued_identifier_map
+            .issued_identifier_map)
     }
 
     #[cfg(feature = "rdf-12")]
@@ -1139,7 +1181,9 @@ impl Dataset {
         identifier: InternedBlankNode,
         issuer: &IdentifierIssuer,
         hash_algorithm: Option<CanonicalizationHashAlgorithm>,
-    ) -> (IdentifierIssuer, String) {
+        budget: &mut CanonicalizationBudget<'_>,
+    ) -> Result<(IdentifierIssuer, String), CanonicalizationError> {
+        budget.check_cancellation()?;
         let mut issuer = issuer.clone();
         // 1)
         let mut h_n = BTreeMap::<_, HashSet<_>>::new();
@@ -1208,7 +1252,10 @@ impl Dataset {
             // 5.3)
             let mut chosen_issuer = IdentifierIssuer::new("");
             // 5.4)
+            // We check the budget before generating the permutations that might not fit in memory
+            budget.consume_permutations(blank_node_list.len())?;
             'perm: for p in generate_permutations(blank_node_list) {
+                budget.check_cancellation()?;
                 // 5.4.1)
                 let mut issuer_copy = issuer.clone();
                 // 5.4.2)
@@ -1252,7 +1299,8 @@ impl Dataset {
                         related,
                         &issuer_copy,
                         hash_algorithm,
-                    );
+                        budget,
+                    )?;
                     // 5.4.5.2)
                     let id = Self::issue_identifier(&mut issuer_copy, related);
                     path.push_str("_:");
@@ -1283,7 +1331,7 @@ impl Dataset {
             issuer = chosen_issuer;
         }
         // 6)
-        (issuer, Self::hash_function(&data_to_hash, hash_algorithm))
+        Ok((issuer, Self::hash_function(&data_to_hash, hash_algorithm)))
     }
 
     #[cfg(feature = "rdf-12")]
@@ -2147,6 +2195,144 @@ pub enum CanonicalizationHashAlgorithm {
     Sha384,
 }
 
+/// Canonicalizes graphs and datasets with an optional bound on the amount of work done.
+///
+/// The canonicalization worst-case complexity is exponential with respect to the number of blank nodes
+/// (see [the RDFC specification section about it](https://www.w3.org/TR/rdf-canon/#dataset-poisoning)).
+/// Setting a budget with [`with_max_permutations`](Self::with_max_permutations) allows to reject
+/// malicious inputs instead of running for a very long time,
+/// and [`with_cancellation_token`](Self::with_cancellation_token) allows to abort a long canonicalization.
+///
+/// See [`Dataset::try_canonicalize`] and [`Graph::try_canonicalize`].
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct Canonicalizer {
+    algorithm: CanonicalizationAlgorithm,
+    max_permutations: Option<usize>,
+    cancellation_token: Option<CancellationToken>,
+}
+
+impl Canonicalizer {
+    /// Builds a canonicalizer using the given algorithm and without budget.
+    pub fn new(algorithm: CanonicalizationAlgorithm) -> Self {
+        Self {
+            algorithm,
+            max_permutations: None,
+            cancellation_token: None,
+        }
+    }
+
+    /// Sets the maximal number of blank node permutations the [Hash N-Degree Quads](https://www.w3.org/TR/rdf-canon/#hash-nd-quads)
+    /// algorithm is allowed to explore during a canonicalization.
+    ///
+    /// Inputs with only a few blank nodes sharing the same neighborhood need very few permutations.
+    pub fn with_max_permutations(mut self, max_permutations: usize) -> Self {
+        self.max_permutations = Some(max_permutations);
+        self
+    }
+
+    /// Aborts the canonicalization with [`CanonicalizationError::Cancelled`] when the token is cancelled.
+    ///
+    /// ```
+    /// use oxrdf::dataset::{
+    ///     CancellationToken, CanonicalizationAlgorithm, CanonicalizationError, Canonicalizer,
+    /// };
+    /// use oxrdf::*;
+    ///
+    /// let p = NamedNodeRef::new("http://example.com/p")?;
+    /// let bnodes = (0..10).map(|_| BlankNode::default()).collect::<Vec<_>>();
+    /// let mut dataset = Dataset::new();
+    /// for s in &bnodes {
+    ///     for o in &bnodes {
+    ///         dataset.insert(QuadRef::new(s, p, o, GraphNameRef::DefaultGraph));
+    ///     }
+    /// }
+    ///
+    /// let cancellation_token = CancellationToken::new();
+    /// let canonicalizer = Canonicalizer::new(CanonicalizationAlgorithm::Unstable)
+    ///     .with_cancellation_token(cancellation_token.clone());
+    /// cancellation_token.cancel(); // We cancel
+    /// assert_eq!(
+    ///     dataset.try_canonicalize(&canonicalizer),
+    ///     Err(CanonicalizationError::Cancelled)
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+}
+
+/// A token that can be used to abort a canonicalization from another thread.
+///
+/// To cancel run [`CancellationToken::cancel`] and to check if the token is cancelled run [`CancellationToken::is_cancelled`].
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    value: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn cancel(&self) {
+        self.value.store(true, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+/// An error raised during [`Canonicalizer`] execution.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum CanonicalizationError {
+    /// The input requires more work than allowed by the canonicalizer budget.
+    #[error("The canonicalization requires more blank node permutations than allowed")]
+    BudgetExceeded,
+    /// The canonicalization has been cancelled with a [`CancellationToken`].
+    #[error("The canonicalization has been cancelled")]
+    Cancelled,
+}
+
+/// The work a canonicalization is still allowed to do.
+struct CanonicalizationBudget<'a> {
+    remaining_permutations: Option<usize>,
+    cancellation_token: Option<&'a CancellationToken>,
+}
+
+impl CanonicalizationBudget<'_> {
+    fn check_cancellation(&self) -> Result<(), CanonicalizationError> {
+        if self
+            .cancellation_token
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(CanonicalizationError::Cancelled);
+        }
+        Ok(())
+    }
+
+    /// Consumes the permutations of a list of blank nodes.
+    fn consume_permutations(
+        &mut self,
+        blank_node_count: usize,
+    ) -> Result<(), CanonicalizationError> {
+        if let Some(remaining_permutations) = &mut self.remaining_permutations {
+            *remaining_permutations = (1..=blank_node_count)
+                .try_fold(1_usize, usize::checked_mul)
+                .and_then(|count| remaining_permutations.checked_sub(count))
+                .ok_or(CanonicalizationError::BudgetExceeded)?;
+        }
+        Ok(())
+    }
+}
+
 /// A RDFC [canonicalization state](https://www.w3.org/TR/rdf-canon/#canon-state)
 struct CanonicalizationState<'a> {
     blank_node_to_quads_map: QuadsPerBlankNode<'a>,
@@ -2174,21 +2360,49 @@ impl IdentifierIssuer {
     }
 }
 
-fn generate_permutations<T: Copy>(items: impl IntoIterator<Item = T>) -> Vec<Vec<T>> {
-    let mut current_output = vec![Vec::new()];
-    for (i, next) in items.into_iter().enumerate() {
-        let mut new_output = Vec::with_capacity(current_output.len() * (i + 1));
-        for mut permutation in current_output {
-            permutation.push(next);
-            for j in 0..=i {
-                let mut new_permutation = permutation.clone();
-                new_permutation.swap(i, j);
-                new_output.push(new_permutation);
+fn generate_permutations<T: Copy>(items: impl IntoIterator<Item = T>) -> Permutations<T> {
+    let items = items.into_iter().collect::<Vec<_>>();
+    Permutations {
+        swaps: Some(vec![0; items.len()]),
+        items,
+    }
+}
+
+/// Lazily generates the permutations of a list.
+///
+/// They are not all allocated at once because their number grows factorially.
+struct Permutations<T> {
+    items: Vec<T>,
+    /// The permutation is built by pushing each item `i` then swapping it with the item at position `swaps[i] <= i`
+    swaps: Option<Vec<usize>>,
+}
+
+impl<T: Copy> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let swaps = self.swaps.as_mut()?;
+        let mut permutation = Vec::with_capacity(self.items.len());
+        for (i, (item, j)) in self.items.iter().zip(swaps.iter()).enumerate() {
+            permutation.push(*item);
+            permutation.swap(i, *j);
+        }
+        // We move to the next swaps, the last one changing the fastest
+        let mut i = swaps.len();
+        loop {
+            if i == 0 {
+                self.swaps = None;
+                break;
             }
+            i -= 1;
+            if swaps[i] < i {
+                swaps[i] += 1;
+                break;
+            }
+            swaps[i] = 0;
         }
-        current_output = new_output;
+        Some(permutation)
     }
-    current_output
 }
 
 #[cfg(feature = "rdfc-10")]
@@ -2228,4 +2442,96 @@ mod tests {
         expected.insert(QuadRef::new(&c14n3, &p, &c14n0, GraphNameRef::DefaultGraph));
         assert_eq!(dataset, expected);
     }
+
+    #[test]
+    fn test_canonicalization_budget() {
+        let p = NamedNode::new_unchecked("http://example.com/#p");
+        let bnodes = (0..50)
+            .map(|i| BlankNode::new_unchecked(format!("b{i}")))
+            .collect::<Vec<_>>();
+        let mut dataset = Dataset::new();
+        for (i, s) in bnodes.iter().enumerate() {
+            for (j, o) in bnodes.iter().enumerate() {
+                if i != j && (i + j) % 3 == 0 {
+                    dataset.insert(QuadRef::new(s, &p, o, GraphNameRef::DefaultGraph));
+                }
+            }
+        }
+        let original = dataset.clone();
+        let canonicalizer = Canonicalizer::new(CanonicalizationAlgorithm::Rdfc10 {
+            hash_algorithm: CanonicalizationHashAlgorithm::Sha256,
+        })
+        .with_max_permutations(10_000);
+        assert_eq!(
+            dataset.try_canonicalize(&canonicalizer),
+            Err(CanonicalizationError::BudgetExceeded)
+        );
+        assert_eq!(dataset, original);
+
+        // Small inputs fit in the budget
+        let mut dataset = Dataset::new();
+        dataset.insert(QuadRef::new(&p, &p, &bnodes[0], GraphNameRef::DefaultGraph));
+        dataset.insert(QuadRef::new(&p, &p, &bnodes[1], GraphNameRef::DefaultGraph));
+        dataset.insert(QuadRef::new(
+            &bnodes[0],
+            &p,
+            &bnodes[1],
+            GraphNameRef::DefaultGraph,
+        ));
+        let mut expected = dataset.clone();
+        dataset.try_canonicalize(&canonicalizer).unwrap();
+        expected.canonicalize(CanonicalizationAlgorithm::Rdfc10 {
+            hash_algorithm: CanonicalizationHashAlgorithm::Sha256,
+        });
+        assert_eq!(dataset, expected);
+    }
+
+    #[test]
+    fn test_canonicalization_cancellation() {
+        let p = NamedNode::new_unchecked("http://example.com/#p");
+        let bnodes = (0..50)
+            .map(|i| BlankNode::new_unchecked(format!("b{i}")))
+            .collect::<Vec<_>>();
+        let mut dataset = Dataset::new();
+        for (i, s) in bnodes.iter().enumerate() {
+            for (j, o) in bnodes.iter().enumerate() {
+                if i != j && (i + j) % 3 == 0 {
+                    dataset.insert(QuadRef::new(s, &p, o, GraphNameRef::DefaultGraph));
+                }
+            }
+        }
+        let cancellation_token = CancellationToken::new();
+        let canonicalizer = Canonicalizer::new(CanonicalizationAlgorithm::Rdfc10 {
+            hash_algorithm: CanonicalizationHashAlgorithm::Sha256,
+        })
+        .with_cancellation_token(cancellation_token.clone());
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            cancellation_token.cancel();
+        });
+        assert_eq!(
+            dataset.try_canonicalize(&canonicalizer),
+            Err(CanonicalizationError::Cancelled)
+        );
+        canceller.join().unwrap();
+    }
+
+    #[test]
+    fn test_generate_permutations() {
+        assert_eq!(
+            generate_permutations([1, 2, 3]).collect::<Vec<_>>(),
+            [
+                [3, 1, 2],
+                [2, 3, 1],
+                [2, 1, 3],
+                [3, 2, 1],
+                [1, 3, 2],
+                [1, 2, 3]
+            ]
+        );
+        assert_eq!(
+            generate_permutations(Vec::<u8>::new()).collect::<Vec<_>>(),
+            [Vec::<u8>::new()]
+        );
+    }
 }
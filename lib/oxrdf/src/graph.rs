@@ -26,7 +26,10 @@
 //! See also [`Dataset`] if you want to get support of multiple RDF graphs at the same time.
 
 use crate::dataset::*;
-pub use crate::dataset::{CanonicalizationAlgorithm, CanonicalizationHashAlgorithm};
+pub use crate::dataset::{
+    CancellationToken, CanonicalizationAlgorithm, CanonicalizationError,
+    CanonicalizationHashAlgorithm, Canonicalizer,
+};
 use crate::*;
 use std::fmt;
 
@@ -386,6 +389,33 @@ impl Graph {
     pub fn canonicalize(&mut self, algorithm: CanonicalizationAlgorithm) {
         self.dataset.canonicalize(algorithm)
     }
+
+    /// Canonicalizes the graph by renaming blank nodes with the given [`Canonicalizer`].
+    ///
+    /// Contrary to [`canonicalize`](Self::canonicalize), it fails instead of running for a very long time
+    /// if the canonicalizer has a budget that the graph exceeds.
+    /// The graph is left unchanged on failure.
+    ///
+    /// ```
+    /// use oxrdf::graph::{CanonicalizationAlgorithm, Canonicalizer};
+    /// use oxrdf::*;
+    ///
+    /// let iri = NamedNodeRef::new("http://example.com")?;
+    /// let mut graph = Graph::new();
+    /// let bnode = BlankNode::default();
+    /// graph.insert(TripleRef::new(iri, iri, &bnode));
+    ///
+    /// let canonicalizer =
+    ///     Canonicalizer::new(CanonicalizationAlgorithm::Unstable).with_max_permutations(1000);
+    /// graph.try_canonicalize(&canonicalizer)?;
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn try_canonicalize(
+        &mut self,
+        canonicalizer: &Canonicalizer,
+    ) -> Result<(), CanonicalizationError> {
+        self.dataset.try_canonicalize(canonicalizer)
+    }
 }
 
 impl PartialEq for Graph {
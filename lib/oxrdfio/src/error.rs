@@ -54,7 +54,7 @@ impl From<RdfParseError> for io::Error {
 
 /// An error in the syntax of the parsed file.
 #[derive(Debug, thiserror::Error)]
-pub struct RdfSyntaxError(SyntaxErrorKind);
+pub struct RdfSyntaxError(Box<SyntaxErrorKind>);
 
 /// An error in the syntax of the parsed file.
 #[derive(Debug, thiserror::Error)]
@@ -73,7 +73,7 @@ impl RdfSyntaxError {
     /// The location of the error inside of the file.
     #[inline]
     pub fn location(&self) -> Option<Range<TextPosition>> {
-        match &self.0 {
+        match &*self.0 {
             SyntaxErrorKind::JsonLd(e) => {
                 let location = e.location()?;
                 Some(
@@ -122,7 +122,7 @@ impl RdfSyntaxError {
     /// Get additional context about what was expected, if available.
     #[inline]
     pub fn expected(&self) -> Option<&str> {
-        match &self.0 {
+        match &*self.0 {
             SyntaxErrorKind::Turtle(e) => e.expected(),
             _ => None,
         }
@@ -131,7 +131,7 @@ impl RdfSyntaxError {
     /// Get additional context about what was found, if available.
     #[inline]
     pub fn found(&self) -> Option<&str> {
-        match &self.0 {
+        match &*self.0 {
             SyntaxErrorKind::Turtle(e) => e.found(),
             _ => None,
         }
@@ -140,14 +140,14 @@ impl RdfSyntaxError {
     /// Get a suggestion for fixing the error, if available.
     #[inline]
     pub fn suggestion(&self) -> Option<&str> {
-        match &self.0 {
+        match &*self.0 {
             SyntaxErrorKind::Turtle(e) => e.suggestion(),
             _ => None,
         }
     }
 
     pub(crate) fn msg(msg: &'static str) -> Self {
-        Self(SyntaxErrorKind::Msg(msg))
+        Self(Box::new(SyntaxErrorKind::Msg(msg)))
     }
 }
 
@@ -161,28 +161,28 @@ impl std::fmt::Display for RdfSyntaxError {
 impl From<oxjsonld::JsonLdSyntaxError> for RdfSyntaxError {
     #[inline]
     fn from(error: oxjsonld::JsonLdSyntaxError) -> Self {
-        Self(SyntaxErrorKind::JsonLd(error))
+        Self(Box::new(SyntaxErrorKind::JsonLd(error)))
     }
 }
 
 impl From<oxttl::TurtleSyntaxError> for RdfSyntaxError {
     #[inline]
     fn from(error: oxttl::TurtleSyntaxError) -> Self {
-        Self(SyntaxErrorKind::Turtle(error))
+        Self(Box::new(SyntaxErrorKind::Turtle(error)))
     }
 }
 
 impl From<oxrdfxml::RdfXmlSyntaxError> for RdfSyntaxError {
     #[inline]
     fn from(error: oxrdfxml::RdfXmlSyntaxError) -> Self {
-        Self(SyntaxErrorKind::RdfXml(error))
+        Self(Box::new(SyntaxErrorKind::RdfXml(error)))
     }
 }
 
 impl From<RdfSyntaxError> for io::Error {
     #[inline]
     fn from(error: RdfSyntaxError) -> Self {
-        match error.0 {
+        match *error.0 {
             SyntaxErrorKind::JsonLd(error) => error.into(),
             SyntaxErrorKind::Turtle(error) => error.into(),
             SyntaxErrorKind::RdfXml(error) => error.into(),
@@ -4,6 +4,11 @@ use std::fmt;
 /// RDF serialization formats.
 ///
 /// This enumeration is non exhaustive. New formats might be added in the future.
+///
+/// With the `rdf-12` feature, [N-Triples](Self::NTriples), [N-Quads](Self::NQuads), [Turtle](Self::Turtle) and [TriG](Self::TriG)
+/// read and write [RDF 1.2](https://www.w3.org/TR/rdf12-concepts/) triple terms (`<<( ex:s ex:p ex:o )>>`), including nested ones.
+/// The Turtle and TriG reifying triples (`<< ex:s ex:p ex:o >>`) and annotations (`{| ex:p ex:o |}`) are parsed into `rdf:reifies` triples.
+/// There is no separate "star" format: RDF 1.2 is supported by the regular formats.
 #[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
 #[non_exhaustive]
 pub enum RdfFormat {
@@ -254,6 +254,40 @@ impl RdfParser {
         self.lenient()
     }
 
+    /// Sets the maximum nesting depth of the parsed structures.
+    ///
+    /// It bounds blank node property lists, collections and quoted triples in Turtle and TriG
+    /// and JSON objects and arrays in JSON-LD.
+    /// Input nested more deeply is rejected with a syntax error instead of exhausting the stack.
+    /// It has no effect on the other formats.
+    ///
+    /// The defaults are 100 levels for Turtle and TriG and 1024 levels for JSON-LD,
+    /// where every JSON object and array counts as a level.
+    ///
+    /// ```
+    /// use oxrdfio::{RdfFormat, RdfParser};
+    ///
+    /// let file = "<http://example.com/s> <http://example.com/p> [ <http://example.com/p> [ <http://example.com/p> <http://example.com/o> ] ] .";
+    ///
+    /// let result = RdfParser::from_format(RdfFormat::Turtle)
+    ///     .with_max_nesting_depth(1)
+    ///     .for_reader(file.as_bytes())
+    ///     .collect::<Result<Vec<_>, _>>();
+    /// assert!(result.is_err());
+    /// ```
+    #[inline]
+    pub fn with_max_nesting_depth(mut self, max_depth: usize) -> Self {
+        self.inner = match self.inner {
+            RdfParserKind::JsonLd(p, f) => {
+                RdfParserKind::JsonLd(p.with_max_nesting_depth(max_depth), f)
+            }
+            RdfParserKind::TriG(p) => RdfParserKind::TriG(p.with_max_nesting_depth(max_depth)),
+            RdfParserKind::Turtle(p) => RdfParserKind::Turtle(p.with_max_nesting_depth(max_depth)),
+            inner => inner,
+        };
+        self
+    }
+
     /// Parses from a [`Read`] implementation and returns an iterator of quads.
     ///
     /// Reads are buffered.
@@ -4,7 +4,8 @@ use crate::format::RdfFormat;
 #[cfg(feature = "async-tokio")]
 use oxjsonld::TokioAsyncWriterJsonLdSerializer;
 use oxjsonld::{JsonLdProfile, JsonLdSerializer, WriterJsonLdSerializer};
-use oxrdf::{GraphNameRef, IriParseError, QuadRef, TripleRef};
+use oxrdf::dataset::Canonicalizer;
+use oxrdf::{Dataset, GraphNameRef, IriParseError, Quad, QuadRef, TripleRef};
 #[cfg(feature = "async-tokio")]
 use oxrdfxml::TokioAsyncWriterRdfXmlSerializer;
 use oxrdfxml::{RdfXmlSerializer, WriterRdfXmlSerializer};
@@ -56,6 +57,7 @@ use tokio::io::AsyncWrite;
 #[derive(Clone)]
 pub struct RdfSerializer {
     inner: RdfSerializerKind,
+    canonicalization: Option<Canonicalizer>,
 }
 
 #[derive(Clone)]
@@ -83,6 +85,7 @@ impl RdfSerializer {
                 RdfFormat::TriG => RdfSerializerKind::TriG(TriGSerializer::new()),
                 RdfFormat::Turtle => RdfSerializerKind::Turtle(TurtleSerializer::new()),
             },
+            canonicalization: None,
         }
     }
 
@@ -193,6 +196,55 @@ impl RdfSerializer {
         Ok(self)
     }
 
+    /// Labels the blank nodes from the content of the data instead of keeping their identifiers.
+    ///
+    /// The same graph or dataset is then always serialized to the same bytes,
+    /// whatever its blank node identifiers and the order of its quads are,
+    /// which is convenient for diff-based tests and content addressing.
+    ///
+    /// The quads are buffered until [`finish`](WriterQuadSerializer::finish()) is called.
+    /// Their blank nodes are then renamed using the given [`Canonicalizer`]
+    /// and they are written in a sorted order, duplicates being written only once.
+    /// Use `CanonicalizationAlgorithm::Rdfc10` (requires the `rdfc-10` feature)
+    /// for labels that do not change between versions.
+    ///
+    /// The canonicalization worst-case complexity is exponential with respect to the number of blank nodes.
+    /// Set a budget with [`Canonicalizer::with_max_permutations`] when serializing untrusted data:
+    /// [`finish`](WriterQuadSerializer::finish()) then fails with an [`io::ErrorKind::InvalidInput`] error
+    /// instead of running for a very long time.
+    ///
+    /// ```
+    /// use oxrdf::dataset::{CanonicalizationAlgorithm, Canonicalizer};
+    /// use oxrdf::{BlankNode, NamedNodeRef, TripleRef};
+    /// use oxrdfio::{RdfFormat, RdfSerializer};
+    ///
+    /// let p = NamedNodeRef::new("http://example.com/p")?;
+    /// let serialize = |triples: &[(&BlankNode, &BlankNode)]| {
+    ///     let mut serializer = RdfSerializer::from_format(RdfFormat::NTriples)
+    ///         .with_canonical_blank_nodes(
+    ///             Canonicalizer::new(CanonicalizationAlgorithm::Unstable).with_max_permutations(1000),
+    ///         )
+    ///         .for_writer(Vec::new());
+    ///     for (s, o) in triples {
+    ///         serializer.serialize_triple(TripleRef::new(*s, p, *o))?;
+    ///     }
+    ///     serializer.finish()
+    /// };
+    ///
+    /// let (a, b, c) = (BlankNode::default(), BlankNode::default(), BlankNode::default());
+    /// let (x, y, z) = (BlankNode::default(), BlankNode::default(), BlankNode::default());
+    /// assert_eq!(
+    ///     serialize(&[(&a, &b), (&b, &c)])?,
+    ///     serialize(&[(&y, &z), (&x, &y)])?
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_canonical_blank_nodes(mut self, canonicalizer: Canonicalizer) -> Self {
+        self.canonicalization = Some(canonicalizer);
+        self
+    }
+
     /// Serializes to a [`Write`] implementation.
     ///
     /// <div class="warning">
@@ -218,7 +270,11 @@ impl RdfSerializer {
     /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
     /// ```
     pub fn for_writer<W: Write>(self, writer: W) -> WriterQuadSerializer<W> {
+        let format = self.format();
         WriterQuadSerializer {
+            canonicalization: self
+                .canonicalization
+                .map(|canonicalizer| CanonicalizationBuffer::new(canonicalizer, format)),
             inner: match self.inner {
                 RdfSerializerKind::JsonLd(s) => {
                     WriterQuadSerializerKind::JsonLd(s.for_writer(writer))
@@ -273,7 +329,11 @@ impl RdfSerializer {
         self,
         writer: W,
     ) -> TokioAsyncWriterQuadSerializer<W> {
+        let format = self.format();
         TokioAsyncWriterQuadSerializer {
+            canonicalization: self
+                .canonicalization
+                .map(|canonicalizer| CanonicalizationBuffer::new(canonicalizer, format)),
             inner: match self.inner {
                 RdfSerializerKind::JsonLd(s) => {
                     TokioAsyncWriterQuadSerializerKind::JsonLd(s.for_tokio_async_writer(writer))
@@ -336,6 +396,7 @@ impl From<RdfFormat> for RdfSerializer {
 #[must_use]
 pub struct WriterQuadSerializer<W: Write> {
     inner: WriterQuadSerializerKind<W>,
+    canonicalization: Option<CanonicalizationBuffer>,
 }
 
 enum WriterQuadSerializerKind<W: Write> {
@@ -352,6 +413,13 @@ impl<W: Write> WriterQuadSerializer<W> {
     /// Serializes a [`QuadRef`]
     pub fn serialize_quad<'a>(&mut self, quad: impl Into<QuadRef<'a>>) -> io::Result<()> {
         let quad = quad.into();
+        if let Some(canonicalization) = &mut self.canonicalization {
+            return canonicalization.insert(quad);
+        }
+        self.serialize_to_inner(quad)
+    }
+
+    fn serialize_to_inner(&mut self, quad: QuadRef<'_>) -> io::Result<()> {
         match &mut self.inner {
             WriterQuadSerializerKind::JsonLd(serializer) => serializer.serialize_quad(quad),
             WriterQuadSerializerKind::N3(serializer) => {
@@ -379,7 +447,12 @@ impl<W: Write> WriterQuadSerializer<W> {
     /// Writes the last bytes of the file
     ///
     /// Note that this function does not flush the writer. You need to do that if you are using a [`BufWriter`](io::BufWriter).
-    pub fn finish(self) -> io::Result<W> {
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(canonicalization) = self.canonicalization.take() {
+            for quad in canonicalization.into_sorted_quads()? {
+                self.serialize_to_inner(quad.as_ref())?;
+            }
+        }
         Ok(match self.inner {
             WriterQuadSerializerKind::JsonLd(serializer) => serializer.finish()?,
             WriterQuadSerializerKind::N3(serializer) => serializer.finish()?,
@@ -425,6 +498,7 @@ impl<W: Write> WriterQuadSerializer<W> {
 #[cfg(feature = "async-tokio")]
 pub struct TokioAsyncWriterQuadSerializer<W: AsyncWrite + Unpin> {
     inner: TokioAsyncWriterQuadSerializerKind<W>,
+    canonicalization: Option<CanonicalizationBuffer>,
 }
 
 #[cfg(feature = "async-tokio")]
@@ -443,6 +517,13 @@ impl<W: AsyncWrite + Unpin> TokioAsyncWriterQuadSerializer<W> {
     /// Serializes a [`QuadRef`]
     pub async fn serialize_quad<'a>(&mut self, quad: impl Into<QuadRef<'a>>) -> io::Result<()> {
         let quad = quad.into();
+        if let Some(canonicalization) = &mut self.canonicalization {
+            return canonicalization.insert(quad);
+        }
+        self.serialize_to_inner(quad).await
+    }
+
+    async fn serialize_to_inner(&mut self, quad: QuadRef<'_>) -> io::Result<()> {
         match &mut self.inner {
             TokioAsyncWriterQuadSerializerKind::JsonLd(serializer) => {
                 serializer.serialize_quad(quad).await
@@ -480,7 +561,12 @@ impl<W: AsyncWrite + Unpin> TokioAsyncWriterQuadSerializer<W> {
     /// Writes the last bytes of the file
     ///
     /// Note that this function does not flush the writer. You need to do that if you are using a [`BufWriter`](io::BufWriter).
-    pub async fn finish(self) -> io::Result<W> {
+    pub async fn finish(mut self) -> io::Result<W> {
+        if let Some(canonicalization) = self.canonicalization.take() {
+            for quad in canonicalization.into_sorted_quads()? {
+                self.serialize_to_inner(quad.as_ref()).await?;
+            }
+        }
         Ok(match self.inner {
             TokioAsyncWriterQuadSerializerKind::JsonLd(serializer) => serializer.finish().await?,
             TokioAsyncWriterQuadSerializerKind::N3(serializer) => serializer.finish().await?,
@@ -493,6 +579,56 @@ impl<W: AsyncWrite + Unpin> TokioAsyncWriterQuadSerializer<W> {
     }
 }
 
+/// Buffers the quads to serialize them with canonical blank node labels.
+struct CanonicalizationBuffer {
+    canonicalizer: Canonicalizer,
+    dataset: Dataset,
+    only_triples: bool,
+}
+
+impl CanonicalizationBuffer {
+    fn new(canonicalizer: Canonicalizer, format: RdfFormat) -> Self {
+        Self {
+            canonicalizer,
+            dataset: Dataset::new(),
+            only_triples: matches!(
+                format,
+                RdfFormat::NTriples | RdfFormat::RdfXml | RdfFormat::Turtle
+            ),
+        }
+    }
+
+    fn insert(&mut self, quad: QuadRef<'_>) -> io::Result<()> {
+        if self.only_triples {
+            // Fails early like the streaming serializers
+            to_triple(quad)?;
+        }
+        self.dataset.insert(quad);
+        Ok(())
+    }
+
+    fn into_sorted_quads(mut self) -> io::Result<Vec<Quad>> {
+        self.dataset
+            .try_canonicalize(&self.canonicalizer)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let mut quads = self
+            .dataset
+            .iter()
+            .map(QuadRef::into_owned)
+            .collect::<Vec<_>>();
+        // The dataset order depends on the insertion order
+        quads.sort_by_cached_key(|quad| {
+            (
+                quad.graph_name.to_string(),
+                quad.subject.to_string(),
+                quad.predicate.to_string(),
+                quad.object.to_string(),
+            )
+        });
+        Ok(quads)
+    }
+}
+
 fn to_triple<'a>(quad: impl Into<QuadRef<'a>>) -> io::Result<TripleRef<'a>> {
     let quad = quad.into();
     if quad.graph_name.is_default_graph() {
@@ -504,3 +640,132 @@ fn to_triple<'a>(quad: impl Into<QuadRef<'a>>) -> io::Result<TripleRef<'a>> {
         ))
     }
 }
+
+#[cfg(test)]
+#[expect(clippy::panic_in_result_fn)]
+mod tests {
+    use super::*;
+    use oxrdf::dataset::CanonicalizationAlgorithm;
+    use oxrdf::{BlankNode, GraphName, Literal, NamedNode, Term};
+
+    /// Returns the same dataset twice, with different blank nodes and quad orders.
+    fn shuffled_datasets() -> (Vec<Quad>, Vec<Quad>) {
+        let ex = |name: &str| NamedNode::new_unchecked(format!("http://example.com/{name}"));
+        let build = |a: BlankNode, b: BlankNode| {
+            vec![
+                Quad::new(ex("s"), ex("p"), a.clone(), ex("g")),
+                Quad::new(a.clone(), ex("q"), b.clone(), ex("g")),
+                Quad::new(b.clone(), ex("p"), Literal::from("b"), ex("g")),
+                Quad::new(a, ex("p"), Literal::from("a"), ex("g")),
+                Quad::new(b, ex("q"), ex("o"), ex("g")),
+            ]
+        };
+        let first = build(BlankNode::default(), BlankNode::default());
+        let mut second = build(BlankNode::default(), BlankNode::default());
+        second.reverse();
+        second.swap(0, 2);
+        (first, second)
+    }
+
+    fn serialize(
+        format: RdfFormat,
+        canonicalizer: &Canonicalizer,
+        quads: &[Quad],
+    ) -> io::Result<Vec<u8>> {
+        let mut serializer = RdfSerializer::from_format(format)
+            .with_canonical_blank_nodes(canonicalizer.clone())
+            .for_writer(Vec::new());
+        for quad in quads {
+            if format.supports_datasets() {
+                serializer.serialize_quad(quad)?;
+            } else {
+                serializer.serialize_triple(quad.as_ref())?;
+            }
+        }
+        serializer.finish()
+    }
+
+    #[test]
+    fn test_canonical_blank_nodes_are_stable() -> io::Result<()> {
+        let (first, second) = shuffled_datasets();
+        for format in [
+            RdfFormat::N3,
+            RdfFormat::NQuads,
+            RdfFormat::NTriples,
+            RdfFormat::RdfXml,
+            RdfFormat::TriG,
+            RdfFormat::Turtle,
+        ] {
+            let canonicalizer = Canonicalizer::new(CanonicalizationAlgorithm::Unstable);
+            assert_eq!(
+                serialize(format, &canonicalizer, &first)?,
+                serialize(format, &canonicalizer, &second)?,
+                "{format}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_blank_nodes_are_kept_by_default() -> Result<(), Box<dyn std::error::Error>> {
+        let (quads, _) = shuffled_datasets();
+        let mut serializer = RdfSerializer::from_format(RdfFormat::NQuads).for_writer(Vec::new());
+        for quad in &quads {
+            serializer.serialize_quad(quad)?;
+        }
+        let output = String::from_utf8(serializer.finish()?)?;
+        let Term::BlankNode(blank_node) = &quads[0].object else {
+            unreachable!()
+        };
+        assert!(output.contains(&blank_node.to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonical_blank_nodes_reject_named_graphs_in_graph_formats() {
+        let (quads, _) = shuffled_datasets();
+        let mut serializer = RdfSerializer::from_format(RdfFormat::Turtle)
+            .with_canonical_blank_nodes(Canonicalizer::new(CanonicalizationAlgorithm::Unstable))
+            .for_writer(Vec::new());
+        serializer.serialize_quad(&quads[0]).unwrap_err();
+    }
+
+    #[test]
+    fn test_canonical_blank_nodes_budget() {
+        let p = NamedNode::new_unchecked("http://example.com/p");
+        let blank_nodes = (0..10).map(|_| BlankNode::default()).collect::<Vec<_>>();
+        let mut quads = Vec::new();
+        for s in &blank_nodes {
+            for o in &blank_nodes {
+                quads.push(Quad::new(
+                    s.clone(),
+                    p.clone(),
+                    o.clone(),
+                    GraphName::DefaultGraph,
+                ));
+            }
+        }
+        let canonicalizer =
+            Canonicalizer::new(CanonicalizationAlgorithm::Unstable).with_max_permutations(1000);
+        let error = serialize(RdfFormat::NQuads, &canonicalizer, &quads).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[cfg(feature = "rdfc-10")]
+    #[test]
+    fn test_rdfc10_blank_nodes() -> Result<(), Box<dyn std::error::Error>> {
+        use oxrdf::dataset::CanonicalizationHashAlgorithm;
+
+        let (first, second) = shuffled_datasets();
+        let canonicalizer = Canonicalizer::new(CanonicalizationAlgorithm::Rdfc10 {
+            hash_algorithm: CanonicalizationHashAlgorithm::Sha256,
+        });
+        let output = serialize(RdfFormat::NQuads, &canonicalizer, &first)?;
+        assert_eq!(
+            output,
+            serialize(RdfFormat::NQuads, &canonicalizer, &second)?
+        );
+        assert!(String::from_utf8(output)?.contains("_:c14n0"));
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "rdf-12")]
+
+use oxrdf::{GraphName, NamedNode, Quad, Term, Triple};
+use oxrdfio::{RdfFormat, RdfParser, RdfSerializer};
+use std::error::Error;
+
+const TURTLE: &str = r#"@prefix ex: <http://example.com/> .
+ex:s ex:p ex:o {| ex:source ex:a {| ex:date "2024-01-01" |} |} .
+<< ex:s ex:p ex:o ~ ex:r >> ex:certainty 0.9 .
+ex:alice ex:says <<( ex:bob ex:says <<( ex:s ex:p ex:o )>> )>> .
+"#;
+
+fn parse(format: RdfFormat, data: &[u8]) -> Result<Vec<Quad>, Box<dyn Error>> {
+    let mut quads = RdfParser::from_format(format)
+        .for_slice(data)
+        .collect::<Result<Vec<_>, _>>()?;
+    quads.sort_by_cached_key(ToString::to_string);
+    Ok(quads)
+}
+
+fn serialize(format: RdfFormat, quads: &[Quad]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut serializer = RdfSerializer::from_format(format)
+        .with_prefix("ex", "http://example.com/")?
+        .for_writer(Vec::new());
+    for quad in quads {
+        serializer.serialize_quad(quad)?;
+    }
+    Ok(serializer.finish()?)
+}
+
+#[test]
+fn test_parse_nested_annotations() -> Result<(), Box<dyn Error>> {
+    let quads = parse(RdfFormat::Turtle, TURTLE.as_bytes())?;
+    let ex = |name: &str| NamedNode::new_unchecked(format!("http://example.com/{name}"));
+    let asserted = Triple::new(ex("s"), ex("p"), ex("o"));
+    assert!(quads.contains(&asserted.clone().in_graph(GraphName::DefaultGraph)));
+    // Each annotation and reifying triple adds a reifier linked to its triple term with rdf:reifies
+    let reified = quads
+        .iter()
+        .filter(|q| q.predicate.as_str() == "http://www.w3.org/1999/02/22-rdf-syntax-ns#reifies")
+        .map(|q| (q.subject.clone(), q.object.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(reified.len(), 3);
+    assert!(reified.contains(&(ex("r").into(), asserted.clone().into())));
+    // The nested annotation is about the triple annotating the asserted one
+    let (annotation_reifier, _) = reified
+        .iter()
+        .find(|(r, t)| *t == Term::from(asserted.clone()) && *r != ex("r").into())
+        .ok_or("no reifier for the annotation")?;
+    let source = Triple::new(annotation_reifier.clone(), ex("source"), ex("a"));
+    assert!(quads.contains(&source.clone().in_graph(GraphName::DefaultGraph)));
+    assert!(
+        reified
+            .iter()
+            .any(|(_, t)| *t == Term::from(source.clone()))
+    );
+    let nested = Triple::new(
+        ex("bob"),
+        ex("says"),
+        Triple::new(ex("s"), ex("p"), ex("o")),
+    );
+    assert!(quads.contains(&Quad::new(
+        ex("alice"),
+        ex("says"),
+        nested,
+        GraphName::DefaultGraph
+    )));
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<(), Box<dyn Error>> {
+    let quads = parse(RdfFormat::Turtle, TURTLE.as_bytes())?;
+    for format in [
+        RdfFormat::NTriples,
+        RdfFormat::NQuads,
+        RdfFormat::Turtle,
+        RdfFormat::TriG,
+    ] {
+        let serialized = serialize(format, &quads)?;
+        assert_eq!(
+            parse(format, &serialized)?,
+            quads,
+            "Round trip failed for {format}:\n{}",
+            String::from_utf8_lossy(&serialized)
+        );
+    }
+    Ok(())
+}
@@ -26,3 +26,5 @@ pub(crate) const MIN_BUFFER_SIZE: usize = 4096;
 pub(crate) const MAX_BUFFER_SIZE: usize = 4096 * 4096;
 #[expect(clippy::decimal_literal_representation)]
 pub(crate) const MIN_PARALLEL_CHUNK_SIZE: usize = 16384;
+/// Default maximal nesting depth of blank node property lists, collections and quoted triples in Turtle and TriG.
+pub(crate) const DEFAULT_MAX_NESTING_DEPTH: usize = 100;
@@ -6,7 +6,7 @@ use crate::lexer::{N3Lexer, N3LexerMode, N3LexerOptions, N3Token, resolve_local_
 use crate::toolkit::TokioAsyncReaderIterator;
 use crate::toolkit::{
     Lexer, Parser, ReaderIterator, RuleRecognizer, RuleRecognizerError, SliceIterator,
-    TokenOrLineJump, TurtleSyntaxError,
+    TextPosition, TokenOrLineJump, TurtleSyntaxError,
 };
 use crate::{MAX_BUFFER_SIZE, MIN_BUFFER_SIZE, TurtleParseError};
 use oxiri::{Iri, IriParseError};
@@ -22,6 +22,7 @@ use std::collections::HashMap;
 use std::collections::hash_map::Iter;
 use std::fmt;
 use std::io::{self, Read, Write};
+use std::ops::Range;
 #[cfg(feature = "async-tokio")]
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 
@@ -653,6 +654,32 @@ impl<R: Read> ReaderN3Parser<R> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a quad has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a quad, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::N3Parser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/foo> a schema:Person ;
+    ///     schema:name "Foo" ."#;
+    ///
+    /// let mut parser = N3Parser::new().for_reader(file.as_bytes());
+    /// parser.next().unwrap()?; // We read the first quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 41)); // ";" as schema:Person might start a path
+    /// parser.next().unwrap()?; // We read the second quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 22)); // The final "."
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 impl<R: Read> Iterator for ReaderN3Parser<R> {
@@ -773,6 +800,35 @@ impl<R: AsyncRead + Unpin> TokioAsyncReaderN3Parser<R> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a quad has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a quad, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use oxttl::N3Parser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/foo> a schema:Person ;
+    ///     schema:name "Foo" ."#;
+    ///
+    /// let mut parser = N3Parser::new().for_tokio_async_reader(file.as_bytes());
+    /// parser.next().await.unwrap()?; // We read the first quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 41)); // ";" as schema:Person might start a path
+    /// parser.next().await.unwrap()?; // We read the second quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 22)); // The final "."
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 /// Parses a N3 file from a byte slice.
@@ -867,6 +923,32 @@ impl SliceN3Parser<'_> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a quad has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a quad, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::N3Parser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/foo> a schema:Person ;
+    ///     schema:name "Foo" ."#;
+    ///
+    /// let mut parser = N3Parser::new().for_slice(file);
+    /// parser.next().unwrap()?; // We read the first quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 41)); // ";" as schema:Person might start a path
+    /// parser.next().unwrap()?; // We read the second quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 22)); // The final "."
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 impl Iterator for SliceN3Parser<'_> {
@@ -1007,6 +1089,34 @@ impl LowLevelN3Parser {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a quad has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a quad, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::N3Parser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/foo> a schema:Person ;
+    ///     schema:name "Foo" ."#;
+    ///
+    /// let mut parser = N3Parser::new().low_level();
+    /// parser.extend_from_slice(file.as_bytes());
+    /// parser.end();
+    /// parser.parse_next().unwrap()?; // We read the first quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 41)); // ";" as schema:Person might start a path
+    /// parser.parse_next().unwrap()?; // We read the second quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 22)); // The final "."
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.parser.last_token_location()
+    }
 }
 
 #[derive(Clone)]
@@ -6,10 +6,13 @@ use crate::chunker::{get_ntriples_file_chunks, get_ntriples_slice_chunks};
 use crate::line_formats::NQuadsRecognizer;
 #[cfg(feature = "async-tokio")]
 use crate::toolkit::TokioAsyncReaderIterator;
-use crate::toolkit::{Parser, ReaderIterator, SliceIterator, TurtleParseError, TurtleSyntaxError};
+use crate::toolkit::{
+    Parser, ReaderIterator, SliceIterator, TextPosition, TurtleParseError, TurtleSyntaxError,
+};
 use oxrdf::{Triple, TripleRef};
 use std::fs::File;
 use std::io::{self, Read, Seek, SeekFrom, Take, Write};
+use std::ops::Range;
 use std::path::Path;
 #[cfg(feature = "async-tokio")]
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
@@ -340,6 +343,33 @@ pub struct ReaderNTriplesParser<R: Read> {
     inner: ReaderIterator<R, NQuadsRecognizer>,
 }
 
+impl<R: Read> ReaderNTriplesParser<R> {
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a triple has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a triple, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::NTriplesParser;
+    ///
+    /// let file = r#"<http://example.com/foo> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://schema.org/Person> .
+    /// <http://example.com/foo> <http://schema.org/name> "Foo" ."#;
+    ///
+    /// let mut parser = NTriplesParser::new().for_reader(file.as_bytes());
+    /// parser.next().unwrap()?; // We read the first triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (0, 102)); // The first "."
+    /// parser.next().unwrap()?; // We read the second triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 56)); // The second "."
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
+}
+
 impl<R: Read> Iterator for ReaderNTriplesParser<R> {
     type Item = Result<Triple, TurtleParseError>;
 
@@ -389,6 +419,34 @@ impl<R: AsyncRead + Unpin> TokioAsyncReaderNTriplesParser<R> {
     pub async fn next(&mut self) -> Option<Result<Triple, TurtleParseError>> {
         Some(self.inner.next().await?.map(Into::into))
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a triple has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a triple, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use oxttl::NTriplesParser;
+    ///
+    /// let file = r#"<http://example.com/foo> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://schema.org/Person> .
+    /// <http://example.com/foo> <http://schema.org/name> "Foo" ."#;
+    ///
+    /// let mut parser = NTriplesParser::new().for_tokio_async_reader(file.as_bytes());
+    /// parser.next().await.unwrap()?; // We read the first triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (0, 102)); // The first "."
+    /// parser.next().await.unwrap()?; // We read the second triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 56)); // The second "."
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 /// Parses an N-Triples file from a byte slice.
@@ -421,6 +479,33 @@ pub struct SliceNTriplesParser<'a> {
     inner: SliceIterator<'a, NQuadsRecognizer>,
 }
 
+impl SliceNTriplesParser<'_> {
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a triple has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a triple, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::NTriplesParser;
+    ///
+    /// let file = r#"<http://example.com/foo> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://schema.org/Person> .
+    /// <http://example.com/foo> <http://schema.org/name> "Foo" ."#;
+    ///
+    /// let mut parser = NTriplesParser::new().for_slice(file);
+    /// parser.next().unwrap()?; // We read the first triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (0, 102)); // The first "."
+    /// parser.next().unwrap()?; // We read the second triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 56)); // The second "."
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
+}
+
 impl Iterator for SliceNTriplesParser<'_> {
     type Item = Result<Triple, TurtleSyntaxError>;
 
@@ -496,6 +581,33 @@ impl LowLevelNTriplesParser {
     pub fn parse_next(&mut self) -> Option<Result<Triple, TurtleSyntaxError>> {
         Some(self.parser.parse_next()?.map(Into::into))
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a triple has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a triple, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::NTriplesParser;
+    ///
+    /// let file = r#"<http://example.com/foo> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://schema.org/Person> .
+    /// <http://example.com/foo> <http://schema.org/name> "Foo" ."#;
+    ///
+    /// let mut parser = NTriplesParser::new().low_level();
+    /// parser.extend_from_slice(file.as_bytes());
+    /// parser.end();
+    /// parser.parse_next().unwrap()?; // We read the first triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (0, 102)); // The first "."
+    /// parser.parse_next().unwrap()?; // We read the second triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 56)); // The second "."
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.parser.last_token_location()
+    }
 }
 
 /// A [canonical](https://www.w3.org/TR/n-triples/#canonical-ntriples) [N-Triples](https://www.w3.org/TR/n-triples/) serializer.
@@ -1251,25 +1251,6 @@ impl RuleRecognizer for TriGRecognizer {
 }
 
 impl TriGRecognizer {
-    pub fn new_parser<B>(
-        data: B,
-        is_ending: bool,
-        with_graph_name: bool,
-        lenient: bool,
-        base_iri: Option<Iri<String>>,
-        prefixes: HashMap<String, Iri<String>>,
-    ) -> Parser<B, Self> {
-        Self::new_parser_with_limits(
-            data,
-            is_ending,
-            with_graph_name,
-            lenient,
-            base_iri,
-            prefixes,
-            100, // Default max nesting depth
-        )
-    }
-
     pub fn new_parser_with_limits<B>(
         data: B,
         is_ending: bool,
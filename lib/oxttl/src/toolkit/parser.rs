@@ -1,7 +1,7 @@
-use crate::toolkit::error::{TurtleParseError, TurtleSyntaxError};
+use crate::toolkit::error::{TextPosition, TurtleParseError, TurtleSyntaxError};
 use crate::toolkit::lexer::{Lexer, TokenOrLineJump, TokenRecognizer};
 use std::io::Read;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 #[cfg(feature = "async-tokio")]
 use tokio::io::AsyncRead;
 
@@ -118,6 +118,11 @@ impl<B: Deref<Target = [u8]>, RR: RuleRecognizer> Parser<B, RR> {
         self.state.is_none() && self.results.is_empty() && self.errors.is_empty()
     }
 
+    #[inline]
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.lexer.last_token_location()
+    }
+
     pub fn parse_next(&mut self) -> Option<Result<RR::Output, TurtleSyntaxError>> {
         loop {
             if let Some(error) = self.errors.pop() {
@@ -1,11 +1,14 @@
 //! A [TriG](https://www.w3.org/TR/trig/) streaming parser implemented by [`TriGParser`]
 //! and a serializer implemented by [`TriGSerializer`].
 
+use crate::DEFAULT_MAX_NESTING_DEPTH;
 use crate::lexer::N3Lexer;
 use crate::terse::TriGRecognizer;
 #[cfg(feature = "async-tokio")]
 use crate::toolkit::TokioAsyncReaderIterator;
-use crate::toolkit::{Parser, ReaderIterator, SliceIterator, TurtleParseError, TurtleSyntaxError};
+use crate::toolkit::{
+    Parser, ReaderIterator, SliceIterator, TextPosition, TurtleParseError, TurtleSyntaxError,
+};
 use oxiri::{Iri, IriParseError};
 use oxrdf::vocab::{rdf, xsd};
 use oxrdf::{
@@ -17,6 +20,7 @@ use std::collections::hash_map::Iter;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::io::{self, Read, Write};
+use std::ops::Range;
 #[cfg(feature = "async-tokio")]
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 
@@ -52,6 +56,7 @@ pub struct TriGParser {
     lenient: bool,
     base: Option<Iri<String>>,
     prefixes: HashMap<String, Iri<String>>,
+    max_nesting_depth: Option<usize>,
 }
 
 impl TriGParser {
@@ -95,6 +100,32 @@ impl TriGParser {
         Ok(self)
     }
 
+    /// Sets the maximum nesting depth for parser structures.
+    ///
+    /// Deeper nesting of blank node property lists, collections or quoted triples
+    /// is reported as a syntax error instead of exhausting the stack.
+    ///
+    /// Default: 100 levels
+    ///
+    /// ```
+    /// use oxttl::TriGParser;
+    ///
+    /// let input = format!(
+    ///     "@base <http://example.com/> . <s> <p> {}<o>{} .",
+    ///     "[ <p> ".repeat(20),
+    ///     " ]".repeat(20)
+    /// );
+    /// let mut parser = TriGParser::new()
+    ///     .with_max_nesting_depth(10)
+    ///     .for_slice(&input);
+    /// assert!(parser.any(|quad| quad.is_err()));
+    /// ```
+    #[inline]
+    pub fn with_max_nesting_depth(mut self, max_depth: usize) -> Self {
+        self.max_nesting_depth = Some(max_depth);
+        self
+    }
+
     /// Parses a TriG file from a [`Read`] implementation.
     ///
     /// Count the number of people:
@@ -194,14 +225,16 @@ impl TriGParser {
     /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
     /// ```
     pub fn for_slice(self, slice: &(impl AsRef<[u8]> + ?Sized)) -> SliceTriGParser<'_> {
+        let max_depth = self.max_nesting_depth.unwrap_or(DEFAULT_MAX_NESTING_DEPTH);
         SliceTriGParser {
-            inner: TriGRecognizer::new_parser(
+            inner: TriGRecognizer::new_parser_with_limits(
                 slice.as_ref(),
                 true,
                 true,
                 self.lenient,
                 self.base,
                 self.prefixes,
+                max_depth,
             )
             .into_iter(),
         }
@@ -246,14 +279,16 @@ impl TriGParser {
     /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
     /// ```
     pub fn low_level(self) -> LowLevelTriGParser {
+        let max_depth = self.max_nesting_depth.unwrap_or(DEFAULT_MAX_NESTING_DEPTH);
         LowLevelTriGParser {
-            parser: TriGRecognizer::new_parser(
+            parser: TriGRecognizer::new_parser_with_limits(
                 Vec::new(),
                 false,
                 true,
                 self.lenient,
                 self.base,
                 self.prefixes,
+                max_depth,
             ),
         }
     }
@@ -350,6 +385,34 @@ impl<R: Read> ReaderTriGParser<R> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a quad has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a quad, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::TriGParser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/g> {
+    ///     <http://example.com/foo> a schema:Person ;
+    ///         schema:name "Foo" .
+    /// }"#;
+    ///
+    /// let mut parser = TriGParser::new().for_reader(file.as_bytes());
+    /// parser.next().unwrap()?; // We read the first quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 31)); // schema:Person
+    /// parser.next().unwrap()?; // We read the second quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (3, 26)); // The final "." as "Foo" might have a language tag
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 impl<R: Read> Iterator for ReaderTriGParser<R> {
@@ -469,6 +532,37 @@ impl<R: AsyncRead + Unpin> TokioAsyncReaderTriGParser<R> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a quad has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a quad, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use oxttl::TriGParser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/g> {
+    ///     <http://example.com/foo> a schema:Person ;
+    ///         schema:name "Foo" .
+    /// }"#;
+    ///
+    /// let mut parser = TriGParser::new().for_tokio_async_reader(file.as_bytes());
+    /// parser.next().await.unwrap()?; // We read the first quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 31)); // schema:Person
+    /// parser.next().await.unwrap()?; // We read the second quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (3, 26)); // The final "." as "Foo" might have a language tag
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 /// Parses a TriG file from a byte slice.
@@ -562,6 +656,34 @@ impl SliceTriGParser<'_> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a quad has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a quad, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::TriGParser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/g> {
+    ///     <http://example.com/foo> a schema:Person ;
+    ///         schema:name "Foo" .
+    /// }"#;
+    ///
+    /// let mut parser = TriGParser::new().for_slice(file);
+    /// parser.next().unwrap()?; // We read the first quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 31)); // schema:Person
+    /// parser.next().unwrap()?; // We read the second quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (3, 26)); // The final "." as "Foo" might have a language tag
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 impl Iterator for SliceTriGParser<'_> {
@@ -701,6 +823,36 @@ impl LowLevelTriGParser {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a quad has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a quad, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::TriGParser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/g> {
+    ///     <http://example.com/foo> a schema:Person ;
+    ///         schema:name "Foo" .
+    /// }"#;
+    ///
+    /// let mut parser = TriGParser::new().low_level();
+    /// parser.extend_from_slice(file.as_bytes());
+    /// parser.end();
+    /// parser.parse_next().unwrap()?; // We read the first quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 31)); // schema:Person
+    /// parser.parse_next().unwrap()?; // We read the second quad
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (3, 26)); // The final "." as "Foo" might have a language tag
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.parser.last_token_location()
+    }
 }
 
 /// Iterator on the file prefixes.
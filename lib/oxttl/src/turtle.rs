@@ -1,12 +1,14 @@
 //! A [Turtle](https://www.w3.org/TR/turtle/) streaming parser implemented by [`TurtleParser`]
 //! and a serializer implemented by [`TurtleSerializer`].
 
-use crate::MIN_PARALLEL_CHUNK_SIZE;
+use crate::{DEFAULT_MAX_NESTING_DEPTH, MIN_PARALLEL_CHUNK_SIZE};
 use crate::chunker::get_turtle_slice_chunks;
 use crate::terse::TriGRecognizer;
 #[cfg(feature = "async-tokio")]
 use crate::toolkit::TokioAsyncReaderIterator;
-use crate::toolkit::{Parser, ReaderIterator, SliceIterator, TurtleParseError, TurtleSyntaxError};
+use crate::toolkit::{
+    Parser, ReaderIterator, SliceIterator, TextPosition, TurtleParseError, TurtleSyntaxError,
+};
 #[cfg(feature = "async-tokio")]
 use crate::trig::TokioAsyncWriterTriGSerializer;
 use crate::trig::{LowLevelTriGSerializer, TriGSerializer, WriterTriGSerializer};
@@ -15,6 +17,7 @@ use oxrdf::{GraphNameRef, Triple, TripleRef};
 use std::collections::HashMap;
 use std::collections::hash_map::Iter;
 use std::io::{self, Read, Write};
+use std::ops::Range;
 #[cfg(feature = "async-tokio")]
 use tokio::io::{AsyncRead, AsyncWrite};
 
@@ -104,8 +107,15 @@ impl TurtleParser {
     /// ```
     /// use oxttl::TurtleParser;
     ///
-    /// let parser = TurtleParser::new()
-    ///     .with_max_nesting_depth(200); // Allow deeper nesting
+    /// let input = format!(
+    ///     "@base <http://example.com/> . <s> <p> {}<o>{} .",
+    ///     "[ <p> ".repeat(50),
+    ///     " ]".repeat(50)
+    /// );
+    /// let mut parser = TurtleParser::new()
+    ///     .with_max_nesting_depth(200) // Allow deeper nesting
+    ///     .for_slice(&input);
+    /// assert!(parser.all(|triple| triple.is_ok()));
     /// ```
     #[inline]
     pub fn with_max_nesting_depth(mut self, max_depth: usize) -> Self {
@@ -212,7 +222,7 @@ impl TurtleParser {
     /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
     /// ```
     pub fn for_slice(self, slice: &(impl AsRef<[u8]> + ?Sized)) -> SliceTurtleParser<'_> {
-        let max_depth = self.max_nesting_depth.unwrap_or(100);
+        let max_depth = self.max_nesting_depth.unwrap_or(DEFAULT_MAX_NESTING_DEPTH);
         SliceTurtleParser {
             inner: TriGRecognizer::new_parser_with_limits(
                 slice.as_ref(),
@@ -329,7 +339,7 @@ impl TurtleParser {
     /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
     /// ```
     pub fn low_level(self) -> LowLevelTurtleParser {
-        let max_depth = self.max_nesting_depth.unwrap_or(100);
+        let max_depth = self.max_nesting_depth.unwrap_or(DEFAULT_MAX_NESTING_DEPTH);
         LowLevelTurtleParser {
             parser: TriGRecognizer::new_parser_with_limits(
                 Vec::new(),
@@ -435,6 +445,32 @@ impl<R: Read> ReaderTurtleParser<R> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a triple has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a triple, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::TurtleParser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/foo> a schema:Person ;
+    ///     schema:name "Foo" ."#;
+    ///
+    /// let mut parser = TurtleParser::new().for_reader(file.as_bytes());
+    /// parser.next().unwrap()?; // We read the first triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 27)); // schema:Person
+    /// parser.next().unwrap()?; // We read the second triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 22)); // The final "." as "Foo" might have a language tag
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 impl<R: Read> Iterator for ReaderTurtleParser<R> {
@@ -554,6 +590,35 @@ impl<R: AsyncRead + Unpin> TokioAsyncReaderTurtleParser<R> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a triple has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a triple, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use oxttl::TurtleParser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/foo> a schema:Person ;
+    ///     schema:name "Foo" ."#;
+    ///
+    /// let mut parser = TurtleParser::new().for_tokio_async_reader(file.as_bytes());
+    /// parser.next().await.unwrap()?; // We read the first triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 27)); // schema:Person
+    /// parser.next().await.unwrap()?; // We read the second triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 22)); // The final "." as "Foo" might have a language tag
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 /// Parses a Turtle file from a byte slice.
@@ -647,6 +712,32 @@ impl SliceTurtleParser<'_> {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a triple has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a triple, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::TurtleParser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/foo> a schema:Person ;
+    ///     schema:name "Foo" ."#;
+    ///
+    /// let mut parser = TurtleParser::new().for_slice(file);
+    /// parser.next().unwrap()?; // We read the first triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 27)); // schema:Person
+    /// parser.next().unwrap()?; // We read the second triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 22)); // The final "." as "Foo" might have a language tag
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.inner.parser.last_token_location()
+    }
 }
 
 impl Iterator for SliceTurtleParser<'_> {
@@ -786,6 +877,34 @@ impl LowLevelTurtleParser {
             .as_ref()
             .map(Iri::as_str)
     }
+
+    /// The location in the file of the last token read by the parser.
+    ///
+    /// After a triple has been returned, it is the location of the last token read to build it, i.e. its last term or the punctuation following it.
+    /// It allows to point users to the source of a triple, for example if it is rejected by a later processing step.
+    /// Syntax errors already carry their own [location](TurtleSyntaxError::location).
+    ///
+    /// ```
+    /// use oxttl::TurtleParser;
+    ///
+    /// let file = r#"@prefix schema: <http://schema.org/> .
+    /// <http://example.com/foo> a schema:Person ;
+    ///     schema:name "Foo" ."#;
+    ///
+    /// let mut parser = TurtleParser::new().low_level();
+    /// parser.extend_from_slice(file.as_bytes());
+    /// parser.end();
+    /// parser.parse_next().unwrap()?; // We read the first triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (1, 27)); // schema:Person
+    /// parser.parse_next().unwrap()?; // We read the second triple
+    /// let location = parser.last_token_location();
+    /// assert_eq!((location.start.line, location.start.column), (2, 22)); // The final "." as "Foo" might have a language tag
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn last_token_location(&self) -> Range<TextPosition> {
+        self.parser.last_token_location()
+    }
 }
 
 /// Iterator on the file prefixes.
@@ -3,7 +3,7 @@
 //! These tests validate that the parser rejects maliciously crafted input
 //! that could cause denial of service through excessive resource consumption.
 
-use oxttl::TurtleParser;
+use oxttl::{TriGParser, TurtleParser};
 
 /// Generate deeply nested RDF collections: ( ( ( ... ) ) )
 fn generate_nested_collections(depth: usize) -> String {
@@ -121,6 +121,58 @@ fn test_deeply_nested_blank_nodes_attack() {
     );
 }
 
+#[test]
+fn test_configured_nesting_limit_turtle() {
+    // 50 levels are accepted by default but rejected with a lower limit
+    let nested_turtle = generate_nested_blank_nodes(50);
+
+    let error = TurtleParser::new()
+        .with_max_nesting_depth(20)
+        .for_slice(&nested_turtle)
+        .find_map(Result::err)
+        .expect("Parser should reject nesting above the configured limit");
+    assert!(
+        error.to_string().contains("nesting depth"),
+        "Expected nesting depth error, got: {error}"
+    );
+
+    for result in TurtleParser::new()
+        .with_max_nesting_depth(200)
+        .for_slice(&nested_turtle)
+    {
+        result.expect("Nesting under the configured limit should be allowed");
+    }
+}
+
+#[test]
+fn test_deeply_nested_blank_nodes_attack_trig() {
+    // The TriG parser enforces the same limit, also inside of named graphs
+    let nested_trig = format!(
+        "@prefix : <http://example.org/> .\n:g {{ :s :p {}:value{} . }}",
+        "[ :p ".repeat(10_000),
+        " ]".repeat(10_000)
+    );
+
+    let error = TriGParser::new()
+        .for_slice(&nested_trig)
+        .find_map(Result::err)
+        .expect("Parser should reject deeply nested blank nodes");
+    assert!(
+        error.to_string().contains("nesting depth"),
+        "Expected nesting depth error, got: {error}"
+    );
+
+    let error = TriGParser::new()
+        .with_max_nesting_depth(5)
+        .for_reader(nested_trig.as_bytes())
+        .find_map(Result::err)
+        .expect("Parser should reject nesting above the configured limit");
+    assert!(
+        error.to_string().contains("nesting depth"),
+        "Expected nesting depth error, got: {error}"
+    );
+}
+
 #[test]
 fn test_moderate_nesting_allowed() {
     // 50 levels should be allowed (under default limit of 100)
@@ -0,0 +1,100 @@
+//! Source Location Tests
+//!
+//! These tests validate that the locations of errors and of parsed triples
+//! point exactly at the relevant tokens, even in the middle of big files.
+
+#![cfg(test)]
+
+use oxttl::{TextPosition, TurtleParseError, TurtleParser};
+
+const LINES: usize = 100_000;
+const BAD_LINE: usize = 54_321;
+
+/// Generate a big Turtle file with an undeclared prefix in the middle
+fn generate_file_with_error() -> String {
+    (0..LINES)
+        .map(|i| {
+            let predicate = if i == BAD_LINE {
+                "ex:p"
+            } else {
+                "<http://example.com/p>"
+            };
+            format!("<http://example.com/s{i}> {predicate} \"{i}\" .\n")
+        })
+        .collect()
+}
+
+fn expected_error_start(file: &str) -> TextPosition {
+    let offset = file.find("ex:p").unwrap();
+    let line_start = file[..offset].rfind('\n').unwrap() + 1;
+    TextPosition {
+        line: BAD_LINE.try_into().unwrap(),
+        column: (offset - line_start).try_into().unwrap(),
+        offset: offset.try_into().unwrap(),
+    }
+}
+
+#[test]
+fn test_error_location_in_big_slice() {
+    let file = generate_file_with_error();
+    let mut parser = TurtleParser::new().for_slice(&file);
+    let mut count = 0;
+    let error = loop {
+        match parser.next().unwrap() {
+            Ok(_) => count += 1,
+            Err(error) => break error,
+        }
+    };
+    assert_eq!(count, BAD_LINE);
+    let start = expected_error_start(&file);
+    assert_eq!(
+        error.location(),
+        start..TextPosition {
+            column: start.column + 4,
+            offset: start.offset + 4,
+            ..start
+        }
+    );
+}
+
+#[test]
+fn test_error_location_in_big_reader() {
+    let file = generate_file_with_error();
+    let mut parser = TurtleParser::new().for_reader(file.as_bytes());
+    let error = loop {
+        if let Err(error) = parser.next().unwrap() {
+            break error;
+        }
+    };
+    let TurtleParseError::Syntax(error) = error else {
+        unreachable!("reading from a byte slice does not fail")
+    };
+    assert_eq!(error.location().start, expected_error_start(&file));
+}
+
+#[test]
+fn test_triple_locations_in_big_reader() {
+    let file = generate_file_with_error();
+    let mut parser = TurtleParser::new().for_reader(file.as_bytes());
+    let mut line = 0;
+    while let Some(result) = parser.next() {
+        if line == BAD_LINE {
+            // The parser reports the error and recovers at the next statement
+            assert!(result.is_err());
+            line += 1;
+            continue;
+        }
+        let triple = result.unwrap();
+        assert_eq!(triple.object.to_string(), format!("\"{line}\""));
+        // Literals are completed by the following "."
+        let location = parser.last_token_location();
+        assert_eq!(location.start.line, u64::try_from(line).unwrap());
+        assert_eq!(
+            &file[usize::try_from(location.start.offset).unwrap()
+                ..usize::try_from(location.end.offset).unwrap()],
+            "."
+        );
+        line += 1;
+    }
+    assert_eq!(line, LINES);
+}
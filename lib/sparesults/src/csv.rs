@@ -144,7 +144,8 @@ impl InnerCsvSolutionsSerializer {
 
 fn write_csv_term<'a>(output: &mut String, term: impl Into<TermRef<'a>>) {
     match term.into() {
-        TermRef::NamedNode(uri) => output.push_str(uri.as_str()),
+        // IRIs might contain commas
+        TermRef::NamedNode(uri) => write_escaped_csv_string(output, uri.as_str()),
         TermRef::BlankNode(bnode) => {
             output.push_str("_:");
             output.push_str(bnode.as_str())
@@ -152,11 +153,33 @@ fn write_csv_term<'a>(output: &mut String, term: impl Into<TermRef<'a>>) {
         TermRef::Literal(literal) => write_escaped_csv_string(output, literal.value()),
         #[cfg(feature = "sparql-12")]
         TermRef::Triple(triple) => {
-            write_csv_term(output, &triple.subject);
+            // The triple is a single field, we escape it as a whole
+            let mut value = String::new();
+            write_unescaped_csv_term(&mut value, &triple.subject);
+            value.push(' ');
+            write_unescaped_csv_term(&mut value, &triple.predicate);
+            value.push(' ');
+            write_unescaped_csv_term(&mut value, &triple.object);
+            write_escaped_csv_string(output, &value)
+        }
+    }
+}
+
+#[cfg(feature = "sparql-12")]
+fn write_unescaped_csv_term<'a>(output: &mut String, term: impl Into<TermRef<'a>>) {
+    match term.into() {
+        TermRef::NamedNode(uri) => output.push_str(uri.as_str()),
+        TermRef::BlankNode(bnode) => {
+            output.push_str("_:");
+            output.push_str(bnode.as_str())
+        }
+        TermRef::Literal(literal) => output.push_str(literal.value()),
+        TermRef::Triple(triple) => {
+            write_unescaped_csv_term(output, &triple.subject);
             output.push(' ');
-            write_csv_term(output, &triple.predicate);
+            write_unescaped_csv_term(output, &triple.predicate);
             output.push(' ');
-            write_csv_term(output, &triple.object)
+            write_unescaped_csv_term(output, &triple.object)
         }
     }
 }
@@ -905,6 +928,151 @@ mod tests {
         assert_eq!(buffer, expected);
     }
 
+    #[test]
+    fn test_csv_escaping() {
+        let variables = vec![Variable::new_unchecked("s"), Variable::new_unchecked("o")];
+        let mut buffer = String::new();
+        let serializer = InnerCsvSolutionsSerializer::start(&mut buffer, variables.clone());
+        let iri = NamedNode::new_unchecked("http://example.com/a,b");
+        let literal = Literal::new_typed_literal("1,\"2\"", xsd::STRING);
+        serializer.write(
+            &mut buffer,
+            [
+                (variables[0].as_ref(), iri.as_ref().into()),
+                (variables[1].as_ref(), literal.as_ref().into()),
+            ],
+        );
+        assert_eq!(
+            buffer,
+            "s,o\r\n\"http://example.com/a,b\",\"1,\"\"2\"\"\"\r\n"
+        );
+    }
+
+    #[cfg(feature = "sparql-12")]
+    #[test]
+    fn test_csv_triple_escaping() {
+        let variable = Variable::new_unchecked("o");
+        let mut buffer = String::new();
+        let serializer = InnerCsvSolutionsSerializer::start(&mut buffer, vec![variable.clone()]);
+        let iri = NamedNode::new_unchecked("http://example.com/a,b");
+        let triple = Triple::new(
+            iri.clone(),
+            iri,
+            Literal::new_typed_literal("1,\"2\"", xsd::STRING),
+        );
+        serializer.write(&mut buffer, [(variable.as_ref(), TermRef::Triple(&triple))]);
+        assert_eq!(
+            buffer,
+            "o\r\n\"http://example.com/a,b http://example.com/a,b 1,\"\"2\"\"\"\r\n"
+        );
+    }
+
+    /// The example of the SPARQL 1.1 Query Results CSV and TSV Formats specification
+    ///
+    /// It is the beginning of [`build_example`].
+    fn spec_example() -> (Vec<Variable>, Vec<Vec<Option<Term>>>) {
+        let (variables, mut solutions) = build_example();
+        solutions.truncate(8);
+        (variables, solutions)
+    }
+
+    #[test]
+    fn test_csv_spec_example() {
+        // https://www.w3.org/TR/sparql11-results-csv-tsv/#csv
+        let (variables, solutions) = spec_example();
+        let mut buffer = String::new();
+        let serializer = InnerCsvSolutionsSerializer::start(&mut buffer, variables.clone());
+        for solution in &solutions {
+            serializer.write(
+                &mut buffer,
+                variables
+                    .iter()
+                    .zip(solution)
+                    .filter_map(|(v, s)| s.as_ref().map(|s| (v.as_ref(), s.as_ref()))),
+            );
+        }
+        assert_eq!(
+            buffer,
+            "x,literal\r\n\
+             http://example/x,String\r\n\
+             http://example/x,\"String-with-dquote\"\"\"\r\n\
+             _:b0,Blank node\r\n\
+             ,Missing 'x'\r\n\
+             ,\r\n\
+             http://example/x,\r\n\
+             _:b1,String-with-lang\r\n\
+             _:b1,123\r\n"
+        );
+    }
+
+    #[test]
+    fn test_tsv_spec_example() -> Result<(), Box<dyn Error>> {
+        // https://www.w3.org/TR/sparql11-results-csv-tsv/#tsv
+        let expected = "?x\t?literal\n\
+                        <http://example/x>\t\"String\"\n\
+                        <http://example/x>\t\"String-with-dquote\\\"\"\n\
+                        _:b0\t\"Blank node\"\n\
+                        \t\"Missing 'x'\"\n\
+                        \t\n\
+                        <http://example/x>\t\n\
+                        _:b1\t\"String-with-lang\"@en\n\
+                        _:b1\t123\n";
+        let (variables, solutions) = spec_example();
+
+        // Write
+        let mut buffer = String::new();
+        let serializer = InnerTsvSolutionsSerializer::start(&mut buffer, variables.clone());
+        for solution in &solutions {
+            serializer.write(
+                &mut buffer,
+                variables
+                    .iter()
+                    .zip(solution)
+                    .filter_map(|(v, s)| s.as_ref().map(|s| (v.as_ref(), s.as_ref()))),
+            );
+        }
+        assert_eq!(buffer, expected);
+
+        // Read
+        let SliceTsvQueryResultsParserOutput::Solutions {
+            solutions: mut solutions_iter,
+            variables: actual_variables,
+        } = SliceTsvQueryResultsParserOutput::read(expected.as_bytes())?
+        else {
+            unreachable!()
+        };
+        assert_eq!(actual_variables, variables);
+        let mut rows = Vec::new();
+        while let Some(row) = solutions_iter.parse_next()? {
+            rows.push(row);
+        }
+        assert_eq!(rows, solutions);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tsv_typed_literals() {
+        let variable = Variable::new_unchecked("o");
+        let mut buffer = String::new();
+        let serializer = InnerTsvSolutionsSerializer::start(&mut buffer, vec![variable.clone()]);
+        for literal in [
+            Literal::from(true),
+            Literal::new_typed_literal("-12", xsd::INTEGER),
+            Literal::new_typed_literal("1.5", xsd::DECIMAL),
+            Literal::new_typed_literal("1e5", xsd::DOUBLE),
+            // Not valid Turtle numbers, they must be quoted
+            Literal::new_typed_literal("NaN", xsd::DOUBLE),
+            Literal::new_typed_literal("foo", xsd::INTEGER),
+            Literal::new_typed_literal("2020-01-01", xsd::DATE),
+        ] {
+            serializer.write(&mut buffer, [(variable.as_ref(), literal.as_ref().into())]);
+        }
+        assert_eq!(
+            buffer,
+            "?o\ntrue\n-12\n1.5\n1e5\n\"NaN\"^^<http://www.w3.org/2001/XMLSchema#double>\n\"foo\"^^<http://www.w3.org/2001/XMLSchema#integer>\n\"2020-01-01\"^^<http://www.w3.org/2001/XMLSchema#date>\n"
+        );
+    }
+
     #[test]
     fn test_tsv_roundtrip() -> Result<(), Box<dyn Error>> {
         let (variables, solutions) = build_example();
@@ -3,6 +3,7 @@ use oxrdf::{NamedNode, Term, Variable};
 use spargebra::SparqlSyntaxError;
 use std::convert::Infallible;
 use std::error::Error;
+use std::io;
 use std::ops::RangeInclusive;
 
 /// A SPARQL evaluation error
@@ -51,20 +52,43 @@ pub enum QueryEvaluationError {
     /// Query result set exceeded the maximum allowed number of rows
     #[error("Query result set exceeded the maximum allowed {0} rows")]
     ResultLimitExceeded(usize),
-    /// Query GROUP BY exceeded the maximum number of groups
-    #[error("Query GROUP BY exceeded the maximum allowed {0} groups")]
+    /// Query DISTINCT or GROUP BY kept in memory more than the maximum allowed number of rows or groups
+    #[error("Query DISTINCT or GROUP BY exceeded the maximum allowed {0} rows or groups")]
     GroupLimitExceeded(usize),
-    /// Property path evaluation exceeded the maximum depth
-    #[error("Property path evaluation exceeded the maximum depth of {0}")]
+    /// Transitive property path evaluation visited more than the maximum allowed number of nodes
+    #[error("Property path evaluation visited more than the maximum allowed {0} nodes")]
     PropertyPathDepthExceeded(usize),
+    /// Error while spilling sorted solutions to a temporary file
+    #[error("Failed to spill ORDER BY solutions to a temporary file: {0}")]
+    SortSpill(#[source] io::Error),
     /// Query execution exceeded the maximum allowed memory
     #[error("Query execution exceeded the maximum allowed memory of {0} bytes")]
     MemoryLimitExceeded(usize),
+    /// Error while writing the query results
+    #[error("Failed to write the query results: {0}")]
+    ResultsSerialization(#[source] io::Error),
+    /// The query results are not an RDF graph and can't be written with an RDF format
+    #[error("The query results are not an RDF graph")]
+    NotAGraph,
+    /// The query results are not solutions and can't be collected as solutions
+    #[error("The query results are not solutions")]
+    NotSolutions,
     #[doc(hidden)]
     #[error(transparent)]
     Unexpected(Box<dyn Error + Send + Sync>),
 }
 
+impl QueryEvaluationError {
+    /// Returns `true` if the evaluation has been interrupted by a [`CancellationToken`](crate::CancellationToken) or a timeout.
+    ///
+    /// Such an error is always the last item returned by a [`QuerySolutionIter`](crate::QuerySolutionIter) or a [`QueryTripleIter`](crate::QueryTripleIter):
+    /// the results returned before it are valid but the result set is truncated.
+    #[inline]
+    pub fn is_interruption(&self) -> bool {
+        matches!(self, Self::Cancelled | Self::Timeout(_))
+    }
+}
+
 impl From<Infallible> for QueryEvaluationError {
     #[inline]
     fn from(error: Infallible) -> Self {
@@ -17,6 +17,7 @@ use oxiri::Iri;
 use oxrdf::{BaseDirection, NamedOrBlankNode};
 use oxrdf::{BlankNode, GraphName, Literal, NamedNode, Term, Triple, Variable};
 use oxsdatatypes::{DateTime, DayTimeDuration, Decimal, Double, Float, Integer};
+use rand::random;
 use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet, FxHasher};
 use spargebra::algebra::{AggregateFunction, PropertyPathExpression};
 #[cfg(feature = "sparql-12")]
@@ -24,19 +25,30 @@ use spargebra::term::GroundTriple;
 use spargebra::term::{
     GroundTerm, GroundTermPattern, NamedNodePattern, TermPattern, TriplePattern,
 };
+use sparopt::Optimizer;
 use sparopt::algebra::{
     AggregateExpression, Expression, GraphPattern, JoinAlgorithm, LeftJoinAlgorithm,
     MinusAlgorithm, OrderExpression,
 };
 use std::cell::Cell;
 use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fs::{File, OpenOptions, remove_file};
 use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, BufWriter, Seek, Write as _};
 use std::iter::{Peekable, empty, once};
 use std::marker::PhantomData;
+use std::mem::take;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::sync::atomic::AtomicBool;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::sync::{Arc, atomic};
-use std::{fmt, io};
+use std::time::Duration;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use std::time::Instant;
+use std::{env, fmt, io, process};
 // TODO: make expression raise error when relevant (storage I/O)
 
 type InternalTupleEvaluator<'a, T> =
@@ -418,9 +430,21 @@ pub struct SimpleEvaluator<'a, D: QueryableDataset<'a>> {
     service_handler: Rc<ServiceHandlerRegistry>,
     custom_functions: Rc<CustomFunctionRegistry>,
     custom_aggregate_functions: Rc<CustomAggregateFunctionRegistry>,
+    limits: EvaluatorLimits,
     run_stats: bool,
 }
 
+/// The resource limits enforced by [`SimpleEvaluator`].
+#[derive(Clone, Copy, Default)]
+pub struct EvaluatorLimits {
+    /// Maximal number of nodes visited by each evaluation of a transitive property path
+    pub max_path_visits: Option<usize>,
+    /// Maximal number of rows kept in memory by DISTINCT or of groups kept by GROUP BY
+    pub max_groups: Option<usize>,
+    /// Approximate memory used by ORDER BY before spilling sorted runs to disk
+    pub sort_memory_budget: Option<usize>,
+}
+
 impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
     pub fn new(
         dataset: D,
@@ -430,6 +454,7 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
         custom_aggregate_functions: Rc<CustomAggregateFunctionRegistry>,
         cancellation_token: CancellationToken,
         dataset_spec: QueryDatasetSpecification,
+        limits: EvaluatorLimits,
         run_stats: bool,
     ) -> Result<Self, QueryEvaluationError> {
         Ok(Self {
@@ -439,6 +464,7 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
             service_handler,
             custom_functions,
             custom_aggregate_functions,
+            limits,
             run_stats,
         })
     }
@@ -566,6 +592,7 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
         &self,
         pattern: &GraphPattern,
         substitutions: impl IntoIterator<Item = (Variable, Term)>,
+        strategy: DescribeStrategy,
     ) -> (
         Result<QueryTripleIter<'a>, QueryEvaluationError>,
         Rc<EvalNodeWithStats>,
@@ -584,8 +611,18 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
             Ok(QueryTripleIter::new(DescribeIterator {
                 eval: self.clone(),
                 tuples_to_describe: eval(from),
-                nodes_described: FxHashSet::default(),
-                nodes_to_describe: Vec::default(),
+                symmetric: strategy == DescribeStrategy::SymmetricCbd,
+                max_depth: match strategy {
+                    DescribeStrategy::Cbd | DescribeStrategy::SymmetricCbd => None,
+                    DescribeStrategy::ForwardOnly => Some(0),
+                    DescribeStrategy::BlankNodeClosure { max_depth } => Some(max_depth),
+                },
+                nodes_described: FxHashMap::default(),
+                nodes_to_describe: VecDeque::default(),
+                current_depth: 0,
+                current_emit: true,
+                emitted_triples: (strategy == DescribeStrategy::SymmetricCbd)
+                    .then(FxHashSet::default),
                 quads: Box::new(empty()),
             })),
             stats,
@@ -608,12 +645,47 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
             children: stat_children,
             exec_count: Cell::new(0),
             exec_duration: Cell::new(self.run_stats.then(DayTimeDuration::default)),
+            estimated_size: self
+                .run_stats
+                .then(|| Optimizer::estimate_graph_pattern_size(pattern)),
+            used_indexes: Cell::new(UsedIndexes::default()),
         });
         let mut evaluator = match evaluator {
             Ok(e) => e,
             Err(e) => return (Err(e), stats),
         };
         if self.run_stats {
+            if let GraphPattern::QuadPattern {
+                subject,
+                predicate,
+                object,
+                ..
+            } = pattern
+            {
+                // We record which index is able to answer each lookup
+                let mut subject_variables = Vec::new();
+                ground_term_pattern_variables(subject, encoded_variables, &mut subject_variables);
+                let mut predicate_variables = Vec::new();
+                named_node_pattern_variables(
+                    predicate,
+                    encoded_variables,
+                    &mut predicate_variables,
+                );
+                let mut object_variables = Vec::new();
+                ground_term_pattern_variables(object, encoded_variables, &mut object_variables);
+                let stats = Rc::clone(&stats);
+                let inner = evaluator;
+                evaluator = Rc::new(move |tuple| {
+                    let is_bound =
+                        |variables: &[usize]| variables.iter().all(|v| tuple.contains(*v));
+                    stats.used_indexes.set(stats.used_indexes.get().with_lookup(
+                        is_bound(&subject_variables),
+                        is_bound(&predicate_variables),
+                        is_bound(&object_variables),
+                    ));
+                    inner(tuple)
+                })
+            }
             let stats = Rc::clone(&stats);
             evaluator = Rc::new(move |tuple| {
                 let start = Timer::now();
@@ -843,6 +915,7 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
                     None
                 };
                 let dataset = self.dataset.clone();
+                let max_path_visits = self.limits.max_path_visits;
                 Rc::new(move |from| {
                     let input_subject = match subject_selector.get_pattern_value(
                         &from,
@@ -854,6 +927,7 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
                     };
                     let path_eval = PathEvaluator {
                         dataset: dataset.clone(),
+                        max_visits: max_path_visits,
                     };
                     let input_object = match object_selector.get_pattern_value(
                         &from,
@@ -1537,48 +1611,85 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
                         })
                     })
                     .collect::<Result<Vec<_>, QueryEvaluationError>>()?;
-                Rc::new(move |from| {
-                    let mut errors = Vec::default();
-                    let mut values = child(from)
-                        .filter_map(|result| match result {
-                            Ok(result) => Some(result),
-                            Err(error) => {
-                                errors.push(Err(error));
-                                None
+                let compare: TupleComparator<'a, D::InternalTerm> = Rc::new(move |a, b| {
+                    for comp in &by {
+                        match comp {
+                            ComparatorFunction::Asc(expression) => {
+                                match cmp_terms(expression(a).as_ref(), expression(b).as_ref()) {
+                                    Ordering::Greater => return Ordering::Greater,
+                                    Ordering::Less => return Ordering::Less,
+                                    Ordering::Equal => (),
+                                }
                             }
-                        })
-                        .collect::<Vec<_>>();
-                    values.sort_unstable_by(|a, b| {
-                        for comp in &by {
-                            match comp {
-                                ComparatorFunction::Asc(expression) => {
-                                    match cmp_terms(expression(a).as_ref(), expression(b).as_ref())
-                                    {
-                                        Ordering::Greater => return Ordering::Greater,
-                                        Ordering::Less => return Ordering::Less,
-                                        Ordering::Equal => (),
-                                    }
+                            ComparatorFunction::Desc(expression) => {
+                                match cmp_terms(expression(a).as_ref(), expression(b).as_ref()) {
+                                    Ordering::Greater => return Ordering::Less,
+                                    Ordering::Less => return Ordering::Greater,
+                                    Ordering::Equal => (),
                                 }
-                                ComparatorFunction::Desc(expression) => {
-                                    match cmp_terms(expression(a).as_ref(), expression(b).as_ref())
+                            }
+                        }
+                    }
+                    Ordering::Equal
+                });
+                let sort_memory_budget = self.limits.sort_memory_budget;
+                let dataset = self.dataset.clone();
+                Rc::new(move |from| {
+                    let mut errors = Vec::default();
+                    let mut values = Vec::new();
+                    let mut values_size = 0;
+                    let mut runs = SpilledRuns::new(dataset.clone(), Rc::clone(&compare));
+                    for result in child(from) {
+                        match result {
+                            Ok(tuple) => {
+                                let Some(sort_memory_budget) = sort_memory_budget else {
+                                    values.push(tuple);
+                                    continue;
+                                };
+                                values_size += tuple_memory_size(&tuple);
+                                values.push(tuple);
+                                if values_size > sort_memory_budget {
+                                    // We spill a sorted run to disk
+                                    values.sort_by(|a, b| compare(a, b));
+                                    if let Err(e) =
+                                        SpilledRun::new(values.drain(..).map(Ok), &dataset)
+                                            .and_then(|run| runs.push(run))
                                     {
-                                        Ordering::Greater => return Ordering::Less,
-                                        Ordering::Less => return Ordering::Greater,
-                                        Ordering::Equal => (),
+                                        return Box::new(once(Err(e)));
                                     }
+                                    values_size = 0;
                                 }
                             }
+                            Err(error) => errors.push(Err(error)),
                         }
-                        Ordering::Equal
-                    });
-                    Box::new(errors.into_iter().chain(values.into_iter().map(Ok)))
+                    }
+                    // The sort is stable so that the merge of the spilled runs gives the same order
+                    values.sort_by(|a, b| compare(a, b));
+                    if runs.is_empty() {
+                        return Box::new(errors.into_iter().chain(values.into_iter().map(Ok)));
+                    }
+                    if !values.is_empty() {
+                        if let Err(e) = SpilledRun::new(values.into_iter().map(Ok), &dataset)
+                            .and_then(|run| runs.push(run))
+                        {
+                            return Box::new(once(Err(e)));
+                        }
+                    }
+                    match runs.into_merge() {
+                        Ok(merge) => Box::new(errors.into_iter().chain(merge)),
+                        Err(e) => Box::new(once(Err(e))),
+                    }
                 })
             }
             GraphPattern::Distinct { inner } => {
                 let (child, child_stats) = self.graph_pattern_evaluator(inner, encoded_variables);
                 stat_children.push(child_stats);
                 let child = child?;
-                Rc::new(move |from| Box::new(hash_deduplicate(child(from))))
+                if let Some(max_groups) = self.limits.max_groups {
+                    Rc::new(move |from| Box::new(limited_hash_deduplicate(child(from), max_groups)))
+                } else {
+                    Rc::new(move |from| Box::new(hash_deduplicate(child(from))))
+                }
             }
             GraphPattern::Reduced { inner } => {
                 let (child, child_stats) = self.graph_pattern_evaluator(inner, encoded_variables);
@@ -1676,6 +1787,7 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
                     .map(|(variable, _)| encode_variable(encoded_variables, variable))
                     .collect::<Vec<_>>();
                 let dataset = self.dataset.clone();
+                let max_groups = self.limits.max_groups;
                 Rc::new(move |from| {
                     let tuple_size = from.capacity();
                     let key_variables = Rc::clone(&key_variables);
@@ -1691,7 +1803,7 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
                             accumulator_builders.iter().map(|c| c()).collect::<Vec<_>>(),
                         );
                     }
-                    child(from)
+                    let grouping = child(from)
                         .filter_map(|result| match result {
                             Ok(result) => Some(result),
                             Err(error) => {
@@ -1699,13 +1811,22 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
                                 None
                             }
                         })
-                        .for_each(|tuple| {
+                        .try_for_each(|tuple| {
                             // TODO avoid copy for key?
                             let key = key_variables
                                 .iter()
                                 .map(|v| tuple.get(*v).cloned())
-                                .collect();
+                                .collect::<Vec<_>>();
 
+                            if let Some(max_groups) = max_groups {
+                                if accumulators_for_group.len() >= max_groups
+                                    && !accumulators_for_group.contains_key(&key)
+                                {
+                                    return Err(QueryEvaluationError::GroupLimitExceeded(
+                                        max_groups,
+                                    ));
+                                }
+                            }
                             let key_accumulators =
                                 accumulators_for_group.entry(key).or_insert_with(|| {
                                     accumulator_builders.iter().map(|c| c()).collect::<Vec<_>>()
@@ -1713,7 +1834,11 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
                             for accumulator in key_accumulators {
                                 accumulator.accumulate(&tuple);
                             }
+                            Ok(())
                         });
+                    if let Err(error) = grouping {
+                        return Box::new(once(Err(error)));
+                    }
                     let accumulator_variables = accumulator_variables.clone();
                     let dataset = dataset.clone();
                     Box::new(
@@ -2092,6 +2217,10 @@ impl<'a, D: QueryableDataset<'a>> SimpleEvaluator<'a, D> {
                     .map(|p| self.encode_term(p.clone()))
                     .collect::<Result<Rc<[_]>, _>>()?,
             ),
+            #[cfg(feature = "path-repetition")]
+            PropertyPathExpression::Repeat { path: p, min, max } => {
+                PropertyPath::Repeat(self.encode_property_path(p)?, *min, *max)
+            }
         }))
     }
 }
@@ -2104,6 +2233,7 @@ impl<'a, D: QueryableDataset<'a>> Clone for SimpleEvaluator<'a, D> {
             now: self.now,
             service_handler: Rc::clone(&self.service_handler),
             custom_functions: Rc::clone(&self.custom_functions),
+            limits: self.limits,
             custom_aggregate_functions: Rc::clone(&self.custom_aggregate_functions),
             run_stats: self.run_stats,
         }
@@ -2954,10 +3084,13 @@ pub enum PropertyPath<T> {
     OneOrMore(Rc<Self>),
     ZeroOrOne(Rc<Self>),
     NegatedPropertySet(Rc<[T]>),
+    #[cfg(feature = "path-repetition")]
+    Repeat(Rc<Self>, usize, usize),
 }
 
 struct PathEvaluator<'a, D: QueryableDataset<'a>> {
     dataset: EvalDataset<'a, D>,
+    max_visits: Option<usize>,
 }
 
 impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
@@ -2998,6 +3131,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                     self.is_subject_or_object_in_graph(start, graph_name)?
                 } else {
                     look_in_transitive_closure(
+                        self.max_visits,
                         self.eval_from_in_graph(p, start, graph_name),
                         move |e| self.eval_from_in_graph(p, &e, graph_name),
                         end,
@@ -3005,6 +3139,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 }
             }
             PropertyPath::OneOrMore(p) => look_in_transitive_closure(
+                self.max_visits,
                 self.eval_from_in_graph(p, start, graph_name),
                 move |e| self.eval_from_in_graph(p, &e, graph_name),
                 end,
@@ -3031,6 +3166,16 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 })
                 .transpose()?
                 .is_some(),
+            #[cfg(feature = "path-repetition")]
+            PropertyPath::Repeat(p, min, max) => {
+                if *min == 0 && start == end {
+                    self.is_subject_or_object_in_graph(start, graph_name)?
+                } else {
+                    self.eval_repeat_from_in_graph(p, *min, *max, start, graph_name)
+                        .collect::<Result<Vec<_>, _>>()?
+                        .contains(end)
+                }
+            }
         })
     }
 
@@ -3070,6 +3215,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let p = Rc::clone(p);
                 self.run_if_term_is_a_dataset_node(start, move |graph_name| {
                     look_in_transitive_closure(
+                        eval.max_visits,
                         Some(Ok(start2.clone())),
                         |e| eval.eval_from_in_graph(&p, &e, graph_name.as_ref()),
                         &end,
@@ -3087,6 +3233,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                         .filter_map(move |r| {
                             r.and_then(|(start, graph_name)| {
                                 look_in_transitive_closure(
+                                    eval.max_visits,
                                     Some(Ok(start)),
                                     |e| eval.eval_from_in_graph(&p, &e, graph_name.as_ref()),
                                     &end,
@@ -3129,6 +3276,24 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                         }),
                 )
             }
+            #[cfg(feature = "path-repetition")]
+            PropertyPath::Repeat(p, min, max) => {
+                if *min == 0 && start == end {
+                    self.run_if_term_is_a_dataset_node(start, |graph_name| Some(Ok(graph_name)))
+                } else {
+                    let eval = self.clone();
+                    let start2 = start.clone();
+                    let end = end.clone();
+                    let p = Rc::clone(p);
+                    let (min, max) = (*min, *max);
+                    self.run_if_term_is_a_dataset_node(start, move |graph_name| {
+                        eval.eval_repeat_from_in_graph(&p, min, max, &start2, graph_name.as_ref())
+                            .collect::<Result<Vec<_>, _>>()
+                            .map(|ends| ends.contains(&end).then_some(graph_name))
+                            .transpose()
+                    })
+                }
+            }
         }
     }
 
@@ -3165,7 +3330,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                     let eval = self.clone();
                     let p = Rc::clone(p);
                     let graph_name2 = graph_name.cloned();
-                    transitive_closure(Some(Ok(start.clone())), move |e| {
+                    transitive_closure(eval.max_visits, Some(Ok(start.clone())), move |e| {
                         eval.eval_from_in_graph(&p, &e, graph_name2.as_ref())
                     })
                 })
@@ -3175,6 +3340,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let p = Rc::clone(p);
                 let graph_name2 = graph_name.cloned();
                 Box::new(transitive_closure(
+                    eval.max_visits,
                     self.eval_from_in_graph(&p, start, graph_name),
                     move |e| eval.eval_from_in_graph(&p, &e, graph_name2.as_ref()),
                 ))
@@ -3204,6 +3370,10 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                         }),
                 )
             }
+            #[cfg(feature = "path-repetition")]
+            PropertyPath::Repeat(p, min, max) => {
+                self.eval_repeat_from_in_graph(p, *min, *max, start, graph_name)
+            }
         }
     }
 
@@ -3248,7 +3418,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                     let eval = eval.clone();
                     let p = Rc::clone(&p);
                     let graph_name2 = graph_name.clone();
-                    transitive_closure(Some(Ok(start2.clone())), move |e| {
+                    transitive_closure(eval.max_visits, Some(Ok(start2.clone())), move |e| {
                         eval.eval_from_in_graph(&p, &e, graph_name2.as_ref())
                     })
                     .map(move |e| Ok((e?, graph_name.clone())))
@@ -3258,6 +3428,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let eval = self.clone();
                 let p = Rc::clone(p);
                 Box::new(transitive_closure(
+                    eval.max_visits,
                     self.eval_from_in_unknown_graph(&p, start),
                     move |(e, graph_name)| {
                         eval.eval_from_in_graph(&p, &e, graph_name.as_ref())
@@ -3295,6 +3466,17 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                         }),
                 )
             }
+            #[cfg(feature = "path-repetition")]
+            PropertyPath::Repeat(p, min, max) => {
+                let eval = self.clone();
+                let start2 = start.clone();
+                let p = Rc::clone(p);
+                let (min, max) = (*min, *max);
+                self.run_if_term_is_a_dataset_node(start, move |graph_name| {
+                    eval.eval_repeat_from_in_graph(&p, min, max, &start2, graph_name.as_ref())
+                        .map(move |e| Ok((e?, graph_name.clone())))
+                })
+            }
         }
     }
 
@@ -3331,7 +3513,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                     let eval = self.clone();
                     let p = Rc::clone(p);
                     let graph_name2 = graph_name.cloned();
-                    transitive_closure(Some(Ok(end.clone())), move |e| {
+                    transitive_closure(eval.max_visits, Some(Ok(end.clone())), move |e| {
                         eval.eval_to_in_graph(&p, &e, graph_name2.as_ref())
                     })
                 })
@@ -3341,6 +3523,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let p = Rc::clone(p);
                 let graph_name2 = graph_name.cloned();
                 Box::new(transitive_closure(
+                    eval.max_visits,
                     self.eval_to_in_graph(&p, end, graph_name),
                     move |e| eval.eval_to_in_graph(&p, &e, graph_name2.as_ref()),
                 ))
@@ -3367,6 +3550,10 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                         }),
                 )
             }
+            #[cfg(feature = "path-repetition")]
+            PropertyPath::Repeat(p, min, max) => {
+                self.eval_repeat_to_in_graph(p, *min, *max, end, graph_name)
+            }
         }
     }
 
@@ -3411,7 +3598,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                     let eval = eval.clone();
                     let p = Rc::clone(&p);
                     let graph_name2 = graph_name.clone();
-                    transitive_closure(Some(Ok(end2.clone())), move |e| {
+                    transitive_closure(eval.max_visits, Some(Ok(end2.clone())), move |e| {
                         eval.eval_to_in_graph(&p, &e, graph_name2.as_ref())
                     })
                     .map(move |e| Ok((e?, graph_name.clone())))
@@ -3421,6 +3608,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let eval = self.clone();
                 let p = Rc::clone(p);
                 Box::new(transitive_closure(
+                    eval.max_visits,
                     self.eval_to_in_unknown_graph(&p, end),
                     move |(e, graph_name)| {
                         eval.eval_to_in_graph(&p, &e, graph_name.as_ref())
@@ -3458,6 +3646,17 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                         }),
                 )
             }
+            #[cfg(feature = "path-repetition")]
+            PropertyPath::Repeat(p, min, max) => {
+                let eval = self.clone();
+                let end2 = end.clone();
+                let p = Rc::clone(p);
+                let (min, max) = (*min, *max);
+                self.run_if_term_is_a_dataset_node(end, move |graph_name| {
+                    eval.eval_repeat_to_in_graph(&p, min, max, &end2, graph_name.as_ref())
+                        .map(move |e| Ok((e?, graph_name.clone())))
+                })
+            }
         }
     }
 
@@ -3501,6 +3700,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let p = Rc::clone(p);
                 let graph_name2 = graph_name.cloned();
                 Box::new(transitive_closure(
+                    eval.max_visits,
                     self.get_subject_or_object_identity_pairs_in_graph(graph_name),
                     move |(start, middle)| {
                         eval.eval_from_in_graph(&p, &middle, graph_name2.as_ref())
@@ -3513,6 +3713,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let p = Rc::clone(p);
                 let graph_name2 = graph_name.cloned();
                 Box::new(transitive_closure(
+                    eval.max_visits,
                     self.eval_open_in_graph(&p, graph_name),
                     move |(start, middle)| {
                         eval.eval_from_in_graph(&p, &middle, graph_name2.as_ref())
@@ -3541,6 +3742,32 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                         }),
                 )
             }
+            #[cfg(feature = "path-repetition")]
+            PropertyPath::Repeat(p, min, max) => {
+                // Without repetition, the pairs are the identity pairs, otherwise the first level
+                let (start, min, max): (Box<dyn Iterator<Item = _>>, _, _) = if *min == 0 {
+                    (
+                        Box::new(self.get_subject_or_object_identity_pairs_in_graph(graph_name)),
+                        0,
+                        *max,
+                    )
+                } else {
+                    (self.eval_open_in_graph(p, graph_name), *min - 1, *max - 1)
+                };
+                let eval = self.clone();
+                let p = Rc::clone(p);
+                let graph_name2 = graph_name.cloned();
+                Box::new(bounded_repetition(
+                    eval.max_visits,
+                    min,
+                    max,
+                    start,
+                    move |(start, middle)| {
+                        eval.eval_from_in_graph(&p, &middle, graph_name2.as_ref())
+                            .map(move |end| Ok((start.clone(), end?)))
+                    },
+                ))
+            }
         }
     }
 
@@ -3586,6 +3813,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let eval = self.clone();
                 let p = Rc::clone(p);
                 Box::new(transitive_closure(
+                    eval.max_visits,
                     self.get_subject_or_object_identity_pairs_in_dataset(),
                     move |(start, middle, graph_name)| {
                         eval.eval_from_in_graph(&p, &middle, graph_name.as_ref())
@@ -3597,6 +3825,7 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                 let eval = self.clone();
                 let p = Rc::clone(p);
                 Box::new(transitive_closure(
+                    eval.max_visits,
                     self.eval_open_in_unknown_graph(&p),
                     move |(start, middle, graph_name)| {
                         eval.eval_from_in_graph(&p, &middle, graph_name.as_ref())
@@ -3625,6 +3854,85 @@ impl<'a, D: QueryableDataset<'a>> PathEvaluator<'a, D> {
                         }),
                 )
             }
+            #[cfg(feature = "path-repetition")]
+            PropertyPath::Repeat(p, min, max) => {
+                // Without repetition, the pairs are the identity pairs, otherwise the first level
+                let (start, min, max): (Box<dyn Iterator<Item = _>>, _, _) = if *min == 0 {
+                    (
+                        Box::new(self.get_subject_or_object_identity_pairs_in_dataset()),
+                        0,
+                        *max,
+                    )
+                } else {
+                    (self.eval_open_in_unknown_graph(p), *min - 1, *max - 1)
+                };
+                let eval = self.clone();
+                let p = Rc::clone(p);
+                Box::new(bounded_repetition(
+                    eval.max_visits,
+                    min,
+                    max,
+                    start,
+                    move |(start, middle, graph_name)| {
+                        eval.eval_from_in_graph(&p, &middle, graph_name.as_ref())
+                            .map(move |end| Ok((start.clone(), end?, graph_name.clone())))
+                    },
+                ))
+            }
+        }
+    }
+
+    /// Evaluates `path{min,max}` from `start`, the start being returned if `min` is 0 and it is in the graph
+    #[cfg(feature = "path-repetition")]
+    fn eval_repeat_from_in_graph(
+        &self,
+        path: &Rc<PropertyPath<D::InternalTerm>>,
+        min: usize,
+        max: usize,
+        start: &D::InternalTerm,
+        graph_name: Option<&D::InternalTerm>,
+    ) -> Box<dyn Iterator<Item = Result<D::InternalTerm, QueryEvaluationError>> + 'a> {
+        let repeat = || {
+            let eval = self.clone();
+            let path = Rc::clone(path);
+            let graph_name2 = graph_name.cloned();
+            bounded_repetition(
+                eval.max_visits,
+                min,
+                max,
+                Some(Ok(start.clone())),
+                move |e| eval.eval_from_in_graph(&path, &e, graph_name2.as_ref()),
+            )
+        };
+        if min == 0 {
+            self.run_if_term_is_a_graph_node(start, graph_name, repeat)
+        } else {
+            Box::new(repeat())
+        }
+    }
+
+    /// Evaluates `path{min,max}` to `end`, the end being returned if `min` is 0 and it is in the graph
+    #[cfg(feature = "path-repetition")]
+    fn eval_repeat_to_in_graph(
+        &self,
+        path: &Rc<PropertyPath<D::InternalTerm>>,
+        min: usize,
+        max: usize,
+        end: &D::InternalTerm,
+        graph_name: Option<&D::InternalTerm>,
+    ) -> Box<dyn Iterator<Item = Result<D::InternalTerm, QueryEvaluationError>> + 'a> {
+        let repeat = || {
+            let eval = self.clone();
+            let path = Rc::clone(path);
+            let graph_name2 = graph_name.cloned();
+            bounded_repetition(eval.max_visits, min, max, Some(Ok(end.clone())), move |e| {
+                eval.eval_to_in_graph(&path, &e, graph_name2.as_ref())
+            })
+        };
+        if min == 0 {
+            self.run_if_term_is_a_graph_node(end, graph_name, repeat)
+        } else {
+            Box::new(repeat())
         }
     }
 
@@ -3734,6 +4042,7 @@ impl<'a, D: QueryableDataset<'a>> Clone for PathEvaluator<'a, D> {
     fn clone(&self) -> Self {
         Self {
             dataset: self.dataset.clone(),
+            max_visits: self.max_visits,
         }
     }
 }
@@ -3914,6 +4223,274 @@ impl<T: Clone> Iterator for UnionIterator<'_, T> {
     }
 }
 
+type TupleComparator<'a, T> = Rc<dyn Fn(&InternalTuple<T>, &InternalTuple<T>) -> Ordering + 'a>;
+
+/// Rough estimation of the memory used by a tuple, ignoring the memory owned by the terms.
+fn tuple_memory_size<T>(tuple: &InternalTuple<T>) -> usize {
+    size_of::<InternalTuple<T>>() + tuple.capacity() * size_of::<Option<T>>()
+}
+
+/// A sorted run of tuples spilled to a temporary file.
+///
+/// Each tuple is written on its own line, with its terms in N-Triples syntax separated by tabs.
+/// The file is removed when the run is dropped.
+struct SpilledRun {
+    path: PathBuf,
+    reader: BufReader<File>,
+    line: String,
+}
+
+impl SpilledRun {
+    fn new<'a, D: QueryableDataset<'a>>(
+        tuples: impl IntoIterator<Item = Result<InternalTuple<D::InternalTerm>, QueryEvaluationError>>,
+        dataset: &EvalDataset<'a, D>,
+    ) -> Result<Self, QueryEvaluationError> {
+        let path = env::temp_dir().join(format!(
+            "spareval-sort-{}-{:032x}.tmp",
+            process::id(),
+            random::<u128>()
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(QueryEvaluationError::SortSpill)?;
+        // We build the run first to remove the file if the writing fails
+        let mut run = Self {
+            path,
+            reader: BufReader::new(file),
+            line: String::new(),
+        };
+        let mut writer = BufWriter::new(run.reader.get_mut());
+        for tuple in tuples {
+            for (i, term) in tuple?.iter().enumerate() {
+                if i > 0 {
+                    writer
+                        .write_all(b"\t")
+                        .map_err(QueryEvaluationError::SortSpill)?;
+                }
+                if let Some(term) = term {
+                    write!(writer, "{}", dataset.externalize_term(term)?)
+                        .map_err(QueryEvaluationError::SortSpill)?;
+                }
+            }
+            writer
+                .write_all(b"\n")
+                .map_err(QueryEvaluationError::SortSpill)?;
+        }
+        writer.flush().map_err(QueryEvaluationError::SortSpill)?;
+        drop(writer);
+        run.reader
+            .rewind()
+            .map_err(QueryEvaluationError::SortSpill)?;
+        Ok(run)
+    }
+
+    fn next_tuple<'a, D: QueryableDataset<'a>>(
+        &mut self,
+        dataset: &EvalDataset<'a, D>,
+    ) -> Option<Result<InternalTuple<D::InternalTerm>, QueryEvaluationError>> {
+        self.line.clear();
+        match self.reader.read_line(&mut self.line) {
+            Ok(0) => return None,
+            Ok(_) => (),
+            Err(e) => return Some(Err(QueryEvaluationError::SortSpill(e))),
+        }
+        let line = self.line.strip_suffix('\n').unwrap_or(&self.line);
+        let mut tuple = InternalTuple::with_capacity(line.split('\t').count());
+        for (i, term) in line.split('\t').enumerate() {
+            if term.is_empty() {
+                continue;
+            }
+            let term = match Term::from_str(term) {
+                Ok(term) => term,
+                Err(e) => {
+                    return Some(Err(QueryEvaluationError::SortSpill(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        e,
+                    ))));
+                }
+            };
+            match dataset.internalize_term(term) {
+                Ok(term) => tuple.set(i, term),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        Some(Ok(tuple))
+    }
+}
+
+impl Drop for SpilledRun {
+    fn drop(&mut self) {
+        #[expect(unused_must_use)] // There is no way to report the error from drop
+        remove_file(&self.path);
+    }
+}
+
+/// Maximal number of spilled runs merged at once.
+///
+/// Each run keeps its temporary file open so merging more runs at once could exhaust the file descriptors.
+const MAX_MERGED_SPILLED_RUNS: usize = 64;
+
+/// Sorted runs spilled to temporary files, from the oldest to the newest.
+///
+/// Runs are organized in levels: when a level gets [`MAX_MERGED_SPILLED_RUNS`] runs,
+/// they are merged into a single run of the next level.
+/// The runs of a level are all older than the runs of the levels below,
+/// so the number of open files stays logarithmic in the number of spilled tuples
+/// and the merge is still stable.
+struct SpilledRuns<'a, D: QueryableDataset<'a>> {
+    levels: Vec<Vec<SpilledRun>>,
+    dataset: EvalDataset<'a, D>,
+    compare: TupleComparator<'a, D::InternalTerm>,
+}
+
+impl<'a, D: QueryableDataset<'a>> SpilledRuns<'a, D> {
+    fn new(dataset: EvalDataset<'a, D>, compare: TupleComparator<'a, D::InternalTerm>) -> Self {
+        Self {
+            levels: Vec::new(),
+            dataset,
+            compare,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.levels.iter().all(Vec::is_empty)
+    }
+
+    fn push(&mut self, run: SpilledRun) -> Result<(), QueryEvaluationError> {
+        self.push_to_level(0, run);
+        let mut level = 0;
+        while self.levels[level].len() >= MAX_MERGED_SPILLED_RUNS {
+            self.merge_level(level)?;
+            level += 1;
+        }
+        Ok(())
+    }
+
+    /// Merges all the runs of `level` into a single run of the next level.
+    fn merge_level(&mut self, level: usize) -> Result<(), QueryEvaluationError> {
+        let run = match <[_; 1]>::try_from(take(&mut self.levels[level])) {
+            Ok([run]) => run,
+            Err(runs) => SpilledRun::new(
+                SpilledRunsMerge::new(runs, self.dataset.clone(), &self.compare),
+                &self.dataset,
+            )?,
+        };
+        self.push_to_level(level + 1, run);
+        Ok(())
+    }
+
+    fn push_to_level(&mut self, level: usize, run: SpilledRun) {
+        if self.levels.len() <= level {
+            self.levels.resize_with(level + 1, Vec::new);
+        }
+        self.levels[level].push(run);
+    }
+
+    /// Returns the merge of all the runs.
+    fn into_merge(mut self) -> Result<SpilledRunsMerge<'a, D>, QueryEvaluationError> {
+        // We merge the newest runs first until the remaining ones can be merged at once
+        let mut level = 0;
+        while self.levels.iter().map(Vec::len).sum::<usize>() > MAX_MERGED_SPILLED_RUNS {
+            if !self.levels[level].is_empty() {
+                self.merge_level(level)?;
+            }
+            level += 1;
+        }
+        let runs = self.levels.into_iter().rev().flatten().collect();
+        Ok(SpilledRunsMerge::new(runs, self.dataset, &self.compare))
+    }
+}
+
+/// Merges sorted spilled runs given from the oldest to the newest.
+///
+/// Ties are broken by taking first the tuple of the oldest run so that the result is the same as a stable sort.
+struct SpilledRunsMerge<'a, D: QueryableDataset<'a>> {
+    runs: Vec<SpilledRun>,
+    heads: BinaryHeap<SpilledRunHead<'a, D::InternalTerm>>,
+    errors: Vec<QueryEvaluationError>,
+    dataset: EvalDataset<'a, D>,
+}
+
+impl<'a, D: QueryableDataset<'a>> SpilledRunsMerge<'a, D> {
+    fn new(
+        runs: Vec<SpilledRun>,
+        dataset: EvalDataset<'a, D>,
+        compare: &TupleComparator<'a, D::InternalTerm>,
+    ) -> Self {
+        let mut merge = Self {
+            heads: BinaryHeap::with_capacity(runs.len()),
+            runs,
+            errors: Vec::new(),
+            dataset,
+        };
+        for run in 0..merge.runs.len() {
+            merge.read_head(run, Rc::clone(compare));
+        }
+        merge
+    }
+
+    fn read_head(&mut self, run: usize, compare: TupleComparator<'a, D::InternalTerm>) {
+        match self.runs[run].next_tuple(&self.dataset) {
+            Some(Ok(tuple)) => self.heads.push(SpilledRunHead {
+                tuple,
+                run,
+                compare,
+            }),
+            // Errors end their run
+            Some(Err(e)) => self.errors.push(e),
+            None => (),
+        }
+    }
+}
+
+impl<'a, D: QueryableDataset<'a>> Iterator for SpilledRunsMerge<'a, D> {
+    type Item = Result<InternalTuple<D::InternalTerm>, QueryEvaluationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.errors.pop() {
+            return Some(Err(error));
+        }
+        let SpilledRunHead {
+            tuple,
+            run,
+            compare,
+        } = self.heads.pop()?;
+        self.read_head(run, compare);
+        Some(Ok(tuple))
+    }
+}
+
+/// The next tuple of a spilled run, ordered such that the greatest head is the one to return first.
+struct SpilledRunHead<'a, T> {
+    tuple: InternalTuple<T>,
+    run: usize,
+    compare: TupleComparator<'a, T>,
+}
+
+impl<T> PartialEq for SpilledRunHead<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for SpilledRunHead<'_, T> {}
+
+impl<T> PartialOrd for SpilledRunHead<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for SpilledRunHead<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max heap: the smallest tuple, then the one of the oldest run, must be the greatest
+        (self.compare)(&other.tuple, &self.tuple).then_with(|| other.run.cmp(&self.run))
+    }
+}
+
 struct ConsecutiveDeduplication<'a, T> {
     inner: InternalTuplesIterator<'a, T>,
     current: Option<InternalTuple<T>>,
@@ -4141,13 +4718,67 @@ fn get_triple_template_value<'a, D: QueryableDataset<'a>>(
     }
 }
 
+/// How the resources returned by a `DESCRIBE` query are described.
+///
+/// The description of a resource is made of triples of the default graph.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum DescribeStrategy {
+    /// [Concise Bounded Description](https://www.w3.org/submission/CBD/):
+    /// the triples whose subject is the resource,
+    /// recursively completed by the description of the blank nodes in object position.
+    #[default]
+    Cbd,
+    /// [Symmetric Concise Bounded Description](https://www.w3.org/submission/CBD/#alternatives):
+    /// the triples whose subject or object is the resource,
+    /// recursively completed by the symmetric description of the blank nodes they contain.
+    SymmetricCbd,
+    /// Only the triples whose subject is the resource.
+    ForwardOnly,
+    /// Like [`Cbd`](Self::Cbd) but the blank nodes are only followed up to `max_depth` hops from the resource.
+    ///
+    /// `BlankNodeClosure { max_depth: 0 }` is the same as [`ForwardOnly`](Self::ForwardOnly).
+    BlankNodeClosure { max_depth: usize },
+}
+
 struct DescribeIterator<'a, D: QueryableDataset<'a>> {
     eval: SimpleEvaluator<'a, D>,
     tuples_to_describe: InternalTuplesIterator<'a, D::InternalTerm>,
-    nodes_described: FxHashSet<D::InternalTerm>,
-    nodes_to_describe: Vec<D::InternalTerm>,
-    quads:
-        Box<dyn Iterator<Item = Result<InternalQuad<D::InternalTerm>, QueryEvaluationError>> + 'a>,
+    symmetric: bool,
+    max_depth: Option<usize>,
+    /// The smallest depth at which each node has been described
+    nodes_described: FxHashMap<D::InternalTerm, usize>,
+    /// Nodes to describe with their depth and if their triples should be emitted
+    nodes_to_describe: VecDeque<(D::InternalTerm, usize, bool)>,
+    current_depth: usize,
+    current_emit: bool,
+    /// Only set if the same triple might be found from its subject and from its object
+    emitted_triples: Option<FxHashSet<(D::InternalTerm, D::InternalTerm, D::InternalTerm)>>,
+    /// The quads of the currently described node with `true` if the node is their object
+    quads: Box<
+        dyn Iterator<Item = Result<(InternalQuad<D::InternalTerm>, bool), QueryEvaluationError>>
+            + 'a,
+    >,
+}
+
+impl<'a, D: QueryableDataset<'a>> DescribeIterator<'a, D> {
+    fn push_node(&mut self, node: D::InternalTerm, depth: usize) {
+        match self.nodes_described.entry(node) {
+            Entry::Vacant(entry) => {
+                self.nodes_to_describe
+                    .push_back((entry.key().clone(), depth, true));
+                entry.insert(depth);
+            }
+            Entry::Occupied(mut entry) => {
+                // The node has already been described but its blank node closure might have been cut
+                if self.max_depth.is_some() && depth < *entry.get() {
+                    self.nodes_to_describe
+                        .push_back((entry.key().clone(), depth, false));
+                    entry.insert(depth);
+                }
+            }
+        }
+    }
 }
 
 impl<'a, D: QueryableDataset<'a>> Iterator for DescribeIterator<'a, D> {
@@ -4156,60 +4787,106 @@ impl<'a, D: QueryableDataset<'a>> Iterator for DescribeIterator<'a, D> {
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             if let Some(quad) = self.quads.next() {
-                let quad = match quad {
+                let (quad, incoming) = match quad {
                     Ok(quad) => quad,
                     Err(error) => return Some(Err(error)),
                 };
-                // We yield the triple
-                let subject = match self.eval.dataset.externalize_term(quad.subject) {
-                    Ok(t) => t,
-                    Err(e) => return Some(Err(e)),
+                let neighbor = if incoming {
+                    quad.subject.clone()
+                } else {
+                    quad.object.clone()
                 };
-                let predicate = match self.eval.dataset.externalize_term(quad.predicate) {
+                let neighbor_term = match self.eval.dataset.externalize_term(neighbor.clone()) {
                     Ok(t) => t,
                     Err(e) => return Some(Err(e)),
                 };
-                let object = match self.eval.dataset.externalize_term(quad.object.clone()) {
+                // If there is a blank node neighbor, we need to describe it too
+                if neighbor_term.is_blank_node()
+                    && self
+                        .max_depth
+                        .is_none_or(|max_depth| self.current_depth < max_depth)
+                {
+                    self.push_node(neighbor, self.current_depth + 1);
+                }
+                if !self.current_emit {
+                    continue;
+                }
+                if let Some(emitted_triples) = &mut self.emitted_triples {
+                    if !emitted_triples.insert((
+                        quad.subject.clone(),
+                        quad.predicate.clone(),
+                        quad.object.clone(),
+                    )) {
+                        continue;
+                    }
+                }
+                // We yield the triple
+                let (subject, object) = if incoming {
+                    let object = match self.eval.dataset.externalize_term(quad.object) {
+                        Ok(t) => t,
+                        Err(e) => return Some(Err(e)),
+                    };
+                    (neighbor_term, object)
+                } else {
+                    let subject = match self.eval.dataset.externalize_term(quad.subject) {
+                        Ok(t) => t,
+                        Err(e) => return Some(Err(e)),
+                    };
+                    (subject, neighbor_term)
+                };
+                let predicate = match self.eval.dataset.externalize_term(quad.predicate) {
                     Ok(t) => t,
                     Err(e) => return Some(Err(e)),
                 };
-                // If there is a blank node object, we need to describe it too
-                if object.is_blank_node() && self.nodes_described.insert(quad.object.clone()) {
-                    self.nodes_to_describe.push(quad.object);
-                }
                 return Some(Ok(Triple {
                     subject: subject.try_into().ok()?,
                     predicate: predicate.try_into().ok()?,
                     object,
                 }));
             }
-            if let Some(node_to_describe) = self.nodes_to_describe.pop() {
+            if let Some((node_to_describe, depth, emit)) = self.nodes_to_describe.pop_front() {
                 // We have a new node to describe
-                self.quads = self.eval.dataset.internal_quads_for_pattern(
-                    Some(&node_to_describe),
-                    None,
-                    None,
-                    Some(None),
-                );
+                self.current_depth = depth;
+                self.current_emit = emit;
+                let outgoing = self
+                    .eval
+                    .dataset
+                    .internal_quads_for_pattern(Some(&node_to_describe), None, None, Some(None))
+                    .map(|quad| Ok((quad?, false)));
+                self.quads = if self.symmetric {
+                    let incoming = self
+                        .eval
+                        .dataset
+                        .internal_quads_for_pattern(None, None, Some(&node_to_describe), Some(None))
+                        .map(|quad| Ok((quad?, true)));
+                    Box::new(outgoing.chain(incoming))
+                } else {
+                    Box::new(outgoing)
+                };
             } else {
                 let tuple = match self.tuples_to_describe.next()? {
                     Ok(tuple) => tuple,
                     Err(error) => return Some(Err(error)),
                 };
                 for node in tuple.into_iter().flatten() {
-                    if self.nodes_described.insert(node.clone()) {
-                        self.nodes_to_describe.push(node);
-                    }
+                    self.push_node(node, 0);
                 }
             }
         }
     }
 }
 
-fn transitive_closure<T: Clone + Eq + Hash, E, NI: Iterator<Item = Result<T, E>>>(
-    start: impl IntoIterator<Item = Result<T, E>>,
+/// Computes the transitive closure of `start` following `next`.
+///
+/// Fails with [`QueryEvaluationError::PropertyPathDepthExceeded`] if more than `max_visits` nodes are visited.
+fn transitive_closure<
+    T: Clone + Eq + Hash,
+    NI: Iterator<Item = Result<T, QueryEvaluationError>>,
+>(
+    max_visits: Option<usize>,
+    start: impl IntoIterator<Item = Result<T, QueryEvaluationError>>,
     mut next: impl FnMut(T) -> NI,
-) -> impl Iterator<Item = Result<T, E>> {
+) -> impl Iterator<Item = Result<T, QueryEvaluationError>> {
     let mut errors = Vec::new();
     let mut todo = start
         .into_iter()
@@ -4223,6 +4900,13 @@ fn transitive_closure<T: Clone + Eq + Hash, E, NI: Iterator<Item = Result<T, E>>
         .collect::<Vec<_>>();
     let mut all = todo.iter().cloned().collect::<FxHashSet<_>>();
     while let Some(e) = todo.pop() {
+        // Each newly visited node is in todo so the check covers the whole closure
+        if let Err(e) = check_path_visits(max_visits, all.len()) {
+            // We do not return a partial closure
+            all.clear();
+            errors.push(e);
+            break;
+        }
         for e in next(e) {
             match e {
                 Ok(e) => {
@@ -4237,13 +4921,21 @@ fn transitive_closure<T: Clone + Eq + Hash, E, NI: Iterator<Item = Result<T, E>>
     errors.into_iter().map(Err).chain(all.into_iter().map(Ok))
 }
 
-fn look_in_transitive_closure<T: Clone + Eq + Hash, E, NI: Iterator<Item = Result<T, E>>>(
-    start: impl IntoIterator<Item = Result<T, E>>,
+/// Checks if `target` is in the transitive closure of `start` following `next`.
+///
+/// Fails with [`QueryEvaluationError::PropertyPathDepthExceeded`] if more than `max_visits` nodes are visited.
+fn look_in_transitive_closure<
+    T: Clone + Eq + Hash,
+    NI: Iterator<Item = Result<T, QueryEvaluationError>>,
+>(
+    max_visits: Option<usize>,
+    start: impl IntoIterator<Item = Result<T, QueryEvaluationError>>,
     mut next: impl FnMut(T) -> NI,
     target: &T,
-) -> Result<bool, E> {
+) -> Result<bool, QueryEvaluationError> {
     let mut todo = start.into_iter().collect::<Result<Vec<_>, _>>()?;
     let mut all = todo.iter().cloned().collect::<FxHashSet<_>>();
+    check_path_visits(max_visits, all.len())?;
     while let Some(e) = todo.pop() {
         if e == *target {
             return Ok(true);
@@ -4251,6 +4943,7 @@ fn look_in_transitive_closure<T: Clone + Eq + Hash, E, NI: Iterator<Item = Resul
         for e in next(e) {
             let e = e?;
             if all.insert(e.clone()) {
+                check_path_visits(max_visits, all.len())?;
                 todo.push(e);
             }
         }
@@ -4258,6 +4951,83 @@ fn look_in_transitive_closure<T: Clone + Eq + Hash, E, NI: Iterator<Item = Resul
     Ok(false)
 }
 
+/// Computes the elements reachable from `start` following `next` between `min` and `max` times.
+///
+/// The repetitions are evaluated level by level, each level being deduplicated, so the work is
+/// bounded by `max` times the number of distinct elements even on dense or cyclic graphs.
+/// Once `min` is reached, the elements already returned are not expanded again:
+/// they were reached earlier, with more repetitions left.
+///
+/// Fails with [`QueryEvaluationError::PropertyPathDepthExceeded`] if more than `max_visits` elements are visited.
+#[cfg(feature = "path-repetition")]
+fn bounded_repetition<
+    T: Clone + Eq + Hash,
+    NI: Iterator<Item = Result<T, QueryEvaluationError>>,
+>(
+    max_visits: Option<usize>,
+    min: usize,
+    max: usize,
+    start: impl IntoIterator<Item = Result<T, QueryEvaluationError>>,
+    mut next: impl FnMut(T) -> NI,
+) -> impl Iterator<Item = Result<T, QueryEvaluationError>> {
+    let mut errors = Vec::new();
+    let mut frontier = start
+        .into_iter()
+        .filter_map(|e| match e {
+            Ok(e) => Some(e),
+            Err(e) => {
+                errors.push(e);
+                None
+            }
+        })
+        .collect::<FxHashSet<_>>();
+    let mut all = if min == 0 {
+        frontier.clone()
+    } else {
+        FxHashSet::default()
+    };
+    let mut visited = frontier.len();
+    for level in 1..=max {
+        if frontier.is_empty() {
+            break;
+        }
+        let mut next_frontier = FxHashSet::default();
+        for e in frontier {
+            for e in next(e) {
+                match e {
+                    Ok(e) => {
+                        if level < min || all.insert(e.clone()) {
+                            next_frontier.insert(e);
+                        }
+                    }
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
+        visited += next_frontier.len();
+        if let Err(e) = check_path_visits(max_visits, visited) {
+            // We do not return a partial result
+            all.clear();
+            errors.push(e);
+            break;
+        }
+        frontier = next_frontier;
+    }
+    errors.into_iter().map(Err).chain(all.into_iter().map(Ok))
+}
+
+fn check_path_visits(
+    max_visits: Option<usize>,
+    visited: usize,
+) -> Result<(), QueryEvaluationError> {
+    if let Some(max_visits) = max_visits {
+        if visited > max_visits {
+            return Err(QueryEvaluationError::PropertyPathDepthExceeded(max_visits));
+        }
+    }
+    Ok(())
+}
+
 fn hash_deduplicate<T: Eq + Hash + Clone, E>(
     iter: impl Iterator<Item = Result<T, E>>,
 ) -> impl Iterator<Item = Result<T, E>> {
@@ -4276,6 +5046,34 @@ fn hash_deduplicate<T: Eq + Hash + Clone, E>(
     })
 }
 
+/// Like [`hash_deduplicate`] but fails if there are more than `max_size` distinct elements.
+fn limited_hash_deduplicate<T: Eq + Hash + Clone>(
+    iter: impl Iterator<Item = Result<T, QueryEvaluationError>>,
+    max_size: usize,
+) -> impl Iterator<Item = Result<T, QueryEvaluationError>> {
+    let mut already_seen = FxHashSet::default();
+    iter.scan(false, move |limit_exceeded, e| {
+        if *limit_exceeded {
+            return None; // We stop the evaluation
+        }
+        Some(match e {
+            Ok(e) => {
+                if already_seen.contains(&e) {
+                    None
+                } else if already_seen.len() >= max_size {
+                    *limit_exceeded = true;
+                    Some(Err(QueryEvaluationError::GroupLimitExceeded(max_size)))
+                } else {
+                    already_seen.insert(e.clone());
+                    Some(Ok(e))
+                }
+            }
+            Err(e) => Some(Err(e)),
+        })
+    })
+    .flatten()
+}
+
 trait ResultIterator<T, E>: Iterator<Item = Result<T, E>> + Sized {
     fn flat_map_ok<O, F: FnMut(T) -> U, U: IntoIterator<Item = Result<O, E>>>(
         self,
@@ -4431,6 +5229,10 @@ pub struct EvalNodeWithStats {
     pub children: Vec<Rc<EvalNodeWithStats>>,
     pub exec_count: Cell<usize>,
     pub exec_duration: Cell<Option<DayTimeDuration>>,
+    /// The number of results estimated by the optimizer
+    pub estimated_size: Option<usize>,
+    /// The indexes used by the quad pattern lookups
+    pub used_indexes: Cell<UsedIndexes>,
 }
 
 impl EvalNodeWithStats {
@@ -4440,9 +5242,36 @@ impl EvalNodeWithStats {
             children: Vec::new(),
             exec_count: Cell::new(0),
             exec_duration: Cell::new(None),
+            estimated_size: None,
+            used_indexes: Cell::new(UsedIndexes::default()),
         }
     }
 
+    /// Writes the node and its children as an indented tree.
+    pub fn fmt_tree(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:width$}{}", "", self.label, width = 2 * depth)?;
+        let mut annotations = Vec::new();
+        if let Some(estimated_size) = self.estimated_size {
+            annotations.push(format!("estimated results: {estimated_size}"));
+        }
+        if let Some(duration) = self.exec_duration.get() {
+            annotations.push(format!("results: {}", self.exec_count.get()));
+            annotations.push(format!("duration: {}s", duration.as_seconds()));
+        }
+        let used_indexes = self.used_indexes.get();
+        if !used_indexes.is_empty() {
+            annotations.push(format!("indexes: {used_indexes}"));
+        }
+        if !annotations.is_empty() {
+            write!(f, " [{}]", annotations.join(", "))?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.fmt_tree(f, depth + 1)?;
+        }
+        Ok(())
+    }
+
     pub fn json_node(
         &self,
         serializer: &mut WriterJsonSerializer<impl io::Write>,
@@ -4460,6 +5289,20 @@ impl EvalNodeWithStats {
                 serializer
                     .serialize_event(JsonEvent::Number(duration.as_seconds().to_string().into()))?;
             }
+            if let Some(estimated_size) = self.estimated_size {
+                serializer
+                    .serialize_event(JsonEvent::ObjectKey("estimated number of results".into()))?;
+                serializer.serialize_event(JsonEvent::Number(estimated_size.to_string().into()))?;
+            }
+            let used_indexes = self.used_indexes.get();
+            if !used_indexes.is_empty() {
+                serializer.serialize_event(JsonEvent::ObjectKey("used indexes".into()))?;
+                serializer.serialize_event(JsonEvent::StartArray)?;
+                for index in used_indexes.names() {
+                    serializer.serialize_event(JsonEvent::String(index.into()))?;
+                }
+                serializer.serialize_event(JsonEvent::EndArray)?;
+            }
         }
         serializer.serialize_event(JsonEvent::ObjectKey("children".into()))?;
         serializer.serialize_event(JsonEvent::StartArray)?;
@@ -4482,6 +5325,13 @@ impl fmt::Debug for EvalNodeWithStats {
                 &f32::from(Float::from(exec_duration.as_seconds())),
             );
         }
+        if let Some(estimated_size) = self.estimated_size {
+            obj.field("estimated number of results", &estimated_size);
+        }
+        let used_indexes = self.used_indexes.get();
+        if !used_indexes.is_empty() {
+            obj.field("used indexes", &format_args!("{used_indexes}"));
+        }
         if !self.children.is_empty() {
             obj.field("children", &self.children);
         }
@@ -4489,6 +5339,78 @@ impl fmt::Debug for EvalNodeWithStats {
     }
 }
 
+/// The triple indexes able to answer the lookups of a quad pattern.
+///
+/// The index depends on which of the subject, predicate and object are bound during the lookup.
+#[derive(Clone, Copy, Default)]
+pub struct UsedIndexes {
+    spo: bool,
+    pos: bool,
+    osp: bool,
+}
+
+impl UsedIndexes {
+    fn with_lookup(
+        mut self,
+        subject_bound: bool,
+        predicate_bound: bool,
+        object_bound: bool,
+    ) -> Self {
+        match (subject_bound, predicate_bound, object_bound) {
+            (false, true, _) => self.pos = true,
+            (_, false, true) => self.osp = true,
+            _ => self.spo = true,
+        }
+        self
+    }
+
+    pub fn is_empty(self) -> bool {
+        !self.spo && !self.pos && !self.osp
+    }
+
+    pub fn names(self) -> impl Iterator<Item = &'static str> {
+        [(self.spo, "SPO"), (self.pos, "POS"), (self.osp, "OSP")]
+            .into_iter()
+            .filter_map(|(used, name)| used.then_some(name))
+    }
+}
+
+impl fmt::Display for UsedIndexes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_list(self.names()))
+    }
+}
+
+/// Adds to `ids` the variables that must be bound for the pattern value to be known.
+fn ground_term_pattern_variables(
+    pattern: &GroundTermPattern,
+    encoded_variables: &mut Vec<Variable>,
+    ids: &mut Vec<usize>,
+) {
+    match pattern {
+        GroundTermPattern::Variable(variable) => {
+            ids.push(encode_variable(encoded_variables, variable))
+        }
+        #[cfg(feature = "sparql-12")]
+        GroundTermPattern::Triple(triple) => {
+            ground_term_pattern_variables(&triple.subject, encoded_variables, ids);
+            named_node_pattern_variables(&triple.predicate, encoded_variables, ids);
+            ground_term_pattern_variables(&triple.object, encoded_variables, ids);
+        }
+        _ => (),
+    }
+}
+
+fn named_node_pattern_variables(
+    pattern: &NamedNodePattern,
+    encoded_variables: &mut Vec<Variable>,
+    ids: &mut Vec<usize>,
+) {
+    if let NamedNodePattern::Variable(variable) = pattern {
+        ids.push(encode_variable(encoded_variables, variable));
+    }
+}
+
 fn eval_node_label(node: &GraphPattern) -> String {
     match node {
         GraphPattern::Distinct { .. } => "Distinct(Hash)".to_owned(),
@@ -4665,6 +5587,61 @@ impl Timer {
 #[derive(Clone, Default)]
 pub struct CancellationToken {
     value: Arc<AtomicBool>,
+    deadline: Option<Deadline>,
+}
+
+/// The time after which an evaluation is aborted with [`QueryEvaluationError::Timeout`].
+#[derive(Clone)]
+struct Deadline {
+    // The monotonic clock is not available on wasm32-unknown-unknown
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    end: Instant,
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    end: DateTime,
+    timeout: Duration,
+    /// Number of calls to [`CancellationToken::ensure_alive`], used to only read the clock from time to time
+    checks: Arc<AtomicU32>,
+}
+
+impl Deadline {
+    /// Number of calls to [`CancellationToken::ensure_alive`] between two reads of the clock
+    const CHECK_INTERVAL: u32 = 256;
+
+    /// Returns `None` if the timeout is too large to be represented, it then never expires
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    fn new(timeout: Duration) -> Option<Self> {
+        Some(Self {
+            end: Instant::now().checked_add(timeout)?,
+            timeout,
+            checks: Arc::default(),
+        })
+    }
+
+    /// Returns `None` if the timeout is too large to be represented, it then never expires
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    fn new(timeout: Duration) -> Option<Self> {
+        Some(Self {
+            end: DateTime::now()
+                .checked_add_day_time_duration(DayTimeDuration::try_from(timeout).ok()?)?,
+            timeout,
+            checks: Arc::default(),
+        })
+    }
+
+    fn is_exceeded(&self) -> bool {
+        if !self
+            .checks
+            .fetch_add(1, atomic::Ordering::Relaxed)
+            .is_multiple_of(Self::CHECK_INTERVAL)
+        {
+            return false;
+        }
+        #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+        let now = Instant::now();
+        #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+        let now = DateTime::now();
+        now > self.end
+    }
 }
 
 impl CancellationToken {
@@ -4672,6 +5649,17 @@ impl CancellationToken {
     pub fn new() -> Self {
         Self {
             value: Arc::new(AtomicBool::new(false)),
+            deadline: None,
+        }
+    }
+
+    /// Returns a token sharing the cancellation state of this one that also expires after `timeout`.
+    ///
+    /// The timeout starts now.
+    pub(crate) fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            deadline: Deadline::new(timeout),
         }
     }
 
@@ -4687,9 +5675,13 @@ impl CancellationToken {
 
     fn ensure_alive(&self) -> Result<(), QueryEvaluationError> {
         if self.is_cancelled() {
-            Err(QueryEvaluationError::Cancelled)
-        } else {
-            Ok(())
+            return Err(QueryEvaluationError::Cancelled);
+        }
+        if let Some(deadline) = &self.deadline {
+            if deadline.is_exceeded() {
+                return Err(QueryEvaluationError::Timeout(deadline.timeout));
+            }
         }
+        Ok(())
     }
 }
@@ -9,6 +9,7 @@ mod error;
 mod eval;
 mod expression;
 mod limits;
+mod lru;
 mod model;
 mod n3_builtins;
 mod service;
@@ -18,13 +19,14 @@ mod update;
 pub use crate::dataset::ExpressionTriple;
 pub use crate::dataset::{ExpressionTerm, InternalQuad, QueryableDataset};
 pub use crate::error::QueryEvaluationError;
-pub use crate::eval::CancellationToken;
-pub use crate::limits::QueryExecutionLimits;
-pub use crate::n3_builtins::{get_all_n3_builtins, N3BuiltinFn};
-use crate::eval::{EvalNodeWithStats, SimpleEvaluator, Timer};
+pub use crate::eval::{CancellationToken, DescribeStrategy};
+use crate::eval::{EvalNodeWithStats, EvaluatorLimits, SimpleEvaluator, Timer};
 use crate::expression::{
     CustomFunctionRegistry, ExpressionEvaluatorContext, build_expression_evaluator,
 };
+pub use crate::limits::QueryExecutionLimits;
+pub use crate::lru::LruCache;
+pub use crate::n3_builtins::{N3BuiltinFn, get_all_n3_builtins};
 pub use crate::model::{QueryResults, QuerySolution, QuerySolutionIter, QueryTripleIter};
 use crate::service::ServiceHandlerRegistry;
 pub use crate::service::{DefaultServiceHandler, ServiceHandler};
@@ -41,6 +43,7 @@ use sparopt::algebra::GraphPattern;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{fmt, io};
 
 /// Evaluates a query against a given [RDF dataset](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset)
@@ -77,7 +80,10 @@ pub struct QueryEvaluator {
     without_optimizations: bool,
     run_stats: bool,
     cancellation_token: Option<CancellationToken>,
+    sort_memory_budget: Option<usize>,
+    plan_cache: Option<LruCache<spargebra::algebra::GraphPattern, Arc<GraphPattern>>>,
     limits: Option<QueryExecutionLimits>,
+    describe_strategy: DescribeStrategy,
 }
 
 impl QueryEvaluator {
@@ -226,6 +232,10 @@ impl QueryEvaluator {
 
     /// Adds a custom SPARQL evaluation function.
     ///
+    /// It is called with the evaluated arguments when the query calls the function IRI.
+    /// Returning `None` is an evaluation error: the variable bound by `BIND` or `SELECT` stays unbound
+    /// and the `FILTER` evaluates to false.
+    ///
     /// Example with a function serializing terms to N-Triples:
     /// ```
     /// use oxrdf::{Dataset, Literal, NamedNode};
@@ -259,6 +269,50 @@ impl QueryEvaluator {
         self
     }
 
+    /// Adds a custom SPARQL evaluation function that might fail.
+    ///
+    /// It behaves like [`with_custom_function`](Self::with_custom_function)
+    /// but allows to use `?` in the function body.
+    /// Returning `Ok(None)` or an error is an evaluation error:
+    /// the variable bound by `BIND` or `SELECT` stays unbound and the `FILTER` evaluates to false.
+    ///
+    /// ```
+    /// use oxrdf::{Dataset, Literal, NamedNode, Term};
+    /// use spareval::{QueryEvaluator, QueryResults};
+    /// use spargebra::SparqlParser;
+    ///
+    /// let evaluator = QueryEvaluator::new().with_fallible_custom_function(
+    ///     NamedNode::new("http://example.com/double")?,
+    ///     |args| {
+    ///         let [Term::Literal(value)] = args else {
+    ///             return Ok(None);
+    ///         };
+    ///         let value = value.value().parse::<i64>()?;
+    ///         Ok::<_, std::num::ParseIntError>(value.checked_mul(2).map(|v| Literal::from(v).into()))
+    ///     },
+    /// );
+    /// let query = SparqlParser::new()
+    ///     .parse_query("SELECT (<http://example.com/double>(21) AS ?d) WHERE {}")?;
+    /// if let QueryResults::Solutions(mut solutions) =
+    ///     evaluator.prepare(&query).execute(&Dataset::new())?
+    /// {
+    ///     assert_eq!(
+    ///         solutions.next().unwrap()?.get("d"),
+    ///         Some(&Literal::from(42).into())
+    ///     );
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_fallible_custom_function<E>(
+        self,
+        name: NamedNode,
+        evaluator: impl Fn(&[Term]) -> Result<Option<Term>, E> + Send + Sync + 'static,
+    ) -> Self {
+        self.with_custom_function(name, move |args| evaluator(args).ok().flatten())
+    }
+
     /// Adds a custom SPARQL evaluation aggregate function.
     ///
     /// Note that it must also be given to the SPARQL parser using [`SparqlParser::with_custom_aggregate_function`](spargebra::SparqlParser::with_custom_aggregate_function).
@@ -378,21 +432,216 @@ impl QueryEvaluator {
         self
     }
 
+    /// Sets a timeout for the SPARQL evaluation.
+    ///
+    /// The timeout starts when the query is executed and covers the full evaluation,
+    /// including the iteration over the results.
+    /// When it is exceeded, the evaluation fails with [`QueryEvaluationError::Timeout`].
+    ///
+    /// The deadline is checked each time data is read from the dataset, no extra thread is spawned.
+    ///
+    /// ```
+    /// use oxrdf::{Dataset, GraphName, NamedNode, Quad};
+    /// use spareval::{QueryEvaluationError, QueryEvaluator, QueryResults};
+    /// use spargebra::SparqlParser;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let ex = NamedNode::new("http://example.com")?;
+    /// let dataset = Dataset::from_iter([Quad::new(
+    ///     ex.clone(),
+    ///     ex.clone(),
+    ///     ex.clone(),
+    ///     GraphName::DefaultGraph,
+    /// )]);
+    /// let query = SparqlParser::new().parse_query("SELECT * WHERE { ?s ?p ?o }")?;
+    /// let evaluator = QueryEvaluator::new().with_timeout(Duration::from_millis(10));
+    /// let results = evaluator.prepare(&query).execute(&dataset)?;
+    /// if let QueryResults::Solutions(mut solutions) = results {
+    ///     sleep(Duration::from_millis(20));
+    ///     assert!(matches!(
+    ///         solutions.next().unwrap().unwrap_err(), // It's timed out
+    ///         QueryEvaluationError::Timeout(_)
+    ///     ));
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.limits
+            .get_or_insert_with(QueryExecutionLimits::unlimited)
+            .timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of nodes visited when evaluating a transitive property path (`*` or `+`).
+    ///
+    /// The limit applies to each evaluation of a transitive path independently.
+    /// When it is exceeded, the evaluation fails with [`QueryEvaluationError::PropertyPathDepthExceeded`]
+    /// instead of building an arbitrarily large closure in memory.
+    ///
+    /// This is a shortcut for setting [`QueryExecutionLimits::max_property_path_depth`].
+    ///
+    /// ```
+    /// use oxrdf::{Dataset, GraphName, NamedNode, Quad};
+    /// use spareval::{QueryEvaluationError, QueryEvaluator, QueryResults};
+    /// use spargebra::SparqlParser;
+    ///
+    /// let p = NamedNode::new("http://example.com/p")?;
+    /// let mut dataset = Dataset::new();
+    /// for i in 0..10 {
+    ///     dataset.insert(&Quad::new(
+    ///         NamedNode::new(format!("http://example.com/{i}"))?,
+    ///         p.clone(),
+    ///         NamedNode::new(format!("http://example.com/{}", i + 1))?,
+    ///         GraphName::DefaultGraph,
+    ///     ));
+    /// }
+    /// let query = SparqlParser::new()
+    ///     .parse_query("SELECT ?o WHERE { <http://example.com/0> <http://example.com/p>+ ?o }")?;
+    /// let evaluator = QueryEvaluator::new().with_max_path_visits(5);
+    /// if let QueryResults::Solutions(mut solutions) = evaluator.prepare(&query).execute(&dataset)? {
+    ///     assert!(matches!(
+    ///         solutions.next().unwrap().unwrap_err(),
+    ///         QueryEvaluationError::PropertyPathDepthExceeded(5)
+    ///     ));
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[must_use]
+    pub fn with_max_path_visits(mut self, max_path_visits: usize) -> Self {
+        self.limits
+            .get_or_insert_with(QueryExecutionLimits::unlimited)
+            .max_property_path_depth = Some(max_path_visits);
+        self
+    }
+
+    /// Bounds the number of rows kept in memory by `DISTINCT` and by the groups of `GROUP BY`.
+    ///
+    /// These operators have to remember all the rows they have seen,
+    /// so they can use an arbitrary amount of memory even if the results are streamed.
+    /// When the limit is exceeded, the evaluation fails with [`QueryEvaluationError::GroupLimitExceeded`].
+    ///
+    /// This is a shortcut for setting [`QueryExecutionLimits::max_groups`].
+    ///
+    /// ```
+    /// use oxrdf::Dataset;
+    /// use spareval::{QueryEvaluationError, QueryEvaluator, QueryResults};
+    /// use spargebra::SparqlParser;
+    ///
+    /// let query = SparqlParser::new()
+    ///     .parse_query("SELECT DISTINCT ?v WHERE { VALUES ?v { 1 2 3 } }")?;
+    /// let evaluator = QueryEvaluator::new().with_max_materialized_rows(2);
+    /// if let QueryResults::Solutions(solutions) = evaluator.prepare(&query).execute(&Dataset::new())? {
+    ///     assert!(matches!(
+    ///         solutions.last().unwrap().unwrap_err(),
+    ///         QueryEvaluationError::GroupLimitExceeded(2)
+    ///     ));
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[must_use]
+    pub fn with_max_materialized_rows(mut self, max_materialized_rows: usize) -> Self {
+        self.limits
+            .get_or_insert_with(QueryExecutionLimits::unlimited)
+            .max_groups = Some(max_materialized_rows);
+        self
+    }
+
+    /// Bounds the memory used by `ORDER BY` to approximately `sort_memory_budget` bytes.
+    ///
+    /// When the solutions to sort exceed the budget, they are sorted in runs written to temporary files
+    /// that are merged while the results are iterated.
+    /// The results are the same as with an in-memory sort.
+    /// The temporary files are removed when the results iterator is dropped.
+    ///
+    /// ```
+    /// use oxrdf::{Dataset, Literal};
+    /// use spareval::{QueryEvaluator, QueryResults};
+    /// use spargebra::SparqlParser;
+    ///
+    /// let query = SparqlParser::new()
+    ///     .parse_query("SELECT ?v WHERE { VALUES ?v { 3 1 2 } } ORDER BY DESC(?v)")?;
+    /// let evaluator = QueryEvaluator::new().with_sort_memory_budget(1);
+    /// if let QueryResults::Solutions(solutions) = evaluator.prepare(&query).execute(&Dataset::new())? {
+    ///     let values = solutions
+    ///         .map(|s| Ok(s?.get("v").cloned()))
+    ///         .collect::<Result<Vec<_>, spareval::QueryEvaluationError>>()?;
+    ///     assert_eq!(
+    ///         values,
+    ///         [3, 2, 1].map(|v| Some(Literal::from(v).into()))
+    ///     );
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[must_use]
+    pub fn with_sort_memory_budget(mut self, sort_memory_budget: usize) -> Self {
+        self.sort_memory_budget = Some(sort_memory_budget);
+        self
+    }
+
+    /// Caches up to `capacity` query plans so that executing again the same query
+    /// skips its translation and optimization.
+    ///
+    /// Plans are keyed on the query algebra so prefixes and formatting do not matter.
+    /// The cache is shared between the clones of this evaluator.
+    /// When it is full, the least recently used plan is evicted.
+    ///
+    /// ```
+    /// use oxrdf::Dataset;
+    /// use spareval::{QueryEvaluator, QueryResults};
+    /// use spargebra::SparqlParser;
+    ///
+    /// let evaluator = QueryEvaluator::new().with_plan_cache(100);
+    /// for _ in 0..10 {
+    ///     let query = SparqlParser::new().parse_query("SELECT * WHERE { ?s ?p ?o }")?;
+    ///     if let QueryResults::Solutions(solutions) =
+    ///         evaluator.prepare(&query).execute(&Dataset::new())?
+    ///     {
+    ///         assert_eq!(solutions.count(), 0);
+    ///     }
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[must_use]
+    pub fn with_plan_cache(mut self, capacity: usize) -> Self {
+        self.plan_cache = Some(LruCache::new(capacity));
+        self
+    }
+
     /// Set resource limits for query execution.
     ///
     /// Limits help prevent denial-of-service attacks from long-running or resource-intensive queries.
+    /// The [`timeout`](QueryExecutionLimits::timeout) is enforced like [`with_timeout`](Self::with_timeout).
+    /// The given limits replace the ones set before, including by [`with_timeout`](Self::with_timeout).
     ///
     /// ```
-    /// use oxrdf::Dataset;
-    /// use spareval::{QueryEvaluationError, QueryEvaluator, QueryExecutionLimits};
+    /// use oxrdf::{Dataset, GraphName, NamedNode, Quad};
+    /// use spareval::{QueryEvaluationError, QueryEvaluator, QueryExecutionLimits, QueryResults};
     /// use spargebra::SparqlParser;
+    /// use std::thread::sleep;
     /// use std::time::Duration;
     ///
-    /// let evaluator = QueryEvaluator::new()
-    ///     .with_limits(QueryExecutionLimits::strict());
+    /// let evaluator = QueryEvaluator::new().with_limits(QueryExecutionLimits {
+    ///     timeout: Some(Duration::from_millis(10)),
+    ///     ..QueryExecutionLimits::strict()
+    /// });
     ///
+    /// let ex = NamedNode::new("http://example.com")?;
+    /// let dataset = Dataset::from_iter([Quad::new(
+    ///     ex.clone(),
+    ///     ex.clone(),
+    ///     ex.clone(),
+    ///     GraphName::DefaultGraph,
+    /// )]);
     /// let query = SparqlParser::new().parse_query("SELECT * WHERE { ?s ?p ?o }")?;
-    /// let results = evaluator.prepare(&query).execute(&Dataset::new())?;
+    /// if let QueryResults::Solutions(mut solutions) = evaluator.prepare(&query).execute(&dataset)? {
+    ///     sleep(Duration::from_millis(20));
+    ///     assert!(matches!(
+    ///         solutions.next().unwrap().unwrap_err(), // It's timed out
+    ///         QueryEvaluationError::Timeout(_)
+    ///     ));
+    /// }
     /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
     /// ```
     #[must_use]
@@ -401,6 +650,34 @@ impl QueryEvaluator {
         self
     }
 
+    /// Sets how the resources returned by `DESCRIBE` queries are described.
+    ///
+    /// Defaults to [`DescribeStrategy::Cbd`].
+    ///
+    /// ```
+    /// use oxrdf::{BlankNode, Dataset, GraphName, NamedNode, Quad};
+    /// use spareval::{DescribeStrategy, QueryEvaluator, QueryResults};
+    /// use spargebra::SparqlParser;
+    ///
+    /// let ex = NamedNode::new("http://example.com")?;
+    /// let bnode = BlankNode::default();
+    /// let dataset = Dataset::from_iter([
+    ///     Quad::new(ex.clone(), ex.clone(), bnode.clone(), GraphName::DefaultGraph),
+    ///     Quad::new(bnode, ex.clone(), ex.clone(), GraphName::DefaultGraph),
+    /// ]);
+    /// let query = SparqlParser::new().parse_query("DESCRIBE <http://example.com>")?;
+    /// let evaluator = QueryEvaluator::new().with_describe_strategy(DescribeStrategy::ForwardOnly);
+    /// if let QueryResults::Graph(triples) = evaluator.prepare(&query).execute(&dataset)? {
+    ///     assert_eq!(triples.count(), 1);
+    /// }
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[must_use]
+    pub fn with_describe_strategy(mut self, describe_strategy: DescribeStrategy) -> Self {
+        self.describe_strategy = describe_strategy;
+        self
+    }
+
     /// Registers all N3 built-in functions with this evaluator.
     ///
     /// This includes:
@@ -624,20 +901,54 @@ impl QueryEvaluator {
         }
     }
 
+    /// Translates the pattern to the evaluator algebra and optimizes it,
+    /// or returns the plan from the cache if there is one.
+    fn plan(&self, pattern: &spargebra::algebra::GraphPattern) -> Arc<GraphPattern> {
+        let plan = || {
+            let mut pattern = GraphPattern::from(pattern);
+            if !self.without_optimizations {
+                pattern = Optimizer::optimize_graph_pattern(pattern);
+            }
+            pattern
+        };
+        let Some(plan_cache) = &self.plan_cache else {
+            return Arc::new(plan());
+        };
+        if let Some(plan) = plan_cache.get(pattern) {
+            return plan;
+        }
+        // We do not hold the cache lock while planning so that other queries are not blocked
+        let plan = Arc::new(plan());
+        plan_cache.insert(pattern.clone(), Arc::clone(&plan));
+        plan
+    }
+
     fn simple_evaluator<'a, D: QueryableDataset<'a>>(
         &self,
         dataset: D,
         dataset_spec: QueryDatasetSpecification,
         base_iri: &Option<Iri<String>>,
     ) -> Result<SimpleEvaluator<'a, D>, QueryEvaluationError> {
+        let mut cancellation_token = self.cancellation_token.clone().unwrap_or_default();
+        if let Some(timeout) = self.limits.as_ref().and_then(|limits| limits.timeout) {
+            cancellation_token = cancellation_token.with_timeout(timeout);
+        }
         SimpleEvaluator::new(
             dataset,
             base_iri.clone().map(Arc::new),
             Rc::new(self.service_handler.clone()),
             Rc::new(self.custom_functions.clone()),
             Rc::new(self.custom_aggregate_functions.clone()),
-            self.cancellation_token.clone().unwrap_or_default(),
+            cancellation_token,
             dataset_spec,
+            EvaluatorLimits {
+                max_path_visits: self
+                    .limits
+                    .as_ref()
+                    .and_then(|limits| limits.max_property_path_depth),
+                max_groups: self.limits.as_ref().and_then(|limits| limits.max_groups),
+                sort_memory_budget: self.sort_memory_budget,
+            },
             self.run_stats,
         )
     }
@@ -740,10 +1051,7 @@ impl PreparedQuery<'_> {
             Query::Select {
                 pattern, base_iri, ..
             } => {
-                let mut pattern = GraphPattern::from(pattern);
-                if !self.evaluator.without_optimizations {
-                    pattern = Optimizer::optimize_graph_pattern(pattern);
-                }
+                let pattern = self.evaluator.plan(pattern);
                 let planning_duration = start_planning.elapsed();
                 let (results, explanation) =
                     match self
@@ -762,10 +1070,7 @@ impl PreparedQuery<'_> {
             Query::Ask {
                 pattern, base_iri, ..
             } => {
-                let mut pattern = GraphPattern::from(pattern);
-                if !self.evaluator.without_optimizations {
-                    pattern = Optimizer::optimize_graph_pattern(pattern);
-                }
+                let pattern = self.evaluator.plan(pattern);
                 let planning_duration = start_planning.elapsed();
                 let (results, explanation) =
                     match self
@@ -787,10 +1092,7 @@ impl PreparedQuery<'_> {
                 base_iri,
                 ..
             } => {
-                let mut pattern = GraphPattern::from(pattern);
-                if !self.evaluator.without_optimizations {
-                    pattern = Optimizer::optimize_graph_pattern(pattern);
-                }
+                let pattern = self.evaluator.plan(pattern);
                 let planning_duration = start_planning.elapsed();
                 let (results, explanation) =
                     match self
@@ -811,17 +1113,18 @@ impl PreparedQuery<'_> {
             Query::Describe {
                 pattern, base_iri, ..
             } => {
-                let mut pattern = GraphPattern::from(pattern);
-                if !self.evaluator.without_optimizations {
-                    pattern = Optimizer::optimize_graph_pattern(pattern);
-                }
+                let pattern = self.evaluator.plan(pattern);
                 let planning_duration = start_planning.elapsed();
                 let (results, explanation) =
                     match self
                         .evaluator
                         .simple_evaluator(dataset, self.dataset, base_iri)
                     {
-                        Ok(evaluator) => evaluator.evaluate_describe(&pattern, self.substitutions),
+                        Ok(evaluator) => evaluator.evaluate_describe(
+                            &pattern,
+                            self.substitutions,
+                            self.evaluator.describe_strategy,
+                        ),
                         Err(e) => (Err(e), Rc::new(EvalNodeWithStats::empty())),
                     };
                 (
@@ -1107,6 +1410,13 @@ pub struct QueryExplanation {
 }
 
 impl QueryExplanation {
+    /// The time spent translating and optimizing the query before its evaluation.
+    ///
+    /// It is close to zero when the plan is found in the cache set with [`QueryEvaluator::with_plan_cache`].
+    pub fn planning_duration(&self) -> Option<Duration> {
+        self.planning_duration?.try_into().ok()
+    }
+
     /// Writes the explanation as JSON.
     pub fn write_in_json(&self, writer: impl io::Write) -> io::Result<()> {
         let mut serializer = WriterJsonSerializer::new(writer);
@@ -1124,6 +1434,48 @@ impl QueryExplanation {
     }
 }
 
+/// Pretty-prints the evaluation plan as an indented tree.
+///
+/// Each operator is annotated with the number of results estimated by the optimizer,
+/// the number of results actually returned, the time spent in it
+/// and, for triple patterns, the SPO, POS or OSP indexes able to answer its lookups,
+/// if statistics have been computed with [`QueryEvaluator::compute_statistics`].
+///
+/// ```
+/// use oxrdf::{Dataset, GraphName, NamedNode, Quad};
+/// use spareval::{QueryEvaluator, QueryResults};
+/// use spargebra::SparqlParser;
+///
+/// let ex = NamedNode::new("http://example.com")?;
+/// let dataset = Dataset::from_iter([Quad::new(
+///     ex.clone(),
+///     ex.clone(),
+///     ex.clone(),
+///     GraphName::DefaultGraph,
+/// )]);
+/// let query = SparqlParser::new().parse_query("SELECT ?s WHERE { ?s <http://example.com> ?o }")?;
+/// let (results, explanation) = QueryEvaluator::new()
+///     .compute_statistics()
+///     .prepare(&query)
+///     .explain(&dataset);
+/// if let QueryResults::Solutions(solutions) = results? {
+///     assert_eq!(solutions.count(), 1); // Statistics are filled while consuming the results
+/// }
+/// assert!(explanation.to_string().contains(
+///     "QuadPattern(?s <http://example.com> ?o) [estimated results: 1000000, results: 1"
+/// ));
+/// assert!(explanation.to_string().contains("indexes: POS]"));
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+impl fmt::Display for QueryExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(planning_duration) = self.planning_duration {
+            writeln!(f, "Planning duration: {}s", planning_duration.as_seconds())?;
+        }
+        self.inner.fmt_tree(f, 0)
+    }
+}
+
 impl fmt::Debug for QueryExplanation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut obj = f.debug_struct("QueryExplanation");
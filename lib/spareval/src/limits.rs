@@ -19,12 +19,16 @@ use std::time::Duration;
 ///     max_result_rows: Some(5_000),
 ///     ..QueryExecutionLimits::default()
 /// };
+/// # let _ = (limits, custom);
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct QueryExecutionLimits {
     /// Maximum query execution time
     ///
-    /// If the query execution exceeds this duration, it will be cancelled.
+    /// If the query execution exceeds this duration, it fails with
+    /// [`QueryEvaluationError::Timeout`](crate::QueryEvaluationError::Timeout).
+    /// The deadline is checked regularly while data is read from the dataset,
+    /// including while iterating over the results.
     /// Default: 30 seconds
     pub timeout: Option<Duration>,
 
@@ -35,16 +39,19 @@ pub struct QueryExecutionLimits {
     /// Default: 10,000 rows
     pub max_result_rows: Option<usize>,
 
-    /// Maximum number of groups in GROUP BY
+    /// Maximum number of groups in GROUP BY and of distinct rows in DISTINCT
     ///
     /// Prevents memory exhaustion from queries that create excessive groupings.
+    /// If it is exceeded, the evaluation fails with
+    /// [`QueryEvaluationError::GroupLimitExceeded`](crate::QueryEvaluationError::GroupLimitExceeded).
     /// Default: 1,000 groups
     pub max_groups: Option<usize>,
 
     /// Maximum depth for property paths
     ///
-    /// Limits recursive property path evaluation (e.g., `?s ex:parent+ ?o`).
-    /// Default: 1,000 levels
+    /// Limits the number of nodes visited by each evaluation of a transitive
+    /// property path (e.g., `?s ex:parent+ ?o`).
+    /// Default: 1,000 nodes
     pub max_property_path_depth: Option<usize>,
 
     /// Maximum memory per query (in bytes)
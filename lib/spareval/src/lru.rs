@@ -0,0 +1,154 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A bounded thread-safe cache that evicts the least recently used entry when it is full.
+///
+/// It is cheap to clone and the clones share the same entries.
+/// Values are cloned out of the cache, so they should be cheap to clone (e.g. wrapped in an [`Arc`]).
+///
+/// The lock is only held during the lookups and insertions:
+/// the values should be computed between [`get`](Self::get) and [`insert`](Self::insert)
+/// so that other threads are not blocked.
+///
+/// ```
+/// use spareval::LruCache;
+///
+/// let cache = LruCache::new(2);
+/// cache.insert("a", 1);
+/// cache.insert("b", 2);
+/// assert_eq!(cache.get("a"), Some(1));
+/// cache.insert("c", 3); // "b" is the least recently used entry
+/// assert_eq!(cache.get("b"), None);
+/// assert_eq!(cache.get("a"), Some(1));
+/// ```
+pub struct LruCache<K, V> {
+    capacity: usize,
+    inner: Arc<Mutex<LruCacheContent<K, V>>>,
+}
+
+struct LruCacheContent<K, V> {
+    /// Values with the value of `clock` when they were last used
+    entries: HashMap<K, (V, u64)>,
+    /// Keys ordered by the value of `clock` when they were last used
+    recency: BTreeMap<u64, K>,
+    clock: u64,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> LruCache<K, V> {
+    /// Builds a cache keeping at most `capacity` entries.
+    ///
+    /// A cache with a capacity of 0 never keeps anything.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(LruCacheContent {
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                clock: 0,
+            })),
+        }
+    }
+
+    /// The maximal number of entries of the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the value cached for `key` and marks it as the most recently used one.
+    pub fn get<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let mut content = self.lock();
+        content.clock += 1;
+        let clock = content.clock;
+        let (value, last_used) = content.entries.get_mut(key)?;
+        let value = value.clone();
+        let previous_use = std::mem::replace(last_used, clock);
+        if let Some(key) = content.recency.remove(&previous_use) {
+            content.recency.insert(clock, key);
+        }
+        Some(value)
+    }
+
+    /// Caches `value` for `key`, evicting the least recently used entry if the cache is full.
+    pub fn insert(&self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut content = self.lock();
+        content.clock += 1;
+        let clock = content.clock;
+        if let Some((_, previous_use)) = content.entries.remove(&key) {
+            content.recency.remove(&previous_use);
+        } else if content.entries.len() >= self.capacity {
+            if let Some((_, least_recently_used)) = content.recency.pop_first() {
+                content.entries.remove(&least_recently_used);
+            }
+        }
+        content.recency.insert(clock, key.clone());
+        content.entries.insert(key, (value, clock));
+    }
+
+    /// Removes all the entries.
+    pub fn clear(&self) {
+        let mut content = self.lock();
+        content.entries.clear();
+        content.recency.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruCacheContent<K, V>> {
+        // The content is always valid, even if another thread panicked while holding the lock
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<K, V> Clone for LruCache<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_cache_evicts_least_recently_used() {
+        let cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get("a"), Some(1));
+        // b is the least recently used entry
+        cache.insert("c", 3);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("c"), Some(3));
+        assert_eq!(cache.get("b"), None);
+        // Replacing a value refreshes it
+        cache.insert("a", 4);
+        cache.insert("d", 5);
+        assert_eq!(cache.get("a"), Some(4));
+        assert_eq!(cache.get("c"), None);
+    }
+
+    #[test]
+    fn test_lru_cache_clones_share_entries() {
+        let cache = LruCache::new(1);
+        cache.clone().insert(String::from("a"), 1);
+        assert_eq!(cache.get("a"), Some(1));
+        cache.clear();
+        assert_eq!(cache.get("a"), None);
+    }
+
+    #[test]
+    fn test_lru_cache_without_capacity() {
+        let cache = LruCache::new(0);
+        cache.insert("a", 1);
+        assert_eq!(cache.get("a"), None);
+    }
+}
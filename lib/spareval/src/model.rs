@@ -1,11 +1,14 @@
 use crate::error::QueryEvaluationError;
-use oxrdf::{Term, Triple, Variable};
+use oxrdf::{Term, Triple, Variable, VariableRef};
+#[cfg(feature = "rdf-io")]
+use oxrdfio::{RdfFormat, RdfSerializer};
 pub use sparesults::QuerySolution;
 use sparesults::{
+    QueryResultsFormat, QueryResultsParseError, QueryResultsParser, QueryResultsSerializer,
     ReaderQueryResultsParserOutput, ReaderSolutionsParser, SliceQueryResultsParserOutput,
     SliceSolutionsParser,
 };
-use std::io::Read;
+use std::io::{Read, Write};
 use std::sync::Arc;
 
 /// Results of a [SPARQL query](https://www.w3.org/TR/sparql11-query/).
@@ -18,6 +21,195 @@ pub enum QueryResults<'a> {
     Graph(QueryTripleIter<'a>),
 }
 
+impl<'a> QueryResults<'a> {
+    /// Reads query results (solutions or boolean) serialized in a [SPARQL query results format](QueryResultsFormat).
+    ///
+    /// The solutions are parsed lazily while iterating on them.
+    /// Syntax errors in the solutions are returned by the [solutions iterator](QuerySolutionIter).
+    ///
+    /// ```
+    /// use sparesults::QueryResultsFormat;
+    /// use spareval::QueryResults;
+    ///
+    /// let json = br#"{"head":{"vars":["s"]},"results":{"bindings":[{"s":{"type":"uri","value":"http://example.com"}}]}}"#;
+    /// let results = QueryResults::read(json.as_slice(), QueryResultsFormat::Json)?;
+    /// assert_eq!(results.write(Vec::new(), QueryResultsFormat::Json)?, json);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn read(
+        reader: impl Read + 'a,
+        format: QueryResultsFormat,
+    ) -> Result<Self, QueryResultsParseError> {
+        Ok(QueryResultsParser::from_format(format)
+            .for_reader(reader)?
+            .into())
+    }
+
+    /// Writes the query results (solutions or boolean) in a [SPARQL query results format](QueryResultsFormat).
+    ///
+    /// Solutions are written while they are computed, without being kept in memory.
+    /// [Graph](QueryResults::Graph) results are written as solutions binding the `subject`, `predicate` and `object` variables.
+    ///
+    /// ```
+    /// use oxrdf::{Dataset, GraphName, NamedNode, Quad};
+    /// use sparesults::QueryResultsFormat;
+    /// use spareval::QueryEvaluator;
+    /// use spargebra::SparqlParser;
+    ///
+    /// let ex = NamedNode::new("http://example.com")?;
+    /// let dataset = Dataset::from_iter([Quad::new(
+    ///     ex.clone(),
+    ///     ex.clone(),
+    ///     ex,
+    ///     GraphName::DefaultGraph,
+    /// )]);
+    /// let query = SparqlParser::new().parse_query("SELECT ?s WHERE { ?s ?p ?o }")?;
+    /// let results = QueryEvaluator::new().prepare(&query).execute(&dataset)?;
+    /// assert_eq!(
+    ///     results.write(Vec::new(), QueryResultsFormat::Json)?,
+    ///     br#"{"head":{"vars":["s"]},"results":{"bindings":[{"s":{"type":"uri","value":"http://example.com"}}]}}"#
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn write<W: Write>(
+        self,
+        writer: W,
+        format: QueryResultsFormat,
+    ) -> Result<W, QueryEvaluationError> {
+        let serializer = QueryResultsSerializer::from_format(format);
+        match self {
+            Self::Boolean(value) => serializer
+                .serialize_boolean_to_writer(writer, value)
+                .map_err(QueryEvaluationError::ResultsSerialization),
+            Self::Solutions(solutions) => {
+                let mut serializer = serializer
+                    .serialize_solutions_to_writer(writer, solutions.variables().to_vec())
+                    .map_err(QueryEvaluationError::ResultsSerialization)?;
+                for solution in solutions {
+                    serializer
+                        .serialize(&solution?)
+                        .map_err(QueryEvaluationError::ResultsSerialization)?;
+                }
+                serializer
+                    .finish()
+                    .map_err(QueryEvaluationError::ResultsSerialization)
+            }
+            Self::Graph(triples) => {
+                let s = VariableRef::new_unchecked("subject");
+                let p = VariableRef::new_unchecked("predicate");
+                let o = VariableRef::new_unchecked("object");
+                let mut serializer = serializer
+                    .serialize_solutions_to_writer(
+                        writer,
+                        vec![s.into_owned(), p.into_owned(), o.into_owned()],
+                    )
+                    .map_err(QueryEvaluationError::ResultsSerialization)?;
+                for triple in triples {
+                    let triple = triple?;
+                    serializer
+                        .serialize([
+                            (s, triple.subject.as_ref().into()),
+                            (p, triple.predicate.as_ref().into()),
+                            (o, triple.object.as_ref()),
+                        ])
+                        .map_err(QueryEvaluationError::ResultsSerialization)?;
+                }
+                serializer
+                    .finish()
+                    .map_err(QueryEvaluationError::ResultsSerialization)
+            }
+        }
+    }
+
+    /// Writes the graph query results in an [RDF format](RdfFormat).
+    ///
+    /// The triples are written while they are computed, without building a graph in memory.
+    /// This method fails with [`QueryEvaluationError::NotAGraph`] if the results are not a [graph](QueryResults::Graph).
+    ///
+    /// ```
+    /// use oxrdf::{Dataset, GraphName, NamedNode, Quad};
+    /// use oxrdfio::RdfFormat;
+    /// use spareval::QueryEvaluator;
+    /// use spargebra::SparqlParser;
+    ///
+    /// let ex = NamedNode::new("http://example.com")?;
+    /// let dataset = Dataset::from_iter([Quad::new(
+    ///     ex.clone(),
+    ///     ex.clone(),
+    ///     ex,
+    ///     GraphName::DefaultGraph,
+    /// )]);
+    /// let query = SparqlParser::new().parse_query("CONSTRUCT WHERE { ?s ?p ?o }")?;
+    /// let results = QueryEvaluator::new().prepare(&query).execute(&dataset)?;
+    /// assert_eq!(
+    ///     results.write_graph(Vec::new(), RdfFormat::NTriples)?,
+    ///     b"<http://example.com> <http://example.com> <http://example.com> .\n"
+    /// );
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(feature = "rdf-io")]
+    pub fn write_graph<W: Write>(
+        self,
+        writer: W,
+        format: impl Into<RdfFormat>,
+    ) -> Result<W, QueryEvaluationError> {
+        let Self::Graph(triples) = self else {
+            return Err(QueryEvaluationError::NotAGraph);
+        };
+        let mut serializer = RdfSerializer::from_format(format.into()).for_writer(writer);
+        for triple in triples {
+            serializer
+                .serialize_triple(&triple?)
+                .map_err(QueryEvaluationError::ResultsSerialization)?;
+        }
+        serializer
+            .finish()
+            .map_err(QueryEvaluationError::ResultsSerialization)
+    }
+
+    /// Collects the solutions of the query, failing if there are more than `max` of them.
+    ///
+    /// At most `max + 1` solutions are pulled from the iterator, so an unexpectedly large result set is not computed in full.
+    /// This method fails with [`QueryEvaluationError::ResultLimitExceeded`] if there are more than `max` solutions
+    /// and with [`QueryEvaluationError::NotSolutions`] if the results are not [solutions](QueryResults::Solutions).
+    ///
+    /// ```
+    /// use oxrdf::{Dataset, GraphName, NamedNode, Quad};
+    /// use spareval::{QueryEvaluationError, QueryEvaluator};
+    /// use spargebra::SparqlParser;
+    ///
+    /// let ex = NamedNode::new("http://example.com")?;
+    /// let dataset = Dataset::from_iter([Quad::new(
+    ///     ex.clone(),
+    ///     ex.clone(),
+    ///     ex,
+    ///     GraphName::DefaultGraph,
+    /// )]);
+    /// let query = SparqlParser::new().parse_query("SELECT * WHERE { ?s ?p ?o }")?;
+    /// let evaluator = QueryEvaluator::new();
+    /// let solutions = evaluator.prepare(&query).execute(&dataset)?.collect_bounded(1)?;
+    /// assert_eq!(solutions.len(), 1);
+    /// assert!(matches!(
+    ///     evaluator.prepare(&query).execute(&dataset)?.collect_bounded(0),
+    ///     Err(QueryEvaluationError::ResultLimitExceeded(0))
+    /// ));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn collect_bounded(self, max: usize) -> Result<Vec<QuerySolution>, QueryEvaluationError> {
+        let Self::Solutions(solutions) = self else {
+            return Err(QueryEvaluationError::NotSolutions);
+        };
+        let mut collected = Vec::new();
+        for solution in solutions {
+            if collected.len() == max {
+                return Err(QueryEvaluationError::ResultLimitExceeded(max));
+            }
+            collected.push(solution?);
+        }
+        Ok(collected)
+    }
+}
+
 impl<'a> From<QuerySolutionIter<'a>> for QueryResults<'a> {
     #[inline]
     fn from(value: QuerySolutionIter<'a>) -> Self {
@@ -76,9 +268,42 @@ impl<'a> From<SliceQueryResultsParserOutput<'a>> for QueryResults<'a> {
 /// }
 /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
 /// ```
+///
+/// If the evaluation is cancelled or times out, the iterator returns a single error for which
+/// [`QueryEvaluationError::is_interruption`] is `true` and then ends.
+/// The solutions returned before are valid and [`QuerySolutionIter::is_truncated`] allows to know that they are partial.
+///
+/// ```
+/// use oxrdf::{Dataset, GraphName, Literal, NamedNode, Quad};
+/// use spareval::{CancellationToken, QueryEvaluator, QueryResults};
+/// use spargebra::SparqlParser;
+///
+/// let ex = NamedNode::new("http://example.com")?;
+/// let dataset = Dataset::from_iter([1, 2].map(|i| {
+///     Quad::new(ex.clone(), ex.clone(), Literal::from(i), GraphName::DefaultGraph)
+/// }));
+/// let query = SparqlParser::new().parse_query("SELECT * WHERE { ?s ?p ?o }")?;
+/// let cancellation_token = CancellationToken::new();
+/// let evaluator = QueryEvaluator::new().with_cancellation_token(cancellation_token.clone());
+/// if let QueryResults::Solutions(mut solutions) = evaluator.prepare(&query).execute(&dataset)? {
+///     let mut partial_solutions = vec![solutions.next().unwrap()?];
+///     cancellation_token.cancel(); // We cancel after the first solution
+///     for solution in &mut solutions {
+///         match solution {
+///             Ok(solution) => partial_solutions.push(solution),
+///             Err(e) if e.is_interruption() => (), // We keep what we have
+///             Err(e) => return Err(e.into()),
+///         }
+///     }
+///     assert_eq!(partial_solutions.len(), 1);
+///     assert!(solutions.is_truncated());
+/// }
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
 pub struct QuerySolutionIter<'a> {
     variables: Arc<[Variable]>,
     iter: Box<dyn Iterator<Item = Result<QuerySolution, QueryEvaluationError>> + 'a>,
+    truncated: bool,
 }
 
 impl<'a> QuerySolutionIter<'a> {
@@ -90,6 +315,7 @@ impl<'a> QuerySolutionIter<'a> {
         Self {
             variables,
             iter: Box::new(iter.into_iter()),
+            truncated: false,
         }
     }
 
@@ -128,6 +354,14 @@ impl<'a> QuerySolutionIter<'a> {
     pub fn variables(&self) -> &[Variable] {
         &self.variables
     }
+
+    /// Returns `true` if the evaluation has been cancelled or has timed out, i.e. if the solutions returned so far are only a part of the query results.
+    ///
+    /// The iterator does not return any solution after this becomes `true`.
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
 }
 
 impl Iterator for QuerySolutionIter<'_> {
@@ -135,12 +369,23 @@ impl Iterator for QuerySolutionIter<'_> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        if self.truncated {
+            return None;
+        }
+        let result = self.iter.next()?;
+        if let Err(e) = &result {
+            self.truncated = e.is_interruption();
+        }
+        Some(result)
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        if self.truncated {
+            (0, Some(0))
+        } else {
+            self.iter.size_hint()
+        }
     }
 }
 
@@ -152,6 +397,7 @@ impl<'a, R: Read + 'a> From<ReaderSolutionsParser<R>> for QuerySolutionIter<'a>
             iter: Box::new(
                 parser.map(|r| r.map_err(|e| QueryEvaluationError::Unexpected(e.into()))),
             ),
+            truncated: false,
         }
     }
 }
@@ -164,6 +410,7 @@ impl<'a> From<SliceSolutionsParser<'a>> for QuerySolutionIter<'a> {
             iter: Box::new(
                 parser.map(|r| r.map_err(|e| QueryEvaluationError::Unexpected(e.into()))),
             ),
+            truncated: false,
         }
     }
 }
@@ -184,18 +431,29 @@ impl<'a> From<SliceSolutionsParser<'a>> for QuerySolutionIter<'a> {
 /// }
 /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
 /// ```
+///
+/// Like [`QuerySolutionIter`], the iterator ends after returning an error for which [`QueryEvaluationError::is_interruption`] is `true`.
 pub struct QueryTripleIter<'a> {
     iter: Box<dyn Iterator<Item = Result<Triple, QueryEvaluationError>> + 'a>,
+    truncated: bool,
 }
 
 impl<'a> QueryTripleIter<'a> {
-    pub(crate) fn new(
-        iter: impl Iterator<Item = Result<Triple, QueryEvaluationError>> + 'a,
-    ) -> Self {
+    /// Construct a new iterator of triples from an iterator of triples
+    pub fn new(iter: impl Iterator<Item = Result<Triple, QueryEvaluationError>> + 'a) -> Self {
         Self {
             iter: Box::new(iter),
+            truncated: false,
         }
     }
+
+    /// Returns `true` if the evaluation has been cancelled or has timed out, i.e. if the triples returned so far are only a part of the query results.
+    ///
+    /// The iterator does not return any triple after this becomes `true`.
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
 }
 
 impl Iterator for QueryTripleIter<'_> {
@@ -203,11 +461,22 @@ impl Iterator for QueryTripleIter<'_> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        if self.truncated {
+            return None;
+        }
+        let result = self.iter.next()?;
+        if let Err(e) = &result {
+            self.truncated = e.is_interruption();
+        }
+        Some(result)
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        if self.truncated {
+            (0, Some(0))
+        } else {
+            self.iter.size_hint()
+        }
     }
 }
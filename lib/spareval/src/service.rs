@@ -141,6 +141,84 @@ pub trait DefaultServiceHandler: Send + Sync {
     ) -> Result<QuerySolutionIter<'static>, Self::Error>;
 }
 
+/// Closures taking the service [`GraphPattern`] and base IRI are [`ServiceHandler`]s.
+///
+/// ```
+/// use oxiri::Iri;
+/// use oxrdf::{Dataset, Literal, NamedNode, Variable};
+/// use sparesults::QuerySolution;
+/// use spareval::{QueryEvaluator, QueryResults, QuerySolutionIter};
+/// use spargebra::SparqlParser;
+/// use spargebra::algebra::GraphPattern;
+/// use std::convert::Infallible;
+/// use std::iter::once;
+/// use std::sync::Arc;
+///
+/// let evaluator = QueryEvaluator::default().with_service_handler(
+///     NamedNode::new("http://example.com/service")?,
+///     |_pattern: &GraphPattern, _base_iri: Option<&Iri<String>>| {
+///         let variables = [Variable::new_unchecked("foo")].into();
+///         Ok::<_, Infallible>(QuerySolutionIter::new(
+///             Arc::clone(&variables),
+///             once(Ok(QuerySolution::from((
+///                 variables,
+///                 vec![Some(Literal::from(1).into())],
+///             )))),
+///         ))
+///     },
+/// );
+/// let query = SparqlParser::new()
+///     .parse_query("SELECT ?foo WHERE { SERVICE <http://example.com/service> {} }")?;
+/// if let QueryResults::Solutions(mut solutions) =
+///     evaluator.prepare(&query).execute(&Dataset::new())?
+/// {
+///     assert_eq!(
+///         solutions.next().unwrap()?.get("foo"),
+///         Some(&Literal::from(1).into())
+///     );
+/// }
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+impl<F, E> ServiceHandler for F
+where
+    F: Fn(&GraphPattern, Option<&Iri<String>>) -> Result<QuerySolutionIter<'static>, E>
+        + Send
+        + Sync,
+    E: Error + Send + Sync + 'static,
+{
+    type Error = E;
+
+    #[inline]
+    fn handle(
+        &self,
+        pattern: &GraphPattern,
+        base_iri: Option<&Iri<String>>,
+    ) -> Result<QuerySolutionIter<'static>, E> {
+        self(pattern, base_iri)
+    }
+}
+
+/// Closures taking the service name, [`GraphPattern`] and base IRI are [`DefaultServiceHandler`]s.
+impl<F, E> DefaultServiceHandler for F
+where
+    F: Fn(&NamedNode, &GraphPattern, Option<&Iri<String>>) -> Result<QuerySolutionIter<'static>, E>
+        + Send
+        + Sync,
+    E: Error + Send + Sync + 'static,
+{
+    type Error = E;
+
+    #[inline]
+    fn handle(
+        &self,
+        service_name: &NamedNode,
+        pattern: &GraphPattern,
+        base_iri: Option<&Iri<String>>,
+    ) -> Result<QuerySolutionIter<'static>, E> {
+        self(service_name, pattern, base_iri)
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct ServiceHandlerRegistry {
     default: Option<Arc<dyn DefaultServiceHandler<Error = QueryEvaluationError>>>,
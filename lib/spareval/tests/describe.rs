@@ -0,0 +1,191 @@
+//! `DESCRIBE` queries with the different [`DescribeStrategy`]
+
+#![cfg(test)]
+
+use oxrdf::{BlankNode, Dataset, GraphName, Literal, NamedNode, NamedOrBlankNode, Quad, Term};
+use spareval::{DescribeStrategy, QueryEvaluator, QueryResults};
+use spargebra::SparqlParser;
+
+fn ex(name: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("http://example.com/{name}"))
+}
+
+fn bnode(id: &str) -> BlankNode {
+    BlankNode::new_unchecked(id)
+}
+
+/// ex:alice has an address blank node with a geo blank node.
+/// ex:carol and the blank node _:mention point to ex:alice.
+fn create_dataset() -> Dataset {
+    let triples: [(NamedOrBlankNode, &str, Term); 11] = [
+        (ex("alice").into(), "address", bnode("address").into()),
+        (ex("alice").into(), "knows", ex("bob").into()),
+        (bnode("address").into(), "geo", bnode("geo").into()),
+        (bnode("geo").into(), "lat", Literal::from("45.7").into()),
+        (bnode("geo").into(), "part", bnode("part").into()),
+        (bnode("part").into(), "value", Literal::from("x").into()),
+        (ex("bob").into(), "name", Literal::from("Bob").into()),
+        (ex("carol").into(), "knows", ex("alice").into()),
+        (bnode("mention").into(), "about", ex("alice").into()),
+        (bnode("mention").into(), "source", ex("dave").into()),
+        (ex("erin").into(), "location", bnode("geo").into()),
+    ];
+    let mut dataset = Dataset::new();
+    for (subject, predicate, object) in triples {
+        dataset.insert(&Quad::new(
+            subject,
+            ex(predicate),
+            object,
+            GraphName::DefaultGraph,
+        ));
+    }
+    // Named graphs are not part of the description
+    dataset.insert(&Quad::new(
+        ex("alice"),
+        ex("name"),
+        Literal::from("Alice"),
+        ex("graph"),
+    ));
+    dataset
+}
+
+/// Returns the sorted N-Triples serialization of the description
+fn describe(query: &str, strategy: DescribeStrategy) -> Vec<String> {
+    let query = SparqlParser::new()
+        .with_prefix("ex", "http://example.com/")
+        .unwrap()
+        .parse_query(query)
+        .unwrap();
+    let dataset = create_dataset();
+    let QueryResults::Graph(triples) = QueryEvaluator::new()
+        .with_describe_strategy(strategy)
+        .prepare(&query)
+        .execute(&dataset)
+        .unwrap()
+    else {
+        panic!("DESCRIBE queries must return a graph")
+    };
+    let mut triples = triples
+        .map(|triple| triple.unwrap().to_string())
+        .collect::<Vec<_>>();
+    triples.sort();
+    triples
+}
+
+fn triples(triples: &[&str]) -> Vec<String> {
+    let mut triples = triples
+        .iter()
+        .map(|triple| {
+            triple
+                .replace("ex:", "http://example.com/")
+                .split(' ')
+                .map(|term| {
+                    if term.starts_with("http://") {
+                        format!("<{term}>")
+                    } else {
+                        term.into()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+    triples.sort();
+    triples
+}
+
+const ALICE_FORWARD: [&str; 2] = ["ex:alice ex:address _:address", "ex:alice ex:knows ex:bob"];
+
+const ALICE_BLANK_NODES: [&str; 4] = [
+    "_:address ex:geo _:geo",
+    "_:geo ex:lat \"45.7\"",
+    "_:geo ex:part _:part",
+    "_:part ex:value \"x\"",
+];
+
+#[test]
+fn test_cbd_is_default() {
+    assert_eq!(
+        describe("DESCRIBE ex:alice", DescribeStrategy::default()),
+        triples(&[ALICE_FORWARD.as_slice(), &ALICE_BLANK_NODES].concat())
+    );
+}
+
+#[test]
+fn test_forward_only() {
+    assert_eq!(
+        describe("DESCRIBE ex:alice", DescribeStrategy::ForwardOnly),
+        triples(&ALICE_FORWARD)
+    );
+}
+
+#[test]
+fn test_blank_node_closure_depth() {
+    assert_eq!(
+        describe(
+            "DESCRIBE ex:alice",
+            DescribeStrategy::BlankNodeClosure { max_depth: 0 }
+        ),
+        triples(&ALICE_FORWARD)
+    );
+    assert_eq!(
+        describe(
+            "DESCRIBE ex:alice",
+            DescribeStrategy::BlankNodeClosure { max_depth: 1 }
+        ),
+        triples(&[ALICE_FORWARD.as_slice(), &ALICE_BLANK_NODES[..1]].concat())
+    );
+    assert_eq!(
+        describe(
+            "DESCRIBE ex:alice",
+            DescribeStrategy::BlankNodeClosure { max_depth: 2 }
+        ),
+        triples(&[ALICE_FORWARD.as_slice(), &ALICE_BLANK_NODES[..3]].concat())
+    );
+    assert_eq!(
+        describe(
+            "DESCRIBE ex:alice",
+            DescribeStrategy::BlankNodeClosure { max_depth: 3 }
+        ),
+        triples(&[ALICE_FORWARD.as_slice(), &ALICE_BLANK_NODES].concat())
+    );
+}
+
+#[test]
+fn test_blank_node_closure_depth_with_shorter_path_from_other_resource() {
+    // _:geo is first reached at depth 2 from ex:alice then at depth 1 from ex:erin
+    assert_eq!(
+        describe(
+            "DESCRIBE ?s WHERE { VALUES ?s { ex:alice ex:erin } }",
+            DescribeStrategy::BlankNodeClosure { max_depth: 2 }
+        ),
+        triples(
+            &[
+                ALICE_FORWARD.as_slice(),
+                &ALICE_BLANK_NODES,
+                &["ex:erin ex:location _:geo"]
+            ]
+            .concat()
+        )
+    );
+}
+
+#[test]
+fn test_symmetric_cbd() {
+    assert_eq!(
+        describe("DESCRIBE ex:alice", DescribeStrategy::SymmetricCbd),
+        triples(
+            &[
+                ALICE_FORWARD.as_slice(),
+                &ALICE_BLANK_NODES,
+                &[
+                    "ex:carol ex:knows ex:alice",
+                    "_:mention ex:about ex:alice",
+                    "_:mention ex:source ex:dave",
+                    "ex:erin ex:location _:geo",
+                ]
+            ]
+            .concat()
+        )
+    );
+}
@@ -3,7 +3,8 @@
 //! This test suite validates the audit claim that SPARQL SELECT queries
 //! without ORDER BY may produce non-deterministic results due to FxHashMap usage.
 
-use oxrdf::{BlankNode, Dataset, Literal, NamedNode, Quad, GraphName};
+use oxrdf::vocab::xsd;
+use oxrdf::{BlankNode, Dataset, GraphName, Literal, NamedNode, Quad, Term};
 use spareval::{QueryEvaluator, QueryResults};
 use spargebra::SparqlParser;
 
@@ -284,6 +285,90 @@ fn test_aggregation_determinism() {
     }
 }
 
+/// Evaluates a query returning a single `?avg` literal
+fn evaluate_avg(dataset: &Dataset, query_str: &str) -> Literal {
+    let query = SparqlParser::new().parse_query(query_str).unwrap();
+    let QueryResults::Solutions(mut solutions) = QueryEvaluator::new()
+        .prepare(&query)
+        .execute(dataset)
+        .unwrap()
+    else {
+        panic!("SELECT queries return solutions");
+    };
+    let solution = solutions.next().unwrap().unwrap();
+    assert!(
+        solutions.next().is_none(),
+        "AVG without GROUP BY returns a single row"
+    );
+    match solution.get("avg") {
+        Some(Term::Literal(avg)) => avg.clone(),
+        other => panic!("Expected an AVG literal, got {other:?}"),
+    }
+}
+
+/// Dataset of books with their number of pages
+fn create_books_dataset(pages: &[Literal]) -> Dataset {
+    let number_of_pages = NamedNode::new_unchecked("http://schema.org/numberOfPages");
+    pages
+        .iter()
+        .enumerate()
+        .map(|(i, pages)| {
+            Quad::new(
+                NamedNode::new_unchecked(format!("http://example.org/book{i}")),
+                number_of_pages.clone(),
+                pages.clone(),
+                GraphName::DefaultGraph,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_avg_type_promotion() {
+    let query =
+        "SELECT (AVG(?pages) AS ?avg) WHERE { ?book <http://schema.org/numberOfPages> ?pages }";
+
+    // AVG of integers is a decimal computed with the full xsd:decimal precision
+    let integer_pages = [100, 200, 250].map(Literal::from);
+    let avg = evaluate_avg(&create_books_dataset(&integer_pages), query);
+    assert_eq!(avg.datatype(), xsd::DECIMAL);
+    assert_eq!(avg.value(), "183.333333333333333333");
+
+    // Integers and decimals are promoted to decimal
+    let decimal_pages = [
+        Literal::from(100),
+        Literal::new_typed_literal("201.5", xsd::DECIMAL),
+    ];
+    let avg = evaluate_avg(&create_books_dataset(&decimal_pages), query);
+    assert_eq!(avg.datatype(), xsd::DECIMAL);
+    assert_eq!(avg.value(), "150.75");
+
+    // Integers and floats are promoted to float
+    let float_pages = [Literal::from(100), Literal::from(201_f32)];
+    let avg = evaluate_avg(&create_books_dataset(&float_pages), query);
+    assert_eq!(avg.datatype(), xsd::FLOAT);
+    assert_eq!(avg.value(), "150.5");
+
+    // Integers and doubles are promoted to double
+    let double_pages = [Literal::from(100), Literal::from(201_f64)];
+    let avg = evaluate_avg(&create_books_dataset(&double_pages), query);
+    assert_eq!(avg.datatype(), xsd::DOUBLE);
+    assert_eq!(avg.value(), "150.5");
+
+    // The result does not depend on the evaluation run nor on the insertion order
+    let mut reversed_pages = integer_pages.clone();
+    reversed_pages.reverse();
+    for pages in [integer_pages, reversed_pages] {
+        let dataset = create_books_dataset(&pages);
+        for _ in 0..10 {
+            assert_eq!(
+                evaluate_avg(&dataset, query).value(),
+                "183.333333333333333333"
+            );
+        }
+    }
+}
+
 #[test]
 fn test_optional_determinism() {
     // OPTIONAL patterns - test determinism
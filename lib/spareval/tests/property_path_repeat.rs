@@ -0,0 +1,244 @@
+//! Bounded repetitions of property paths (`path{n}`, `path{n,}` and `path{n,m}`)
+//!
+//! They are evaluated level by level, so that only `path{n,}` needs a transitive closure.
+//! They require the `path-repetition` feature.
+
+#![cfg(all(test, feature = "path-repetition"))]
+
+use oxrdf::{Dataset, GraphName, NamedNode, Quad, Term};
+use spareval::{QueryEvaluator, QueryResults};
+use spargebra::SparqlParser;
+
+fn ex(name: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("http://example.com/{name}"))
+}
+
+/// a -> b -> c -> d -> e with the ex:parent predicate
+fn create_chain_dataset() -> Dataset {
+    let mut dataset = Dataset::new();
+    for [child, parent] in [["a", "b"], ["b", "c"], ["c", "d"], ["d", "e"]] {
+        dataset.insert(&Quad::new(
+            ex(child),
+            ex("parent"),
+            ex(parent),
+            GraphName::DefaultGraph,
+        ));
+    }
+    dataset
+}
+
+/// Returns the sorted values of the projected variables of each solution
+fn evaluate(dataset: &Dataset, query: &str) -> Vec<Vec<String>> {
+    let query = SparqlParser::new()
+        .with_prefix("ex", "http://example.com/")
+        .unwrap()
+        .parse_query(query)
+        .unwrap();
+    let QueryResults::Solutions(solutions) = QueryEvaluator::new()
+        .prepare(&query)
+        .execute(dataset)
+        .unwrap()
+    else {
+        panic!("SELECT queries must return solutions")
+    };
+    let mut results = solutions
+        .map(|solution| {
+            solution
+                .unwrap()
+                .values()
+                .iter()
+                .map(|value| value.as_ref().map_or_else(String::new, Term::to_string))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    results.sort();
+    results
+}
+
+fn names(names: &[&str]) -> Vec<Vec<String>> {
+    names
+        .iter()
+        .map(|name| vec![ex(name).to_string()])
+        .collect()
+}
+
+#[test]
+fn test_exact_repetition_returns_only_grandparents() {
+    let results = evaluate(
+        &create_chain_dataset(),
+        "SELECT ?x ?y WHERE { ?x ex:parent{2,2} ?y }",
+    );
+    let expected = [["a", "c"], ["b", "d"], ["c", "e"]]
+        .iter()
+        .map(|pair| pair.iter().map(|name| ex(name).to_string()).collect())
+        .collect::<Vec<Vec<_>>>();
+    assert_eq!(results, expected);
+    assert_eq!(
+        evaluate(
+            &create_chain_dataset(),
+            "SELECT ?y WHERE { ex:a ex:parent{2} ?y }"
+        ),
+        names(&["c"])
+    );
+}
+
+#[test]
+fn test_bounded_repetition() {
+    let dataset = create_chain_dataset();
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?y WHERE { ex:a ex:parent{1,3} ?y }"),
+        names(&["b", "c", "d"])
+    );
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?x WHERE { ?x ex:parent{2,3} ex:e }"),
+        names(&["b", "c"])
+    );
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?y WHERE { ex:a ex:parent{0,1} ?y }"),
+        names(&["a", "b"])
+    );
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?y WHERE { ex:d ex:parent{2,4} ?y }"),
+        names(&[])
+    );
+}
+
+#[test]
+fn test_unbounded_repetition() {
+    let dataset = create_chain_dataset();
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?y WHERE { ex:a ex:parent{2,} ?y }"),
+        names(&["c", "d", "e"])
+    );
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?y WHERE { ex:c ex:parent{0,} ?y }"),
+        names(&["c", "d", "e"])
+    );
+}
+
+#[test]
+fn test_bounded_repetition_on_cycle() {
+    let mut dataset = create_chain_dataset();
+    dataset.insert(&Quad::new(
+        ex("e"),
+        ex("parent"),
+        ex("a"),
+        GraphName::DefaultGraph,
+    ));
+    assert_eq!(
+        evaluate(
+            &dataset,
+            "SELECT DISTINCT ?y WHERE { ex:d ex:parent{1,3} ?y }"
+        ),
+        names(&["a", "b", "e"])
+    );
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?y WHERE { ex:d ex:parent{7} ?y }"),
+        names(&["a"])
+    );
+}
+
+#[test]
+fn test_bounded_repetition_with_unbound_ends() {
+    let dataset = create_chain_dataset();
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?x ?y WHERE { ?x ex:parent{3,4} ?y }"),
+        [["a", "d"], ["a", "e"], ["b", "e"]]
+            .iter()
+            .map(|pair| pair.iter().map(|name| ex(name).to_string()).collect())
+            .collect::<Vec<Vec<_>>>()
+    );
+    // With a zero minimum, every node of the graph is related to itself
+    assert_eq!(
+        evaluate(
+            &dataset,
+            "SELECT ?x WHERE { ?x ex:parent{0,1} ?y FILTER(?x = ?y) }"
+        ),
+        names(&["a", "b", "c", "d", "e"])
+    );
+    assert_eq!(
+        evaluate(&dataset, "SELECT * WHERE { ex:a ex:parent{2,3} ex:d }"),
+        vec![Vec::<String>::new()]
+    );
+    assert_eq!(
+        evaluate(&dataset, "SELECT * WHERE { ex:a ex:parent{1,2} ex:d }"),
+        Vec::<Vec<String>>::new()
+    );
+}
+
+#[test]
+fn test_bounded_repetition_in_named_graph() {
+    let mut dataset = Dataset::new();
+    for [child, parent] in [["a", "b"], ["b", "c"]] {
+        dataset.insert(&Quad::new(ex(child), ex("parent"), ex(parent), ex("g")));
+    }
+    assert_eq!(
+        evaluate(
+            &dataset,
+            "SELECT ?y ?g WHERE { GRAPH ?g { ex:a ex:parent{2} ?y } }"
+        ),
+        vec![vec![ex("c").to_string(), ex("g").to_string()]]
+    );
+    assert_eq!(
+        evaluate(
+            &dataset,
+            "SELECT ?x ?g WHERE { GRAPH ?g { ?x ex:parent{1,2} ex:c } }"
+        ),
+        vec![
+            vec![ex("a").to_string(), ex("g").to_string()],
+            vec![ex("b").to_string(), ex("g").to_string()]
+        ]
+    );
+}
+
+#[test]
+fn test_long_bounded_repetition() {
+    // The repetitions are not nested, so large counts do not overflow the stack
+    let mut dataset = Dataset::new();
+    for i in 0..1100 {
+        dataset.insert(&Quad::new(
+            ex(&format!("n{i}")),
+            ex("next"),
+            ex(&format!("n{}", i + 1)),
+            GraphName::DefaultGraph,
+        ));
+    }
+    assert_eq!(
+        evaluate(&dataset, "SELECT ?y WHERE { ex:n0 ex:next{1024} ?y }"),
+        names(&["n1024"])
+    );
+    assert_eq!(
+        evaluate(
+            &dataset,
+            "SELECT (COUNT(*) AS ?c) WHERE { ex:n0 ex:next{1,1024} ?y }"
+        ),
+        vec![vec![
+            "\"1024\"^^<http://www.w3.org/2001/XMLSchema#integer>".to_owned()
+        ]]
+    );
+}
+
+#[test]
+fn test_bounded_repetition_on_dense_graph() {
+    // Each level is deduplicated, so the work does not grow exponentially with the count
+    let mut dataset = Dataset::new();
+    for i in 0..20 {
+        for j in 0..20 {
+            dataset.insert(&Quad::new(
+                ex(&format!("n{i}")),
+                ex("link"),
+                ex(&format!("n{j}")),
+                GraphName::DefaultGraph,
+            ));
+        }
+    }
+    assert_eq!(
+        evaluate(
+            &dataset,
+            "SELECT (COUNT(*) AS ?c) WHERE { ?x ex:link{2,100} ?y }"
+        ),
+        vec![vec![
+            "\"400\"^^<http://www.w3.org/2001/XMLSchema#integer>".to_owned()
+        ]]
+    );
+}
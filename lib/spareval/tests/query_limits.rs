@@ -1,6 +1,11 @@
-use oxrdf::{Dataset, GraphName, NamedNode, Quad};
-use spareval::{QueryEvaluator, QueryExecutionLimits, QueryResults};
+use oxrdf::{BlankNode, Dataset, GraphName, Literal, NamedNode, Quad, Term, Variable};
+use spareval::{
+    CancellationToken, QueryEvaluationError, QueryEvaluator, QueryExecutionLimits, QueryResults,
+    QuerySolution, QuerySolutionIter,
+};
 use spargebra::SparqlParser;
+use std::cell::Cell;
+use std::sync::Arc;
 use std::time::Duration;
 
 fn create_test_dataset(size: usize) -> Dataset {
@@ -238,7 +243,7 @@ fn test_filter_with_limits() -> Result<(), Box<dyn std::error::Error>> {
         let quad = Quad::new(
             ex.clone(),
             ex.clone(),
-            oxrdf::Literal::from(i),
+            Literal::from(i),
             GraphName::DefaultGraph,
         );
         dataset.insert(&quad);
@@ -334,3 +339,381 @@ fn test_offset_with_limits() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+fn first_error(
+    dataset: &Dataset,
+    query: &str,
+    timeout: Duration,
+) -> Result<Option<QueryEvaluationError>, Box<dyn std::error::Error>> {
+    let query = SparqlParser::new().parse_query(query)?;
+    let evaluator = QueryEvaluator::new().with_timeout(timeout);
+    let QueryResults::Solutions(mut solutions) = evaluator.prepare(&query).execute(dataset)? else {
+        return Err("SELECT queries should return solutions".into());
+    };
+    Ok(solutions.find_map(Result::err))
+}
+
+#[test]
+fn test_timeout_stops_join() -> Result<(), Box<dyn std::error::Error>> {
+    let dataset = create_test_dataset(1_000);
+    let error = first_error(
+        &dataset,
+        "SELECT * WHERE { ?s1 ?p1 ?o1 . ?s2 ?p2 ?o2 . ?s3 ?p3 ?o3 }",
+        Duration::from_millis(1),
+    )?;
+    assert!(matches!(error, Some(QueryEvaluationError::Timeout(_))));
+    Ok(())
+}
+
+#[test]
+fn test_timeout_stops_property_path() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dataset = Dataset::new();
+    let next = NamedNode::new("http://example.com/next")?;
+    for i in 0..1_000 {
+        dataset.insert(&Quad::new(
+            NamedNode::new(format!("http://example.com/n{i}"))?,
+            next.clone(),
+            NamedNode::new(format!("http://example.com/n{}", i + 1))?,
+            GraphName::DefaultGraph,
+        ));
+    }
+    let error = first_error(
+        &dataset,
+        "SELECT * WHERE { ?s <http://example.com/next>* ?o }",
+        Duration::from_millis(1),
+    )?;
+    assert!(matches!(error, Some(QueryEvaluationError::Timeout(_))));
+    Ok(())
+}
+
+#[test]
+fn test_limits_timeout_stops_join() -> Result<(), Box<dyn std::error::Error>> {
+    let dataset = create_test_dataset(1_000);
+    let query = SparqlParser::new()
+        .parse_query("SELECT * WHERE { ?s1 ?p1 ?o1 . ?s2 ?p2 ?o2 . ?s3 ?p3 ?o3 }")?;
+    let evaluator = QueryEvaluator::new().with_limits(QueryExecutionLimits {
+        timeout: Some(Duration::from_millis(1)),
+        ..QueryExecutionLimits::unlimited()
+    });
+    let QueryResults::Solutions(mut solutions) = evaluator.prepare(&query).execute(&dataset)?
+    else {
+        return Err("SELECT queries should return solutions".into());
+    };
+    assert!(matches!(
+        solutions.find_map(Result::err),
+        Some(QueryEvaluationError::Timeout(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_query_within_timeout_succeeds() -> Result<(), Box<dyn std::error::Error>> {
+    let dataset = create_test_dataset(100);
+    let error = first_error(
+        &dataset,
+        "SELECT * WHERE { ?s1 ?p1 ?o1 . ?s2 ?p2 ?o2 }",
+        Duration::from_secs(300),
+    )?;
+    assert!(error.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_cancellation_keeps_partial_results() -> Result<(), Box<dyn std::error::Error>> {
+    let dataset = create_test_dataset(1_000);
+    let query = SparqlParser::new()
+        .parse_query("SELECT * WHERE { ?s1 ?p1 ?o1 . ?s2 ?p2 ?o2 . ?s3 ?p3 ?o3 }")?;
+    let cancellation_token = CancellationToken::new();
+    let evaluator = QueryEvaluator::new().with_cancellation_token(cancellation_token.clone());
+    let QueryResults::Solutions(mut solutions) = evaluator.prepare(&query).execute(&dataset)?
+    else {
+        return Err("SELECT queries should return solutions".into());
+    };
+    let mut partial_solutions = solutions.by_ref().take(5).collect::<Result<Vec<_>, _>>()?;
+    assert!(!solutions.is_truncated());
+    cancellation_token.cancel();
+    for solution in &mut solutions {
+        match solution {
+            Ok(solution) => partial_solutions.push(solution),
+            Err(e) => assert!(matches!(e, QueryEvaluationError::Cancelled) && e.is_interruption()),
+        }
+    }
+    assert!(solutions.is_truncated());
+    assert!(partial_solutions.len() >= 5);
+    // The stream is over after the interruption
+    assert!(solutions.next().is_none());
+    Ok(())
+}
+
+fn create_dense_graph(size: usize) -> Dataset {
+    let connected = NamedNode::new("http://example.com/connected").unwrap();
+    let mut dataset = Dataset::new();
+    for i in 0..size {
+        for j in 0..size {
+            dataset.insert(&Quad::new(
+                NamedNode::new(format!("http://example.com/n{i}")).unwrap(),
+                connected.clone(),
+                NamedNode::new(format!("http://example.com/n{j}")).unwrap(),
+                GraphName::DefaultGraph,
+            ));
+        }
+    }
+    dataset
+}
+
+fn path_results(
+    dataset: &Dataset,
+    query: &str,
+    max_path_visits: usize,
+) -> Result<Vec<Result<QuerySolution, QueryEvaluationError>>, Box<dyn std::error::Error>> {
+    let query = SparqlParser::new().parse_query(query)?;
+    let evaluator = QueryEvaluator::new().with_max_path_visits(max_path_visits);
+    let QueryResults::Solutions(solutions) = evaluator.prepare(&query).execute(dataset)? else {
+        return Err("SELECT queries should return solutions".into());
+    };
+    Ok(solutions.collect())
+}
+
+#[test]
+fn test_max_path_visits_exceeded() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dataset = create_dense_graph(20);
+    let isolated = NamedNode::new("http://example.com/isolated")?;
+    dataset.insert(&Quad::new(
+        isolated.clone(),
+        NamedNode::new("http://example.com/connected")?,
+        isolated,
+        GraphName::DefaultGraph,
+    ));
+    for query in [
+        "SELECT * WHERE { <http://example.com/n0> <http://example.com/connected>* ?o }",
+        "SELECT * WHERE { ?s <http://example.com/connected>+ <http://example.com/n0> }",
+        "SELECT * WHERE { ?s <http://example.com/connected>* ?o }",
+        "SELECT * WHERE { <http://example.com/n0> (^<http://example.com/connected>)+ <http://example.com/isolated> }",
+    ] {
+        let results = path_results(&dataset, query, 10)?;
+        assert!(
+            results
+                .iter()
+                .any(|r| matches!(r, Err(QueryEvaluationError::PropertyPathDepthExceeded(10)))),
+            "{query} should exceed the path limit"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_max_path_visits_is_per_path_evaluation() -> Result<(), Box<dyn std::error::Error>> {
+    let dataset = create_dense_graph(20);
+    // Each path evaluation visits 20 nodes
+    let results = path_results(
+        &dataset,
+        "SELECT * WHERE {
+            <http://example.com/n0> <http://example.com/connected>+ ?a .
+            <http://example.com/n1> <http://example.com/connected>+ ?b
+        }",
+        20,
+    )?;
+    assert_eq!(results.len(), 400);
+    assert!(results.iter().all(Result::is_ok));
+    Ok(())
+}
+
+fn materialization_results(
+    query: &str,
+    max_materialized_rows: usize,
+) -> Result<Vec<Result<QuerySolution, QueryEvaluationError>>, Box<dyn std::error::Error>> {
+    // 10K distinct triples
+    let dataset = create_dense_graph(100);
+    let query = SparqlParser::new().parse_query(query)?;
+    let evaluator = QueryEvaluator::new().with_max_materialized_rows(max_materialized_rows);
+    let QueryResults::Solutions(solutions) = evaluator.prepare(&query).execute(&dataset)? else {
+        return Err("SELECT queries should return solutions".into());
+    };
+    Ok(solutions.collect())
+}
+
+#[test]
+fn test_max_materialized_rows_exceeded() -> Result<(), Box<dyn std::error::Error>> {
+    for query in [
+        "SELECT DISTINCT ?s ?o WHERE { ?s ?p ?o }",
+        "SELECT ?s ?o (COUNT(*) AS ?c) WHERE { ?s ?p ?o } GROUP BY ?s ?o",
+    ] {
+        let results = materialization_results(query, 100)?;
+        // The evaluation stops at the error
+        let Some(Err(QueryEvaluationError::GroupLimitExceeded(100))) = results.last() else {
+            return Err(format!("{query} should exceed the materialization limit").into());
+        };
+        assert!(results.len() <= 101, "{query} should stop at the limit");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_max_materialized_rows_not_exceeded() -> Result<(), Box<dyn std::error::Error>> {
+    for query in [
+        "SELECT DISTINCT ?s WHERE { ?s ?p ?o }",
+        "SELECT ?s (COUNT(*) AS ?c) WHERE { ?s ?p ?o } GROUP BY ?s",
+        "SELECT (COUNT(*) AS ?c) WHERE { ?s ?p ?o }",
+    ] {
+        for result in materialization_results(query, 100)? {
+            result?;
+        }
+    }
+    Ok(())
+}
+
+fn sorted_results(
+    dataset: &Dataset,
+    query: &str,
+    sort_memory_budget: Option<usize>,
+) -> Result<Vec<Vec<Option<Term>>>, Box<dyn std::error::Error>> {
+    let query = SparqlParser::new().parse_query(query)?;
+    let mut evaluator = QueryEvaluator::new();
+    if let Some(sort_memory_budget) = sort_memory_budget {
+        evaluator = evaluator.with_sort_memory_budget(sort_memory_budget);
+    }
+    let QueryResults::Solutions(solutions) = evaluator.prepare(&query).execute(dataset)? else {
+        return Err("SELECT queries should return solutions".into());
+    };
+    solutions
+        .map(|solution| Ok(solution?.values().to_vec()))
+        .collect()
+}
+
+fn spilled_run_files() -> Result<usize, Box<dyn std::error::Error>> {
+    let prefix = format!("spareval-sort-{}-", std::process::id());
+    let mut count = 0;
+    for entry in std::fs::read_dir(std::env::temp_dir())? {
+        if entry?.file_name().to_string_lossy().starts_with(&prefix) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+#[test]
+fn test_sort_memory_budget() -> Result<(), Box<dyn std::error::Error>> {
+    let ex = NamedNode::new("http://example.com/p")?;
+    let mut dataset = Dataset::new();
+    for i in 0..500 {
+        // Many ties on ?o, some unbound ?l and terms that need escaping
+        let object: Term = match i % 4 {
+            0 => Literal::from(i % 7).into(),
+            1 => Literal::new_simple_literal(format!("a\tb\n{}", i % 5)).into(),
+            2 => BlankNode::default().into(),
+            _ => NamedNode::new(format!("http://example.com/o{}", i % 3))?.into(),
+        };
+        let subject = NamedNode::new(format!("http://example.com/s{i}"))?;
+        dataset.insert(&Quad::new(
+            subject.clone(),
+            ex.clone(),
+            object,
+            GraphName::DefaultGraph,
+        ));
+        if i % 2 == 0 {
+            dataset.insert(&Quad::new(
+                subject,
+                NamedNode::new("http://example.com/l")?,
+                Literal::new_language_tagged_literal(format!("l{}", i % 11), "en")?,
+                GraphName::DefaultGraph,
+            ));
+        }
+    }
+    for query in [
+        "SELECT ?s ?o ?l WHERE { ?s <http://example.com/p> ?o OPTIONAL { ?s <http://example.com/l> ?l } } ORDER BY ?o",
+        "SELECT ?s ?o ?l WHERE { ?s <http://example.com/p> ?o OPTIONAL { ?s <http://example.com/l> ?l } } ORDER BY DESC(?l) ?o",
+    ] {
+        let in_memory = sorted_results(&dataset, query, None)?;
+        let spilled = sorted_results(&dataset, query, Some(1000))?;
+        assert_eq!(in_memory.len(), 500);
+        assert_eq!(
+            in_memory, spilled,
+            "{query} should give the same results when spilled"
+        );
+        // One run per solution: the runs are merged in several passes
+        let spilled = sorted_results(&dataset, query, Some(1))?;
+        assert_eq!(
+            in_memory, spilled,
+            "{query} should give the same results when spilled in many runs"
+        );
+    }
+    assert_eq!(spilled_run_files()?, 0);
+
+    // The temporary files are removed even if the iteration is abandoned
+    let query = SparqlParser::new()
+        .parse_query("SELECT ?s ?o WHERE { ?s <http://example.com/p> ?o } ORDER BY ?o")?;
+    let evaluator = QueryEvaluator::new().with_sort_memory_budget(1000);
+    let QueryResults::Solutions(mut solutions) = evaluator.prepare(&query).execute(&dataset)?
+    else {
+        return Err("SELECT queries should return solutions".into());
+    };
+    solutions.next().transpose()?;
+    assert!(spilled_run_files()? > 1);
+    drop(solutions);
+    assert_eq!(spilled_run_files()?, 0);
+
+    // The number of runs kept open at the same time is bounded
+    let evaluator = QueryEvaluator::new().with_sort_memory_budget(1);
+    let QueryResults::Solutions(mut solutions) = evaluator.prepare(&query).execute(&dataset)?
+    else {
+        return Err("SELECT queries should return solutions".into());
+    };
+    solutions.next().transpose()?;
+    let open_runs = spilled_run_files()?;
+    assert!(
+        (2..=64).contains(&open_runs),
+        "{open_runs} runs should have been merged"
+    );
+    drop(solutions);
+    assert_eq!(spilled_run_files()?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_collect_bounded() -> Result<(), Box<dyn std::error::Error>> {
+    let dataset = create_test_dataset(10);
+    let query = SparqlParser::new().parse_query("SELECT ?s WHERE { ?s ?p ?o }")?;
+    let evaluator = QueryEvaluator::new();
+
+    let solutions = evaluator
+        .prepare(&query)
+        .execute(&dataset)?
+        .collect_bounded(10)?;
+    assert_eq!(solutions.len(), 10);
+    assert!(matches!(
+        evaluator
+            .prepare(&query)
+            .execute(&dataset)?
+            .collect_bounded(9),
+        Err(QueryEvaluationError::ResultLimitExceeded(9))
+    ));
+
+    let ask = SparqlParser::new().parse_query("ASK { ?s ?p ?o }")?;
+    assert!(matches!(
+        evaluator
+            .prepare(&ask)
+            .execute(&dataset)?
+            .collect_bounded(10),
+        Err(QueryEvaluationError::NotSolutions)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_collect_bounded_pulls_at_most_max_plus_one() -> Result<(), Box<dyn std::error::Error>> {
+    let pulled = Cell::new(0);
+    let variables: Arc<[Variable]> = [Variable::new("v")?].into();
+    let solutions = QuerySolutionIter::from_tuples(
+        variables,
+        (0..1_000).map(|i| {
+            pulled.set(pulled.get() + 1);
+            Ok(vec![Some(Literal::from(i).into())])
+        }),
+    );
+    assert!(matches!(
+        QueryResults::from(solutions).collect_bounded(5),
+        Err(QueryEvaluationError::ResultLimitExceeded(5))
+    ));
+    assert_eq!(pulled.get(), 6);
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! Variable scoping of sub-SELECT queries
+//!
+//! Per [SPARQL 1.1 §18.2.1](https://www.w3.org/TR/sparql11-query/#variableScope),
+//! only the variables projected by a sub-SELECT are visible to the enclosing query.
+//! Aggregate aliases are visible to the following SELECT expressions and to the enclosing query.
+
+#![cfg(test)]
+
+use oxrdf::{Dataset, GraphName, Literal, NamedNode, Quad, Variable};
+use spareval::{QueryEvaluator, QueryResults, QuerySolution};
+use spargebra::SparqlParser;
+
+fn ex(name: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("http://example.com/{name}"))
+}
+
+/// ex:a and ex:b both have a name, only ex:a has an age
+fn create_test_dataset() -> Dataset {
+    let mut dataset = Dataset::new();
+    for (subject, name) in [("a", "A"), ("b", "B")] {
+        dataset.insert(&Quad::new(
+            ex(subject),
+            ex("name"),
+            Literal::new_simple_literal(name),
+            GraphName::DefaultGraph,
+        ));
+    }
+    dataset.insert(&Quad::new(
+        ex("a"),
+        ex("age"),
+        Literal::from(42),
+        GraphName::DefaultGraph,
+    ));
+    dataset
+}
+
+fn evaluate(query: &str) -> (Vec<Variable>, Vec<QuerySolution>) {
+    let query = SparqlParser::new().parse_query(query).unwrap();
+    let dataset = create_test_dataset();
+    let QueryResults::Solutions(solutions) = QueryEvaluator::new()
+        .prepare(&query)
+        .execute(&dataset)
+        .unwrap()
+    else {
+        panic!("SELECT queries must return solutions")
+    };
+    let variables = solutions.variables().to_vec();
+    let solutions = solutions.collect::<Result<Vec<_>, _>>().unwrap();
+    (variables, solutions)
+}
+
+#[test]
+fn test_non_projected_variable_does_not_join() {
+    // ?name is used inside of the sub-SELECT but not projected:
+    // the outer ?name is a different variable and must not be joined with it
+    let (_, solutions) = evaluate(
+        "PREFIX ex: <http://example.com/>
+        SELECT ?x ?name WHERE {
+            { SELECT ?x WHERE { ?x ex:name ?name } }
+            ex:a ex:name ?name
+        }",
+    );
+    assert_eq!(solutions.len(), 2);
+    for solution in &solutions {
+        assert_eq!(
+            solution.get("name"),
+            Some(&Literal::new_simple_literal("A").into())
+        );
+    }
+}
+
+#[test]
+fn test_non_projected_variable_is_unbound_outside() {
+    let (variables, solutions) = evaluate(
+        "PREFIX ex: <http://example.com/>
+        SELECT * WHERE {
+            { SELECT ?x WHERE { ?x ex:name ?name } }
+        }",
+    );
+    assert_eq!(variables, [Variable::new_unchecked("x")]);
+    assert_eq!(solutions.len(), 2);
+    for solution in &solutions {
+        assert_eq!(solution.get("name"), None);
+    }
+}
+
+#[test]
+fn test_non_projected_variable_is_unbound_in_filter() {
+    // The outer FILTER sees an unbound ?name, so the comparison is an error and removes every solution
+    let (_, solutions) = evaluate(
+        "PREFIX ex: <http://example.com/>
+        SELECT ?x WHERE {
+            { SELECT ?x WHERE { ?x ex:name ?name } }
+            FILTER(?name = \"A\")
+        }",
+    );
+    assert!(solutions.is_empty());
+}
+
+#[test]
+fn test_select_star_in_subquery_exposes_only_in_scope_variables() {
+    // The inner SELECT * only exposes ?x and ?age, not the ?name of the nested sub-SELECT
+    let (mut variables, solutions) = evaluate(
+        "PREFIX ex: <http://example.com/>
+        SELECT * WHERE {
+            {
+                SELECT * WHERE {
+                    { SELECT ?x WHERE { ?x ex:name ?name } }
+                    ?x ex:age ?age
+                }
+            }
+        }",
+    );
+    variables.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    assert_eq!(
+        variables,
+        [Variable::new_unchecked("age"), Variable::new_unchecked("x")]
+    );
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0].get("x"), Some(&ex("a").into()));
+    assert_eq!(solutions[0].get("name"), None);
+}
+
+#[test]
+fn test_bind_over_aggregate_alias() {
+    let (_, solutions) = evaluate(
+        "PREFIX ex: <http://example.com/>
+        SELECT ?count ?doubled WHERE {
+            { SELECT (COUNT(?name) AS ?count) WHERE { ?x ex:name ?name } }
+            BIND(?count * 2 AS ?doubled)
+        }",
+    );
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0].get("count"), Some(&Literal::from(2).into()));
+    assert_eq!(solutions[0].get("doubled"), Some(&Literal::from(4).into()));
+}
+
+#[test]
+fn test_select_expression_over_aggregate_alias() {
+    let (variables, solutions) = evaluate(
+        "PREFIX ex: <http://example.com/>
+        SELECT ?x (COUNT(?name) AS ?count) (?count + 1 AS ?next) WHERE { ?x ex:name ?name } GROUP BY ?x",
+    );
+    assert_eq!(
+        variables,
+        [
+            Variable::new_unchecked("x"),
+            Variable::new_unchecked("count"),
+            Variable::new_unchecked("next")
+        ]
+    );
+    assert_eq!(solutions.len(), 2);
+    for solution in &solutions {
+        assert_eq!(solution.get("count"), Some(&Literal::from(1).into()));
+        assert_eq!(solution.get("next"), Some(&Literal::from(2).into()));
+    }
+}
@@ -15,6 +15,13 @@ pub enum PropertyPathExpression {
     OneOrMore(Box<Self>),
     ZeroOrOne(Box<Self>),
     NegatedPropertySet(Vec<NamedNode>),
+    /// `path{min,max}`: the path repeated between `min` and `max` times, with `max` at least 1.
+    #[cfg(feature = "path-repetition")]
+    Repeat {
+        path: Box<Self>,
+        min: usize,
+        max: usize,
+    },
 }
 
 impl PropertyPathExpression {
@@ -63,6 +70,12 @@ impl PropertyPathExpression {
                 }
                 f.write_str(")")
             }
+            #[cfg(feature = "path-repetition")]
+            Self::Repeat { path, min, max } => {
+                write!(f, "(mod {min} {max} ")?;
+                path.fmt_sse(f)?;
+                f.write_str(")")
+            }
         }
     }
 }
@@ -87,6 +100,8 @@ impl fmt::Display for PropertyPathExpression {
                 }
                 f.write_str(")")
             }
+            #[cfg(feature = "path-repetition")]
+            Self::Repeat { path, min, max } => write!(f, "({path}){{{min},{max}}}"),
         }
     }
 }
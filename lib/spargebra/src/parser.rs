@@ -35,6 +35,7 @@ pub struct SparqlParser {
     base_iri: Option<Iri<String>>,
     prefixes: HashMap<String, String>,
     custom_aggregate_functions: HashSet<NamedNode>,
+    strict_projection: bool,
 }
 
 impl SparqlParser {
@@ -104,6 +105,25 @@ impl SparqlParser {
         self
     }
 
+    /// Makes the parsing of `SELECT` queries fail if a projected variable is never bound by the query pattern.
+    ///
+    /// Such variables are always unbound in the results, which is allowed by the SPARQL specification
+    /// but is most of the time a typo.
+    /// Variables assigned with `BIND`, `VALUES` or a `SELECT` expression are considered bound.
+    ///
+    /// ```
+    /// use spargebra::SparqlParser;
+    ///
+    /// let query = "SELECT ?nmae WHERE { ?s <http://example.com/name> ?name }";
+    /// assert!(SparqlParser::new().parse_query(query).is_ok());
+    /// assert!(SparqlParser::new().with_strict_projection().parse_query(query).is_err());
+    /// ```
+    #[inline]
+    pub fn with_strict_projection(mut self) -> Self {
+        self.strict_projection = true;
+        self
+    }
+
     /// Parse the given query string using the already set options.
     ///
     /// ```
@@ -126,7 +146,11 @@ impl SparqlParser {
         );
         #[cfg(feature = "standard-unicode-escaping")]
         let query = unescape_unicode_codepoints(query);
-        Ok(parser::QueryUnit(&query, &mut state).map_err(SparqlSyntaxErrorKind::Syntax)?)
+        let query = parser::QueryUnit(&query, &mut state).map_err(SparqlSyntaxErrorKind::Syntax)?;
+        if self.strict_projection {
+            check_if_projected_variables_are_bound(&query)?;
+        }
+        Ok(query)
     }
 
     /// Parse the given update string using the already set options.
@@ -183,6 +207,8 @@ enum SparqlSyntaxErrorKind {
     Syntax(#[from] peg::error::ParseError<LineCol>),
     #[error("The blank node {0} cannot be shared by multiple blocks")]
     SharedBlankNode(BlankNode),
+    #[error("The projected variable {0} is never bound in the query pattern")]
+    UnboundProjectedVariable(Variable),
 }
 
 #[cfg(feature = "standard-unicode-escaping")]
@@ -571,6 +597,56 @@ fn new_join(l: GraphPattern, r: GraphPattern) -> GraphPattern {
     }
 }
 
+enum PathMod {
+    ZeroOrOne,
+    ZeroOrMore,
+    OneOrMore,
+    #[cfg(feature = "path-repetition")]
+    Repeat(usize, Option<usize>),
+}
+
+/// Maximal repetition count allowed in a `path{n,m}` property path
+#[cfg(feature = "path-repetition")]
+const MAX_PATH_REPETITION: usize = 1024;
+
+/// Builds the `path{min}`, `path{min,}` and `path{min,max}` bounded repetitions
+///
+/// `path{min,}` becomes `path{min}` followed by `path*`, so that only the unbounded part
+/// needs a transitive closure.
+#[cfg(feature = "path-repetition")]
+fn repeat_path(
+    path: PropertyPathExpression,
+    min: usize,
+    max: Option<usize>,
+) -> Result<PropertyPathExpression, &'static str> {
+    if max.unwrap_or(min).max(min) > MAX_PATH_REPETITION {
+        return Err("Property path repetition counts must not exceed 1024");
+    }
+    match max {
+        None => Ok(match min {
+            0 => PropertyPathExpression::ZeroOrMore(Box::new(path)),
+            1 => PropertyPathExpression::OneOrMore(Box::new(path)),
+            _ => PropertyPathExpression::Sequence(
+                Box::new(PropertyPathExpression::Repeat {
+                    path: Box::new(path.clone()),
+                    min,
+                    max: min,
+                }),
+                Box::new(PropertyPathExpression::ZeroOrMore(Box::new(path))),
+            ),
+        }),
+        Some(0) => Err("Zero-length property path repetitions are not supported"),
+        Some(max) if max < min => {
+            Err("The maximal property path repetition count must not be lower than the minimal one")
+        }
+        Some(max) => Ok(PropertyPathExpression::Repeat {
+            path: Box::new(path),
+            min,
+            max,
+        }),
+    }
+}
+
 fn not_empty_fold<T>(
     iter: impl Iterator<Item = T>,
     combine: impl Fn(T, T) -> T,
@@ -696,6 +772,8 @@ fn build_select(
                             variable: variable.clone(),
                             expression,
                         };
+                        // The following expressions are allowed to use the new variable
+                        visible.insert(variable.clone());
                         variable
                     }
                 };
@@ -795,6 +873,34 @@ fn are_variables_bound(expression: &Expression, variables: &HashSet<Variable>) -
     }
 }
 
+fn check_if_projected_variables_are_bound(query: &Query) -> Result<(), SparqlSyntaxError> {
+    let Query::Select { pattern: root, .. } = query else {
+        return Ok(());
+    };
+    let mut pattern = root;
+    loop {
+        match pattern {
+            GraphPattern::Distinct { inner }
+            | GraphPattern::Reduced { inner }
+            | GraphPattern::Slice { inner, .. }
+            | GraphPattern::OrderBy { inner, .. } => pattern = inner,
+            GraphPattern::Project { inner, variables } => {
+                let mut bound = HashSet::new();
+                inner.on_in_scope_variable(|v| {
+                    bound.insert(v);
+                });
+                return match variables.iter().find(|v| !bound.contains(v)) {
+                    Some(v) => {
+                        Err(SparqlSyntaxErrorKind::UnboundProjectedVariable(v.clone()).into())
+                    }
+                    None => Ok(()),
+                };
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
 /// Called on every variable defined using "AS" or "VALUES"
 #[cfg(feature = "sep-0006")]
 fn add_defined_variables<'a>(pattern: &'a GraphPattern, set: &mut HashSet<&'a Variable>) {
@@ -1831,19 +1937,31 @@ parser! {
         }
         rule PathSequence_item() -> PropertyPathExpression = p:PathEltOrInverse() _ { p }
 
-        rule PathElt() -> PropertyPathExpression = p:PathPrimary() _ o:PathElt_op()? {
+        rule PathElt() -> PropertyPathExpression = p:PathPrimary() _ o:PathElt_op()? {?
             match o {
-                Some('?') => PropertyPathExpression::ZeroOrOne(Box::new(p)),
-                Some('*') => PropertyPathExpression::ZeroOrMore(Box::new(p)),
-                Some('+') => PropertyPathExpression::OneOrMore(Box::new(p)),
-                Some(_) => unreachable!(),
-                None => p
+                Some(PathMod::ZeroOrOne) => Ok(PropertyPathExpression::ZeroOrOne(Box::new(p))),
+                Some(PathMod::ZeroOrMore) => Ok(PropertyPathExpression::ZeroOrMore(Box::new(p))),
+                Some(PathMod::OneOrMore) => Ok(PropertyPathExpression::OneOrMore(Box::new(p))),
+                #[cfg(feature = "path-repetition")]
+                Some(PathMod::Repeat(min, max)) => repeat_path(p, min, max),
+                None => Ok(p)
+            }
+        }
+        rule PathElt_op() -> PathMod =
+            "*" { PathMod::ZeroOrMore } /
+            "+" { PathMod::OneOrMore } /
+            "?" !(['0'..='9'] / PN_CHARS_U()) { PathMod::ZeroOrOne } / // We mandate that this is not a variable
+            r:PathRepeat() {?
+                #[cfg(feature = "path-repetition")]{Ok(PathMod::Repeat(r.0, r.1))}
+                #[cfg(not(feature = "path-repetition"))]{Err("Bounded property path repetitions are not supported")}
             }
+
+        rule PathRepeat() -> (usize, Option<usize>) =
+            "{" _ min:PathRepeat_count() _ "," _ max:PathRepeat_count()? _ "}" { (min, max) } /
+            "{" _ count:PathRepeat_count() _ "}" { (count, Some(count)) }
+        rule PathRepeat_count() -> usize = c:$(INTEGER()) {?
+            c.parse().map_err(|_| "Property path repetition counts must not exceed 1024")
         }
-        rule PathElt_op() -> char =
-            "*" { '*' } /
-            "+" { '+' } /
-            "?" !(['0'..='9'] / PN_CHARS_U()) { '?' } // We mandate that this is not a variable
 
         rule PathEltOrInverse() -> PropertyPathExpression =
             "^" _ p:PathElt() { PropertyPathExpression::Reverse(Box::new(p)) } /
@@ -589,6 +589,56 @@ fn test_property_path_one_or_more() {
     }
 }
 
+#[test]
+#[cfg(feature = "path-repetition")]
+fn test_property_path_bounded_repetition() {
+    // The alternative avoids the sequences to be split into several triple patterns
+    for (repetition, expected) in [
+        ("{1,3}", "(mod 1 3 <http://example.org/p>)"),
+        ("{2}", "(mod 2 2 <http://example.org/p>)"),
+        (
+            "{ 2 , }",
+            "(seq (mod 2 2 <http://example.org/p>) (path* <http://example.org/p>))",
+        ),
+        ("{1,}", "(path+ <http://example.org/p>)"),
+        ("{0,}", "(path* <http://example.org/p>)"),
+        ("{0,1}", "(mod 0 1 <http://example.org/p>)"),
+        ("{1024}", "(mod 1024 1024 <http://example.org/p>)"),
+    ] {
+        let query_str =
+            format!("ASK {{ ?s <http://example.org/p>{repetition}|<http://example.org/q> ?o }}");
+        let query = SparqlParser::new().parse_query(&query_str).unwrap();
+        assert_eq!(
+            query.to_sse(),
+            format!("(ask (path ?s (alt {expected} <http://example.org/q>) ?o))"),
+            "{repetition}"
+        );
+        // The serialized path must be parsed back to the same path
+        let query_str = format!("SELECT * WHERE {{ ?s <http://example.org/p>{repetition} ?o }}");
+        let query = SparqlParser::new().parse_query(&query_str).unwrap();
+        assert_eq!(
+            SparqlParser::new()
+                .parse_query(&query.to_string())
+                .unwrap()
+                .to_sse(),
+            query.to_sse(),
+            "{repetition}"
+        );
+    }
+    for invalid in ["{3,1}", "{0}", "{0,0}", "{2000}", "{,2}", "{}"] {
+        let query_str = format!("ASK {{ ?s <http://example.org/p>{invalid} ?o }}");
+        SparqlParser::new().parse_query(&query_str).unwrap_err();
+    }
+}
+
+#[test]
+#[cfg(not(feature = "path-repetition"))]
+fn test_property_path_bounded_repetition_requires_feature() {
+    SparqlParser::new()
+        .parse_query("ASK { ?s <http://example.org/p>{1,3} ?o }")
+        .unwrap_err();
+}
+
 // Aggregate function tests
 
 #[test]
@@ -624,6 +674,19 @@ fn test_avg_aggregate() {
     }
 }
 
+#[test]
+fn test_select_expression_over_aggregate_alias() {
+    let query_str = "SELECT (AVG(?value) AS ?average) (?average * 2 AS ?doubled) WHERE { ?s <http://example.org/value> ?value }";
+    let query = SparqlParser::new().parse_query(query_str).unwrap();
+    // The serialization can be parsed again
+    SparqlParser::new().parse_query(&query.to_string()).unwrap();
+
+    // Variables that are neither grouped nor aliases are still rejected
+    SparqlParser::new()
+        .parse_query("SELECT (AVG(?value) AS ?average) (?s AS ?t) WHERE { ?s <http://example.org/value> ?value }")
+        .unwrap_err();
+}
+
 #[test]
 fn test_min_max_aggregates() {
     let query_str = "SELECT (MIN(?value) AS ?min) (MAX(?value) AS ?max) WHERE { ?s <http://example.org/value> ?value }";
@@ -9,6 +9,11 @@ use spargebra::algebra::PropertyPathExpression;
 use spargebra::term::{GroundTermPattern, NamedNodePattern};
 use std::cmp::{max, min};
 
+/// Maximal number of rows of a `VALUES` clause evaluated once per input tuple in a for loop join.
+///
+/// Larger `VALUES` clauses are hash joined instead to avoid a nested loop over all their rows.
+const MAX_FOR_LOOP_JOIN_VALUES_SIZE: usize = 64;
+
 pub struct Optimizer;
 
 impl Optimizer {
@@ -18,6 +23,13 @@ impl Optimizer {
         Self::push_filters(pattern, Vec::new(), &VariableTypes::default())
     }
 
+    /// Estimates the number of results of the pattern if no variable is bound before its evaluation.
+    ///
+    /// It is the same rough heuristic as the one used to reorder joins.
+    pub fn estimate_graph_pattern_size(pattern: &GraphPattern) -> usize {
+        estimate_graph_pattern_size(pattern, &VariableTypes::default())
+    }
+
     /// Normalize the pattern, discarding any join ordering information
     fn normalize_pattern(pattern: GraphPattern, input_types: &VariableTypes) -> GraphPattern {
         match pattern {
@@ -451,7 +463,21 @@ impl Optimizer {
                 GraphPattern::reduced(Self::push_filters(*inner, filters, input_types))
             }
             GraphPattern::Project { inner, variables } => {
-                GraphPattern::project(Self::push_filters(*inner, filters, input_types), variables)
+                // Variables that are not projected are out of scope: they are not the same as the ones inside
+                let (inner_filters, outer_filters): (Vec<_>, Vec<_>) =
+                    filters.into_iter().partition(|filter| {
+                        filter
+                            .used_variables()
+                            .into_iter()
+                            .all(|v| variables.contains(v))
+                    });
+                GraphPattern::filter(
+                    GraphPattern::project(
+                        Self::push_filters(*inner, inner_filters, input_types),
+                        variables,
+                    ),
+                    Expression::and_all(outer_filters),
+                )
             }
             GraphPattern::OrderBy { inner, expression } => {
                 GraphPattern::order_by(Self::push_filters(*inner, filters, input_types), expression)
@@ -734,8 +760,8 @@ fn is_fit_for_for_loop_join(
 ) -> bool {
     // TODO: think more about it
     match pattern {
-        GraphPattern::Values { .. }
-        | GraphPattern::QuadPattern { .. }
+        GraphPattern::Values { bindings, .. } => bindings.len() <= MAX_FOR_LOOP_JOIN_VALUES_SIZE,
+        GraphPattern::QuadPattern { .. }
         | GraphPattern::Path { .. }
         | GraphPattern::Graph { .. } => true,
         #[cfg(feature = "sep-0006")]
@@ -1064,6 +1090,16 @@ fn estimate_path_size(start_bound: bool, path: &PropertyPathExpression, end_boun
         PropertyPathExpression::NegatedPropertySet(_) => {
             estimate_triple_pattern_size(start_bound, false, end_bound)
         }
+        #[cfg(feature = "path-repetition")]
+        PropertyPathExpression::Repeat { path: p, min, max } => {
+            if start_bound && end_bound {
+                1
+            } else if *min == 0 && !start_bound && !end_bound {
+                1_000_000_000
+            } else {
+                estimate_path_size(start_bound, p, end_bound).saturating_mul(*max)
+            }
+        }
     }
 }
 
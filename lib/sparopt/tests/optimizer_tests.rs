@@ -1,5 +1,5 @@
-use oxrdf::Variable;
-use spargebra::term::{GroundTermPattern, NamedNodePattern};
+use oxrdf::{NamedNode, Variable};
+use spargebra::term::{GroundTerm, GroundTermPattern, NamedNodePattern};
 use sparopt::algebra::{Expression, GraphPattern, JoinAlgorithm, LeftJoinAlgorithm};
 use sparopt::Optimizer;
 
@@ -557,3 +557,69 @@ fn test_complex_nested_pattern() {
         _ => panic!("Expected Extend pattern"),
     }
 }
+
+// Test 21: Filter Pushing - Projection
+#[test]
+fn test_filter_pushing_project() {
+    // Only filters on projected variables may be pushed into a sub-SELECT:
+    // the ?o inside of the projection is not the same variable as the ?o outside of it
+    let pattern = GraphPattern::filter(
+        GraphPattern::project(triple_pattern("s", "p", "o"), vec![var("s")]),
+        Expression::and_all([var_expr("s"), var_expr("o")]),
+    );
+
+    let optimized = Optimizer::optimize_graph_pattern(pattern);
+
+    match optimized {
+        GraphPattern::Filter { inner, expression } => {
+            assert_eq!(expression, var_expr("o"));
+            match *inner {
+                GraphPattern::Project { inner: projected, .. } => match *projected {
+                    GraphPattern::Filter { expression, .. } => {
+                        assert_eq!(expression, var_expr("s"));
+                    }
+                    _ => panic!("Expected Filter inside of Project, got: {projected:?}"),
+                },
+                _ => panic!("Expected Project pattern, got: {inner:?}"),
+            }
+        }
+        _ => panic!("Expected Filter pattern, got: {optimized:?}"),
+    }
+}
+
+// Test 22: Large VALUES are hash joined
+#[test]
+fn test_large_values_hash_join() {
+    // <s> ?p ?o is estimated smaller than the VALUES so it is evaluated first,
+    // the VALUES must not be scanned again for each of its solutions
+    let pattern = GraphPattern::join(
+        GraphPattern::QuadPattern {
+            subject: GroundTermPattern::NamedNode(NamedNode::new_unchecked("http://example.com/s")),
+            predicate: NamedNodePattern::Variable(var("p")),
+            object: GroundTermPattern::Variable(var("o")),
+            graph_name: None,
+        },
+        GraphPattern::values(
+            vec![var("o")],
+            (0..1000)
+                .map(|i| vec![Some(GroundTerm::Literal(i.into()))])
+                .collect(),
+        ),
+        JoinAlgorithm::HashBuildLeftProbeRight { keys: vec![] },
+    );
+
+    let optimized = Optimizer::optimize_graph_pattern(pattern);
+
+    match optimized {
+        GraphPattern::Join {
+            left,
+            right,
+            algorithm: JoinAlgorithm::HashBuildLeftProbeRight { keys },
+        } => {
+            assert!(matches!(*left, GraphPattern::QuadPattern { .. }));
+            assert!(matches!(*right, GraphPattern::Values { .. }));
+            assert_eq!(keys, vec![var("o")]);
+        }
+        _ => panic!("Expected hash Join pattern, got: {optimized:?}"),
+    }
+}
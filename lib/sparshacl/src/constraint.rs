@@ -5,6 +5,8 @@
 use oxrdf::{Literal, NamedNode, NamedNodeRef, Term};
 
 use crate::model::ShapeId;
+#[cfg(feature = "sparql")]
+use crate::sparql::SparqlConstraint;
 
 /// Represents a SHACL constraint.
 #[derive(Debug, Clone, PartialEq)]
@@ -105,6 +107,11 @@ pub enum Constraint {
 
     /// sh:in - Value must be one of the specified values.
     In(Vec<Term>),
+
+    // === SPARQL Constraints ===
+    /// sh:sparql - The SELECT query must not return any solution.
+    #[cfg(feature = "sparql")]
+    Sparql(Box<SparqlConstraint>),
 }
 
 impl Constraint {
@@ -139,6 +146,8 @@ impl Constraint {
             Self::Closed { .. } => shacl::CLOSED_CONSTRAINT_COMPONENT,
             Self::HasValue(_) => shacl::HAS_VALUE_CONSTRAINT_COMPONENT,
             Self::In(_) => shacl::IN_CONSTRAINT_COMPONENT,
+            #[cfg(feature = "sparql")]
+            Self::Sparql(_) => shacl::SPARQL_CONSTRAINT_COMPONENT,
         }
     }
 
@@ -172,8 +181,67 @@ impl Constraint {
             Self::Closed { .. } => "closed",
             Self::HasValue(_) => "hasValue",
             Self::In(_) => "in",
+            #[cfg(feature = "sparql")]
+            Self::Sparql(_) => "sparql",
         }
     }
+
+    /// Returns the value of the constraint parameter, bound to [`name`](Self::name) in `sh:message` templates.
+    ///
+    /// Lists are joined with spaces.
+    pub(crate) fn parameter_value(&self) -> Option<Term> {
+        Some(match self {
+            Self::Class(node)
+            | Self::Datatype(node)
+            | Self::NodeKind(node)
+            | Self::Equals(node)
+            | Self::Disjoint(node)
+            | Self::LessThan(node)
+            | Self::LessThanOrEquals(node) => node.clone().into(),
+            Self::MinCount(count)
+            | Self::MaxCount(count)
+            | Self::MinLength(count)
+            | Self::MaxLength(count) => Literal::from(u64::try_from(*count).ok()?).into(),
+            Self::MinExclusive(value)
+            | Self::MaxExclusive(value)
+            | Self::MinInclusive(value)
+            | Self::MaxInclusive(value) => value.clone().into(),
+            Self::Pattern { pattern, .. } => Literal::new_simple_literal(pattern).into(),
+            Self::LanguageIn(languages) => Literal::new_simple_literal(languages.join(" ")).into(),
+            Self::UniqueLang | Self::Closed { .. } => Literal::from(true).into(),
+            Self::Not(shape) | Self::Node(shape) | Self::QualifiedValueShape { shape, .. } => {
+                shape.to_term()
+            }
+            Self::And(shapes) | Self::Or(shapes) | Self::Xone(shapes) => {
+                join_values(shapes.iter().map(ShapeId::to_term))
+            }
+            Self::HasValue(value) => value.clone(),
+            Self::In(values) => join_values(values.iter().cloned()),
+            #[cfg(feature = "sparql")]
+            Self::Sparql(_) => return None,
+        })
+    }
+
+    /// Returns the shapes the value nodes are validated against.
+    pub(crate) fn referenced_shapes(&self) -> Vec<&ShapeId> {
+        match self {
+            Self::Not(shape) | Self::Node(shape) | Self::QualifiedValueShape { shape, .. } => {
+                vec![shape]
+            }
+            Self::And(shapes) | Self::Or(shapes) | Self::Xone(shapes) => shapes.iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn join_values(values: impl Iterator<Item = Term>) -> Term {
+    let values = values
+        .map(|value| match value {
+            Term::Literal(literal) => literal.value().to_owned(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>();
+    Literal::new_simple_literal(values.join(" ")).into()
 }
 
 /// Represents a constraint component (the type of constraint).
@@ -206,6 +274,8 @@ pub enum ConstraintComponent {
     Closed,
     HasValue,
     In,
+    #[cfg(feature = "sparql")]
+    Sparql,
 }
 
 impl ConstraintComponent {
@@ -240,6 +310,8 @@ impl ConstraintComponent {
             Self::Closed => shacl::CLOSED_CONSTRAINT_COMPONENT,
             Self::HasValue => shacl::HAS_VALUE_CONSTRAINT_COMPONENT,
             Self::In => shacl::IN_CONSTRAINT_COMPONENT,
+            #[cfg(feature = "sparql")]
+            Self::Sparql => shacl::SPARQL_CONSTRAINT_COMPONENT,
         }
     }
 }
@@ -274,6 +346,8 @@ impl From<&Constraint> for ConstraintComponent {
             Constraint::Closed { .. } => Self::Closed,
             Constraint::HasValue(_) => Self::HasValue,
             Constraint::In(_) => Self::In,
+            #[cfg(feature = "sparql")]
+            Constraint::Sparql(_) => Self::Sparql,
         }
     }
 }
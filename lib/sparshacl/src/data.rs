@@ -0,0 +1,132 @@
+//! Access to the data graph being validated.
+//!
+//! The validator only reads the data graph through the [`DataGraph`] trait
+//! so that it can validate data stored elsewhere than in an in-memory [`Graph`].
+
+use oxrdf::{
+    Graph, NamedNode, NamedNodeRef, NamedOrBlankNode, NamedOrBlankNodeRef, Term, TermRef, Triple,
+    TripleRef,
+};
+
+use crate::error::ShaclError;
+
+/// A source of triples that can be validated by a [`ShaclValidator`](crate::ShaclValidator).
+///
+/// It is implemented by [`Graph`] and can be implemented for other storages
+/// to validate their content without copying it into a [`Graph`] first.
+/// Reading errors should be returned as [`ShaclError::DataGraph`].
+pub trait DataGraph {
+    /// Returns the objects of the triples with the given subject and predicate.
+    fn objects_for_subject_predicate<'a>(
+        &'a self,
+        subject: NamedOrBlankNodeRef<'_>,
+        predicate: NamedNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<Term, ShaclError>> + 'a>;
+
+    /// Returns the subjects of the triples with the given predicate and object.
+    fn subjects_for_predicate_object<'a>(
+        &'a self,
+        predicate: NamedNodeRef<'_>,
+        object: TermRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<NamedOrBlankNode, ShaclError>> + 'a>;
+
+    /// Returns the subjects and objects of the triples with the given predicate.
+    fn subjects_objects_for_predicate<'a>(
+        &'a self,
+        predicate: NamedNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<(NamedOrBlankNode, Term), ShaclError>> + 'a>;
+
+    /// Returns the predicates and objects of the triples with the given subject.
+    fn predicates_objects_for_subject<'a>(
+        &'a self,
+        subject: NamedOrBlankNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<(NamedNode, Term), ShaclError>> + 'a>;
+
+    /// Returns the triples matching a pattern, `None` matching any term.
+    fn triples_for_pattern<'a>(
+        &'a self,
+        subject: Option<NamedOrBlankNodeRef<'_>>,
+        predicate: Option<NamedNodeRef<'_>>,
+        object: Option<TermRef<'_>>,
+    ) -> Box<dyn Iterator<Item = Result<Triple, ShaclError>> + 'a>;
+}
+
+impl DataGraph for Graph {
+    fn objects_for_subject_predicate<'a>(
+        &'a self,
+        subject: NamedOrBlankNodeRef<'_>,
+        predicate: NamedNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<Term, ShaclError>> + 'a> {
+        Box::new(
+            self.objects_for_subject_predicate(subject, predicate)
+                .map(|o| Ok(o.into_owned())),
+        )
+    }
+
+    fn subjects_for_predicate_object<'a>(
+        &'a self,
+        predicate: NamedNodeRef<'_>,
+        object: TermRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<NamedOrBlankNode, ShaclError>> + 'a> {
+        Box::new(
+            self.subjects_for_predicate_object(predicate, object)
+                .map(|s| Ok(s.into_owned())),
+        )
+    }
+
+    fn subjects_objects_for_predicate<'a>(
+        &'a self,
+        predicate: NamedNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<(NamedOrBlankNode, Term), ShaclError>> + 'a> {
+        Box::new(
+            self.triples_for_predicate(predicate)
+                .map(|t| Ok((t.subject.into_owned(), t.object.into_owned()))),
+        )
+    }
+
+    fn predicates_objects_for_subject<'a>(
+        &'a self,
+        subject: NamedOrBlankNodeRef<'_>,
+    ) -> Box<dyn Iterator<Item = Result<(NamedNode, Term), ShaclError>> + 'a> {
+        Box::new(
+            self.triples_for_subject(subject)
+                .map(|t| Ok((t.predicate.into_owned(), t.object.into_owned()))),
+        )
+    }
+
+    fn triples_for_pattern<'a>(
+        &'a self,
+        subject: Option<NamedOrBlankNodeRef<'_>>,
+        predicate: Option<NamedNodeRef<'_>>,
+        object: Option<TermRef<'_>>,
+    ) -> Box<dyn Iterator<Item = Result<Triple, ShaclError>> + 'a> {
+        let triples: Box<dyn Iterator<Item = TripleRef<'a>>> = match (subject, predicate, object) {
+            (Some(subject), _, _) => Box::new(self.triples_for_subject(subject)),
+            (None, Some(predicate), _) => Box::new(self.triples_for_predicate(predicate)),
+            (None, None, Some(object)) => Box::new(self.triples_for_object(object)),
+            (None, None, None) => Box::new(self.iter()),
+        };
+        // The returned iterator can't borrow the pattern terms
+        let predicate = predicate.map(NamedNodeRef::into_owned);
+        let object = object.map(TermRef::into_owned);
+        Box::new(
+            triples
+                .filter(move |t| {
+                    predicate.as_ref().is_none_or(|p| t.predicate == *p)
+                        && object.as_ref().is_none_or(|o| t.object == o.as_ref())
+                })
+                .map(|t| Ok(t.into_owned())),
+        )
+    }
+}
+
+/// Returns the subject form of a term or `None` if it can't be the subject of a triple.
+pub(crate) fn as_subject(term: TermRef<'_>) -> Option<NamedOrBlankNodeRef<'_>> {
+    match term {
+        TermRef::NamedNode(node) => Some(node.into()),
+        TermRef::BlankNode(node) => Some(node.into()),
+        TermRef::Literal(_) => None,
+        #[cfg(feature = "rdf-12")]
+        TermRef::Triple(_) => None,
+    }
+}
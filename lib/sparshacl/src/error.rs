@@ -1,6 +1,8 @@
 //! Error types for SHACL validation.
 
 use oxrdf::{NamedNode, Term};
+use std::error::Error;
+use std::time::Duration;
 
 /// Main error type for SHACL operations.
 #[derive(Debug, thiserror::Error)]
@@ -13,6 +15,18 @@ pub enum ShaclError {
     /// Error during validation.
     #[error(transparent)]
     Validation(#[from] ShaclValidationError),
+
+    /// Validation exceeded the configured timeout.
+    #[error("SHACL validation exceeded the timeout limit of {0:?}")]
+    Timeout(Duration),
+
+    /// Validation exceeded the configured maximum recursion depth.
+    #[error("Maximum recursion depth ({depth}) exceeded during validation")]
+    RecursionLimit { depth: usize },
+
+    /// Error while reading the data graph.
+    #[error("Error while reading the data graph: {0}")]
+    DataGraph(#[source] Box<dyn Error + Send + Sync>),
 }
 
 /// Error type for parsing shapes from RDF graphs.
@@ -71,6 +85,7 @@ pub enum ShaclParseError {
 #[non_exhaustive]
 pub enum ShaclValidationError {
     /// Maximum recursion depth exceeded.
+    #[deprecated(note = "Use ShaclError::RecursionLimit, returned by the validator instead")]
     #[error("Maximum recursion depth ({depth}) exceeded during validation")]
     MaxRecursionDepth { depth: usize },
 
@@ -166,6 +181,8 @@ impl ShaclParseError {
 
 impl ShaclValidationError {
     /// Creates a max recursion depth error.
+    #[deprecated(note = "Use ShaclError::RecursionLimit, returned by the validator instead")]
+    #[expect(deprecated)]
     pub fn max_recursion_depth(depth: usize) -> Self {
         Self::MaxRecursionDepth { depth }
     }
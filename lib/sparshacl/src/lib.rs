@@ -5,17 +5,23 @@
 #![doc(html_logo_url = "https://raw.githubusercontent.com/oxigraph/oxigraph/main/logo.svg")]
 
 mod constraint;
+mod data;
 mod error;
 mod model;
 mod path;
 mod report;
+#[cfg(feature = "sparql")]
+mod sparql;
 mod validator;
 
 pub use constraint::{Constraint, ConstraintComponent};
+pub use data::DataGraph;
 pub use error::{ShaclError, ShaclParseError, ShaclValidationError};
 pub use model::{NodeShape, PropertyShape, Shape, ShapeId, ShapesGraph, Target};
 pub use path::PropertyPath;
 pub use report::{Severity, ValidationReport, ValidationResult};
+#[cfg(feature = "sparql")]
+pub use sparql::SparqlConstraint;
 pub use validator::ShaclValidator;
 
 // Re-export vocabulary for convenience
@@ -16,9 +16,14 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use std::sync::Arc;
 
 use crate::constraint::Constraint;
-use crate::error::ShaclParseError;
+use crate::data::{DataGraph, as_subject};
+use crate::error::{ShaclError, ShaclParseError};
 use crate::path::PropertyPath;
 use crate::report::Severity;
+#[cfg(feature = "sparql")]
+use crate::sparql::SparqlConstraint;
+#[cfg(feature = "sparql")]
+use spargebra::SparqlParser;
 
 /// Unique identifier for a shape.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -94,15 +99,24 @@ pub enum Target {
 impl Target {
     /// Finds all focus nodes matching this target in a data graph.
     pub fn find_focus_nodes(&self, graph: &Graph) -> Vec<Term> {
-        match self {
+        // Reading an in-memory graph never fails
+        self.find_focus_nodes_in(graph).unwrap_or_default()
+    }
+
+    /// Finds all focus nodes matching this target in any data graph.
+    pub(crate) fn find_focus_nodes_in(
+        &self,
+        graph: &dyn DataGraph,
+    ) -> Result<Vec<Term>, ShaclError> {
+        Ok(match self {
             Self::Class(class) => {
                 // Find all instances of the class (including subclass instances)
                 let mut instances = Vec::new();
-                let classes = get_class_hierarchy(graph, class);
+                let classes = get_class_hierarchy(graph, class)?;
 
                 for cls in classes {
-                    for subj in graph.subjects_for_predicate_object(rdf::TYPE, &cls) {
-                        instances.push(subj.into_owned().into());
+                    for subj in graph.subjects_for_predicate_object(rdf::TYPE, cls.as_ref()) {
+                        instances.push(subj?.into());
                     }
                 }
 
@@ -116,39 +130,93 @@ impl Target {
 
             Self::SubjectsOf(predicate) => {
                 // All subjects of triples with this predicate
-                let mut subjects = Vec::new();
-                for triple in graph.triples_for_predicate(predicate) {
-                    subjects.push(triple.subject.into_owned().into());
-                }
-                subjects
+                graph
+                    .subjects_objects_for_predicate(predicate.as_ref())
+                    .map(|t| Ok(t?.0.into()))
+                    .collect::<Result<_, ShaclError>>()?
             }
 
             Self::ObjectsOf(predicate) => {
                 // All objects of triples with this predicate
-                let mut objects = Vec::new();
-                for triple in graph.triples_for_predicate(predicate) {
-                    objects.push(triple.object.into_owned());
-                }
-                objects
+                graph
+                    .subjects_objects_for_predicate(predicate.as_ref())
+                    .map(|t| Ok(t?.1))
+                    .collect::<Result<_, ShaclError>>()?
             }
 
             Self::Implicit(class) => {
                 // Same as Class target
-                Self::Class(class.clone()).find_focus_nodes(graph)
+                Self::Class(class.clone()).find_focus_nodes_in(graph)?
             }
-        }
+        })
+    }
+
+    /// Checks if this target selects the given node without enumerating all its focus nodes.
+    pub(crate) fn selects_in(
+        &self,
+        graph: &dyn DataGraph,
+        node: &Term,
+    ) -> Result<bool, ShaclError> {
+        Ok(match self {
+            Self::Class(class) | Self::Implicit(class) => {
+                let Some(subject) = as_subject(node.as_ref()) else {
+                    return Ok(false);
+                };
+                // Walk up the superclasses of the node types
+                let target = Term::NamedNode(class.clone());
+                let mut classes = graph
+                    .objects_for_subject_predicate(subject, rdf::TYPE)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut to_check = classes.clone();
+                while let Some(current) = to_check.pop() {
+                    if current == target {
+                        return Ok(true);
+                    }
+                    let Some(current) = as_subject(current.as_ref()) else {
+                        continue;
+                    };
+                    for superclass in
+                        graph.objects_for_subject_predicate(current, rdfs::SUB_CLASS_OF)
+                    {
+                        let superclass = superclass?;
+                        if !classes.contains(&superclass) {
+                            classes.push(superclass.clone());
+                            to_check.push(superclass);
+                        }
+                    }
+                }
+                false
+            }
+
+            Self::Node(target) => target == node,
+
+            Self::SubjectsOf(predicate) => match as_subject(node.as_ref()) {
+                Some(subject) => graph
+                    .objects_for_subject_predicate(subject, predicate.as_ref())
+                    .next()
+                    .transpose()?
+                    .is_some(),
+                None => false,
+            },
+
+            Self::ObjectsOf(predicate) => graph
+                .subjects_for_predicate_object(predicate.as_ref(), node.as_ref())
+                .next()
+                .transpose()?
+                .is_some(),
+        })
     }
 }
 
 /// Gets a class and all its subclasses.
-fn get_class_hierarchy(graph: &Graph, class: &NamedNode) -> Vec<Term> {
+fn get_class_hierarchy(graph: &dyn DataGraph, class: &NamedNode) -> Result<Vec<Term>, ShaclError> {
     let mut classes = vec![Term::NamedNode(class.clone())];
     let mut to_check: Vec<Term> = vec![Term::NamedNode(class.clone())];
 
     while let Some(current) = to_check.pop() {
         // Find subclasses
         for subclass in graph.subjects_for_predicate_object(rdfs::SUB_CLASS_OF, current.as_ref()) {
-            let subclass_term: Term = subclass.into_owned().into();
+            let subclass_term: Term = subclass?.into();
             if !classes.contains(&subclass_term) {
                 classes.push(subclass_term.clone());
                 to_check.push(subclass_term);
@@ -156,7 +224,7 @@ fn get_class_hierarchy(graph: &Graph, class: &NamedNode) -> Vec<Term> {
         }
     }
 
-    classes
+    Ok(classes)
 }
 
 /// Base shape containing common properties.
@@ -178,8 +246,8 @@ pub struct Shape {
     pub name: Option<String>,
     /// Human-readable description.
     pub description: Option<String>,
-    /// Custom validation message.
-    pub message: Option<String>,
+    /// Custom validation message templates, possibly in several languages.
+    pub messages: Vec<Literal>,
 }
 
 impl Shape {
@@ -194,7 +262,7 @@ impl Shape {
             deactivated: false,
             name: None,
             description: None,
-            message: None,
+            messages: Vec::new(),
         }
     }
 
@@ -217,6 +285,12 @@ impl Shape {
     pub fn add_property_shape(&mut self, property_shape: Arc<PropertyShape>) {
         self.property_shapes.push(property_shape);
     }
+
+    /// Returns the first validation message template.
+    #[deprecated(note = "Use `messages` instead", since = "0.1.1")]
+    pub fn message(&self) -> Option<&str> {
+        self.messages.first().map(Literal::value)
+    }
 }
 
 /// Node shape for validating focus nodes directly.
@@ -292,28 +366,35 @@ impl ShapesGraph {
 
     /// Parses shapes from an RDF graph.
     pub fn from_graph(graph: &Graph) -> Result<Self, ShaclParseError> {
+        match Self::from_data_graph(graph) {
+            Ok(shapes_graph) => Ok(shapes_graph),
+            Err(ShaclError::Parse(e)) => Err(e),
+            Err(e) => unreachable!("Reading an in-memory graph never fails: {e}"),
+        }
+    }
+
+    /// Parses shapes from any [`DataGraph`].
+    ///
+    /// It allows to read shapes without copying them into a [`Graph`] first.
+    /// The shapes are the same as with [`from_graph`](Self::from_graph).
+    pub fn from_data_graph(graph: &dyn DataGraph) -> Result<Self, ShaclError> {
         let mut shapes_graph = Self::new();
 
         // Find all node shapes
-        for shape_node in graph.subjects_for_predicate_object(rdf::TYPE, shacl::NODE_SHAPE) {
-            let id = ShapeId::from_named_or_blank(shape_node.into_owned());
+        for shape_node in graph.subjects_for_predicate_object(rdf::TYPE, shacl::NODE_SHAPE.into()) {
+            let id = ShapeId::from_named_or_blank(shape_node?);
             let node_shape = parse_node_shape(graph, &id)?;
             shapes_graph.add_node_shape(Arc::new(node_shape));
         }
 
         // Find shapes that are both sh:NodeShape and rdfs:Class (implicit targets)
-        for shape_node in graph.subjects_for_predicate_object(rdf::TYPE, shacl::SHAPE) {
-            let id = ShapeId::from_named_or_blank(shape_node.into_owned());
+        for shape_node in graph.subjects_for_predicate_object(rdf::TYPE, shacl::SHAPE.into()) {
+            let id = ShapeId::from_named_or_blank(shape_node?);
             if !shapes_graph.node_shapes.contains_key(&id) {
                 // Check if it's also a class (implicit target)
-                let is_class = match &id {
-                    ShapeId::Named(n) => graph
-                        .objects_for_subject_predicate(n, rdf::TYPE)
-                        .any(|t| t == TermRef::NamedNode(rdfs::CLASS)),
-                    ShapeId::Blank(b) => graph
-                        .objects_for_subject_predicate(b, rdf::TYPE)
-                        .any(|t| t == TermRef::NamedNode(rdfs::CLASS)),
-                };
+                let is_class = get_objects(graph, &id.to_term(), rdf::TYPE)?
+                    .iter()
+                    .any(|t| t.as_ref() == TermRef::NamedNode(rdfs::CLASS));
 
                 if is_class {
                     let mut node_shape = parse_node_shape(graph, &id)?;
@@ -326,8 +407,10 @@ impl ShapesGraph {
         }
 
         // Find standalone property shapes (not nested)
-        for shape_node in graph.subjects_for_predicate_object(rdf::TYPE, shacl::PROPERTY_SHAPE) {
-            let id = ShapeId::from_named_or_blank(shape_node.into_owned());
+        for shape_node in
+            graph.subjects_for_predicate_object(rdf::TYPE, shacl::PROPERTY_SHAPE.into())
+        {
+            let id = ShapeId::from_named_or_blank(shape_node?);
             if !shapes_graph.property_shapes.contains_key(&id) {
                 if let Some(property_shape) = parse_property_shape(graph, &id)? {
                     shapes_graph.add_property_shape(Arc::new(property_shape));
@@ -335,6 +418,30 @@ impl ShapesGraph {
             }
         }
 
+        // Shapes referenced by other shapes do not need to be typed
+        let mut referenced = Vec::new();
+        for shape in shapes_graph.node_shapes.values() {
+            collect_referenced_shapes(&shape.base, &mut referenced);
+        }
+        for shape in shapes_graph.property_shapes.values() {
+            collect_referenced_shapes(&shape.base, &mut referenced);
+        }
+        while let Some(id) = referenced.pop() {
+            if shapes_graph.node_shapes.contains_key(&id)
+                || shapes_graph.property_shapes.contains_key(&id)
+            {
+                continue;
+            }
+            if let Some(property_shape) = parse_property_shape(graph, &id)? {
+                collect_referenced_shapes(&property_shape.base, &mut referenced);
+                shapes_graph.add_property_shape(Arc::new(property_shape));
+            } else {
+                let node_shape = parse_node_shape(graph, &id)?;
+                collect_referenced_shapes(&node_shape.base, &mut referenced);
+                shapes_graph.add_node_shape(Arc::new(node_shape));
+            }
+        }
+
         Ok(shapes_graph)
     }
 
@@ -395,109 +502,138 @@ impl Default for ShapesGraph {
 
 // Parsing helpers
 
-fn parse_node_shape(graph: &Graph, id: &ShapeId) -> Result<NodeShape, ShaclParseError> {
+/// Adds the shapes referenced by the constraints of `shape` and of its property shapes to `referenced`.
+fn collect_referenced_shapes(shape: &Shape, referenced: &mut Vec<ShapeId>) {
+    for constraint in &shape.constraints {
+        referenced.extend(constraint.referenced_shapes().into_iter().cloned());
+    }
+    for property_shape in &shape.property_shapes {
+        collect_referenced_shapes(&property_shape.base, referenced);
+    }
+}
+
+fn parse_node_shape(graph: &dyn DataGraph, id: &ShapeId) -> Result<NodeShape, ShaclError> {
     let mut shape = NodeShape::new(id.clone());
     let term = id.to_term();
 
     // Parse targets
-    parse_targets(graph, &term, &mut shape.base);
+    parse_targets(graph, &term, &mut shape.base)?;
 
     // Parse constraints
-    parse_constraints(graph, &term, &mut shape.base)?;
+    parse_constraints(graph, &term, None, &mut shape.base)?;
 
     // Parse property shapes
     parse_property_shapes(graph, &term, &mut shape.base)?;
 
     // Parse metadata
-    parse_metadata(graph, &term, &mut shape.base);
+    parse_metadata(graph, &term, &mut shape.base)?;
 
     Ok(shape)
 }
 
 fn parse_property_shape(
-    graph: &Graph,
+    graph: &dyn DataGraph,
     id: &ShapeId,
-) -> Result<Option<PropertyShape>, ShaclParseError> {
+) -> Result<Option<PropertyShape>, ShaclError> {
     let term = id.to_term();
 
     // Property shapes must have sh:path
-    let path_term = get_object(graph, &term, shacl::PATH);
+    let path_term = get_object(graph, &term, shacl::PATH)?;
     let path = match path_term {
-        Some(p) => PropertyPath::parse(graph, p.as_ref())?,
+        Some(p) => PropertyPath::parse_in(graph, p.as_ref())?,
         None => return Ok(None), // Not a valid property shape without path
     };
 
     let mut shape = PropertyShape::new(id.clone(), path);
 
     // Parse targets
-    parse_targets(graph, &term, &mut shape.base);
+    parse_targets(graph, &term, &mut shape.base)?;
 
     // Parse constraints
-    parse_constraints(graph, &term, &mut shape.base)?;
+    parse_constraints(graph, &term, Some(&shape.path), &mut shape.base)?;
 
     // Parse nested property shapes
     parse_property_shapes(graph, &term, &mut shape.base)?;
 
     // Parse metadata
-    parse_metadata(graph, &term, &mut shape.base);
+    parse_metadata(graph, &term, &mut shape.base)?;
 
     Ok(Some(shape))
 }
 
-fn parse_targets(graph: &Graph, shape_term: &Term, shape: &mut Shape) {
+fn parse_targets(
+    graph: &dyn DataGraph,
+    shape_term: &Term,
+    shape: &mut Shape,
+) -> Result<(), ShaclError> {
     // sh:targetClass
-    for obj in get_objects(graph, shape_term, shacl::TARGET_CLASS) {
+    for obj in get_objects(graph, shape_term, shacl::TARGET_CLASS)? {
         if let Term::NamedNode(class) = obj {
             shape.targets.push(Target::Class(class));
         }
     }
 
     // sh:targetNode
-    for obj in get_objects(graph, shape_term, shacl::TARGET_NODE) {
+    for obj in get_objects(graph, shape_term, shacl::TARGET_NODE)? {
         shape.targets.push(Target::Node(obj));
     }
 
     // sh:targetSubjectsOf
-    for obj in get_objects(graph, shape_term, shacl::TARGET_SUBJECTS_OF) {
+    for obj in get_objects(graph, shape_term, shacl::TARGET_SUBJECTS_OF)? {
         if let Term::NamedNode(pred) = obj {
             shape.targets.push(Target::SubjectsOf(pred));
         }
     }
 
     // sh:targetObjectsOf
-    for obj in get_objects(graph, shape_term, shacl::TARGET_OBJECTS_OF) {
+    for obj in get_objects(graph, shape_term, shacl::TARGET_OBJECTS_OF)? {
         if let Term::NamedNode(pred) = obj {
             shape.targets.push(Target::ObjectsOf(pred));
         }
     }
+    Ok(())
 }
 
+/// Parses the constraints of a shape, `path` being the path of property shapes.
 fn parse_constraints(
-    graph: &Graph,
+    graph: &dyn DataGraph,
     shape_term: &Term,
+    #[cfg_attr(not(feature = "sparql"), expect(unused_variables))] path: Option<&PropertyPath>,
     shape: &mut Shape,
-) -> Result<(), ShaclParseError> {
+) -> Result<(), ShaclError> {
     // Parse all constraint types
 
     // sh:class
-    for obj in get_objects(graph, shape_term, shacl::CLASS) {
+    for obj in get_objects(graph, shape_term, shacl::CLASS)? {
         if let Term::NamedNode(class) = obj {
             shape.constraints.push(Constraint::Class(class));
         }
     }
 
     // sh:datatype
-    if let Some(Term::NamedNode(dt)) = get_object(graph, shape_term, shacl::DATATYPE) {
+    if let Some(Term::NamedNode(dt)) = get_object(graph, shape_term, shacl::DATATYPE)? {
         shape.constraints.push(Constraint::Datatype(dt));
     }
 
     // sh:nodeKind
-    if let Some(Term::NamedNode(nk)) = get_object(graph, shape_term, shacl::NODE_KIND) {
-        shape.constraints.push(Constraint::NodeKind(nk));
+    if let Some(node_kind) = get_object(graph, shape_term, shacl::NODE_KIND)? {
+        match node_kind {
+            Term::NamedNode(nk) if NODE_KINDS.contains(&nk.as_ref()) => {
+                shape.constraints.push(Constraint::NodeKind(nk));
+            }
+            other => {
+                return Err(ShaclParseError::invalid_property_value(
+                    shape_term.clone(),
+                    shacl::NODE_KIND.into_owned(),
+                    "sh:IRI, sh:BlankNode, sh:Literal, sh:BlankNodeOrIRI, sh:BlankNodeOrLiteral or sh:IRIOrLiteral",
+                    other,
+                ).into());
+            }
+        }
     }
 
     // sh:minCount
-    if let Some(n) = get_integer(graph, shape_term, shacl::MIN_COUNT) {
+    if let Some(n) = get_integer(graph, shape_term, shacl::MIN_COUNT)? {
         let count = usize::try_from(n).map_err(|_| {
             ShaclParseError::invalid_property_value(
                 shape_term.clone(),
@@ -510,7 +646,7 @@ fn parse_constraints(
     }
 
     // sh:maxCount
-    if let Some(n) = get_integer(graph, shape_term, shacl::MAX_COUNT) {
+    if let Some(n) = get_integer(graph, shape_term, shacl::MAX_COUNT)? {
         let count = usize::try_from(n).map_err(|_| {
             ShaclParseError::invalid_property_value(
                 shape_term.clone(),
@@ -523,27 +659,27 @@ fn parse_constraints(
     }
 
     // sh:minExclusive
-    if let Some(lit) = get_literal(graph, shape_term, shacl::MIN_EXCLUSIVE) {
+    if let Some(lit) = get_literal(graph, shape_term, shacl::MIN_EXCLUSIVE)? {
         shape.constraints.push(Constraint::MinExclusive(lit));
     }
 
     // sh:maxExclusive
-    if let Some(lit) = get_literal(graph, shape_term, shacl::MAX_EXCLUSIVE) {
+    if let Some(lit) = get_literal(graph, shape_term, shacl::MAX_EXCLUSIVE)? {
         shape.constraints.push(Constraint::MaxExclusive(lit));
     }
 
     // sh:minInclusive
-    if let Some(lit) = get_literal(graph, shape_term, shacl::MIN_INCLUSIVE) {
+    if let Some(lit) = get_literal(graph, shape_term, shacl::MIN_INCLUSIVE)? {
         shape.constraints.push(Constraint::MinInclusive(lit));
     }
 
     // sh:maxInclusive
-    if let Some(lit) = get_literal(graph, shape_term, shacl::MAX_INCLUSIVE) {
+    if let Some(lit) = get_literal(graph, shape_term, shacl::MAX_INCLUSIVE)? {
         shape.constraints.push(Constraint::MaxInclusive(lit));
     }
 
     // sh:minLength
-    if let Some(n) = get_integer(graph, shape_term, shacl::MIN_LENGTH) {
+    if let Some(n) = get_integer(graph, shape_term, shacl::MIN_LENGTH)? {
         let length = usize::try_from(n).map_err(|_| {
             ShaclParseError::invalid_property_value(
                 shape_term.clone(),
@@ -556,7 +692,7 @@ fn parse_constraints(
     }
 
     // sh:maxLength
-    if let Some(n) = get_integer(graph, shape_term, shacl::MAX_LENGTH) {
+    if let Some(n) = get_integer(graph, shape_term, shacl::MAX_LENGTH)? {
         let length = usize::try_from(n).map_err(|_| {
             ShaclParseError::invalid_property_value(
                 shape_term.clone(),
@@ -569,104 +705,105 @@ fn parse_constraints(
     }
 
     // sh:pattern
-    if let Some(pattern) = get_string(graph, shape_term, shacl::PATTERN) {
-        let flags = get_string(graph, shape_term, shacl::FLAGS);
+    if let Some(pattern) = get_string(graph, shape_term, shacl::PATTERN)? {
+        let flags = get_string(graph, shape_term, shacl::FLAGS)?;
         shape
             .constraints
             .push(Constraint::Pattern { pattern, flags });
     }
 
     // sh:languageIn
-    if let Some(list_head) = get_object(graph, shape_term, shacl::LANGUAGE_IN) {
+    if let Some(list_head) = get_object(graph, shape_term, shacl::LANGUAGE_IN)? {
         let languages = parse_string_list(graph, list_head, shape_term)?;
         shape.constraints.push(Constraint::LanguageIn(languages));
     }
 
     // sh:uniqueLang
-    if let Some(b) = get_boolean(graph, shape_term, shacl::UNIQUE_LANG) {
+    if let Some(b) = get_boolean(graph, shape_term, shacl::UNIQUE_LANG)? {
         if b {
             shape.constraints.push(Constraint::UniqueLang);
         }
     }
 
     // sh:equals
-    for obj in get_objects(graph, shape_term, shacl::EQUALS) {
+    for obj in get_objects(graph, shape_term, shacl::EQUALS)? {
         if let Term::NamedNode(prop) = obj {
             shape.constraints.push(Constraint::Equals(prop));
         }
     }
 
     // sh:disjoint
-    for obj in get_objects(graph, shape_term, shacl::DISJOINT) {
+    for obj in get_objects(graph, shape_term, shacl::DISJOINT)? {
         if let Term::NamedNode(prop) = obj {
             shape.constraints.push(Constraint::Disjoint(prop));
         }
     }
 
     // sh:lessThan
-    for obj in get_objects(graph, shape_term, shacl::LESS_THAN) {
+    for obj in get_objects(graph, shape_term, shacl::LESS_THAN)? {
         if let Term::NamedNode(prop) = obj {
             shape.constraints.push(Constraint::LessThan(prop));
         }
     }
 
     // sh:lessThanOrEquals
-    for obj in get_objects(graph, shape_term, shacl::LESS_THAN_OR_EQUALS) {
+    for obj in get_objects(graph, shape_term, shacl::LESS_THAN_OR_EQUALS)? {
         if let Term::NamedNode(prop) = obj {
             shape.constraints.push(Constraint::LessThanOrEquals(prop));
         }
     }
 
     // sh:not
-    for obj in get_objects(graph, shape_term, shacl::NOT) {
+    for obj in get_objects(graph, shape_term, shacl::NOT)? {
         let shape_id = term_to_shape_id(obj)?;
         shape.constraints.push(Constraint::Not(shape_id));
     }
 
     // sh:and
-    if let Some(list_head) = get_object(graph, shape_term, shacl::AND) {
+    if let Some(list_head) = get_object(graph, shape_term, shacl::AND)? {
         let shape_ids = parse_shape_list(graph, list_head, shape_term)?;
         shape.constraints.push(Constraint::And(shape_ids));
     }
 
     // sh:or
-    if let Some(list_head) = get_object(graph, shape_term, shacl::OR) {
+    if let Some(list_head) = get_object(graph, shape_term, shacl::OR)? {
         let shape_ids = parse_shape_list(graph, list_head, shape_term)?;
         shape.constraints.push(Constraint::Or(shape_ids));
     }
 
     // sh:xone
-    if let Some(list_head) = get_object(graph, shape_term, shacl::XONE) {
+    if let Some(list_head) = get_object(graph, shape_term, shacl::XONE)? {
         let shape_ids = parse_shape_list(graph, list_head, shape_term)?;
         shape.constraints.push(Constraint::Xone(shape_ids));
     }
 
     // sh:node
-    for obj in get_objects(graph, shape_term, shacl::NODE) {
+    for obj in get_objects(graph, shape_term, shacl::NODE)? {
         let shape_id = term_to_shape_id(obj)?;
         shape.constraints.push(Constraint::Node(shape_id));
     }
 
     // sh:hasValue
-    for obj in get_objects(graph, shape_term, shacl::HAS_VALUE) {
+    for obj in get_objects(graph, shape_term, shacl::HAS_VALUE)? {
         shape.constraints.push(Constraint::HasValue(obj));
     }
 
     // sh:in
-    if let Some(list_head) = get_object(graph, shape_term, shacl::IN) {
+    if let Some(list_head) = get_object(graph, shape_term, shacl::IN)? {
         let values = parse_term_list(graph, list_head, shape_term)?;
         shape.constraints.push(Constraint::In(values));
     }
 
     // sh:closed
-    if let Some(b) = get_boolean(graph, shape_term, shacl::CLOSED) {
+    if let Some(b) = get_boolean(graph, shape_term, shacl::CLOSED)? {
         if b {
-            let ignored =
-                if let Some(list_head) = get_object(graph, shape_term, shacl::IGNORED_PROPERTIES) {
-                    parse_named_node_list(graph, list_head, shape_term)?
-                } else {
-                    Vec::new()
-                };
+            let ignored = if let Some(list_head) =
+                get_object(graph, shape_term, shacl::IGNORED_PROPERTIES)?
+            {
+                parse_named_node_list(graph, list_head, shape_term)?
+            } else {
+                Vec::new()
+            };
             shape.constraints.push(Constraint::Closed {
                 ignored_properties: ignored,
             });
@@ -674,14 +811,14 @@ fn parse_constraints(
     }
 
     // sh:qualifiedValueShape
-    if let Some(qvs) = get_object(graph, shape_term, shacl::QUALIFIED_VALUE_SHAPE) {
+    if let Some(qvs) = get_object(graph, shape_term, shacl::QUALIFIED_VALUE_SHAPE)? {
         let shape_id = term_to_shape_id(qvs)?;
-        let min = get_integer(graph, shape_term, shacl::QUALIFIED_MIN_COUNT)
+        let min = get_integer(graph, shape_term, shacl::QUALIFIED_MIN_COUNT)?
             .and_then(|n| usize::try_from(n).ok());
-        let max = get_integer(graph, shape_term, shacl::QUALIFIED_MAX_COUNT)
+        let max = get_integer(graph, shape_term, shacl::QUALIFIED_MAX_COUNT)?
             .and_then(|n| usize::try_from(n).ok());
-        let disjoint =
-            get_boolean(graph, shape_term, shacl::QUALIFIED_VALUE_SHAPES_DISJOINT).unwrap_or(false);
+        let disjoint = get_boolean(graph, shape_term, shacl::QUALIFIED_VALUE_SHAPES_DISJOINT)?
+            .unwrap_or(false);
         shape.constraints.push(Constraint::QualifiedValueShape {
             shape: shape_id,
             min_count: min,
@@ -690,15 +827,65 @@ fn parse_constraints(
         });
     }
 
+    // sh:sparql
+    #[cfg(feature = "sparql")]
+    for obj in get_objects(graph, shape_term, shacl::SPARQL)? {
+        if get_boolean(graph, &obj, shacl::DEACTIVATED)? != Some(true) {
+            let constraint = parse_sparql_constraint(graph, &obj, shape_term, path)?;
+            shape
+                .constraints
+                .push(Constraint::Sparql(Box::new(constraint)));
+        }
+    }
+
     Ok(())
 }
 
+/// Parses a `sh:sparql` constraint, using the prefixes declared with `sh:prefixes`.
+///
+/// `path` is the path of the property shape replacing `$PATH`.
+#[cfg(feature = "sparql")]
+fn parse_sparql_constraint(
+    graph: &dyn DataGraph,
+    constraint: &Term,
+    shape_term: &Term,
+    path: Option<&PropertyPath>,
+) -> Result<SparqlConstraint, ShaclError> {
+    let Some(select) = get_string(graph, constraint, shacl::SELECT)? else {
+        return Err(ShaclParseError::missing_property(constraint.clone(), shacl::SELECT).into());
+    };
+
+    let mut parser = SparqlParser::new();
+    for prefixes in get_objects(graph, constraint, shacl::PREFIXES)? {
+        for declaration in get_objects(graph, &prefixes, shacl::DECLARE)? {
+            if let (Some(prefix), Some(namespace)) = (
+                get_string(graph, &declaration, shacl::PREFIX)?,
+                get_string(graph, &declaration, shacl::NAMESPACE_PROP)?,
+            ) {
+                parser = parser.with_prefix(prefix, namespace).map_err(|e| {
+                    ShaclParseError::invalid_shape(
+                        shape_term.clone(),
+                        format!("Invalid prefix declaration: {e}"),
+                    )
+                })?;
+            }
+        }
+    }
+
+    let select = parser.parse_query(&select).map_err(|e| {
+        ShaclParseError::invalid_shape(shape_term.clone(), format!("Invalid sh:select query: {e}"))
+    })?;
+    let messages = get_literals(graph, constraint, shacl::MESSAGE)?;
+    SparqlConstraint::new(select, messages, path)
+        .map_err(|e| ShaclParseError::invalid_shape(shape_term.clone(), e).into())
+}
+
 fn parse_property_shapes(
-    graph: &Graph,
+    graph: &dyn DataGraph,
     shape_term: &Term,
     shape: &mut Shape,
-) -> Result<(), ShaclParseError> {
-    for obj in get_objects(graph, shape_term, shacl::PROPERTY) {
+) -> Result<(), ShaclError> {
+    for obj in get_objects(graph, shape_term, shacl::PROPERTY)? {
         let prop_id = term_to_shape_id(obj)?;
         if let Some(prop_shape) = parse_property_shape(graph, &prop_id)? {
             shape.property_shapes.push(Arc::new(prop_shape));
@@ -707,14 +894,18 @@ fn parse_property_shapes(
     Ok(())
 }
 
-fn parse_metadata(graph: &Graph, shape_term: &Term, shape: &mut Shape) {
+fn parse_metadata(
+    graph: &dyn DataGraph,
+    shape_term: &Term,
+    shape: &mut Shape,
+) -> Result<(), ShaclError> {
     // sh:deactivated
-    if let Some(b) = get_boolean(graph, shape_term, shacl::DEACTIVATED) {
+    if let Some(b) = get_boolean(graph, shape_term, shacl::DEACTIVATED)? {
         shape.deactivated = b;
     }
 
     // sh:severity
-    if let Some(Term::NamedNode(sev)) = get_object(graph, shape_term, shacl::SEVERITY) {
+    if let Some(Term::NamedNode(sev)) = get_object(graph, shape_term, shacl::SEVERITY)? {
         shape.severity = match sev.as_ref() {
             s if s == shacl::VIOLATION => Severity::Violation,
             s if s == shacl::WARNING => Severity::Warning,
@@ -724,69 +915,79 @@ fn parse_metadata(graph: &Graph, shape_term: &Term, shape: &mut Shape) {
     }
 
     // sh:name
-    shape.name = get_string(graph, shape_term, shacl::NAME);
+    shape.name = get_string(graph, shape_term, shacl::NAME)?;
 
     // sh:description
-    shape.description = get_string(graph, shape_term, shacl::DESCRIPTION);
+    shape.description = get_string(graph, shape_term, shacl::DESCRIPTION)?;
 
     // sh:message
-    shape.message = get_string(graph, shape_term, shacl::MESSAGE);
+    shape.messages = get_literals(graph, shape_term, shacl::MESSAGE)?;
+    Ok(())
 }
 
 // Helper functions
 
-fn get_object(graph: &Graph, subject: &Term, predicate: NamedNodeRef<'_>) -> Option<Term> {
-    match subject {
-        Term::NamedNode(n) => graph
-            .object_for_subject_predicate(n, predicate)
-            .map(TermRef::into_owned),
-        Term::BlankNode(b) => graph
-            .object_for_subject_predicate(b, predicate)
-            .map(TermRef::into_owned),
-        Term::Literal(_) => None,
-        #[cfg(feature = "rdf-12")]
-        Term::Triple(_) => None,
-    }
+fn get_object(
+    graph: &dyn DataGraph,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Result<Option<Term>, ShaclError> {
+    let Some(subject) = as_subject(subject.as_ref()) else {
+        return Ok(None);
+    };
+    graph
+        .objects_for_subject_predicate(subject, predicate)
+        .next()
+        .transpose()
 }
 
-fn get_objects(graph: &Graph, subject: &Term, predicate: NamedNodeRef<'_>) -> Vec<Term> {
-    match subject {
-        Term::NamedNode(n) => graph
-            .objects_for_subject_predicate(n, predicate)
-            .map(TermRef::into_owned)
-            .collect(),
-        Term::BlankNode(b) => graph
-            .objects_for_subject_predicate(b, predicate)
-            .map(TermRef::into_owned)
-            .collect(),
-        Term::Literal(_) => Vec::new(),
-        #[cfg(feature = "rdf-12")]
-        Term::Triple(_) => Vec::new(),
-    }
+fn get_objects(
+    graph: &dyn DataGraph,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Result<Vec<Term>, ShaclError> {
+    let Some(subject) = as_subject(subject.as_ref()) else {
+        return Ok(Vec::new());
+    };
+    graph
+        .objects_for_subject_predicate(subject, predicate)
+        .collect()
 }
 
-fn get_string(graph: &Graph, subject: &Term, predicate: NamedNodeRef<'_>) -> Option<String> {
-    get_object(graph, subject, predicate).and_then(|t| {
+fn get_string(
+    graph: &dyn DataGraph,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Result<Option<String>, ShaclError> {
+    Ok(get_object(graph, subject, predicate)?.and_then(|t| {
         if let Term::Literal(lit) = t {
             Some(lit.value().to_owned())
         } else {
             None
         }
-    })
+    }))
 }
 
-fn get_integer(graph: &Graph, subject: &Term, predicate: NamedNodeRef<'_>) -> Option<i64> {
-    get_object(graph, subject, predicate).and_then(|t| {
+fn get_integer(
+    graph: &dyn DataGraph,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Result<Option<i64>, ShaclError> {
+    Ok(get_object(graph, subject, predicate)?.and_then(|t| {
         if let Term::Literal(lit) = t {
             lit.value().parse().ok()
         } else {
             None
         }
-    })
+    }))
 }
 
-fn get_boolean(graph: &Graph, subject: &Term, predicate: NamedNodeRef<'_>) -> Option<bool> {
-    get_object(graph, subject, predicate).and_then(|t| {
+fn get_boolean(
+    graph: &dyn DataGraph,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Result<Option<bool>, ShaclError> {
+    Ok(get_object(graph, subject, predicate)?.and_then(|t| {
         if let Term::Literal(lit) = t {
             match lit.value() {
                 "true" | "1" => Some(true),
@@ -796,17 +997,38 @@ fn get_boolean(graph: &Graph, subject: &Term, predicate: NamedNodeRef<'_>) -> Op
         } else {
             None
         }
-    })
+    }))
 }
 
-fn get_literal(graph: &Graph, subject: &Term, predicate: NamedNodeRef<'_>) -> Option<Literal> {
-    get_object(graph, subject, predicate).and_then(|t| {
+fn get_literal(
+    graph: &dyn DataGraph,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Result<Option<Literal>, ShaclError> {
+    Ok(get_object(graph, subject, predicate)?.and_then(|t| {
         if let Term::Literal(lit) = t {
             Some(lit)
         } else {
             None
         }
-    })
+    }))
+}
+
+fn get_literals(
+    graph: &dyn DataGraph,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Result<Vec<Literal>, ShaclError> {
+    Ok(get_objects(graph, subject, predicate)?
+        .into_iter()
+        .filter_map(|t| {
+            if let Term::Literal(lit) = t {
+                Some(lit)
+            } else {
+                None
+            }
+        })
+        .collect())
 }
 
 fn term_to_shape_id(term: Term) -> Result<ShapeId, ShaclParseError> {
@@ -828,11 +1050,21 @@ fn term_to_shape_id(term: Term) -> Result<ShapeId, ShaclParseError> {
 /// Maximum allowed length for RDF lists to prevent DoS attacks.
 const MAX_LIST_LENGTH: usize = 10000;
 
+/// The allowed values of `sh:nodeKind`.
+const NODE_KINDS: [NamedNodeRef<'static>; 6] = [
+    shacl::IRI,
+    shacl::BLANK_NODE,
+    shacl::LITERAL,
+    shacl::BLANK_NODE_OR_IRI,
+    shacl::BLANK_NODE_OR_LITERAL,
+    shacl::IRI_OR_LITERAL,
+];
+
 fn parse_string_list(
-    graph: &Graph,
+    graph: &dyn DataGraph,
     list_head: Term,
     shape: &Term,
-) -> Result<Vec<String>, ShaclParseError> {
+) -> Result<Vec<String>, ShaclError> {
     use oxrdf::vocab::rdf;
     let mut strings = Vec::new();
     let mut current = list_head;
@@ -848,22 +1080,22 @@ fn parse_string_list(
 
         // Check for list length limit
         if strings.len() >= MAX_LIST_LENGTH {
-            return Err(ShaclParseError::list_too_long(MAX_LIST_LENGTH));
+            return Err(ShaclParseError::list_too_long(MAX_LIST_LENGTH).into());
         }
 
         // Check for circular reference
         if !visited.insert(current.clone()) {
-            return Err(ShaclParseError::circular_list(current));
+            return Err(ShaclParseError::circular_list(current).into());
         }
 
-        let first = get_object(graph, &current, rdf::FIRST)
+        let first = get_object(graph, &current, rdf::FIRST)?
             .ok_or_else(|| ShaclParseError::invalid_rdf_list(shape.clone(), "Missing rdf:first"))?;
 
         if let Term::Literal(lit) = first {
             strings.push(lit.value().to_owned());
         }
 
-        let rest = get_object(graph, &current, rdf::REST)
+        let rest = get_object(graph, &current, rdf::REST)?
             .ok_or_else(|| ShaclParseError::invalid_rdf_list(shape.clone(), "Missing rdf:rest"))?;
 
         current = rest;
@@ -873,10 +1105,10 @@ fn parse_string_list(
 }
 
 fn parse_term_list(
-    graph: &Graph,
+    graph: &dyn DataGraph,
     list_head: Term,
     shape: &Term,
-) -> Result<Vec<Term>, ShaclParseError> {
+) -> Result<Vec<Term>, ShaclError> {
     use oxrdf::vocab::rdf;
     let mut terms = Vec::new();
     let mut current = list_head;
@@ -892,20 +1124,20 @@ fn parse_term_list(
 
         // Check for list length limit
         if terms.len() >= MAX_LIST_LENGTH {
-            return Err(ShaclParseError::list_too_long(MAX_LIST_LENGTH));
+            return Err(ShaclParseError::list_too_long(MAX_LIST_LENGTH).into());
         }
 
         // Check for circular reference
         if !visited.insert(current.clone()) {
-            return Err(ShaclParseError::circular_list(current));
+            return Err(ShaclParseError::circular_list(current).into());
         }
 
-        let first = get_object(graph, &current, rdf::FIRST)
+        let first = get_object(graph, &current, rdf::FIRST)?
             .ok_or_else(|| ShaclParseError::invalid_rdf_list(shape.clone(), "Missing rdf:first"))?;
 
         terms.push(first);
 
-        let rest = get_object(graph, &current, rdf::REST)
+        let rest = get_object(graph, &current, rdf::REST)?
             .ok_or_else(|| ShaclParseError::invalid_rdf_list(shape.clone(), "Missing rdf:rest"))?;
 
         current = rest;
@@ -915,10 +1147,10 @@ fn parse_term_list(
 }
 
 fn parse_named_node_list(
-    graph: &Graph,
+    graph: &dyn DataGraph,
     list_head: Term,
     shape: &Term,
-) -> Result<Vec<NamedNode>, ShaclParseError> {
+) -> Result<Vec<NamedNode>, ShaclError> {
     let terms = parse_term_list(graph, list_head, shape)?;
     let mut nodes = Vec::new();
     for term in terms {
@@ -930,12 +1162,15 @@ fn parse_named_node_list(
 }
 
 fn parse_shape_list(
-    graph: &Graph,
+    graph: &dyn DataGraph,
     list_head: Term,
     shape: &Term,
-) -> Result<Vec<ShapeId>, ShaclParseError> {
+) -> Result<Vec<ShapeId>, ShaclError> {
     let terms = parse_term_list(graph, list_head, shape)?;
-    terms.into_iter().map(term_to_shape_id).collect()
+    Ok(terms
+        .into_iter()
+        .map(term_to_shape_id)
+        .collect::<Result<_, _>>()?)
 }
 
 #[cfg(test)]
@@ -9,11 +9,16 @@
 //! - One-or-more path (sh:oneOrMorePath)
 //! - Zero-or-one path (sh:zeroOrOnePath)
 
-use oxrdf::{Graph, NamedNode, NamedNodeRef, Term, TermRef, vocab::shacl};
+use oxrdf::{
+    BlankNode, Graph, NamedNode, NamedNodeRef, Term, TermRef, Triple,
+    vocab::{rdf, shacl},
+};
 use rustc_hash::FxHashSet;
+use std::collections::VecDeque;
 use std::fmt;
 
-use crate::error::ShaclParseError;
+use crate::data::{DataGraph, as_subject};
+use crate::error::{ShaclError, ShaclParseError};
 
 /// Represents a SHACL property path.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -78,6 +83,15 @@ impl PropertyPath {
 
     /// Parses a property path from a term in an RDF graph.
     pub fn parse(graph: &Graph, term: TermRef<'_>) -> Result<Self, ShaclParseError> {
+        match Self::parse_in(graph, term) {
+            Ok(path) => Ok(path),
+            Err(ShaclError::Parse(e)) => Err(e),
+            Err(e) => unreachable!("Reading an in-memory graph never fails: {e}"),
+        }
+    }
+
+    /// Parses a property path from a term in any data graph.
+    pub(crate) fn parse_in(graph: &dyn DataGraph, term: TermRef<'_>) -> Result<Self, ShaclError> {
         match term {
             // Simple predicate path (IRI)
             TermRef::NamedNode(node) => Ok(Self::Predicate(node.into_owned())),
@@ -87,37 +101,37 @@ impl PropertyPath {
                 let bnode_term: Term = bnode.into_owned().into();
 
                 // Check for alternative path
-                if let Some(list_head) = get_object(graph, &bnode_term, shacl::ALTERNATIVE_PATH) {
+                if let Some(list_head) = get_object(graph, &bnode_term, shacl::ALTERNATIVE_PATH)? {
                     let paths = parse_path_list(graph, list_head, &bnode_term)?;
                     return Ok(Self::Alternative(paths));
                 }
 
                 // Check for inverse path
-                if let Some(inner) = get_object(graph, &bnode_term, shacl::INVERSE_PATH) {
-                    let inner_path = Self::parse(graph, inner.as_ref())?;
+                if let Some(inner) = get_object(graph, &bnode_term, shacl::INVERSE_PATH)? {
+                    let inner_path = Self::parse_in(graph, inner.as_ref())?;
                     return Ok(Self::Inverse(Box::new(inner_path)));
                 }
 
                 // Check for zero-or-more path
-                if let Some(inner) = get_object(graph, &bnode_term, shacl::ZERO_OR_MORE_PATH) {
-                    let inner_path = Self::parse(graph, inner.as_ref())?;
+                if let Some(inner) = get_object(graph, &bnode_term, shacl::ZERO_OR_MORE_PATH)? {
+                    let inner_path = Self::parse_in(graph, inner.as_ref())?;
                     return Ok(Self::ZeroOrMore(Box::new(inner_path)));
                 }
 
                 // Check for one-or-more path
-                if let Some(inner) = get_object(graph, &bnode_term, shacl::ONE_OR_MORE_PATH) {
-                    let inner_path = Self::parse(graph, inner.as_ref())?;
+                if let Some(inner) = get_object(graph, &bnode_term, shacl::ONE_OR_MORE_PATH)? {
+                    let inner_path = Self::parse_in(graph, inner.as_ref())?;
                     return Ok(Self::OneOrMore(Box::new(inner_path)));
                 }
 
                 // Check for zero-or-one path
-                if let Some(inner) = get_object(graph, &bnode_term, shacl::ZERO_OR_ONE_PATH) {
-                    let inner_path = Self::parse(graph, inner.as_ref())?;
+                if let Some(inner) = get_object(graph, &bnode_term, shacl::ZERO_OR_ONE_PATH)? {
+                    let inner_path = Self::parse_in(graph, inner.as_ref())?;
                     return Ok(Self::ZeroOrOne(Box::new(inner_path)));
                 }
 
                 // Check for sequence path (RDF list starting from this blank node)
-                if is_rdf_list_head(graph, &bnode_term) {
+                if is_rdf_list_head(graph, &bnode_term)? {
                     let paths = parse_path_list(graph, bnode_term.clone(), &bnode_term)?;
                     if paths.len() >= 2 {
                         return Ok(Self::Sequence(paths));
@@ -127,181 +141,255 @@ impl PropertyPath {
                 Err(ShaclParseError::invalid_property_path(
                     bnode_term,
                     "Unknown property path structure",
-                ))
+                )
+                .into())
             }
 
             TermRef::Literal(_) => Err(ShaclParseError::invalid_property_path(
                 term.into_owned(),
                 "Property path must be an IRI or blank node",
-            )),
+            )
+            .into()),
             #[cfg(feature = "rdf-12")]
             TermRef::Triple(_) => Err(ShaclParseError::invalid_property_path(
                 term.into_owned(),
                 "Property path must be an IRI or blank node",
-            )),
+            )
+            .into()),
+        }
+    }
+
+    /// Adds the RDF representation of the path to a graph and returns the term identifying it.
+    ///
+    /// It is the inverse of [`parse`](Self::parse).
+    pub fn add_to_graph(&self, graph: &mut Graph) -> Term {
+        match self {
+            Self::Predicate(p) => p.clone().into(),
+            Self::Sequence(paths) => {
+                let items = paths.iter().map(|p| p.add_to_graph(graph)).collect();
+                add_list_to_graph(graph, items)
+            }
+            Self::Alternative(paths) => {
+                let items = paths.iter().map(|p| p.add_to_graph(graph)).collect();
+                let list = add_list_to_graph(graph, items);
+                add_path_node_to_graph(graph, shacl::ALTERNATIVE_PATH, list)
+            }
+            Self::Inverse(p) => {
+                let inner = p.add_to_graph(graph);
+                add_path_node_to_graph(graph, shacl::INVERSE_PATH, inner)
+            }
+            Self::ZeroOrMore(p) => {
+                let inner = p.add_to_graph(graph);
+                add_path_node_to_graph(graph, shacl::ZERO_OR_MORE_PATH, inner)
+            }
+            Self::OneOrMore(p) => {
+                let inner = p.add_to_graph(graph);
+                add_path_node_to_graph(graph, shacl::ONE_OR_MORE_PATH, inner)
+            }
+            Self::ZeroOrOne(p) => {
+                let inner = p.add_to_graph(graph);
+                add_path_node_to_graph(graph, shacl::ZERO_OR_ONE_PATH, inner)
+            }
         }
     }
 
     /// Evaluates the property path starting from a focus node and returns all value nodes.
     pub fn evaluate<'a>(&self, graph: &'a Graph, focus_node: TermRef<'a>) -> Vec<Term> {
+        // Reading an in-memory graph never fails
+        CompiledPath::new(self)
+            .evaluate(graph, focus_node, &|| Ok(()))
+            .unwrap_or_default()
+    }
+
+    /// Returns true if this is a simple predicate path.
+    pub fn is_predicate(&self) -> bool {
+        matches!(self, Self::Predicate(_))
+    }
+
+    /// Returns the predicate if this is a simple predicate path.
+    pub fn as_predicate(&self) -> Option<&NamedNode> {
+        match self {
+            Self::Predicate(p) => Some(p),
+            _ => None,
+        }
+    }
+}
+
+/// Adds a blank node with the given path operator to a graph.
+fn add_path_node_to_graph(graph: &mut Graph, operator: NamedNodeRef<'_>, inner: Term) -> Term {
+    let node = BlankNode::default();
+    graph.insert(&Triple::new(node.clone(), operator, inner));
+    node.into()
+}
+
+/// Adds an RDF list to a graph and returns its head.
+fn add_list_to_graph(graph: &mut Graph, items: Vec<Term>) -> Term {
+    let mut head = Term::from(rdf::NIL.into_owned());
+    for item in items.into_iter().rev() {
+        let node = BlankNode::default();
+        graph.insert(&Triple::new(node.clone(), rdf::FIRST, item));
+        graph.insert(&Triple::new(node.clone(), rdf::REST, head));
+        head = node.into();
+    }
+    head
+}
+
+/// A [`PropertyPath`] prepared for evaluation from many focus nodes.
+///
+/// Inverse paths are pushed down to the predicates they contain
+/// (e.g. `^(p/q)*` becomes `(^q/^p)*`), so that every step of the path only
+/// looks at the triples around the current nodes instead of scanning the whole graph.
+#[derive(Debug, Clone)]
+pub(crate) enum CompiledPath {
+    Predicate(NamedNode),
+    InversePredicate(NamedNode),
+    Sequence(Vec<CompiledPath>),
+    Alternative(Vec<CompiledPath>),
+    ZeroOrMore(Box<CompiledPath>),
+    OneOrMore(Box<CompiledPath>),
+    ZeroOrOne(Box<CompiledPath>),
+}
+
+impl CompiledPath {
+    /// Compiles a property path.
+    pub(crate) fn new(path: &PropertyPath) -> Self {
+        Self::compile(path, false)
+    }
+
+    fn compile(path: &PropertyPath, inverse: bool) -> Self {
+        match path {
+            PropertyPath::Predicate(predicate) => {
+                if inverse {
+                    Self::InversePredicate(predicate.clone())
+                } else {
+                    Self::Predicate(predicate.clone())
+                }
+            }
+            PropertyPath::Sequence(paths) => {
+                let mut paths = paths
+                    .iter()
+                    .map(|path| Self::compile(path, inverse))
+                    .collect::<Vec<_>>();
+                if inverse {
+                    paths.reverse();
+                }
+                Self::Sequence(paths)
+            }
+            PropertyPath::Alternative(paths) => Self::Alternative(
+                paths
+                    .iter()
+                    .map(|path| Self::compile(path, inverse))
+                    .collect(),
+            ),
+            PropertyPath::Inverse(inner) => Self::compile(inner, !inverse),
+            PropertyPath::ZeroOrMore(inner) => {
+                Self::ZeroOrMore(Box::new(Self::compile(inner, inverse)))
+            }
+            PropertyPath::OneOrMore(inner) => {
+                Self::OneOrMore(Box::new(Self::compile(inner, inverse)))
+            }
+            PropertyPath::ZeroOrOne(inner) => {
+                Self::ZeroOrOne(Box::new(Self::compile(inner, inverse)))
+            }
+        }
+    }
+
+    /// Evaluates the path starting from a focus node and returns the distinct value nodes.
+    ///
+    /// `check_deadline` is called for each node visited by a `*` or `+` path
+    /// so that long traversals can be interrupted.
+    pub(crate) fn evaluate(
+        &self,
+        graph: &dyn DataGraph,
+        focus_node: TermRef<'_>,
+        check_deadline: &dyn Fn() -> Result<(), ShaclError>,
+    ) -> Result<Vec<Term>, ShaclError> {
         let mut results = Vec::new();
-        self.evaluate_into(
-            graph,
-            focus_node,
-            &mut results,
-            &mut FxHashSet::default(),
-            0,
-        );
-        results
+        self.evaluate_into(graph, focus_node, &mut results, check_deadline)?;
+        let mut seen = FxHashSet::default();
+        results.retain(|node| seen.insert(node.clone()));
+        Ok(results)
     }
 
-    fn evaluate_into<'a>(
+    fn evaluate_into(
         &self,
-        graph: &'a Graph,
-        focus_node: TermRef<'a>,
+        graph: &dyn DataGraph,
+        focus_node: TermRef<'_>,
         results: &mut Vec<Term>,
-        visited: &mut FxHashSet<Term>,
-        depth: usize,
-    ) {
-        // Prevent infinite recursion
-        const MAX_DEPTH: usize = 100;
-        if depth > MAX_DEPTH {
-            return;
-        }
-
+        check_deadline: &dyn Fn() -> Result<(), ShaclError>,
+    ) -> Result<(), ShaclError> {
         match self {
             Self::Predicate(predicate) => {
-                // Get all objects where focus_node is the subject
-                if let TermRef::NamedNode(subj) = focus_node {
-                    for obj in graph.objects_for_subject_predicate(subj, predicate) {
-                        results.push(obj.into_owned());
-                    }
-                } else if let TermRef::BlankNode(subj) = focus_node {
-                    for obj in graph.objects_for_subject_predicate(subj, predicate) {
-                        results.push(obj.into_owned());
+                if let Some(subject) = as_subject(focus_node) {
+                    for object in graph.objects_for_subject_predicate(subject, predicate.as_ref()) {
+                        results.push(object?);
                     }
                 }
             }
-
-            Self::Sequence(paths) => {
-                if paths.is_empty() {
-                    results.push(focus_node.into_owned());
-                    return;
+            Self::InversePredicate(predicate) => {
+                for subject in graph.subjects_for_predicate_object(predicate.as_ref(), focus_node) {
+                    results.push(subject?.into());
                 }
-
+            }
+            Self::Sequence(paths) => {
                 let mut current_nodes = vec![focus_node.into_owned()];
-
                 for path in paths {
                     let mut next_nodes = Vec::new();
                     for node in &current_nodes {
-                        path.evaluate_into(
-                            graph,
-                            node.as_ref(),
-                            &mut next_nodes,
-                            visited,
-                            depth + 1,
-                        );
+                        path.evaluate_into(graph, node.as_ref(), &mut next_nodes, check_deadline)?;
                     }
+                    // Deduplicate to avoid a blowup with alternatives
+                    let mut seen = FxHashSet::default();
+                    next_nodes.retain(|node| seen.insert(node.clone()));
                     current_nodes = next_nodes;
                 }
-
                 results.extend(current_nodes);
             }
-
             Self::Alternative(paths) => {
                 for path in paths {
-                    path.evaluate_into(graph, focus_node, results, visited, depth + 1);
+                    path.evaluate_into(graph, focus_node, results, check_deadline)?;
                 }
             }
-
-            Self::Inverse(inner) => {
-                // For inverse, we need to find subjects where focus_node is the object
-                if let Self::Predicate(predicate) = inner.as_ref() {
-                    for subj in graph.subjects_for_predicate_object(predicate, focus_node) {
-                        results.push(subj.into_owned().into());
-                    }
-                } else {
-                    // For complex inverse paths, we need to iterate all triples
-                    // This is less efficient but correct
-                    for triple in graph {
-                        let mut temp_results = Vec::new();
-                        inner.evaluate_into(
-                            graph,
-                            triple.subject.into(),
-                            &mut temp_results,
-                            visited,
-                            depth + 1,
-                        );
-                        if temp_results.iter().any(|r| r.as_ref() == focus_node) {
-                            results.push(triple.subject.into_owned().into());
-                        }
-                    }
-                }
-            }
-
             Self::ZeroOrMore(inner) => {
-                // Include the focus node itself (zero repetitions)
-                let focus_owned = focus_node.into_owned();
-                if visited.insert(focus_owned.clone()) {
-                    results.push(focus_owned.clone());
-
-                    // Recursively follow the path
-                    let mut temp_results = Vec::new();
-                    inner.evaluate_into(graph, focus_node, &mut temp_results, visited, depth + 1);
-
-                    for node in temp_results {
-                        if visited.insert(node.clone()) {
-                            results.push(node.clone());
-                            self.evaluate_into(graph, node.as_ref(), results, visited, depth + 1);
-                        }
-                    }
-                }
+                results.push(focus_node.into_owned());
+                inner.evaluate_closure(graph, focus_node, results, check_deadline)?;
             }
-
             Self::OneOrMore(inner) => {
-                // Don't include the focus node itself (one or more repetitions required)
-                let _focus_owned = focus_node.into_owned();
-
-                let mut temp_results = Vec::new();
-                inner.evaluate_into(graph, focus_node, &mut temp_results, visited, depth + 1);
-
-                for node in temp_results {
-                    if visited.insert(node.clone()) {
-                        results.push(node.clone());
-                        // Continue with zero-or-more from here
-                        let zero_or_more = Self::ZeroOrMore(inner.clone());
-                        zero_or_more.evaluate_into(
-                            graph,
-                            node.as_ref(),
-                            results,
-                            visited,
-                            depth + 1,
-                        );
-                    }
-                }
+                inner.evaluate_closure(graph, focus_node, results, check_deadline)?
             }
-
             Self::ZeroOrOne(inner) => {
-                // Include the focus node itself
                 results.push(focus_node.into_owned());
-
-                // Also include one step
-                inner.evaluate_into(graph, focus_node, results, visited, depth + 1);
+                inner.evaluate_into(graph, focus_node, results, check_deadline)?;
             }
         }
+        Ok(())
     }
 
-    /// Returns true if this is a simple predicate path.
-    pub fn is_predicate(&self) -> bool {
-        matches!(self, Self::Predicate(_))
-    }
-
-    /// Returns the predicate if this is a simple predicate path.
-    pub fn as_predicate(&self) -> Option<&NamedNode> {
-        match self {
-            Self::Predicate(p) => Some(p),
-            _ => None,
+    /// Adds the nodes reachable from the focus node with one or more repetitions of this path.
+    ///
+    /// The graph is traversed breadth-first and each node is expanded at most once,
+    /// so the traversal terminates on cyclic data.
+    fn evaluate_closure(
+        &self,
+        graph: &dyn DataGraph,
+        focus_node: TermRef<'_>,
+        results: &mut Vec<Term>,
+        check_deadline: &dyn Fn() -> Result<(), ShaclError>,
+    ) -> Result<(), ShaclError> {
+        let mut visited = FxHashSet::default();
+        let mut to_visit = VecDeque::from([focus_node.into_owned()]);
+        while let Some(node) = to_visit.pop_front() {
+            check_deadline()?;
+            let mut next_nodes = Vec::new();
+            self.evaluate_into(graph, node.as_ref(), &mut next_nodes, check_deadline)?;
+            for next_node in next_nodes {
+                if visited.insert(next_node.clone()) {
+                    results.push(next_node.clone());
+                    to_visit.push_back(next_node);
+                }
+            }
         }
+        Ok(())
     }
 }
 
@@ -339,33 +427,33 @@ impl fmt::Display for PropertyPath {
 
 // Helper functions
 
-fn get_object(graph: &Graph, subject: &Term, predicate: NamedNodeRef<'_>) -> Option<Term> {
-    match subject {
-        Term::NamedNode(n) => graph
-            .object_for_subject_predicate(n, predicate)
-            .map(TermRef::into_owned),
-        Term::BlankNode(b) => graph
-            .object_for_subject_predicate(b, predicate)
-            .map(TermRef::into_owned),
-        Term::Literal(_) => None,
-        #[cfg(feature = "rdf-12")]
-        Term::Triple(_) => None,
-    }
+fn get_object(
+    graph: &dyn DataGraph,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Result<Option<Term>, ShaclError> {
+    let Some(subject) = as_subject(subject.as_ref()) else {
+        return Ok(None);
+    };
+    graph
+        .objects_for_subject_predicate(subject, predicate)
+        .next()
+        .transpose()
 }
 
-fn is_rdf_list_head(graph: &Graph, term: &Term) -> bool {
+fn is_rdf_list_head(graph: &dyn DataGraph, term: &Term) -> Result<bool, ShaclError> {
     use oxrdf::vocab::rdf;
-    get_object(graph, term, rdf::FIRST).is_some()
+    Ok(get_object(graph, term, rdf::FIRST)?.is_some())
 }
 
 /// Maximum length for RDF lists to prevent DoS attacks.
 const MAX_LIST_LENGTH: usize = 10000;
 
 fn parse_path_list(
-    graph: &Graph,
+    graph: &dyn DataGraph,
     list_head: Term,
     shape: &Term,
-) -> Result<Vec<PropertyPath>, ShaclParseError> {
+) -> Result<Vec<PropertyPath>, ShaclError> {
     use oxrdf::vocab::rdf;
 
     let mut paths = Vec::new();
@@ -382,24 +470,24 @@ fn parse_path_list(
 
         // Check for cycles
         if !visited.insert(current.clone()) {
-            return Err(ShaclParseError::circular_list(current));
+            return Err(ShaclParseError::circular_list(current).into());
         }
 
         // Check list length to prevent DoS
         if paths.len() >= MAX_LIST_LENGTH {
-            return Err(ShaclParseError::list_too_long(MAX_LIST_LENGTH));
+            return Err(ShaclParseError::list_too_long(MAX_LIST_LENGTH).into());
         }
 
         // Get first element
-        let first = get_object(graph, &current, rdf::FIRST)
+        let first = get_object(graph, &current, rdf::FIRST)?
             .ok_or_else(|| ShaclParseError::invalid_rdf_list(shape.clone(), "Missing rdf:first"))?;
 
         // Parse the path element
-        let path = PropertyPath::parse(graph, first.as_ref())?;
+        let path = PropertyPath::parse_in(graph, first.as_ref())?;
         paths.push(path);
 
         // Get rest of list
-        let rest = get_object(graph, &current, rdf::REST)
+        let rest = get_object(graph, &current, rdf::REST)?
             .ok_or_else(|| ShaclParseError::invalid_rdf_list(shape.clone(), "Missing rdf:rest"))?;
 
         current = rest;
@@ -445,6 +533,177 @@ mod tests {
         assert_eq!(results[0], Term::NamedNode(s));
     }
 
+    #[test]
+    fn test_inverse_sequence_path() {
+        let mut graph = Graph::new();
+        let start = NamedNode::new("http://example.org/start").unwrap();
+        let middle = NamedNode::new("http://example.org/middle").unwrap();
+        let end = NamedNode::new("http://example.org/end").unwrap();
+        let first = NamedNode::new("http://example.org/first").unwrap();
+        let second = NamedNode::new("http://example.org/second").unwrap();
+
+        graph.insert(&Triple::new(start.clone(), first.clone(), middle.clone()));
+        graph.insert(&Triple::new(middle, second.clone(), end.clone()));
+
+        // ^(first/second) is compiled to ^second/^first
+        let path = PropertyPath::inverse(PropertyPath::sequence(vec![
+            PropertyPath::predicate(first),
+            PropertyPath::predicate(second),
+        ]));
+        let results = path.evaluate(&graph, end.as_ref().into());
+
+        assert_eq!(results, vec![Term::NamedNode(start)]);
+    }
+
+    /// Builds `alice -knows-> bob -knows-> carol`, `bob -parent-> dave`
+    /// and gives everyone an `email` except carol who has a `phone`.
+    fn social_graph() -> (Graph, impl Fn(&str) -> NamedNode) {
+        let ex = |name: &str| NamedNode::new(format!("http://example.org/{name}")).unwrap();
+        let mut graph = Graph::new();
+        for (s, p, o) in [
+            ("alice", "knows", "bob"),
+            ("bob", "knows", "carol"),
+            ("bob", "parent", "dave"),
+            ("alice", "email", "alice-email"),
+            ("bob", "email", "bob-email"),
+            ("carol", "phone", "carol-phone"),
+            ("dave", "email", "dave-email"),
+        ] {
+            graph.insert(&Triple::new(ex(s), ex(p), ex(o)));
+        }
+        (graph, ex)
+    }
+
+    fn sorted_values(path: &PropertyPath, graph: &Graph, focus_node: &NamedNode) -> Vec<Term> {
+        let mut results = path.evaluate(graph, focus_node.as_ref().into());
+        results.sort_by_key(ToString::to_string);
+        results
+    }
+
+    #[test]
+    fn test_sequence_path() {
+        let (graph, ex) = social_graph();
+        let path = PropertyPath::sequence(vec![
+            PropertyPath::predicate(ex("knows")),
+            PropertyPath::predicate(ex("knows")),
+        ]);
+        assert_eq!(
+            sorted_values(&path, &graph, &ex("alice")),
+            vec![ex("carol").into()]
+        );
+        assert!(sorted_values(&path, &graph, &ex("bob")).is_empty());
+    }
+
+    #[test]
+    fn test_alternative_path() {
+        let (graph, ex) = social_graph();
+        let path = PropertyPath::alternative(vec![
+            PropertyPath::predicate(ex("email")),
+            PropertyPath::predicate(ex("phone")),
+        ]);
+        assert_eq!(
+            sorted_values(&path, &graph, &ex("alice")),
+            vec![ex("alice-email").into()]
+        );
+        assert_eq!(
+            sorted_values(&path, &graph, &ex("carol")),
+            vec![ex("carol-phone").into()]
+        );
+    }
+
+    #[test]
+    fn test_sequence_of_inverse_path() {
+        let (graph, ex) = social_graph();
+        // The emails of the people knowing the focus node
+        let path = PropertyPath::sequence(vec![
+            PropertyPath::inverse(PropertyPath::predicate(ex("knows"))),
+            PropertyPath::predicate(ex("email")),
+        ]);
+        assert_eq!(
+            sorted_values(&path, &graph, &ex("carol")),
+            vec![ex("bob-email").into()]
+        );
+        assert!(sorted_values(&path, &graph, &ex("alice")).is_empty());
+    }
+
+    #[test]
+    fn test_alternative_of_sequence_path() {
+        let (graph, ex) = social_graph();
+        // The emails of the focus node and of the parents of the people it knows
+        let path = PropertyPath::alternative(vec![
+            PropertyPath::predicate(ex("email")),
+            PropertyPath::sequence(vec![
+                PropertyPath::predicate(ex("knows")),
+                PropertyPath::predicate(ex("parent")),
+                PropertyPath::alternative(vec![
+                    PropertyPath::predicate(ex("email")),
+                    PropertyPath::predicate(ex("phone")),
+                ]),
+            ]),
+        ]);
+        assert_eq!(
+            sorted_values(&path, &graph, &ex("alice")),
+            vec![ex("alice-email").into(), ex("dave-email").into()]
+        );
+        assert_eq!(
+            sorted_values(&path, &graph, &ex("bob")),
+            vec![ex("bob-email").into()]
+        );
+    }
+
+    #[test]
+    fn test_one_or_more_path() {
+        let mut graph = Graph::new();
+        let nodes = (0..4)
+            .map(|i| NamedNode::new(format!("http://example.org/n{i}")).unwrap())
+            .collect::<Vec<_>>();
+        let p = NamedNode::new("http://example.org/p").unwrap();
+
+        // n0 -> n1 -> n2 -> n3 -> n1
+        for (from, to) in nodes.iter().zip(&nodes[1..]) {
+            graph.insert(&Triple::new(from.clone(), p.clone(), to.clone()));
+        }
+        graph.insert(&Triple::new(nodes[3].clone(), p.clone(), nodes[1].clone()));
+
+        let path = PropertyPath::one_or_more(PropertyPath::alternative(vec![
+            PropertyPath::predicate(p.clone()),
+            PropertyPath::predicate(p),
+        ]));
+        let mut results = path.evaluate(&graph, nodes[0].as_ref().into());
+        results.sort_by_key(ToString::to_string);
+
+        // Each node is returned once, the focus node is not reachable
+        assert_eq!(
+            results,
+            nodes[1..]
+                .iter()
+                .cloned()
+                .map(Term::NamedNode)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_closure_path_checks_deadline() {
+        let (graph, ex) = social_graph();
+        let path = CompiledPath::new(&PropertyPath::zero_or_more(PropertyPath::predicate(ex(
+            "knows",
+        ))));
+        let focus_node = Term::from(ex("alice"));
+        assert_eq!(
+            path.evaluate(&graph, focus_node.as_ref(), &|| Ok(()))
+                .unwrap()
+                .len(),
+            3
+        );
+        assert!(matches!(
+            path.evaluate(&graph, focus_node.as_ref(), &|| Err(ShaclError::Timeout(
+                std::time::Duration::ZERO
+            ))),
+            Err(ShaclError::Timeout(_))
+        ));
+    }
+
     #[test]
     fn test_circular_list_detection() {
         use oxrdf::{BlankNode, vocab::rdf};
@@ -490,7 +749,7 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
-            ShaclParseError::CircularList { .. }
+            ShaclError::Parse(ShaclParseError::CircularList { .. })
         ));
     }
 
@@ -538,7 +797,7 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
-            ShaclParseError::ListTooLong { .. }
+            ShaclError::Parse(ShaclParseError::ListTooLong { .. })
         ));
     }
 }
@@ -7,6 +7,10 @@ use oxrdf::{
     BlankNode, Graph, Literal, NamedNodeRef, Term, Triple,
     vocab::{rdf, shacl, xsd},
 };
+#[cfg(feature = "turtle")]
+use oxttl::TurtleSerializer;
+#[cfg(feature = "turtle")]
+use std::io;
 
 use crate::constraint::ConstraintComponent;
 use crate::model::ShapeId;
@@ -76,8 +80,8 @@ pub struct ValidationResult {
     /// The constraint component that was violated.
     pub source_constraint_component: ConstraintComponent,
 
-    /// Human-readable message describing the violation.
-    pub result_message: Option<String>,
+    /// Human-readable messages describing the violation, possibly in several languages.
+    pub result_messages: Vec<Literal>,
 
     /// Severity level.
     pub result_severity: Severity,
@@ -99,7 +103,7 @@ impl ValidationResult {
             value: None,
             source_shape,
             source_constraint_component,
-            result_message: None,
+            result_messages: Vec::new(),
             result_severity: Severity::Violation,
             detail: Vec::new(),
         }
@@ -122,10 +126,28 @@ impl ValidationResult {
     /// Sets the result message.
     #[must_use]
     pub fn with_message(mut self, message: impl Into<String>) -> Self {
-        self.result_message = Some(message.into());
+        self.result_messages = vec![Literal::new_simple_literal(message)];
         self
     }
 
+    /// Sets the result messages, for example one per language.
+    #[must_use]
+    pub fn with_messages(mut self, messages: impl IntoIterator<Item = Literal>) -> Self {
+        self.result_messages = messages.into_iter().collect();
+        self
+    }
+
+    /// Returns the first result message.
+    pub fn message(&self) -> Option<&str> {
+        self.result_messages.first().map(Literal::value)
+    }
+
+    /// Returns the first result message.
+    #[deprecated(note = "Use `result_messages` or `message` instead", since = "0.1.1")]
+    pub fn result_message(&self) -> Option<&str> {
+        self.message()
+    }
+
     /// Sets the severity.
     #[must_use]
     pub fn with_severity(mut self, severity: Severity) -> Self {
@@ -161,6 +183,8 @@ impl ValidationReport {
     }
 
     /// Returns true if the data graph conforms to the shapes graph.
+    ///
+    /// Only the results with the [`Violation`](Severity::Violation) severity make the data graph not conform.
     pub fn conforms(&self) -> bool {
         self.conforms
     }
@@ -170,28 +194,31 @@ impl ValidationReport {
         &self.results
     }
 
-    /// Returns the number of violations.
-    pub fn violation_count(&self) -> usize {
+    /// Returns the validation results with the given severity.
+    ///
+    /// It allows to report warnings and infos even if the data graph conforms.
+    pub fn results_with_severity(
+        &self,
+        severity: Severity,
+    ) -> impl Iterator<Item = &ValidationResult> {
         self.results
             .iter()
-            .filter(|r| r.result_severity == Severity::Violation)
-            .count()
+            .filter(move |r| r.result_severity == severity)
+    }
+
+    /// Returns the number of violations.
+    pub fn violation_count(&self) -> usize {
+        self.results_with_severity(Severity::Violation).count()
     }
 
     /// Returns the number of warnings.
     pub fn warning_count(&self) -> usize {
-        self.results
-            .iter()
-            .filter(|r| r.result_severity == Severity::Warning)
-            .count()
+        self.results_with_severity(Severity::Warning).count()
     }
 
     /// Returns the number of info results.
     pub fn info_count(&self) -> usize {
-        self.results
-            .iter()
-            .filter(|r| r.result_severity == Severity::Info)
-            .count()
+        self.results_with_severity(Severity::Info).count()
     }
 
     /// Adds a validation result.
@@ -203,6 +230,11 @@ impl ValidationReport {
         self.results.push(result);
     }
 
+    /// Returns the results mutably, to complete them after they have been added.
+    pub(crate) fn results_mut(&mut self) -> &mut [ValidationResult] {
+        &mut self.results
+    }
+
     /// Merges another report into this one.
     pub fn merge(&mut self, other: ValidationReport) {
         if !other.conforms {
@@ -211,6 +243,13 @@ impl ValidationReport {
         self.results.extend(other.results);
     }
 
+    /// Sorts the results in a canonical order: by focus node, then result path, then constraint component.
+    ///
+    /// It makes reports of the same validation comparable row by row.
+    pub(crate) fn sort_results(&mut self) {
+        sort_results(&mut self.results);
+    }
+
     /// Returns true if there are no results.
     pub fn is_empty(&self) -> bool {
         self.results.is_empty()
@@ -237,87 +276,50 @@ impl ValidationReport {
 
         // Add results
         for result in &self.results {
-            let result_node = BlankNode::default();
-
-            // Link result to report
+            let result_node = add_result_to_graph(&mut graph, result);
             graph.insert(&Triple::new(
                 report_node.clone(),
                 shacl::RESULT,
-                result_node.clone(),
-            ));
-
-            // Add result type
-            graph.insert(&Triple::new(
-                result_node.clone(),
-                rdf::TYPE,
-                shacl::VALIDATION_RESULT,
-            ));
-
-            // Add focus node
-            graph.insert(&Triple::new(
-                result_node.clone(),
-                shacl::FOCUS_NODE,
-                result.focus_node.clone(),
-            ));
-
-            // Add result path (if present)
-            if let Some(path) = &result.result_path {
-                if let Some(pred) = path.as_predicate() {
-                    graph.insert(&Triple::new(
-                        result_node.clone(),
-                        shacl::RESULT_PATH,
-                        pred.clone(),
-                    ));
-                }
-            }
-
-            // Add value (if present)
-            if let Some(value) = &result.value {
-                graph.insert(&Triple::new(
-                    result_node.clone(),
-                    shacl::VALUE,
-                    value.clone(),
-                ));
-            }
-
-            // Add source shape
-            graph.insert(&Triple::new(
-                result_node.clone(),
-                shacl::SOURCE_SHAPE,
-                result.source_shape.to_term(),
-            ));
-
-            // Add source constraint component
-            graph.insert(&Triple::new(
-                result_node.clone(),
-                shacl::SOURCE_CONSTRAINT_COMPONENT,
-                result.source_constraint_component.iri(),
+                result_node,
             ));
-
-            // Add result message (if present)
-            if let Some(message) = &result.result_message {
-                graph.insert(&Triple::new(
-                    result_node.clone(),
-                    shacl::RESULT_MESSAGE,
-                    Literal::new_simple_literal(message),
-                ));
-            }
-
-            // Add severity
-            graph.insert(&Triple::new(
-                result_node.clone(),
-                shacl::RESULT_SEVERITY,
-                result.result_severity.iri(),
-            ));
-
-            // Add nested details
-            for detail in &result.detail {
-                add_detail_to_graph(&mut graph, &result_node, detail);
-            }
         }
 
         graph
     }
+
+    /// Serializes the report in Turtle, using the standard SHACL report vocabulary.
+    ///
+    /// The triples are the ones returned by [`to_graph`](Self::to_graph).
+    /// It requires the `turtle` feature.
+    ///
+    /// ```
+    /// use oxrdf::{NamedNode, Term};
+    /// use sparshacl::{ConstraintComponent, ShapeId, ValidationReport, ValidationResult};
+    ///
+    /// let mut report = ValidationReport::new();
+    /// report.add_result(ValidationResult::new(
+    ///     Term::from(NamedNode::new("http://example.com/alice")?),
+    ///     ShapeId::Named(NamedNode::new("http://example.com/PersonShape")?),
+    ///     ConstraintComponent::MinCount,
+    /// ));
+    /// let turtle = report.to_turtle()?;
+    /// assert!(turtle.contains("sh:conforms false"));
+    /// assert!(turtle.contains("sh:focusNode <http://example.com/alice>"));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[cfg(feature = "turtle")]
+    pub fn to_turtle(&self) -> io::Result<String> {
+        let mut serializer = TurtleSerializer::new()
+            .with_prefix("sh", "http://www.w3.org/ns/shacl#")
+            .and_then(|s| s.with_prefix("xsd", "http://www.w3.org/2001/XMLSchema#"))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            .for_writer(Vec::new());
+        for triple in &self.to_graph() {
+            serializer.serialize_triple(triple)?;
+        }
+        String::from_utf8(serializer.finish()?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
 }
 
 impl Default for ValidationReport {
@@ -326,50 +328,124 @@ impl Default for ValidationReport {
     }
 }
 
-fn add_detail_to_graph(graph: &mut Graph, parent: &BlankNode, detail: &ValidationResult) {
-    let detail_node = BlankNode::default();
-
-    // Link detail to parent
-    graph.insert(&Triple::new(
-        parent.clone(),
-        shacl::DETAIL,
-        detail_node.clone(),
-    ));
+/// Adds a `sh:ValidationResult` and its details to a graph and returns its node.
+fn add_result_to_graph(graph: &mut Graph, result: &ValidationResult) -> BlankNode {
+    let result_node = BlankNode::default();
 
-    // Add detail type
+    // Add result type
     graph.insert(&Triple::new(
-        detail_node.clone(),
+        result_node.clone(),
         rdf::TYPE,
         shacl::VALIDATION_RESULT,
     ));
 
     // Add focus node
     graph.insert(&Triple::new(
-        detail_node.clone(),
+        result_node.clone(),
         shacl::FOCUS_NODE,
-        detail.focus_node.clone(),
+        result.focus_node.clone(),
+    ));
+
+    // Add result path (if present)
+    if let Some(path) = &result.result_path {
+        let path = path.add_to_graph(graph);
+        graph.insert(&Triple::new(result_node.clone(), shacl::RESULT_PATH, path));
+    }
+
+    // Add value (if present)
+    if let Some(value) = &result.value {
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            shacl::VALUE,
+            value.clone(),
+        ));
+    }
+
+    // Add source shape
+    graph.insert(&Triple::new(
+        result_node.clone(),
+        shacl::SOURCE_SHAPE,
+        result.source_shape.to_term(),
     ));
 
     // Add source constraint component
     graph.insert(&Triple::new(
-        detail_node.clone(),
+        result_node.clone(),
         shacl::SOURCE_CONSTRAINT_COMPONENT,
-        detail.source_constraint_component.iri(),
+        result.source_constraint_component.iri(),
     ));
 
-    // Add result message (if present)
-    if let Some(message) = &detail.result_message {
+    // Add result messages
+    for message in &result.result_messages {
         graph.insert(&Triple::new(
-            detail_node.clone(),
+            result_node.clone(),
             shacl::RESULT_MESSAGE,
-            Literal::new_simple_literal(message),
+            message.clone(),
         ));
     }
 
-    // Recursively add nested details
-    for nested in &detail.detail {
-        add_detail_to_graph(graph, &detail_node, nested);
+    // Add severity
+    graph.insert(&Triple::new(
+        result_node.clone(),
+        shacl::RESULT_SEVERITY,
+        result.result_severity.iri(),
+    ));
+
+    // Add nested details
+    for detail in &result.detail {
+        let detail_node = add_result_to_graph(graph, detail);
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            shacl::DETAIL,
+            detail_node,
+        ));
     }
+
+    result_node
+}
+
+/// Fills a `sh:message` template, replacing `{?name}` and `{$name}` by the value bound to `name`.
+///
+/// The language tag of the template is kept.
+pub(crate) fn fill_message_template<'a>(
+    template: &Literal,
+    bindings: impl IntoIterator<Item = (&'a str, &'a Term)>,
+) -> Literal {
+    let mut message = template.value().to_owned();
+    for (name, value) in bindings {
+        let value = match value {
+            Term::Literal(literal) => literal.value().to_owned(),
+            other => other.to_string(),
+        };
+        for prefix in ['?', '$'] {
+            message = message.replace(&format!("{{{prefix}{name}}}"), &value);
+        }
+    }
+    match template.language() {
+        Some(language) => Literal::new_language_tagged_literal_unchecked(message, language),
+        None => Literal::new_simple_literal(message),
+    }
+}
+
+fn sort_results(results: &mut [ValidationResult]) {
+    for result in &mut *results {
+        sort_results(&mut result.detail);
+    }
+    // The value, source shape and message break the ties
+    results.sort_by_cached_key(|result| {
+        (
+            result.focus_node.to_string(),
+            result.result_path.as_ref().map(ToString::to_string),
+            result.source_constraint_component.iri().as_str().to_owned(),
+            result.value.as_ref().map(ToString::to_string),
+            result.source_shape.to_string(),
+            result
+                .result_messages
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+        )
+    });
 }
 
 #[cfg(test)]
@@ -0,0 +1,557 @@
+//! [SHACL-SPARQL](https://www.w3.org/TR/shacl/#sparql-constraints) constraints.
+//!
+//! The `sh:select` queries are evaluated with the [`spareval`] engine
+//! directly against the [`DataGraph`] being validated.
+
+use oxrdf::vocab::xsd;
+use oxrdf::{Literal, Term, Variable};
+use spareval::{
+    InternalQuad, QueryEvaluationError, QueryEvaluator, QueryResults, QuerySolution,
+    QueryableDataset,
+};
+use spargebra::Query;
+use spargebra::algebra::{
+    AggregateExpression, Expression, GraphPattern, OrderExpression, PropertyPathExpression,
+};
+use spargebra::term::{NamedNodePattern, TermPattern, TriplePattern};
+
+use crate::data::{DataGraph, as_subject};
+use crate::error::{ShaclError, ShaclValidationError};
+use crate::path::PropertyPath;
+use crate::report::fill_message_template;
+
+/// The variables [pre-bound](https://www.w3.org/TR/shacl/#pre-binding) by the validator.
+const PRE_BOUND_VARIABLES: [&str; 2] = ["this", "currentShape"];
+
+/// A SPARQL-based constraint declared with `sh:sparql`.
+///
+/// Each solution of the `sh:select` query, with `$this` pre-bound to the focus node
+/// and `$currentShape` to the shape, is a validation result.
+/// The `?value`, `?path` and `?message` bindings are used to fill it
+/// and a solution with `?failure` bound to `true` makes the validation fail.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparqlConstraint {
+    /// The `sh:select` query.
+    ///
+    /// `$PATH` is already replaced by the path of the property shape
+    /// and the pre-bound variables are projected.
+    pub select: Query,
+    /// The `sh:message` templates of the constraint.
+    ///
+    /// `{?name}` and `{$name}` are replaced by the bindings of the solution.
+    pub messages: Vec<Literal>,
+}
+
+impl SparqlConstraint {
+    /// Builds a constraint from its `sh:select` query.
+    ///
+    /// `path` is the path of the property shape of the constraint that replaces `$PATH`.
+    /// Queries using `$shapesGraph`, `$PATH` outside of property shapes
+    /// or assigning a pre-bound variable are rejected.
+    pub(crate) fn new(
+        select: Query,
+        messages: Vec<Literal>,
+        path: Option<&PropertyPath>,
+    ) -> Result<Self, String> {
+        let Query::Select {
+            dataset,
+            pattern,
+            base_iri,
+        } = select
+        else {
+            return Err("sh:select must be a SELECT query".into());
+        };
+        let mut pattern = PreBinding {
+            path: path.map(path_expression).transpose()?,
+        }
+        .pattern(&pattern)?;
+        project_pre_bound_variables(&mut pattern);
+        Ok(Self {
+            select: Query::Select {
+                dataset,
+                pattern,
+                base_iri,
+            },
+            messages,
+        })
+    }
+
+    /// Evaluates the query with `$this` bound to the focus node and `$currentShape` to the shape
+    /// and returns the solutions.
+    pub(crate) fn evaluate(
+        &self,
+        data_graph: &dyn DataGraph,
+        focus_node: &Term,
+        current_shape: &Term,
+    ) -> Result<Vec<QuerySolution>, ShaclError> {
+        // The optimizer does not know that the pre-bound variables are bound
+        // and would remove the expressions that only use them
+        let results = QueryEvaluator::new()
+            .without_optimizations()
+            .prepare(&self.select)
+            .substitute_variable(Variable::new_unchecked("this"), focus_node.clone())
+            .substitute_variable(
+                Variable::new_unchecked("currentShape"),
+                current_shape.clone(),
+            )
+            .execute(SparqlDataGraph(data_graph))
+            .map_err(|e| sparql_error(&e))?;
+        let QueryResults::Solutions(solutions) = results else {
+            return Err(
+                ShaclValidationError::sparql_error("sh:select must be a SELECT query").into(),
+            );
+        };
+        solutions
+            .map(|solution| {
+                let solution = solution.map_err(|e| sparql_error(&e))?;
+                if let Some(Term::Literal(failure)) = solution.get("failure") {
+                    if failure.datatype() == xsd::BOOLEAN && matches!(failure.value(), "true" | "1")
+                    {
+                        return Err(ShaclValidationError::sparql_error(
+                            "The SPARQL constraint reported a failure",
+                        )
+                        .into());
+                    }
+                }
+                Ok(solution)
+            })
+            .collect()
+    }
+
+    /// Returns the first `sh:message` template.
+    #[deprecated(note = "Use `messages` instead", since = "0.1.1")]
+    pub fn message(&self) -> Option<&str> {
+        self.messages.first().map(Literal::value)
+    }
+
+    /// Builds the messages of a result from the `sh:message` templates and the solution bindings.
+    pub(crate) fn messages_for(&self, solution: &QuerySolution) -> Vec<Literal> {
+        self.messages
+            .iter()
+            .map(|template| {
+                fill_message_template(
+                    template,
+                    solution
+                        .iter()
+                        .map(|(variable, value)| (variable.as_str(), value)),
+                )
+            })
+            .collect()
+    }
+}
+
+fn sparql_error(error: &QueryEvaluationError) -> ShaclError {
+    ShaclValidationError::sparql_error(error.to_string()).into()
+}
+
+/// Exposes a [`DataGraph`] as the default graph of a SPARQL dataset.
+struct SparqlDataGraph<'a>(&'a dyn DataGraph);
+
+impl<'a> QueryableDataset<'a> for SparqlDataGraph<'a> {
+    type InternalTerm = Term;
+    type Error = ShaclError;
+
+    fn internal_quads_for_pattern(
+        &self,
+        subject: Option<&Term>,
+        predicate: Option<&Term>,
+        object: Option<&Term>,
+        graph_name: Option<Option<&Term>>,
+    ) -> impl Iterator<Item = Result<InternalQuad<Term>, ShaclError>> + use<'a> {
+        let subject = subject.map(|s| as_subject(s.as_ref()));
+        let predicate = predicate.map(|p| match p {
+            Term::NamedNode(p) => Some(p.as_ref()),
+            _ => None,
+        });
+        // There is no named graph and subjects and predicates have restricted kinds
+        let triples =
+            if graph_name != Some(None) || subject == Some(None) || predicate == Some(None) {
+                Box::new(std::iter::empty())
+            } else {
+                self.0.triples_for_pattern(
+                    subject.flatten(),
+                    predicate.flatten(),
+                    object.map(Term::as_ref),
+                )
+            };
+        triples.map(|t| {
+            let t = t?;
+            Ok(InternalQuad {
+                subject: t.subject.into(),
+                predicate: t.predicate.into(),
+                object: t.object,
+                graph_name: None,
+            })
+        })
+    }
+
+    fn internalize_term(&self, term: Term) -> Result<Term, ShaclError> {
+        Ok(term)
+    }
+
+    fn externalize_term(&self, term: Term) -> Result<Term, ShaclError> {
+        Ok(term)
+    }
+}
+
+/// Rewrites a `sh:select` query following the [pre-binding](https://www.w3.org/TR/shacl/#pre-binding) rules.
+struct PreBinding {
+    /// The path replacing `$PATH`, `None` outside of property shapes
+    path: Option<PropertyPathExpression>,
+}
+
+impl PreBinding {
+    fn pattern(&self, pattern: &GraphPattern) -> Result<GraphPattern, String> {
+        #[allow(unreachable_patterns, clippy::allow_attributes)]
+        Ok(match pattern {
+            GraphPattern::Bgp { patterns } => self.bgp(patterns)?,
+            GraphPattern::Path {
+                subject, object, ..
+            } => {
+                Self::term_pattern(subject)?;
+                Self::term_pattern(object)?;
+                pattern.clone()
+            }
+            GraphPattern::Join { left, right } => GraphPattern::Join {
+                left: Box::new(self.pattern(left)?),
+                right: Box::new(self.pattern(right)?),
+            },
+            GraphPattern::LeftJoin {
+                left,
+                right,
+                expression,
+            } => GraphPattern::LeftJoin {
+                left: Box::new(self.pattern(left)?),
+                right: Box::new(self.pattern(right)?),
+                expression: expression
+                    .as_ref()
+                    .map(|e| self.expression(e))
+                    .transpose()?,
+            },
+            GraphPattern::Filter { expr, inner } => GraphPattern::Filter {
+                expr: self.expression(expr)?,
+                inner: Box::new(self.pattern(inner)?),
+            },
+            GraphPattern::Union { left, right } => GraphPattern::Union {
+                left: Box::new(self.pattern(left)?),
+                right: Box::new(self.pattern(right)?),
+            },
+            GraphPattern::Graph { name, inner } => {
+                Self::named_node_pattern(name)?;
+                GraphPattern::Graph {
+                    name: name.clone(),
+                    inner: Box::new(self.pattern(inner)?),
+                }
+            }
+            GraphPattern::Extend {
+                inner,
+                variable,
+                expression,
+            } => {
+                Self::assigned_variable(variable)?;
+                GraphPattern::Extend {
+                    inner: Box::new(self.pattern(inner)?),
+                    variable: variable.clone(),
+                    expression: self.expression(expression)?,
+                }
+            }
+            GraphPattern::Minus { left, right } => GraphPattern::Minus {
+                left: Box::new(self.pattern(left)?),
+                right: Box::new(self.pattern(right)?),
+            },
+            GraphPattern::Values { variables, .. } => {
+                for variable in variables {
+                    Self::assigned_variable(variable)?;
+                }
+                pattern.clone()
+            }
+            GraphPattern::OrderBy { inner, expression } => GraphPattern::OrderBy {
+                inner: Box::new(self.pattern(inner)?),
+                expression: expression
+                    .iter()
+                    .map(|e| {
+                        Ok(match e {
+                            OrderExpression::Asc(e) => OrderExpression::Asc(self.expression(e)?),
+                            OrderExpression::Desc(e) => OrderExpression::Desc(self.expression(e)?),
+                        })
+                    })
+                    .collect::<Result<_, String>>()?,
+            },
+            GraphPattern::Project { inner, variables } => {
+                for variable in variables {
+                    Self::variable(variable)?;
+                }
+                GraphPattern::Project {
+                    inner: Box::new(self.pattern(inner)?),
+                    variables: variables.clone(),
+                }
+            }
+            GraphPattern::Distinct { inner } => GraphPattern::Distinct {
+                inner: Box::new(self.pattern(inner)?),
+            },
+            GraphPattern::Reduced { inner } => GraphPattern::Reduced {
+                inner: Box::new(self.pattern(inner)?),
+            },
+            GraphPattern::Slice {
+                inner,
+                start,
+                length,
+            } => GraphPattern::Slice {
+                inner: Box::new(self.pattern(inner)?),
+                start: *start,
+                length: *length,
+            },
+            GraphPattern::Group {
+                inner,
+                variables,
+                aggregates,
+            } => GraphPattern::Group {
+                inner: Box::new(self.pattern(inner)?),
+                variables: variables.clone(),
+                aggregates: aggregates
+                    .iter()
+                    .map(|(variable, aggregate)| {
+                        Self::assigned_variable(variable)?;
+                        Ok((
+                            variable.clone(),
+                            match aggregate {
+                                AggregateExpression::CountSolutions { .. } => aggregate.clone(),
+                                AggregateExpression::FunctionCall {
+                                    name,
+                                    expr,
+                                    distinct,
+                                } => AggregateExpression::FunctionCall {
+                                    name: name.clone(),
+                                    expr: self.expression(expr)?,
+                                    distinct: *distinct,
+                                },
+                            },
+                        ))
+                    })
+                    .collect::<Result<_, String>>()?,
+            },
+            GraphPattern::Service {
+                name,
+                inner,
+                silent,
+            } => {
+                Self::named_node_pattern(name)?;
+                GraphPattern::Service {
+                    name: name.clone(),
+                    inner: Box::new(self.pattern(inner)?),
+                    silent: *silent,
+                }
+            }
+            #[cfg(feature = "sep-0006")]
+            GraphPattern::Lateral { .. } => {
+                return Err(format!("{pattern} is not supported in a SPARQL constraint"));
+            }
+        })
+    }
+
+    /// Replaces the `$PATH` predicates by the property shape path.
+    fn bgp(&self, patterns: &[TriplePattern]) -> Result<GraphPattern, String> {
+        let mut triples = Vec::new();
+        let mut paths = Vec::new();
+        for pattern in patterns {
+            Self::term_pattern(&pattern.subject)?;
+            Self::term_pattern(&pattern.object)?;
+            match &pattern.predicate {
+                NamedNodePattern::Variable(v) if v.as_str() == "PATH" => {
+                    let Some(path) = &self.path else {
+                        return Err(
+                            "$PATH can only be used in the constraints of property shapes".into(),
+                        );
+                    };
+                    paths.push(GraphPattern::Path {
+                        subject: pattern.subject.clone(),
+                        path: path.clone(),
+                        object: pattern.object.clone(),
+                    });
+                }
+                predicate => {
+                    Self::named_node_pattern(predicate)?;
+                    triples.push(pattern.clone());
+                }
+            }
+        }
+        Ok(paths
+            .into_iter()
+            .fold(GraphPattern::Bgp { patterns: triples }, |left, right| {
+                GraphPattern::Join {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }
+            }))
+    }
+
+    fn expression(&self, expression: &Expression) -> Result<Expression, String> {
+        let binary = |a: &Expression, b: &Expression| -> Result<_, String> {
+            Ok((Box::new(self.expression(a)?), Box::new(self.expression(b)?)))
+        };
+        let list = |l: &[Expression]| -> Result<Vec<_>, String> {
+            l.iter().map(|e| self.expression(e)).collect()
+        };
+        Ok(match expression {
+            Expression::NamedNode(_) | Expression::Literal(_) => expression.clone(),
+            Expression::Variable(v) | Expression::Bound(v) => {
+                Self::variable(v)?;
+                expression.clone()
+            }
+            Expression::Or(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::Or(a, b)
+            }
+            Expression::And(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::And(a, b)
+            }
+            Expression::Equal(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::Equal(a, b)
+            }
+            Expression::SameTerm(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::SameTerm(a, b)
+            }
+            Expression::Greater(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::Greater(a, b)
+            }
+            Expression::GreaterOrEqual(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::GreaterOrEqual(a, b)
+            }
+            Expression::Less(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::Less(a, b)
+            }
+            Expression::LessOrEqual(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::LessOrEqual(a, b)
+            }
+            Expression::In(a, b) => Expression::In(Box::new(self.expression(a)?), list(b)?),
+            Expression::Add(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::Add(a, b)
+            }
+            Expression::Subtract(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::Subtract(a, b)
+            }
+            Expression::Multiply(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::Multiply(a, b)
+            }
+            Expression::Divide(a, b) => {
+                let (a, b) = binary(a, b)?;
+                Expression::Divide(a, b)
+            }
+            Expression::UnaryPlus(e) => Expression::UnaryPlus(Box::new(self.expression(e)?)),
+            Expression::UnaryMinus(e) => Expression::UnaryMinus(Box::new(self.expression(e)?)),
+            Expression::Not(e) => Expression::Not(Box::new(self.expression(e)?)),
+            Expression::Exists(p) => Expression::Exists(Box::new(self.pattern(p)?)),
+            Expression::If(a, b, c) => Expression::If(
+                Box::new(self.expression(a)?),
+                Box::new(self.expression(b)?),
+                Box::new(self.expression(c)?),
+            ),
+            Expression::Coalesce(l) => Expression::Coalesce(list(l)?),
+            Expression::FunctionCall(f, args) => Expression::FunctionCall(f.clone(), list(args)?),
+        })
+    }
+
+    fn term_pattern(pattern: &TermPattern) -> Result<(), String> {
+        if let TermPattern::Variable(v) = pattern {
+            Self::variable(v)?;
+        }
+        Ok(())
+    }
+
+    fn named_node_pattern(pattern: &NamedNodePattern) -> Result<(), String> {
+        if let NamedNodePattern::Variable(v) = pattern {
+            Self::variable(v)?;
+        }
+        Ok(())
+    }
+
+    fn variable(variable: &Variable) -> Result<(), String> {
+        if variable.as_str() == "shapesGraph" {
+            return Err("$shapesGraph is not supported".into());
+        }
+        Ok(())
+    }
+
+    fn assigned_variable(variable: &Variable) -> Result<(), String> {
+        Self::variable(variable)?;
+        if PRE_BOUND_VARIABLES.contains(&variable.as_str()) || variable.as_str() == "PATH" {
+            return Err(format!(
+                "The pre-bound variable {variable} can't be assigned"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Adds the pre-bound variables to the query projection so that they can be substituted
+/// even if the query does not return them.
+fn project_pre_bound_variables(pattern: &mut GraphPattern) {
+    match pattern {
+        GraphPattern::Slice { inner, .. }
+        | GraphPattern::Distinct { inner }
+        | GraphPattern::Reduced { inner }
+        | GraphPattern::OrderBy { inner, .. } => project_pre_bound_variables(inner),
+        GraphPattern::Project { variables, .. } => {
+            for name in PRE_BOUND_VARIABLES {
+                let variable = Variable::new_unchecked(name);
+                if !variables.contains(&variable) {
+                    variables.push(variable);
+                }
+            }
+        }
+        _ => {
+            let mut variables = Vec::new();
+            pattern.on_in_scope_variable(|v| {
+                if !variables.contains(v) {
+                    variables.push(v.clone());
+                }
+            });
+            *pattern = GraphPattern::Project {
+                inner: Box::new(pattern.clone()),
+                variables,
+            };
+            project_pre_bound_variables(pattern);
+        }
+    }
+}
+
+/// Converts a SHACL path to a SPARQL one.
+fn path_expression(path: &PropertyPath) -> Result<PropertyPathExpression, String> {
+    Ok(match path {
+        PropertyPath::Predicate(p) => PropertyPathExpression::NamedNode(p.clone()),
+        PropertyPath::Sequence(paths) | PropertyPath::Alternative(paths) => {
+            let mut paths = paths.iter().map(path_expression);
+            let first = paths
+                .next()
+                .ok_or_else(|| format!("The path {path} is empty"))??;
+            paths.try_fold(first, |left, right| {
+                let (left, right) = (Box::new(left), Box::new(right?));
+                Ok::<_, String>(if matches!(path, PropertyPath::Sequence(_)) {
+                    PropertyPathExpression::Sequence(left, right)
+                } else {
+                    PropertyPathExpression::Alternative(left, right)
+                })
+            })?
+        }
+        PropertyPath::Inverse(p) => PropertyPathExpression::Reverse(Box::new(path_expression(p)?)),
+        PropertyPath::ZeroOrMore(p) => {
+            PropertyPathExpression::ZeroOrMore(Box::new(path_expression(p)?))
+        }
+        PropertyPath::OneOrMore(p) => {
+            PropertyPathExpression::OneOrMore(Box::new(path_expression(p)?))
+        }
+        PropertyPath::ZeroOrOne(p) => {
+            PropertyPathExpression::ZeroOrOne(Box::new(path_expression(p)?))
+        }
+    })
+}
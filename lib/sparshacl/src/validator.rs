@@ -3,34 +3,109 @@
 //! This module implements the core SHACL validation algorithm.
 
 use oxrdf::{
-    Graph, NamedNode, NamedNodeRef, Term, TermRef,
-    vocab::{rdf, shacl},
+    Graph, Literal, NamedNode, NamedNodeRef, Term,
+    vocab::{rdf, shacl, xsd},
+};
+use oxsdatatypes::{
+    Boolean, Date, DateTime, Decimal, Double, Float, GYear, GYearMonth, Integer, Time,
 };
 use regex::Regex;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::constraint::{Constraint, ConstraintComponent};
+use crate::data::{DataGraph, as_subject};
 use crate::error::{ShaclError, ShaclValidationError};
 use crate::model::{NodeShape, PropertyShape, Shape, ShapeId, ShapesGraph};
-use crate::path::PropertyPath;
-use crate::report::{Severity, ValidationReport, ValidationResult};
+use crate::path::{CompiledPath, PropertyPath};
+use crate::report::{Severity, ValidationReport, ValidationResult, fill_message_template};
 
-/// Maximum recursion depth for shape validation.
-const MAX_RECURSION_DEPTH: usize = 50;
+/// Default maximum recursion depth for shape validation.
+const DEFAULT_MAX_RECURSION_DEPTH: usize = 50;
 
 /// SHACL validator for validating RDF graphs against shapes.
+///
+/// The property paths and the `sh:pattern` regular expressions of the shapes are compiled once
+/// when the validator is created. Build the validator once and reuse it to validate any number
+/// of data graphs: validation only takes `&self` and clones share the compiled shapes.
+///
+/// ```
+/// use oxrdf::{Graph, Triple};
+/// use oxrdfio::{RdfFormat, RdfParser};
+/// use sparshacl::{ShaclValidator, ShapesGraph};
+///
+/// let parse = |turtle: &str| {
+///     RdfParser::from_format(RdfFormat::Turtle)
+///         .for_reader(turtle.as_bytes())
+///         .map(|quad| quad.map(Triple::from))
+///         .collect::<Result<Graph, _>>()
+/// };
+/// let validator = ShaclValidator::new(ShapesGraph::from_graph(&parse(
+///     "@prefix sh: <http://www.w3.org/ns/shacl#> .
+///     <http://example.com/PersonShape> a sh:NodeShape ;
+///         sh:targetClass <http://example.com/Person> ;
+///         sh:property [ sh:path <http://example.com/name> ; sh:pattern \"^[A-Z]\" ] .",
+/// )?)?);
+///
+/// for (data, conforms) in [
+///     ("<http://example.com/alice> a <http://example.com/Person> ; <http://example.com/name> \"Alice\" .", true),
+///     ("<http://example.com/bob> a <http://example.com/Person> ; <http://example.com/name> \"bob\" .", false),
+/// ] {
+///     assert_eq!(validator.validate(&parse(data)?)?.conforms(), conforms);
+/// }
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
 #[derive(Debug, Clone)]
 pub struct ShaclValidator {
     /// The shapes graph containing all shapes.
     shapes_graph: ShapesGraph,
+    /// The compiled shape paths and patterns, shared between clones.
+    compiled: Arc<CompiledShapes>,
+    /// Maximum duration of a validation.
+    timeout: Option<Duration>,
+    /// Maximum depth of nested shape validation.
+    max_recursion_depth: usize,
 }
 
 impl ShaclValidator {
     /// Creates a new validator with the given shapes graph.
+    ///
+    /// The shapes are compiled here, see [`ShaclValidator`] about reusing the validator.
     pub fn new(shapes_graph: ShapesGraph) -> Self {
-        Self { shapes_graph }
+        Self {
+            compiled: Arc::new(CompiledShapes::new(&shapes_graph)),
+            shapes_graph,
+            timeout: None,
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
+        }
+    }
+
+    /// Sets the maximum duration of a validation.
+    ///
+    /// The deadline is checked before each focus node is validated against a shape, before each
+    /// constraint is evaluated on a focus node and for each node visited by a `sh:zeroOrMorePath`
+    /// or `sh:oneOrMorePath` path.
+    /// [`validate`](Self::validate) returns [`ShaclError::Timeout`] once it is exceeded.
+    /// The evaluation of a single constraint on its value nodes, e.g. a `sh:sparql` query,
+    /// is not interrupted.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum depth of nested shape validation (default: 50).
+    ///
+    /// The depth grows with each shape reference (`sh:node`, `sh:and`, `sh:or`, `sh:not`...)
+    /// and nested property shape followed during the validation of a focus node,
+    /// [`validate`](Self::validate) returns [`ShaclError::RecursionLimit`] once it is exceeded.
+    #[must_use]
+    pub fn with_max_recursion_depth(mut self, max_recursion_depth: usize) -> Self {
+        self.max_recursion_depth = max_recursion_depth;
+        self
     }
 
     /// Returns a reference to the shapes graph.
@@ -39,7 +114,76 @@ impl ShaclValidator {
     }
 
     /// Validates a data graph against the shapes graph.
+    ///
+    /// The results are sorted by focus node, then result path, then constraint component.
     pub fn validate(&self, data_graph: &Graph) -> Result<ValidationReport, ShaclError> {
+        self.validate_data_graph(data_graph)
+    }
+
+    /// Validates the triples of any [`DataGraph`] against the shapes graph.
+    ///
+    /// It allows to validate data without copying it into a [`Graph`] first.
+    /// The results are the same as with [`validate`](Self::validate).
+    pub fn validate_data_graph(
+        &self,
+        data_graph: &dyn DataGraph,
+    ) -> Result<ValidationReport, ShaclError> {
+        self.validate_focus_nodes(data_graph, None)
+    }
+
+    /// Validates only the given focus nodes of any [`DataGraph`].
+    ///
+    /// Each node is validated against the shapes whose targets select it,
+    /// so that only the nodes changed since a previous validation have to be checked again.
+    /// Targets are checked on the given nodes only: `sh:targetClass` does not enumerate
+    /// all the instances of the class. Nodes that no shape targets are ignored.
+    ///
+    /// The results are the ones of [`validate`](Self::validate) about these focus nodes.
+    ///
+    /// ```
+    /// use oxrdf::{Graph, NamedNode, Term, Triple};
+    /// use oxrdfio::{RdfFormat, RdfParser};
+    /// use sparshacl::{ShaclValidator, ShapesGraph};
+    ///
+    /// let parse = |turtle: &str| {
+    ///     RdfParser::from_format(RdfFormat::Turtle)
+    ///         .for_reader(turtle.as_bytes())
+    ///         .map(|quad| quad.map(Triple::from))
+    ///         .collect::<Result<Graph, _>>()
+    /// };
+    /// let shapes = ShapesGraph::from_graph(&parse(
+    ///     "@prefix sh: <http://www.w3.org/ns/shacl#> .
+    ///     <http://example.com/PersonShape> a sh:NodeShape ;
+    ///         sh:targetClass <http://example.com/Person> ;
+    ///         sh:property [ sh:path <http://example.com/name> ; sh:minCount 1 ] .",
+    /// )?)?;
+    /// let data = parse(
+    ///     "<http://example.com/alice> a <http://example.com/Person> .
+    ///     <http://example.com/bob> a <http://example.com/Person> .",
+    /// )?;
+    ///
+    /// let validator = ShaclValidator::new(shapes);
+    /// let alice = Term::from(NamedNode::new("http://example.com/alice")?);
+    /// let report = validator.validate_nodes(&data, &[alice.clone()])?;
+    /// assert_eq!(report.results().len(), 1);
+    /// assert_eq!(report.results()[0].focus_node, alice);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn validate_nodes(
+        &self,
+        data_graph: &dyn DataGraph,
+        focus_nodes: &[Term],
+    ) -> Result<ValidationReport, ShaclError> {
+        self.validate_focus_nodes(data_graph, Some(focus_nodes))
+    }
+
+    /// Validates the focus nodes selected by the shape targets,
+    /// restricted to `candidates` if set.
+    fn validate_focus_nodes(
+        &self,
+        data_graph: &dyn DataGraph,
+        candidates: Option<&[Term]>,
+    ) -> Result<ValidationReport, ShaclError> {
         let mut report = ValidationReport::new();
         let mut context = ValidationContext::new(self, data_graph);
 
@@ -50,10 +194,11 @@ impl ShaclValidator {
             }
 
             // Find focus nodes for this shape
-            let focus_nodes = self.find_focus_nodes(&node_shape.base, data_graph);
+            let focus_nodes = self.find_focus_nodes(&node_shape.base, data_graph, candidates)?;
 
             // Validate each focus node against the shape
             for focus_node in focus_nodes {
+                context.check_deadline()?;
                 self.validate_node_against_shape(
                     &mut context,
                     &mut report,
@@ -72,9 +217,11 @@ impl ShaclValidator {
 
             // Only validate property shapes that have explicit targets
             if prop_shape.base.has_targets() {
-                let focus_nodes = self.find_focus_nodes(&prop_shape.base, data_graph);
+                let focus_nodes =
+                    self.find_focus_nodes(&prop_shape.base, data_graph, candidates)?;
 
                 for focus_node in focus_nodes {
+                    context.check_deadline()?;
                     self.validate_property_shape(
                         &mut context,
                         &mut report,
@@ -87,21 +234,37 @@ impl ShaclValidator {
             }
         }
 
+        report.sort_results();
         Ok(report)
     }
 
     /// Finds all focus nodes for a shape based on its targets.
+    ///
+    /// If `candidates` is set, only the candidates selected by a target are returned.
     #[expect(clippy::unused_self)]
-    fn find_focus_nodes(&self, shape: &Shape, data_graph: &Graph) -> Vec<Term> {
+    fn find_focus_nodes(
+        &self,
+        shape: &Shape,
+        data_graph: &dyn DataGraph,
+        candidates: Option<&[Term]>,
+    ) -> Result<Vec<Term>, ShaclError> {
         let mut focus_nodes = FxHashSet::default();
 
         for target in &shape.targets {
-            for node in target.find_focus_nodes(data_graph) {
-                focus_nodes.insert(node);
+            if let Some(candidates) = candidates {
+                for node in candidates {
+                    if !focus_nodes.contains(node) && target.selects_in(data_graph, node)? {
+                        focus_nodes.insert(node.clone());
+                    }
+                }
+            } else {
+                for node in target.find_focus_nodes_in(data_graph)? {
+                    focus_nodes.insert(node);
+                }
             }
         }
 
-        focus_nodes.into_iter().collect()
+        Ok(focus_nodes.into_iter().collect())
     }
 
     /// Validates a focus node against a node shape.
@@ -113,8 +276,8 @@ impl ShaclValidator {
         shape: &Arc<NodeShape>,
         depth: usize,
     ) -> Result<(), ShaclError> {
-        if depth > MAX_RECURSION_DEPTH {
-            return Err(ShaclValidationError::max_recursion_depth(depth).into());
+        if depth > self.max_recursion_depth {
+            return Err(ShaclError::RecursionLimit { depth });
         }
 
         let parent_severity = shape.base.severity;
@@ -159,8 +322,8 @@ impl ShaclValidator {
         depth: usize,
         parent_severity: Severity,
     ) -> Result<(), ShaclError> {
-        if depth > MAX_RECURSION_DEPTH {
-            return Err(ShaclValidationError::max_recursion_depth(depth).into());
+        if depth > self.max_recursion_depth {
+            return Err(ShaclError::RecursionLimit { depth });
         }
 
         // Use shape's own severity if non-default, otherwise inherit from parent
@@ -171,7 +334,7 @@ impl ShaclValidator {
         };
 
         // Get value nodes via property path
-        let value_nodes = shape.path.evaluate(context.data_graph, focus_node.as_ref());
+        let value_nodes = context.evaluate_path(shape, focus_node)?;
 
         // Validate constraints against value nodes
         for constraint in &shape.base.constraints {
@@ -218,8 +381,10 @@ impl ShaclValidator {
         depth: usize,
         effective_severity: Severity,
     ) -> Result<(), ShaclError> {
+        context.check_deadline()?;
         let severity = effective_severity;
         let shape_id = shape.id.clone();
+        let first_result = report.results().len();
 
         match constraint {
             // === Cardinality Constraints ===
@@ -240,9 +405,6 @@ impl ShaclValidator {
                     if let Some(p) = path {
                         result = result.with_path(p.clone());
                     }
-                    if let Some(msg) = &shape.message {
-                        result = result.with_message(msg.clone());
-                    }
 
                     report.add_result(result);
                 }
@@ -265,9 +427,6 @@ impl ShaclValidator {
                     if let Some(p) = path {
                         result = result.with_path(p.clone());
                     }
-                    if let Some(msg) = &shape.message {
-                        result = result.with_message(msg.clone());
-                    }
 
                     report.add_result(result);
                 }
@@ -276,7 +435,7 @@ impl ShaclValidator {
             // === Value Type Constraints ===
             Constraint::Class(class) => {
                 for value in value_nodes {
-                    if !is_instance_of(context.data_graph, value, class) {
+                    if !is_instance_of(context.data_graph, value, class)? {
                         let mut result = ValidationResult::new(
                             focus_node.clone(),
                             shape_id.clone(),
@@ -476,118 +635,126 @@ impl ShaclValidator {
             }
 
             // === Value Range Constraints ===
+            // Values that can't be compared with the bound, e.g. IRIs, are violations
             Constraint::MinExclusive(min) => {
                 for value in value_nodes {
-                    if let Some(cmp) = compare_values(value, &Term::Literal(min.clone())) {
-                        if cmp != Ordering::Greater {
-                            let mut result = ValidationResult::new(
-                                focus_node.clone(),
-                                shape_id.clone(),
-                                ConstraintComponent::MinExclusive,
-                            )
-                            .with_value(value.clone())
-                            .with_severity(severity)
-                            .with_message(format!("Value must be greater than {}", min.value()));
-
-                            if let Some(p) = path {
-                                result = result.with_path(p.clone());
-                            }
+                    if compare_values(value, &Term::Literal(min.clone())) != Some(Ordering::Greater)
+                    {
+                        let mut result = ValidationResult::new(
+                            focus_node.clone(),
+                            shape_id.clone(),
+                            ConstraintComponent::MinExclusive,
+                        )
+                        .with_value(value.clone())
+                        .with_severity(severity)
+                        .with_message(format!("Value must be greater than {}", min.value()));
 
-                            report.add_result(result);
+                        if let Some(p) = path {
+                            result = result.with_path(p.clone());
                         }
+
+                        report.add_result(result);
                     }
                 }
             }
 
             Constraint::MaxExclusive(max) => {
                 for value in value_nodes {
-                    if let Some(cmp) = compare_values(value, &Term::Literal(max.clone())) {
-                        if cmp != Ordering::Less {
-                            let mut result = ValidationResult::new(
-                                focus_node.clone(),
-                                shape_id.clone(),
-                                ConstraintComponent::MaxExclusive,
-                            )
-                            .with_value(value.clone())
-                            .with_severity(severity)
-                            .with_message(format!("Value must be less than {}", max.value()));
-
-                            if let Some(p) = path {
-                                result = result.with_path(p.clone());
-                            }
+                    if compare_values(value, &Term::Literal(max.clone())) != Some(Ordering::Less) {
+                        let mut result = ValidationResult::new(
+                            focus_node.clone(),
+                            shape_id.clone(),
+                            ConstraintComponent::MaxExclusive,
+                        )
+                        .with_value(value.clone())
+                        .with_severity(severity)
+                        .with_message(format!("Value must be less than {}", max.value()));
 
-                            report.add_result(result);
+                        if let Some(p) = path {
+                            result = result.with_path(p.clone());
                         }
+
+                        report.add_result(result);
                     }
                 }
             }
 
             Constraint::MinInclusive(min) => {
                 for value in value_nodes {
-                    if let Some(cmp) = compare_values(value, &Term::Literal(min.clone())) {
-                        if cmp == Ordering::Less {
-                            let mut result = ValidationResult::new(
-                                focus_node.clone(),
-                                shape_id.clone(),
-                                ConstraintComponent::MinInclusive,
-                            )
-                            .with_value(value.clone())
-                            .with_severity(severity)
-                            .with_message(format!(
-                                "Value must be greater than or equal to {}",
-                                min.value()
-                            ));
-
-                            if let Some(p) = path {
-                                result = result.with_path(p.clone());
-                            }
+                    if !matches!(
+                        compare_values(value, &Term::Literal(min.clone())),
+                        Some(Ordering::Greater | Ordering::Equal)
+                    ) {
+                        let mut result = ValidationResult::new(
+                            focus_node.clone(),
+                            shape_id.clone(),
+                            ConstraintComponent::MinInclusive,
+                        )
+                        .with_value(value.clone())
+                        .with_severity(severity)
+                        .with_message(format!(
+                            "Value must be greater than or equal to {}",
+                            min.value()
+                        ));
 
-                            report.add_result(result);
+                        if let Some(p) = path {
+                            result = result.with_path(p.clone());
                         }
+
+                        report.add_result(result);
                     }
                 }
             }
 
             Constraint::MaxInclusive(max) => {
                 for value in value_nodes {
-                    if let Some(cmp) = compare_values(value, &Term::Literal(max.clone())) {
-                        if cmp == Ordering::Greater {
-                            let mut result = ValidationResult::new(
-                                focus_node.clone(),
-                                shape_id.clone(),
-                                ConstraintComponent::MaxInclusive,
-                            )
-                            .with_value(value.clone())
-                            .with_severity(severity)
-                            .with_message(format!(
-                                "Value must be less than or equal to {}",
-                                max.value()
-                            ));
-
-                            if let Some(p) = path {
-                                result = result.with_path(p.clone());
-                            }
+                    if !matches!(
+                        compare_values(value, &Term::Literal(max.clone())),
+                        Some(Ordering::Less | Ordering::Equal)
+                    ) {
+                        let mut result = ValidationResult::new(
+                            focus_node.clone(),
+                            shape_id.clone(),
+                            ConstraintComponent::MaxInclusive,
+                        )
+                        .with_value(value.clone())
+                        .with_severity(severity)
+                        .with_message(format!(
+                            "Value must be less than or equal to {}",
+                            max.value()
+                        ));
 
-                            report.add_result(result);
+                        if let Some(p) = path {
+                            result = result.with_path(p.clone());
                         }
+
+                        report.add_result(result);
                     }
                 }
             }
 
             // === Property Pair Constraints ===
             Constraint::Equals(property) => {
-                let other_values: FxHashSet<_> =
-                    get_property_values(context.data_graph, focus_node, property)
-                        .into_iter()
-                        .collect();
-                let value_set: FxHashSet<_> = value_nodes.iter().cloned().collect();
+                let other_values = get_property_values(context.data_graph, focus_node, property)
+                    .collect::<Result<FxHashSet<_>, _>>()?;
+                let value_set: FxHashSet<_> = value_nodes.iter().collect();
 
-                if value_set != other_values {
+                // Each value missing from one of the two sets is reported
+                let missing_values = value_nodes
+                    .iter()
+                    .filter(|value| !other_values.contains(*value))
+                    .chain(
+                        other_values
+                            .iter()
+                            .filter(|value| !value_set.contains(value)),
+                    );
+                for value in missing_values {
                     let mut result = ValidationResult::new(
                         focus_node.clone(),
                         shape_id.clone(),
                         ConstraintComponent::Equals,
                     )
+                    .with_value(value.clone())
                     .with_severity(severity)
                     .with_message(format!(
                         "Values do not equal values of property <{}>",
@@ -603,10 +770,8 @@ impl ShaclValidator {
             }
 
             Constraint::Disjoint(property) => {
-                let other_values: FxHashSet<_> =
-                    get_property_values(context.data_graph, focus_node, property)
-                        .into_iter()
-                        .collect();
+                let other_values = get_property_values(context.data_graph, focus_node, property)
+                    .collect::<Result<FxHashSet<_>, _>>()?;
 
                 for value in value_nodes {
                     if other_values.contains(value) {
@@ -632,60 +797,63 @@ impl ShaclValidator {
             }
 
             Constraint::LessThan(property) => {
-                let other_values = get_property_values(context.data_graph, focus_node, property);
+                let other_values = get_property_values(context.data_graph, focus_node, property)
+                    .collect::<Result<Vec<_>, _>>()?;
 
+                // Values that can't be compared, e.g. IRIs, are violations
                 for value in value_nodes {
                     for other in &other_values {
-                        if let Some(cmp) = compare_values(value, other) {
-                            if cmp != Ordering::Less {
-                                let mut result = ValidationResult::new(
-                                    focus_node.clone(),
-                                    shape_id.clone(),
-                                    ConstraintComponent::LessThan,
-                                )
-                                .with_value(value.clone())
-                                .with_severity(severity)
-                                .with_message(format!(
-                                    "Value is not less than values of <{}>",
-                                    property.as_str()
-                                ));
-
-                                if let Some(p) = path {
-                                    result = result.with_path(p.clone());
-                                }
+                        if compare_values(value, other) != Some(Ordering::Less) {
+                            let mut result = ValidationResult::new(
+                                focus_node.clone(),
+                                shape_id.clone(),
+                                ConstraintComponent::LessThan,
+                            )
+                            .with_value(value.clone())
+                            .with_severity(severity)
+                            .with_message(format!(
+                                "Value is not less than {other}, value of <{}>",
+                                property.as_str()
+                            ));
 
-                                report.add_result(result);
+                            if let Some(p) = path {
+                                result = result.with_path(p.clone());
                             }
+
+                            report.add_result(result);
                         }
                     }
                 }
             }
 
             Constraint::LessThanOrEquals(property) => {
-                let other_values = get_property_values(context.data_graph, focus_node, property);
+                let other_values = get_property_values(context.data_graph, focus_node, property)
+                    .collect::<Result<Vec<_>, _>>()?;
 
+                // Values that can't be compared, e.g. IRIs, are violations
                 for value in value_nodes {
                     for other in &other_values {
-                        if let Some(cmp) = compare_values(value, other) {
-                            if cmp == Ordering::Greater {
-                                let mut result = ValidationResult::new(
-                                    focus_node.clone(),
-                                    shape_id.clone(),
-                                    ConstraintComponent::LessThanOrEquals,
-                                )
-                                .with_value(value.clone())
-                                .with_severity(severity)
-                                .with_message(format!(
-                                    "Value is not less than or equal to values of <{}>",
-                                    property.as_str()
-                                ));
-
-                                if let Some(p) = path {
-                                    result = result.with_path(p.clone());
-                                }
+                        if !matches!(
+                            compare_values(value, other),
+                            Some(Ordering::Less | Ordering::Equal)
+                        ) {
+                            let mut result = ValidationResult::new(
+                                focus_node.clone(),
+                                shape_id.clone(),
+                                ConstraintComponent::LessThanOrEquals,
+                            )
+                            .with_value(value.clone())
+                            .with_severity(severity)
+                            .with_message(format!(
+                                "Value is not less than or equal to {other}, value of <{}>",
+                                property.as_str()
+                            ));
 
-                                report.add_result(result);
+                            if let Some(p) = path {
+                                result = result.with_path(p.clone());
                             }
+
+                            report.add_result(result);
                         }
                     }
                 }
@@ -871,18 +1039,16 @@ impl ShaclValidator {
 
                 // Check for unexpected properties
                 for triple in get_triples_for_subject(context.data_graph, focus_node) {
-                    if !allowed_properties.contains(&triple.predicate) {
+                    let (predicate, object) = triple?;
+                    if !allowed_properties.contains(&predicate) {
                         let mut result = ValidationResult::new(
                             focus_node.clone(),
                             shape_id.clone(),
                             ConstraintComponent::Closed,
                         )
-                        .with_value(triple.object.clone())
+                        .with_value(object)
                         .with_severity(severity)
-                        .with_message(format!(
-                            "Unexpected property <{}>",
-                            triple.predicate.as_str()
-                        ));
+                        .with_message(format!("Unexpected property <{}>", predicate.as_str()));
 
                         if let Some(p) = path {
                             result = result.with_path(p.clone());
@@ -893,18 +1059,72 @@ impl ShaclValidator {
                 }
             }
 
+            #[cfg(feature = "sparql")]
+            Constraint::Sparql(sparql) => {
+                for solution in
+                    sparql.evaluate(context.data_graph, focus_node, &shape_id.to_term())?
+                {
+                    let mut result = ValidationResult::new(
+                        focus_node.clone(),
+                        shape_id.clone(),
+                        ConstraintComponent::Sparql,
+                    )
+                    .with_severity(severity)
+                    .with_message("SPARQL constraint returned a solution");
+
+                    // Node shapes report the focus node if ?value is not bound
+                    if let Some(value) = solution.get("value") {
+                        result = result.with_value(value.clone());
+                    } else if path.is_none() {
+                        result = result.with_value(focus_node.clone());
+                    }
+                    if let Some(Term::NamedNode(p)) = solution.get("path") {
+                        result = result.with_path(PropertyPath::Predicate(p.clone()));
+                    } else if let Some(p) = path {
+                        result = result.with_path(p.clone());
+                    }
+                    let messages = match solution.get("message") {
+                        Some(Term::Literal(message)) => vec![message.clone()],
+                        Some(message) => {
+                            vec![Literal::new_simple_literal(get_string_value(message))]
+                        }
+                        None => sparql.messages_for(&solution),
+                    };
+                    if !messages.is_empty() {
+                        result = result.with_messages(messages);
+                    }
+
+                    report.add_result(result);
+                }
+            }
+
             Constraint::QualifiedValueShape {
                 shape: ref_shape_id,
                 min_count,
                 max_count,
-                ..
+                disjoint,
             } => {
+                let siblings = if *disjoint {
+                    self.compiled
+                        .qualified_value_shape_siblings
+                        .get(&shape.id)
+                        .map_or(&[][..], Vec::as_slice)
+                } else {
+                    &[]
+                };
                 // Check each value node and propagate errors
                 let mut conforming_count = 0;
-                for v in value_nodes {
-                    if self.node_conforms_to_shape(context, v, ref_shape_id, depth + 1)? {
-                        conforming_count += 1;
+                'values: for v in value_nodes {
+                    if !self.node_conforms_to_shape(context, v, ref_shape_id, depth + 1)? {
+                        continue;
+                    }
+                    // With sh:qualifiedValueShapesDisjoint, values conforming to a sibling shape are not counted
+                    for sibling in siblings {
+                        if self.node_conforms_to_shape(context, v, sibling, depth + 1)? {
+                            continue 'values;
+                        }
                     }
+                    conforming_count += 1;
                 }
 
                 if let Some(min) = min_count {
@@ -949,6 +1169,37 @@ impl ShaclValidator {
             }
         }
 
+        // The sh:message templates of the shape replace the default messages
+        if !shape.messages.is_empty() {
+            let parameter = constraint.parameter_value();
+            for result in &mut report.results_mut()[first_result..] {
+                let path = result.result_path.as_ref().map(|path| match path {
+                    PropertyPath::Predicate(predicate) => Term::from(predicate.clone()),
+                    other => Literal::new_simple_literal(other.to_string()).into(),
+                });
+                let bindings = [
+                    ("this", Some(&result.focus_node)),
+                    ("focusNode", Some(&result.focus_node)),
+                    ("value", result.value.as_ref()),
+                    ("path", path.as_ref()),
+                    (constraint.name(), parameter.as_ref()),
+                ];
+                let messages = shape
+                    .messages
+                    .iter()
+                    .map(|template| {
+                        fill_message_template(
+                            template,
+                            bindings
+                                .iter()
+                                .filter_map(|(name, value)| Some((*name, (*value)?))),
+                        )
+                    })
+                    .collect();
+                result.result_messages = messages;
+            }
+        }
+
         Ok(())
     }
 
@@ -960,18 +1211,22 @@ impl ShaclValidator {
         shape_id: &ShapeId,
         depth: usize,
     ) -> Result<bool, ShaclError> {
-        if depth > MAX_RECURSION_DEPTH {
-            return Err(ShaclValidationError::max_recursion_depth(depth).into());
+        if depth > self.max_recursion_depth {
+            return Err(ShaclError::RecursionLimit { depth });
+        }
+
+        // The same node is often checked against the same shape from many focus nodes
+        let key = (node.clone(), shape_id.clone());
+        if let Some(conforms) = context.conformance_cache.get(&key) {
+            return Ok(*conforms);
         }
 
         // Try to find the shape
-        if let Some(node_shape) = self.shapes_graph.get_node_shape(shape_id) {
+        let conforms = if let Some(node_shape) = self.shapes_graph.get_node_shape(shape_id) {
             let mut temp_report = ValidationReport::new();
             self.validate_node_against_shape(context, &mut temp_report, node, node_shape, depth)?;
-            return Ok(temp_report.conforms());
-        }
-
-        if let Some(prop_shape) = self.shapes_graph.get_property_shape(shape_id) {
+            temp_report.conforms()
+        } else if let Some(prop_shape) = self.shapes_graph.get_property_shape(shape_id) {
             let mut temp_report = ValidationReport::new();
             self.validate_property_shape(
                 context,
@@ -981,29 +1236,149 @@ impl ShaclValidator {
                 depth,
                 prop_shape.base.severity,
             )?;
-            return Ok(temp_report.conforms());
+            temp_report.conforms()
+        } else {
+            // Shape not found - treat as conforming (or could return error)
+            true
+        };
+        context.conformance_cache.insert(key, conforms);
+        Ok(conforms)
+    }
+}
+
+/// Property paths and regular expressions compiled when the validator is created.
+#[derive(Debug, Default)]
+struct CompiledShapes {
+    paths: FxHashMap<ShapeId, CompiledPath>,
+    /// Regular expressions keyed by [`regex_key`]. Invalid ones are reported during validation.
+    regexes: FxHashMap<(String, String), Regex>,
+    /// The qualified value shapes of the sibling property shapes,
+    /// keyed by the property shapes with `sh:qualifiedValueShapesDisjoint`.
+    qualified_value_shape_siblings: FxHashMap<ShapeId, Vec<ShapeId>>,
+}
+
+impl CompiledShapes {
+    fn new(shapes_graph: &ShapesGraph) -> Self {
+        let mut compiled = Self::default();
+        for shape in shapes_graph.node_shapes() {
+            compiled.add_shape(&shape.base);
+        }
+        for shape in shapes_graph.property_shapes() {
+            compiled.add_property_shape(shape);
+        }
+        compiled
+    }
+
+    fn add_property_shape(&mut self, shape: &PropertyShape) {
+        if self.paths.contains_key(&shape.base.id) {
+            return;
         }
+        self.paths
+            .insert(shape.base.id.clone(), CompiledPath::new(&shape.path));
+        self.add_shape(&shape.base);
+    }
 
-        // Shape not found - treat as conforming (or could return error)
-        Ok(true)
+    fn add_shape(&mut self, shape: &Shape) {
+        for constraint in &shape.constraints {
+            if let Constraint::Pattern { pattern, flags } = constraint {
+                if let Entry::Vacant(entry) =
+                    self.regexes.entry(regex_key(pattern, flags.as_deref()))
+                {
+                    if let Ok(regex) = compile_regex(pattern, flags.as_deref()) {
+                        entry.insert(regex);
+                    }
+                }
+            }
+        }
+        for property_shape in &shape.property_shapes {
+            self.add_qualified_value_shape_siblings(property_shape, &shape.property_shapes);
+            self.add_property_shape(property_shape);
+        }
+    }
+
+    /// Collects the qualified value shapes of the siblings of a property shape
+    /// if it has `sh:qualifiedValueShapesDisjoint` set.
+    fn add_qualified_value_shape_siblings(
+        &mut self,
+        shape: &PropertyShape,
+        siblings: &[Arc<PropertyShape>],
+    ) {
+        let Some(qualified_value_shape) = shape.base.constraints.iter().find_map(|c| match c {
+            Constraint::QualifiedValueShape {
+                shape, disjoint, ..
+            } if *disjoint => Some(shape),
+            _ => None,
+        }) else {
+            return;
+        };
+        let shape_siblings = self
+            .qualified_value_shape_siblings
+            .entry(shape.base.id.clone())
+            .or_default();
+        for sibling in siblings {
+            if sibling.base.id == shape.base.id {
+                continue;
+            }
+            for constraint in &sibling.base.constraints {
+                if let Constraint::QualifiedValueShape { shape: id, .. } = constraint {
+                    if id != qualified_value_shape && !shape_siblings.contains(id) {
+                        shape_siblings.push(id.clone());
+                    }
+                }
+            }
+        }
     }
 }
 
 /// Internal validation context.
 struct ValidationContext<'a> {
-    #[expect(dead_code)]
     validator: &'a ShaclValidator,
-    data_graph: &'a Graph,
-    regex_cache: FxHashMap<String, Regex>,
+    data_graph: &'a dyn DataGraph,
+    regex_cache: FxHashMap<(String, String), Regex>,
+    path_cache: FxHashMap<ShapeId, CompiledPath>,
+    /// Conformance of the (node, shape) pairs already checked during this validation.
+    conformance_cache: FxHashMap<(Term, ShapeId), bool>,
+    deadline: Option<Instant>,
 }
 
 impl<'a> ValidationContext<'a> {
-    fn new(validator: &'a ShaclValidator, data_graph: &'a Graph) -> Self {
+    fn new(validator: &'a ShaclValidator, data_graph: &'a dyn DataGraph) -> Self {
         Self {
             validator,
             data_graph,
             regex_cache: FxHashMap::default(),
+            path_cache: FxHashMap::default(),
+            conformance_cache: FxHashMap::default(),
+            deadline: validator
+                .timeout
+                .and_then(|timeout| Instant::now().checked_add(timeout)),
+        }
+    }
+
+    /// Fails if the validation timeout is exceeded.
+    fn check_deadline(&self) -> Result<(), ShaclError> {
+        deadline_check(self.deadline, self.validator.timeout)
+    }
+
+    /// Evaluates the path of a property shape, compiling it on first use if the validator has not.
+    fn evaluate_path(
+        &mut self,
+        shape: &PropertyShape,
+        focus_node: &Term,
+    ) -> Result<Vec<Term>, ShaclError> {
+        if let Some(path) = self.validator.compiled.paths.get(&shape.base.id) {
+            return path.evaluate(self.data_graph, focus_node.as_ref(), &|| {
+                self.check_deadline()
+            });
         }
+        let (deadline, timeout) = (self.deadline, self.validator.timeout);
+        let path = self
+            .path_cache
+            .entry(shape.base.id.clone())
+            .or_insert_with(|| CompiledPath::new(&shape.path));
+        path.evaluate(self.data_graph, focus_node.as_ref(), &|| {
+            deadline_check(deadline, timeout)
+        })
     }
 
     fn get_or_compile_regex(
@@ -1011,33 +1386,14 @@ impl<'a> ValidationContext<'a> {
         pattern: &str,
         flags: Option<&str>,
     ) -> Result<&Regex, ShaclError> {
-        let key = format!("{}{}", pattern, flags.unwrap_or(""));
+        let key = regex_key(pattern, flags);
+        let validator = self.validator;
+        if let Some(regex) = validator.compiled.regexes.get(&key) {
+            return Ok(regex);
+        }
 
         if !self.regex_cache.contains_key(&key) {
-            let mut regex_pattern = String::new();
-
-            // Handle flags
-            if let Some(f) = flags {
-                if f.contains('i') {
-                    regex_pattern.push_str("(?i)");
-                }
-                if f.contains('m') {
-                    regex_pattern.push_str("(?m)");
-                }
-                if f.contains('s') {
-                    regex_pattern.push_str("(?s)");
-                }
-            }
-
-            regex_pattern.push_str(pattern);
-
-            let regex = Regex::new(&regex_pattern).map_err(|e| {
-                ShaclError::Parse(crate::error::ShaclParseError::invalid_regex(
-                    pattern,
-                    e.to_string(),
-                ))
-            })?;
-
+            let regex = compile_regex(pattern, flags)?;
             self.regex_cache.insert(key.clone(), regex);
         }
 
@@ -1047,26 +1403,64 @@ impl<'a> ValidationContext<'a> {
     }
 }
 
+/// Fails if `deadline` has passed, reporting the configured `timeout`.
+fn deadline_check(deadline: Option<Instant>, timeout: Option<Duration>) -> Result<(), ShaclError> {
+    match (deadline, timeout) {
+        (Some(deadline), Some(timeout)) if Instant::now() > deadline => {
+            Err(ShaclError::Timeout(timeout))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The pattern and the flags are kept apart so that e.g. `a` with flag `i` and `ai` without flags do not collide.
+fn regex_key(pattern: &str, flags: Option<&str>) -> (String, String) {
+    (pattern.to_owned(), flags.unwrap_or_default().to_owned())
+}
+
+/// Compiles a `sh:pattern` with its `sh:flags`.
+fn compile_regex(pattern: &str, flags: Option<&str>) -> Result<Regex, ShaclError> {
+    let mut regex_pattern = String::new();
+
+    // Handle flags
+    if let Some(f) = flags {
+        if f.contains('i') {
+            regex_pattern.push_str("(?i)");
+        }
+        if f.contains('m') {
+            regex_pattern.push_str("(?m)");
+        }
+        if f.contains('s') {
+            regex_pattern.push_str("(?s)");
+        }
+    }
+
+    regex_pattern.push_str(pattern);
+
+    Regex::new(&regex_pattern).map_err(|e| {
+        ShaclError::Parse(crate::error::ShaclParseError::invalid_regex(
+            pattern,
+            e.to_string(),
+        ))
+    })
+}
+
 // Helper functions
 
-fn is_instance_of(graph: &Graph, term: &Term, class: &NamedNode) -> bool {
-    match term {
-        Term::NamedNode(n) => graph
-            .objects_for_subject_predicate(n, rdf::TYPE)
-            .any(|t| match t {
-                TermRef::NamedNode(type_node) => type_node == class.as_ref(),
-                _ => false,
-            }),
-        Term::BlankNode(b) => graph
-            .objects_for_subject_predicate(b, rdf::TYPE)
-            .any(|t| match t {
-                TermRef::NamedNode(type_node) => type_node == class.as_ref(),
-                _ => false,
-            }),
-        Term::Literal(_) => false,
-        #[cfg(feature = "rdf-12")]
-        Term::Triple(_) => false,
+fn is_instance_of(
+    graph: &dyn DataGraph,
+    term: &Term,
+    class: &NamedNode,
+) -> Result<bool, ShaclError> {
+    let Some(subject) = as_subject(term.as_ref()) else {
+        return Ok(false);
+    };
+    for t in graph.objects_for_subject_predicate(subject, rdf::TYPE) {
+        if matches!(t?, Term::NamedNode(type_node) if type_node == *class) {
+            return Ok(true);
+        }
     }
+    Ok(false)
 }
 
 fn matches_node_kind(term: &Term, node_kind: NamedNodeRef<'_>) -> bool {
@@ -1101,61 +1495,138 @@ fn get_string_length(term: &Term) -> usize {
     get_string_value(term).chars().count()
 }
 
+/// Compares two terms following the ordering of their XSD datatypes.
+///
+/// Returns `None` if the terms are not comparable,
+/// e.g. IRIs, blank nodes, a number and a date or an ill-typed literal.
 fn compare_values(a: &Term, b: &Term) -> Option<Ordering> {
-    match (a, b) {
-        (Term::Literal(la), Term::Literal(lb)) => {
-            // Try numeric comparison first
-            if let (Ok(na), Ok(nb)) = (la.value().parse::<f64>(), lb.value().parse::<f64>()) {
-                return na.partial_cmp(&nb);
+    let (Term::Literal(a), Term::Literal(b)) = (a, b) else {
+        return None;
+    };
+    TypedValue::from_literal(a)?.partial_cmp(&TypedValue::from_literal(b)?)
+}
+
+/// The value of a literal with an ordered XSD datatype.
+enum TypedValue<'a> {
+    Integer(Integer),
+    Decimal(Decimal),
+    Float(Float),
+    Double(Double),
+    Boolean(Boolean),
+    String(&'a str),
+    DateTime(DateTime),
+    Date(Date),
+    Time(Time),
+    GYearMonth(GYearMonth),
+    GYear(GYear),
+}
+
+impl<'a> TypedValue<'a> {
+    fn from_literal(literal: &'a Literal) -> Option<Self> {
+        let value = literal.value();
+        let datatype = literal.datatype();
+        Some(if INTEGER_DATATYPES.contains(&datatype) {
+            match value.parse() {
+                Ok(value) => Self::Integer(value),
+                // Out of the i64 range, e.g. a large xsd:unsignedLong
+                Err(_) if is_integer_lexical_form(value) => Self::Decimal(value.parse().ok()?),
+                Err(_) => return None,
             }
-            // Fall back to string comparison
-            Some(la.value().cmp(lb.value()))
+        } else if datatype == xsd::DECIMAL {
+            Self::Decimal(value.parse().ok()?)
+        } else if datatype == xsd::FLOAT {
+            Self::Float(value.parse().ok()?)
+        } else if datatype == xsd::DOUBLE {
+            Self::Double(value.parse().ok()?)
+        } else if datatype == xsd::BOOLEAN {
+            Self::Boolean(value.parse().ok()?)
+        } else if datatype == xsd::STRING {
+            Self::String(value)
+        } else if datatype == xsd::DATE_TIME || datatype == xsd::DATE_TIME_STAMP {
+            Self::DateTime(value.parse().ok()?)
+        } else if datatype == xsd::DATE {
+            Self::Date(value.parse().ok()?)
+        } else if datatype == xsd::TIME {
+            Self::Time(value.parse().ok()?)
+        } else if datatype == xsd::G_YEAR_MONTH {
+            Self::GYearMonth(value.parse().ok()?)
+        } else if datatype == xsd::G_YEAR {
+            Self::GYear(value.parse().ok()?)
+        } else {
+            return None;
+        })
+    }
+
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => a.partial_cmp(b),
+            (Self::Integer(a), Self::Decimal(b)) => Decimal::from(*a).partial_cmp(b),
+            (Self::Decimal(a), Self::Integer(b)) => a.partial_cmp(&Decimal::from(*b)),
+            (Self::Decimal(a), Self::Decimal(b)) => a.partial_cmp(b),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::Boolean(a), Self::Boolean(b)) => a.partial_cmp(b),
+            (Self::String(a), Self::String(b)) => a.partial_cmp(b),
+            (Self::DateTime(a), Self::DateTime(b)) => a.partial_cmp(b),
+            (Self::Date(a), Self::Date(b)) => a.partial_cmp(b),
+            (Self::Time(a), Self::Time(b)) => a.partial_cmp(b),
+            (Self::GYearMonth(a), Self::GYearMonth(b)) => a.partial_cmp(b),
+            (Self::GYear(a), Self::GYear(b)) => a.partial_cmp(b),
+            // Other numeric types are promoted to double
+            _ => self.to_double()?.partial_cmp(&other.to_double()?),
         }
-        (Term::NamedNode(na), Term::NamedNode(nb)) => Some(na.as_str().cmp(nb.as_str())),
-        _ => None,
     }
-}
 
-fn get_property_values(graph: &Graph, subject: &Term, predicate: &NamedNode) -> Vec<Term> {
-    match subject {
-        Term::NamedNode(n) => graph
-            .objects_for_subject_predicate(n, predicate)
-            .map(TermRef::into_owned)
-            .collect(),
-        Term::BlankNode(b) => graph
-            .objects_for_subject_predicate(b, predicate)
-            .map(TermRef::into_owned)
-            .collect(),
-        Term::Literal(_) => Vec::new(),
-        #[cfg(feature = "rdf-12")]
-        Term::Triple(_) => Vec::new(),
+    fn to_double(&self) -> Option<Double> {
+        match self {
+            Self::Integer(value) => Some((*value).into()),
+            Self::Decimal(value) => Some((*value).into()),
+            Self::Float(value) => Some((*value).into()),
+            Self::Double(value) => Some(*value),
+            _ => None,
+        }
     }
 }
 
-struct SimpleTriple {
-    predicate: NamedNode,
-    object: Term,
+/// `xsd:integer` and its derived datatypes.
+fn is_integer_lexical_form(value: &str) -> bool {
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
 }
 
-fn get_triples_for_subject(graph: &Graph, subject: &Term) -> Vec<SimpleTriple> {
-    match subject {
-        Term::NamedNode(n) => graph
-            .triples_for_subject(n)
-            .map(|t| SimpleTriple {
-                predicate: t.predicate.into_owned(),
-                object: t.object.into_owned(),
-            })
-            .collect(),
-        Term::BlankNode(b) => graph
-            .triples_for_subject(b)
-            .map(|t| SimpleTriple {
-                predicate: t.predicate.into_owned(),
-                object: t.object.into_owned(),
-            })
-            .collect(),
-        Term::Literal(_) => Vec::new(),
-        #[cfg(feature = "rdf-12")]
-        Term::Triple(_) => Vec::new(),
+const INTEGER_DATATYPES: [NamedNodeRef<'static>; 13] = [
+    xsd::INTEGER,
+    xsd::LONG,
+    xsd::INT,
+    xsd::SHORT,
+    xsd::BYTE,
+    xsd::NON_NEGATIVE_INTEGER,
+    xsd::POSITIVE_INTEGER,
+    xsd::NON_POSITIVE_INTEGER,
+    xsd::NEGATIVE_INTEGER,
+    xsd::UNSIGNED_LONG,
+    xsd::UNSIGNED_INT,
+    xsd::UNSIGNED_SHORT,
+    xsd::UNSIGNED_BYTE,
+];
+
+fn get_property_values<'a>(
+    graph: &'a dyn DataGraph,
+    subject: &Term,
+    predicate: &NamedNode,
+) -> Box<dyn Iterator<Item = Result<Term, ShaclError>> + 'a> {
+    match as_subject(subject.as_ref()) {
+        Some(subject) => graph.objects_for_subject_predicate(subject, predicate.as_ref()),
+        None => Box::new(std::iter::empty()),
+    }
+}
+
+fn get_triples_for_subject<'a>(
+    graph: &'a dyn DataGraph,
+    subject: &Term,
+) -> Box<dyn Iterator<Item = Result<(NamedNode, Term), ShaclError>> + 'a> {
+    match as_subject(subject.as_ref()) {
+        Some(subject) => graph.predicates_objects_for_subject(subject),
+        None => Box::new(std::iter::empty()),
     }
 }
 
@@ -1,8 +1,10 @@
 //! Integration tests for SHACL validation.
 
-use oxrdf::{Dataset, Formula, Graph, GraphName, Literal, NamedNode, Triple};
+use oxrdf::{Dataset, Formula, Graph, GraphName, Literal, NamedNode, Term, Triple};
 use oxrdfio::{RdfFormat, RdfParser};
-use sparshacl::{Severity, ShaclValidator, ShapesGraph};
+use sparshacl::{
+    ConstraintComponent, Severity, ShaclError, ShaclValidator, ShapesGraph, ValidationResult,
+};
 
 /// Helper to parse a Turtle string into a Graph.
 fn parse_turtle(turtle: &str) -> Graph {
@@ -201,6 +203,77 @@ fn test_target_objects_of() {
     assert_eq!(report.violation_count(), 1);
 }
 
+#[test]
+fn test_validate_nodes() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:PersonShape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path ex:name ;
+                sh:minCount 1
+            ] .
+
+        ex:AddressShape a sh:NodeShape ;
+            sh:targetObjectsOf ex:address ;
+            sh:property [
+                sh:path ex:city ;
+                sh:minCount 1
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        ex:Student rdfs:subClassOf ex:Person .
+        ex:alice a ex:Student ; ex:address ex:addr1 .
+        ex:bob a ex:Person .
+        ex:carol a ex:Person ; ex:name "Carol" .
+        ex:addr1 ex:street "Main St" .
+        ex:addr2 ex:street "Side St" .
+    "#,
+    );
+
+    let nodes: Vec<Term> = ["alice", "carol", "addr1", "addr2", "unknown"]
+        .into_iter()
+        .map(|name| NamedNode::new_unchecked(format!("http://example.org/{name}")).into())
+        .collect();
+    let report = validator
+        .validate_nodes(&data, &nodes)
+        .expect("Validation failed");
+
+    // Same results as the full validation, without the non selected ex:bob
+    let full_report = validator.validate(&data).expect("Validation failed");
+    let summary = |results: &[ValidationResult]| {
+        results
+            .iter()
+            .filter(|result| nodes.contains(&result.focus_node))
+            .map(|result| {
+                (
+                    result.focus_node.clone(),
+                    result.source_constraint_component,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(full_report.violation_count(), 3);
+    assert_eq!(report.violation_count(), 2);
+    assert_eq!(summary(report.results()), summary(full_report.results()));
+
+    // Nodes not targeted by any shape are ignored
+    let report = validator
+        .validate_nodes(&data, &nodes[3..])
+        .expect("Validation failed");
+    assert!(report.conforms());
+}
+
 // =============================================================================
 // Cardinality constraint tests
 // =============================================================================
@@ -399,6 +472,125 @@ fn test_node_kind_literal() {
     assert_eq!(report.violation_count(), 1);
 }
 
+#[test]
+fn test_node_kind_all_kinds() {
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:withBlankNode a ex:Thing ; ex:ref [] .
+        ex:withIri a ex:Thing ; ex:ref ex:other .
+        ex:withLiteral a ex:Thing ; ex:ref "value" .
+    "#,
+    );
+
+    let cases: [(&str, &[&str]); 6] = [
+        ("IRI", &["withBlankNode", "withLiteral"]),
+        ("BlankNode", &["withIri", "withLiteral"]),
+        ("Literal", &["withBlankNode", "withIri"]),
+        ("BlankNodeOrIRI", &["withLiteral"]),
+        ("BlankNodeOrLiteral", &["withIri"]),
+        ("IRIOrLiteral", &["withBlankNode"]),
+    ];
+    for (node_kind, violating) in cases {
+        let shapes = parse_shapes(&format!(
+            r#"
+            @prefix sh: <http://www.w3.org/ns/shacl#> .
+            @prefix ex: <http://example.org/> .
+
+            ex:Shape a sh:NodeShape ;
+                sh:targetClass ex:Thing ;
+                sh:property [
+                    sh:path ex:ref ;
+                    sh:nodeKind sh:{node_kind}
+                ] .
+        "#
+        ));
+
+        let validator = ShaclValidator::new(shapes);
+        let report = validator.validate(&data).expect("Validation failed");
+
+        assert!(!report.conforms(), "sh:{node_kind}");
+        let mut focus_nodes = report
+            .results()
+            .iter()
+            .map(|result| {
+                assert_eq!(
+                    result.source_constraint_component,
+                    ConstraintComponent::NodeKind,
+                    "sh:{node_kind}"
+                );
+                result.focus_node.to_string()
+            })
+            .collect::<Vec<_>>();
+        focus_nodes.sort();
+        let expected = violating
+            .iter()
+            .map(|name| format!("<http://example.org/{name}>"))
+            .collect::<Vec<_>>();
+        assert_eq!(focus_nodes, expected, "sh:{node_kind}");
+    }
+}
+
+#[test]
+fn test_node_kind_on_node_shape() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetSubjectsOf ex:ref ;
+            sh:nodeKind sh:IRI .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:thing ex:ref ex:other .
+        [] ex:ref ex:other .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert_eq!(report.violation_count(), 1);
+    let result = &report.results()[0];
+    assert_eq!(
+        result.source_constraint_component,
+        ConstraintComponent::NodeKind
+    );
+    assert!(result.focus_node.is_blank_node());
+    assert_eq!(result.value.as_ref(), Some(&result.focus_node));
+}
+
+#[test]
+fn test_node_kind_invalid_value() {
+    let graph = parse_turtle(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Thing ;
+            sh:property [
+                sh:path ex:ref ;
+                sh:nodeKind sh:Resource
+            ] .
+    "#,
+    );
+
+    let error = ShapesGraph::from_graph(&graph).unwrap_err();
+    assert!(
+        matches!(
+            error,
+            sparshacl::ShaclParseError::InvalidPropertyValue { .. }
+        ),
+        "Expected InvalidPropertyValue error, got: {error:?}"
+    );
+}
+
 // =============================================================================
 // String constraint tests
 // =============================================================================
@@ -496,6 +688,97 @@ fn test_pattern_constraint() {
     assert_eq!(report.violation_count(), 1);
 }
 
+#[test]
+fn test_validator_reuse() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Thing ;
+            sh:property [
+                sh:path ( ex:contact ex:email ) ;
+                sh:pattern "^[a-z]+@example\\.com$" ;
+                sh:flags "i"
+            ] .
+    "#,
+    );
+
+    // A single validator, and its clones, validate many data graphs
+    let validator = ShaclValidator::new(shapes);
+    let clone = validator.clone();
+    for (email, conforms) in [
+        ("alice@example.com", true),
+        ("Bob@Example.com", true),
+        ("carol@example.org", false),
+    ] {
+        let data = parse_turtle(&format!(
+            r#"
+            @prefix ex: <http://example.org/> .
+            ex:thing a ex:Thing ; ex:contact [ ex:email "{email}" ] .
+        "#
+        ));
+        assert_eq!(validator.validate(&data).unwrap().conforms(), conforms);
+        assert_eq!(clone.validate(&data).unwrap().conforms(), conforms);
+    }
+}
+
+#[test]
+fn test_pattern_flags_are_not_merged_into_the_pattern() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Thing ;
+            sh:property [ sh:path ex:a ; sh:pattern "^a" ; sh:flags "i" ] ;
+            sh:property [ sh:path ex:b ; sh:pattern "^ai" ] .
+    "#,
+    );
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:thing a ex:Thing ; ex:a "A" ; ex:b "A" .
+    "#,
+    );
+    let report = ShaclValidator::new(shapes)
+        .validate(&data)
+        .expect("Validation failed");
+    assert_eq!(report.violation_count(), 1);
+    assert_eq!(
+        report.results()[0]
+            .result_path
+            .as_ref()
+            .and_then(|p| p.as_predicate()),
+        Some(&NamedNode::new_unchecked("http://example.org/b"))
+    );
+}
+
+#[test]
+fn test_invalid_pattern_is_reported_on_validation() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Thing ;
+            sh:property [ sh:path ex:email ; sh:pattern "(" ] .
+    "#,
+    );
+    // The validator is still built, the error is returned by the validation
+    let validator = ShaclValidator::new(shapes);
+    let data = parse_turtle(
+        r#"<http://example.org/thing> a <http://example.org/Thing> ; <http://example.org/email> "a" ."#,
+    );
+    assert!(matches!(
+        validator.validate(&data),
+        Err(ShaclError::Parse(_))
+    ));
+}
+
 // =============================================================================
 // Value range constraint tests
 // =============================================================================
@@ -566,6 +849,270 @@ fn test_max_inclusive_constraint() {
     assert_eq!(report.violation_count(), 1);
 }
 
+#[test]
+fn test_inclusive_range_uses_datatype_ordering() {
+    // (range, values within it, values outside of it)
+    let cases = [
+        (
+            "sh:minInclusive 9 ; sh:maxInclusive 150",
+            vec!["9", "10", "150", "\"99\"^^xsd:long", "9.0", "1.5e2"],
+            // Values that can't be compared with the bounds are violations
+            vec![
+                "8",
+                "151",
+                "1000",
+                "8.99",
+                "1.51e2",
+                "ex:other",
+                "[]",
+                "\"10\"",
+                "\"2024-01-09Z\"^^xsd:date",
+                "\"foo\"^^xsd:integer",
+            ],
+        ),
+        // Out of the i64 range
+        (
+            "sh:minInclusive \"9223372036854775808\"^^xsd:unsignedLong ; sh:maxInclusive \"18446744073709551615\"^^xsd:unsignedLong",
+            vec![
+                "9223372036854775808",
+                "\"18446744073709551615\"^^xsd:unsignedLong",
+                "1.8e19",
+            ],
+            vec![
+                "9223372036854775807",
+                "\"18446744073709551616\"^^xsd:unsignedLong",
+                "-18446744073709551615",
+            ],
+        ),
+        (
+            "sh:minInclusive 0.5 ; sh:maxInclusive 10.25",
+            vec!["0.5", "0.50", "9.75", "10.25", "10", "1"],
+            vec!["0.49", "10.26", "11", "0"],
+        ),
+        // Dates with a timezone compare by their starting instant
+        (
+            "sh:minInclusive \"2024-01-09Z\"^^xsd:date ; sh:maxInclusive \"2024-12-31Z\"^^xsd:date",
+            vec![
+                "\"2024-01-09Z\"^^xsd:date",
+                "\"2024-01-10Z\"^^xsd:date",
+                "\"2024-12-31Z\"^^xsd:date",
+                "\"2024-01-09-10:00\"^^xsd:date",
+            ],
+            vec![
+                "\"2024-01-08Z\"^^xsd:date",
+                "\"2025-01-01Z\"^^xsd:date",
+                "\"2024-12-31-10:00\"^^xsd:date",
+            ],
+        ),
+    ];
+
+    for (range, valid, invalid) in cases {
+        let shapes = parse_shapes(&format!(
+            "
+            @prefix sh: <http://www.w3.org/ns/shacl#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+            @prefix ex: <http://example.org/> .
+
+            ex:Shape a sh:NodeShape ;
+                sh:targetClass ex:Thing ;
+                sh:property [ sh:path ex:value ; {range} ] .
+        "
+        ));
+        let validator = ShaclValidator::new(shapes);
+        for (value, conforms) in valid
+            .iter()
+            .map(|value| (value, true))
+            .chain(invalid.iter().map(|value| (value, false)))
+        {
+            let data = parse_turtle(&format!(
+                "
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+                @prefix ex: <http://example.org/> .
+                ex:thing a ex:Thing ; ex:value {value} .
+            "
+            ));
+            let report = validator.validate(&data).expect("Validation failed");
+            assert_eq!(report.conforms(), conforms, "{value} in {range}");
+        }
+    }
+}
+
+// =============================================================================
+// Property pair constraint tests
+// =============================================================================
+
+/// Returns the sorted (focus node local name, constraint component, value) of the results.
+fn result_summary(results: &[ValidationResult]) -> Vec<(String, ConstraintComponent, String)> {
+    let mut summary = results
+        .iter()
+        .map(|result| {
+            let focus_node = result
+                .focus_node
+                .to_string()
+                .trim_start_matches("<http://example.org/")
+                .trim_end_matches('>')
+                .to_owned();
+            let value = result
+                .value
+                .as_ref()
+                .map(|value| match value {
+                    Term::Literal(literal) => literal.value().to_owned(),
+                    other => other.to_string(),
+                })
+                .unwrap_or_default();
+            (focus_node, result.source_constraint_component, value)
+        })
+        .collect::<Vec<_>>();
+    summary.sort_by(|a, b| (&a.0, a.1.iri(), &a.2).cmp(&(&b.0, b.1.iri(), &b.2)));
+    summary
+}
+
+#[test]
+fn test_less_than_date_range() {
+    let shapes = parse_shapes(
+        "
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:EventShape a sh:NodeShape ;
+            sh:targetClass ex:Event ;
+            sh:property [ sh:path ex:startDate ; sh:lessThan ex:endDate ] ;
+            sh:property [ sh:path ex:startDate ; sh:lessThanOrEquals ex:endDate ] .
+    ",
+    );
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+        @prefix ex: <http://example.org/> .
+        ex:ordered a ex:Event ;
+            ex:startDate "2024-01-09"^^xsd:date ; ex:endDate "2024-01-10"^^xsd:date .
+        ex:sameDay a ex:Event ;
+            ex:startDate "2024-01-10"^^xsd:date ; ex:endDate "2024-01-10"^^xsd:date .
+        ex:reversed a ex:Event ;
+            ex:startDate "2024-02-01"^^xsd:date ; ex:endDate "2024-01-31"^^xsd:date .
+        ex:twoEnds a ex:Event ;
+            ex:startDate "2024-03-10"^^xsd:date ;
+            ex:endDate "2024-03-09"^^xsd:date , "2024-03-11"^^xsd:date .
+        ex:noEnd a ex:Event ; ex:startDate "2024-04-01"^^xsd:date .
+        ex:notADate a ex:Event ; ex:startDate "2024-05-01"^^xsd:date ; ex:endDate "soon" .
+        ex:iris a ex:Event ; ex:startDate ex:a ; ex:endDate ex:b .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert_eq!(
+        result_summary(report.results()),
+        [
+            // IRIs are not comparable
+            (
+                "iris".to_owned(),
+                ConstraintComponent::LessThan,
+                "<http://example.org/a>".to_owned()
+            ),
+            (
+                "iris".to_owned(),
+                ConstraintComponent::LessThanOrEquals,
+                "<http://example.org/a>".to_owned()
+            ),
+            (
+                "notADate".to_owned(),
+                ConstraintComponent::LessThan,
+                "2024-05-01".to_owned()
+            ),
+            (
+                "notADate".to_owned(),
+                ConstraintComponent::LessThanOrEquals,
+                "2024-05-01".to_owned()
+            ),
+            (
+                "reversed".to_owned(),
+                ConstraintComponent::LessThan,
+                "2024-02-01".to_owned()
+            ),
+            (
+                "reversed".to_owned(),
+                ConstraintComponent::LessThanOrEquals,
+                "2024-02-01".to_owned()
+            ),
+            (
+                "sameDay".to_owned(),
+                ConstraintComponent::LessThan,
+                "2024-01-10".to_owned()
+            ),
+            (
+                "twoEnds".to_owned(),
+                ConstraintComponent::LessThan,
+                "2024-03-10".to_owned()
+            ),
+            (
+                "twoEnds".to_owned(),
+                ConstraintComponent::LessThanOrEquals,
+                "2024-03-10".to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_equals_and_disjoint() {
+    let shapes = parse_shapes(
+        "
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:PersonShape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [ sh:path ex:name ; sh:equals ex:label ] ;
+            sh:property [ sh:path ex:name ; sh:disjoint ex:nickname ] .
+    ",
+    );
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Person ; ex:name "Alice" , "Ally" ; ex:label "Ally" , "Alice" ; ex:nickname "Al" .
+        ex:bob a ex:Person ; ex:name "Bob" , "Robert" ; ex:label "Bob" .
+        ex:carol a ex:Person ; ex:name "Carol" ; ex:label "Caroline" .
+        ex:dave a ex:Person ; ex:name "Dave" ; ex:nickname "Dave" .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert_eq!(
+        result_summary(report.results()),
+        [
+            (
+                "bob".to_owned(),
+                ConstraintComponent::Equals,
+                "Robert".to_owned()
+            ),
+            (
+                "carol".to_owned(),
+                ConstraintComponent::Equals,
+                "Carol".to_owned()
+            ),
+            (
+                "carol".to_owned(),
+                ConstraintComponent::Equals,
+                "Caroline".to_owned()
+            ),
+            (
+                "dave".to_owned(),
+                ConstraintComponent::Disjoint,
+                "Dave".to_owned()
+            ),
+            (
+                "dave".to_owned(),
+                ConstraintComponent::Equals,
+                "Dave".to_owned()
+            ),
+        ]
+    );
+}
+
 // =============================================================================
 // Value enumeration constraint tests
 // =============================================================================
@@ -706,6 +1253,59 @@ fn test_info_severity() {
     assert_eq!(report.info_count(), 1);
 }
 
+#[test]
+fn test_results_with_severity() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Thing ;
+            sh:property [
+                sh:path ex:label ;
+                sh:minCount 1 ;
+                sh:severity sh:Warning
+            ] ;
+            sh:property [
+                sh:path ex:label ;
+                sh:maxCount 1
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:thing1 a ex:Thing .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert!(report.conforms()); // The warning is reported without failing the validation
+    let warnings: Vec<_> = report.results_with_severity(Severity::Warning).collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].source_constraint_component,
+        ConstraintComponent::MinCount
+    );
+    assert_eq!(report.results_with_severity(Severity::Violation).count(), 0);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:thing1 a ex:Thing ; ex:label "a", "b" .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert!(!report.conforms());
+    assert_eq!(report.results_with_severity(Severity::Warning).count(), 0);
+    assert_eq!(report.results_with_severity(Severity::Violation).count(), 1);
+}
+
 // =============================================================================
 // Validation report tests
 // =============================================================================
@@ -751,6 +1351,78 @@ fn test_validation_report_to_graph() {
     assert!(has_report);
 }
 
+#[cfg(feature = "turtle")]
+#[test]
+fn test_validation_report_to_turtle() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path [ sh:inversePath ex:child ] ;
+                sh:datatype xsd:string
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Person .
+        ex:bob ex:child ex:alice .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    let turtle = report.to_turtle().expect("Serialization failed");
+    let parsed = parse_turtle(&turtle);
+    assert_eq!(parsed.len(), report.to_graph().len());
+
+    let sh = |name: &str| NamedNode::new_unchecked(format!("http://www.w3.org/ns/shacl#{name}"));
+    let object = |subject: &oxrdf::NamedOrBlankNode, predicate: &NamedNode| {
+        parsed
+            .object_for_subject_predicate(subject, predicate)
+            .map(oxrdf::TermRef::into_owned)
+    };
+    let report_node = parsed
+        .subject_for_predicate_object(&sh("conforms"), &Literal::from(false))
+        .expect("sh:conforms false")
+        .into_owned();
+    let Some(Term::BlankNode(result)) = object(&report_node, &sh("result")) else {
+        panic!("sh:result expected")
+    };
+    let result = result.into();
+    assert_eq!(
+        object(&result, &sh("focusNode")),
+        Some(NamedNode::new_unchecked("http://example.org/alice").into())
+    );
+    assert_eq!(
+        object(&result, &sh("value")),
+        Some(NamedNode::new_unchecked("http://example.org/bob").into())
+    );
+    assert_eq!(
+        object(&result, &sh("sourceConstraintComponent")),
+        Some(sh("DatatypeConstraintComponent").into())
+    );
+    assert_eq!(
+        object(&result, &sh("resultSeverity")),
+        Some(sh("Violation").into())
+    );
+    let Some(Term::BlankNode(path)) = object(&result, &sh("resultPath")) else {
+        panic!("sh:resultPath expected")
+    };
+    assert_eq!(
+        object(&path.into(), &sh("inversePath")),
+        Some(NamedNode::new_unchecked("http://example.org/child").into())
+    );
+}
+
 #[test]
 fn test_validation_result_properties() {
     let shapes = parse_shapes(
@@ -790,6 +1462,83 @@ fn test_validation_result_properties() {
     assert_eq!(result.result_severity, Severity::Violation);
 }
 
+#[test]
+fn test_message_templates() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path ex:name ;
+                sh:minCount 1 ;
+                sh:message "{$this} needs {$minCount} value(s) for {?path}"@en ,
+                    "{$this} a besoin de {$minCount} valeur(s) pour {?path}"@fr
+            ] ;
+            sh:property [
+                sh:path ex:age ;
+                sh:datatype xsd:integer
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Person ; ex:age "old" .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    let results = report.results();
+    assert_eq!(results.len(), 2);
+
+    // All the sh:message values are filled
+    let min_count = results
+        .iter()
+        .find(|r| r.source_constraint_component == ConstraintComponent::MinCount)
+        .unwrap();
+    let mut messages = min_count.result_messages.clone();
+    messages.sort_by(|a, b| a.language().cmp(&b.language()));
+    assert_eq!(
+        messages,
+        [
+            Literal::new_language_tagged_literal_unchecked(
+                "<http://example.org/alice> needs 1 value(s) for <http://example.org/name>",
+                "en"
+            ),
+            Literal::new_language_tagged_literal_unchecked(
+                "<http://example.org/alice> a besoin de 1 valeur(s) pour <http://example.org/name>",
+                "fr"
+            ),
+        ]
+    );
+
+    // Without sh:message, the default message of the constraint component is used
+    let datatype = results
+        .iter()
+        .find(|r| r.source_constraint_component == ConstraintComponent::Datatype)
+        .unwrap();
+    assert_eq!(datatype.result_messages.len(), 1);
+    assert!(datatype.message().is_some());
+    #[expect(deprecated)]
+    let result_message = datatype.result_message();
+    assert_eq!(result_message, datatype.message());
+
+    // The report graph contains all the messages
+    let report_graph = report.to_graph();
+    let message_count = report_graph
+        .iter()
+        .filter(|t| t.predicate == oxrdf::vocab::shacl::RESULT_MESSAGE)
+        .count();
+    assert_eq!(message_count, 3);
+}
+
 // =============================================================================
 // Property path tests
 // =============================================================================
@@ -902,7 +1651,226 @@ fn test_sequence_path_multiple_hops() {
 }
 
 #[test]
-fn test_alternative_path() {
+fn test_alternative_path() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path [ sh:alternativePath ( ex:email ex:phone ) ] ;
+                sh:minCount 1
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Person ;
+            ex:email "alice@example.com" .
+        ex:bob a ex:Person ;
+            ex:phone "555-1234" .
+        ex:carol a ex:Person ;
+            ex:name "Carol" .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert!(!report.conforms());
+    // carol has neither email nor phone
+    assert_eq!(report.violation_count(), 1);
+}
+
+#[test]
+fn test_alternative_path_multiple_values() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Product ;
+            sh:property [
+                sh:path [ sh:alternativePath ( ex:label ex:name ex:title ) ] ;
+                sh:minLength 3
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:prod1 a ex:Product ;
+            ex:label "Widget" .
+        ex:prod2 a ex:Product ;
+            ex:name "Gadget" .
+        ex:prod3 a ex:Product ;
+            ex:title "AB" .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert!(!report.conforms());
+    // prod3's title is too short
+    assert_eq!(report.violation_count(), 1);
+}
+
+#[test]
+fn test_zero_or_more_path() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path [ sh:zeroOrMorePath ex:parent ] ;
+                sh:maxCount 5
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Person ;
+            ex:parent ex:bob .
+        ex:bob ex:parent ex:carol .
+        ex:carol ex:parent ex:dave .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert!(report.conforms());
+    // alice has ancestors: bob, carol, dave (and alice itself via zero-or-more)
+}
+
+#[test]
+fn test_one_or_more_path() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Employee ;
+            sh:property [
+                sh:path [ sh:oneOrMorePath ex:manager ] ;
+                sh:minCount 1
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Employee ;
+            ex:manager ex:bob .
+        ex:bob ex:manager ex:ceo .
+        ex:carol a ex:Employee .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert!(!report.conforms());
+    // carol has no manager (one-or-more requires at least one)
+    assert_eq!(report.violation_count(), 1);
+}
+
+#[test]
+fn test_one_or_more_path_ancestors() {
+    let shapes = parse_shapes(
+        "
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path [ sh:oneOrMorePath ex:parent ] ;
+                sh:minCount 3
+            ] .
+    ",
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    // The ancestors of dave are carol, bob and alice. alice and bob are each other parent.
+    let data = parse_turtle(
+        "
+        @prefix ex: <http://example.org/> .
+        ex:dave a ex:Person ; ex:parent ex:carol .
+        ex:carol a ex:Person ; ex:parent ex:bob .
+        ex:bob a ex:Person ; ex:parent ex:alice .
+        ex:alice a ex:Person ; ex:parent ex:bob .
+        ex:eve a ex:Person ; ex:parent ex:frank .
+    ",
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    // Only dave has three ancestors, the cycle between alice and bob gives them two
+    let mut focus_nodes = report
+        .results()
+        .iter()
+        .map(|result| result.focus_node.to_string())
+        .collect::<Vec<_>>();
+    focus_nodes.sort();
+    assert_eq!(
+        focus_nodes,
+        [
+            "<http://example.org/alice>",
+            "<http://example.org/bob>",
+            "<http://example.org/carol>",
+            "<http://example.org/eve>"
+        ]
+    );
+}
+
+#[test]
+fn test_zero_or_one_path() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:Shape a sh:NodeShape ;
+            sh:targetNode ex:doc1 ;
+            sh:property [
+                sh:path [ sh:zeroOrOnePath ex:author ] ;
+                sh:minCount 1
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:doc1 ex:title "Document" .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    // Zero-or-one path includes the node itself (zero hops)
+    // so minCount is satisfied even without ex:author
+    assert!(report.conforms());
+}
+
+#[test]
+fn test_combined_sequence_and_alternative_path() {
     let shapes = parse_shapes(
         r#"
         @prefix sh: <http://www.w3.org/ns/shacl#> .
@@ -911,7 +1879,7 @@ fn test_alternative_path() {
         ex:Shape a sh:NodeShape ;
             sh:targetClass ex:Person ;
             sh:property [
-                sh:path [ sh:alternativePath ( ex:email ex:phone ) ] ;
+                sh:path ( ex:address [ sh:alternativePath ( ex:city ex:town ) ] ) ;
                 sh:minCount 1
             ] .
     "#,
@@ -923,34 +1891,43 @@ fn test_alternative_path() {
         r#"
         @prefix ex: <http://example.org/> .
         ex:alice a ex:Person ;
-            ex:email "alice@example.com" .
+            ex:address ex:addr1 .
+        ex:addr1 ex:city "New York" .
         ex:bob a ex:Person ;
-            ex:phone "555-1234" .
+            ex:address ex:addr2 .
+        ex:addr2 ex:town "Springfield" .
         ex:carol a ex:Person ;
-            ex:name "Carol" .
+            ex:address ex:addr3 .
+        ex:addr3 ex:street "Main St" .
     "#,
     );
 
     let report = validator.validate(&data).expect("Validation failed");
     assert!(!report.conforms());
-    // carol has neither email nor phone
+    // carol's address has neither city nor town
     assert_eq!(report.violation_count(), 1);
 }
 
 #[test]
-fn test_alternative_path_multiple_values() {
+fn test_nested_sequence_and_alternative_paths() {
     let shapes = parse_shapes(
-        r#"
+        "
         @prefix sh: <http://www.w3.org/ns/shacl#> .
         @prefix ex: <http://example.org/> .
 
         ex:Shape a sh:NodeShape ;
-            sh:targetClass ex:Product ;
+            sh:targetClass ex:Person ;
+            # The people knowing a person must all live in the same country
             sh:property [
-                sh:path [ sh:alternativePath ( ex:label ex:name ex:title ) ] ;
-                sh:minLength 3
+                sh:path ( [ sh:inversePath ex:knows ] ex:address ex:country ) ;
+                sh:maxCount 1
+            ] ;
+            # A person must be reachable by email, directly or through their address
+            sh:property [
+                sh:path [ sh:alternativePath ( ex:email ( ex:address ex:email ) ) ] ;
+                sh:minCount 1
             ] .
-    "#,
+    ",
     );
 
     let validator = ShaclValidator::new(shapes);
@@ -958,33 +1935,52 @@ fn test_alternative_path_multiple_values() {
     let data = parse_turtle(
         r#"
         @prefix ex: <http://example.org/> .
-        ex:prod1 a ex:Product ;
-            ex:label "Widget" .
-        ex:prod2 a ex:Product ;
-            ex:name "Gadget" .
-        ex:prod3 a ex:Product ;
-            ex:title "AB" .
+        ex:alice a ex:Person ; ex:email "alice@example.com" ; ex:knows ex:carol ;
+            ex:address [ ex:country ex:France ] .
+        ex:bob a ex:Person ; ex:knows ex:carol ;
+            ex:address [ ex:country ex:Germany ; ex:email "bob@example.com" ] .
+        ex:carol a ex:Person ; ex:knows ex:alice .
     "#,
     );
 
     let report = validator.validate(&data).expect("Validation failed");
     assert!(!report.conforms());
-    // prod3's title is too short
-    assert_eq!(report.violation_count(), 1);
+    // carol is known by people from two countries and has no email
+    assert_eq!(report.violation_count(), 2);
+    let carol = Term::from(NamedNode::new("http://example.org/carol").unwrap());
+    assert!(
+        report
+            .results()
+            .iter()
+            .all(|result| result.focus_node == carol)
+    );
 }
 
+// =============================================================================
+// SPARQL constraint tests
+// =============================================================================
+
+#[cfg(feature = "sparql")]
 #[test]
-fn test_zero_or_more_path() {
+fn test_sparql_constraint() {
     let shapes = parse_shapes(
         r#"
         @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
         @prefix ex: <http://example.org/> .
 
-        ex:Shape a sh:NodeShape ;
+        ex:PersonShape a sh:NodeShape ;
             sh:targetClass ex:Person ;
-            sh:property [
-                sh:path [ sh:zeroOrMorePath ex:parent ] ;
-                sh:maxCount 5
+            sh:sparql [
+                sh:message "Birth date {?value} is in the future" ;
+                sh:prefixes [ sh:declare [ sh:prefix "ex" ; sh:namespace "http://example.org/"^^xsd:anyURI ] ] ;
+                sh:select """
+                    SELECT $this ?value ?path WHERE {
+                        $this ex:birthDate ?value .
+                        BIND(ex:birthDate AS ?path)
+                        FILTER (?value > NOW())
+                    }
+                """
             ] .
     "#,
     );
@@ -993,31 +1989,65 @@ fn test_zero_or_more_path() {
 
     let data = parse_turtle(
         r#"
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
         @prefix ex: <http://example.org/> .
-        ex:alice a ex:Person ;
-            ex:parent ex:bob .
-        ex:bob ex:parent ex:carol .
-        ex:carol ex:parent ex:dave .
+        ex:alice a ex:Person ; ex:birthDate "1990-05-01T00:00:00Z"^^xsd:dateTime .
+        ex:bob a ex:Person ; ex:birthDate "2999-01-01T00:00:00Z"^^xsd:dateTime .
     "#,
     );
 
     let report = validator.validate(&data).expect("Validation failed");
-    assert!(report.conforms());
-    // alice has ancestors: bob, carol, dave (and alice itself via zero-or-more)
+    assert_eq!(report.violation_count(), 1);
+    let result = &report.results()[0];
+    assert_eq!(
+        result.focus_node,
+        NamedNode::new_unchecked("http://example.org/bob").into()
+    );
+    assert_eq!(
+        result.source_constraint_component,
+        ConstraintComponent::Sparql
+    );
+    assert_eq!(
+        result.value,
+        Some(
+            Literal::new_typed_literal("2999-01-01T00:00:00Z", oxrdf::vocab::xsd::DATE_TIME).into()
+        )
+    );
+    assert_eq!(
+        result.result_path.as_ref().and_then(|p| p.as_predicate()),
+        Some(&NamedNode::new_unchecked("http://example.org/birthDate"))
+    );
+    assert_eq!(
+        result.message(),
+        Some("Birth date 2999-01-01T00:00:00Z is in the future")
+    );
 }
 
+#[cfg(feature = "sparql")]
 #[test]
-fn test_one_or_more_path() {
+fn test_sparql_constraint_pre_binding() {
     let shapes = parse_shapes(
         r#"
         @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
         @prefix ex: <http://example.org/> .
 
-        ex:Shape a sh:NodeShape ;
-            sh:targetClass ex:Employee ;
+        ex:PersonShape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
             sh:property [
-                sh:path [ sh:oneOrMorePath ex:manager ] ;
-                sh:minCount 1
+                sh:path ex:knows ;
+                sh:sparql [
+                    sh:prefixes [ sh:declare [ sh:prefix "ex" ; sh:namespace "http://example.org/"^^xsd:anyURI ] ] ;
+                    sh:select """
+                        SELECT ?value WHERE {
+                            $this $PATH ?value .
+                            FILTER NOT EXISTS { ?value a ex:Person }
+                        }
+                    """
+                ]
+            ] ;
+            sh:sparql [
+                sh:select "SELECT ?value WHERE { BIND($currentShape AS ?value) FILTER(isBlank($this)) }"
             ] .
     "#,
     );
@@ -1027,87 +2057,113 @@ fn test_one_or_more_path() {
     let data = parse_turtle(
         r#"
         @prefix ex: <http://example.org/> .
-        ex:alice a ex:Employee ;
-            ex:manager ex:bob .
-        ex:bob ex:manager ex:ceo .
-        ex:carol a ex:Employee .
+        ex:alice a ex:Person ; ex:knows ex:bob, ex:rex .
+        ex:bob a ex:Person .
+        [] a ex:Person .
     "#,
     );
 
+    // $this and $currentShape are bound even if they are not projected
     let report = validator.validate(&data).expect("Validation failed");
-    assert!(!report.conforms());
-    // carol has no manager (one-or-more requires at least one)
-    assert_eq!(report.violation_count(), 1);
+    assert_eq!(report.violation_count(), 2);
+    let alice = report
+        .results()
+        .iter()
+        .find(|r| r.focus_node == NamedNode::new_unchecked("http://example.org/alice").into())
+        .expect("alice should be reported");
+    assert_eq!(
+        alice.value,
+        Some(NamedNode::new_unchecked("http://example.org/rex").into())
+    );
+    assert_eq!(
+        alice.result_path.as_ref().and_then(|p| p.as_predicate()),
+        Some(&NamedNode::new_unchecked("http://example.org/knows"))
+    );
+    let blank = report
+        .results()
+        .iter()
+        .find(|r| r.focus_node.is_blank_node())
+        .expect("The blank node should be reported");
+    assert_eq!(
+        blank.value,
+        Some(NamedNode::new_unchecked("http://example.org/PersonShape").into())
+    );
 }
 
+#[cfg(feature = "sparql")]
 #[test]
-fn test_zero_or_one_path() {
+fn test_sparql_constraint_failure() {
     let shapes = parse_shapes(
         r#"
         @prefix sh: <http://www.w3.org/ns/shacl#> .
         @prefix ex: <http://example.org/> .
 
-        ex:Shape a sh:NodeShape ;
-            sh:targetNode ex:doc1 ;
-            sh:property [
-                sh:path [ sh:zeroOrOnePath ex:author ] ;
-                sh:minCount 1
-            ] .
+        ex:PersonShape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:sparql [ sh:select "SELECT $this ?failure WHERE { BIND(true AS ?failure) }" ] .
     "#,
     );
-
-    let validator = ShaclValidator::new(shapes);
-
     let data = parse_turtle(
         r#"
         @prefix ex: <http://example.org/> .
-        ex:doc1 ex:title "Document" .
+        ex:alice a ex:Person .
     "#,
     );
+    let error = ShaclValidator::new(shapes).validate(&data).unwrap_err();
+    assert!(
+        matches!(
+            error,
+            ShaclError::Validation(sparshacl::ShaclValidationError::SparqlError { .. })
+        ),
+        "Expected SparqlError, got: {error:?}"
+    );
+}
 
-    let report = validator.validate(&data).expect("Validation failed");
-    // Zero-or-one path includes the node itself (zero hops)
-    // so minCount is satisfied even without ex:author
-    assert!(report.conforms());
+#[cfg(feature = "sparql")]
+#[test]
+fn test_sparql_constraint_unsupported_variables() {
+    for select in [
+        "SELECT $this WHERE { GRAPH $shapesGraph { $this ?p ?o } }",
+        "SELECT $this WHERE { $this $PATH ?o }",
+        "SELECT $this WHERE { BIND(1 AS $this) }",
+    ] {
+        let graph = parse_turtle(&format!(
+            r#"
+            @prefix sh: <http://www.w3.org/ns/shacl#> .
+            @prefix ex: <http://example.org/> .
+
+            ex:PersonShape a sh:NodeShape ;
+                sh:targetClass ex:Person ;
+                sh:sparql [ sh:select "{select}" ] .
+            "#
+        ));
+        let error = ShapesGraph::from_graph(&graph).unwrap_err();
+        assert!(
+            matches!(error, sparshacl::ShaclParseError::InvalidShape { .. }),
+            "Expected InvalidShape error for {select}, got: {error:?}"
+        );
+    }
 }
 
+#[cfg(feature = "sparql")]
 #[test]
-fn test_combined_sequence_and_alternative_path() {
-    let shapes = parse_shapes(
+fn test_sparql_constraint_invalid_query() {
+    let graph = parse_turtle(
         r#"
         @prefix sh: <http://www.w3.org/ns/shacl#> .
         @prefix ex: <http://example.org/> .
 
-        ex:Shape a sh:NodeShape ;
+        ex:PersonShape a sh:NodeShape ;
             sh:targetClass ex:Person ;
-            sh:property [
-                sh:path ( ex:address [ sh:alternativePath ( ex:city ex:town ) ] ) ;
-                sh:minCount 1
-            ] .
+            sh:sparql [ sh:select "SELECT $this WHERE { $this ex:name ?name }" ] .
     "#,
     );
-
-    let validator = ShaclValidator::new(shapes);
-
-    let data = parse_turtle(
-        r#"
-        @prefix ex: <http://example.org/> .
-        ex:alice a ex:Person ;
-            ex:address ex:addr1 .
-        ex:addr1 ex:city "New York" .
-        ex:bob a ex:Person ;
-            ex:address ex:addr2 .
-        ex:addr2 ex:town "Springfield" .
-        ex:carol a ex:Person ;
-            ex:address ex:addr3 .
-        ex:addr3 ex:street "Main St" .
-    "#,
+    // The ex: prefix is not declared with sh:prefixes
+    let error = ShapesGraph::from_graph(&graph).unwrap_err();
+    assert!(
+        matches!(error, sparshacl::ShaclParseError::InvalidShape { .. }),
+        "Expected InvalidShape error, got: {error:?}"
     );
-
-    let report = validator.validate(&data).expect("Validation failed");
-    assert!(!report.conforms());
-    // carol's address has neither city nor town
-    assert_eq!(report.violation_count(), 1);
 }
 
 // =============================================================================
@@ -1255,6 +2311,56 @@ fn test_nested_property_shapes() {
     assert!(report.violation_count() >= 1);
 }
 
+#[test]
+fn test_shared_node_shape_target() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:PersonShape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path ex:employer ;
+                sh:node ex:CompanyShape
+            ] .
+
+        ex:CompanyShape a sh:NodeShape ;
+            sh:property [
+                sh:path ex:name ;
+                sh:minCount 1
+            ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    // Every person points to the same company
+    let data = |company: &str| {
+        parse_turtle(&format!(
+            r#"
+            @prefix ex: <http://example.org/> .
+            ex:alice a ex:Person ; ex:employer ex:acme .
+            ex:bob a ex:Person ; ex:employer ex:acme .
+            ex:carol a ex:Person ; ex:employer ex:acme .
+            {company}
+        "#
+        ))
+    };
+
+    let report = validator
+        .validate(&data(r#"ex:acme ex:name "ACME" ."#))
+        .expect("Validation failed");
+    assert!(report.conforms());
+
+    // The conformance of ex:acme is not reused across validations
+    let report = validator
+        .validate(&data(r#"ex:acme ex:city "Paris" ."#))
+        .expect("Validation failed");
+    assert!(!report.conforms());
+    assert_eq!(report.violation_count(), 3);
+}
+
 #[test]
 fn test_has_value_constraint() {
     let shapes = parse_shapes(
@@ -1764,6 +2870,109 @@ fn test_qualified_value_shape_with_additional_constraint() {
     assert_eq!(report.violation_count(), 0);
 }
 
+#[test]
+fn test_qualified_value_shape_exact_count() {
+    // The qualified value shape is only referenced, it is not declared as a sh:NodeShape
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+
+        ex:PersonShape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path ex:address ;
+                sh:qualifiedValueShape ex:PostalAddressShape ;
+                sh:qualifiedMinCount 2 ;
+                sh:qualifiedMaxCount 2
+            ] .
+
+        ex:PostalAddressShape sh:property [
+            sh:path ex:postalCode ;
+            sh:minCount 1
+        ] .
+    "#,
+    );
+
+    let validator = ShaclValidator::new(shapes);
+
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Person ;
+            ex:address ex:home , ex:work .
+        ex:bob a ex:Person ;
+            ex:address ex:flat , ex:boat .
+        ex:home ex:postalCode "75001" .
+        ex:work ex:postalCode "69001" .
+        ex:flat ex:postalCode "13001" .
+        ex:boat ex:name "Moored somewhere" .
+    "#,
+    );
+
+    let report = validator.validate(&data).expect("Validation failed");
+    assert!(!report.conforms());
+    assert_eq!(report.violation_count(), 1);
+    let result = &report.results()[0];
+    assert_eq!(
+        result.focus_node,
+        Term::from(NamedNode::new_unchecked("http://example.org/bob"))
+    );
+    assert_eq!(
+        result.source_constraint_component,
+        ConstraintComponent::QualifiedValueShape
+    );
+}
+
+#[test]
+fn test_qualified_value_shapes_disjoint() {
+    let shapes = |disjoint: bool| {
+        parse_shapes(&format!(
+            r#"
+            @prefix sh: <http://www.w3.org/ns/shacl#> .
+            @prefix ex: <http://example.org/> .
+
+            ex:PersonShape a sh:NodeShape ;
+                sh:targetClass ex:Person ;
+                sh:property [
+                    sh:path ex:address ;
+                    sh:qualifiedValueShape ex:PostalAddressShape ;
+                    sh:qualifiedMinCount 1 ;
+                    sh:qualifiedValueShapesDisjoint {disjoint}
+                ] , [
+                    sh:path ex:address ;
+                    sh:qualifiedValueShape ex:ShippingAddressShape ;
+                    sh:qualifiedMinCount 1 ;
+                    sh:qualifiedValueShapesDisjoint {disjoint}
+                ] .
+
+            ex:PostalAddressShape sh:property [ sh:path ex:postalCode ; sh:minCount 1 ] .
+            ex:ShippingAddressShape sh:property [ sh:path ex:deliveryNote ; sh:minCount 1 ] .
+        "#
+        ))
+    };
+
+    // A single address conforming to both shapes
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Person ; ex:address ex:home .
+        ex:home ex:postalCode "75001" ; ex:deliveryNote "Ring twice" .
+    "#,
+    );
+
+    let report = ShaclValidator::new(shapes(false))
+        .validate(&data)
+        .expect("Validation failed");
+    assert!(report.conforms());
+
+    let report = ShaclValidator::new(shapes(true))
+        .validate(&data)
+        .expect("Validation failed");
+    assert!(!report.conforms());
+    assert_eq!(report.violation_count(), 2);
+}
+
 // =============================================================================
 // Security and validation error tests
 // =============================================================================
@@ -2013,6 +3222,130 @@ fn test_negative_max_length_rejected() {
     );
 }
 
+#[test]
+fn test_results_are_sorted() {
+    let shapes = parse_shapes(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        ex:PersonShape a sh:NodeShape ;
+            sh:targetClass ex:Person ;
+            sh:property [
+                sh:path ex:name ;
+                sh:minCount 1 ;
+                sh:datatype xsd:string
+            ] , [
+                sh:path ex:age ;
+                sh:maxCount 1 ;
+                sh:datatype xsd:integer
+            ] .
+    "#,
+    );
+    let data = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:dave a ex:Person ; ex:name 1 ; ex:age 1, 2 .
+        ex:carol a ex:Person ; ex:age "old" .
+        ex:bob a ex:Person ; ex:name 2 .
+        ex:alice a ex:Person ; ex:age "young", "older" .
+    "#,
+    );
+    let validator = ShaclValidator::new(shapes);
+
+    let key = |result: &ValidationResult| {
+        (
+            result.focus_node.to_string(),
+            result.result_path.as_ref().map(ToString::to_string),
+            result.source_constraint_component.iri().as_str().to_owned(),
+        )
+    };
+    let report = validator.validate(&data).expect("Validation failed");
+    let results = report.results().iter().map(key).collect::<Vec<_>>();
+    assert!(results.is_sorted(), "{results:?}");
+    assert_eq!(
+        results,
+        [
+            (
+                "<http://example.org/alice>",
+                Some("<http://example.org/age>"),
+                "http://www.w3.org/ns/shacl#DatatypeConstraintComponent",
+            ),
+            (
+                "<http://example.org/alice>",
+                Some("<http://example.org/age>"),
+                "http://www.w3.org/ns/shacl#DatatypeConstraintComponent",
+            ),
+            (
+                "<http://example.org/alice>",
+                Some("<http://example.org/age>"),
+                "http://www.w3.org/ns/shacl#MaxCountConstraintComponent",
+            ),
+            (
+                "<http://example.org/alice>",
+                Some("<http://example.org/name>"),
+                "http://www.w3.org/ns/shacl#MinCountConstraintComponent",
+            ),
+            (
+                "<http://example.org/bob>",
+                Some("<http://example.org/name>"),
+                "http://www.w3.org/ns/shacl#DatatypeConstraintComponent",
+            ),
+            (
+                "<http://example.org/carol>",
+                Some("<http://example.org/age>"),
+                "http://www.w3.org/ns/shacl#DatatypeConstraintComponent",
+            ),
+            (
+                "<http://example.org/carol>",
+                Some("<http://example.org/name>"),
+                "http://www.w3.org/ns/shacl#MinCountConstraintComponent",
+            ),
+            (
+                "<http://example.org/dave>",
+                Some("<http://example.org/age>"),
+                "http://www.w3.org/ns/shacl#MaxCountConstraintComponent",
+            ),
+            (
+                "<http://example.org/dave>",
+                Some("<http://example.org/name>"),
+                "http://www.w3.org/ns/shacl#DatatypeConstraintComponent",
+            ),
+        ]
+        .map(|(focus_node, path, component)| {
+            (
+                focus_node.to_owned(),
+                path.map(ToOwned::to_owned),
+                component.to_owned(),
+            )
+        })
+    );
+
+    // The order is the same across runs, even if the data is loaded in a different order
+    let mut reversed_data = Graph::new();
+    let mut triples = data.iter().collect::<Vec<_>>();
+    triples.reverse();
+    for triple in triples {
+        reversed_data.insert(triple);
+    }
+    for data in [&data, &reversed_data] {
+        let other_report = validator.validate(data).expect("Validation failed");
+        assert_eq!(
+            other_report
+                .results()
+                .iter()
+                .map(|result| (key(result), result.value.clone()))
+                .collect::<Vec<_>>(),
+            report
+                .results()
+                .iter()
+                .map(|result| (key(result), result.value.clone()))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
 // =============================================================================
 // N3 Formula validation tests
 // =============================================================================
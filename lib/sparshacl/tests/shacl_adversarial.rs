@@ -4,8 +4,8 @@
 //! not total graph size, and that depth limits are enforced to prevent attacks.
 
 use oxrdf::{BlankNode, Graph, Literal, NamedNode, Term, Triple, vocab::{rdf, shacl, xsd}};
-use sparshacl::{ShaclValidator, ShapesGraph};
-use std::time::Instant;
+use sparshacl::{ShaclError, ShaclValidator, ShapesGraph};
+use std::time::{Duration, Instant};
 
 // =============================================================================
 // Test 1: Validation Cost Proportional to Affected Nodes
@@ -555,6 +555,82 @@ fn shacl_large_graph_small_shape_fast() {
              data.len() / 1000, duration);
 }
 
+// =============================================================================
+// Test 6: Validation Timeout Enforcement
+// =============================================================================
+
+#[test]
+fn shacl_validation_timeout_enforced() {
+    println!("\n=== Test: Validation Timeout Enforcement ===");
+
+    // Create a shape with a complex inverse path: sh:inversePath [ sh:zeroOrMorePath ex:next ]
+    // Complex inverse paths iterate over the whole data graph for each focus node
+    let mut shapes_graph = Graph::new();
+    let target_class = NamedNode::new("http://example.org/ChainNode").unwrap();
+    let root_shape = NamedNode::new("http://example.org/ExpensiveShape").unwrap();
+    let next_prop = NamedNode::new("http://example.org/next").unwrap();
+    let prop_shape = BlankNode::default();
+    let inverse_path = BlankNode::default();
+    let zero_or_more_path = BlankNode::default();
+
+    shapes_graph.insert(&Triple::new(root_shape.clone(), rdf::TYPE, shacl::NODE_SHAPE));
+    shapes_graph.insert(&Triple::new(root_shape.clone(), shacl::TARGET_CLASS, target_class.clone()));
+    shapes_graph.insert(&Triple::new(root_shape, shacl::PROPERTY, prop_shape.clone()));
+    shapes_graph.insert(&Triple::new(prop_shape.clone(), shacl::PATH, inverse_path.clone()));
+    shapes_graph.insert(&Triple::new(inverse_path, shacl::INVERSE_PATH, zero_or_more_path.clone()));
+    shapes_graph.insert(&Triple::new(zero_or_more_path, shacl::ZERO_OR_MORE_PATH, next_prop.clone()));
+    shapes_graph.insert(&Triple::new(
+        prop_shape,
+        shacl::MIN_COUNT,
+        Literal::new_typed_literal("1", xsd::INTEGER),
+    ));
+
+    let shapes = ShapesGraph::from_graph(&shapes_graph).expect("Failed to parse shapes");
+
+    // A small graph is validated well within the timeout
+    let mut small_data = Graph::new();
+    let small_node = NamedNode::new("http://example.org/small").unwrap();
+    small_data.insert(&Triple::new(small_node, rdf::TYPE, target_class.clone()));
+    let report = ShaclValidator::new(shapes.clone())
+        .with_timeout(Duration::from_secs(10))
+        .validate(&small_data)
+        .expect("Validation of a small graph should not time out");
+    assert!(report.conforms(), "Validation should pass");
+
+    // A long chain of targeted nodes makes validation quadratic in the graph size
+    let mut data = Graph::new();
+    let chain_length = 20_000;
+    for i in 0..chain_length {
+        let node = NamedNode::new(format!("http://example.org/node{}", i)).unwrap();
+        let next = NamedNode::new(format!("http://example.org/node{}", i + 1)).unwrap();
+        data.insert(&Triple::new(node.clone(), rdf::TYPE, target_class.clone()));
+        data.insert(&Triple::new(node, next_prop.clone(), next));
+    }
+
+    println!("Graph size: {} triples", data.len());
+
+    let timeout = Duration::from_millis(100);
+    let validator = ShaclValidator::new(shapes).with_timeout(timeout);
+    let start = Instant::now();
+    let result = validator.validate(&data);
+    let duration = start.elapsed();
+
+    println!("Validation time: {:?}", duration);
+
+    assert!(
+        matches!(result, Err(ShaclError::Timeout(t)) if t == timeout),
+        "Expected a timeout error, got: {:?}", result.map(|report| report.conforms())
+    );
+    assert!(
+        duration < Duration::from_secs(2),
+        "Validation took {:?}, expected to abort shortly after the {:?} timeout",
+        duration,
+        timeout
+    );
+
+    println!("✓ Validation is aborted once the timeout is exceeded");
+}
+
 // =============================================================================
 // Additional Security Tests
 // =============================================================================
@@ -712,3 +788,65 @@ fn shacl_cyclic_shape_reference() {
 
     println!("✓ Cyclic shape references are bounded by MAX_RECURSION_DEPTH");
 }
+
+#[test]
+fn shacl_mutual_recursion_depth_configurable() {
+    println!("\n=== Test: Configurable Recursion Depth With Mutually Recursive Shapes ===");
+
+    // ShapeA -> ShapeB -> ShapeA through sh:node on ex:next
+    let mut shapes_graph = Graph::new();
+    let target_class = NamedNode::new("http://example.org/Linked").unwrap();
+    let shape_a = NamedNode::new("http://example.org/ShapeA").unwrap();
+    let shape_b = NamedNode::new("http://example.org/ShapeB").unwrap();
+    let next_prop = NamedNode::new("http://example.org/next").unwrap();
+
+    shapes_graph.insert(&Triple::new(shape_a.clone(), rdf::TYPE, shacl::NODE_SHAPE));
+    shapes_graph.insert(&Triple::new(shape_a.clone(), shacl::TARGET_CLASS, target_class.clone()));
+    shapes_graph.insert(&Triple::new(shape_b.clone(), rdf::TYPE, shacl::NODE_SHAPE));
+    for (from, to) in [(&shape_a, &shape_b), (&shape_b, &shape_a)] {
+        let prop_shape = BlankNode::default();
+        shapes_graph.insert(&Triple::new(from.clone(), shacl::PROPERTY, prop_shape.clone()));
+        shapes_graph.insert(&Triple::new(prop_shape.clone(), shacl::PATH, next_prop.clone()));
+        shapes_graph.insert(&Triple::new(prop_shape, shacl::NODE, to.clone()));
+    }
+
+    let shapes = ShapesGraph::from_graph(&shapes_graph).expect("Failed to parse shapes");
+    let max_depth = 10;
+    let validator = ShaclValidator::new(shapes).with_max_recursion_depth(max_depth);
+
+    // A chain shorter than the limit is validated
+    let mut data = Graph::new();
+    let chain_length = 5;
+    for i in 0..chain_length {
+        let node = NamedNode::new(format!("http://example.org/chain{}", i)).unwrap();
+        let next = NamedNode::new(format!("http://example.org/chain{}", i + 1)).unwrap();
+        data.insert(&Triple::new(node, next_prop.clone(), next));
+    }
+    data.insert(&Triple::new(
+        NamedNode::new("http://example.org/chain0").unwrap(),
+        rdf::TYPE,
+        target_class.clone(),
+    ));
+    let report = validator.validate(&data).expect("Validation of a short chain should succeed");
+    assert!(report.conforms(), "Validation should pass");
+
+    // A data cycle makes the shapes recurse until the limit is hit
+    let node1 = NamedNode::new("http://example.org/node1").unwrap();
+    let node2 = NamedNode::new("http://example.org/node2").unwrap();
+    data.insert(&Triple::new(node1.clone(), rdf::TYPE, target_class));
+    data.insert(&Triple::new(node1.clone(), next_prop.clone(), node2.clone()));
+    data.insert(&Triple::new(node2, next_prop, node1));
+
+    let start = Instant::now();
+    let result = validator.validate(&data);
+    let duration = start.elapsed();
+
+    println!("Validation time: {:?}", duration);
+
+    assert!(
+        matches!(result, Err(ShaclError::RecursionLimit { depth }) if depth == max_depth + 1),
+        "Expected a recursion limit error, got: {:?}", result.map(|report| report.conforms())
+    );
+
+    println!("✓ Mutually recursive shapes terminate at the configured depth");
+}
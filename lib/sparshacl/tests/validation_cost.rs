@@ -378,3 +378,135 @@ fn test_validation_cost_per_node() {
     println!("  Per-node validation cost should be roughly constant.");
     println!("  If it increases, there's overhead from graph size.");
 }
+
+/// Builds organizations with one department each and one member per department,
+/// except for the first organization that has no member and the second one that has two.
+fn create_organization_graph(organization_count: usize) -> Graph {
+    let mut graph = Graph::new();
+    let organization_class = NamedNode::new("http://example.org/Organization").unwrap();
+    let part_of = NamedNode::new("http://example.org/partOf").unwrap();
+    let member_of = NamedNode::new("http://example.org/memberOf").unwrap();
+    let name_prop = NamedNode::new("http://example.org/name").unwrap();
+
+    for i in 0..organization_count {
+        let organization = NamedNode::new(format!("http://example.org/org{}", i)).unwrap();
+        let department = NamedNode::new(format!("http://example.org/dept{}", i)).unwrap();
+        graph.insert(&Triple::new(organization.clone(), rdf::TYPE, organization_class.clone()));
+        graph.insert(&Triple::new(department.clone(), part_of.clone(), organization));
+        let member_count = match i {
+            0 => 0,
+            1 => 2,
+            _ => 1,
+        };
+        for j in 0..member_count {
+            let person = NamedNode::new(format!("http://example.org/person{}_{}", i, j)).unwrap();
+            graph.insert(&Triple::new(person.clone(), member_of.clone(), department.clone()));
+            graph.insert(&Triple::new(
+                person,
+                name_prop.clone(),
+                Literal::new_simple_literal(format!("Person {} {}", i, j)),
+            ));
+        }
+    }
+
+    graph
+}
+
+#[test]
+fn test_complex_sequence_path_cost_per_node() {
+    // TEST: Is the per-node cost of a complex path independent of the graph size?
+    //
+    // The path ( [ sh:inversePath ( ex:memberOf ex:partOf ) ] ex:name ) gets the names of
+    // the members of the departments of an organization.
+    // Evaluating the inverse of a sequence naively requires scanning the whole graph for
+    // each focus node, the path is compiled once per validation to only follow the
+    // triples around the focus node.
+
+    use oxrdf::BlankNode;
+    use oxrdf::vocab::shacl;
+
+    println!("\n=== Complex Sequence Path Cost Test ===");
+
+    let mut shapes_graph = Graph::new();
+    let shape = NamedNode::new("http://example.org/OrganizationShape").unwrap();
+    let organization_class = NamedNode::new("http://example.org/Organization").unwrap();
+    let part_of = NamedNode::new("http://example.org/partOf").unwrap();
+    let member_of = NamedNode::new("http://example.org/memberOf").unwrap();
+    let name_prop = NamedNode::new("http://example.org/name").unwrap();
+    let prop_shape = BlankNode::default();
+    let path = BlankNode::default();
+    let path_rest = BlankNode::default();
+    let inverse_path = BlankNode::default();
+    let inner_path = BlankNode::default();
+    let inner_path_rest = BlankNode::default();
+
+    shapes_graph.insert(&Triple::new(shape.clone(), rdf::TYPE, shacl::NODE_SHAPE));
+    shapes_graph.insert(&Triple::new(shape.clone(), shacl::TARGET_CLASS, organization_class));
+    shapes_graph.insert(&Triple::new(shape, shacl::PROPERTY, prop_shape.clone()));
+    shapes_graph.insert(&Triple::new(prop_shape.clone(), shacl::PATH, path.clone()));
+    shapes_graph.insert(&Triple::new(path.clone(), rdf::FIRST, inverse_path.clone()));
+    shapes_graph.insert(&Triple::new(path, rdf::REST, path_rest.clone()));
+    shapes_graph.insert(&Triple::new(path_rest.clone(), rdf::FIRST, name_prop));
+    shapes_graph.insert(&Triple::new(path_rest, rdf::REST, rdf::NIL));
+    shapes_graph.insert(&Triple::new(inverse_path, shacl::INVERSE_PATH, inner_path.clone()));
+    shapes_graph.insert(&Triple::new(inner_path.clone(), rdf::FIRST, member_of));
+    shapes_graph.insert(&Triple::new(inner_path, rdf::REST, inner_path_rest.clone()));
+    shapes_graph.insert(&Triple::new(inner_path_rest.clone(), rdf::FIRST, part_of));
+    shapes_graph.insert(&Triple::new(inner_path_rest, rdf::REST, rdf::NIL));
+    shapes_graph.insert(&Triple::new(
+        prop_shape.clone(),
+        shacl::MIN_COUNT,
+        Literal::new_typed_literal("1", xsd::INTEGER),
+    ));
+    shapes_graph.insert(&Triple::new(
+        prop_shape,
+        shacl::MAX_COUNT,
+        Literal::new_typed_literal("1", xsd::INTEGER),
+    ));
+
+    let shapes = ShapesGraph::from_graph(&shapes_graph).unwrap();
+    let validator = ShaclValidator::new(shapes);
+
+    println!("{:<15} {:<20} {:<20}", "Focus Nodes", "Total Triples", "Time per Node (μs)");
+    println!("{}", "-".repeat(55));
+
+    let mut timings = Vec::new();
+    for organization_count in [1_000, 8_000] {
+        let graph = create_organization_graph(organization_count);
+
+        let start = Instant::now();
+        let report = validator.validate(&graph).unwrap();
+        let duration = start.elapsed();
+
+        // Only the organization without member and the one with two members are invalid
+        let mut invalid = report
+            .results()
+            .iter()
+            .map(|result| result.focus_node.to_string())
+            .collect::<Vec<_>>();
+        invalid.sort();
+        assert_eq!(
+            invalid,
+            ["<http://example.org/org0>", "<http://example.org/org1>"],
+            "Unexpected violations"
+        );
+
+        let time_per_node = duration.as_secs_f64() * 1_000_000.0 / organization_count as f64;
+        timings.push(time_per_node);
+        println!(
+            "{:<15} {:<20} {:<20.2}",
+            organization_count,
+            graph.len(),
+            time_per_node
+        );
+    }
+
+    // With a graph 8 times bigger, a per-node cost growing with the graph size would be about 8 times higher
+    let ratio = timings[1] / timings[0];
+    println!("Per-node cost ratio: {:.2}x", ratio);
+    assert!(
+        ratio < 4.0,
+        "Per-node cost grew {:.2}x with a graph 8 times bigger, the path should not scan the whole graph",
+        ratio
+    );
+}
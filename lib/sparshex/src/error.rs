@@ -1,6 +1,8 @@
 //! Error types for ShEx validation.
 
+use crate::limits::ValidationLimitError;
 use oxrdf::{NamedNode, Term};
+use std::time::Duration;
 
 /// Main error type for ShEx operations.
 #[derive(Debug, thiserror::Error)]
@@ -13,6 +15,65 @@ pub enum ShexError {
     /// Error during validation.
     #[error(transparent)]
     Validation(#[from] ShexValidationError),
+
+    /// Validation exceeded the configured timeout.
+    #[error("ShEx validation exceeded the timeout limit of {0:?}")]
+    Timeout(Duration),
+
+    /// The shapes validated examined more triples than allowed.
+    #[error("ShEx validation exceeded the budget of {limit} examined triples")]
+    TripleBudgetExceeded {
+        /// The maximum number of triples that could be examined.
+        limit: usize,
+    },
+
+    /// Validation expanded more shape references than allowed.
+    #[error("ShEx validation expanded more than {limit} shape references")]
+    ShapeReferenceLimit {
+        /// The maximum number of shape references that could be expanded.
+        limit: usize,
+    },
+
+    /// A pattern facet is longer than allowed.
+    #[error("Regex pattern of {length} characters exceeds the limit of {limit}")]
+    RegexTooLong {
+        /// The length of the pattern in characters.
+        length: usize,
+        /// The maximum allowed pattern length in characters.
+        limit: usize,
+    },
+
+    /// A value set has more values than allowed.
+    #[error("Value set of {length} values exceeds the limit of {limit}")]
+    ValueSetTooLong {
+        /// The number of values in the value set.
+        length: usize,
+        /// The maximum allowed number of values.
+        limit: usize,
+    },
+}
+
+impl From<ValidationLimitError> for ShexError {
+    fn from(error: ValidationLimitError) -> Self {
+        match error {
+            ValidationLimitError::MaxRecursionDepthExceeded { depth, .. } => {
+                ShexValidationError::max_recursion_depth(depth).into()
+            }
+            ValidationLimitError::MaxShapeReferencesExceeded { limit, .. } => {
+                Self::ShapeReferenceLimit { limit }
+            }
+            ValidationLimitError::MaxTriplesExaminedExceeded { limit, .. } => {
+                Self::TripleBudgetExceeded { limit }
+            }
+            ValidationLimitError::TimeoutExceeded { limit, .. } => Self::Timeout(limit),
+            ValidationLimitError::RegexTooLong { length, limit } => {
+                Self::RegexTooLong { length, limit }
+            }
+            ValidationLimitError::ListTooLong { length, limit } => {
+                Self::ValueSetTooLong { length, limit }
+            }
+        }
+    }
 }
 
 /// Error type for parsing shapes from RDF graphs or ShExC.
@@ -88,6 +149,12 @@ pub enum ShexParseError {
     /// Invalid value set constraint.
     #[error("Invalid value set constraint in shape {shape}: {message}")]
     InvalidValueSet { shape: Box<Term>, message: String },
+    /// ShExC syntax error.
+    #[error("ShExC syntax error: {message}")]
+    Syntax {
+        /// The description of the syntax error.
+        message: String,
+    },
 }
 
 /// Error type for validation operations.
@@ -106,6 +173,10 @@ pub enum ShexValidationError {
     #[error("Shape not found: {label}")]
     ShapeNotFound { label: String },
 
+    /// The schema has no start shape.
+    #[error("The schema has no start shape to validate the graph nodes against")]
+    MissingStartShape,
+
     /// Cardinality violation.
     #[error("Cardinality violation: expected {expected}, got {actual}")]
     CardinalityViolation { expected: String, actual: usize },
@@ -239,6 +310,13 @@ impl ShexParseError {
             message: message.into(),
         }
     }
+
+    /// Creates a ShExC syntax error.
+    pub fn syntax(message: impl Into<String>) -> Self {
+        Self::Syntax {
+            message: message.into(),
+        }
+    }
 }
 
 impl ShexValidationError {
@@ -61,6 +61,7 @@ mod limits;
 mod model;
 mod parser;
 mod result;
+mod shape_map;
 mod validator;
 
 #[cfg(test)]
@@ -68,9 +69,15 @@ mod tests;
 
 // Public API exports - following SHACL pattern for consistency
 pub use error::{ShexError, ShexParseError, ShexValidationError};
+pub use limits::ValidationLimits;
 pub use model::{
     Annotation, Cardinality, NodeConstraint, NodeKind, NumericFacet, NumericLiteral, Shape,
     ShapeExpression, ShapeLabel, ShapesSchema, StringFacet, TripleConstraint, ValueSetValue,
 };
-pub use result::ValidationResult;
+pub use parser::parse_shex;
+pub use result::{
+    ConstraintType, ConstraintViolation, ShapeId, ShexReport, ShexReportEntry, ValidationReport,
+    ValidationResult,
+};
+pub use shape_map::ShapeMap;
 pub use validator::ShexValidator;
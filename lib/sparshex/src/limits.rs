@@ -69,6 +69,7 @@ pub const DEFAULT_MAX_LIST_LENGTH: usize = 10_000;
 ///
 /// // Trusted environment with minimal limits
 /// let trusted_limits = ValidationLimits::permissive();
+/// # let _ = (limits, dev_limits, trusted_limits);
 /// ```
 #[derive(Debug, Clone)]
 pub struct ValidationLimits {
@@ -79,6 +80,9 @@ pub struct ValidationLimits {
     pub max_shape_references: usize,
 
     /// Maximum number of RDF triples to examine.
+    ///
+    /// The triples of a node are counted once each time the node is validated against a shape,
+    /// whatever the number of triple constraints of the shape.
     pub max_triples_examined: usize,
 
     /// Optional timeout for the entire validation operation.
@@ -125,6 +129,20 @@ impl ValidationLimits {
         }
     }
 
+    /// Creates the limits used by validators without configured limits.
+    ///
+    /// Only the recursion depth is bounded, to avoid stack overflows.
+    pub(crate) fn unbounded() -> Self {
+        Self {
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
+            max_shape_references: usize::MAX,
+            max_triples_examined: usize::MAX,
+            timeout: None,
+            max_regex_length: usize::MAX,
+            max_list_length: usize::MAX,
+        }
+    }
+
     /// Creates strict limits suitable for public-facing services.
     ///
     /// Recommended for production environments handling untrusted input.
@@ -137,6 +137,7 @@ impl ShapeExpression {
             Self::ShapeNot(shape) => shape.collect_refs_impl(refs),
             Self::ShapeRef(label) => refs.push(label),
             Self::Shape(shape) => {
+                refs.extend(&shape.extends);
                 for tc in &shape.triple_constraints {
                     if let Some(value_expr) = &tc.value_expr {
                         value_expr.collect_refs_impl(refs);
@@ -153,6 +154,7 @@ impl ShapeExpression {
 /// Validates triples where the focus node is the subject, matching
 /// against a set of triple constraints with cardinalities.
 #[derive(Debug, Clone, PartialEq)]
+#[expect(clippy::partial_pub_fields)]
 pub struct Shape {
     /// Optional label for this shape.
     pub label: Option<ShapeLabel>,
@@ -168,6 +170,9 @@ pub struct Shape {
 
     /// Annotations for this shape.
     pub annotations: Vec<Annotation>,
+
+    /// Shapes extended by this shape (`EXTENDS @<label>`).
+    extends: Vec<ShapeLabel>,
 }
 
 impl Shape {
@@ -179,6 +184,7 @@ impl Shape {
             extra: Vec::new(),
             triple_constraints: Vec::new(),
             annotations: Vec::new(),
+            extends: Vec::new(),
         }
     }
 
@@ -190,6 +196,7 @@ impl Shape {
             extra: Vec::new(),
             triple_constraints: Vec::new(),
             annotations: Vec::new(),
+            extends: Vec::new(),
         }
     }
 
@@ -202,6 +209,20 @@ impl Shape {
     pub fn is_empty(&self) -> bool {
         self.triple_constraints.is_empty()
     }
+
+    /// Makes this shape extend the shape with the given label (`EXTENDS @<label>`).
+    ///
+    /// A node must also satisfy the triple constraints of the extended shapes,
+    /// the triples being partitioned between the constraints sharing a predicate.
+    /// Only the closedness of this shape applies, the predicates of the extended shapes being allowed.
+    pub fn add_extends(&mut self, label: ShapeLabel) {
+        self.extends.push(label);
+    }
+
+    /// Returns the labels of the shapes extended by this shape.
+    pub fn extends(&self) -> &[ShapeLabel] {
+        &self.extends
+    }
 }
 
 impl Default for Shape {
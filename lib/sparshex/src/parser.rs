@@ -6,8 +6,7 @@
 //! # Examples
 //!
 //! ```
-//! use sparshex::parser::{ShExParser, ShExDocument};
-//! use oxrdf::NamedNode;
+//! use sparshex::parse_shex;
 //!
 //! let shex_str = r#"
 //! PREFIX ex: <http://example.org/>
@@ -19,24 +18,29 @@
 //! }
 //! "#;
 //!
-//! let parser = ShExParser::new();
-//! let document = parser.parse(shex_str)?;
-//! # Ok::<_, sparshex::parser::ShExSyntaxError>(())
+//! let schema = parse_shex(shex_str)?;
+//! assert_eq!(schema.labels().count(), 1);
+//! # Ok::<_, sparshex::ShexParseError>(())
 //! ```
 
 use nom::{
     branch::alt,
     bytes::complete::{tag, tag_no_case, take_while},
     character::complete::{
-        alpha1, alphanumeric1, char, digit1, multispace0, one_of,
+        alpha1, alphanumeric1, char, digit1, multispace0, multispace1, one_of,
     },
-    combinator::{all_consuming, map, map_res, opt, recognize, value},
+    combinator::{all_consuming, map, map_res, opt, peek, recognize, value},
     error::{ContextError, ErrorKind, ParseError as NomParseError},
     multi::{many0, separated_list0, separated_list1},
     sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
-    Err, IResult, Offset,
+    Err, IResult,
 };
-use oxiri::{Iri, IriParseError};
+use crate::error::ShexParseError;
+use crate::model;
+use oxiri::Iri;
+use oxrdf::vocab::{rdf, xsd};
+use oxrdf::{BlankNode, NamedNode};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -57,26 +61,10 @@ impl ShExSyntaxError {
     pub fn new(message: String, location: Option<Location>) -> Self {
         ShExSyntaxErrorKind::Syntax { message, location }.into()
     }
-
-    /// Creates an error for invalid base IRI.
-    pub fn from_bad_base_iri(e: IriParseError) -> Self {
-        ShExSyntaxErrorKind::InvalidBaseIri(e).into()
-    }
-
-    /// Creates an error for invalid IRI.
-    pub fn from_bad_iri(e: IriParseError) -> Self {
-        ShExSyntaxErrorKind::InvalidIri(e).into()
-    }
 }
 
 #[derive(Debug, thiserror::Error)]
 enum ShExSyntaxErrorKind {
-    #[error("Invalid ShEx base IRI provided: {0}")]
-    InvalidBaseIri(#[from] IriParseError),
-
-    #[error("Invalid IRI: {0}")]
-    InvalidIri(IriParseError),
-
     #[error("Syntax error{}: {message}", location.as_ref().map(|l| format!(" at {}", l)).unwrap_or_default())]
     Syntax {
         message: String,
@@ -85,15 +73,6 @@ enum ShExSyntaxErrorKind {
 
     #[error("Undefined prefix: {0}")]
     UndefinedPrefix(String),
-
-    #[error("Invalid cardinality: {0}")]
-    InvalidCardinality(String),
-
-    #[error("Invalid numeric literal: {0}")]
-    InvalidNumeric(String),
-
-    #[error("Invalid regex pattern: {0}")]
-    InvalidRegex(String),
 }
 
 /// Location information for parse errors (line and column).
@@ -112,7 +91,8 @@ impl fmt::Display for Location {
 impl Location {
     /// Create a new location from input and current position.
     pub fn from_input(original: &str, current: &str) -> Self {
-        let offset = original.offset(current);
+        // The current input is always a suffix of the original one.
+        let offset = original.len().saturating_sub(current.len());
         let prefix = &original[..offset];
         let line = prefix.chars().filter(|&c| c == '\n').count() + 1;
         let column = prefix
@@ -196,6 +176,8 @@ pub enum ShapeExpr {
 /// A shape definition with triple constraints.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Shape {
+    /// The shapes extended by this shape.
+    pub extends: Vec<ShapeLabel>,
     /// Whether this is a closed shape (no extra properties allowed).
     pub closed: bool,
     /// Properties that are allowed in addition to those in the expression.
@@ -252,8 +234,6 @@ pub enum ValueSetValue {
     ObjectValue(ObjectValue),
     /// A language stem.
     LanguageStem(String),
-    /// An exclusion (NOT a value).
-    Exclusion(Box<ValueSetValue>),
 }
 
 /// An object value (IRI or literal).
@@ -288,14 +268,8 @@ pub enum TripleExpr {
     EachOf(Vec<TripleExpr>),
     /// OR combination of triple expressions.
     OneOf(Vec<TripleExpr>),
-    /// A triple expression reference.
-    TripleExprRef(TripleExprLabel),
 }
 
-/// Label for a triple expression.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct TripleExprLabel(pub String);
-
 /// A constraint on a triple (property constraint).
 #[derive(Debug, Clone, PartialEq)]
 pub struct TripleConstraint {
@@ -337,12 +311,14 @@ impl Cardinality {
 // ============================================================================
 
 /// Parser state for tracking context during parsing.
+///
+/// The base IRI and the prefixes are updated while the directives of the document are parsed.
 #[derive(Debug, Clone)]
 struct ParserState {
     /// Base IRI for resolving relative IRIs.
-    base_iri: Option<String>,
+    base_iri: RefCell<Option<String>>,
     /// Prefix mappings.
-    prefixes: HashMap<String, String>,
+    prefixes: RefCell<HashMap<String, String>>,
     /// Original input for location tracking.
     original_input: String,
 }
@@ -350,14 +326,14 @@ struct ParserState {
 impl ParserState {
     fn new(input: &str) -> Self {
         Self {
-            base_iri: None,
-            prefixes: HashMap::new(),
+            base_iri: RefCell::new(None),
+            prefixes: RefCell::new(HashMap::new()),
             original_input: input.to_string(),
         }
     }
 
     fn resolve_prefixed_name(&self, prefix: &str, local: &str) -> Result<String, ShExSyntaxError> {
-        if let Some(namespace) = self.prefixes.get(prefix) {
+        if let Some(namespace) = self.prefixes.borrow().get(prefix) {
             Ok(format!("{}{}", namespace, local))
         } else {
             Err(ShExSyntaxErrorKind::UndefinedPrefix(prefix.to_string()).into())
@@ -377,10 +353,7 @@ impl ParserState {
 ///
 /// Parses ShExC (Shape Expressions Compact Syntax) documents into an AST.
 #[derive(Default)]
-pub struct ShExParser {
-    base_iri: Option<String>,
-    prefixes: HashMap<String, String>,
-}
+pub struct ShExParser;
 
 impl ShExParser {
     /// Creates a new ShExC parser.
@@ -388,43 +361,12 @@ impl ShExParser {
         Self::default()
     }
 
-    /// Sets the base IRI for resolving relative IRIs.
-    pub fn with_base_iri(mut self, base_iri: impl Into<String>) -> Result<Self, IriParseError> {
-        let base = base_iri.into();
-        Iri::parse(base.clone())?;
-        self.base_iri = Some(base);
-        Ok(self)
-    }
-
-    /// Adds a prefix declaration.
-    pub fn with_prefix(
-        mut self,
-        prefix_name: impl Into<String>,
-        prefix_iri: impl Into<String>,
-    ) -> Result<Self, IriParseError> {
-        let iri = prefix_iri.into();
-        Iri::parse(iri.clone())?;
-        self.prefixes.insert(prefix_name.into(), iri);
-        Ok(self)
-    }
-
     /// Parses a ShExC document.
     pub fn parse(&self, input: &str) -> Result<ShExDocument, ShExSyntaxError> {
-        let mut state = ParserState::new(input);
-        state.base_iri = self.base_iri.clone();
-        state.prefixes = self.prefixes.clone();
+        let state = ParserState::new(input);
 
         match all_consuming(shex_doc(&state))(input) {
-            Ok((_, mut doc)) => {
-                // Merge parser's base and prefixes
-                if doc.base.is_none() {
-                    doc.base = state.base_iri.clone();
-                }
-                for (prefix, iri) in &state.prefixes {
-                    doc.prefixes.entry(prefix.clone()).or_insert_with(|| iri.clone());
-                }
-                Ok(doc)
-            }
+            Ok((_, doc)) => Ok(doc),
             Err(Err::Error(e)) | Err(Err::Failure(e)) => {
                 let location = state.location(e.input);
                 Err(ShExSyntaxError::new(
@@ -443,15 +385,13 @@ impl ShExParser {
 #[derive(Debug, Clone)]
 struct ParseErr<'a> {
     input: &'a str,
-    kind: ErrorKind,
     context: Vec<&'static str>,
 }
 
 impl<'a> NomParseError<&'a str> for ParseErr<'a> {
-    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+    fn from_error_kind(input: &'a str, _kind: ErrorKind) -> Self {
         ParseErr {
             input,
-            kind,
             context: Vec::new(),
         }
     }
@@ -469,10 +409,9 @@ impl<'a> ContextError<&'a str> for ParseErr<'a> {
 }
 
 impl<'a, E> nom::error::FromExternalError<&'a str, E> for ParseErr<'a> {
-    fn from_external_error(input: &'a str, kind: ErrorKind, _e: E) -> Self {
+    fn from_external_error(input: &'a str, _kind: ErrorKind, _e: E) -> Self {
         ParseErr {
             input,
-            kind,
             context: Vec::new(),
         }
     }
@@ -492,7 +431,7 @@ fn error_to_string<'a>(e: &ParseErr<'a>) -> String {
 
 type PResult<'a, O> = IResult<&'a str, O, ParseErr<'a>>;
 
-fn ws<'a>(state: &ParserState) -> impl FnMut(&'a str) -> PResult<'a, ()> + '_ {
+fn ws<'a>(_state: &ParserState) -> impl FnMut(&'a str) -> PResult<'a, ()> + '_ {
     move |input| {
         let (input, _) = multispace0(input)?;
         let (input, _) = many0(preceded(comment, multispace0))(input)?;
@@ -573,6 +512,16 @@ fn pn_chars(input: &str) -> PResult<&str> {
     ))(input)
 }
 
+fn blank_node_label(input: &str) -> PResult<'_, String> {
+    map(
+        preceded(
+            tag("_:"),
+            recognize(pair(alt((pn_chars_u, digit1)), many0(pn_chars))),
+        ),
+        str::to_owned,
+    )(input)
+}
+
 fn string_literal_quote(input: &str) -> PResult<String> {
     delimited(
         char('"'),
@@ -643,9 +592,25 @@ fn double(input: &str) -> PResult<f64> {
 
 fn shex_doc<'a>(state: &'a ParserState) -> impl FnMut(&'a str) -> PResult<'a, ShExDocument> + 'a {
     move |input| {
-        let (input, _) = ws(state)(input)?;
-        let (input, directives) = many0(terminated(directive(state), ws(state)))(input)?;
-        let (input, _) = ws(state)(input)?;
+        let (mut input, ()) = ws(state)(input)?;
+        // Directives are registered as soon as they are parsed so that the following
+        // prefixed names can be resolved.
+        let mut directives = Vec::new();
+        while let Ok((rest, directive)) = terminated(directive(state), ws(state))(input) {
+            match &directive {
+                Directive::Base(base) => {
+                    state.base_iri.replace(Some(base.clone()));
+                }
+                Directive::Prefix(prefix, iri) => {
+                    state
+                        .prefixes
+                        .borrow_mut()
+                        .insert(prefix.clone(), iri.clone());
+                }
+            }
+            directives.push(directive);
+            input = rest;
+        }
         let (input, start_actions) = opt(start_actions(state))(input)?;
         let (input, _) = ws(state)(input)?;
         let (input, shapes) = many0(terminated(shape_decl(state), ws(state)))(input)?;
@@ -740,6 +705,7 @@ fn shape_label<'a>(
     move |input| {
         alt((
             map(iriref, ShapeLabel::Iri),
+            map(blank_node_label, ShapeLabel::BNode),
             map(prefixed_name(state), ShapeLabel::Iri),
         ))(input)
     }
@@ -835,13 +801,15 @@ fn node_constraint<'a>(
     state: &'a ParserState,
 ) -> impl FnMut(&'a str) -> PResult<'a, NodeConstraint> + 'a {
     move |input| {
-        let (input, node_kind) = opt(node_kind)(input)?;
-        let (input, _) = ws(state)(input)?;
-        let (input, datatype) = opt(prefixed_name(state))(input)?;
-        let (input, _) = ws(state)(input)?;
-        let (input, xsfacets) = many0(terminated(xsfacet(state), ws(state)))(input)?;
-        let (input, _) = ws(state)(input)?;
-        let (input, values) = opt(value_set(state))(input)?;
+        let (input, (node_kind, datatype, values)) = alt((
+            map(node_kind, |kind| (Some(kind), None, Vec::new())),
+            map(value_set(state), |values| (None, None, values)),
+            map(alt((iriref, prefixed_name(state))), |datatype| {
+                (None, Some(datatype), Vec::new())
+            }),
+            map(peek(xsfacet(state)), |_| (None, None, Vec::new())),
+        ))(input)?;
+        let (input, xsfacets) = many0(preceded(ws(state), xsfacet(state)))(input)?;
 
         Ok((
             input,
@@ -849,7 +817,7 @@ fn node_constraint<'a>(
                 node_kind,
                 datatype,
                 xsfacets,
-                values: values.unwrap_or_default(),
+                values,
             },
         ))
     }
@@ -857,10 +825,10 @@ fn node_constraint<'a>(
 
 fn node_kind(input: &str) -> PResult<NodeKind> {
     alt((
-        value(NodeKind::Iri, tag("IRI")),
-        value(NodeKind::BNode, tag("BNODE")),
-        value(NodeKind::Literal, tag("LITERAL")),
-        value(NodeKind::NonLiteral, tag("NONLITERAL")),
+        value(NodeKind::Iri, tag_no_case("IRI")),
+        value(NodeKind::BNode, tag_no_case("BNODE")),
+        value(NodeKind::Literal, tag_no_case("LITERAL")),
+        value(NodeKind::NonLiteral, tag_no_case("NONLITERAL")),
     ))(input)
 }
 
@@ -899,6 +867,20 @@ fn xsfacet<'a>(state: &'a ParserState) -> impl FnMut(&'a str) -> PResult<'a, XsF
                 preceded(tuple((tag("MAXEXCLUSIVE"), ws(state))), numeric_literal),
                 XsFacet::MaxExclusive,
             ),
+            map(
+                preceded(
+                    tuple((tag("TOTALDIGITS"), ws(state))),
+                    map_res(integer, u32::try_from),
+                ),
+                XsFacet::TotalDigits,
+            ),
+            map(
+                preceded(
+                    tuple((tag("FRACTIONDIGITS"), ws(state))),
+                    map_res(integer, u32::try_from),
+                ),
+                XsFacet::FractionDigits,
+            ),
         ))(input)
     }
 }
@@ -943,6 +925,7 @@ fn value_set_value<'a>(
                 ),
                 |v| v,
             ),
+            map(terminated(langtag, char('~')), ValueSetValue::LanguageStem),
             map(iriref, ValueSetValue::IriRef),
             map(prefixed_name(state), ValueSetValue::IriRef),
             map(object_value(state), ValueSetValue::ObjectValue),
@@ -991,24 +974,52 @@ fn shape_definition<'a>(
     state: &'a ParserState,
 ) -> impl FnMut(&'a str) -> PResult<'a, Shape> + 'a {
     move |input| {
-        let (input, closed) = opt(tag("CLOSED"))(input)?;
-        let (input, _) = ws(state)(input)?;
-        let (input, extra) = opt(extra_property_set(state))(input)?;
-        let (input, _) = ws(state)(input)?;
+        let (input, qualifiers) = many0(terminated(shape_qualifier(state), ws(state)))(input)?;
         let (input, _) = char('{')(input)?;
         let (input, _) = ws(state)(input)?;
         let (input, expr) = opt(triple_expr(state))(input)?;
         let (input, _) = ws(state)(input)?;
+        let (input, _) = opt(char(';'))(input)?;
+        let (input, ()) = ws(state)(input)?;
         let (input, _) = char('}')(input)?;
 
-        Ok((
-            input,
-            Shape {
-                closed: closed.is_some(),
-                extra: extra.unwrap_or_default(),
-                expression: expr.map(Box::new),
-            },
-        ))
+        let mut shape = Shape {
+            extends: Vec::new(),
+            closed: false,
+            extra: Vec::new(),
+            expression: expr.map(Box::new),
+        };
+        for qualifier in qualifiers {
+            match qualifier {
+                ShapeQualifier::Extends(label) => shape.extends.push(label),
+                ShapeQualifier::Extra(extra) => shape.extra.extend(extra),
+                ShapeQualifier::Closed => shape.closed = true,
+            }
+        }
+        Ok((input, shape))
+    }
+}
+
+/// A qualifier written before the `{` of a shape definition.
+#[derive(Clone)]
+enum ShapeQualifier {
+    Extends(ShapeLabel),
+    Extra(Vec<String>),
+    Closed,
+}
+
+fn shape_qualifier<'a>(
+    state: &'a ParserState,
+) -> impl FnMut(&'a str) -> PResult<'a, ShapeQualifier> + 'a {
+    move |input| {
+        alt((
+            map(
+                preceded(tuple((tag("EXTENDS"), ws(state))), shape_ref(state)),
+                ShapeQualifier::Extends,
+            ),
+            map(extra_property_set(state), ShapeQualifier::Extra),
+            value(ShapeQualifier::Closed, tag("CLOSED")),
+        ))(input)
     }
 }
 
@@ -1106,9 +1117,16 @@ fn triple_constraint<'a>(
     move |input| {
         let (input, inverse) = opt(char('^'))(input)?;
         let (input, _) = ws(state)(input)?;
-        let (input, predicate) = prefixed_name(state)(input)?;
+        let (input, predicate) = alt((
+            iriref,
+            prefixed_name(state),
+            value(
+                rdf::TYPE.as_str().to_owned(),
+                terminated(char('a'), peek(multispace1)),
+            ),
+        ))(input)?;
         let (input, _) = ws(state)(input)?;
-        let (input, value_expr) = opt(shape_expr(state))(input)?;
+        let (input, value_expr) = alt((value(None, char('.')), opt(shape_expr(state))))(input)?;
 
         Ok((
             input,
@@ -1130,21 +1148,333 @@ fn cardinality(input: &str) -> PResult<Cardinality> {
         map(
             delimited(
                 char('{'),
-                separated_pair(
+                pair(
                     map_res(digit1, |s: &str| s.parse::<u32>()),
-                    char(','),
-                    alt((
-                        map(char('*'), |_| None),
-                        map(map_res(digit1, |s: &str| s.parse::<u32>()), Some),
+                    opt(preceded(
+                        char(','),
+                        opt(alt((
+                            map(char('*'), |_| None),
+                            map(map_res(digit1, |s: &str| s.parse::<u32>()), Some),
+                        ))),
                     )),
                 ),
                 char('}'),
             ),
-            |(min, max)| Cardinality::new(min, max),
+            // {m} is exactly m, {m,} and {m,*} are unbounded.
+            |(min, max)| match max {
+                None => Cardinality::new(min, Some(min)),
+                Some(max) => Cardinality::new(min, max.flatten()),
+            },
         ),
     ))(input)
 }
 
+// ============================================================================
+// Conversion to the ShEx model
+// ============================================================================
+
+/// Parses a ShExC document into a [`ShapesSchema`](crate::ShapesSchema).
+///
+/// Supports shape labels, triple constraints with cardinalities, node kinds, datatypes,
+/// facets, value sets and the `AND`, `OR` and `NOT` shape operators.
+/// Relative IRIs are resolved against the `BASE` of the document.
+///
+/// ```
+/// use oxrdf::NamedNode;
+/// use sparshex::{ShapeLabel, parse_shex};
+///
+/// let schema = parse_shex(
+///     "PREFIX ex: <http://example.org/>
+///      PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+///      ex:PersonShape { ex:name xsd:string ; ex:knows @ex:PersonShape * }",
+/// )?;
+/// let label = ShapeLabel::Iri(NamedNode::new("http://example.org/PersonShape")?);
+/// assert!(schema.get_shape(&label).is_some());
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+pub fn parse_shex(input: &str) -> Result<model::ShapesSchema, ShexParseError> {
+    let document = ShExParser::new()
+        .parse(input)
+        .map_err(|e| ShexParseError::syntax(e.to_string()))?;
+    ModelConverter::new(document.base.as_deref())?.convert(document)
+}
+
+/// Converts the ShExC AST into the [`model`] types.
+struct ModelConverter {
+    base: Option<Iri<String>>,
+}
+
+impl ModelConverter {
+    fn new(base: Option<&str>) -> Result<Self, ShexParseError> {
+        let base = base
+            .map(|base| Iri::parse(base.to_owned()))
+            .transpose()
+            .map_err(|e| ShexParseError::syntax(format!("Invalid base IRI: {e}")))?;
+        Ok(Self { base })
+    }
+
+    fn convert(&self, document: ShExDocument) -> Result<model::ShapesSchema, ShexParseError> {
+        let mut schema = model::ShapesSchema::new();
+        for decl in document.shapes {
+            let label = self.shape_label(decl.label)?;
+            let expr = self.shape_expr(decl.shape_expr, &label)?;
+            schema.add_shape(label, expr);
+        }
+        match document.start {
+            Some(ShapeExpr::ShapeRef(label)) => schema.set_start(self.shape_label(label)?),
+            Some(_) => {
+                return Err(ShexParseError::syntax(
+                    "only shape references are supported as start shape",
+                ));
+            }
+            None => (),
+        }
+        Ok(schema)
+    }
+
+    fn named_node(&self, iri: String) -> Result<NamedNode, ShexParseError> {
+        let iri = if let Some(base) = &self.base {
+            base.resolve(&iri)
+                .map_err(|e| ShexParseError::syntax(format!("Invalid IRI <{iri}>: {e}")))?
+                .into_inner()
+        } else {
+            iri
+        };
+        NamedNode::new(iri).map_err(|e| ShexParseError::syntax(e.to_string()))
+    }
+
+    fn shape_label(&self, label: ShapeLabel) -> Result<model::ShapeLabel, ShexParseError> {
+        match label {
+            ShapeLabel::Iri(iri) => Ok(model::ShapeLabel::Iri(self.named_node(iri)?)),
+            ShapeLabel::BNode(id) => Ok(model::ShapeLabel::BNode(
+                BlankNode::new(id).map_err(|e| ShexParseError::syntax(e.to_string()))?,
+            )),
+        }
+    }
+
+    fn shape_expr(
+        &self,
+        expr: ShapeExpr,
+        label: &model::ShapeLabel,
+    ) -> Result<model::ShapeExpression, ShexParseError> {
+        Ok(match expr {
+            ShapeExpr::ShapeAnd(exprs) => model::ShapeExpression::ShapeAnd(
+                exprs
+                    .into_iter()
+                    .map(|e| self.shape_expr(e, label))
+                    .collect::<Result<_, _>>()?,
+            ),
+            ShapeExpr::ShapeOr(exprs) => model::ShapeExpression::ShapeOr(
+                exprs
+                    .into_iter()
+                    .map(|e| self.shape_expr(e, label))
+                    .collect::<Result<_, _>>()?,
+            ),
+            ShapeExpr::ShapeNot(expr) => {
+                model::ShapeExpression::ShapeNot(Box::new(self.shape_expr(*expr, label)?))
+            }
+            ShapeExpr::NodeConstraint(constraint) => {
+                model::ShapeExpression::NodeConstraint(self.node_constraint(constraint)?)
+            }
+            ShapeExpr::Shape(shape) => model::ShapeExpression::Shape(self.shape(shape, label)?),
+            ShapeExpr::ShapeRef(reference) => {
+                model::ShapeExpression::ShapeRef(self.shape_label(reference)?)
+            }
+            ShapeExpr::External => model::ShapeExpression::ShapeExternal,
+        })
+    }
+
+    fn shape(
+        &self,
+        shape: Shape,
+        label: &model::ShapeLabel,
+    ) -> Result<model::Shape, ShexParseError> {
+        let mut result = model::Shape::new();
+        for label in shape.extends {
+            result.add_extends(self.shape_label(label)?);
+        }
+        result.closed = shape.closed;
+        result.extra = shape
+            .extra
+            .into_iter()
+            .map(|iri| self.named_node(iri))
+            .collect::<Result<_, _>>()?;
+        if let Some(expression) = shape.expression {
+            self.triple_expr(*expression, label, &mut result)?;
+        }
+        Ok(result)
+    }
+
+    /// Adds the triple constraints of `expr` to `shape`, `EachOf` groups are flattened.
+    fn triple_expr(
+        &self,
+        expr: TripleExpr,
+        label: &model::ShapeLabel,
+        shape: &mut model::Shape,
+    ) -> Result<(), ShexParseError> {
+        match expr {
+            TripleExpr::TripleConstraint(constraint) => {
+                let TripleConstraint {
+                    inverse,
+                    predicate,
+                    value_expr,
+                    cardinality,
+                } = *constraint;
+                let cardinality = cardinality.unwrap_or(Cardinality::ONE);
+                let mut result = model::TripleConstraint::new(self.named_node(predicate)?)
+                    .with_cardinality(
+                        model::Cardinality::new(cardinality.min, cardinality.max).map_err(
+                            |_| {
+                                ShexParseError::invalid_cardinality(
+                                    label.to_term(),
+                                    cardinality.min,
+                                    cardinality.max,
+                                )
+                            },
+                        )?,
+                    )
+                    .with_inverse(inverse);
+                if let Some(value_expr) = value_expr {
+                    result.value_expr = Some(Box::new(self.shape_expr(value_expr, label)?));
+                }
+                shape.add_triple_constraint(result);
+            }
+            TripleExpr::EachOf(exprs) => {
+                for expr in exprs {
+                    self.triple_expr(expr, label, shape)?;
+                }
+            }
+            TripleExpr::OneOf(_) => {
+                return Err(ShexParseError::invalid_triple_constraint(
+                    label.to_term(),
+                    "OneOf triple expressions are not supported",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn node_constraint(
+        &self,
+        constraint: NodeConstraint,
+    ) -> Result<model::NodeConstraint, ShexParseError> {
+        let mut result = model::NodeConstraint::new();
+        result.node_kind = constraint.node_kind.map(|kind| match kind {
+            NodeKind::Iri => model::NodeKind::Iri,
+            NodeKind::BNode => model::NodeKind::BNode,
+            NodeKind::Literal => model::NodeKind::Literal,
+            NodeKind::NonLiteral => model::NodeKind::NonLiteral,
+        });
+        result.datatype = constraint
+            .datatype
+            .map(|datatype| self.named_node(datatype))
+            .transpose()?;
+        for facet in constraint.xsfacets {
+            match facet {
+                XsFacet::Length(length) => {
+                    result
+                        .string_facets
+                        .push(model::StringFacet::MinLength(length as usize));
+                    result
+                        .string_facets
+                        .push(model::StringFacet::MaxLength(length as usize));
+                }
+                XsFacet::MinLength(length) => result
+                    .string_facets
+                    .push(model::StringFacet::MinLength(length as usize)),
+                XsFacet::MaxLength(length) => result
+                    .string_facets
+                    .push(model::StringFacet::MaxLength(length as usize)),
+                XsFacet::Pattern(pattern) => {
+                    result.string_facets.push(model::StringFacet::Pattern {
+                        pattern,
+                        flags: None,
+                    })
+                }
+                XsFacet::MinInclusive(value) => {
+                    result
+                        .numeric_facets
+                        .push(model::NumericFacet::MinInclusive(numeric_literal_value(
+                            value,
+                        )))
+                }
+                XsFacet::MinExclusive(value) => {
+                    result
+                        .numeric_facets
+                        .push(model::NumericFacet::MinExclusive(numeric_literal_value(
+                            value,
+                        )))
+                }
+                XsFacet::MaxInclusive(value) => {
+                    result
+                        .numeric_facets
+                        .push(model::NumericFacet::MaxInclusive(numeric_literal_value(
+                            value,
+                        )))
+                }
+                XsFacet::MaxExclusive(value) => {
+                    result
+                        .numeric_facets
+                        .push(model::NumericFacet::MaxExclusive(numeric_literal_value(
+                            value,
+                        )))
+                }
+                XsFacet::TotalDigits(digits) => result
+                    .numeric_facets
+                    .push(model::NumericFacet::TotalDigits(digits)),
+                XsFacet::FractionDigits(digits) => result
+                    .numeric_facets
+                    .push(model::NumericFacet::FractionDigits(digits)),
+            }
+        }
+        for value in constraint.values {
+            result.add_value(self.value_set_value(value)?);
+        }
+        Ok(result)
+    }
+
+    fn value_set_value(
+        &self,
+        value: ValueSetValue,
+    ) -> Result<model::ValueSetValue, ShexParseError> {
+        Ok(match value {
+            ValueSetValue::IriRef(iri) | ValueSetValue::ObjectValue(ObjectValue::Iri(iri)) => {
+                model::ValueSetValue::ObjectValue(self.named_node(iri)?.into())
+            }
+            ValueSetValue::IriStem(stem) => model::ValueSetValue::IriStem(
+                self.named_node(stem.trim_end_matches('~').to_owned())?
+                    .into_string(),
+            ),
+            ValueSetValue::ObjectValue(ObjectValue::Literal(literal)) => {
+                model::ValueSetValue::ObjectValue(self.literal(literal)?.into())
+            }
+            ValueSetValue::LanguageStem(stem) => model::ValueSetValue::LanguageStem(stem),
+        })
+    }
+
+    fn literal(&self, literal: Literal) -> Result<oxrdf::Literal, ShexParseError> {
+        if let Some(language) = literal.lang {
+            oxrdf::Literal::new_language_tagged_literal(literal.value, language)
+                .map_err(|e| ShexParseError::syntax(e.to_string()))
+        } else if let Some(datatype) = literal.datatype {
+            Ok(oxrdf::Literal::new_typed_literal(
+                literal.value,
+                self.named_node(datatype)?,
+            ))
+        } else {
+            Ok(oxrdf::Literal::new_simple_literal(literal.value))
+        }
+    }
+}
+
+fn numeric_literal_value(value: NumericLiteral) -> model::NumericLiteral {
+    model::NumericLiteral::new(match value {
+        NumericLiteral::Integer(value) => value.into(),
+        NumericLiteral::Decimal(value) => oxrdf::Literal::new_typed_literal(value, xsd::DECIMAL),
+        NumericLiteral::Double(value) => value.into(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1204,11 +1534,10 @@ mod tests {
 
     #[test]
     fn test_parse_with_base() {
-        let parser = ShExParser::new()
-            .with_base_iri("http://example.org/")
-            .unwrap();
+        let parser = ShExParser::new();
 
         let shex = r#"
+            BASE <http://example.org/>
             <PersonShape> {
                 <name> <string>
             }
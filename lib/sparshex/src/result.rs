@@ -3,6 +3,7 @@
 //! This module provides types for representing ShEx validation results,
 //! including detailed constraint violation information.
 
+use crate::model::{Cardinality, ShapeLabel};
 use oxrdf::{NamedNode, NamedNodeRef, Term};
 use std::fmt;
 
@@ -11,6 +12,7 @@ use std::fmt;
 pub struct ValidationResult {
     valid: bool,
     errors: Vec<String>,
+    violations: Vec<ConstraintViolation>,
 }
 
 impl ValidationResult {
@@ -19,6 +21,7 @@ impl ValidationResult {
         Self {
             valid: true,
             errors: Vec::new(),
+            violations: Vec::new(),
         }
     }
 
@@ -27,6 +30,16 @@ impl ValidationResult {
         Self {
             valid: false,
             errors,
+            violations: Vec::new(),
+        }
+    }
+
+    /// Create a result from the violated constraints, valid if there is none
+    pub fn from_violations(violations: Vec<ConstraintViolation>) -> Self {
+        Self {
+            valid: violations.is_empty(),
+            errors: violations.iter().map(ToString::to_string).collect(),
+            violations,
         }
     }
 
@@ -40,6 +53,11 @@ impl ValidationResult {
         &self.errors
     }
 
+    /// Get the violated constraints
+    pub fn violations(&self) -> &[ConstraintViolation] {
+        &self.violations
+    }
+
     /// Add an error message
     pub fn add_error(&mut self, error: String) {
         self.valid = false;
@@ -53,6 +71,67 @@ impl Default for ValidationResult {
     }
 }
 
+/// The validation result of a node against a shape, part of a [`ShexReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShexReportEntry {
+    /// The validated node.
+    pub node: Term,
+    /// The shape the node has been validated against.
+    pub shape: ShapeLabel,
+    /// The validation result.
+    pub result: ValidationResult,
+}
+
+/// Results of the validation of several nodes, each against a shape.
+///
+/// Returned by [`ShexValidator::validate_all`](crate::ShexValidator::validate_all)
+/// and [`ShexValidator::validate_shape_map`](crate::ShexValidator::validate_shape_map).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShexReport {
+    entries: Vec<ShexReportEntry>,
+}
+
+impl ShexReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the result of the validation of `node` against `shape`.
+    pub fn add_result(&mut self, node: Term, shape: ShapeLabel, result: ValidationResult) {
+        self.entries.push(ShexReportEntry {
+            node,
+            shape,
+            result,
+        });
+    }
+
+    /// Returns true if all the validated nodes conform to their shape.
+    pub fn conforms(&self) -> bool {
+        self.entries.iter().all(|entry| entry.result.is_valid())
+    }
+
+    /// Returns the results, in validation order.
+    pub fn entries(&self) -> &[ShexReportEntry] {
+        &self.entries
+    }
+
+    /// Returns the results of the nodes that do not conform to their shape.
+    pub fn failures(&self) -> impl Iterator<Item = &ShexReportEntry> {
+        self.entries.iter().filter(|entry| !entry.result.is_valid())
+    }
+
+    /// Returns the number of validated (node, shape) pairs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no node has been validated.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
 /// Detailed validation report with structured constraint violations.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ValidationReport {
@@ -124,16 +203,7 @@ impl ValidationReport {
 
     /// Converts to a simple ValidationResult.
     pub fn to_validation_result(&self) -> ValidationResult {
-        if self.conforms {
-            ValidationResult::valid()
-        } else {
-            let errors = self
-                .violations
-                .iter()
-                .map(|v| v.message.clone())
-                .collect();
-            ValidationResult::invalid(errors)
-        }
+        ValidationResult::from_violations(self.violations.clone())
     }
 }
 
@@ -158,7 +228,7 @@ impl fmt::Display for ValidationReport {
 }
 
 /// A specific constraint violation found during validation.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ConstraintViolation {
     /// The focus node that failed validation.
     pub focus_node: Term,
@@ -168,8 +238,18 @@ pub struct ConstraintViolation {
     pub constraint_type: ConstraintType,
     /// The predicate that was being validated (for triple constraints).
     pub predicate: Option<NamedNode>,
+    /// Whether the triple constraint is inverse (the focus node is the object of the triples).
+    pub inverse: bool,
     /// The value that caused the violation.
     pub value: Option<Term>,
+    /// The cardinality of the triple constraint (for cardinality violations).
+    pub expected_cardinality: Option<Cardinality>,
+    /// The number of triples matching the triple constraint (for cardinality violations).
+    pub actual_count: Option<u32>,
+    /// Why the value does not conform to the value expression of the triple constraint.
+    pub details: Vec<ConstraintViolation>,
+    /// Why the focus node does not conform to each branch (for `OR` violations).
+    pub branches: Vec<Vec<ConstraintViolation>>,
     /// Human-readable message describing the violation.
     pub message: String,
 }
@@ -187,7 +267,12 @@ impl ConstraintViolation {
             shape_id,
             constraint_type,
             predicate: None,
+            inverse: false,
             value: None,
+            expected_cardinality: None,
+            actual_count: None,
+            details: Vec::new(),
+            branches: Vec::new(),
             message: message.into(),
         }
     }
@@ -205,13 +290,48 @@ impl ConstraintViolation {
         self.value = Some(value);
         self
     }
+
+    /// Sets the predicate of an inverse triple constraint.
+    #[must_use]
+    pub fn with_inverse_predicate(mut self, predicate: NamedNode) -> Self {
+        self.predicate = Some(predicate);
+        self.inverse = true;
+        self
+    }
+
+    /// Sets the cardinality of the triple constraint and the number of matching triples.
+    #[must_use]
+    pub fn with_cardinality(mut self, expected: Cardinality, actual: u32) -> Self {
+        self.expected_cardinality = Some(expected);
+        self.actual_count = Some(actual);
+        self
+    }
+
+    /// Sets why the value does not conform to the value expression.
+    #[must_use]
+    pub fn with_details(mut self, details: Vec<ConstraintViolation>) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Sets why the focus node does not conform to each branch.
+    #[must_use]
+    pub fn with_branches(mut self, branches: Vec<Vec<ConstraintViolation>>) -> Self {
+        self.branches = branches;
+        self
+    }
 }
 
 impl fmt::Display for ConstraintViolation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}: {}", self.constraint_type, self.message)?;
         if let Some(pred) = &self.predicate {
-            write!(f, " [predicate: {}]", pred)?;
+            write!(
+                f,
+                " [predicate: {}{}]",
+                if self.inverse { "^" } else { "" },
+                pred
+            )?;
         }
         if let Some(val) = &self.value {
             write!(f, " [value: {}]", val)?;
@@ -283,6 +403,15 @@ impl From<NamedNodeRef<'_>> for ShapeId {
     }
 }
 
+impl From<&ShapeLabel> for ShapeId {
+    fn from(label: &ShapeLabel) -> Self {
+        match label {
+            ShapeLabel::Iri(n) => ShapeId::Named(n.clone()),
+            ShapeLabel::BNode(b) => ShapeId::Blank(b.as_str().to_owned()),
+        }
+    }
+}
+
 /// The type of constraint that was violated.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConstraintType {
@@ -318,6 +447,8 @@ pub enum ConstraintType {
     ShapeOr,
     /// ShapeNot constraint (shape must not match).
     ShapeNot,
+    /// Value expression of a triple constraint (the values must conform to it).
+    ValueExpression,
     /// Closed shape constraint (no extra properties).
     Closed,
     /// Language constraint.
@@ -345,6 +476,7 @@ impl fmt::Display for ConstraintType {
             ConstraintType::ShapeAnd => write!(f, "ShapeAnd"),
             ConstraintType::ShapeOr => write!(f, "ShapeOr"),
             ConstraintType::ShapeNot => write!(f, "ShapeNot"),
+            ConstraintType::ValueExpression => write!(f, "ValueExpression"),
             ConstraintType::Closed => write!(f, "Closed"),
             ConstraintType::Language => write!(f, "Language"),
             ConstraintType::ValidationError => write!(f, "ValidationError"),
@@ -0,0 +1,63 @@
+//! Shape maps associating focus nodes with the shapes they must conform to.
+
+use crate::model::ShapeLabel;
+use oxrdf::Term;
+
+/// A fixed [shape map](https://shexspec.github.io/shape-map/): a list of (node, shape) associations.
+///
+/// It drives [`ShexValidator::validate_shape_map`](crate::ShexValidator::validate_shape_map).
+///
+/// ```
+/// use oxrdf::NamedNode;
+/// use sparshex::{ShapeLabel, ShapeMap};
+///
+/// let mut shape_map = ShapeMap::new();
+/// shape_map.insert(
+///     NamedNode::new("http://example.com/alice")?,
+///     ShapeLabel::Iri(NamedNode::new("http://example.com/PersonShape")?),
+/// );
+/// assert_eq!(shape_map.len(), 1);
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShapeMap {
+    associations: Vec<(Term, ShapeLabel)>,
+}
+
+impl ShapeMap {
+    /// Creates an empty shape map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Associates a node with a shape it must conform to.
+    pub fn insert(&mut self, node: impl Into<Term>, shape: ShapeLabel) {
+        self.associations.push((node.into(), shape));
+    }
+
+    /// Returns the associations, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Term, &ShapeLabel)> {
+        self.associations.iter().map(|(node, shape)| (node, shape))
+    }
+
+    /// Returns the number of associations.
+    pub fn len(&self) -> usize {
+        self.associations.len()
+    }
+
+    /// Returns true if the shape map has no association.
+    pub fn is_empty(&self) -> bool {
+        self.associations.is_empty()
+    }
+}
+
+impl<T: Into<Term>> FromIterator<(T, ShapeLabel)> for ShapeMap {
+    fn from_iter<I: IntoIterator<Item = (T, ShapeLabel)>>(iter: I) -> Self {
+        Self {
+            associations: iter
+                .into_iter()
+                .map(|(node, shape)| (node.into(), shape))
+                .collect(),
+        }
+    }
+}
@@ -3,30 +3,66 @@
 //! This module implements the core ShEx validation algorithm based on the
 //! ShEx specification: https://shex.io/shex-semantics/
 
-use crate::error::ShexValidationError;
+use crate::error::{ShexError, ShexParseError, ShexValidationError};
+use crate::limits::{self, ValidationLimits};
 use crate::model::{
     NodeConstraint, NumericFacet, Shape, ShapeExpression, ShapeLabel, ShapesSchema, StringFacet,
     TripleConstraint, ValueSetValue,
 };
-use crate::result::ValidationResult;
+use crate::result::{ConstraintType, ConstraintViolation, ShapeId, ShexReport, ValidationResult};
+use crate::shape_map::ShapeMap;
 use oxrdf::{Graph, Literal, NamedNode, Term};
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::cmp::Ordering;
 
-/// Maximum recursion depth for shape validation to prevent infinite loops.
-const MAX_RECURSION_DEPTH: usize = 100;
+/// Maximum size of a compiled PATTERN regular expression.
+const REGEX_SIZE_LIMIT: usize = 1_000_000;
 
 /// ShEx validator for validating RDF graphs against ShEx shapes.
 #[derive(Debug)]
 pub struct ShexValidator {
     schema: ShapesSchema,
+    limits: Option<ValidationLimits>,
 }
 
 impl ShexValidator {
     /// Creates a new validator with the given shapes schema.
+    ///
+    /// No resource limit is enforced, only the nesting of shape references is bounded to 100 levels.
+    /// Use [`with_limits`](Self::with_limits) to validate untrusted schemas or data.
     pub fn new(schema: ShapesSchema) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            limits: None,
+        }
+    }
+
+    /// Creates a new validator with the given shapes schema and resource limits.
+    ///
+    /// Each exceeded limit makes [`validate`](Self::validate) fail with a dedicated [`ShexError`] variant.
+    ///
+    /// ```
+    /// use oxrdf::{Graph, NamedNode, Term};
+    /// use sparshex::{ShapeExpression, ShapeLabel, ShapesSchema, ShexError, ShexValidator, ValidationLimits};
+    ///
+    /// let label = ShapeLabel::Iri(NamedNode::new("http://example.com/Shape")?);
+    /// let mut schema = ShapesSchema::new();
+    /// schema.add_shape(label.clone(), ShapeExpression::ShapeRef(label.clone()));
+    ///
+    /// let validator = ShexValidator::with_limits(schema, ValidationLimits::default().with_max_shape_references(0));
+    /// let node = Term::from(NamedNode::new("http://example.com/alice")?);
+    /// assert!(matches!(
+    ///     validator.validate(&Graph::new(), &node, &label),
+    ///     Err(ShexError::ShapeReferenceLimit { limit: 0 })
+    /// ));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn with_limits(schema: ShapesSchema, limits: ValidationLimits) -> Self {
+        Self {
+            schema,
+            limits: Some(limits),
+        }
     }
 
     /// Returns a reference to the shapes schema.
@@ -34,6 +70,11 @@ impl ShexValidator {
         &self.schema
     }
 
+    /// Returns the resource limits enforced during validation, if any.
+    pub fn limits(&self) -> Option<&ValidationLimits> {
+        self.limits.as_ref()
+    }
+
     /// Validates a node against a specific shape in the schema.
     ///
     /// # Arguments
@@ -48,11 +89,86 @@ impl ShexValidator {
         graph: &Graph,
         node: &Term,
         shape_label: &ShapeLabel,
-    ) -> Result<ValidationResult, ShexValidationError> {
-        let mut context = ValidationContext::new(graph);
+    ) -> Result<ValidationResult, ShexError> {
+        let mut context = ValidationContext::new(
+            graph,
+            self.limits
+                .clone()
+                .unwrap_or_else(ValidationLimits::unbounded),
+            shape_label,
+        );
         self.validate_node_against_shape(&mut context, node, shape_label, 0)
     }
 
+    /// Validates all the nodes of the graph against the start shape of the schema.
+    ///
+    /// The validated nodes are the subjects of the graph triples.
+    /// Fails with [`ShexValidationError::MissingStartShape`] if the schema has no start shape,
+    /// [`validate_shape_map`](Self::validate_shape_map) allows to choose the shape of each node instead.
+    ///
+    /// ```
+    /// use oxrdf::{Graph, NamedNodeRef, TripleRef};
+    /// use sparshex::{ShexValidator, parse_shex};
+    ///
+    /// let schema = parse_shex(
+    ///     "PREFIX ex: <http://example.com/>
+    ///     start = @ex:PersonShape
+    ///     ex:PersonShape { ex:name . }",
+    /// )?;
+    /// let mut graph = Graph::new();
+    /// graph.insert(TripleRef::new(
+    ///     NamedNodeRef::new("http://example.com/alice")?,
+    ///     NamedNodeRef::new("http://example.com/knows")?,
+    ///     NamedNodeRef::new("http://example.com/bob")?,
+    /// ));
+    ///
+    /// let report = ShexValidator::new(schema).validate_all(&graph)?;
+    /// assert!(!report.conforms()); // ex:alice has no ex:name
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn validate_all(&self, graph: &Graph) -> Result<ShexReport, ShexError> {
+        let start = self
+            .schema
+            .start()
+            .ok_or(ShexValidationError::MissingStartShape)?;
+        let mut seen = FxHashSet::default();
+        let shape_map = graph
+            .iter()
+            .map(|t| t.subject)
+            .filter(|subject| seen.insert(*subject))
+            .map(|subject| (subject.into_owned(), start.clone()))
+            .collect::<ShapeMap>();
+        self.validate_shape_map(graph, &shape_map)
+    }
+
+    /// Validates each node of the shape map against its associated shape.
+    ///
+    /// The [`ValidationLimits`] apply to the validation of each node of the shape map separately,
+    /// like if [`validate`](Self::validate) was called for each of them.
+    pub fn validate_shape_map(
+        &self,
+        graph: &Graph,
+        shape_map: &ShapeMap,
+    ) -> Result<ShexReport, ShexError> {
+        let mut report = ShexReport::new();
+        // Only the compiled regular expressions are shared between the nodes
+        let mut regex_cache = FxHashMap::default();
+        for (node, shape_label) in shape_map.iter() {
+            let mut context = ValidationContext::new(
+                graph,
+                self.limits
+                    .clone()
+                    .unwrap_or_else(ValidationLimits::unbounded),
+                shape_label,
+            );
+            context.regex_cache = regex_cache;
+            let result = self.validate_node_against_shape(&mut context, node, shape_label, 0)?;
+            regex_cache = context.regex_cache;
+            report.add_result(node.clone(), shape_label.clone(), result);
+        }
+        Ok(report)
+    }
+
     /// Validates a node against a shape expression.
     fn validate_node_against_shape(
         &self,
@@ -60,10 +176,11 @@ impl ShexValidator {
         node: &Term,
         shape_label: &ShapeLabel,
         depth: usize,
-    ) -> Result<ValidationResult, ShexValidationError> {
-        if depth > MAX_RECURSION_DEPTH {
-            return Err(ShexValidationError::max_recursion_depth(depth));
+    ) -> Result<ValidationResult, ShexError> {
+        if depth > context.usage.limits().max_recursion_depth {
+            return Err(ShexValidationError::max_recursion_depth(depth).into());
         }
+        context.usage.check_timeout()?;
 
         // Check if we've already validated this (node, shape) pair to detect cycles
         let key = (node.clone(), shape_label.clone());
@@ -79,7 +196,9 @@ impl ShexValidator {
             .get_shape(shape_label)
             .ok_or_else(|| ShexValidationError::shape_not_found(shape_label.to_string()))?;
 
+        let previous_shape = std::mem::replace(&mut context.current_shape, shape_label.into());
         let result = self.validate_shape_expression(context, node, shape, depth)?;
+        context.current_shape = previous_shape;
 
         context.visited.remove(&key);
 
@@ -93,13 +212,14 @@ impl ShexValidator {
         node: &Term,
         shape_expr: &ShapeExpression,
         depth: usize,
-    ) -> Result<ValidationResult, ShexValidationError> {
+    ) -> Result<ValidationResult, ShexError> {
         match shape_expr {
             ShapeExpression::NodeConstraint(nc) => {
                 self.validate_node_constraint(context, node, nc)
             }
             ShapeExpression::Shape(shape) => self.validate_shape(context, node, shape, depth),
             ShapeExpression::ShapeRef(label) => {
+                context.usage.record_shape_reference()?;
                 self.validate_node_against_shape(context, node, label, depth + 1)
             }
             ShapeExpression::ShapeAnd(shapes) => {
@@ -124,16 +244,25 @@ impl ShexValidator {
         context: &mut ValidationContext<'_>,
         node: &Term,
         constraint: &NodeConstraint,
-    ) -> Result<ValidationResult, ShexValidationError> {
-        let mut errors = Vec::new();
+    ) -> Result<ValidationResult, ShexError> {
+        let mut violations = Vec::new();
+        let shape_id = context.current_shape.clone();
+        let mut fail = |constraint_type, message: String| {
+            violations.push(ConstraintViolation::new(
+                node.clone(),
+                shape_id.clone(),
+                constraint_type,
+                message,
+            ));
+        };
 
         // Check node kind
         if let Some(node_kind) = &constraint.node_kind {
             if !node_kind.matches(node) {
-                errors.push(format!(
-                    "Node {} does not match node kind {}",
-                    node, node_kind
-                ));
+                fail(
+                    ConstraintType::NodeKind,
+                    format!("Node {node} does not match node kind {node_kind}"),
+                );
             }
         }
 
@@ -142,15 +271,21 @@ impl ShexValidator {
             match node {
                 Term::Literal(lit) => {
                     if lit.datatype() != datatype.as_ref() {
-                        errors.push(format!(
-                            "Literal datatype {} does not match expected {}",
-                            lit.datatype(),
-                            datatype
-                        ));
+                        fail(
+                            ConstraintType::Datatype,
+                            format!(
+                                "Literal datatype {} does not match expected {}",
+                                lit.datatype(),
+                                datatype
+                            ),
+                        );
                     }
                 }
                 _ => {
-                    errors.push("Datatype constraint requires a literal".to_string());
+                    fail(
+                        ConstraintType::Datatype,
+                        "Datatype constraint requires a literal".to_owned(),
+                    );
                 }
             }
         }
@@ -161,23 +296,29 @@ impl ShexValidator {
                 StringFacet::MinLength(min) => {
                     let len = get_string_length(node);
                     if len < *min {
-                        errors.push(format!(
-                            "String length {} is less than minimum {}",
-                            len, min
-                        ));
+                        fail(
+                            ConstraintType::MinLength,
+                            format!("String length {len} is less than minimum {min}"),
+                        );
                     }
                 }
                 StringFacet::MaxLength(max) => {
                     let len = get_string_length(node);
                     if len > *max {
-                        errors.push(format!("String length {} exceeds maximum {}", len, max));
+                        fail(
+                            ConstraintType::MaxLength,
+                            format!("String length {len} exceeds maximum {max}"),
+                        );
                     }
                 }
                 StringFacet::Pattern { pattern, flags } => {
                     let regex = context.get_or_compile_regex(pattern, flags.as_deref())?;
                     let string_value = get_string_value(node);
                     if !regex.is_match(&string_value) {
-                        errors.push(format!("Value does not match pattern '{}'", pattern));
+                        fail(
+                            ConstraintType::Pattern,
+                            format!("Value does not match pattern '{pattern}'"),
+                        );
                     }
                 }
             }
@@ -189,40 +330,49 @@ impl ShexValidator {
                 NumericFacet::MinInclusive(min) => {
                     if let Some(cmp) = compare_values(node, &min.value) {
                         if cmp == Ordering::Less {
-                            errors.push(format!(
-                                "Value is less than minimum inclusive {}",
-                                min.value.value()
-                            ));
+                            fail(
+                                ConstraintType::MinValue,
+                                format!(
+                                    "Value is less than minimum inclusive {}",
+                                    min.value.value()
+                                ),
+                            );
                         }
                     }
                 }
                 NumericFacet::MinExclusive(min) => {
                     if let Some(cmp) = compare_values(node, &min.value) {
                         if cmp != Ordering::Greater {
-                            errors.push(format!(
-                                "Value is not greater than minimum exclusive {}",
-                                min.value.value()
-                            ));
+                            fail(
+                                ConstraintType::MinValue,
+                                format!(
+                                    "Value is not greater than minimum exclusive {}",
+                                    min.value.value()
+                                ),
+                            );
                         }
                     }
                 }
                 NumericFacet::MaxInclusive(max) => {
                     if let Some(cmp) = compare_values(node, &max.value) {
                         if cmp == Ordering::Greater {
-                            errors.push(format!(
-                                "Value exceeds maximum inclusive {}",
-                                max.value.value()
-                            ));
+                            fail(
+                                ConstraintType::MaxValue,
+                                format!("Value exceeds maximum inclusive {}", max.value.value()),
+                            );
                         }
                     }
                 }
                 NumericFacet::MaxExclusive(max) => {
                     if let Some(cmp) = compare_values(node, &max.value) {
                         if cmp != Ordering::Less {
-                            errors.push(format!(
-                                "Value is not less than maximum exclusive {}",
-                                max.value.value()
-                            ));
+                            fail(
+                                ConstraintType::MaxValue,
+                                format!(
+                                    "Value is not less than maximum exclusive {}",
+                                    max.value.value()
+                                ),
+                            );
                         }
                     }
                 }
@@ -234,17 +384,19 @@ impl ShexValidator {
 
         // Check value set
         if !constraint.values.is_empty() {
+            context
+                .usage
+                .validate_list_length(constraint.values.len())?;
             let matches = constraint.values.iter().any(|v| matches_value_set(node, v));
             if !matches {
-                errors.push("Value is not in the allowed value set".to_string());
+                fail(
+                    ConstraintType::Values,
+                    "Value is not in the allowed value set".to_owned(),
+                );
             }
         }
 
-        if errors.is_empty() {
-            Ok(ValidationResult::valid())
-        } else {
-            Ok(ValidationResult::invalid(errors))
-        }
+        Ok(ValidationResult::from_violations(violations))
     }
 
     /// Validates a node against a shape (triple constraints).
@@ -254,43 +406,108 @@ impl ShexValidator {
         node: &Term,
         shape: &Shape,
         depth: usize,
-    ) -> Result<ValidationResult, ShexValidationError> {
-        let mut errors = Vec::new();
+    ) -> Result<ValidationResult, ShexError> {
+        // The constraints of the extended shapes apply as if they were declared by this shape
+        let mut extended = ExtendedShape::default();
+        self.collect_extended_shape(context, node, shape, depth, &mut extended)?;
+        let mut violations = extended.violations;
 
         // Get all triples where node is the subject
+        // They are charged once to the budget shared by all the shapes evaluated
+        // during the validation of the focus node
         let triples = get_triples_for_subject(context.graph, node);
+        context.usage.record_triples_examined(triples.len())?;
 
         // Validate each triple constraint
-        for tc in &shape.triple_constraints {
-            let tc_errors =
-                self.validate_triple_constraint(context, node, &triples, tc, depth + 1)?;
-            errors.extend(tc_errors);
+        // The triples are partitioned between the constraints sharing a predicate and a direction,
+        // this happens when the shape and the shapes it extends constrain the same predicate
+        let mut validated = FxHashSet::default();
+        for tc in &extended.triple_constraints {
+            if !validated.insert((&tc.predicate, tc.inverse)) {
+                continue;
+            }
+            let group = extended
+                .triple_constraints
+                .iter()
+                .copied()
+                .filter(|other| other.predicate == tc.predicate && other.inverse == tc.inverse)
+                .collect::<Vec<_>>();
+            let tc_violations = if let [tc] = group.as_slice() {
+                self.validate_triple_constraint(context, node, &triples, tc, depth + 1)?
+            } else {
+                self.validate_partitioned_triple_constraints(
+                    context,
+                    node,
+                    &triples,
+                    &group,
+                    depth + 1,
+                )?
+            };
+            violations.extend(tc_violations);
         }
 
         // Check closed shape constraint
         if shape.closed {
-            let allowed_predicates: FxHashSet<_> = shape
+            let allowed_predicates: FxHashSet<_> = extended
                 .triple_constraints
                 .iter()
-                .map(|tc| tc.predicate.clone())
-                .chain(shape.extra.iter().cloned())
+                .map(|tc| &tc.predicate)
+                .chain(extended.extra.iter().copied())
                 .collect();
 
             for triple in &triples {
                 if !allowed_predicates.contains(&triple.predicate) {
-                    errors.push(format!(
-                        "Closed shape violation: unexpected predicate {}",
-                        triple.predicate
-                    ));
+                    violations.push(
+                        ConstraintViolation::new(
+                            node.clone(),
+                            context.current_shape.clone(),
+                            ConstraintType::Closed,
+                            format!(
+                                "Closed shape violation: unexpected predicate {}",
+                                triple.predicate
+                            ),
+                        )
+                        .with_predicate(triple.predicate.clone()),
+                    );
                 }
             }
         }
 
-        if errors.is_empty() {
-            Ok(ValidationResult::valid())
-        } else {
-            Ok(ValidationResult::invalid(errors))
+        Ok(ValidationResult::from_violations(violations))
+    }
+
+    /// Collects the triple constraints and extra predicates of `shape` and of the shapes it extends.
+    ///
+    /// The extended shape expressions that are not shapes are validated against the node directly.
+    fn collect_extended_shape<'s>(
+        &'s self,
+        context: &mut ValidationContext<'_>,
+        node: &Term,
+        shape: &'s Shape,
+        depth: usize,
+        extended: &mut ExtendedShape<'s>,
+    ) -> Result<(), ShexError> {
+        extended
+            .triple_constraints
+            .extend(&shape.triple_constraints);
+        extended.extra.extend(&shape.extra);
+        for label in shape.extends() {
+            if !extended.visited.insert(label) {
+                continue;
+            }
+            context.usage.record_shape_reference()?;
+            let base = self
+                .schema
+                .get_shape(label)
+                .ok_or_else(|| ShexValidationError::shape_not_found(label.to_string()))?;
+            if let ShapeExpression::Shape(base) = &**base {
+                self.collect_extended_shape(context, node, base, depth + 1, extended)?;
+            } else {
+                let result = self.validate_shape_expression(context, node, base, depth + 1)?;
+                extended.violations.extend_from_slice(result.violations());
+            }
         }
+        Ok(())
     }
 
     /// Validates a triple constraint against a set of triples.
@@ -301,29 +518,27 @@ impl ShexValidator {
         all_triples: &[TriplePattern],
         tc: &TripleConstraint,
         depth: usize,
-    ) -> Result<Vec<String>, ShexValidationError> {
-        let mut errors = Vec::new();
+    ) -> Result<Vec<ConstraintViolation>, ShexError> {
+        let mut violations = Vec::new();
 
         // Get matching triples (subject matches focus node, predicate matches constraint)
-        let matching_triples: Vec<_> = all_triples
-            .iter()
-            .filter(|t| {
-                if tc.inverse {
-                    &t.object == focus_node && &t.predicate == &tc.predicate
-                } else {
-                    &t.subject == focus_node && &t.predicate == &tc.predicate
-                }
-            })
-            .collect();
+        let mut matching_triples = Vec::new();
+        for t in all_triples {
+            let matches = if tc.inverse {
+                t.object == *focus_node && t.predicate == tc.predicate
+            } else {
+                t.subject == *focus_node && t.predicate == tc.predicate
+            };
+            if matches {
+                matching_triples.push(t);
+            }
+        }
 
         let count = matching_triples.len() as u32;
 
         // Check cardinality
         if !tc.cardinality.allows(count) {
-            errors.push(format!(
-                "Cardinality violation for predicate {}: expected {}, found {}",
-                tc.predicate, tc.cardinality, count
-            ));
+            violations.push(context.cardinality_violation(focus_node, tc, count));
         }
 
         // Validate values against value expression if present
@@ -337,17 +552,97 @@ impl ShexValidator {
 
                 let result = self.validate_shape_expression(context, value, value_expr, depth)?;
                 if !result.is_valid() {
-                    for error in result.errors() {
-                        errors.push(format!(
-                            "Value {} for predicate {} failed validation: {}",
-                            value, tc.predicate, error
-                        ));
+                    violations.push(context.value_violation(
+                        focus_node,
+                        tc,
+                        value,
+                        result.violations().to_vec(),
+                    ));
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Validates triple constraints sharing a predicate and a direction against a set of triples.
+    ///
+    /// Each matching triple must be assigned to exactly one of the constraints
+    /// such that its value conforms to the value expression of the constraint
+    /// and that the cardinalities of all the constraints are satisfied.
+    fn validate_partitioned_triple_constraints(
+        &self,
+        context: &mut ValidationContext<'_>,
+        focus_node: &Term,
+        all_triples: &[TriplePattern],
+        tcs: &[&TripleConstraint],
+        depth: usize,
+    ) -> Result<Vec<ConstraintViolation>, ShexError> {
+        let mut violations = Vec::new();
+        let (predicate, inverse) = (&tcs[0].predicate, tcs[0].inverse);
+
+        let values = all_triples
+            .iter()
+            .filter_map(|t| {
+                if inverse {
+                    (t.object == *focus_node && t.predicate == *predicate).then_some(&t.subject)
+                } else {
+                    (t.subject == *focus_node && t.predicate == *predicate).then_some(&t.object)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // The constraints each value conforms to, and why it does not conform to the other ones
+        let mut candidates = Vec::with_capacity(values.len());
+        let mut value_violations = Vec::with_capacity(values.len());
+        for value in &values {
+            let mut value_candidates = Vec::new();
+            let mut value_violation = Vec::new();
+            for (i, tc) in tcs.iter().enumerate() {
+                if let Some(value_expr) = &tc.value_expr {
+                    let result =
+                        self.validate_shape_expression(context, value, value_expr, depth)?;
+                    if !result.is_valid() {
+                        value_violation.extend_from_slice(result.violations());
+                        continue;
                     }
                 }
+                value_candidates.push(i);
+            }
+            candidates.push(value_candidates);
+            value_violations.push(value_violation);
+        }
+
+        // The minimal cardinalities are reached first, the remaining values are then assigned up to the maximal ones
+        let mut partition = Partition::new(&candidates, tcs.len());
+        partition.assign(|i| tcs[i].cardinality.min);
+        partition.assign(|i| tcs[i].cardinality.max.unwrap_or(u32::MAX));
+
+        // The unassigned values conforming to some constraints that are all full
+        // are counted as exceeding the first of them
+        let mut counts = partition.counts.clone();
+        for (i, value) in values.iter().enumerate() {
+            if partition.assignment[i].is_some() {
+                continue;
+            }
+            if let Some(&first) = candidates[i].first() {
+                counts[first] += 1;
+            } else {
+                violations.push(context.value_violation(
+                    focus_node,
+                    tcs[0],
+                    value,
+                    std::mem::take(&mut value_violations[i]),
+                ));
+            }
+        }
+        for (tc, count) in tcs.iter().zip(counts) {
+            if !tc.cardinality.allows(count) {
+                violations.push(context.cardinality_violation(focus_node, tc, count));
             }
         }
 
-        Ok(errors)
+        Ok(violations)
     }
 
     /// Validates ShapeAnd (all shapes must match).
@@ -357,21 +652,15 @@ impl ShexValidator {
         node: &Term,
         shapes: &[ShapeExpression],
         depth: usize,
-    ) -> Result<ValidationResult, ShexValidationError> {
-        let mut all_errors = Vec::new();
+    ) -> Result<ValidationResult, ShexError> {
+        let mut all_violations = Vec::new();
 
         for shape in shapes {
             let result = self.validate_shape_expression(context, node, shape, depth + 1)?;
-            if !result.is_valid() {
-                all_errors.extend(result.errors().to_vec());
-            }
+            all_violations.extend_from_slice(result.violations());
         }
 
-        if all_errors.is_empty() {
-            Ok(ValidationResult::valid())
-        } else {
-            Ok(ValidationResult::invalid(all_errors))
-        }
+        Ok(ValidationResult::from_violations(all_violations))
     }
 
     /// Validates ShapeOr (at least one shape must match).
@@ -381,19 +670,29 @@ impl ShexValidator {
         node: &Term,
         shapes: &[ShapeExpression],
         depth: usize,
-    ) -> Result<ValidationResult, ShexValidationError> {
+    ) -> Result<ValidationResult, ShexError> {
+        let mut branches = Vec::with_capacity(shapes.len());
         for shape in shapes {
             let result = self.validate_shape_expression(context, node, shape, depth + 1)?;
             if result.is_valid() {
                 return Ok(ValidationResult::valid());
             }
+            branches.push(result.violations().to_vec());
         }
 
-        Ok(ValidationResult::invalid(vec![format!(
-            "ShapeOr violation: node {} does not match any of the {} shapes",
-            node,
-            shapes.len()
-        )]))
+        let message = format!(
+            "ShapeOr violation: node {node} does not match any of the {} shapes",
+            branches.len()
+        );
+        Ok(ValidationResult::from_violations(vec![
+            ConstraintViolation::new(
+                node.clone(),
+                context.current_shape.clone(),
+                ConstraintType::ShapeOr,
+                message,
+            )
+            .with_branches(branches),
+        ]))
     }
 
     /// Validates ShapeNot (shape must not match).
@@ -403,13 +702,17 @@ impl ShexValidator {
         node: &Term,
         shape: &ShapeExpression,
         depth: usize,
-    ) -> Result<ValidationResult, ShexValidationError> {
+    ) -> Result<ValidationResult, ShexError> {
         let result = self.validate_shape_expression(context, node, shape, depth + 1)?;
         if result.is_valid() {
-            Ok(ValidationResult::invalid(vec![format!(
-                "ShapeNot violation: node {} matches the negated shape",
-                node
-            )]))
+            Ok(ValidationResult::from_violations(vec![
+                ConstraintViolation::new(
+                    node.clone(),
+                    context.current_shape.clone(),
+                    ConstraintType::ShapeNot,
+                    format!("ShapeNot violation: node {node} matches the negated shape"),
+                ),
+            ]))
         } else {
             Ok(ValidationResult::valid())
         }
@@ -420,57 +723,126 @@ impl ShexValidator {
 struct ValidationContext<'a> {
     /// The data graph being validated.
     graph: &'a Graph,
+    /// Resources consumed so far, checked against the validator limits.
+    usage: limits::ValidationContext,
     /// Set of (node, shape_label) pairs currently being validated (for cycle detection).
     visited: FxHashSet<(Term, ShapeLabel)>,
     /// Cache of compiled regular expressions.
-    regex_cache: FxHashMap<String, Regex>,
+    regex_cache: FxHashMap<(String, String), Regex>,
+    /// The labelled shape currently being validated, the violations refer to it.
+    current_shape: ShapeId,
 }
 
 impl<'a> ValidationContext<'a> {
-    /// Creates a new validation context.
-    fn new(graph: &'a Graph) -> Self {
+    /// Creates a new validation context for the validation against the shape with the given label.
+    fn new(graph: &'a Graph, limits: ValidationLimits, shape_label: &ShapeLabel) -> Self {
         Self {
             graph,
+            usage: limits::ValidationContext::new(limits),
             visited: FxHashSet::default(),
             regex_cache: FxHashMap::default(),
+            current_shape: shape_label.into(),
         }
     }
 
+    /// Builds the violation of the cardinality of a triple constraint.
+    fn cardinality_violation(
+        &self,
+        focus_node: &Term,
+        tc: &TripleConstraint,
+        count: u32,
+    ) -> ConstraintViolation {
+        let constraint_type = if tc.cardinality.max == Some(tc.cardinality.min) {
+            ConstraintType::ExactCardinality
+        } else if count < tc.cardinality.min {
+            ConstraintType::MinCardinality
+        } else {
+            ConstraintType::MaxCardinality
+        };
+        let violation = ConstraintViolation::new(
+            focus_node.clone(),
+            self.current_shape.clone(),
+            constraint_type,
+            format!(
+                "Cardinality violation for predicate {}{}: expected {}, found {count}",
+                if tc.inverse { "^" } else { "" },
+                tc.predicate,
+                tc.cardinality
+            ),
+        )
+        .with_cardinality(tc.cardinality, count);
+        with_triple_constraint(violation, tc)
+    }
+
+    /// Builds the violation of the value expression of a triple constraint by one of its values.
+    fn value_violation(
+        &self,
+        focus_node: &Term,
+        tc: &TripleConstraint,
+        value: &Term,
+        details: Vec<ConstraintViolation>,
+    ) -> ConstraintViolation {
+        let message = format!(
+            "Value {value} for predicate {}{} failed validation: {}",
+            if tc.inverse { "^" } else { "" },
+            tc.predicate,
+            details
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+        let violation = ConstraintViolation::new(
+            focus_node.clone(),
+            self.current_shape.clone(),
+            ConstraintType::ValueExpression,
+            message,
+        )
+        .with_value(value.clone())
+        .with_details(details);
+        with_triple_constraint(violation, tc)
+    }
+
     /// Gets or compiles a regular expression with optional flags.
     fn get_or_compile_regex(
         &mut self,
         pattern: &str,
         flags: Option<&str>,
-    ) -> Result<&Regex, ShexValidationError> {
-        let key = format!(
-            "{}{}",
-            pattern,
-            flags.map_or(String::new(), |f| format!("/{}", f))
-        );
+    ) -> Result<&Regex, ShexError> {
+        let key = (pattern.to_owned(), flags.unwrap_or_default().to_owned());
 
         if !self.regex_cache.contains_key(&key) {
-            let mut regex_pattern = String::new();
-
-            // Handle flags
-            if let Some(f) = flags {
-                if f.contains('i') {
-                    regex_pattern.push_str("(?i)");
-                }
-                if f.contains('m') {
-                    regex_pattern.push_str("(?m)");
-                }
-                if f.contains('s') {
-                    regex_pattern.push_str("(?s)");
+            self.usage.validate_regex_length(pattern)?;
+
+            // The regex crate matches in linear time, the compiled program size is bounded too
+            let mut regex_builder = RegexBuilder::new(pattern);
+            regex_builder.size_limit(REGEX_SIZE_LIMIT);
+            for flag in flags.unwrap_or_default().chars() {
+                match flag {
+                    'i' => {
+                        regex_builder.case_insensitive(true);
+                    }
+                    'm' => {
+                        regex_builder.multi_line(true);
+                    }
+                    's' => {
+                        regex_builder.dot_matches_new_line(true);
+                    }
+                    'x' => {
+                        regex_builder.ignore_whitespace(true);
+                    }
+                    _ => {
+                        return Err(ShexParseError::invalid_regex(
+                            pattern,
+                            format!("unsupported flag '{flag}'"),
+                        )
+                        .into());
+                    }
                 }
             }
-
-            regex_pattern.push_str(pattern);
-
-            let regex = Regex::new(&regex_pattern).map_err(|e| {
-                ShexValidationError::Internal {
-                    message: format!("Invalid regex pattern '{}': {}", pattern, e),
-                }
-            })?;
+            let regex = regex_builder
+                .build()
+                .map_err(|e| ShexParseError::invalid_regex(pattern, e.to_string()))?;
 
             self.regex_cache.insert(key.clone(), regex);
         }
@@ -479,10 +851,112 @@ impl<'a> ValidationContext<'a> {
             ShexValidationError::Internal {
                 message: "regex cache miss".to_string(),
             }
+            .into()
         })
     }
 }
 
+/// A shape together with the shapes it extends.
+#[derive(Default)]
+struct ExtendedShape<'s> {
+    /// Triple constraints of the shape and of the extended shapes.
+    triple_constraints: Vec<&'s TripleConstraint>,
+    /// Extra predicates of the shape and of the extended shapes.
+    extra: Vec<&'s NamedNode>,
+    /// Violations of the extended shape expressions that are not shapes.
+    violations: Vec<ConstraintViolation>,
+    /// Extended shapes already collected.
+    visited: FxHashSet<&'s ShapeLabel>,
+}
+
+/// An assignment of values to the triple constraints they conform to, each value to at most one constraint.
+struct Partition<'a> {
+    /// The constraints each value conforms to.
+    candidates: &'a [Vec<usize>],
+    /// The constraint each value is assigned to.
+    assignment: Vec<Option<usize>>,
+    /// The values assigned to each constraint.
+    assigned: Vec<Vec<usize>>,
+    /// The number of values assigned to each constraint.
+    counts: Vec<u32>,
+}
+
+impl<'a> Partition<'a> {
+    fn new(candidates: &'a [Vec<usize>], constraint_count: usize) -> Self {
+        Self {
+            candidates,
+            assignment: vec![None; candidates.len()],
+            assigned: vec![Vec::new(); constraint_count],
+            counts: vec![0; constraint_count],
+        }
+    }
+
+    /// Assigns as many values as possible, at most `capacity(constraint)` to each constraint.
+    ///
+    /// It is a maximal bipartite matching built with augmenting paths.
+    /// The number of values assigned to a constraint never decreases,
+    /// so the capacities can be raised between calls.
+    fn assign(&mut self, capacity: impl Fn(usize) -> u32) {
+        for value in 0..self.assignment.len() {
+            if self.assignment[value].is_none() {
+                let mut visited = vec![false; self.counts.len()];
+                self.augment(value, &capacity, &mut visited);
+            }
+        }
+    }
+
+    /// Assigns `value`, moving already assigned values to other constraints if needed.
+    fn augment(
+        &mut self,
+        value: usize,
+        capacity: &impl Fn(usize) -> u32,
+        visited: &mut [bool],
+    ) -> bool {
+        let candidates = self.candidates;
+        for &constraint in &candidates[value] {
+            if !visited[constraint] && self.counts[constraint] < capacity(constraint) {
+                self.set(value, constraint);
+                return true;
+            }
+        }
+        for &constraint in &candidates[value] {
+            if visited[constraint] {
+                continue;
+            }
+            visited[constraint] = true;
+            for i in 0..self.assigned[constraint].len() {
+                let other = self.assigned[constraint][i];
+                if other != value && self.augment(other, capacity, visited) {
+                    self.set(value, constraint);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn set(&mut self, value: usize, constraint: usize) {
+        if let Some(previous) = self.assignment[value].replace(constraint) {
+            self.counts[previous] -= 1;
+            self.assigned[previous].retain(|v| *v != value);
+        }
+        self.counts[constraint] += 1;
+        self.assigned[constraint].push(value);
+    }
+}
+
+/// Sets the predicate and the direction of the triple constraint of a violation.
+fn with_triple_constraint(
+    violation: ConstraintViolation,
+    tc: &TripleConstraint,
+) -> ConstraintViolation {
+    if tc.inverse {
+        violation.with_inverse_predicate(tc.predicate.clone())
+    } else {
+        violation.with_predicate(tc.predicate.clone())
+    }
+}
+
 /// A simple triple pattern for validation.
 #[derive(Debug, Clone, PartialEq)]
 struct TriplePattern {
@@ -626,6 +1100,7 @@ mod tests {
         let schema = ShapesSchema::new();
         let validator = ShexValidator::new(schema);
         assert!(validator.schema().is_empty());
+        assert!(validator.limits().is_none());
     }
 
     #[test]
@@ -3,20 +3,16 @@
 //! These tests verify that ShEx validation handles malicious or resource-intensive
 //! inputs safely without stack overflow, unbounded memory, or exponential complexity.
 //!
-//! CRITICAL FINDINGS (as of test creation):
-//! 1. ValidationLimits exists in limits.rs but is NOT integrated into validator.rs
-//! 2. Validator uses hardcoded MAX_RECURSION_DEPTH = 100
-//! 3. No timeout enforcement
-//! 4. No memory limits
-//! 5. No cardinality explosion detection
-//!
-//! These tests DOCUMENT the current state and will FAIL if security issues exist.
+//! The resource limits of `ValidationLimits` are enforced by `ShexValidator::with_limits`, each
+//! exceeded limit is reported with a dedicated `ShexError` variant.
 
 use oxrdf::{Graph, Literal, NamedNode, Term, Triple};
 use sparshex::{
     Cardinality, NodeConstraint, NodeKind, Shape, ShapeExpression, ShapeLabel, ShapesSchema,
-    ShexValidator, TripleConstraint,
+    ShexError, ShexParseError, ShexValidator, StringFacet, TripleConstraint, ValidationLimits,
+    ValueSetValue,
 };
+use std::time::Duration;
 
 // ============================================================================
 // Test Helper Functions
@@ -245,7 +241,7 @@ fn test_shex_deep_shape_and_nesting() {
 
 #[test]
 fn test_shex_max_recursion_depth_exceeded() {
-    // Create a very deep chain exceeding MAX_RECURSION_DEPTH (100)
+    // Create a very deep chain exceeding the default max_recursion_depth (100)
     let mut schema = ShapesSchema::new();
 
     // Create 150 shapes in a chain (exceeds limit of 100)
@@ -280,7 +276,7 @@ fn test_shex_max_recursion_depth_exceeded() {
     let root_node = Term::NamedNode(nn("http://example.org/node0"));
     let root_shape = shape_label("http://example.org/Deep0");
 
-    // This should hit the MAX_RECURSION_DEPTH limit
+    // This should hit the max_recursion_depth limit
     let result = validator.validate(&graph, &root_node, &root_shape);
 
     // ASSERTION: Should return an error (MaxRecursionDepth), not panic
@@ -308,143 +304,313 @@ fn test_shex_max_recursion_depth_exceeded() {
 }
 
 // ============================================================================
-// Test 6: Regex Pattern Complexity (Potential ReDoS)
+// Test 6: Regex Pattern Length (Potential ReDoS)
 // ============================================================================
 
 #[test]
-fn test_shex_regex_complexity() {
-    // This test would check regex pattern validation
-    // Currently ShEx doesn't expose regex limits, but we document the risk
+fn test_shex_regex_length_limit() {
+    // Patterns longer than ValidationLimits.max_regex_length are rejected before compilation
+    let pattern = "(a+)+b".repeat(10);
 
-    // Create a pattern that could be exploited for ReDoS
-    let pattern = "(a+)+b";
+    let mut nc = NodeConstraint::new();
+    nc.string_facets.push(StringFacet::Pattern {
+        pattern: pattern.clone(),
+        flags: None,
+    });
 
-    // NOTE: The current implementation doesn't enforce regex length limits
-    // ValidationLimits.max_regex_length exists but is NOT integrated
+    let mut schema = ShapesSchema::new();
+    let shape_label = shape_label("http://example.org/PatternShape");
+    schema.add_shape(shape_label.clone(), ShapeExpression::NodeConstraint(nc));
 
-    println!("WARNING: Regex pattern limits (ValidationLimits.max_regex_length) exist but are NOT enforced in validator");
-    println!("Potential ReDoS risk with patterns like: {}", pattern);
+    let graph = Graph::new();
+    let node = Term::Literal(Literal::new_simple_literal("aaaa"));
 
-    // This test passes because we're just documenting the issue
-    assert!(true, "Regex limits not enforced - see ValidationLimits integration gap");
+    let validator = ShexValidator::with_limits(
+        schema,
+        ValidationLimits::default().with_max_regex_length(10),
+    );
+    let result = validator.validate(&graph, &node, &shape_label);
+    assert!(
+        matches!(
+            result,
+            Err(ShexError::RegexTooLong { length, limit: 10 }) if length == pattern.len()
+        ),
+        "Expected a regex length error, got: {result:?}"
+    );
 }
 
-// ============================================================================
-// Test 7: Timeout Enforcement (NOT IMPLEMENTED)
-// ============================================================================
-
 #[test]
-fn test_shex_timeout_not_enforced() {
-    // ValidationLimits has timeout support, but it's NOT integrated into ShexValidator
+fn test_shex_regex_pathological_pattern_is_linear() {
+    // (a+)+b is catastrophic for backtracking engines on a long run of 'a' without 'b'
+    let mut nc = NodeConstraint::new();
+    nc.string_facets.push(StringFacet::Pattern {
+        pattern: "^(a+)+b$".to_owned(),
+        flags: None,
+    });
 
-    // Create a complex validation scenario
     let mut schema = ShapesSchema::new();
-    let mut shape = Shape::new();
+    let shape_label = shape_label("http://example.org/PatternShape");
+    schema.add_shape(shape_label.clone(), ShapeExpression::NodeConstraint(nc));
 
-    // Add many triple constraints
-    for i in 0..100 {
-        let mut tc = TripleConstraint::new(nn(&format!("http://example.org/prop{}", i)));
-        tc.cardinality = Cardinality::zero_or_more();
-        shape.add_triple_constraint(tc);
-    }
+    let node = Term::Literal(Literal::new_simple_literal(format!(
+        "{}c",
+        "a".repeat(100_000)
+    )));
+    let validator = ShexValidator::new(schema);
 
-    let shape_label = shape_label("http://example.org/ComplexShape");
-    schema.add_shape(shape_label.clone(), ShapeExpression::Shape(shape));
+    let start = std::time::Instant::now();
+    let result = validator
+        .validate(&Graph::new(), &node, &shape_label)
+        .unwrap();
+    let elapsed = start.elapsed();
 
-    let graph = Graph::new();
-    let validator = ShexValidator::new(schema);
-    let node = Term::NamedNode(nn("http://example.org/test"));
+    assert!(!result.is_valid());
+    assert!(
+        elapsed.as_secs() < 1,
+        "Pathological pattern took too long: {elapsed:?}"
+    );
+}
 
-    // NOTE: There's no way to configure a timeout!
-    // ValidationLimits.timeout exists but ShexValidator doesn't accept it
+#[test]
+fn test_shex_regex_compiled_size_limit() {
+    // A short pattern that expands to a huge program is rejected instead of being compiled
+    let pattern = "\\w{1000}{1000}";
 
-    let result = validator.validate(&graph, &node, &shape_label);
+    let mut nc = NodeConstraint::new();
+    nc.string_facets.push(StringFacet::Pattern {
+        pattern: pattern.to_owned(),
+        flags: None,
+    });
 
-    println!("WARNING: Timeout limits (ValidationLimits.timeout) exist but cannot be configured");
-    println!("ShexValidator::new() doesn't accept ValidationLimits parameter");
+    let mut schema = ShapesSchema::new();
+    let shape_label = shape_label("http://example.org/PatternShape");
+    schema.add_shape(shape_label.clone(), ShapeExpression::NodeConstraint(nc));
 
-    assert!(result.is_ok(), "Validation completed (no timeout enforcement exists)");
+    let node = Term::Literal(Literal::new_simple_literal("aaaa"));
+    let result = ShexValidator::new(schema).validate(&Graph::new(), &node, &shape_label);
+    assert!(
+        matches!(
+            result,
+            Err(ShexError::Parse(ShexParseError::InvalidRegex { .. }))
+        ),
+        "Expected an invalid regex error, got: {result:?}"
+    );
 }
 
 // ============================================================================
-// Test 8: Memory Bounds (NOT IMPLEMENTED)
+// Test 7: Timeout Enforcement
 // ============================================================================
 
 #[test]
-fn test_shex_memory_bounds_not_tracked() {
-    // ValidationLimits has max_triples_examined, but it's NOT used
+fn test_shex_timeout_enforced() {
+    // Create a schema that evaluates many shapes
+    let mut schema = ShapesSchema::new();
+    let mut or_shapes = Vec::new();
+    for i in 0..100 {
+        let ref_label = shape_label(&format!("http://example.org/RefShape{i}"));
+        or_shapes.push(ShapeExpression::ShapeRef(ref_label.clone()));
+        schema.add_shape(
+            ref_label,
+            ShapeExpression::NodeConstraint(NodeConstraint::with_node_kind(NodeKind::Literal)),
+        );
+    }
+    let root_label = shape_label("http://example.org/RootShape");
+    schema.add_shape(root_label.clone(), ShapeExpression::ShapeOr(or_shapes));
+
+    let graph = Graph::new();
+    let node = Term::NamedNode(nn("http://example.org/test"));
+
+    let timeout = Duration::from_nanos(1);
+    let validator =
+        ShexValidator::with_limits(schema, ValidationLimits::default().with_timeout(timeout));
+    let result = validator.validate(&graph, &node, &root_label);
+    assert!(
+        matches!(result, Err(ShexError::Timeout(limit)) if limit == timeout),
+        "Expected a timeout error, got: {result:?}"
+    );
+}
 
-    // Create a graph with many triples
+// ============================================================================
+// Test 8: Triple Budget
+// ============================================================================
+
+#[test]
+fn test_shex_triple_budget_enforced() {
+    // Create a node with many triples
     let mut graph = Graph::new();
     for i in 0..1000 {
-        let subj = format!("http://example.org/s{}", i);
-        let obj = format!("http://example.org/o{}", i);
         graph.insert(&Triple::new(
-            nn(&subj),
+            nn("http://example.org/s0"),
             nn("http://example.org/p"),
-            nn(&obj),
+            nn(&format!("http://example.org/o{i}")),
         ));
     }
 
     let mut schema = ShapesSchema::new();
-    let shape = Shape::new();
     let shape_label = shape_label("http://example.org/Shape");
+    let mut shape = Shape::new();
+    shape.add_triple_constraint(
+        TripleConstraint::new(nn("http://example.org/p"))
+            .with_cardinality(Cardinality::zero_or_more()),
+    );
     schema.add_shape(shape_label.clone(), ShapeExpression::Shape(shape));
-
-    let validator = ShexValidator::new(schema);
     let node = Term::NamedNode(nn("http://example.org/s0"));
 
-    // NOTE: There's no tracking of how many triples are examined
-    // ValidationLimits.max_triples_examined exists but is not integrated
-
+    // The triples of the node exceed the budget
+    let validator = ShexValidator::with_limits(
+        schema,
+        ValidationLimits::default().with_max_triples_examined(100),
+    );
     let result = validator.validate(&graph, &node, &shape_label);
+    assert!(
+        matches!(result, Err(ShexError::TripleBudgetExceeded { limit: 100 })),
+        "Expected a triple budget error, got: {result:?}"
+    );
 
-    println!("WARNING: Triple examination limits (ValidationLimits.max_triples_examined) exist but are NOT enforced");
-    println!("No memory bounds are tracked during validation");
+    // Validators without limits allow it
+    let validator = ShexValidator::new(validator.schema().clone());
+    assert!(validator.validate(&graph, &node, &shape_label).is_ok());
+}
 
-    assert!(result.is_ok());
+#[test]
+fn test_shex_triple_budget_shared_across_shapes() {
+    // A chain of 10 nodes with 20 triples each
+    let mut graph = Graph::new();
+    for i in 0..10 {
+        let node = nn(&format!("http://example.org/node{i}"));
+        graph.insert(&Triple::new(
+            node.clone(),
+            nn("http://example.org/next"),
+            nn(&format!("http://example.org/node{}", i + 1)),
+        ));
+        for j in 0..19 {
+            graph.insert(&Triple::new(
+                node.clone(),
+                nn("http://example.org/p"),
+                Literal::new_simple_literal(format!("value{j}")),
+            ));
+        }
+    }
+
+    // Each shape evaluation examines the 20 triples of its node once, the whole chain 200
+    let label = shape_label("http://example.org/ChainShape");
+    let mut shape = Shape::new();
+    shape.add_triple_constraint(
+        TripleConstraint::with_value_expr(
+            nn("http://example.org/next"),
+            ShapeExpression::ShapeRef(label.clone()),
+        )
+        .with_cardinality(Cardinality::zero_or_more()),
+    );
+    shape.add_triple_constraint(
+        TripleConstraint::new(nn("http://example.org/p"))
+            .with_cardinality(Cardinality::zero_or_more()),
+    );
+    let mut schema = ShapesSchema::new();
+    schema.add_shape(label.clone(), ShapeExpression::Shape(shape));
+    let node = Term::NamedNode(nn("http://example.org/node0"));
+
+    let validator = ShexValidator::with_limits(
+        schema,
+        ValidationLimits::default().with_max_triples_examined(199),
+    );
+    let result = validator.validate(&graph, &node, &label);
+    assert!(
+        matches!(result, Err(ShexError::TripleBudgetExceeded { limit: 199 })),
+        "Expected a triple budget error, got: {result:?}"
+    );
+
+    let validator = ShexValidator::with_limits(
+        validator.schema().clone(),
+        ValidationLimits::default().with_max_triples_examined(200),
+    );
+    assert!(validator.validate(&graph, &node, &label).is_ok());
 }
 
 // ============================================================================
-// Test 9: Shape Reference Count (NOT TRACKED)
+// Test 9: Shape Reference Count
 // ============================================================================
 
 #[test]
-fn test_shex_shape_reference_count_not_tracked() {
-    // ValidationLimits.max_shape_references exists but is NOT used
-
-    // Create a schema with many shape references
-    let mut schema = ShapesSchema::new();
-
+fn test_shex_shape_reference_limit() {
     // Create a root shape that references many other shapes via OR
+    let mut schema = ShapesSchema::new();
     let mut or_shapes = Vec::new();
     for i in 0..100 {
-        let ref_label = shape_label(&format!("http://example.org/RefShape{}", i));
+        let ref_label = shape_label(&format!("http://example.org/RefShape{i}"));
         or_shapes.push(ShapeExpression::ShapeRef(ref_label.clone()));
-
-        // Define each referenced shape
-        let shape = Shape::new();
-        schema.add_shape(ref_label, ShapeExpression::Shape(shape));
+        schema.add_shape(
+            ref_label,
+            ShapeExpression::NodeConstraint(NodeConstraint::with_node_kind(NodeKind::Literal)),
+        );
     }
-
     let root_label = shape_label("http://example.org/RootShape");
     schema.add_shape(root_label.clone(), ShapeExpression::ShapeOr(or_shapes));
 
     let graph = Graph::new();
-    let validator = ShexValidator::new(schema);
     let node = Term::NamedNode(nn("http://example.org/test"));
 
-    // This will evaluate all 100 shape references
+    // All 100 references are evaluated since none of them matches
+    let validator = ShexValidator::with_limits(
+        schema,
+        ValidationLimits::default().with_max_shape_references(50),
+    );
     let result = validator.validate(&graph, &node, &root_label);
+    assert!(
+        matches!(result, Err(ShexError::ShapeReferenceLimit { limit: 50 })),
+        "Expected a shape reference error, got: {result:?}"
+    );
 
-    println!("WARNING: Shape reference counting (ValidationLimits.max_shape_references) exists but is NOT tracked");
-    println!("No limit on number of shape evaluations per validation");
+    let validator = ShexValidator::with_limits(
+        validator.schema().clone(),
+        ValidationLimits::default().with_max_shape_references(100),
+    );
+    assert!(validator.validate(&graph, &node, &root_label).is_ok());
 
-    assert!(result.is_ok());
+    // Validators are unlimited unless limits are given
+    let validator = ShexValidator::new(validator.schema().clone());
+    assert!(validator.limits().is_none());
+    assert!(!validator.validate(&graph, &node, &root_label).unwrap().is_valid());
+}
+
+// ============================================================================
+// Test 10: Value Set Length
+// ============================================================================
+
+#[test]
+fn test_shex_value_set_length_limit() {
+    let mut nc = NodeConstraint::new();
+    for i in 0..100 {
+        nc.values
+            .push(ValueSetValue::ObjectValue(Term::NamedNode(nn(&format!(
+                "http://example.org/value{i}"
+            )))));
+    }
+
+    let mut schema = ShapesSchema::new();
+    let shape_label = shape_label("http://example.org/ValueSetShape");
+    schema.add_shape(shape_label.clone(), ShapeExpression::NodeConstraint(nc));
+
+    let graph = Graph::new();
+    let node = Term::NamedNode(nn("http://example.org/value0"));
+
+    let validator =
+        ShexValidator::with_limits(schema, ValidationLimits::default().with_max_list_length(10));
+    let result = validator.validate(&graph, &node, &shape_label);
+    assert!(
+        matches!(
+            result,
+            Err(ShexError::ValueSetTooLong {
+                length: 100,
+                limit: 10
+            })
+        ),
+        "Expected a value set length error, got: {result:?}"
+    );
 }
 
 // ============================================================================
-// Test 10: Validator Instantiation
+// Test 11: Validator Instantiation
 // ============================================================================
 
 #[test]
@@ -475,27 +641,15 @@ fn test_summary_report() {
     println!();
     println!("✓ WORKING:");
     println!("  - Basic ShEx validation (validator.rs)");
-    println!("  - Recursion depth limit (hardcoded MAX_RECURSION_DEPTH = 100)");
     println!("  - Cycle detection (visited set in ValidationContext)");
     println!("  - ShapeOr/ShapeAnd evaluation");
-    println!();
-    println!("✗ NOT WORKING / NOT INTEGRATED:");
-    println!("  - ValidationLimits configuration (exists but not used)");
-    println!("  - Timeout enforcement (no way to configure)");
-    println!("  - Memory bounds tracking (max_triples_examined not enforced)");
-    println!("  - Shape reference counting (max_shape_references not tracked)");
-    println!("  - Regex length limits (max_regex_length not enforced)");
-    println!("  - List length limits (max_list_length not enforced)");
-    println!();
-    println!("CRITICAL GAPS:");
-    println!("  1. limits.rs::ValidationContext exists but validator.rs has its own");
-    println!("  2. ShexValidator::new() doesn't accept ValidationLimits parameter");
-    println!("  3. No timeout configuration available");
-    println!("  4. No resource consumption tracking");
-    println!();
-    println!("RECOMMENDATION:");
-    println!("  - Do NOT deploy for untrusted input without integrating ValidationLimits");
-    println!("  - Estimated fix time: 2-4 weeks to integrate limits into validator");
+    println!("  - ValidationLimits configuration (ShexValidator::with_limits)");
+    println!("  - Recursion depth limit (max_recursion_depth)");
+    println!("  - Timeout enforcement (timeout)");
+    println!("  - Triple budget shared across shapes (max_triples_examined)");
+    println!("  - Shape reference counting (max_shape_references)");
+    println!("  - Regex length limits (max_regex_length)");
+    println!("  - Value set length limits (max_list_length)");
     println!("================================================================================");
     println!();
 }
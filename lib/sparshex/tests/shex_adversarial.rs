@@ -109,7 +109,7 @@ fn shex_recursion_bounded() {
 #[test]
 fn shex_max_recursion_depth_enforced() {
     // Create an extremely deep shape reference chain to test recursion limits.
-    // The validator has MAX_RECURSION_DEPTH = 100, so we create 110+ levels.
+    // The default max_recursion_depth is 100, so we create 110+ levels.
 
     let mut schema = ShapesSchema::new();
 
@@ -0,0 +1,343 @@
+//! ShExC parser tests.
+//!
+//! Each schema is parsed with `parse_shex` and compared with the same schema built by hand
+//! with the model API.
+
+use oxrdf::vocab::{rdf, xsd};
+use oxrdf::{BlankNode, Literal, NamedNode};
+use sparshex::{
+    Cardinality, NodeConstraint, NodeKind, NumericFacet, NumericLiteral, Shape, ShapeExpression,
+    ShapeLabel, ShapesSchema, ShexParseError, StringFacet, TripleConstraint, ValueSetValue,
+    parse_shex,
+};
+
+fn nn(iri: &str) -> NamedNode {
+    NamedNode::new_unchecked(iri)
+}
+
+fn ex(local: &str) -> NamedNode {
+    nn(&format!("http://example.org/{local}"))
+}
+
+fn label(local: &str) -> ShapeLabel {
+    ShapeLabel::Iri(ex(local))
+}
+
+fn shape(constraints: Vec<TripleConstraint>) -> ShapeExpression {
+    let mut shape = Shape::new();
+    for constraint in constraints {
+        shape.add_triple_constraint(constraint);
+    }
+    ShapeExpression::Shape(shape)
+}
+
+fn datatype(datatype: impl Into<NamedNode>) -> ShapeExpression {
+    ShapeExpression::NodeConstraint(NodeConstraint::with_datatype(datatype.into()))
+}
+
+fn node_kind(node_kind: NodeKind) -> ShapeExpression {
+    ShapeExpression::NodeConstraint(NodeConstraint::with_node_kind(node_kind))
+}
+
+/// Asserts that both schemas have the same shapes, in the same declaration order.
+fn assert_same_schema(parsed: &ShapesSchema, expected: &ShapesSchema) {
+    let parsed_labels = parsed.labels().collect::<Vec<_>>();
+    let expected_labels = expected.labels().collect::<Vec<_>>();
+    assert_eq!(parsed_labels, expected_labels);
+    for label in expected_labels {
+        assert_eq!(
+            parsed.get_shape(label),
+            expected.get_shape(label),
+            "Shape {label:?} differs"
+        );
+    }
+    assert_eq!(parsed.start(), expected.start());
+}
+
+#[test]
+fn test_parse_empty_schema() {
+    let schema = parse_shex("").unwrap();
+    assert_eq!(schema.labels().count(), 0);
+}
+
+#[test]
+fn test_parse_triple_constraints_with_cardinalities() {
+    let parsed = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+
+        # A person with every kind of cardinality
+        ex:PersonShape {
+            ex:name xsd:string ;
+            ex:nickname xsd:string ? ;
+            ex:email xsd:string * ;
+            ex:phone xsd:string + ;
+            ex:parent @ex:PersonShape {0,2} ;
+            ex:child @ex:PersonShape {1,*} ;
+            ex:eyes . {2} ;
+            ex:award . {3,} ;
+        }
+        "#,
+    )
+    .unwrap();
+
+    let mut expected = ShapesSchema::new();
+    expected.add_shape(
+        label("PersonShape"),
+        shape(vec![
+            TripleConstraint::with_value_expr(ex("name"), datatype(xsd::STRING)),
+            TripleConstraint::with_value_expr(ex("nickname"), datatype(xsd::STRING))
+                .with_cardinality(Cardinality::optional()),
+            TripleConstraint::with_value_expr(ex("email"), datatype(xsd::STRING))
+                .with_cardinality(Cardinality::zero_or_more()),
+            TripleConstraint::with_value_expr(ex("phone"), datatype(xsd::STRING))
+                .with_cardinality(Cardinality::one_or_more()),
+            TripleConstraint::with_value_expr(
+                ex("parent"),
+                ShapeExpression::ShapeRef(label("PersonShape")),
+            )
+            .with_cardinality(Cardinality::new(0, Some(2)).unwrap()),
+            TripleConstraint::with_value_expr(
+                ex("child"),
+                ShapeExpression::ShapeRef(label("PersonShape")),
+            )
+            .with_cardinality(Cardinality::one_or_more()),
+            TripleConstraint::new(ex("eyes")).with_cardinality(Cardinality::exactly(2)),
+            TripleConstraint::new(ex("award")).with_cardinality(Cardinality::new(3, None).unwrap()),
+        ]),
+    );
+    assert_same_schema(&parsed, &expected);
+}
+
+#[test]
+fn test_parse_node_kinds_and_datatypes() {
+    let parsed = parse_shex(
+        r#"
+        BASE <http://example.org/>
+        PREFIX ex: <http://example.org/>
+
+        ex:ResourceShape CLOSED {
+            a [ex:Resource] ;
+            ex:homepage IRI ;
+            ex:label Literal ;
+            ex:node bnode ;
+            ex:size <http://www.w3.org/2001/XMLSchema#integer> MININCLUSIVE 0 ;
+            ex:code LITERAL MINLENGTH 2 MAXLENGTH 8 PATTERN "^[A-Z]+$" ;
+            ^ex:contains NONLITERAL ;
+            <related> @<ResourceShape> *
+        }
+        "#,
+    )
+    .unwrap();
+
+    let mut size = NodeConstraint::with_datatype(xsd::INTEGER.into());
+    size.numeric_facets
+        .push(NumericFacet::MinInclusive(NumericLiteral::new(
+            Literal::from(0),
+        )));
+    let mut code = NodeConstraint::with_node_kind(NodeKind::Literal);
+    code.string_facets = vec![
+        StringFacet::MinLength(2),
+        StringFacet::MaxLength(8),
+        StringFacet::Pattern {
+            pattern: "^[A-Z]+$".to_owned(),
+            flags: None,
+        },
+    ];
+    let mut resource_type = NodeConstraint::new();
+    resource_type.add_value(ValueSetValue::ObjectValue(ex("Resource").into()));
+    let ShapeExpression::Shape(mut resource) = shape(vec![
+        TripleConstraint::with_value_expr(
+            rdf::TYPE.into(),
+            ShapeExpression::NodeConstraint(resource_type),
+        ),
+        TripleConstraint::with_value_expr(ex("homepage"), node_kind(NodeKind::Iri)),
+        TripleConstraint::with_value_expr(ex("label"), node_kind(NodeKind::Literal)),
+        TripleConstraint::with_value_expr(ex("node"), node_kind(NodeKind::BNode)),
+        TripleConstraint::with_value_expr(ex("size"), ShapeExpression::NodeConstraint(size)),
+        TripleConstraint::with_value_expr(ex("code"), ShapeExpression::NodeConstraint(code)),
+        TripleConstraint::with_value_expr(ex("contains"), node_kind(NodeKind::NonLiteral))
+            .with_inverse(true),
+        TripleConstraint::with_value_expr(
+            ex("related"),
+            ShapeExpression::ShapeRef(label("ResourceShape")),
+        )
+        .with_cardinality(Cardinality::zero_or_more()),
+    ]) else {
+        unreachable!()
+    };
+    resource.closed = true;
+
+    let mut expected = ShapesSchema::new();
+    expected.add_shape(label("ResourceShape"), ShapeExpression::Shape(resource));
+    assert_same_schema(&parsed, &expected);
+}
+
+#[test]
+fn test_parse_blank_node_labels_digits_and_language_stems() {
+    let parsed = parse_shex(
+        "
+        PREFIX ex: <http://example.org/>
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+
+        _:Price {
+            ex:amount xsd:decimal TOTALDIGITS 5 FRACTIONDIGITS 2 ;
+            ex:label [@en~]
+        }
+        ex:ProductShape { ex:price @_:Price }
+        ",
+    )
+    .unwrap();
+
+    let price_label = ShapeLabel::BNode(BlankNode::new("Price").unwrap());
+    let mut amount = NodeConstraint::with_datatype(xsd::DECIMAL.into());
+    amount.numeric_facets = vec![
+        NumericFacet::TotalDigits(5),
+        NumericFacet::FractionDigits(2),
+    ];
+    let mut language = NodeConstraint::new();
+    language.add_value(ValueSetValue::LanguageStem("en".to_owned()));
+    let mut expected = ShapesSchema::new();
+    expected.add_shape(
+        price_label.clone(),
+        shape(vec![
+            TripleConstraint::with_value_expr(
+                ex("amount"),
+                ShapeExpression::NodeConstraint(amount),
+            ),
+            TripleConstraint::with_value_expr(
+                ex("label"),
+                ShapeExpression::NodeConstraint(language),
+            ),
+        ]),
+    );
+    expected.add_shape(
+        label("ProductShape"),
+        shape(vec![TripleConstraint::with_value_expr(
+            ex("price"),
+            ShapeExpression::ShapeRef(price_label),
+        )]),
+    );
+    assert_same_schema(&parsed, &expected);
+}
+
+#[test]
+fn test_parse_and_or() {
+    let parsed = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+
+        start = @ex:AgentShape
+
+        ex:AgentShape @ex:PersonShape OR @ex:OrganizationShape
+        ex:PersonShape IRI AND { ex:name xsd:string }
+        ex:OrganizationShape { ex:legalName xsd:string ; ex:member @ex:PersonShape + }
+        ex:IdShape xsd:integer OR xsd:string AND NOT @ex:PersonShape
+        "#,
+    )
+    .unwrap();
+
+    let mut expected = ShapesSchema::new();
+    expected.add_shape(
+        label("AgentShape"),
+        ShapeExpression::ShapeOr(vec![
+            ShapeExpression::ShapeRef(label("PersonShape")),
+            ShapeExpression::ShapeRef(label("OrganizationShape")),
+        ]),
+    );
+    expected.add_shape(
+        label("PersonShape"),
+        ShapeExpression::ShapeAnd(vec![
+            node_kind(NodeKind::Iri),
+            shape(vec![TripleConstraint::with_value_expr(
+                ex("name"),
+                datatype(xsd::STRING),
+            )]),
+        ]),
+    );
+    expected.add_shape(
+        label("OrganizationShape"),
+        shape(vec![
+            TripleConstraint::with_value_expr(ex("legalName"), datatype(xsd::STRING)),
+            TripleConstraint::with_value_expr(
+                ex("member"),
+                ShapeExpression::ShapeRef(label("PersonShape")),
+            )
+            .with_cardinality(Cardinality::one_or_more()),
+        ]),
+    );
+    expected.add_shape(
+        label("IdShape"),
+        ShapeExpression::ShapeOr(vec![
+            datatype(xsd::INTEGER),
+            ShapeExpression::ShapeAnd(vec![
+                datatype(xsd::STRING),
+                ShapeExpression::ShapeNot(Box::new(ShapeExpression::ShapeRef(label(
+                    "PersonShape",
+                )))),
+            ]),
+        ]),
+    );
+    expected.set_start(label("AgentShape"));
+    assert_same_schema(&parsed, &expected);
+}
+
+#[test]
+fn test_parse_extends() {
+    let parsed = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+
+        ex:AgentShape { ex:name xsd:string }
+        ex:PersonShape EXTENDS @ex:AgentShape CLOSED EXTRA ex:nick { ex:birthDate xsd:date ? }
+        "#,
+    )
+    .unwrap();
+
+    let mut expected = ShapesSchema::new();
+    expected.add_shape(
+        label("AgentShape"),
+        shape(vec![TripleConstraint::with_value_expr(
+            ex("name"),
+            datatype(xsd::STRING),
+        )]),
+    );
+    let ShapeExpression::Shape(mut person) = shape(vec![
+        TripleConstraint::with_value_expr(ex("birthDate"), datatype(xsd::DATE))
+            .with_cardinality(Cardinality::optional()),
+    ]) else {
+        unreachable!()
+    };
+    person.add_extends(label("AgentShape"));
+    person.closed = true;
+    person.extra = vec![ex("nick")];
+    expected.add_shape(label("PersonShape"), ShapeExpression::Shape(person));
+    assert_same_schema(&parsed, &expected);
+}
+
+#[test]
+fn test_parse_errors() {
+    // Undefined prefix
+    assert!(matches!(
+        parse_shex("ex:PersonShape { ex:name . }"),
+        Err(ShexParseError::Syntax { .. })
+    ));
+    // Unbalanced braces
+    assert!(matches!(
+        parse_shex("PREFIX ex: <http://example.org/> ex:BadShape { { { }"),
+        Err(ShexParseError::Syntax { .. })
+    ));
+    // Maximum lower than minimum
+    assert!(matches!(
+        parse_shex("PREFIX ex: <http://example.org/> ex:S { ex:p . {3,1} }"),
+        Err(ShexParseError::InvalidCardinality { .. })
+    ));
+    // OneOf is not supported by the model
+    assert!(matches!(
+        parse_shex("PREFIX ex: <http://example.org/> ex:S { ex:p . | ex:q . }"),
+        Err(ShexParseError::InvalidTripleConstraint { .. })
+    ));
+}
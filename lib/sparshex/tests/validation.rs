@@ -0,0 +1,303 @@
+//! ShEx validation tests.
+//!
+//! Each schema is parsed with `parse_shex` and a few nodes of a Turtle graph are validated against it.
+
+use oxrdf::{Graph, NamedNode, Term, Triple};
+use oxrdfio::{RdfFormat, RdfParser};
+use sparshex::{
+    Cardinality, ConstraintType, ShapeId, ShapeLabel, ShapeMap, ShexError, ShexValidationError,
+    ShexValidator, ValidationLimits, parse_shex,
+};
+
+fn parse_turtle(turtle: &str) -> Graph {
+    RdfParser::from_format(RdfFormat::Turtle)
+        .for_reader(turtle.as_bytes())
+        .map(|quad| Triple::from(quad.unwrap()))
+        .collect()
+}
+
+fn ex(local: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("http://example.org/{local}"))
+}
+
+fn label(local: &str) -> ShapeLabel {
+    ShapeLabel::Iri(ex(local))
+}
+
+fn conforms(validator: &ShexValidator, graph: &Graph, node: &str, shape: &str) -> bool {
+    validator
+        .validate(graph, &Term::from(ex(node)), &label(shape))
+        .unwrap()
+        .is_valid()
+}
+
+#[test]
+fn test_extends() {
+    let schema = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+
+        ex:AgentShape { ex:name xsd:string }
+        ex:PersonShape EXTENDS @ex:AgentShape { ex:birthDate xsd:date }
+        ex:EmployeeShape EXTENDS @ex:PersonShape CLOSED { ex:employer IRI }
+        "#,
+    )
+    .unwrap();
+    let validator = ShexValidator::new(schema);
+
+    let graph = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        ex:alice ex:name "Alice" ; ex:birthDate "1990-01-01"^^xsd:date .
+        ex:bob ex:birthDate "1985-05-05"^^xsd:date .
+        ex:carol ex:name "Carol" ; ex:birthDate "1970-03-03"^^xsd:date ; ex:employer ex:acme .
+        ex:dave ex:name "Dave" ; ex:birthDate "1975-04-04"^^xsd:date ; ex:employer ex:acme ;
+            ex:nick "D" .
+        "#,
+    );
+
+    assert!(conforms(&validator, &graph, "alice", "PersonShape"));
+    // The inherited ex:name triple is missing
+    assert!(!conforms(&validator, &graph, "bob", "PersonShape"));
+    assert!(!conforms(&validator, &graph, "bob", "AgentShape"));
+
+    // Transitively inherited predicates are allowed by the closed shape
+    assert!(conforms(&validator, &graph, "carol", "EmployeeShape"));
+    assert!(!conforms(&validator, &graph, "dave", "EmployeeShape"));
+    assert!(!conforms(&validator, &graph, "alice", "EmployeeShape"));
+}
+
+#[test]
+fn test_extends_partitions_triples() {
+    let schema = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+
+        ex:CodedShape { ex:code xsd:integer }
+        ex:DoubleCodedShape EXTENDS @ex:CodedShape { ex:code xsd:string }
+        "#,
+    )
+    .unwrap();
+    let validator = ShexValidator::new(schema);
+
+    let graph = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+
+        ex:a ex:code 1, "one" .
+        ex:b ex:code 1 .
+        ex:c ex:code 1, 2 .
+        ex:d ex:code 1, "one", "un" .
+        "#,
+    );
+
+    assert!(conforms(&validator, &graph, "a", "DoubleCodedShape"));
+    // Each triple matches a single triple constraint
+    assert!(!conforms(&validator, &graph, "b", "DoubleCodedShape"));
+    assert!(!conforms(&validator, &graph, "c", "DoubleCodedShape"));
+    assert!(!conforms(&validator, &graph, "d", "DoubleCodedShape"));
+
+    let result = validator
+        .validate(&graph, &Term::from(ex("b")), &label("DoubleCodedShape"))
+        .unwrap();
+    assert!(matches!(
+        result.violations(),
+        [violation] if violation.actual_count == Some(0)
+            && violation.expected_cardinality == Some(Cardinality::default())
+    ));
+}
+
+#[test]
+fn test_validate_all() {
+    let schema = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+
+        start = @ex:PersonShape
+        ex:PersonShape { ex:name xsd:string ; ex:knows @ex:PersonShape * }
+        "#,
+    )
+    .unwrap();
+    let validator = ShexValidator::new(schema);
+
+    let graph = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+
+        ex:alice ex:name "Alice" ; ex:knows ex:bob .
+        ex:bob ex:name "Bob" .
+        ex:carol ex:knows ex:alice .
+        "#,
+    );
+
+    let report = validator.validate_all(&graph).unwrap();
+    assert!(!report.conforms());
+    assert_eq!(report.len(), 3);
+    assert!(
+        report
+            .entries()
+            .iter()
+            .all(|entry| entry.shape == label("PersonShape"))
+    );
+    let failures = report
+        .failures()
+        .map(|entry| entry.node.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(failures, [Term::from(ex("carol"))]);
+}
+
+#[test]
+fn test_validate_all_without_start_shape() {
+    let schema =
+        parse_shex("PREFIX ex: <http://example.org/> ex:PersonShape { ex:name . }").unwrap();
+    let validator = ShexValidator::new(schema);
+    assert!(matches!(
+        validator.validate_all(&Graph::new()),
+        Err(ShexError::Validation(
+            ShexValidationError::MissingStartShape
+        ))
+    ));
+}
+
+#[test]
+fn test_validate_shape_map() {
+    let schema = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+
+        ex:PersonShape { ex:name . }
+        ex:OrganizationShape { ex:member @ex:PersonShape + }
+        "#,
+    )
+    .unwrap();
+    let validator = ShexValidator::new(schema);
+
+    let graph = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+
+        ex:acme ex:member ex:alice , ex:bob .
+        ex:alice ex:name "Alice" .
+        "#,
+    );
+
+    let shape_map = [
+        (ex("alice"), label("PersonShape")),
+        (ex("acme"), label("OrganizationShape")),
+    ]
+    .into_iter()
+    .collect::<ShapeMap>();
+    let report = validator.validate_shape_map(&graph, &shape_map).unwrap();
+    assert_eq!(report.len(), 2);
+    assert!(report.entries()[0].result.is_valid());
+    // ex:bob has no ex:name
+    assert!(!report.entries()[1].result.is_valid());
+    assert_eq!(report.entries()[1].node, Term::from(ex("acme")));
+    assert!(!report.conforms());
+}
+
+#[test]
+fn test_validate_shape_map_limits_each_node() {
+    let schema = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+
+        start = @ex:PersonShape
+        ex:PersonShape { ex:name . ; ex:age . }
+        "#,
+    )
+    .unwrap();
+    // Each node has 2 triples
+    let validator = ShexValidator::with_limits(
+        schema,
+        ValidationLimits::default().with_max_triples_examined(2),
+    );
+
+    let graph = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+
+        ex:alice ex:name "Alice" ; ex:age 30 .
+        ex:bob ex:name "Bob" ; ex:age 40 .
+        ex:carol ex:name "Carol" ; ex:age 50 .
+        "#,
+    );
+
+    let report = validator.validate_all(&graph).unwrap();
+    assert_eq!(report.len(), 3);
+    assert!(report.conforms());
+}
+
+#[test]
+fn test_failure_reasons() {
+    let schema = parse_shex(
+        r#"
+        PREFIX ex: <http://example.org/>
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+
+        ex:PersonShape {
+            ex:name xsd:string ;
+            ex:email . + ;
+            ex:contact @ex:ContactShape
+        }
+        ex:ContactShape @ex:PhoneShape OR @ex:EmailShape
+        ex:PhoneShape { ex:phone xsd:string }
+        ex:EmailShape { ex:email IRI }
+        "#,
+    )
+    .unwrap();
+    let validator = ShexValidator::new(schema);
+
+    let graph = parse_turtle(
+        r#"
+        @prefix ex: <http://example.org/> .
+
+        ex:alice ex:name 42 ; ex:contact ex:desk .
+        ex:desk ex:email "desk@example.org" .
+        "#,
+    );
+
+    let result = validator
+        .validate(&graph, &Term::from(ex("alice")), &label("PersonShape"))
+        .unwrap();
+    assert!(!result.is_valid());
+    assert_eq!(result.errors().len(), result.violations().len());
+    let [name, email, contact] = result.violations() else {
+        panic!("Unexpected violations: {:?}", result.violations());
+    };
+
+    // The value of ex:name is not a string
+    assert_eq!(name.constraint_type, ConstraintType::ValueExpression);
+    assert_eq!(name.predicate, Some(ex("name")));
+    assert_eq!(name.details[0].constraint_type, ConstraintType::Datatype);
+
+    // There is no ex:email
+    assert_eq!(email.focus_node, Term::from(ex("alice")));
+    assert_eq!(email.shape_id, ShapeId::Named(ex("PersonShape")));
+    assert_eq!(email.constraint_type, ConstraintType::MinCardinality);
+    assert_eq!(email.predicate, Some(ex("email")));
+    assert!(!email.inverse);
+    assert_eq!(email.expected_cardinality, Some(Cardinality::one_or_more()));
+    assert_eq!(email.actual_count, Some(0));
+
+    // ex:desk conforms to neither branch
+    assert_eq!(contact.value, Some(Term::from(ex("desk"))));
+    let [or] = contact.details.as_slice() else {
+        panic!("Unexpected violations: {:?}", contact.details);
+    };
+    assert_eq!(or.constraint_type, ConstraintType::ShapeOr);
+    assert_eq!(or.shape_id, ShapeId::Named(ex("ContactShape")));
+    assert_eq!(or.branches.len(), 2);
+    assert_eq!(or.branches[0][0].actual_count, Some(0));
+    assert_eq!(or.branches[0][0].shape_id, ShapeId::Named(ex("PhoneShape")));
+    assert_eq!(
+        or.branches[1][0].constraint_type,
+        ConstraintType::ValueExpression
+    );
+    assert_eq!(or.branches[1][0].predicate, Some(ex("email")));
+}
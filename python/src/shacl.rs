@@ -302,7 +302,7 @@ impl PyShaclValidationResult {
     /// The human-readable message (if any).
     #[getter]
     pub fn message(&self) -> Option<String> {
-        self.inner.result_message.clone()
+        self.inner.message().map(ToOwned::to_owned)
     }
 
     /// The severity level as a string.
@@ -8,7 +8,7 @@ use oxigraph::io::{RdfParser, RdfSerializer};
 use oxigraph::model::GraphNameRef;
 use oxigraph::sparql::QueryResults;
 use oxigraph::store::{self, LoaderError, SerializerError, StorageError, Store};
-use pyo3::exceptions::{PyRuntimeError, PySyntaxError, PyValueError};
+use pyo3::exceptions::{PyRuntimeError, PySyntaxError, PyTimeoutError, PyValueError};
 use pyo3::prelude::*;
 use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
@@ -23,7 +23,7 @@ use std::path::PathBuf;
 /// of a read operation (e.g. a SPARQL query) or a read/write operation (e.g. a SPARQL update).
 ///
 /// The :py:class:`Store` constructor opens a read-write instance.
-/// To open a static read-only instance use :py:func:`Store.read_only`.
+/// To open a read-only instance use :py:func:`Store.read_only`.
 ///
 /// :param path: the path of the directory in which the store should read and write its data. If the directory does not exist, it is created.
 ///              If no directory is provided a temporary one is created and removed when the Python garbage collector removes the store.
@@ -89,7 +89,8 @@ impl PyStore {
 
     /// Opens a read-only store from disk.
     ///
-    /// Opening as read-only while having an other process writing the database is undefined behavior.
+    /// Another process might write the database at the same time,
+    /// call :py:meth:`Store.catch_up_with_primary` to see its writes done after the opening.
     ///
     /// :param path: path to the primary read-write instance data.
     /// :type path: str
@@ -178,7 +179,7 @@ impl PyStore {
         loader.load_ok_quads::<PyErr, PythonOrStorageError>(
             quads.try_iter()?.map(|q| Ok(q?.extract::<PyQuad>()?)),
         )?;
-        loader.commit().map_err(map_storage_error)?;
+        loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
         Ok(())
     }
 
@@ -553,7 +554,7 @@ impl PyStore {
                 loader
                     .parallel_load_from_file(parser, &path)
                     .map_err(|e| map_loader_error(e, Some(path)))?;
-                loader.commit().map_err(map_storage_error)?;
+                loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
                 Ok(())
             }),
             #[cfg(not(target_family = "wasm"))]
@@ -562,7 +563,7 @@ impl PyStore {
                 loader
                     .parallel_load_from_slice(parser, &input)
                     .map_err(|e| map_loader_error(e, None))?;
-                loader.commit().map_err(map_storage_error)?;
+                loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
                 Ok(())
             }),
             #[cfg(not(target_family = "wasm"))]
@@ -571,7 +572,7 @@ impl PyStore {
                 loader
                     .parallel_load_from_slice(parser, &input)
                     .map_err(|e| map_loader_error(e, None))?;
-                loader.commit().map_err(map_storage_error)?;
+                loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
                 Ok(())
             }),
             (path, input) => {
@@ -581,7 +582,7 @@ impl PyStore {
                     loader
                         .load_from_reader(parser, input)
                         .map_err(|e| map_loader_error(e, path))?;
-                    loader.commit().map_err(map_storage_error)?;
+                    loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
                     Ok(())
                 })
             }
@@ -821,6 +822,21 @@ impl PyStore {
         py.detach(|| self.inner.flush().map_err(map_storage_error))
     }
 
+    /// Makes visible the writes done by the read-write instance since the opening of this read-only store or the previous call.
+    ///
+    /// It is a no-op on read-write and in-memory stores.
+    ///
+    /// :rtype: None
+    /// :raises OSError: if an error happens while reading the new writes.
+    #[cfg(not(target_family = "wasm"))]
+    fn catch_up_with_primary(&self, py: Python<'_>) -> PyResult<()> {
+        py.detach(|| {
+            self.inner
+                .catch_up_with_primary()
+                .map_err(map_storage_error)
+        })
+    }
+
     /// Optimizes the database for future workload.
     ///
     /// Useful to call after a batch upload or another similar operation.
@@ -1366,7 +1382,7 @@ impl PyBulkLoader {
             loader.load_ok_quads::<PyErr, PythonOrStorageError>(
                 quads.into_iter().map(Ok),
             )?;
-            loader.commit().map_err(map_storage_error)?;
+            loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
             Ok(())
         })
     }
@@ -1450,7 +1466,7 @@ impl PyBulkLoader {
                 loader
                     .parallel_load_from_file(parser, &path)
                     .map_err(|e| map_loader_error(e, Some(path)))?;
-                loader.commit().map_err(map_storage_error)?;
+                loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
                 Ok(())
             }),
             (None, Some(PyReadableInput::Bytes(input))) => py.detach(|| {
@@ -1473,7 +1489,7 @@ impl PyBulkLoader {
                 loader
                     .parallel_load_from_slice(parser, &input)
                     .map_err(|e| map_loader_error(e, None))?;
-                loader.commit().map_err(map_storage_error)?;
+                loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
                 Ok(())
             }),
             (None, Some(PyReadableInput::String(input))) => py.detach(|| {
@@ -1496,7 +1512,7 @@ impl PyBulkLoader {
                 loader
                     .parallel_load_from_slice(parser, &input)
                     .map_err(|e| map_loader_error(e, None))?;
-                loader.commit().map_err(map_storage_error)?;
+                loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
                 Ok(())
             }),
             (path, input) => {
@@ -1521,7 +1537,7 @@ impl PyBulkLoader {
                     loader
                         .load_from_reader(parser, input)
                         .map_err(|e| map_loader_error(e, path))?;
-                    loader.commit().map_err(map_storage_error)?;
+                    loader.commit().map_err(|e| map_loader_error(e.into(), None))?;
                     Ok(())
                 })
             }
@@ -1541,6 +1557,8 @@ pub fn map_loader_error(error: LoaderError, file_path: Option<PathBuf>) -> PyErr
         LoaderError::Storage(error) => map_storage_error(error),
         LoaderError::Parsing(error) => map_parse_error(error, file_path),
         LoaderError::InvalidBaseIri { .. } => PyValueError::new_err(error.to_string()),
+        LoaderError::Timeout(_) => PyTimeoutError::new_err(error.to_string()),
+        LoaderError::DatasetFormatExpected(_) => PyValueError::new_err(error.to_string()),
     }
 }
 